@@ -1,5 +1,7 @@
 //! Bootstrap 설정 관리
 
+use crate::dht::DhtTuning;
+use crate::turn::{TurnAuthMethod, TurnConfig};
 use serde::{Deserialize, Serialize};
 
 /// 내장 부트스트랩 노드 설정
@@ -19,6 +21,14 @@ pub struct BootstrapConfig {
     pub turn_username: Option<String>,
     pub turn_password: Option<String>,
     pub turn_secret: Option<String>,
+    #[serde(default)]
+    pub dht: DhtTuning,
+    /// Stats API 토큰 (없으면 시작 시 자동 생성해 저장)
+    #[serde(default)]
+    pub stats_api_token: Option<String>,
+    /// Stats API를 127.0.0.1에만 바인딩
+    #[serde(default)]
+    pub stats_localhost_only: bool,
 }
 
 impl Default for BootstrapConfig {
@@ -38,6 +48,9 @@ impl Default for BootstrapConfig {
             turn_username: None,
             turn_password: None,
             turn_secret: None,
+            dht: DhtTuning::default(),
+            stats_api_token: None,
+            stats_localhost_only: false,
         }
     }
 }
@@ -56,6 +69,43 @@ impl BootstrapConfig {
             return Err("max_relay_sessions must be <= 1000".to_string());
         }
 
+        self.dht.validate()?;
+
+        if let Some(token) = &self.stats_api_token {
+            if token.len() < 16 {
+                return Err("stats_api_token must be at least 16 characters".to_string());
+            }
+        }
+
         Ok(())
     }
+
+    /// TURN 클라이언트 설정 (`enable_turn`이 꺼져 있으면 None)
+    pub fn turn_config(&self) -> Option<TurnConfig> {
+        if !self.enable_turn {
+            return None;
+        }
+
+        Some(TurnConfig {
+            server_url: self
+                .turn_server_url
+                .clone()
+                .unwrap_or_else(|| "turn.ponslink.online:3478".to_string()),
+            realm: self
+                .turn_realm
+                .clone()
+                .unwrap_or_else(|| "ponslink.online".to_string()),
+            enable_tls: true,
+            auth_method: if self.turn_secret.is_some() {
+                TurnAuthMethod::LongTerm
+            } else {
+                TurnAuthMethod::ShortTerm
+            },
+            username: self.turn_username.clone(),
+            password: self.turn_password.clone(),
+            secret: self.turn_secret.clone(),
+            timeout_sec: 30,
+            refresh_ratio: 0.8,
+        })
+    }
 }
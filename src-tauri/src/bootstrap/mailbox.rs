@@ -0,0 +1,424 @@
+//! 저장 후 전달(Store-and-Forward) 시그널링 우편함
+//!
+//! NAT 뒤에 있고 동시에 온라인이 아닌 피어끼리도 Offer/Answer 등 시그널링 메시지를 주고받을 수 있도록,
+//! 내장 부트스트랩의 QUIC 릴레이 포트에서 수신자 노드 ID별로 서명된 시그널을 TTL 동안 보관합니다.
+//! 수신자는 다시 접속했을 때 자기 키로 서명한 조회 요청으로 쌓인 시그널을 가져갑니다.
+//!
+//! 노드 ID는 Ed25519 공개 키(hex)이므로 등록 절차 없이 서명만으로 발신자와 수신자를 확인합니다.
+//! 부트스트랩은 저장 시, 수신자는 받은 뒤 다시 서명을 검증하므로 부트스트랩이 시그널을 위조할 수 없습니다.
+//!
+//! 요청 형식은 `rpc` 참고 (마커 `PSMB`)
+
+use super::rpc;
+use crate::protocol::Command;
+use crate::util::now_secs;
+use anyhow::{anyhow, Result};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::net::SocketAddr;
+use std::path::Path;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+/// 우편함 스트림 식별자 (릴레이 세션 ID 대신 첫 바이트로 전송)
+pub const MAILBOX_MAGIC: &[u8; 4] = b"PSMB";
+
+/// 서명 키 파일 이름 (앱 데이터 폴더, 32바이트 시드)
+pub const SIGNING_KEY_FILE: &str = "signaling_identity.key";
+
+/// TTL을 지정하지 않았을 때의 보관 시간
+pub const DEFAULT_SIGNAL_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// 최대 보관 시간
+pub const MAX_SIGNAL_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// 수신자 하나당 보관하는 최대 시그널 수
+const MAX_SIGNALS_PER_RECIPIENT: usize = 32;
+
+/// 우편함을 가진 최대 수신자 수
+const MAX_RECIPIENTS: usize = 10_000;
+
+/// 서명된 시각과 서버 시각의 허용 오차 (조회 요청 재사용 방지)
+pub(super) const MAX_CLOCK_SKEW_SECS: u64 = 120;
+
+const SIGNAL_DOMAIN: &[u8] = b"ponswarp signal v1";
+const POLL_DOMAIN: &[u8] = b"ponswarp mailbox poll v1";
+
+fn is_signal_command(command: &Command) -> bool {
+    // 🆕 ICE 재시작 때는 직접 경로가 끊긴 상태라 후보도 우편함으로 다시 교환
+    matches!(
+        command,
+        Command::Offer { .. }
+            | Command::Answer { .. }
+            | Command::IceCandidate { .. }
+            | Command::Renegotiate { .. }
+            | Command::Bye { .. }
+    )
+}
+
+fn parse_verifying_key(node_id: &str) -> Result<VerifyingKey> {
+    let bytes: [u8; 32] = hex::decode(node_id)?
+        .try_into()
+        .map_err(|_| anyhow!("잘못된 노드 ID 길이"))?;
+    Ok(VerifyingKey::from_bytes(&bytes)?)
+}
+
+/// 노드 ID의 서명 검증 (서명은 hex)
+pub fn verify(node_id: &str, message: &[u8], signature: &str) -> Result<()> {
+    let key = parse_verifying_key(node_id)?;
+    let signature: [u8; 64] = hex::decode(signature)?
+        .try_into()
+        .map_err(|_| anyhow!("잘못된 서명 길이"))?;
+    key.verify(message, &Signature::from_bytes(&signature))
+        .map_err(|_| anyhow!("서명 검증 실패"))
+}
+
+/// 시그널링 서명 키 (노드 ID = 공개 키 hex)
+pub struct SigningIdentity {
+    key: SigningKey,
+}
+
+impl SigningIdentity {
+    /// 새 키 생성
+    pub fn generate() -> Self {
+        let mut seed = [0u8; 32];
+        rand::RngCore::fill_bytes(&mut rand::rngs::OsRng, &mut seed);
+        Self {
+            key: SigningKey::from_bytes(&seed),
+        }
+    }
+
+    /// 폴더에 저장된 키 로드 (없거나 손상되었으면 새로 생성해 저장)
+    pub fn load_or_generate(dir: &Path) -> Result<Self> {
+        let path = dir.join(SIGNING_KEY_FILE);
+        if let Ok(bytes) = std::fs::read(&path) {
+            match <[u8; 32]>::try_from(bytes.as_slice()) {
+                Ok(seed) => {
+                    return Ok(Self {
+                        key: SigningKey::from_bytes(&seed),
+                    })
+                }
+                Err(_) => warn!("저장된 시그널링 키가 손상되어 새로 생성합니다"),
+            }
+        }
+
+        let identity = Self::generate();
+        std::fs::create_dir_all(dir)?;
+        std::fs::write(&path, identity.key.to_bytes())?;
+        info!("🔑 시그널링 키 생성: {}", identity.node_id());
+        Ok(identity)
+    }
+
+    /// 이 기기의 노드 ID
+    pub fn node_id(&self) -> String {
+        hex::encode(self.key.verifying_key().as_bytes())
+    }
+
+    /// 임의 메시지 서명 (QR 연결 정보 등, 도메인 구분은 호출자 책임)
+    pub fn sign(&self, message: &[u8]) -> String {
+        hex::encode(self.key.sign(message).to_bytes())
+    }
+
+    /// 수신자에게 맡길 시그널 서명 (시그널링 메시지만 가능)
+    pub fn sign_signal(&self, to: &str, command: Command, ttl: Duration) -> Result<SignedSignal> {
+        if !is_signal_command(&command) {
+            return Err(anyhow!("시그널링 메시지만 우편함에 맡길 수 있습니다"));
+        }
+        parse_verifying_key(to)?;
+
+        let mut signal = SignedSignal {
+            from: self.node_id(),
+            to: to.to_string(),
+            command,
+            created_at: now_secs(),
+            ttl_secs: ttl.min(MAX_SIGNAL_TTL).as_secs(),
+            signature: String::new(),
+        };
+        let signature = self.key.sign(&signal.signing_bytes()?);
+        signal.signature = hex::encode(signature.to_bytes());
+        Ok(signal)
+    }
+
+    /// 우편함 조회 요청 서명
+    pub fn sign_poll(&self) -> PollRequest {
+        let node_id = self.node_id();
+        let timestamp = now_secs();
+        let signature = self
+            .key
+            .sign(&PollRequest::signing_bytes(&node_id, timestamp));
+        PollRequest {
+            node_id,
+            timestamp,
+            signature: hex::encode(signature.to_bytes()),
+        }
+    }
+}
+
+/// 발신자가 서명한 시그널 (수신자 노드 ID 앞으로 보관)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedSignal {
+    pub from: String,
+    pub to: String,
+    pub command: Command,
+    /// 서명 시각 (Unix 초)
+    pub created_at: u64,
+    pub ttl_secs: u64,
+    /// Ed25519 서명 (hex)
+    pub signature: String,
+}
+
+impl SignedSignal {
+    fn signing_bytes(&self) -> Result<Vec<u8>> {
+        let mut bytes = SIGNAL_DOMAIN.to_vec();
+        for part in [self.from.as_bytes(), self.to.as_bytes()] {
+            bytes.extend_from_slice(&(part.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(part);
+        }
+        bytes.extend_from_slice(&self.created_at.to_le_bytes());
+        bytes.extend_from_slice(&self.ttl_secs.to_le_bytes());
+        bytes.extend_from_slice(&serde_json::to_vec(&self.command)?);
+        Ok(bytes)
+    }
+
+    /// 발신자 서명 검증
+    pub fn verify(&self) -> Result<()> {
+        if !is_signal_command(&self.command) {
+            return Err(anyhow!("시그널링 메시지가 아닌 시그널"));
+        }
+        verify(&self.from, &self.signing_bytes()?, &self.signature)
+    }
+
+    /// 만료 시각 (Unix 초)
+    pub fn expires_at(&self) -> u64 {
+        self.created_at.saturating_add(self.ttl_secs)
+    }
+}
+
+/// 수신자의 우편함 조회 요청
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PollRequest {
+    pub node_id: String,
+    /// 서명 시각 (Unix 초)
+    pub timestamp: u64,
+    pub signature: String,
+}
+
+impl PollRequest {
+    fn signing_bytes(node_id: &str, timestamp: u64) -> Vec<u8> {
+        let mut bytes = POLL_DOMAIN.to_vec();
+        bytes.extend_from_slice(node_id.as_bytes());
+        bytes.extend_from_slice(&timestamp.to_le_bytes());
+        bytes
+    }
+
+    /// 노드 ID 소유 확인 (시각이 허용 오차를 벗어나면 거부)
+    pub fn verify(&self, now: u64) -> Result<()> {
+        if now.abs_diff(self.timestamp) > MAX_CLOCK_SKEW_SECS {
+            return Err(anyhow!("조회 요청 시각이 허용 범위를 벗어났습니다"));
+        }
+        verify(
+            &self.node_id,
+            &Self::signing_bytes(&self.node_id, self.timestamp),
+            &self.signature,
+        )
+    }
+}
+
+/// 우편함 요청
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum MailboxRequest {
+    Deposit { signal: SignedSignal },
+    Poll(PollRequest),
+}
+
+/// 우편함 응답
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum MailboxResponse {
+    Stored,
+    Signals { signals: Vec<SignedSignal> },
+    Error { message: String },
+}
+
+/// 부트스트랩 측 수신자별 시그널 보관함
+#[derive(Default)]
+pub struct SignalMailbox {
+    queues: Mutex<HashMap<String, VecDeque<SignedSignal>>>,
+}
+
+impl SignalMailbox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 시그널 보관 (서명/TTL 검증)
+    pub fn deposit(&self, signal: SignedSignal, now: u64) -> Result<()> {
+        signal.verify()?;
+        parse_verifying_key(&signal.to)?;
+        if signal.ttl_secs > MAX_SIGNAL_TTL.as_secs() {
+            return Err(anyhow!("TTL이 너무 깁니다: {}초", signal.ttl_secs));
+        }
+        if signal.created_at > now + MAX_CLOCK_SKEW_SECS || signal.expires_at() <= now {
+            return Err(anyhow!("만료되었거나 시각이 잘못된 시그널"));
+        }
+
+        let mut queues = self.queues.lock();
+        if !queues.contains_key(&signal.to) && queues.len() >= MAX_RECIPIENTS {
+            return Err(anyhow!("우편함 수 한도 초과"));
+        }
+        let queue = queues.entry(signal.to.clone()).or_default();
+        queue.retain(|queued| queued.expires_at() > now);
+        if queue.len() >= MAX_SIGNALS_PER_RECIPIENT {
+            return Err(anyhow!("수신자의 우편함이 가득 찼습니다"));
+        }
+        debug!("📮 시그널 보관: {} -> {}", signal.from, signal.to);
+        queue.push_back(signal);
+        Ok(())
+    }
+
+    /// 조회 요청자의 시그널을 꺼내 전달 (꺼낸 시그널은 삭제)
+    pub fn take(&self, poll: &PollRequest, now: u64) -> Result<Vec<SignedSignal>> {
+        poll.verify(now)?;
+        let signals = self.queues.lock().remove(&poll.node_id).unwrap_or_default();
+        Ok(signals
+            .into_iter()
+            .filter(|signal| signal.expires_at() > now)
+            .collect())
+    }
+
+    /// 만료된 시그널 정리 (정리한 개수 반환)
+    pub fn purge_expired(&self, now: u64) -> usize {
+        let mut purged = 0;
+        self.queues.lock().retain(|_, queue| {
+            let before = queue.len();
+            queue.retain(|signal| signal.expires_at() > now);
+            purged += before - queue.len();
+            !queue.is_empty()
+        });
+        purged
+    }
+
+    fn handle(&self, request: MailboxRequest) -> MailboxResponse {
+        let now = now_secs();
+        let result = match request {
+            MailboxRequest::Deposit { signal } => {
+                self.deposit(signal, now).map(|_| MailboxResponse::Stored)
+            }
+            MailboxRequest::Poll(poll) => self
+                .take(&poll, now)
+                .map(|signals| MailboxResponse::Signals { signals }),
+        };
+        result.unwrap_or_else(|e| MailboxResponse::Error {
+            message: e.to_string(),
+        })
+    }
+
+    /// 릴레이 스트림으로 들어온 우편함 요청 처리 (`first_chunk`는 마커를 포함한 첫 읽기)
+    pub async fn serve(
+        &self,
+        first_chunk: &[u8],
+        mut send: quinn::SendStream,
+        mut recv: quinn::RecvStream,
+    ) -> Result<()> {
+        let response = match rpc::read_request::<MailboxRequest>(first_chunk, &mut recv).await {
+            Ok(request) => self.handle(request),
+            Err(e) => MailboxResponse::Error {
+                message: format!("잘못된 우편함 요청: {}", e),
+            },
+        };
+        rpc::respond(&mut send, &response).await
+    }
+}
+
+/// 수신자 앞으로 시그널 맡기기
+pub async fn deposit(bootstrap: SocketAddr, signal: SignedSignal) -> Result<()> {
+    let request = MailboxRequest::Deposit { signal };
+    match rpc::call(bootstrap, MAILBOX_MAGIC, &request).await? {
+        MailboxResponse::Stored => Ok(()),
+        MailboxResponse::Error { message } => Err(anyhow!(message)),
+        other => Err(anyhow!("예상하지 못한 응답: {:?}", other)),
+    }
+}
+
+/// 내 우편함에 쌓인 시그널 가져오기 (서명이 맞고 나에게 온 것만 반환)
+pub async fn poll(bootstrap: SocketAddr, identity: &SigningIdentity) -> Result<Vec<SignedSignal>> {
+    let node_id = identity.node_id();
+    let request = MailboxRequest::Poll(identity.sign_poll());
+    match rpc::call(bootstrap, MAILBOX_MAGIC, &request).await? {
+        MailboxResponse::Signals { signals } => Ok(signals
+            .into_iter()
+            .filter(|signal| match signal.verify() {
+                Ok(()) => signal.to == node_id,
+                Err(e) => {
+                    warn!("우편함 시그널 검증 실패 ({}): {}", signal.from, e);
+                    false
+                }
+            })
+            .collect()),
+        MailboxResponse::Error { message } => Err(anyhow!(message)),
+        other => Err(anyhow!("예상하지 못한 응답: {:?}", other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn offer() -> Command {
+        Command::Offer {
+            room_id: "room-1".to_string(),
+            sdp: "v=0".to_string(),
+            target: None,
+            version: crate::protocol::SIGNALING_VERSION,
+        }
+    }
+
+    #[test]
+    fn test_deposit_and_take() {
+        let alice = SigningIdentity::generate();
+        let bob = SigningIdentity::generate();
+        let mailbox = SignalMailbox::new();
+        let now = now_secs();
+
+        let signal = alice
+            .sign_signal(&bob.node_id(), offer(), DEFAULT_SIGNAL_TTL)
+            .unwrap();
+        mailbox.deposit(signal.clone(), now).unwrap();
+
+        // 내용을 바꾸면 서명이 맞지 않음
+        let mut tampered = signal.clone();
+        tampered.to = alice.node_id();
+        assert!(mailbox.deposit(tampered, now).is_err());
+
+        // 시그널링 메시지 외의 명령은 서명 단계에서 거부
+        assert!(alice
+            .sign_signal(&bob.node_id(), Command::Ping, DEFAULT_SIGNAL_TTL)
+            .is_err());
+        let candidate = Command::IceCandidate {
+            room_id: "room-1".to_string(),
+            candidate: "192.0.2.1:4000".to_string(),
+        };
+        assert!(alice
+            .sign_signal(&bob.node_id(), candidate, DEFAULT_SIGNAL_TTL)
+            .is_ok());
+
+        // 다른 사람의 조회 요청으로는 가져갈 수 없음
+        let mut forged = alice.sign_poll();
+        forged.node_id = bob.node_id();
+        assert!(mailbox.take(&forged, now).is_err());
+
+        let signals = mailbox.take(&bob.sign_poll(), now).unwrap();
+        assert_eq!(signals.len(), 1);
+        assert_eq!(signals[0].from, alice.node_id());
+        assert!(mailbox.take(&bob.sign_poll(), now).unwrap().is_empty());
+
+        // 만료된 시그널은 정리
+        mailbox.deposit(signal.clone(), now).unwrap();
+        assert_eq!(mailbox.purge_expired(signal.expires_at()), 1);
+    }
+}
@@ -0,0 +1,348 @@
+//! 접속 명부 (WAN 피어 발견)
+//!
+//! mDNS가 닿지 않는 인터넷 너머의 피어를 찾을 수 있도록, 피어는 노드 ID와 연결 후보를 부트스트랩에
+//! 주기적으로 등록하고, 다른 피어는 알고 있는 연락처의 노드 ID로 누가 접속 중인지(와 그 후보)를 조회합니다.
+//!
+//! 등록은 노드 ID(Ed25519 공개 키)로 서명하므로 다른 사람 행세를 할 수 없고, 갱신이 끊기면
+//! `PRESENCE_TTL` 뒤 오프라인으로 봅니다. 후보가 빈 등록은 명시적인 오프라인 알림입니다.
+//! 부트스트랩은 랑데부와 같이 요청이 들어온 공인 주소로 반사 후보를 덧붙입니다. 요청 형식은 `rpc` 참고 (마커 `PSPR`)
+
+use super::mailbox::{self, SigningIdentity, MAX_CLOCK_SKEW_SECS};
+use super::rendezvous::with_reflexive;
+use super::rpc;
+use crate::util::now_secs;
+use anyhow::{anyhow, Result};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+use tracing::{debug, info};
+
+/// 접속 명부 스트림 식별자
+pub const PRESENCE_MAGIC: &[u8; 4] = b"PSPR";
+
+/// 갱신이 없으면 오프라인으로 보는 시간
+pub const PRESENCE_TTL: Duration = Duration::from_secs(90);
+
+/// 앱의 등록 갱신 주기 (TTL 안에 두 번 이상 갱신)
+pub const PRESENCE_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// 명부에 올릴 수 있는 최대 노드 수
+const MAX_ENTRIES: usize = 10_000;
+
+/// 한 번에 조회할 수 있는 최대 노드 수
+const MAX_LOOKUP: usize = 256;
+
+const PRESENCE_DOMAIN: &[u8] = b"ponswarp presence v1";
+
+/// 노드 ID로 서명한 접속 등록
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresenceRegistration {
+    pub node_id: String,
+    /// 연결 후보 ("ip:port", 비어 있으면 오프라인 알림)
+    pub candidates: Vec<String>,
+    /// 서명 시각 (Unix 초)
+    pub timestamp: u64,
+    pub signature: String,
+}
+
+impl PresenceRegistration {
+    /// 이 기기의 후보로 등록 요청 서명
+    pub fn sign(identity: &SigningIdentity, candidates: Vec<String>) -> Self {
+        let mut registration = Self {
+            node_id: identity.node_id(),
+            candidates,
+            timestamp: now_secs(),
+            signature: String::new(),
+        };
+        registration.signature = identity.sign(&registration.signing_bytes());
+        registration
+    }
+
+    fn signing_bytes(&self) -> Vec<u8> {
+        let mut bytes = PRESENCE_DOMAIN.to_vec();
+        bytes.extend_from_slice(self.node_id.as_bytes());
+        bytes.extend_from_slice(&self.timestamp.to_le_bytes());
+        for candidate in &self.candidates {
+            bytes.extend_from_slice(candidate.as_bytes());
+            bytes.push(b'\n');
+        }
+        bytes
+    }
+
+    /// 서명과 시각 확인
+    pub fn verify(&self, now: u64) -> Result<()> {
+        if now.abs_diff(self.timestamp) > MAX_CLOCK_SKEW_SECS {
+            return Err(anyhow!("등록 시각이 허용 범위를 벗어났습니다"));
+        }
+        mailbox::verify(&self.node_id, &self.signing_bytes(), &self.signature)
+    }
+}
+
+/// 접속 중인 노드 (조회 결과)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresenceEntry {
+    pub node_id: String,
+    pub candidates: Vec<SocketAddr>,
+    /// 마지막 등록 후 지난 시간 (초)
+    pub last_seen_secs: u64,
+}
+
+/// 접속 명부 요청
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum PresenceRequest {
+    Register(PresenceRegistration),
+    Lookup { node_ids: Vec<String> },
+}
+
+/// 접속 명부 응답
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum PresenceResponse {
+    Registered { ttl_secs: u64 },
+    Online { peers: Vec<PresenceEntry> },
+    Error { message: String },
+}
+
+struct Presence {
+    /// 비어 있으면 오프라인 알림 (만료 전까지 오래된 등록의 재전송을 막기 위해 남겨 둠)
+    candidates: Vec<SocketAddr>,
+    timestamp: u64,
+    seen_at: Instant,
+}
+
+/// 부트스트랩 측 접속 명부
+#[derive(Default)]
+pub struct PresenceService {
+    entries: Mutex<HashMap<String, Presence>>,
+}
+
+impl PresenceService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 접속 등록/갱신 (`observed`는 요청한 연결의 원격 주소)
+    pub fn register(
+        &self,
+        registration: PresenceRegistration,
+        observed: SocketAddr,
+        now_secs: u64,
+        now: Instant,
+    ) -> Result<()> {
+        registration.verify(now_secs)?;
+
+        let mut entries = self.entries.lock();
+        match entries.get(&registration.node_id) {
+            Some(existing) if existing.timestamp > registration.timestamp => {
+                return Err(anyhow!("이전 등록보다 오래된 요청입니다"));
+            }
+            None if entries.len() >= MAX_ENTRIES => {
+                entries.retain(|_, presence| is_fresh(presence, now));
+                if entries.len() >= MAX_ENTRIES {
+                    return Err(anyhow!("접속 명부가 가득 찼습니다"));
+                }
+            }
+            _ => {}
+        }
+
+        let candidates = if registration.candidates.is_empty() {
+            Vec::new()
+        } else {
+            with_reflexive(&registration.candidates, observed)
+        };
+        debug!(
+            "📇 접속 등록: {} (후보 {}개, {})",
+            registration.node_id,
+            candidates.len(),
+            observed
+        );
+        entries.insert(
+            registration.node_id,
+            Presence {
+                candidates,
+                timestamp: registration.timestamp,
+                seen_at: now,
+            },
+        );
+        Ok(())
+    }
+
+    /// 요청한 노드 중 접속 중인 노드와 후보
+    pub fn lookup(&self, node_ids: &[String], now: Instant) -> Vec<PresenceEntry> {
+        let entries = self.entries.lock();
+        node_ids
+            .iter()
+            .take(MAX_LOOKUP)
+            .filter_map(|node_id| {
+                let presence = entries.get(node_id)?;
+                (is_fresh(presence, now) && !presence.candidates.is_empty()).then(|| {
+                    PresenceEntry {
+                        node_id: node_id.clone(),
+                        candidates: presence.candidates.clone(),
+                        last_seen_secs: now.saturating_duration_since(presence.seen_at).as_secs(),
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// 만료된 등록 정리 (정리한 개수 반환)
+    pub fn purge_expired(&self, now: Instant) -> usize {
+        let mut entries = self.entries.lock();
+        let before = entries.len();
+        entries.retain(|_, presence| is_fresh(presence, now));
+        before - entries.len()
+    }
+
+    fn handle(&self, request: PresenceRequest, observed: SocketAddr) -> PresenceResponse {
+        let result = match request {
+            PresenceRequest::Register(registration) => self
+                .register(registration, observed, now_secs(), Instant::now())
+                .map(|_| PresenceResponse::Registered {
+                    ttl_secs: PRESENCE_TTL.as_secs(),
+                }),
+            PresenceRequest::Lookup { node_ids } => Ok(PresenceResponse::Online {
+                peers: self.lookup(&node_ids, Instant::now()),
+            }),
+        };
+        result.unwrap_or_else(|e| PresenceResponse::Error {
+            message: e.to_string(),
+        })
+    }
+
+    /// 릴레이 스트림으로 들어온 접속 명부 요청 처리 (`observed`는 요청한 연결의 원격 주소)
+    pub async fn serve(
+        &self,
+        first_chunk: &[u8],
+        mut send: quinn::SendStream,
+        mut recv: quinn::RecvStream,
+        observed: SocketAddr,
+    ) -> Result<()> {
+        let response = match rpc::read_request::<PresenceRequest>(first_chunk, &mut recv).await {
+            Ok(request) => self.handle(request, observed),
+            Err(e) => PresenceResponse::Error {
+                message: format!("잘못된 접속 명부 요청: {}", e),
+            },
+        };
+        rpc::respond(&mut send, &response).await
+    }
+}
+
+fn is_fresh(presence: &Presence, now: Instant) -> bool {
+    now.saturating_duration_since(presence.seen_at) < PRESENCE_TTL
+}
+
+/// 부트스트랩에 접속 등록 (후보가 비어 있으면 오프라인 알림)
+pub async fn register(
+    bootstrap: SocketAddr,
+    identity: &SigningIdentity,
+    candidates: Vec<String>,
+) -> Result<()> {
+    let request = PresenceRequest::Register(PresenceRegistration::sign(identity, candidates));
+    match rpc::call(bootstrap, PRESENCE_MAGIC, &request).await? {
+        PresenceResponse::Registered { .. } => Ok(()),
+        PresenceResponse::Error { message } => Err(anyhow!(message)),
+        other => Err(anyhow!("예상하지 못한 응답: {:?}", other)),
+    }
+}
+
+/// 노드 ID 목록 중 접속 중인 노드 조회
+pub async fn lookup(bootstrap: SocketAddr, node_ids: Vec<String>) -> Result<Vec<PresenceEntry>> {
+    let request = PresenceRequest::Lookup { node_ids };
+    match rpc::call(bootstrap, PRESENCE_MAGIC, &request).await? {
+        PresenceResponse::Online { peers } => Ok(peers),
+        PresenceResponse::Error { message } => Err(anyhow!(message)),
+        other => Err(anyhow!("예상하지 못한 응답: {:?}", other)),
+    }
+}
+
+/// 앱 쪽 주기적 등록 작업 (부트스트랩 하나에만 등록)
+#[derive(Default)]
+pub struct PresenceAnnouncer {
+    task: Mutex<Option<(SocketAddr, tauri::async_runtime::JoinHandle<()>)>>,
+}
+
+impl PresenceAnnouncer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 등록 작업 교체 (이전 작업은 중단)
+    pub fn replace(&self, bootstrap: SocketAddr, task: tauri::async_runtime::JoinHandle<()>) {
+        if let Some((_, previous)) = self.task.lock().replace((bootstrap, task)) {
+            previous.abort();
+        }
+        info!("📇 접속 등록 시작: {}", bootstrap);
+    }
+
+    /// 등록 작업 중단 (등록하던 부트스트랩 반환)
+    pub fn stop(&self) -> Option<SocketAddr> {
+        let (bootstrap, task) = self.task.lock().take()?;
+        task.abort();
+        Some(bootstrap)
+    }
+
+    /// 현재 등록 중인 부트스트랩
+    pub fn bootstrap(&self) -> Option<SocketAddr> {
+        self.task.lock().as_ref().map(|(bootstrap, _)| *bootstrap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_lookup_and_offline() {
+        let service = PresenceService::new();
+        let alice = SigningIdentity::generate();
+        let bob = SigningIdentity::generate();
+        let observed: SocketAddr = "203.0.113.5:40000".parse().unwrap();
+        let start = Instant::now();
+        let now = now_secs();
+
+        let registration =
+            PresenceRegistration::sign(&alice, vec!["192.168.0.10:5000".to_string()]);
+        service
+            .register(registration.clone(), observed, now, start)
+            .unwrap();
+
+        // 다른 노드 ID로 바꾸거나 후보를 고치면 서명이 맞지 않음
+        let mut forged = registration.clone();
+        forged.node_id = bob.node_id();
+        assert!(service.register(forged, observed, now, start).is_err());
+        let mut tampered = registration.clone();
+        tampered.candidates = vec!["198.51.100.1:5000".to_string()];
+        assert!(service.register(tampered, observed, now, start).is_err());
+
+        let ids = vec![alice.node_id(), bob.node_id()];
+        let online = service.lookup(&ids, start);
+        assert_eq!(online.len(), 1);
+        assert_eq!(online[0].node_id, alice.node_id());
+        assert_eq!(
+            online[0].candidates,
+            vec![
+                "192.168.0.10:5000".parse().unwrap(),
+                "203.0.113.5:5000".parse().unwrap()
+            ]
+        );
+
+        // 오프라인 알림 뒤에는 이전 등록을 다시 보내도 받아들이지 않음
+        let mut offline = PresenceRegistration::sign(&alice, Vec::new());
+        offline.timestamp = registration.timestamp + 1;
+        offline.signature = alice.sign(&offline.signing_bytes());
+        service.register(offline, observed, now, start).unwrap();
+        assert!(service.lookup(&ids, start).is_empty());
+        assert!(service
+            .register(registration, observed, now, start)
+            .is_err());
+
+        // 갱신이 없으면 만료
+        assert_eq!(service.purge_expired(start + PRESENCE_TTL), 1);
+    }
+}
@@ -1,7 +1,12 @@
 //! QUIC 릴레이 서버 (ponswarp-bootstrap에서 포팅)
 //!
 //! NAT 환경에서 직접 연결이 불가능한 피어들을 위한 릴레이 서비스를 제공합니다.
+//! 같은 포트에서 오프라인 피어용 시그널링 우편함(`mailbox`), 방 코드 랑데부(`rendezvous`),
+//! WAN 피어 발견용 접속 명부(`presence`)도 처리합니다.
 
+use super::mailbox::{SignalMailbox, MAILBOX_MAGIC};
+use super::presence::{PresenceService, PRESENCE_MAGIC};
+use super::rendezvous::{RendezvousService, RENDEZVOUS_MAGIC};
 use super::stats::StatsCollector;
 use dashmap::DashMap;
 use quinn::{Endpoint, ServerConfig};
@@ -13,21 +18,70 @@ use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
 
-/// 릴레이 세션 정보
+/// 스트림 활동이 이 시간 동안 없으면 세션 종료 (조용히 사라진 피어)
+const SESSION_IDLE_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// 활동과 무관한 세션 최대 유지 시간
+const SESSION_MAX_LIFETIME: Duration = Duration::from_secs(2 * 60 * 60);
+
+/// 세션 유휴/수명 점검 주기
+const SESSION_CHECK_INTERVAL: Duration = Duration::from_secs(15);
+
+/// 세션 정리 사유
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Eviction {
+    Idle,
+    Lifetime,
+}
+
+impl Eviction {
+    /// 정리 대상이면 사유 반환 (수명 초과 우선)
+    fn check(created_at: Instant, last_activity: Instant, now: Instant) -> Option<Self> {
+        if now.saturating_duration_since(created_at) >= SESSION_MAX_LIFETIME {
+            Some(Eviction::Lifetime)
+        } else if now.saturating_duration_since(last_activity) >= SESSION_IDLE_TIMEOUT {
+            Some(Eviction::Idle)
+        } else {
+            None
+        }
+    }
+
+    fn reason(self) -> &'static [u8] {
+        match self {
+            Eviction::Idle => b"idle",
+            Eviction::Lifetime => b"lifetime",
+        }
+    }
+}
+
+/// 릴레이 세션 정보 (연결 하나당 하나)
 #[derive(Debug, Clone)]
 struct RelaySession {
+    connection: quinn::Connection,
     peer_a: SocketAddr,
     peer_b: Option<SocketAddr>,
+    /// 첫 스트림에서 받은 릴레이 대상 세션 ID
+    session_id: Option<String>,
     created_at: Instant,
+    last_activity: Instant,
     bytes_relayed: u64,
 }
 
+/// 연결별 세션 (키: `quinn::Connection::stable_id`)
+type Sessions = Arc<DashMap<usize, RelaySession>>;
+
 /// QUIC 릴레이 서버
 pub struct RelayServer {
     endpoint: Endpoint,
-    sessions: DashMap<String, RelaySession>,
+    sessions: Sessions,
     stats: Arc<RwLock<StatsCollector>>,
     max_sessions: usize,
+    /// 오프라인 피어 앞으로 맡겨진 시그널
+    mailbox: Arc<SignalMailbox>,
+    /// 방 코드 랑데부
+    rendezvous: Arc<RendezvousService>,
+    /// 🆕 접속 명부
+    presence: Arc<PresenceService>,
 }
 
 impl RelayServer {
@@ -45,9 +99,12 @@ impl RelayServer {
 
         Ok(Self {
             endpoint,
-            sessions: DashMap::new(),
+            sessions: Arc::new(DashMap::new()),
             stats,
             max_sessions,
+            mailbox: Arc::new(SignalMailbox::new()),
+            rendezvous: Arc::new(RendezvousService::new()),
+            presence: Arc::new(PresenceService::new()),
         })
     }
 
@@ -91,7 +148,7 @@ impl RelayServer {
     }
 
     pub async fn run(self) {
-        let mut cleanup_interval = tokio::time::interval(Duration::from_secs(60));
+        let mut cleanup_interval = tokio::time::interval(SESSION_CHECK_INTERVAL);
 
         loop {
             tokio::select! {
@@ -105,6 +162,9 @@ impl RelayServer {
 
                     let sessions = self.sessions.clone();
                     let stats = self.stats.clone();
+                    let mailbox = self.mailbox.clone();
+                    let rendezvous = self.rendezvous.clone();
+                    let presence = self.presence.clone();
 
                     tauri::async_runtime::spawn(async move {
                         match incoming.await {
@@ -112,12 +172,29 @@ impl RelayServer {
                                 let addr = connection.remote_address();
                                 info!("📥 릴레이 연결: {}", addr);
 
+                                let now = Instant::now();
+                                sessions.insert(
+                                    connection.stable_id(),
+                                    RelaySession {
+                                        connection: connection.clone(),
+                                        peer_a: addr,
+                                        peer_b: None,
+                                        session_id: None,
+                                        created_at: now,
+                                        last_activity: now,
+                                        bytes_relayed: 0,
+                                    },
+                                );
+
                                 let mut stats_guard = stats.write().await;
                                 stats_guard.relay_connections += 1;
                                 stats_guard.active_relay_sessions += 1;
                                 drop(stats_guard);
 
-                                Self::handle_connection(connection, sessions, stats).await;
+                                Self::handle_connection(
+                                    connection, sessions, stats, mailbox, rendezvous, presence,
+                                )
+                                .await;
                             }
                             Err(e) => {
                                 error!("연결 수락 실패: {}", e);
@@ -126,9 +203,10 @@ impl RelayServer {
                     });
                 }
 
-                // 주기적 세션 정리
+                // 유휴/수명 초과 세션 정리
                 _ = cleanup_interval.tick() => {
-                    self.cleanup_stale_sessions().await;
+                    self.evict_sessions().await;
+                    self.purge_services();
                 }
             }
         }
@@ -136,25 +214,53 @@ impl RelayServer {
 
     async fn handle_connection(
         connection: quinn::Connection,
-        sessions: DashMap<String, RelaySession>,
+        sessions: Sessions,
         stats: Arc<RwLock<StatsCollector>>,
+        mailbox: Arc<SignalMailbox>,
+        rendezvous: Arc<RendezvousService>,
+        presence: Arc<PresenceService>,
     ) {
         let addr = connection.remote_address();
+        let id = connection.stable_id();
 
         loop {
             match connection.accept_bi().await {
-                Ok((mut send, mut recv)) => {
+                Ok((send, mut recv)) => {
+                    Self::touch(&sessions, id);
                     let sessions = sessions.clone();
                     let stats = stats.clone();
+                    let mailbox = mailbox.clone();
+                    let rendezvous = rendezvous.clone();
+                    let presence = presence.clone();
 
                     tauri::async_runtime::spawn(async move {
                         let mut buf = vec![0u8; 65536];
 
-                        // 첫 메시지: 릴레이 요청 (대상 세션 ID)
+                        // 첫 메시지: 릴레이 요청 (대상 세션 ID) 또는 우편함 요청
                         match recv.read(&mut buf).await {
+                            Ok(Some(n)) if buf[..n].starts_with(MAILBOX_MAGIC) => {
+                                if let Err(e) = mailbox.serve(&buf[..n], send, recv).await {
+                                    warn!("우편함 요청 처리 실패 ({}): {}", addr, e);
+                                }
+                            }
+                            Ok(Some(n)) if buf[..n].starts_with(RENDEZVOUS_MAGIC) => {
+                                if let Err(e) = rendezvous.serve(&buf[..n], send, recv, addr).await
+                                {
+                                    warn!("랑데부 요청 처리 실패 ({}): {}", addr, e);
+                                }
+                            }
+                            Ok(Some(n)) if buf[..n].starts_with(PRESENCE_MAGIC) => {
+                                if let Err(e) = presence.serve(&buf[..n], send, recv, addr).await {
+                                    warn!("접속 명부 요청 처리 실패 ({}): {}", addr, e);
+                                }
+                            }
                             Ok(Some(n)) => {
                                 let session_id = String::from_utf8_lossy(&buf[..n]).to_string();
                                 debug!("릴레이 요청: {} -> {}", addr, session_id);
+                                if let Some(mut session) = sessions.get_mut(&id) {
+                                    session.session_id = Some(session_id);
+                                    session.last_activity = Instant::now();
+                                }
 
                                 // 세션 처리 로직
                                 // 실제 구현에서는 두 피어를 연결하여 데이터 릴레이
@@ -169,12 +275,10 @@ impl RelayServer {
                 }
                 Err(quinn::ConnectionError::ApplicationClosed(_)) => {
                     info!("📴 릴레이 연결 종료: {}", addr);
-
-                    // 세션 카운트 감소
-                    let mut stats_guard = stats.write().await;
-                    stats_guard.active_relay_sessions =
-                        stats_guard.active_relay_sessions.saturating_sub(1);
-
+                    break;
+                }
+                Err(quinn::ConnectionError::LocallyClosed) => {
+                    // 유휴/수명 초과로 서버가 정리한 세션 (카운트는 정리 시 반영)
                     break;
                 }
                 Err(e) => {
@@ -183,22 +287,99 @@ impl RelayServer {
                 }
             }
         }
+
+        // 타임아웃 등 어떤 이유로 끝나든 세션 슬롯 반환
+        if sessions.remove(&id).is_some() {
+            let mut stats_guard = stats.write().await;
+            stats_guard.active_relay_sessions = stats_guard.active_relay_sessions.saturating_sub(1);
+        }
     }
 
-    async fn cleanup_stale_sessions(&self) {
-        let timeout = Duration::from_secs(300);
-        let before_count = self.sessions.len();
+    /// 세션 활동 기록
+    fn touch(sessions: &Sessions, id: usize) {
+        if let Some(mut session) = sessions.get_mut(&id) {
+            session.last_activity = Instant::now();
+        }
+    }
 
-        self.sessions
-            .retain(|_, session| session.created_at.elapsed() < timeout);
+    /// 만료된 우편함 시그널, 방 코드, 접속 등록 정리
+    fn purge_services(&self) {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let purged = self.mailbox.purge_expired(now);
+        if purged > 0 {
+            debug!("🧹 만료된 우편함 시그널 정리: {}개", purged);
+        }
+        let rooms = self.rendezvous.purge_expired(Instant::now());
+        if rooms > 0 {
+            debug!("🧹 만료된 방 코드 정리: {}개", rooms);
+        }
+        let presences = self.presence.purge_expired(Instant::now());
+        if presences > 0 {
+            debug!("🧹 만료된 접속 등록 정리: {}개", presences);
+        }
+    }
 
-        let removed = before_count - self.sessions.len();
-        if removed > 0 {
-            debug!(
-                "🧹 릴레이 세션 정리: {} 제거, {} 활성",
-                removed,
+    /// 유휴/수명 초과 세션 종료 (연결을 닫아 스트림 버퍼와 세션 슬롯을 회수)
+    async fn evict_sessions(&self) {
+        let now = Instant::now();
+        let mut idle = 0u64;
+        let mut expired = 0u64;
+
+        self.sessions.retain(|_, session| {
+            match Eviction::check(session.created_at, session.last_activity, now) {
+                Some(eviction) => {
+                    debug!(
+                        "🧹 릴레이 세션 정리 ({:?}): {} ({} bytes)",
+                        eviction, session.peer_a, session.bytes_relayed
+                    );
+                    session.connection.close(0u32.into(), eviction.reason());
+                    match eviction {
+                        Eviction::Idle => idle += 1,
+                        Eviction::Lifetime => expired += 1,
+                    }
+                    false
+                }
+                None => true,
+            }
+        });
+
+        if idle + expired > 0 {
+            let mut stats = self.stats.write().await;
+            stats.active_relay_sessions =
+                stats.active_relay_sessions.saturating_sub(idle + expired);
+            stats.relay_sessions_evicted_idle += idle;
+            stats.relay_sessions_evicted_lifetime += expired;
+            drop(stats);
+
+            info!(
+                "🧹 릴레이 세션 정리: 유휴 {}, 수명 초과 {}, {} 활성",
+                idle,
+                expired,
                 self.sessions.len()
             );
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eviction_check() {
+        let start = Instant::now();
+
+        assert_eq!(Eviction::check(start, start, start), None);
+        assert_eq!(
+            Eviction::check(start, start, start + SESSION_IDLE_TIMEOUT),
+            Some(Eviction::Idle)
+        );
+
+        // 계속 활동 중이어도 최대 수명을 넘으면 종료
+        let end = start + SESSION_MAX_LIFETIME;
+        assert_eq!(Eviction::check(start, end, end), Some(Eviction::Lifetime));
+    }
+}
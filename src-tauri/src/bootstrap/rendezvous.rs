@@ -0,0 +1,425 @@
+//! 방 코드 랑데부 (WAN 피어 연결)
+//!
+//! 송신자가 자신의 연결 후보 주소를 맡기고 짧은 방 코드를 받으면, 수신자는 그 코드만 입력해
+//! 후보 목록을 받아 `connect_to_peer_race`로 동시에 연결을 시도합니다. IP:포트를 직접 복사할 필요가 없습니다.
+//!
+//! 부트스트랩은 요청이 들어온 공인 주소(NAT 바깥 IP)를 보고 후보 포트마다 반사 후보를 덧붙입니다.
+//! 방은 한 번만 참여할 수 있고 일정 시간이 지나면 사라지며, 코드 대입을 막기 위해
+//! 같은 IP의 잘못된 참여 시도를 제한합니다. 요청 형식은 `rpc` 참고 (마커 `PSRV`)
+
+use super::rpc;
+use anyhow::{anyhow, Result};
+use parking_lot::Mutex;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+/// 랑데부 스트림 식별자
+pub const RENDEZVOUS_MAGIC: &[u8; 4] = b"PSRV";
+
+/// 방 유지 시간
+pub const ROOM_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// 방 코드 길이와 문자 (헷갈리는 0/O, 1/I 제외)
+pub const CODE_LEN: usize = 6;
+const CODE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+/// 동시에 열 수 있는 최대 방 수
+const MAX_ROOMS: usize = 10_000;
+
+/// 한쪽이 맡길 수 있는 최대 후보 수
+const MAX_CANDIDATES: usize = 16;
+
+/// IP당 허용하는 잘못된 참여 시도 (창 안에서)
+const MAX_FAILED_JOINS: u32 = 10;
+const FAILED_JOIN_WINDOW: Duration = Duration::from_secs(60);
+
+/// 랑데부 요청
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum RendezvousRequest {
+    /// 송신자: 방 만들기
+    Create { candidates: Vec<String> },
+    /// 수신자: 코드로 참여 (송신자 후보 수신)
+    Join {
+        code: String,
+        candidates: Vec<String>,
+    },
+    /// 송신자: 참여 여부 확인 (참여했으면 수신자 후보)
+    Status { code: String, token: String },
+    /// 송신자: 방 닫기
+    Close { code: String, token: String },
+}
+
+/// 랑데부 응답
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum RendezvousResponse {
+    Created {
+        code: String,
+        token: String,
+        expires_in_secs: u64,
+    },
+    Joined {
+        candidates: Vec<SocketAddr>,
+    },
+    Status {
+        joined: bool,
+        candidates: Vec<SocketAddr>,
+    },
+    Closed,
+    Error {
+        message: String,
+    },
+}
+
+/// 만든 방 (송신자 보관용, 토큰은 상태 조회/닫기에 필요)
+#[derive(Debug, Clone)]
+pub struct RoomTicket {
+    pub code: String,
+    pub token: String,
+    pub expires_in_secs: u64,
+}
+
+struct Room {
+    token: String,
+    sender_candidates: Vec<SocketAddr>,
+    receiver_candidates: Option<Vec<SocketAddr>>,
+    created_at: Instant,
+}
+
+/// 입력한 방 코드 정규화 (대소문자, 공백/`-` 무시)
+pub fn normalize_code(code: &str) -> String {
+    code.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+fn generate_code() -> String {
+    let mut rng = rand::thread_rng();
+    (0..CODE_LEN)
+        .map(|_| CODE_ALPHABET[rng.gen_range(0..CODE_ALPHABET.len())] as char)
+        .collect()
+}
+
+/// 후보 파싱 후 관측된 공인 IP로 반사 후보 추가
+pub(super) fn with_reflexive(candidates: &[String], observed: SocketAddr) -> Vec<SocketAddr> {
+    let mut parsed: Vec<SocketAddr> = candidates
+        .iter()
+        .filter_map(|candidate| candidate.parse().ok())
+        .take(MAX_CANDIDATES)
+        .collect();
+
+    let ports: Vec<u16> = parsed.iter().map(|addr| addr.port()).collect();
+    for port in ports {
+        let reflexive = SocketAddr::new(observed.ip(), port);
+        if !parsed.contains(&reflexive) {
+            parsed.push(reflexive);
+        }
+    }
+    parsed
+}
+
+/// 부트스트랩 측 방 코드 랑데부 서비스
+#[derive(Default)]
+pub struct RendezvousService {
+    rooms: Mutex<HashMap<String, Room>>,
+    failed_joins: Mutex<HashMap<IpAddr, (u32, Instant)>>,
+}
+
+impl RendezvousService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 방 만들기 (코드와 토큰 반환)
+    pub fn create(
+        &self,
+        candidates: &[String],
+        observed: SocketAddr,
+        now: Instant,
+    ) -> Result<RoomTicket> {
+        let sender_candidates = with_reflexive(candidates, observed);
+        if sender_candidates.is_empty() {
+            return Err(anyhow!("연결 후보 주소가 없습니다"));
+        }
+
+        let mut rooms = self.rooms.lock();
+        rooms.retain(|_, room| now.saturating_duration_since(room.created_at) < ROOM_TTL);
+        if rooms.len() >= MAX_ROOMS {
+            return Err(anyhow!("열린 방이 너무 많습니다"));
+        }
+        let code = loop {
+            let code = generate_code();
+            if !rooms.contains_key(&code) {
+                break code;
+            }
+        };
+        let token = hex::encode(rand::random::<[u8; 16]>());
+        rooms.insert(
+            code.clone(),
+            Room {
+                token: token.clone(),
+                sender_candidates,
+                receiver_candidates: None,
+                created_at: now,
+            },
+        );
+        debug!("🚪 방 생성: {} ({})", code, observed);
+        Ok(RoomTicket {
+            code,
+            token,
+            expires_in_secs: ROOM_TTL.as_secs(),
+        })
+    }
+
+    /// 코드로 참여 (송신자 후보 반환, 방마다 한 번만)
+    pub fn join(
+        &self,
+        code: &str,
+        candidates: &[String],
+        observed: SocketAddr,
+        now: Instant,
+    ) -> Result<Vec<SocketAddr>> {
+        {
+            let failed = self.failed_joins.lock();
+            if let Some((count, since)) = failed.get(&observed.ip()) {
+                if *count >= MAX_FAILED_JOINS
+                    && now.saturating_duration_since(*since) < FAILED_JOIN_WINDOW
+                {
+                    return Err(anyhow!("잘못된 시도가 많습니다. 잠시 후 다시 시도하세요"));
+                }
+            }
+        }
+
+        let code = normalize_code(code);
+        let mut rooms = self.rooms.lock();
+        let room = rooms
+            .get_mut(&code)
+            .filter(|room| now.saturating_duration_since(room.created_at) < ROOM_TTL)
+            .filter(|room| room.receiver_candidates.is_none());
+        match room {
+            Some(room) => {
+                room.receiver_candidates = Some(with_reflexive(candidates, observed));
+                debug!("🚪 방 참여: {} ({})", code, observed);
+                Ok(room.sender_candidates.clone())
+            }
+            None => {
+                drop(rooms);
+                self.record_failed_join(observed.ip(), now);
+                Err(anyhow!("방을 찾을 수 없거나 이미 참여한 방입니다"))
+            }
+        }
+    }
+
+    fn record_failed_join(&self, ip: IpAddr, now: Instant) {
+        let mut failed = self.failed_joins.lock();
+        let entry = failed.entry(ip).or_insert((0, now));
+        if now.saturating_duration_since(entry.1) >= FAILED_JOIN_WINDOW {
+            *entry = (0, now);
+        }
+        entry.0 += 1;
+    }
+
+    /// 참여 여부 확인 (참여했으면 수신자 후보)
+    pub fn status(&self, code: &str, token: &str, now: Instant) -> Result<Option<Vec<SocketAddr>>> {
+        let rooms = self.rooms.lock();
+        let room = rooms
+            .get(code)
+            .filter(|room| room.token == token)
+            .filter(|room| now.saturating_duration_since(room.created_at) < ROOM_TTL)
+            .ok_or_else(|| anyhow!("방이 없거나 만료되었습니다"))?;
+        Ok(room.receiver_candidates.clone())
+    }
+
+    /// 방 닫기
+    pub fn close(&self, code: &str, token: &str) -> bool {
+        let mut rooms = self.rooms.lock();
+        if rooms.get(code).is_some_and(|room| room.token == token) {
+            rooms.remove(code);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// 만료된 방과 오래된 실패 기록 정리 (정리한 방 수 반환)
+    pub fn purge_expired(&self, now: Instant) -> usize {
+        self.failed_joins
+            .lock()
+            .retain(|_, (_, since)| now.saturating_duration_since(*since) < FAILED_JOIN_WINDOW);
+
+        let mut rooms = self.rooms.lock();
+        let before = rooms.len();
+        rooms.retain(|_, room| now.saturating_duration_since(room.created_at) < ROOM_TTL);
+        before - rooms.len()
+    }
+
+    fn handle(&self, request: RendezvousRequest, observed: SocketAddr) -> RendezvousResponse {
+        let now = Instant::now();
+        let result = match request {
+            RendezvousRequest::Create { candidates } => self
+                .create(&candidates, observed, now)
+                .map(|ticket| RendezvousResponse::Created {
+                    code: ticket.code,
+                    token: ticket.token,
+                    expires_in_secs: ticket.expires_in_secs,
+                }),
+            RendezvousRequest::Join { code, candidates } => self
+                .join(&code, &candidates, observed, now)
+                .map(|candidates| RendezvousResponse::Joined { candidates }),
+            RendezvousRequest::Status { code, token } => {
+                self.status(&code, &token, now)
+                    .map(|candidates| RendezvousResponse::Status {
+                        joined: candidates.is_some(),
+                        candidates: candidates.unwrap_or_default(),
+                    })
+            }
+            RendezvousRequest::Close { code, token } => {
+                self.close(&code, &token);
+                Ok(RendezvousResponse::Closed)
+            }
+        };
+        result.unwrap_or_else(|e| RendezvousResponse::Error {
+            message: e.to_string(),
+        })
+    }
+
+    /// 릴레이 스트림으로 들어온 랑데부 요청 처리 (`observed`는 요청한 연결의 원격 주소)
+    pub async fn serve(
+        &self,
+        first_chunk: &[u8],
+        mut send: quinn::SendStream,
+        mut recv: quinn::RecvStream,
+        observed: SocketAddr,
+    ) -> Result<()> {
+        let response = match rpc::read_request::<RendezvousRequest>(first_chunk, &mut recv).await {
+            Ok(request) => self.handle(request, observed),
+            Err(e) => RendezvousResponse::Error {
+                message: format!("잘못된 랑데부 요청: {}", e),
+            },
+        };
+        rpc::respond(&mut send, &response).await
+    }
+}
+
+/// 송신자: 후보를 맡기고 방 코드 받기
+pub async fn create_room(bootstrap: SocketAddr, candidates: Vec<String>) -> Result<RoomTicket> {
+    let request = RendezvousRequest::Create { candidates };
+    match rpc::call(bootstrap, RENDEZVOUS_MAGIC, &request).await? {
+        RendezvousResponse::Created {
+            code,
+            token,
+            expires_in_secs,
+        } => {
+            info!("🚪 방 코드 발급: {}", code);
+            Ok(RoomTicket {
+                code,
+                token,
+                expires_in_secs,
+            })
+        }
+        RendezvousResponse::Error { message } => Err(anyhow!(message)),
+        other => Err(anyhow!("예상하지 못한 응답: {:?}", other)),
+    }
+}
+
+/// 수신자: 코드로 참여해 송신자 후보 받기
+pub async fn join_room(
+    bootstrap: SocketAddr,
+    code: &str,
+    candidates: Vec<String>,
+) -> Result<Vec<SocketAddr>> {
+    let request = RendezvousRequest::Join {
+        code: normalize_code(code),
+        candidates,
+    };
+    match rpc::call(bootstrap, RENDEZVOUS_MAGIC, &request).await? {
+        RendezvousResponse::Joined { candidates } => Ok(candidates),
+        RendezvousResponse::Error { message } => Err(anyhow!(message)),
+        other => Err(anyhow!("예상하지 못한 응답: {:?}", other)),
+    }
+}
+
+/// 송신자: 참여 여부 확인 (참여했으면 수신자 후보)
+pub async fn room_status(
+    bootstrap: SocketAddr,
+    ticket: &RoomTicket,
+) -> Result<Option<Vec<SocketAddr>>> {
+    let request = RendezvousRequest::Status {
+        code: ticket.code.clone(),
+        token: ticket.token.clone(),
+    };
+    match rpc::call(bootstrap, RENDEZVOUS_MAGIC, &request).await? {
+        RendezvousResponse::Status { joined, candidates } => Ok(joined.then_some(candidates)),
+        RendezvousResponse::Error { message } => Err(anyhow!(message)),
+        other => Err(anyhow!("예상하지 못한 응답: {:?}", other)),
+    }
+}
+
+/// 송신자: 방 닫기 (실패해도 만료되면 사라지므로 경고만)
+pub async fn close_room(bootstrap: SocketAddr, ticket: &RoomTicket) {
+    let request = RendezvousRequest::Close {
+        code: ticket.code.clone(),
+        token: ticket.token.clone(),
+    };
+    if let Err(e) = rpc::call::<_, RendezvousResponse>(bootstrap, RENDEZVOUS_MAGIC, &request).await
+    {
+        warn!("방 닫기 실패 ({}): {}", ticket.code, e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_join_status() {
+        let service = RendezvousService::new();
+        let now = Instant::now();
+        let sender: SocketAddr = "203.0.113.5:40000".parse().unwrap();
+        let receiver: SocketAddr = "198.51.100.7:50000".parse().unwrap();
+
+        let ticket = service
+            .create(&["192.168.0.10:5000".to_string()], sender, now)
+            .unwrap();
+        assert_eq!(ticket.code.len(), CODE_LEN);
+        assert_eq!(
+            service.status(&ticket.code, &ticket.token, now).unwrap(),
+            None
+        );
+        assert!(service.status(&ticket.code, "wrong", now).is_err());
+
+        // 소문자/구분자를 섞어 입력해도 참여 가능, 공인 IP 반사 후보 포함
+        let typed = format!("{}-{}", &ticket.code[..3], &ticket.code[3..]).to_lowercase();
+        let candidates = service.join(&typed, &[], receiver, now).unwrap();
+        assert_eq!(
+            candidates,
+            vec![
+                "192.168.0.10:5000".parse().unwrap(),
+                "203.0.113.5:5000".parse().unwrap()
+            ]
+        );
+        assert_eq!(
+            service.status(&ticket.code, &ticket.token, now).unwrap(),
+            Some(vec![])
+        );
+
+        // 방은 한 번만 참여 가능, 잘못된 시도가 쌓이면 잠시 차단
+        assert!(service.join(&ticket.code, &[], receiver, now).is_err());
+        for _ in 0..MAX_FAILED_JOINS {
+            let _ = service.join("ZZZZZZ", &[], receiver, now);
+        }
+        let other = service
+            .create(&["192.168.0.11:5000".to_string()], sender, now)
+            .unwrap();
+        assert!(service.join(&other.code, &[], receiver, now).is_err());
+
+        // 만료
+        assert_eq!(service.purge_expired(now + ROOM_TTL), 2);
+    }
+}
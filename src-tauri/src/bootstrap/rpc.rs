@@ -0,0 +1,78 @@
+//! 부트스트랩 릴레이 포트의 단발성 요청/응답
+//!
+//! 릴레이 세션과 같은 QUIC 포트를 쓰며, 양방향 스트림의 첫 4바이트 마커로 서비스를 구분합니다.
+//! 형식: `마커` + JSON 요청, 송신측 종료 후 JSON 응답 (우편함 `PSMB`, 방 코드 `PSRV`, 접속 명부 `PSPR`)
+
+use anyhow::{anyhow, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// 요청/응답 최대 크기
+const MAX_REQUEST_LEN: usize = 256 * 1024;
+const MAX_RESPONSE_LEN: usize = 8 * 1024 * 1024;
+
+/// 부트스트랩 연결/응답 타임아웃
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 서버: 첫 읽기(`first_chunk`, 마커 포함) 이후 나머지를 읽어 요청 파싱
+pub async fn read_request<T: DeserializeOwned>(
+    first_chunk: &[u8],
+    recv: &mut quinn::RecvStream,
+) -> Result<T> {
+    let mut request = first_chunk.get(4..).unwrap_or_default().to_vec();
+    let rest = recv
+        .read_to_end(MAX_REQUEST_LEN.saturating_sub(request.len()))
+        .await?;
+    request.extend_from_slice(&rest);
+    Ok(serde_json::from_slice(&request)?)
+}
+
+/// 서버: 응답 전송 후 스트림 종료
+pub async fn respond<T: Serialize>(send: &mut quinn::SendStream, response: &T) -> Result<()> {
+    send.write_all(&serde_json::to_vec(response)?).await?;
+    send.finish()?;
+    Ok(())
+}
+
+/// 클라이언트: 부트스트랩에 연결해 요청 하나를 보내고 응답 수신
+pub async fn call<Req: Serialize, Resp: DeserializeOwned>(
+    bootstrap: SocketAddr,
+    magic: &[u8; 4],
+    request: &Req,
+) -> Result<Resp> {
+    let mut client_crypto = rustls::ClientConfig::builder()
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(crate::quic::client::SkipServerVerification))
+        .with_no_client_auth();
+    client_crypto.alpn_protocols = vec![b"ponswarp-relay".to_vec()];
+    let client_config = quinn::ClientConfig::new(Arc::new(
+        quinn::crypto::rustls::QuicClientConfig::try_from(client_crypto)?,
+    ));
+
+    let bind_addr: SocketAddr = if bootstrap.is_ipv6() {
+        "[::]:0".parse()?
+    } else {
+        "0.0.0.0:0".parse()?
+    };
+    let mut endpoint = quinn::Endpoint::client(bind_addr)?;
+    endpoint.set_default_client_config(client_config);
+
+    let result = tokio::time::timeout(REQUEST_TIMEOUT, async {
+        let conn = endpoint.connect(bootstrap, "ponswarp-relay")?.await?;
+        let (mut send, mut recv) = conn.open_bi().await?;
+        let mut payload = magic.to_vec();
+        payload.extend_from_slice(&serde_json::to_vec(request)?);
+        send.write_all(&payload).await?;
+        send.finish()?;
+        let response = recv.read_to_end(MAX_RESPONSE_LEN).await?;
+        conn.close(0u32.into(), b"done");
+        anyhow::Ok(serde_json::from_slice(&response)?)
+    })
+    .await
+    .map_err(|_| anyhow!("부트스트랩 응답 시간 초과"))?;
+    endpoint.wait_idle().await;
+    result
+}
@@ -1,14 +1,26 @@
-use crate::turn::{ConnectionStats, IceConnectionManager, StunClient, TurnAuthMethod, TurnClient, TurnConfig};
+use crate::turn::{ConnectionStats, IceConnectionManager, StunClient, TurnClient};
 use crate::quic::client_enhanced::QuicClientEnhanced;
 use crate::grid::bootstrap_discovery::{BootstrapDiscovery, BootstrapDiscoveryEvent};
 use crate::bootstrap::{BootstrapConfig, DhtStats, RelayStats, StatsCollector, StatsServer, RelayServer, DhtHandle, PeerDiscoveredEvent, DhtNode};
+use crate::bootstrap::stats::generate_api_token;
+use crate::dht::routing_store::ROUTING_TABLE_FILE;
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tokio::sync::{mpsc, RwLock};
 use tokio::task::JoinHandle;
 use tracing::{error, info, warn};
 
+/// 자동 생성한 Stats API 토큰 저장 파일 (앱 데이터 디렉토리)
+const STATS_TOKEN_FILE: &str = "stats_api_token";
+
+/// 🆕 저장된 DHT 노드의 응답을 기다리는 시간 (지나면 mDNS/외부 부트스트랩으로 대체)
+const KNOWN_NODES_WAIT: Duration = Duration::from_secs(3);
+
+/// 🆕 이만큼 응답하면 저장된 노드만으로 부트스트랩 (저장된 노드가 더 적으면 그 수)
+const MIN_KNOWN_NODES: usize = 3;
+
 /// 서비스 실행 상태
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
@@ -100,6 +112,9 @@ pub struct EmbeddedBootstrapService {
 
     /// 고성능 QUIC 클라이언트 (TURN 지원)
     quic_client: Option<QuicClientEnhanced>,
+
+    /// 라우팅 테이블 등 영속 데이터 저장 디렉토리
+    data_dir: Option<PathBuf>,
 }
 
 impl EmbeddedBootstrapService {
@@ -123,9 +138,16 @@ impl EmbeddedBootstrapService {
             turn_client: None,
             stun_client: None,
             quic_client: None,
+            data_dir: None,
         }
     }
 
+    /// 영속 데이터 디렉토리 지정 (DHT 라우팅 테이블 저장/복원)
+    pub fn with_data_dir(mut self, dir: PathBuf) -> Self {
+        self.data_dir = Some(dir);
+        self
+    }
+
     /// 현재 상태 조회
     pub async fn state(&self) -> ServiceState {
         self.state.read().await.clone()
@@ -148,9 +170,61 @@ impl EmbeddedBootstrapService {
     }
 
     /// 설정 업데이트
-    pub async fn update_config(&self, new_config: BootstrapConfig) {
+    pub async fn update_config(&self, mut new_config: BootstrapConfig) {
         info!("부트스트랩 설정 업데이트");
-        *self.config.write().await = new_config;
+        let mut config = self.config.write().await;
+        // 토큰 없이 보낸 설정이면 기존 Stats API 토큰 유지
+        if new_config.stats_api_token.is_none() {
+            new_config.stats_api_token = config.stats_api_token.take();
+        }
+        *config = new_config;
+    }
+
+    /// Stats API 토큰 조회 (서비스를 한 번 시작한 뒤부터 존재)
+    pub async fn stats_api_token(&self) -> Option<String> {
+        self.config.read().await.stats_api_token.clone()
+    }
+
+    /// Stats API 토큰 확보 (설정 → 저장된 파일 → 새로 생성 순, 결과는 설정에 기록)
+    async fn ensure_stats_api_token(&self) -> (String, bool) {
+        let mut config = self.config.write().await;
+        let token = match config.stats_api_token.clone() {
+            Some(token) => token,
+            None => {
+                let path = self.data_dir.as_ref().map(|dir| dir.join(STATS_TOKEN_FILE));
+                let saved = path
+                    .as_ref()
+                    .and_then(|path| std::fs::read_to_string(path).ok())
+                    .map(|token| token.trim().to_string())
+                    .filter(|token| !token.is_empty());
+
+                match saved {
+                    Some(token) => token,
+                    None => {
+                        let token = generate_api_token();
+                        if let Some(path) = &path {
+                            let written = path
+                                .parent()
+                                .map_or(Ok(()), std::fs::create_dir_all)
+                                .and_then(|_| std::fs::write(path, &token));
+                            if let Err(e) = written {
+                                warn!("Stats API 토큰 저장 실패: {}", e);
+                            }
+                        }
+                        info!("🔑 Stats API 토큰 생성됨");
+                        token
+                    }
+                }
+            }
+        };
+
+        config.stats_api_token = Some(token.clone());
+        (token, config.stats_localhost_only)
+    }
+
+    /// DHT 노드 핸들 조회 (실행 중일 때만)
+    pub fn dht_handle(&self) -> Option<DhtHandle> {
+        self.dht_handle.clone()
     }
 
     /// 통계 수집기 조회
@@ -173,11 +247,15 @@ impl EmbeddedBootstrapService {
                 providers_stored: stats_guard.providers_stored,
                 messages_received: stats_guard.dht_messages_received,
                 messages_sent: stats_guard.dht_messages_sent,
+                messages_dropped_flood: stats_guard.dht_messages_dropped_flood,
+                messages_dropped_malformed: stats_guard.dht_messages_dropped_malformed,
             },
             relay_stats: RelayStats {
                 active_sessions: stats_guard.active_relay_sessions,
                 total_connections: stats_guard.relay_connections,
                 bytes_relayed: stats_guard.bytes_relayed,
+                sessions_evicted_idle: stats_guard.relay_sessions_evicted_idle,
+                sessions_evicted_lifetime: stats_guard.relay_sessions_evicted_lifetime,
             },
             connected_bootstrap_nodes: self.connected_bootstrap_nodes,
             discovered_peers: self.discovered_peers,
@@ -317,7 +395,17 @@ impl EmbeddedBootstrapService {
             self.peer_discovered_rx = Some(peer_rx);
 
             // DHT 노드 시작
-            let dht_node = DhtNode::new(ports.dht_port, self.stats.clone(), Some(peer_tx)).await?;
+            let routing_path = self.data_dir.as_ref().map(|dir| dir.join(ROUTING_TABLE_FILE));
+            let dht_tuning = self.config.read().await.dht.clone();
+            let dht_node = DhtNode::new(
+                ports.dht_port,
+                self.stats.clone(),
+                Some(peer_tx),
+                routing_path,
+                dht_tuning,
+            )
+            .await?;
+            let saved_node_count = dht_node.saved_node_count();
             self.dht_handle = Some(dht_node.handle());
 
             self.dht_task = Some(tokio::spawn(async move {
@@ -353,13 +441,24 @@ impl EmbeddedBootstrapService {
             }
 
             // Stats HTTP 서버 시작
-            let stats_server = StatsServer::new(ports.stats_port, self.stats.clone()).await?;
+            let (api_token, stats_localhost_only) = self.ensure_stats_api_token().await;
+            let stats_server = StatsServer::new(
+                ports.stats_port,
+                self.stats.clone(),
+                api_token,
+                stats_localhost_only,
+            )
+            .await?;
 
             self.stats_task = Some(tokio::spawn(async move {
                 stats_server.run().await;
             }));
 
             info!("✅ Stats API 서버 시작됨: 포트 {}", ports.stats_port);
+            info!("📈 상태 대시보드: http://localhost:{}/dashboard", ports.stats_port);
+
+            // 🆕 이전 실행에서 응답하던 노드부터 시도하고, 응답이 없을 때만 부트스트랩 목록 사용
+            let warm_started = self.boot_from_known_nodes(saved_node_count).await;
 
             // mDNS 탐색 시작 및 DHT 연동
             if enable_mdns_discovery {
@@ -373,11 +472,13 @@ impl EmbeddedBootstrapService {
 
                             if let Some(dht_handle) = self.dht_handle.clone() {
                                 let mdns_task = tokio::spawn(async move {
-                                    // 초기 발견된 노드 주소 가져오기
-                                    let initial_nodes = discovery.get_addresses().await;
-                                    for addr in initial_nodes {
-                                        info!("🔗 mDNS 초기 발견 노드 추가: {}", addr);
-                                        let _ = dht_handle.add_bootstrap_node(addr).await;
+                                    // 초기 발견된 노드 주소 가져오기 (저장된 노드로 시작했으면 생략)
+                                    if !warm_started {
+                                        let initial_nodes = discovery.get_addresses().await;
+                                        for addr in initial_nodes {
+                                            info!("🔗 mDNS 초기 발견 노드 추가: {}", addr);
+                                            let _ = dht_handle.add_bootstrap_node(addr).await;
+                                        }
                                     }
 
                                     // 실시간 발견 이벤트 처리
@@ -405,7 +506,7 @@ impl EmbeddedBootstrapService {
             }
 
             // 외부 부트스트랩 노드 연결
-            if has_external_bootstrap {
+            if has_external_bootstrap && !warm_started {
                 self.connect_to_bootstrap_nodes().await;
             }
 
@@ -420,35 +521,9 @@ impl EmbeddedBootstrapService {
         _ports: &BoundPorts,
         config: Arc<RwLock<BootstrapConfig>>,
     ) -> anyhow::Result<()> {
-        let cfg = config.read().await.clone();
-
-        if !cfg.enable_turn {
+        let Some(turn_config) = config.read().await.turn_config() else {
             info!("TURN 기능 비활성화");
             return Ok(());
-        }
-
-        let server_url = cfg
-            .turn_server_url
-            .clone()
-            .unwrap_or_else(|| "turn.ponslink.online:3478".to_string());
-
-        let turn_config = TurnConfig {
-            server_url,
-            realm: cfg
-                .turn_realm
-                .clone()
-                .unwrap_or_else(|| "ponslink.online".to_string()),
-            enable_tls: true,
-            auth_method: if cfg.turn_secret.is_some() {
-                TurnAuthMethod::LongTerm
-            } else {
-                TurnAuthMethod::ShortTerm
-            },
-            username: cfg.turn_username.clone(),
-            password: cfg.turn_password.clone(),
-            secret: cfg.turn_secret.clone(),
-            timeout_sec: 30,
-            refresh_ratio: 0.8,
         };
 
         let turn_client = TurnClient::new(turn_config.clone()).map_err(anyhow::Error::msg)?;
@@ -469,6 +544,44 @@ impl EmbeddedBootstrapService {
         Ok(())
     }
 
+    /// 🆕 저장된 노드의 응답을 잠시 기다렸다가, 충분히 응답하면 그 노드들로 내 ID 조회 시작
+    ///
+    /// 설정된 부트스트랩 노드가 가끔 내려가 있는 네트워크에서도 바로 라우팅 테이블을 채웁니다.
+    /// 응답이 모자라면 false를 반환해 mDNS/외부 부트스트랩 목록으로 넘어갑니다.
+    async fn boot_from_known_nodes(&self, saved_node_count: usize) -> bool {
+        let Some(ref dht_handle) = self.dht_handle else {
+            return false;
+        };
+        if saved_node_count == 0 {
+            return false;
+        }
+
+        let min_nodes = saved_node_count.min(MIN_KNOWN_NODES);
+        let nodes = match dht_handle.wait_for_nodes(min_nodes, KNOWN_NODES_WAIT).await {
+            Ok(nodes) => nodes,
+            Err(e) => {
+                warn!("저장된 DHT 노드 확인 실패: {}", e);
+                return false;
+            }
+        };
+        if nodes.len() < min_nodes {
+            info!(
+                "저장된 DHT 노드 응답 부족 ({}/{}), 부트스트랩 목록 사용",
+                nodes.len(),
+                min_nodes
+            );
+            return false;
+        }
+
+        info!("🔁 저장된 DHT 노드 {}개 응답, 이 노드들로 부트스트랩", nodes.len());
+        // 라우팅 테이블의 가까운 노드부터 내 ID 조회
+        if let Err(e) = dht_handle.add_bootstrap_node(nodes[0]).await {
+            warn!("저장된 노드로 부트스트랩 실패: {}", e);
+            return false;
+        }
+        true
+    }
+
     /// 외부 부트스트랩 노드에 연결
     async fn connect_to_bootstrap_nodes(&mut self) {
         if let Some(ref dht_handle) = self.dht_handle {
@@ -534,6 +647,11 @@ impl EmbeddedBootstrapService {
             let _ = dht_handle.shutdown().await;
         }
 
+        // 라우팅 테이블 저장이 끝날 때까지 잠시 대기
+        if let Some(task) = self.dht_task.take() {
+            let _ = tokio::time::timeout(std::time::Duration::from_secs(1), task).await;
+        }
+
         // 정리 작업
         self.cleanup().await;
 
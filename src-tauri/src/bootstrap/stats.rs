@@ -1,5 +1,6 @@
 //! 통계 수집 및 HTTP API 서버
 
+use crate::util::constant_time_eq;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use std::time::Instant;
@@ -26,6 +27,12 @@ pub struct StatsCollector {
     /// 저장된 제공자 수
     pub providers_stored: u64,
 
+    /// 출발지별 속도 제한/제공자 상한으로 버린 DHT 메시지 수
+    pub dht_messages_dropped_flood: u64,
+
+    /// 크기/형식 검사에 실패해 버린 DHT 메시지 수
+    pub dht_messages_dropped_malformed: u64,
+
     /// 릴레이 연결 수
     pub relay_connections: u64,
 
@@ -34,6 +41,12 @@ pub struct StatsCollector {
 
     /// 활성 릴레이 세션 수
     pub active_relay_sessions: u64,
+
+    /// 유휴 시간 초과로 정리한 릴레이 세션 수
+    pub relay_sessions_evicted_idle: u64,
+
+    /// 최대 수명 초과로 정리한 릴레이 세션 수
+    pub relay_sessions_evicted_lifetime: u64,
 }
 
 impl StatsCollector {
@@ -44,9 +57,13 @@ impl StatsCollector {
             dht_messages_sent: 0,
             nodes_in_routing_table: 0,
             providers_stored: 0,
+            dht_messages_dropped_flood: 0,
+            dht_messages_dropped_malformed: 0,
             relay_connections: 0,
             bytes_relayed: 0,
             active_relay_sessions: 0,
+            relay_sessions_evicted_idle: 0,
+            relay_sessions_evicted_lifetime: 0,
         }
     }
 
@@ -60,9 +77,13 @@ impl StatsCollector {
         self.dht_messages_sent = 0;
         self.nodes_in_routing_table = 0;
         self.providers_stored = 0;
+        self.dht_messages_dropped_flood = 0;
+        self.dht_messages_dropped_malformed = 0;
         self.relay_connections = 0;
         self.bytes_relayed = 0;
         self.active_relay_sessions = 0;
+        self.relay_sessions_evicted_idle = 0;
+        self.relay_sessions_evicted_lifetime = 0;
     }
 }
 
@@ -79,6 +100,10 @@ pub struct DhtStats {
     pub providers_stored: u64,
     pub messages_received: u64,
     pub messages_sent: u64,
+    #[serde(default)]
+    pub messages_dropped_flood: u64,
+    #[serde(default)]
+    pub messages_dropped_malformed: u64,
 }
 
 /// 릴레이 통계
@@ -87,6 +112,10 @@ pub struct RelayStats {
     pub active_sessions: u64,
     pub total_connections: u64,
     pub bytes_relayed: u64,
+    #[serde(default)]
+    pub sessions_evicted_idle: u64,
+    #[serde(default)]
+    pub sessions_evicted_lifetime: u64,
 }
 
 /// HTTP 통계 API 응답 (standalone bootstrap과 호환)
@@ -98,16 +127,86 @@ struct StatsResponse {
     relay: RelayStats,
 }
 
+/// 브라우저용 상태 대시보드 (외부 리소스 없이 `/stats`를 주기적으로 조회)
+const DASHBOARD_HTML: &str = include_str!("dashboard.html");
+
+/// Stats API 토큰 헤더 (`Authorization: Bearer <token>`도 허용)
+pub const STATS_TOKEN_HEADER: &str = "X-Api-Token";
+
+/// 새 Stats API 토큰 생성 (32바이트 랜덤, hex)
+pub fn generate_api_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut bytes);
+    hex::encode(bytes)
+}
+
+/// 요청 헤더에 올바른 토큰이 있는지 확인
+fn is_authorized(request: &str, token: &str) -> bool {
+    request
+        .lines()
+        .skip(1)
+        .take_while(|line| !line.is_empty())
+        .filter_map(|line| line.split_once(':'))
+        .any(|(name, value)| {
+            let name = name.trim();
+            let value = value.trim();
+            let presented = if name.eq_ignore_ascii_case(STATS_TOKEN_HEADER) {
+                Some(value)
+            } else if name.eq_ignore_ascii_case("authorization") {
+                value.strip_prefix("Bearer ").map(str::trim)
+            } else {
+                None
+            };
+            presented.is_some_and(|p| constant_time_eq(p.as_bytes(), token.as_bytes()))
+        })
+}
+
+/// 통계 JSON 본문
+fn stats_json(stats: &StatsCollector) -> String {
+    let response_body = StatsResponse {
+        status: "ok",
+        uptime_secs: stats.uptime_secs(),
+        dht: DhtStats {
+            messages_received: stats.dht_messages_received,
+            messages_sent: stats.dht_messages_sent,
+            nodes_in_routing_table: stats.nodes_in_routing_table,
+            providers_stored: stats.providers_stored,
+            messages_dropped_flood: stats.dht_messages_dropped_flood,
+            messages_dropped_malformed: stats.dht_messages_dropped_malformed,
+        },
+        relay: RelayStats {
+            total_connections: stats.relay_connections,
+            active_sessions: stats.active_relay_sessions,
+            bytes_relayed: stats.bytes_relayed,
+            sessions_evicted_idle: stats.relay_sessions_evicted_idle,
+            sessions_evicted_lifetime: stats.relay_sessions_evicted_lifetime,
+        },
+    };
+
+    serde_json::to_string_pretty(&response_body).unwrap_or_default()
+}
+
 /// HTTP 통계 API 서버
 pub struct StatsServer {
     listener: TcpListener,
     stats: Arc<RwLock<StatsCollector>>,
+    /// `/stats` 요청에 필요한 토큰
+    api_token: Arc<str>,
 }
 
 impl StatsServer {
-    pub async fn new(port: u16, stats: Arc<RwLock<StatsCollector>>) -> anyhow::Result<Self> {
-        // 여러 주소에 바인딩 시도 (localhost 연결 문제 해결)
-        let addrs = [format!("127.0.0.1:{}", port), format!("0.0.0.0:{}", port)];
+    pub async fn new(
+        port: u16,
+        stats: Arc<RwLock<StatsCollector>>,
+        api_token: String,
+        localhost_only: bool,
+    ) -> anyhow::Result<Self> {
+        // localhost 전용이 아니면 모든 인터페이스, 실패 시 localhost로 fallback
+        let addrs = if localhost_only {
+            vec![format!("127.0.0.1:{}", port)]
+        } else {
+            vec![format!("0.0.0.0:{}", port), format!("127.0.0.1:{}", port)]
+        };
 
         let mut listener = None;
         for addr in &addrs {
@@ -130,7 +229,11 @@ impl StatsServer {
 
         let listener = listener.ok_or_else(|| anyhow::anyhow!("모든 주소에 바인딩 실패"))?;
 
-        Ok(Self { listener, stats })
+        Ok(Self {
+            listener,
+            stats,
+            api_token: api_token.into(),
+        })
     }
 
     #[allow(dead_code)]
@@ -143,6 +246,7 @@ impl StatsServer {
             match self.listener.accept().await {
                 Ok((mut socket, _addr)) => {
                     let stats = self.stats.clone();
+                    let api_token = self.api_token.clone();
 
                     tauri::async_runtime::spawn(async move {
                         let mut buf = [0u8; 1024];
@@ -152,39 +256,15 @@ impl StatsServer {
                             let request = String::from_utf8_lossy(&buf[..n]);
 
                             // 간단한 라우팅
-                            let response = if request.contains("GET /stats")
-                                || request.contains("GET / ")
-                            {
-                                let stats_guard = stats.read().await;
-
-                                let response_body = StatsResponse {
-                                    status: "ok",
-                                    uptime_secs: stats_guard.uptime_secs(),
-                                    dht: DhtStats {
-                                        messages_received: stats_guard.dht_messages_received,
-                                        messages_sent: stats_guard.dht_messages_sent,
-                                        nodes_in_routing_table: stats_guard.nodes_in_routing_table,
-                                        providers_stored: stats_guard.providers_stored,
-                                    },
-                                    relay: RelayStats {
-                                        total_connections: stats_guard.relay_connections,
-                                        active_sessions: stats_guard.active_relay_sessions,
-                                        bytes_relayed: stats_guard.bytes_relayed,
-                                    },
-                                };
-
-                                let body = serde_json::to_string_pretty(&response_body)
-                                    .unwrap_or_default();
-
+                            let response = if request.starts_with("OPTIONS ") {
+                                // 브라우저 CORS preflight (토큰 헤더 허용)
                                 format!(
-                                    "HTTP/1.1 200 OK\r\n\
-                                    Content-Type: application/json\r\n\
-                                    Content-Length: {}\r\n\
+                                    "HTTP/1.1 204 No Content\r\n\
                                     Access-Control-Allow-Origin: *\r\n\
-                                    \r\n\
-                                    {}",
-                                    body.len(),
-                                    body
+                                    Access-Control-Allow-Headers: Authorization, {}\r\n\
+                                    Content-Length: 0\r\n\
+                                    \r\n",
+                                    STATS_TOKEN_HEADER
                                 )
                             } else if request.contains("GET /health") {
                                 "HTTP/1.1 200 OK\r\n\
@@ -193,13 +273,49 @@ impl StatsServer {
                                 \r\n\
                                 OK"
                                 .to_string()
-                            } else {
+                            } else if request.contains("GET /dashboard") {
+                                // 페이지 자체는 공개, 데이터는 토큰으로 `/stats`에서 조회
+                                format!(
+                                    "HTTP/1.1 200 OK\r\n\
+                                    Content-Type: text/html; charset=utf-8\r\n\
+                                    Content-Length: {}\r\n\
+                                    Cache-Control: no-cache\r\n\
+                                    \r\n\
+                                    {}",
+                                    DASHBOARD_HTML.len(),
+                                    DASHBOARD_HTML
+                                )
+                            } else if !(request.contains("GET /stats")
+                                || request.contains("GET / "))
+                            {
                                 "HTTP/1.1 404 Not Found\r\n\
                                 Content-Type: text/plain\r\n\
                                 Content-Length: 9\r\n\
                                 \r\n\
                                 Not Found"
                                     .to_string()
+                            } else if !is_authorized(&request, &api_token) {
+                                "HTTP/1.1 401 Unauthorized\r\n\
+                                Content-Type: text/plain\r\n\
+                                WWW-Authenticate: Bearer\r\n\
+                                Access-Control-Allow-Origin: *\r\n\
+                                Content-Length: 12\r\n\
+                                \r\n\
+                                Unauthorized"
+                                    .to_string()
+                            } else {
+                                let body = stats_json(&*stats.read().await);
+
+                                format!(
+                                    "HTTP/1.1 200 OK\r\n\
+                                    Content-Type: application/json\r\n\
+                                    Content-Length: {}\r\n\
+                                    Access-Control-Allow-Origin: *\r\n\
+                                    \r\n\
+                                    {}",
+                                    body.len(),
+                                    body
+                                )
                             };
 
                             let _ = socket.write_all(response.as_bytes()).await;
@@ -213,3 +329,35 @@ impl StatsServer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_required_in_header() {
+        let token = generate_api_token();
+        assert_eq!(token.len(), 64);
+
+        let bearer = format!(
+            "GET /stats HTTP/1.1\r\nHost: x\r\nAuthorization: Bearer {}\r\n\r\n",
+            token
+        );
+        let custom = format!("GET /stats HTTP/1.1\r\nx-api-token: {}\r\n\r\n", token);
+        assert!(is_authorized(&bearer, &token));
+        assert!(is_authorized(&custom, &token));
+
+        assert!(!is_authorized(
+            "GET /stats HTTP/1.1\r\nHost: x\r\n\r\n",
+            &token
+        ));
+        assert!(!is_authorized(
+            "GET /stats HTTP/1.1\r\nAuthorization: Bearer wrong\r\n\r\n",
+            &token
+        ));
+
+        // 본문에 있는 토큰은 무시
+        let in_body = format!("GET /stats HTTP/1.1\r\n\r\nX-Api-Token: {}", token);
+        assert!(!is_authorized(&in_body, &token));
+    }
+}
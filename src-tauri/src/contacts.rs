@@ -0,0 +1,446 @@
+//! 연락처 목록
+//!
+//! 자주 주고받는 피어를 별명으로 저장해 두고, 지문 문자열 대신 연락처로 연결·자동 수락 규칙을 지정합니다.
+//! 연락처는 노드 ID(시그널링/접속 명부용 Ed25519 공개 키)와 QUIC 인증서 지문 중 하나 이상을 가지며,
+//! 전송 요청이나 접속 명부 조회로 알게 된 마지막 주소를 함께 기록합니다.
+//! 목록은 앱 데이터 디렉토리에 저장됩니다.
+//! 🆕 짧은 인증 문자열(`quic::sas`)을 비교해 확인한 연락처만 신뢰하는 연락처가 되며, 키가 바뀌면 다시 확인해야 합니다.
+
+use crate::quic::identity::normalize_fingerprint;
+use crate::util::now_secs;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use tracing::{info, warn};
+
+/// 연락처 파일명
+pub const CONTACTS_FILE: &str = "contacts.json";
+
+/// 연락처마다 기억할 최대 주소 수
+const MAX_ADDRESSES: usize = 8;
+
+/// 연락처
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Contact {
+    pub id: String,
+    pub nickname: String,
+    /// 시그널링 노드 ID (Ed25519 공개 키 hex, 우편함/접속 명부 주소)
+    #[serde(default)]
+    pub node_id: Option<String>,
+    /// QUIC 인증서 지문 (SHA-256 hex, 연결 상대 확인)
+    #[serde(default)]
+    pub fingerprint: Option<String>,
+    /// 마지막으로 확인된 주소 ("ip:port", 최근 순)
+    #[serde(default)]
+    pub addresses: Vec<String>,
+    /// 마지막으로 확인된 시각 (Unix 초)
+    #[serde(default)]
+    pub last_seen: Option<u64>,
+    /// 추가한 시각 (Unix 초)
+    #[serde(default)]
+    pub added_at: u64,
+    /// 🆕 짧은 인증 문자열로 확인한 연락처 (`mark_trusted`로만 설정)
+    #[serde(default)]
+    pub trusted: bool,
+    /// 🆕 확인한 시각 (Unix 초)
+    #[serde(default)]
+    pub verified_at: Option<u64>,
+}
+
+impl Contact {
+    /// 양쪽에 모두 있는 키가 서로 같은지 (하나도 겹치지 않으면 false)
+    pub fn has_keys(&self, node_id: Option<&str>, fingerprint: Option<&str>) -> bool {
+        let pairs = [
+            (self.node_id.as_deref(), node_id),
+            (self.fingerprint.as_deref(), fingerprint),
+        ];
+        let known: Vec<_> = pairs
+            .into_iter()
+            .filter_map(|pair| match pair {
+                (Some(ours), Some(theirs)) => Some(ours == theirs),
+                _ => None,
+            })
+            .collect();
+        !known.is_empty() && known.into_iter().all(|same| same)
+    }
+}
+
+/// 연락처 목록 (앱 전체 공유)
+pub struct ContactBook {
+    path: Option<PathBuf>,
+    contacts: Mutex<Vec<Contact>>,
+}
+
+impl ContactBook {
+    /// 저장하지 않는 메모리 전용 목록
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            contacts: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// 파일에서 로드 (없거나 손상되었으면 빈 목록)
+    pub fn load(path: PathBuf) -> Self {
+        let contacts = match std::fs::read(&path) {
+            Ok(data) => match serde_json::from_slice::<Vec<Contact>>(&data) {
+                Ok(contacts) => contacts,
+                Err(e) => {
+                    warn!("연락처 파싱 실패, 초기화: {}", e);
+                    Vec::new()
+                }
+            },
+            Err(_) => Vec::new(),
+        };
+
+        if !contacts.is_empty() {
+            info!("📒 연락처 로드: {}명", contacts.len());
+        }
+
+        Self {
+            path: Some(path),
+            contacts: Mutex::new(contacts),
+        }
+    }
+
+    pub fn list(&self) -> Vec<Contact> {
+        self.contacts.lock().clone()
+    }
+
+    pub fn get(&self, id: &str) -> Option<Contact> {
+        self.contacts.lock().iter().find(|c| c.id == id).cloned()
+    }
+
+    /// 🆕 노드 ID나 인증서 지문이 같은 연락처 (노드 ID 우선)
+    pub fn find_by_keys(
+        &self,
+        node_id: Option<&str>,
+        fingerprint: Option<&str>,
+    ) -> Option<Contact> {
+        let contacts = self.contacts.lock();
+        node_id
+            .and_then(|id| contacts.iter().find(|c| c.node_id.as_deref() == Some(id)))
+            .or_else(|| {
+                fingerprint.and_then(|fp| {
+                    contacts
+                        .iter()
+                        .find(|c| c.fingerprint.as_deref() == Some(fp))
+                })
+            })
+            .cloned()
+    }
+
+    /// 🆕 키가 모두 일치하는 신뢰하는 연락처가 있는지
+    pub fn is_trusted(&self, node_id: Option<&str>, fingerprint: Option<&str>) -> bool {
+        self.find_by_keys(node_id, fingerprint)
+            .is_some_and(|c| c.trusted && c.has_keys(node_id, fingerprint))
+    }
+
+    /// 연락처 추가 또는 같은 ID 연락처 수정 후 저장
+    ///
+    /// 수정할 때 주소를 비워 보내면 기록된 주소와 마지막 확인 시각은 그대로 둡니다.
+    pub fn upsert(&self, mut contact: Contact) -> Result<Contact, String> {
+        contact.nickname = contact.nickname.trim().to_string();
+        if contact.nickname.is_empty() {
+            return Err("별명이 비어 있습니다".to_string());
+        }
+        contact.node_id = contact
+            .node_id
+            .map(|id| id.trim().to_lowercase())
+            .filter(|id| !id.is_empty());
+        if contact
+            .node_id
+            .as_ref()
+            .is_some_and(|id| id.len() != 64 || !id.bytes().all(|b| b.is_ascii_hexdigit()))
+        {
+            return Err("노드 ID는 Ed25519 공개 키 (64자리 hex)여야 합니다".to_string());
+        }
+        contact.fingerprint = contact
+            .fingerprint
+            .map(|fp| normalize_fingerprint(&fp))
+            .filter(|fp| !fp.is_empty());
+        if contact
+            .fingerprint
+            .as_ref()
+            .is_some_and(|fp| fp.len() != 64)
+        {
+            return Err("지문은 SHA-256 (64자리 hex)이어야 합니다".to_string());
+        }
+        if contact.node_id.is_none() && contact.fingerprint.is_none() {
+            return Err("노드 ID나 인증서 지문 중 하나는 있어야 합니다".to_string());
+        }
+        contact.addresses = normalize_addresses(&contact.addresses);
+        if contact.id.is_empty() {
+            contact.id = uuid::Uuid::new_v4().to_string();
+        }
+
+        {
+            let mut contacts = self.contacts.lock();
+            if let Some(other) = contacts.iter().find(|c| {
+                c.id != contact.id
+                    && ((contact.node_id.is_some() && c.node_id == contact.node_id)
+                        || (contact.fingerprint.is_some() && c.fingerprint == contact.fingerprint))
+            }) {
+                return Err(format!("이미 등록된 연락처입니다: {}", other.nickname));
+            }
+            match contacts.iter_mut().find(|c| c.id == contact.id) {
+                Some(existing) => {
+                    contact.added_at = existing.added_at;
+                    // 키가 바뀌지 않았을 때만 확인 상태 유지
+                    let same_keys = existing.node_id == contact.node_id
+                        && existing.fingerprint == contact.fingerprint;
+                    contact.trusted = existing.trusted && same_keys;
+                    contact.verified_at = existing.verified_at.filter(|_| same_keys);
+                    if contact.addresses.is_empty() {
+                        contact.addresses = existing.addresses.clone();
+                        contact.last_seen = existing.last_seen;
+                    }
+                    *existing = contact.clone();
+                }
+                None => {
+                    contact.added_at = now_secs();
+                    contact.trusted = false;
+                    contact.verified_at = None;
+                    contacts.push(contact.clone());
+                }
+            }
+        }
+        info!("📒 연락처 저장: {} ({})", contact.nickname, contact.id);
+        self.persist();
+        Ok(contact)
+    }
+
+    /// 🆕 짧은 인증 문자열로 확인한 상대를 신뢰하는 연락처로 표시 (없으면 `nickname`으로 새로 추가)
+    ///
+    /// 확인한 키로 연락처의 키를 갱신하며, 다른 연락처가 같은 키를 가지고 있으면 실패합니다.
+    pub fn mark_trusted(
+        &self,
+        node_id: Option<String>,
+        fingerprint: Option<String>,
+        addr: SocketAddr,
+        nickname: Option<String>,
+    ) -> Result<Contact, String> {
+        if node_id.is_none() && fingerprint.is_none() {
+            return Err("상대 기기의 키를 아직 받지 못했습니다".to_string());
+        }
+        let existing = self.find_by_keys(node_id.as_deref(), fingerprint.as_deref());
+        let mut contact = match existing {
+            Some(contact) => contact,
+            None => {
+                let nickname = nickname
+                    .clone()
+                    .map(|n| n.trim().to_string())
+                    .filter(|n| !n.is_empty())
+                    .ok_or_else(|| "새 연락처의 별명이 필요합니다".to_string())?;
+                self.upsert(Contact {
+                    id: String::new(),
+                    nickname,
+                    node_id: node_id.clone(),
+                    fingerprint: fingerprint.clone(),
+                    addresses: Vec::new(),
+                    last_seen: None,
+                    added_at: 0,
+                    trusted: false,
+                    verified_at: None,
+                })?
+            }
+        };
+        if let Some(nickname) = nickname.filter(|n| !n.trim().is_empty()) {
+            contact.nickname = nickname;
+        }
+        contact.node_id = node_id.or(contact.node_id);
+        contact.fingerprint = fingerprint.or(contact.fingerprint);
+        let mut contact = self.upsert(contact)?;
+
+        {
+            let mut contacts = self.contacts.lock();
+            if let Some(saved) = contacts.iter_mut().find(|c| c.id == contact.id) {
+                remember(saved, &[addr]);
+                saved.trusted = true;
+                saved.verified_at = Some(now_secs());
+                contact = saved.clone();
+            }
+        }
+        info!("🔐 연락처 확인: {} ({})", contact.nickname, contact.id);
+        self.persist();
+        Ok(contact)
+    }
+
+    /// 연락처 삭제 후 저장
+    pub fn remove(&self, id: &str) -> bool {
+        let removed = {
+            let mut contacts = self.contacts.lock();
+            let before = contacts.len();
+            contacts.retain(|c| c.id != id);
+            contacts.len() != before
+        };
+        if removed {
+            self.persist();
+        }
+        removed
+    }
+
+    /// 인증서 지문이 같은 연락처에 방금 연결된 주소 기록 (연락처가 아니면 None)
+    pub fn record_seen(&self, fingerprint: &str, addr: SocketAddr) -> Option<Contact> {
+        let fingerprint = normalize_fingerprint(fingerprint);
+        let contact = {
+            let mut contacts = self.contacts.lock();
+            let contact = contacts
+                .iter_mut()
+                .find(|c| c.fingerprint.as_deref() == Some(fingerprint.as_str()))?;
+            remember(contact, &[addr]);
+            contact.clone()
+        };
+        self.persist();
+        Some(contact)
+    }
+
+    /// 노드 ID가 같은 연락처에 접속 명부에서 받은 후보 기록
+    pub fn record_candidates(&self, node_id: &str, candidates: &[SocketAddr]) -> bool {
+        {
+            let mut contacts = self.contacts.lock();
+            let Some(contact) = contacts
+                .iter_mut()
+                .find(|c| c.node_id.as_deref() == Some(node_id))
+            else {
+                return false;
+            };
+            remember(contact, candidates);
+        }
+        self.persist();
+        true
+    }
+
+    fn persist(&self) {
+        let Some(ref path) = self.path else {
+            return;
+        };
+        let contacts = self.list();
+
+        let result = (|| -> anyhow::Result<()> {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            let tmp = path.with_extension("json.tmp");
+            std::fs::write(&tmp, serde_json::to_vec_pretty(&contacts)?)?;
+            std::fs::rename(&tmp, path)?;
+            Ok(())
+        })();
+
+        if let Err(e) = result {
+            warn!("연락처 저장 실패: {}", e);
+        }
+    }
+}
+
+/// 새 주소를 앞에 두고 중복 제거
+fn remember(contact: &mut Contact, addrs: &[SocketAddr]) {
+    let mut addresses: Vec<String> = addrs.iter().map(|addr| addr.to_string()).collect();
+    addresses.append(&mut contact.addresses);
+    contact.addresses = normalize_addresses(&addresses);
+    contact.last_seen = Some(now_secs());
+}
+
+/// 주소 형식 확인, 중복 제거, 개수 제한
+fn normalize_addresses(addresses: &[String]) -> Vec<String> {
+    let mut parsed: Vec<SocketAddr> = Vec::new();
+    for addr in addresses.iter().filter_map(|a| a.trim().parse().ok()) {
+        if !parsed.contains(&addr) {
+            parsed.push(addr);
+        }
+    }
+    parsed.truncate(MAX_ADDRESSES);
+    parsed.iter().map(|addr| addr.to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contact(nickname: &str, fingerprint: Option<String>) -> Contact {
+        Contact {
+            id: String::new(),
+            nickname: nickname.to_string(),
+            node_id: None,
+            fingerprint,
+            addresses: Vec::new(),
+            last_seen: None,
+            added_at: 0,
+            trusted: false,
+            verified_at: None,
+        }
+    }
+
+    #[test]
+    fn test_upsert_and_record_seen() {
+        let book = ContactBook::in_memory();
+        let fingerprint = "ab".repeat(32);
+
+        assert!(book.upsert(contact("nobody", None)).is_err());
+        assert!(book.upsert(contact("", Some(fingerprint.clone()))).is_err());
+        assert!(book.upsert(contact("bad", Some("xyz".into()))).is_err());
+
+        let saved = book
+            .upsert(contact("build server", Some(fingerprint.to_uppercase())))
+            .unwrap();
+        assert_eq!(saved.fingerprint.as_deref(), Some(fingerprint.as_str()));
+        // 같은 지문으로 다른 연락처를 만들 수 없음
+        assert!(book
+            .upsert(contact("duplicate", Some(fingerprint.clone())))
+            .is_err());
+
+        let addr: SocketAddr = "192.168.0.10:5000".parse().unwrap();
+        let seen = book.record_seen(&fingerprint, addr).unwrap();
+        assert_eq!(seen.addresses, vec![addr.to_string()]);
+        assert!(seen.last_seen.is_some());
+        assert!(book.record_seen(&"cd".repeat(32), addr).is_none());
+
+        // 주소 없이 수정하면 기록된 주소 유지
+        let renamed = book
+            .upsert(Contact {
+                nickname: "ci".to_string(),
+                ..saved.clone()
+            })
+            .unwrap();
+        assert_eq!(renamed.addresses, vec![addr.to_string()]);
+        assert_eq!(book.get(&saved.id).unwrap().nickname, "ci");
+
+        assert!(book.remove(&saved.id));
+        assert!(book.list().is_empty());
+    }
+
+    #[test]
+    fn test_mark_trusted() {
+        let book = ContactBook::in_memory();
+        let node_id = "11".repeat(32);
+        let fingerprint = "ab".repeat(32);
+        let addr: SocketAddr = "192.168.0.10:5000".parse().unwrap();
+
+        // 새 상대는 별명이 있어야 추가
+        assert!(book
+            .mark_trusted(Some(node_id.clone()), None, addr, None)
+            .is_err());
+        let trusted = book
+            .mark_trusted(Some(node_id.clone()), None, addr, Some("laptop".into()))
+            .unwrap();
+        assert!(trusted.trusted);
+        assert!(book.is_trusted(Some(&node_id), None));
+
+        // 다른 인증서 지문을 알린 같은 노드 ID → 다시 확인할 때까지 신뢰하지 않음
+        let with_fp = book
+            .upsert(Contact {
+                fingerprint: Some(fingerprint.clone()),
+                ..trusted
+            })
+            .unwrap();
+        assert!(!with_fp.trusted);
+        assert!(!book.is_trusted(Some(&node_id), Some(&fingerprint)));
+        book.mark_trusted(Some(node_id.clone()), Some(fingerprint.clone()), addr, None)
+            .unwrap();
+        assert!(book.is_trusted(Some(&node_id), Some(&fingerprint)));
+        assert!(!book.is_trusted(Some(&node_id), Some(&"cd".repeat(32))));
+        assert_eq!(book.list().len(), 1);
+    }
+}
@@ -0,0 +1,122 @@
+//! Announce 토큰 (mainline DHT의 get_peers/announce_peer 토큰 방식)
+//!
+//! GetProviders 응답에 요청자 IP에 묶인 토큰을 실어 보내고, Announce는 최근에 받은
+//! 토큰을 그대로 돌려줄 때만 받아들입니다. 토큰은 `SHA-256(secret || IP)`이며 secret은
+//! 주기적으로 교체되므로, 직접 질의하지 않은 제3자가 다른 호스트를 제공자로 등록할 수
+//! 없습니다. 교체 직후에도 응답이 유효하도록 직전 secret까지 허용합니다.
+
+use parking_lot::Mutex;
+use sha2::{Digest, Sha256};
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+
+/// Announce 토큰
+pub type Token = [u8; 8];
+
+/// secret 교체 주기 (토큰 유효 시간은 최대 이 값의 2배)
+pub const TOKEN_ROTATE_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+struct Secrets {
+    current: [u8; 32],
+    previous: [u8; 32],
+    rotated_at: Instant,
+}
+
+/// 토큰 발급/검증기
+pub struct TokenIssuer {
+    secrets: Mutex<Secrets>,
+}
+
+impl Default for TokenIssuer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TokenIssuer {
+    pub fn new() -> Self {
+        let current = random_secret();
+        Self {
+            secrets: Mutex::new(Secrets {
+                current,
+                previous: current,
+                rotated_at: Instant::now(),
+            }),
+        }
+    }
+
+    /// 요청자 IP용 토큰 발급
+    pub fn issue(&self, ip: &IpAddr) -> Token {
+        let mut secrets = self.secrets.lock();
+        Self::rotate_if_due(&mut secrets);
+        make_token(&secrets.current, ip)
+    }
+
+    /// Announce에 실린 토큰 검증
+    pub fn verify(&self, ip: &IpAddr, token: &Token) -> bool {
+        let mut secrets = self.secrets.lock();
+        Self::rotate_if_due(&mut secrets);
+        make_token(&secrets.current, ip) == *token || make_token(&secrets.previous, ip) == *token
+    }
+
+    fn rotate_if_due(secrets: &mut Secrets) {
+        if secrets.rotated_at.elapsed() >= TOKEN_ROTATE_INTERVAL {
+            Self::rotate(secrets);
+        }
+    }
+
+    fn rotate(secrets: &mut Secrets) {
+        secrets.previous = secrets.current;
+        secrets.current = random_secret();
+        secrets.rotated_at = Instant::now();
+    }
+}
+
+fn random_secret() -> [u8; 32] {
+    let mut secret = [0u8; 32];
+    rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut secret);
+    secret
+}
+
+fn make_token(secret: &[u8; 32], ip: &IpAddr) -> Token {
+    let mut hasher = Sha256::new();
+    hasher.update(secret);
+    match ip {
+        IpAddr::V4(v4) => hasher.update(v4.octets()),
+        IpAddr::V6(v6) => hasher.update(v6.octets()),
+    }
+    let digest = hasher.finalize();
+    let mut token = [0u8; 8];
+    token.copy_from_slice(&digest[..8]);
+    token
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_bound_to_ip() {
+        let issuer = TokenIssuer::new();
+        let ip: IpAddr = "192.168.0.10".parse().unwrap();
+        let other: IpAddr = "192.168.0.11".parse().unwrap();
+
+        let token = issuer.issue(&ip);
+        assert!(issuer.verify(&ip, &token));
+        assert!(!issuer.verify(&other, &token));
+        assert!(!issuer.verify(&ip, &[0u8; 8]));
+    }
+
+    #[test]
+    fn test_token_survives_one_rotation_only() {
+        let issuer = TokenIssuer::new();
+        let ip: IpAddr = "10.0.0.5".parse().unwrap();
+        let token = issuer.issue(&ip);
+
+        TokenIssuer::rotate(&mut issuer.secrets.lock());
+        assert!(issuer.verify(&ip, &token));
+
+        TokenIssuer::rotate(&mut issuer.secrets.lock());
+        assert!(!issuer.verify(&ip, &token));
+    }
+}
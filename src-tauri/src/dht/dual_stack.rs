@@ -0,0 +1,88 @@
+//! DHT용 IPv4/IPv6 듀얼 스택 UDP 소켓
+//!
+//! `[::]`에 `IPV6_V6ONLY = false`로 바인딩해 한 소켓으로 두 주소 체계를 모두 받습니다.
+//! 이 소켓으로 들어온 IPv4 패킷의 출발지는 `::ffff:a.b.c.d` 형태이므로 수신 즉시
+//! IPv4 주소로 되돌리고(라우팅 테이블/노드 목록에는 항상 원래 주소 체계로 저장),
+//! 보낼 때는 반대로 매핑합니다. IPv6를 쓸 수 없는 환경에서는 `0.0.0.0`으로 대체합니다.
+
+use socket2::{Domain, Protocol, Socket, Type};
+use std::net::{IpAddr, Ipv6Addr, SocketAddr};
+use tokio::net::UdpSocket;
+use tracing::warn;
+
+/// 주소 체계 (버킷/응답 노드 목록을 나누는 기준)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AddrFamily {
+    V4,
+    V6,
+}
+
+impl AddrFamily {
+    pub fn of(addr: &SocketAddr) -> Self {
+        if addr.is_ipv4() {
+            AddrFamily::V4
+        } else {
+            AddrFamily::V6
+        }
+    }
+}
+
+/// 듀얼 스택 UDP 소켓 바인딩 (실패 시 IPv4 전용)
+pub async fn bind_udp(port: u16) -> anyhow::Result<UdpSocket> {
+    match bind_v6_dual(port) {
+        Ok(socket) => Ok(socket),
+        Err(e) => {
+            warn!("IPv6 듀얼 스택 바인딩 실패, IPv4 전용으로 진행: {}", e);
+            Ok(UdpSocket::bind(format!("0.0.0.0:{}", port)).await?)
+        }
+    }
+}
+
+fn bind_v6_dual(port: u16) -> std::io::Result<UdpSocket> {
+    let socket = Socket::new(Domain::IPV6, Type::DGRAM, Some(Protocol::UDP))?;
+    socket.set_only_v6(false)?;
+    socket.set_nonblocking(true)?;
+    socket.bind(&SocketAddr::new(IpAddr::V6(Ipv6Addr::UNSPECIFIED), port).into())?;
+    UdpSocket::from_std(socket.into())
+}
+
+/// 수신 주소 정규화 (IPv4-mapped IPv6 -> IPv4)
+pub fn canonical(addr: SocketAddr) -> SocketAddr {
+    match addr {
+        SocketAddr::V6(v6) => match v6.ip().to_ipv4_mapped() {
+            Some(v4) => SocketAddr::new(IpAddr::V4(v4), v6.port()),
+            None => addr,
+        },
+        SocketAddr::V4(_) => addr,
+    }
+}
+
+/// 송신 주소 변환 (IPv6 소켓이면 IPv4 주소를 매핑 주소로)
+pub fn for_socket(addr: SocketAddr, socket_is_v6: bool) -> SocketAddr {
+    match addr {
+        SocketAddr::V4(v4) if socket_is_v6 => {
+            SocketAddr::new(IpAddr::V6(v4.ip().to_ipv6_mapped()), v4.port())
+        }
+        _ => addr,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mapped_address_roundtrip() {
+        let v4: SocketAddr = "192.168.1.20:6881".parse().unwrap();
+        let mapped = for_socket(v4, true);
+        assert_eq!(mapped, "[::ffff:192.168.1.20]:6881".parse().unwrap());
+        assert_eq!(canonical(mapped), v4);
+        assert_eq!(AddrFamily::of(&canonical(mapped)), AddrFamily::V4);
+
+        // IPv4 소켓이거나 실제 IPv6 주소면 그대로
+        assert_eq!(for_socket(v4, false), v4);
+        let v6: SocketAddr = "[fd00::7]:6881".parse().unwrap();
+        assert_eq!(canonical(v6), v6);
+        assert_eq!(for_socket(v6, true), v6);
+    }
+}
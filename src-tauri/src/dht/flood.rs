@@ -0,0 +1,131 @@
+//! DHT 메시지 폭주 방지 (출발지 IP별 토큰 버킷)
+//!
+//! 각 IP는 초당 `SOURCE_RATE`개 메시지(버스트 `SOURCE_BURST`)까지 처리하고, 넘치는
+//! 메시지는 역직렬화/라우팅 작업 전에 버립니다. Announce처럼 상태를 남기는 메시지는
+//! 비용을 높게 매겨 제공자 맵을 채우는 속도를 따로 제한합니다. 추적하는 IP 수에도
+//! 상한을 두어 출발지를 바꿔 가며 보내는 경우에도 메모리가 늘어나지 않게 합니다.
+
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+
+/// 허용하는 최대 DHT 메시지 크기 (bytes)
+pub const MAX_MESSAGE_SIZE: usize = 8 * 1024;
+
+/// 응답 하나에 담길 수 있는 최대 노드 수
+pub const MAX_NODES_PER_MESSAGE: usize = 16;
+
+/// 응답 하나에 담길 수 있는 최대 제공자 수
+pub const MAX_PROVIDERS_PER_MESSAGE: usize = 100;
+
+/// IP별 초당 메시지 비용
+const SOURCE_RATE: f64 = 20.0;
+
+/// IP별 버스트 허용량
+const SOURCE_BURST: f64 = 40.0;
+
+/// 추적하는 최대 출발지 수
+const MAX_TRACKED_SOURCES: usize = 4096;
+
+/// 이 시간 동안 조용한 출발지는 추적 해제
+const SOURCE_IDLE: Duration = Duration::from_secs(60);
+
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// 출발지 IP별 속도 제한기
+pub struct SourceLimiter {
+    buckets: Mutex<HashMap<IpAddr, Bucket>>,
+}
+
+impl Default for SourceLimiter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SourceLimiter {
+    pub fn new() -> Self {
+        Self {
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 비용만큼 소비 가능하면 true
+    pub fn allow(&self, ip: IpAddr, cost: f64) -> bool {
+        self.allow_at(ip, cost, Instant::now())
+    }
+
+    pub fn allow_at(&self, ip: IpAddr, cost: f64, now: Instant) -> bool {
+        let mut buckets = self.buckets.lock();
+
+        if !buckets.contains_key(&ip) && buckets.len() >= MAX_TRACKED_SOURCES {
+            buckets.retain(|_, b| now.saturating_duration_since(b.last_refill) < SOURCE_IDLE);
+            if buckets.len() >= MAX_TRACKED_SOURCES {
+                // 추적 여력이 없으면 새 출발지는 거부 (기존 피어 우선)
+                return false;
+            }
+        }
+
+        let bucket = buckets.entry(ip).or_insert(Bucket {
+            tokens: SOURCE_BURST,
+            last_refill: now,
+        });
+        let elapsed = now
+            .saturating_duration_since(bucket.last_refill)
+            .as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * SOURCE_RATE).min(SOURCE_BURST);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= cost {
+            bucket.tokens -= cost;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// 오래 조용한 출발지 정리
+    pub fn prune(&self) {
+        let now = Instant::now();
+        self.buckets
+            .lock()
+            .retain(|_, b| now.saturating_duration_since(b.last_refill) < SOURCE_IDLE);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_burst_then_refill() {
+        let limiter = SourceLimiter::new();
+        let ip: IpAddr = "10.0.0.9".parse().unwrap();
+        let now = Instant::now();
+
+        let allowed = (0..100).filter(|_| limiter.allow_at(ip, 1.0, now)).count();
+        assert_eq!(allowed, SOURCE_BURST as usize);
+
+        // 다른 IP는 영향 없음
+        assert!(limiter.allow_at("10.0.0.10".parse().unwrap(), 1.0, now));
+
+        // 0.5초 후 10개 회복
+        let later = now + Duration::from_millis(500);
+        let refilled = (0..100).filter(|_| limiter.allow_at(ip, 1.0, later)).count();
+        assert_eq!(refilled, 10);
+    }
+
+    #[test]
+    fn test_expensive_messages_limited_sooner() {
+        let limiter = SourceLimiter::new();
+        let ip: IpAddr = "10.0.0.9".parse().unwrap();
+        let now = Instant::now();
+
+        let allowed = (0..100).filter(|_| limiter.allow_at(ip, 10.0, now)).count();
+        assert_eq!(allowed, 4);
+    }
+}
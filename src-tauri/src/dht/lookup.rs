@@ -0,0 +1,282 @@
+//! 반복(iterative) Kademlia 조회
+//!
+//! 목표 ID에 가장 가까운 후보들에게 동시에 최대 `alpha`개씩 질의하고, 응답으로 받은
+//! 더 가까운 노드를 후보에 합쳐 다시 질의합니다. 가장 가까운 `k`개 후보가 모두 응답하면
+//! 수렴한 것으로 보고 종료하며, 전체 시간 제한을 넘기면 그때까지의 결과로 끝냅니다.
+//! 네트워크 I/O는 하지 않는 상태 기계이며, 메시지 송수신은 `DhtNode`가 담당합니다.
+
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+/// 동시에 진행하는 질의 수
+pub const LOOKUP_ALPHA: usize = 3;
+
+/// 수렴 판정에 쓰는 가장 가까운 후보 수
+pub const LOOKUP_K: usize = 8;
+
+/// 개별 질의 응답 대기 시간
+pub const QUERY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// 조회 전체 시간 제한
+pub const LOOKUP_TIMEOUT: Duration = Duration::from_secs(10);
+
+type Id = [u8; 32];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CandidateState {
+    Pending,
+    InFlight(Instant),
+    Responded,
+    Failed,
+}
+
+#[derive(Debug, Clone)]
+struct Candidate {
+    /// 아직 모르는 경우 (ID 없이 주소만 아는 부트스트랩 노드)
+    id: Option<Id>,
+    addr: SocketAddr,
+    state: CandidateState,
+}
+
+/// 진행 중인 조회
+#[derive(Debug)]
+pub struct Lookup {
+    target: Id,
+    candidates: Vec<Candidate>,
+    providers: Vec<(Id, SocketAddr)>,
+    started_at: Instant,
+    alpha: usize,
+    k: usize,
+}
+
+impl Lookup {
+    pub fn new(target: Id, seeds: impl IntoIterator<Item = (Id, SocketAddr)>) -> Self {
+        Self::with_params(target, seeds, LOOKUP_ALPHA, LOOKUP_K, Instant::now())
+    }
+
+    pub fn with_params(
+        target: Id,
+        seeds: impl IntoIterator<Item = (Id, SocketAddr)>,
+        alpha: usize,
+        k: usize,
+        now: Instant,
+    ) -> Self {
+        let mut lookup = Self {
+            target,
+            candidates: Vec::new(),
+            providers: Vec::new(),
+            started_at: now,
+            alpha: alpha.max(1),
+            k: k.max(1),
+        };
+        for (id, addr) in seeds {
+            lookup.add_candidate(Some(id), addr);
+        }
+        lookup
+    }
+
+    pub fn target(&self) -> &Id {
+        &self.target
+    }
+
+    /// ID를 모르는 노드를 후보로 추가 (부트스트랩 주소)
+    pub fn add_address(&mut self, addr: SocketAddr) {
+        self.add_candidate(None, addr);
+    }
+
+    /// 이 주소에 질의를 보내고 응답을 기다리는 중인지
+    pub fn is_waiting_for(&self, addr: &SocketAddr) -> bool {
+        self.candidates
+            .iter()
+            .any(|c| c.addr == *addr && matches!(c.state, CandidateState::InFlight(_)))
+    }
+
+    /// 다음에 질의할 주소 (응답 시간 초과 질의는 실패 처리)
+    pub fn next_queries(&mut self, now: Instant) -> Vec<SocketAddr> {
+        let mut in_flight = 0;
+        for candidate in &mut self.candidates {
+            if let CandidateState::InFlight(sent) = candidate.state {
+                if now.saturating_duration_since(sent) >= QUERY_TIMEOUT {
+                    candidate.state = CandidateState::Failed;
+                } else {
+                    in_flight += 1;
+                }
+            }
+        }
+
+        let mut queries = Vec::new();
+        let (k, alpha) = (self.k, self.alpha);
+        for candidate in self.active_mut().take(k) {
+            if in_flight + queries.len() >= alpha {
+                break;
+            }
+            if candidate.state == CandidateState::Pending {
+                candidate.state = CandidateState::InFlight(now);
+                queries.push(candidate.addr);
+            }
+        }
+        queries
+    }
+
+    /// 응답 반영, 새로 발견한 제공자 반환
+    pub fn on_response(
+        &mut self,
+        from: SocketAddr,
+        sender_id: Id,
+        nodes: impl IntoIterator<Item = (Id, SocketAddr)>,
+        providers: impl IntoIterator<Item = (Id, SocketAddr)>,
+    ) -> Vec<(Id, SocketAddr)> {
+        if let Some(candidate) = self.candidates.iter_mut().find(|c| c.addr == from) {
+            candidate.id = Some(sender_id);
+            candidate.state = CandidateState::Responded;
+        }
+        self.sort();
+
+        for (id, addr) in nodes {
+            self.add_candidate(Some(id), addr);
+        }
+
+        let mut found = Vec::new();
+        for provider in providers {
+            if !self.providers.iter().any(|(_, addr)| *addr == provider.1) {
+                self.providers.push(provider);
+                found.push(provider);
+            }
+        }
+        found
+    }
+
+    /// 종료 여부 (시간 초과 또는 수렴)
+    pub fn is_finished(&self, now: Instant) -> bool {
+        if now.saturating_duration_since(self.started_at) >= LOOKUP_TIMEOUT {
+            return true;
+        }
+        self.active()
+            .take(self.k)
+            .all(|c| c.state == CandidateState::Responded)
+    }
+
+    /// 지금까지 수집한 제공자
+    pub fn providers(&self) -> &[(Id, SocketAddr)] {
+        &self.providers
+    }
+
+    /// 응답한 노드 중 목표에 가장 가까운 k개
+    pub fn closest(&self) -> Vec<(Id, SocketAddr)> {
+        self.active()
+            .filter(|c| c.state == CandidateState::Responded)
+            .filter_map(|c| c.id.map(|id| (id, c.addr)))
+            .take(self.k)
+            .collect()
+    }
+
+    fn add_candidate(&mut self, id: Option<Id>, addr: SocketAddr) {
+        if self.candidates.iter().any(|c| c.addr == addr) {
+            return;
+        }
+        self.candidates.push(Candidate {
+            id,
+            addr,
+            state: CandidateState::Pending,
+        });
+        self.sort();
+    }
+
+    fn sort(&mut self) {
+        let target = self.target;
+        self.candidates
+            .sort_by_key(|c| distance(&target, c.id.as_ref()));
+    }
+
+    /// 실패하지 않은 후보 (가까운 순)
+    fn active(&self) -> impl Iterator<Item = &Candidate> {
+        self.candidates
+            .iter()
+            .filter(|c| c.state != CandidateState::Failed)
+    }
+
+    fn active_mut(&mut self) -> impl Iterator<Item = &mut Candidate> {
+        self.candidates
+            .iter_mut()
+            .filter(|c| c.state != CandidateState::Failed)
+    }
+}
+
+/// XOR 거리 (ID를 모르면 가장 먼 것으로 취급)
+fn distance(target: &Id, id: Option<&Id>) -> Id {
+    let Some(id) = id else {
+        return [0xff; 32];
+    };
+    let mut distance = [0u8; 32];
+    for i in 0..32 {
+        distance[i] = target[i] ^ id[i];
+    }
+    distance
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(first: u8) -> (Id, SocketAddr) {
+        let mut id = [0u8; 32];
+        id[0] = first;
+        (id, format!("10.0.0.{}:6881", first).parse().unwrap())
+    }
+
+    #[test]
+    fn test_converges_on_closer_nodes() {
+        let now = Instant::now();
+        let target = [0u8; 32];
+        let mut lookup = Lookup::with_params(target, [node(0x80), node(0x40)], 2, 2, now);
+
+        let first = lookup.next_queries(now);
+        assert_eq!(first, vec![node(0x40).1, node(0x80).1]);
+
+        // 0x40이 더 가까운 노드를 알려줌 -> 다음 라운드에서 질의
+        lookup.on_response(node(0x40).1, node(0x40).0, [node(0x02), node(0x01)], []);
+        assert!(!lookup.is_finished(now));
+        let second = lookup.next_queries(now);
+        assert_eq!(second, vec![node(0x01).1]);
+
+        lookup.on_response(node(0x80).1, node(0x80).0, [], []);
+        assert_eq!(lookup.next_queries(now), vec![node(0x02).1]);
+
+        let found = lookup.on_response(node(0x01).1, node(0x01).0, [], [node(0x09)]);
+        assert_eq!(found, vec![node(0x09)]);
+        lookup.on_response(node(0x02).1, node(0x02).0, [], [node(0x09)]);
+
+        // 가장 가까운 2개가 응답 -> 수렴
+        assert!(lookup.is_finished(now));
+        assert_eq!(lookup.closest(), vec![node(0x01), node(0x02)]);
+        assert_eq!(lookup.providers(), &[node(0x09)]);
+    }
+
+    #[test]
+    fn test_query_timeout_and_overall_timeout() {
+        let now = Instant::now();
+        let mut lookup = Lookup::with_params([0u8; 32], [node(0x01), node(0x02)], 1, 2, now);
+
+        assert_eq!(lookup.next_queries(now), vec![node(0x01).1]);
+        assert!(lookup.is_waiting_for(&node(0x01).1));
+
+        // 응답 없음 -> 실패 처리 후 다음 후보
+        let later = now + QUERY_TIMEOUT;
+        assert_eq!(lookup.next_queries(later), vec![node(0x02).1]);
+        assert!(!lookup.is_waiting_for(&node(0x01).1));
+
+        assert!(lookup.is_finished(now + LOOKUP_TIMEOUT));
+    }
+
+    #[test]
+    fn test_unknown_bootstrap_address() {
+        let now = Instant::now();
+        let addr: SocketAddr = "10.1.1.1:6881".parse().unwrap();
+        let mut lookup = Lookup::with_params([0u8; 32], [], 3, 8, now);
+        lookup.add_address(addr);
+
+        assert_eq!(lookup.next_queries(now), vec![addr]);
+        lookup.on_response(addr, node(0x10).0, [node(0x03)], []);
+        assert_eq!(lookup.next_queries(now), vec![node(0x03).1]);
+    }
+}
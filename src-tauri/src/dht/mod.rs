@@ -0,0 +1,19 @@
+//! Kademlia DHT
+//!
+//! 내장 부트스트랩 노드와 Grid 스웜이 함께 사용하는 단일 DHT 서비스입니다.
+//! 노드 하나가 소켓과 라우팅 테이블을 소유하고, 나머지는 `DhtHandle`로 명령을 보냅니다.
+
+pub mod announce_token;
+pub mod dual_stack;
+pub mod flood;
+pub mod lookup;
+pub mod node;
+pub mod node_id;
+pub mod provider_store;
+pub mod reannounce;
+pub mod routing_store;
+pub mod transaction;
+pub mod tuning;
+
+pub use node::{DhtHandle, DhtNode, InfoHash, NodeId, PeerDiscoveredEvent};
+pub use tuning::DhtTuning;
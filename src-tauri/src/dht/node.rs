@@ -0,0 +1,1381 @@
+//! DHT 노드 구현 (ponswarp-bootstrap에서 포팅)
+//!
+//! Kademlia DHT 프로토콜을 구현하여 피어 발견 서비스를 제공합니다.
+
+use super::announce_token::{Token, TokenIssuer};
+use super::dual_stack::{self, AddrFamily};
+use super::flood::{
+    SourceLimiter, MAX_MESSAGE_SIZE, MAX_NODES_PER_MESSAGE, MAX_PROVIDERS_PER_MESSAGE,
+};
+use super::lookup::Lookup;
+use super::node_id::{self as node_ids, ExternalIpVotes};
+use super::provider_store::{ProviderSnapshot, SavedProvider, PROVIDERS_FILE};
+use super::reannounce::{ProvidedSet, REANNOUNCE_CHECK_INTERVAL};
+use super::routing_store::{self, RoutingSnapshot, SavedNode};
+use super::transaction::{PendingQueries, TransactionId};
+use super::tuning::DhtTuning;
+use crate::bootstrap::stats::StatsCollector;
+use bincode::Options;
+use dashmap::DashMap;
+use mdns_sd::{ServiceDaemon, ServiceInfo};
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::net::UdpSocket;
+use tokio::sync::{mpsc, oneshot, RwLock};
+use tracing::{debug, error, info, warn};
+
+/// mDNS 서비스 타입 (부트스트랩 노드 자동 발견용)
+const BOOTSTRAP_SERVICE_TYPE: &str = "_pswp._udp.local.";
+
+/// 버킷이 가득 찼을 때 이 시간 이상 응답이 없던 노드는 새 노드로 교체
+const STALE_ENTRY_REPLACE_AFTER: Duration = Duration::from_secs(300);
+
+/// Announce 토큰 응답을 기다리는 시간
+const PENDING_ANNOUNCE_TTL: Duration = Duration::from_secs(60);
+
+/// 제공자를 저장하는 최대 info_hash 수
+const MAX_PROVIDER_KEYS: usize = 10_000;
+
+pub type NodeId = [u8; 32];
+pub type InfoHash = [u8; 32];
+
+/// DHT 메시지
+///
+/// 질의는 `transaction_id`를 싣고, 응답은 질의의 값을 그대로 돌려줍니다 (`transaction` 참고).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum DhtMessage {
+    Ping {
+        sender_id: NodeId,
+        transaction_id: TransactionId,
+    },
+    Pong {
+        sender_id: NodeId,
+        transaction_id: TransactionId,
+        /// 요청자 주소 (응답 측이 본 출발지, BEP42 `ip`)
+        requester_addr: SocketAddr,
+    },
+    FindNode {
+        sender_id: NodeId,
+        transaction_id: TransactionId,
+        target: NodeId,
+    },
+    FindNodeResponse {
+        sender_id: NodeId,
+        transaction_id: TransactionId,
+        nodes: Vec<(NodeId, SocketAddr)>,
+        requester_addr: SocketAddr,
+    },
+    GetProviders {
+        sender_id: NodeId,
+        transaction_id: TransactionId,
+        info_hash: InfoHash,
+    },
+    GetProvidersResponse {
+        sender_id: NodeId,
+        transaction_id: TransactionId,
+        info_hash: InfoHash,
+        providers: Vec<(NodeId, SocketAddr)>,
+        nodes: Vec<(NodeId, SocketAddr)>,
+        /// 요청자 IP에 묶인 Announce 토큰
+        token: Token,
+        requester_addr: SocketAddr,
+    },
+    Announce {
+        sender_id: NodeId,
+        transaction_id: TransactionId,
+        info_hash: InfoHash,
+        port: u16,
+        /// 이 노드에게서 받은 GetProvidersResponse 토큰
+        token: Token,
+    },
+    AnnounceResponse {
+        sender_id: NodeId,
+        transaction_id: TransactionId,
+        success: bool,
+        requester_addr: SocketAddr,
+    },
+}
+
+impl DhtMessage {
+    fn serialize(&self) -> Vec<u8> {
+        bincode::serialize(self).unwrap_or_default()
+    }
+
+    fn deserialize(data: &[u8]) -> Option<Self> {
+        // 길이 접두사로 큰 할당을 유도하지 못하도록 크기 제한
+        bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .allow_trailing_bytes()
+            .with_limit(MAX_MESSAGE_SIZE as u64)
+            .deserialize(data)
+            .ok()
+    }
+
+    /// 출발지 속도 제한에 쓰는 비용 (상태를 남기는 메시지일수록 큼)
+    fn cost(&self) -> f64 {
+        match self {
+            DhtMessage::Announce { .. } => 10.0,
+            DhtMessage::FindNode { .. } | DhtMessage::GetProviders { .. } => 2.0,
+            _ => 1.0,
+        }
+    }
+
+    /// 응답이면 돌려받은 트랜잭션 ID (질의는 None)
+    fn response_transaction(&self) -> Option<TransactionId> {
+        match self {
+            DhtMessage::Pong { transaction_id, .. }
+            | DhtMessage::FindNodeResponse { transaction_id, .. }
+            | DhtMessage::GetProvidersResponse { transaction_id, .. }
+            | DhtMessage::AnnounceResponse { transaction_id, .. } => Some(*transaction_id),
+            _ => None,
+        }
+    }
+
+    /// 형식 검사 (목록 길이, 포트)
+    fn is_well_formed(&self) -> bool {
+        match self {
+            DhtMessage::FindNodeResponse { nodes, .. } => nodes.len() <= MAX_NODES_PER_MESSAGE,
+            DhtMessage::GetProvidersResponse {
+                providers, nodes, ..
+            } => {
+                nodes.len() <= MAX_NODES_PER_MESSAGE
+                    && providers.len() <= MAX_PROVIDERS_PER_MESSAGE
+                    && providers.iter().all(|(_, addr)| addr.port() != 0)
+            }
+            DhtMessage::Announce { port, .. } => *port != 0,
+            _ => true,
+        }
+    }
+}
+
+/// 라우팅 테이블 엔트리
+#[derive(Debug, Clone)]
+struct RoutingEntry {
+    node_id: NodeId,
+    addr: SocketAddr,
+    last_seen: Instant,
+}
+
+/// 제공자 정보
+#[derive(Debug, Clone)]
+struct ProviderInfo {
+    node_id: NodeId,
+    addr: SocketAddr,
+    announced_at: Instant,
+}
+
+/// 제공자 반복 조회와 결과 전달 채널
+struct ProviderLookup {
+    lookup: Lookup,
+    reply: mpsc::Sender<(NodeId, SocketAddr)>,
+}
+
+/// DHT 노드
+pub struct DhtNode {
+    /// 주소 체계별 노드 ID (상대는 패킷 출발지 IP로 검증하므로 IPv4/IPv6 ID를 따로 둠)
+    ///
+    /// 공인 IP가 확인되면 해당 주소 체계의 ID만 바뀔 수 있음 (`observe_external_ip`)
+    node_id_v4: parking_lot::RwLock<NodeId>,
+    node_id_v6: parking_lot::RwLock<NodeId>,
+    /// 응답으로 받은 내 공인 IP 집계
+    external_ips: parking_lot::Mutex<ExternalIpVotes>,
+    socket: Arc<UdpSocket>,
+    /// IPv6 듀얼 스택 소켓 여부 (IPv4 주소는 매핑해서 전송)
+    socket_is_v6: bool,
+    /// 주소 체계별 라우팅 테이블 (IPv4/IPv6 버킷 분리)
+    routing_v4: Vec<RwLock<Vec<RoutingEntry>>>,
+    routing_v6: Vec<RwLock<Vec<RoutingEntry>>>,
+    providers: DashMap<InfoHash, Vec<ProviderInfo>>,
+    /// 진행 중인 제공자 검색 (응답으로 받은 제공자 주소를 전달)
+    provider_lookups: DashMap<InfoHash, ProviderLookup>,
+    /// 진행 중인 노드 조회 (목표 ID별)
+    node_lookups: DashMap<NodeId, Lookup>,
+    /// 토큰을 받는 대로 Announce할 info_hash (port, 시작 시각)
+    pending_announces: DashMap<InfoHash, (u16, Instant)>,
+    /// 제공 중인 info_hash (주기적으로 재광고)
+    provided: ProvidedSet,
+    /// Announce 토큰 발급/검증
+    tokens: TokenIssuer,
+    /// 응답을 기다리는 질의 (요청하지 않은 응답은 무시)
+    queries: PendingQueries,
+    /// 출발지 IP별 속도 제한
+    limiter: SourceLimiter,
+    /// k-bucket 크기, 복제 계수, alpha, 갱신 주기, 제공자 TTL
+    tuning: DhtTuning,
+    stats: Arc<RwLock<StatsCollector>>,
+    command_rx: mpsc::Receiver<DhtCommand>,
+    command_tx: mpsc::Sender<DhtCommand>,
+    /// 🆕 Tauri 이벤트 발생용 (피어 발견 알림)
+    peer_discovered_tx: Option<mpsc::Sender<PeerDiscoveredEvent>>,
+    /// 라우팅 테이블 저장 경로 (None이면 저장하지 않음)
+    state_path: Option<PathBuf>,
+    /// 이전 실행에서 저장된 노드 (시작 시 Ping, 응답한 노드만 라우팅 테이블에 추가)
+    saved_nodes: Vec<SavedNode>,
+}
+
+/// 피어 발견 이벤트
+#[derive(Debug, Clone, Serialize)]
+pub struct PeerDiscoveredEvent {
+    pub node_id: String,
+    pub address: String,
+    pub source: String,
+}
+
+pub enum DhtCommand {
+    AddBootstrapNode(SocketAddr),
+    /// 라우팅 테이블의 노드 주소 목록 조회
+    ListNodes(oneshot::Sender<Vec<SocketAddr>>),
+    /// info_hash 제공 광고 (port = 실제 데이터 전송 포트), 중지할 때까지 재광고
+    Announce { info_hash: InfoHash, port: u16 },
+    /// info_hash 제공 중지 (재광고 중단)
+    StopProviding { info_hash: InfoHash },
+    /// info_hash 제공자 검색 (발견되는 주소를 채널로 전달)
+    FindProviders {
+        info_hash: InfoHash,
+        reply: mpsc::Sender<(NodeId, SocketAddr)>,
+    },
+    Shutdown,
+}
+
+#[derive(Clone)]
+pub struct DhtHandle {
+    command_tx: mpsc::Sender<DhtCommand>,
+}
+
+impl DhtHandle {
+    pub async fn add_bootstrap_node(&self, addr: SocketAddr) -> anyhow::Result<()> {
+        self.command_tx
+            .send(DhtCommand::AddBootstrapNode(addr))
+            .await?;
+        Ok(())
+    }
+
+    /// 라우팅 테이블에 있는 노드 주소 목록
+    pub async fn list_nodes(&self) -> anyhow::Result<Vec<SocketAddr>> {
+        let (tx, rx) = oneshot::channel();
+        self.command_tx.send(DhtCommand::ListNodes(tx)).await?;
+        Ok(rx.await?)
+    }
+
+    /// 라우팅 테이블에 `min_nodes`개 이상 들어올 때까지 대기 (timeout이면 그때까지의 목록)
+    ///
+    /// 저장된 노드에 Ping을 보낸 직후 응답한 노드가 있는지 확인하는 데 씁니다.
+    pub async fn wait_for_nodes(
+        &self,
+        min_nodes: usize,
+        timeout: Duration,
+    ) -> anyhow::Result<Vec<SocketAddr>> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let nodes = self.list_nodes().await?;
+            if nodes.len() >= min_nodes || Instant::now() >= deadline {
+                return Ok(nodes);
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
+
+    /// info_hash를 제공하고 있음을 가까운 노드에 광고
+    pub async fn announce(&self, info_hash: InfoHash, port: u16) -> anyhow::Result<()> {
+        self.command_tx
+            .send(DhtCommand::Announce { info_hash, port })
+            .await?;
+        Ok(())
+    }
+
+    /// info_hash 제공 중지 (이미 광고된 정보는 TTL이 지나면 만료)
+    pub async fn stop_providing(&self, info_hash: InfoHash) -> anyhow::Result<()> {
+        self.command_tx
+            .send(DhtCommand::StopProviding { info_hash })
+            .await?;
+        Ok(())
+    }
+
+    /// info_hash를 제공하는 피어 주소 검색 (timeout 동안 수집, 중복 제거)
+    pub async fn find_providers(
+        &self,
+        info_hash: InfoHash,
+        timeout: Duration,
+    ) -> anyhow::Result<Vec<SocketAddr>> {
+        let providers = self.find_provider_nodes(info_hash, timeout).await?;
+        Ok(providers.into_iter().map(|(_, addr)| addr).collect())
+    }
+
+    /// info_hash를 제공하는 피어의 노드 ID와 주소 검색
+    pub async fn find_provider_nodes(
+        &self,
+        info_hash: InfoHash,
+        timeout: Duration,
+    ) -> anyhow::Result<Vec<(NodeId, SocketAddr)>> {
+        let (tx, mut rx) = mpsc::channel(64);
+        self.command_tx
+            .send(DhtCommand::FindProviders {
+                info_hash,
+                reply: tx,
+            })
+            .await?;
+
+        let mut found: Vec<(NodeId, SocketAddr)> = Vec::new();
+        let deadline = tokio::time::sleep(timeout);
+        tokio::pin!(deadline);
+
+        loop {
+            tokio::select! {
+                provider = rx.recv() => match provider {
+                    Some(provider) if !found.iter().any(|(_, addr)| *addr == provider.1) => {
+                        found.push(provider)
+                    }
+                    Some(_) => {}
+                    None => break,
+                },
+                _ = &mut deadline => break,
+            }
+        }
+
+        Ok(found)
+    }
+
+    pub async fn shutdown(&self) -> anyhow::Result<()> {
+        self.command_tx.send(DhtCommand::Shutdown).await?;
+        Ok(())
+    }
+}
+
+impl DhtNode {
+    pub async fn new(
+        port: u16,
+        stats: Arc<RwLock<StatsCollector>>,
+        peer_discovered_tx: Option<mpsc::Sender<PeerDiscoveredEvent>>,
+        state_path: Option<PathBuf>,
+        tuning: DhtTuning,
+    ) -> anyhow::Result<Self> {
+        let snapshot = state_path.as_deref().and_then(RoutingSnapshot::load);
+        let (saved_v4, saved_v6, saved_nodes) = match snapshot {
+            Some(snapshot) => (Some(snapshot.node_id), snapshot.node_id_v6, snapshot.nodes),
+            None => (None, None, Vec::new()),
+        };
+        let providers = Self::restore_providers(state_path.as_deref(), &tuning);
+        // 주소 체계별 로컬 IP에서 유도한 ID (IP가 바뀌었으면 새로 생성)
+        let node_id = node_ids::for_local_ip(saved_v4, node_ids::detect_local_ip(AddrFamily::V4));
+        let node_id_v6 =
+            node_ids::for_local_ip(saved_v6, node_ids::detect_local_ip(AddrFamily::V6));
+
+        let socket = dual_stack::bind_udp(port).await?;
+        let local_addr = socket.local_addr()?;
+        info!(
+            "🌐 DHT 노드 시작: {} (ID: {}, IPv6 ID: {})",
+            local_addr,
+            hex::encode(&node_id[..8]),
+            hex::encode(&node_id_v6[..8])
+        );
+
+        let bucket_size = tuning.k_bucket_size;
+        let new_table = || -> Vec<RwLock<Vec<RoutingEntry>>> {
+            (0..256)
+                .map(|_| RwLock::new(Vec::with_capacity(bucket_size)))
+                .collect()
+        };
+
+        let (command_tx, command_rx) = mpsc::channel(100);
+
+        // mDNS로 부트스트랩 노드 광고 (자동 발견용)
+        Self::register_mdns_service(local_addr.port(), &node_id);
+
+        Ok(Self {
+            node_id_v4: parking_lot::RwLock::new(node_id),
+            node_id_v6: parking_lot::RwLock::new(node_id_v6),
+            external_ips: parking_lot::Mutex::new(ExternalIpVotes::new()),
+            socket: Arc::new(socket),
+            socket_is_v6: local_addr.is_ipv6(),
+            routing_v4: new_table(),
+            routing_v6: new_table(),
+            providers,
+            provider_lookups: DashMap::new(),
+            node_lookups: DashMap::new(),
+            pending_announces: DashMap::new(),
+            provided: ProvidedSet::new(),
+            tokens: TokenIssuer::new(),
+            queries: PendingQueries::new(),
+            limiter: SourceLimiter::new(),
+            tuning,
+            stats,
+            command_rx,
+            command_tx,
+            peer_discovered_tx,
+            state_path,
+            saved_nodes,
+        })
+    }
+
+    /// mDNS 서비스 등록 (부트스트랩 노드 자동 발견)
+    fn register_mdns_service(port: u16, node_id: &[u8; 32]) {
+        let node_id_short = hex::encode(&node_id[..1]);
+
+        match ServiceDaemon::new() {
+            Ok(daemon) => {
+                let host_name = "ponswarp.local.";
+                let instance_name = format!("pswp-{}", node_id_short);
+
+                match ServiceInfo::new(
+                    BOOTSTRAP_SERVICE_TYPE,
+                    &instance_name,
+                    host_name,
+                    (),
+                    port,
+                    None,
+                ) {
+                    Ok(service) => {
+                        if let Err(e) = daemon.register(service) {
+                            warn!("mDNS 등록 실패: {}", e);
+                        } else {
+                            info!(
+                                "📡 mDNS 부트스트랩 광고 시작: {} @ port {}",
+                                instance_name, port
+                            );
+                        }
+                    }
+                    Err(e) => warn!("mDNS 서비스 정보 생성 실패: {}", e),
+                }
+            }
+            Err(e) => warn!("mDNS 데몬 생성 실패: {}", e),
+        }
+    }
+
+    pub fn handle(&self) -> DhtHandle {
+        DhtHandle {
+            command_tx: self.command_tx.clone(),
+        }
+    }
+
+    pub fn local_addr(&self) -> anyhow::Result<SocketAddr> {
+        Ok(self.socket.local_addr()?)
+    }
+
+    /// 이전 실행에서 저장해 둔 노드 수 (`run` 시작 시 Ping)
+    pub fn saved_node_count(&self) -> usize {
+        self.saved_nodes.len()
+    }
+
+    pub async fn run(mut self) {
+        let mut buf = vec![0u8; 65535];
+        let mut cleanup_interval = tokio::time::interval(self.tuning.bucket_refresh_interval());
+        let mut lookup_interval = tokio::time::interval(Duration::from_millis(500));
+        let mut reannounce_interval = tokio::time::interval(REANNOUNCE_CHECK_INTERVAL);
+
+        self.ping_saved_nodes().await;
+
+        loop {
+            tokio::select! {
+                // 명령 처리
+                cmd = self.command_rx.recv() => {
+                    match cmd {
+                        Some(DhtCommand::AddBootstrapNode(addr)) => {
+                            self.bootstrap(addr).await;
+                        }
+                        Some(DhtCommand::ListNodes(reply)) => {
+                            let _ = reply.send(self.node_addresses().await);
+                        }
+                        Some(DhtCommand::Announce { info_hash, port }) => {
+                            self.start_providing(info_hash, port).await;
+                        }
+                        Some(DhtCommand::StopProviding { info_hash }) => {
+                            self.provided.remove(&info_hash);
+                            self.pending_announces.remove(&info_hash);
+                        }
+                        Some(DhtCommand::FindProviders { info_hash, reply }) => {
+                            self.start_provider_lookup(info_hash, reply).await;
+                        }
+                        Some(DhtCommand::Shutdown) | None => {
+                            info!("DHT 노드 종료");
+                            self.save_routing_table().await;
+                            self.save_providers();
+                            break;
+                        }
+                    }
+                }
+
+                // UDP 메시지 수신
+                result = self.socket.recv_from(&mut buf) => {
+                    match result {
+                        Ok((len, addr)) => {
+                            let addr = dual_stack::canonical(addr);
+                            let msg = if len <= MAX_MESSAGE_SIZE {
+                                DhtMessage::deserialize(&buf[..len])
+                            } else {
+                                None
+                            };
+                            match msg {
+                                Some(msg) => self.handle_message(msg, addr).await,
+                                None => {
+                                    debug!("🚫 잘못된 DHT 메시지 무시: {} ({} bytes)", addr, len);
+                                    self.stats.write().await.dht_messages_dropped_malformed += 1;
+                                }
+                            }
+                        }
+                        Err(e) => error!("UDP 수신 에러: {}", e),
+                    }
+                }
+
+                // 주기적 정리
+                _ = cleanup_interval.tick() => {
+                    self.cleanup_stale_data().await;
+                    self.save_routing_table().await;
+                    self.save_providers();
+                }
+
+                // 진행 중인 조회 (시간 초과 처리 및 다음 질의)
+                _ = lookup_interval.tick() => {
+                    self.drive_lookups().await;
+                }
+
+                // 제공 중인 info_hash 재광고
+                _ = reannounce_interval.tick() => {
+                    self.reannounce_due().await;
+                }
+            }
+        }
+    }
+
+    /// 이전 실행의 노드에 Ping (Pong을 받은 노드만 라우팅 테이블에 들어감)
+    async fn ping_saved_nodes(&mut self) {
+        let saved = std::mem::take(&mut self.saved_nodes);
+        if saved.is_empty() {
+            return;
+        }
+
+        info!("📂 저장된 DHT 노드 {}개에 재연결 시도", saved.len());
+        for node in &saved {
+            let msg = DhtMessage::Ping {
+                sender_id: self.node_id(AddrFamily::of(&node.addr)),
+                transaction_id: self.queries.issue(node.addr),
+            };
+            self.send_message(&msg, node.addr).await;
+        }
+    }
+
+    /// 최근 응답한 라우팅 엔트리 저장
+    async fn save_routing_table(&self) {
+        let Some(ref path) = self.state_path else {
+            return;
+        };
+
+        let mut nodes = Vec::new();
+        for bucket in self.all_buckets() {
+            nodes.extend(bucket.read().await.iter().map(|e| SavedNode {
+                node_id: e.node_id,
+                addr: e.addr,
+                last_seen: routing_store::unix_secs_ago(e.last_seen.elapsed()),
+            }));
+        }
+
+        let snapshot = RoutingSnapshot::new(self.node_id(AddrFamily::V4), nodes)
+            .with_node_id_v6(self.node_id(AddrFamily::V6));
+        if let Err(e) = snapshot.save(path) {
+            warn!("DHT 라우팅 테이블 저장 실패: {}", e);
+        }
+    }
+
+    /// 제공자 기록 파일 경로 (라우팅 테이블 파일 옆)
+    fn providers_path(state_path: &Path) -> PathBuf {
+        state_path.with_file_name(PROVIDERS_FILE)
+    }
+
+    /// 저장된 제공자 기록 복원 (TTL이 지난 기록 제외, 경과 시간은 그대로 유지)
+    fn restore_providers(
+        state_path: Option<&Path>,
+        tuning: &DhtTuning,
+    ) -> DashMap<InfoHash, Vec<ProviderInfo>> {
+        let providers: DashMap<InfoHash, Vec<ProviderInfo>> = DashMap::new();
+        let Some(state_path) = state_path else {
+            return providers;
+        };
+        let Some(snapshot) =
+            ProviderSnapshot::load(&Self::providers_path(state_path), tuning.provider_ttl())
+        else {
+            return providers;
+        };
+
+        let now = Instant::now();
+        let now_secs = crate::util::now_secs();
+        for saved in snapshot.providers {
+            if providers.len() >= MAX_PROVIDER_KEYS && !providers.contains_key(&saved.info_hash) {
+                continue;
+            }
+            let age = Duration::from_secs(now_secs.saturating_sub(saved.seen_at));
+            providers
+                .entry(saved.info_hash)
+                .or_default()
+                .push(ProviderInfo {
+                    node_id: saved.node_id,
+                    addr: saved.addr,
+                    announced_at: now.checked_sub(age).unwrap_or(now),
+                });
+        }
+        if !providers.is_empty() {
+            info!(
+                "📂 저장된 제공자 기록 복원: {}개 info_hash",
+                providers.len()
+            );
+        }
+        providers
+    }
+
+    /// 유효한 제공자 기록을 디스크에 저장 (라우팅 테이블과 함께 주기적으로, 종료 시)
+    fn save_providers(&self) {
+        let Some(ref state_path) = self.state_path else {
+            return;
+        };
+
+        let provider_ttl = self.tuning.provider_ttl();
+        let mut saved = Vec::new();
+        for entry in self.providers.iter() {
+            saved.extend(
+                entry
+                    .value()
+                    .iter()
+                    .filter(|p| p.announced_at.elapsed() < provider_ttl)
+                    .map(|p| SavedProvider {
+                        info_hash: *entry.key(),
+                        node_id: p.node_id,
+                        addr: p.addr,
+                        seen_at: routing_store::unix_secs_ago(p.announced_at.elapsed()),
+                    }),
+            );
+        }
+
+        let path = Self::providers_path(state_path);
+        if let Err(e) = ProviderSnapshot::new(saved).save(&path) {
+            warn!("DHT 제공자 기록 저장 실패: {}", e);
+        }
+    }
+
+    /// 부트스트랩 노드부터 내 ID(그 주소 체계의 ID)를 반복 조회해 가까운 노드 수집
+    async fn bootstrap(&self, addr: SocketAddr) {
+        let node_id = self.node_id(AddrFamily::of(&addr));
+        self.start_node_lookup(node_id).await;
+        if let Some(mut lookup) = self.node_lookups.get_mut(&node_id) {
+            lookup.add_address(addr);
+        }
+        self.drive_lookups().await;
+    }
+
+    /// 라우팅 테이블에서 가장 가까운 노드로 시작하는 조회 (튜닝 값 적용)
+    async fn new_lookup(&self, target: NodeId) -> Lookup {
+        let seeds = self
+            .find_closest_nodes(&target, self.tuning.replication_factor, None)
+            .await;
+        Lookup::with_params(
+            target,
+            seeds,
+            self.tuning.lookup_alpha,
+            self.tuning.replication_factor,
+            Instant::now(),
+        )
+    }
+
+    /// 노드 조회 시작 (이미 진행 중이면 유지)
+    async fn start_node_lookup(&self, target: NodeId) {
+        if !self.node_lookups.contains_key(&target) {
+            let lookup = self.new_lookup(target).await;
+            self.node_lookups.insert(target, lookup);
+        }
+    }
+
+    /// 조회별로 다음 질의를 보내고, 끝난 조회는 정리
+    ///
+    /// 제공자 조회가 끝나면 채널을 닫아 `DhtHandle::find_providers`가 바로 반환되게 합니다.
+    async fn drive_lookups(&self) {
+        let now = Instant::now();
+        let mut queries = Vec::new();
+
+        self.node_lookups.retain(|target, lookup| {
+            if lookup.is_finished(now) {
+                debug!(
+                    "✅ 노드 조회 완료: {} ({}개 노드)",
+                    hex::encode(&target[..8]),
+                    lookup.closest().len()
+                );
+                return false;
+            }
+            for addr in lookup.next_queries(now) {
+                let msg = DhtMessage::FindNode {
+                    sender_id: self.node_id(AddrFamily::of(&addr)),
+                    transaction_id: self.queries.issue(addr),
+                    target: *target,
+                };
+                queries.push((msg, addr));
+            }
+            true
+        });
+
+        self.provider_lookups.retain(|info_hash, pending| {
+            if pending.reply.is_closed() || pending.lookup.is_finished(now) {
+                debug!(
+                    "🔍 제공자 검색 완료: {} ({}개 제공자)",
+                    hex::encode(&info_hash[..8]),
+                    pending.lookup.providers().len()
+                );
+                return false;
+            }
+            for addr in pending.lookup.next_queries(now) {
+                let msg = DhtMessage::GetProviders {
+                    sender_id: self.node_id(AddrFamily::of(&addr)),
+                    transaction_id: self.queries.issue(addr),
+                    info_hash: *info_hash,
+                };
+                queries.push((msg, addr));
+            }
+            true
+        });
+
+        for (msg, addr) in queries {
+            self.send_message(&msg, addr).await;
+        }
+    }
+
+    /// 조회 후보로 쓸 노드 (자기 자신과 IP에 맞지 않는 ID 제외)
+    fn lookup_candidates(&self, nodes: &[(NodeId, SocketAddr)]) -> Vec<(NodeId, SocketAddr)> {
+        nodes
+            .iter()
+            .filter(|(id, addr)| !self.is_own_id(id) && node_ids::is_valid(id, &addr.ip()))
+            .copied()
+            .collect()
+    }
+
+    /// 제공 목록에 추가하고 바로 광고
+    async fn start_providing(&mut self, info_hash: InfoHash, port: u16) {
+        self.provided.insert(info_hash, port);
+        self.announce(info_hash, port).await;
+        let routing_size = self.routing_size().await;
+        self.provided
+            .mark_announced(&info_hash, Instant::now(), routing_size);
+    }
+
+    /// 재광고 주기가 됐거나 라우팅 테이블이 크게 바뀐 info_hash 다시 광고
+    async fn reannounce_due(&mut self) {
+        let routing_size = self.routing_size().await;
+        let due = self.provided.take_due(
+            Instant::now(),
+            routing_size,
+            self.tuning.reannounce_interval(),
+        );
+        for (info_hash, port) in due {
+            debug!("🔁 제공 재광고: {}", hex::encode(&info_hash[..8]));
+            self.announce(info_hash, port).await;
+        }
+    }
+
+    /// 가까운 노드에 GetProviders를 보내 토큰을 받고, 응답이 오면 Announce
+    async fn announce(&self, info_hash: InfoHash, port: u16) {
+        self.pending_announces
+            .insert(info_hash, (port, Instant::now()));
+
+        let nodes = self
+            .find_closest_nodes(&info_hash, self.tuning.replication_factor, None)
+            .await;
+        for (_, addr) in &nodes {
+            let msg = DhtMessage::GetProviders {
+                sender_id: self.node_id(AddrFamily::of(addr)),
+                transaction_id: self.queries.issue(*addr),
+                info_hash,
+            };
+            self.send_message(&msg, *addr).await;
+        }
+
+        info!(
+            "📢 제공 광고: {} (port {}, {} nodes)",
+            hex::encode(&info_hash[..8]),
+            port,
+            nodes.len()
+        );
+    }
+
+    async fn start_provider_lookup(
+        &self,
+        info_hash: InfoHash,
+        reply: mpsc::Sender<(NodeId, SocketAddr)>,
+    ) {
+        // 로컬에 저장된 제공자 먼저 전달
+        for provider in self.get_providers(&info_hash) {
+            let _ = reply.try_send(provider);
+        }
+
+        let lookup = self.new_lookup(info_hash).await;
+        self.provider_lookups
+            .insert(info_hash, ProviderLookup { lookup, reply });
+
+        debug!("🔍 제공자 검색 시작: {}", hex::encode(&info_hash[..8]));
+        self.drive_lookups().await;
+    }
+
+    async fn handle_message(&self, msg: DhtMessage, from: SocketAddr) {
+        let mut stats = self.stats.write().await;
+        stats.dht_messages_received += 1;
+
+        if !self.limiter.allow(from.ip(), msg.cost()) {
+            stats.dht_messages_dropped_flood += 1;
+            return;
+        }
+        if !msg.is_well_formed() {
+            debug!("🚫 형식이 잘못된 DHT 메시지 무시: {}", from);
+            stats.dht_messages_dropped_malformed += 1;
+            return;
+        }
+        drop(stats);
+
+        // 보내지 않은 질의의 응답은 공인 IP 집계와 라우팅 테이블에 반영하지 않음
+        if let Some(transaction_id) = msg.response_transaction() {
+            if !self.queries.complete(from, transaction_id) {
+                debug!("🚫 요청하지 않은 DHT 응답 무시: {}", from);
+                return;
+            }
+        }
+
+        match msg {
+            DhtMessage::Ping {
+                sender_id,
+                transaction_id,
+            } => {
+                self.add_node(sender_id, from).await;
+                let response = DhtMessage::Pong {
+                    sender_id: self.node_id(AddrFamily::of(&from)),
+                    transaction_id,
+                    requester_addr: from,
+                };
+                self.send_message(&response, from).await;
+            }
+
+            DhtMessage::Pong {
+                sender_id,
+                requester_addr,
+                ..
+            } => {
+                self.observe_external_ip(from, requester_addr).await;
+                self.add_node(sender_id, from).await;
+            }
+
+            DhtMessage::FindNode {
+                sender_id,
+                transaction_id,
+                target,
+            } => {
+                self.add_node(sender_id, from).await;
+                // 요청자가 쓰는 주소 체계의 노드만 응답
+                let nodes = self
+                    .find_closest_nodes(
+                        &target,
+                        self.tuning.replication_factor,
+                        Some(AddrFamily::of(&from)),
+                    )
+                    .await;
+                let response = DhtMessage::FindNodeResponse {
+                    sender_id: self.node_id(AddrFamily::of(&from)),
+                    transaction_id,
+                    nodes,
+                    requester_addr: from,
+                };
+                self.send_message(&response, from).await;
+            }
+
+            DhtMessage::FindNodeResponse {
+                sender_id,
+                nodes,
+                requester_addr,
+                ..
+            } => {
+                self.observe_external_ip(from, requester_addr).await;
+                self.add_node(sender_id, from).await;
+
+                let candidates = self.lookup_candidates(&nodes);
+                for mut lookup in self.node_lookups.iter_mut() {
+                    if lookup.is_waiting_for(&from) {
+                        lookup.on_response(from, sender_id, candidates.iter().copied(), []);
+                    }
+                }
+
+                for (node_id, addr) in nodes {
+                    self.add_node(node_id, addr).await;
+                }
+                self.drive_lookups().await;
+            }
+
+            DhtMessage::GetProviders {
+                sender_id,
+                transaction_id,
+                info_hash,
+            } => {
+                self.add_node(sender_id, from).await;
+
+                let providers = self.get_providers(&info_hash);
+                let nodes = self
+                    .find_closest_nodes(
+                        &info_hash,
+                        self.tuning.replication_factor,
+                        Some(AddrFamily::of(&from)),
+                    )
+                    .await;
+
+                let response = DhtMessage::GetProvidersResponse {
+                    sender_id: self.node_id(AddrFamily::of(&from)),
+                    transaction_id,
+                    info_hash,
+                    providers,
+                    nodes,
+                    token: self.tokens.issue(&from.ip()),
+                    requester_addr: from,
+                };
+                self.send_message(&response, from).await;
+            }
+
+            DhtMessage::GetProvidersResponse {
+                sender_id,
+                info_hash,
+                providers,
+                nodes,
+                token,
+                requester_addr,
+                ..
+            } => {
+                self.observe_external_ip(from, requester_addr).await;
+                self.add_node(sender_id, from).await;
+
+                // 대기 중인 광고가 있으면 받은 토큰으로 Announce
+                let pending_port = self.pending_announces.get(&info_hash).map(|p| p.0);
+                if let Some(port) = pending_port {
+                    let msg = DhtMessage::Announce {
+                        sender_id: self.node_id(AddrFamily::of(&from)),
+                        transaction_id: self.queries.issue(from),
+                        info_hash,
+                        port,
+                        token,
+                    };
+                    self.send_message(&msg, from).await;
+                }
+
+                // 진행 중인 검색에 반영, 새로 발견한 제공자는 바로 전달
+                if let Some(mut pending) = self.provider_lookups.get_mut(&info_hash) {
+                    if pending.lookup.is_waiting_for(&from) {
+                        let providers = providers
+                            .into_iter()
+                            .filter(|(id, addr)| node_ids::is_valid(id, &addr.ip()));
+                        let candidates = self.lookup_candidates(&nodes);
+                        for provider in pending
+                            .lookup
+                            .on_response(from, sender_id, candidates, providers)
+                        {
+                            // 재시작 후에도 바로 다시 연결할 수 있도록 발견한 제공자도 기록
+                            self.add_provider(info_hash, provider.0, provider.1);
+                            let _ = pending.reply.try_send(provider);
+                        }
+                    }
+                }
+
+                for (node_id, addr) in nodes {
+                    self.add_node(node_id, addr).await;
+                }
+                self.drive_lookups().await;
+            }
+
+            DhtMessage::Announce {
+                sender_id,
+                transaction_id,
+                info_hash,
+                port,
+                token,
+            } => {
+                if !self.tokens.verify(&from.ip(), &token) {
+                    warn!("🚫 유효하지 않은 토큰으로 Announce 거부: {}", from);
+                    let response = DhtMessage::AnnounceResponse {
+                        sender_id: self.node_id(AddrFamily::of(&from)),
+                        transaction_id,
+                        success: false,
+                        requester_addr: from,
+                    };
+                    self.send_message(&response, from).await;
+                    return;
+                }
+                if !node_ids::is_valid(&sender_id, &from.ip()) {
+                    warn!("🚫 노드 ID 불일치로 Announce 거부: {}", from);
+                    let response = DhtMessage::AnnounceResponse {
+                        sender_id: self.node_id(AddrFamily::of(&from)),
+                        transaction_id,
+                        success: false,
+                        requester_addr: from,
+                    };
+                    self.send_message(&response, from).await;
+                    return;
+                }
+                self.add_node(sender_id, from).await;
+
+                let provider_addr = SocketAddr::new(from.ip(), port);
+                if !self.add_provider(info_hash, sender_id, provider_addr) {
+                    self.stats.write().await.dht_messages_dropped_flood += 1;
+                    let response = DhtMessage::AnnounceResponse {
+                        sender_id: self.node_id(AddrFamily::of(&from)),
+                        transaction_id,
+                        success: false,
+                        requester_addr: from,
+                    };
+                    self.send_message(&response, from).await;
+                    return;
+                }
+
+                let mut stats = self.stats.write().await;
+                stats.providers_stored += 1;
+                drop(stats);
+
+                info!(
+                    "📢 Announce: {} provides {}",
+                    hex::encode(&sender_id[..8]),
+                    hex::encode(&info_hash[..8])
+                );
+
+                let response = DhtMessage::AnnounceResponse {
+                    sender_id: self.node_id(AddrFamily::of(&from)),
+                    transaction_id,
+                    success: true,
+                    requester_addr: from,
+                };
+                self.send_message(&response, from).await;
+            }
+
+            DhtMessage::AnnounceResponse { requester_addr, .. } => {
+                self.observe_external_ip(from, requester_addr).await;
+            }
+        }
+    }
+
+    /// 주소 체계별 현재 ID (그 주소 체계로 보내는 메시지에 사용)
+    fn node_id(&self, family: AddrFamily) -> NodeId {
+        *self.node_id_lock(family).read()
+    }
+
+    fn node_id_lock(&self, family: AddrFamily) -> &parking_lot::RwLock<NodeId> {
+        match family {
+            AddrFamily::V4 => &self.node_id_v4,
+            AddrFamily::V6 => &self.node_id_v6,
+        }
+    }
+
+    fn is_own_id(&self, id: &NodeId) -> bool {
+        *id == self.node_id(AddrFamily::V4) || *id == self.node_id(AddrFamily::V6)
+    }
+
+    /// 응답에 담긴 내 공인 주소 집계 (정족수를 채운 주소에 ID가 맞지 않으면 ID를 새로 만듦)
+    ///
+    /// 공인 주소와 같은 주소 체계의 ID만 바꾸고, 그 주소 체계의 라우팅 테이블만 다시 배치합니다.
+    async fn observe_external_ip(&self, from: SocketAddr, reported: SocketAddr) {
+        let reported = dual_stack::canonical(reported);
+        let family = AddrFamily::of(&reported);
+        let Some(external) = self.external_ips.lock().record(from.ip(), reported.ip()) else {
+            return;
+        };
+        if node_ids::is_valid(&self.node_id(family), &external) {
+            return;
+        }
+
+        let node_id = node_ids::generate(&external);
+        info!(
+            "🌐 공인 IP 확인 ({}), DHT 노드 ID 변경: {}",
+            external,
+            hex::encode(&node_id[..8])
+        );
+        *self.node_id_lock(family).write() = node_id;
+        self.rebucket(family).await;
+    }
+
+    /// ID가 바뀐 뒤 라우팅 엔트리를 새 거리 기준 버킷으로 다시 배치 (넘치는 엔트리는 버림)
+    async fn rebucket(&self, family: AddrFamily) {
+        let table = self.routing_table(family);
+        let mut entries = Vec::new();
+        for bucket in table.iter() {
+            entries.append(&mut *bucket.write().await);
+        }
+
+        let mut dropped = 0u64;
+        for entry in entries {
+            let mut bucket = table[self.bucket_index(family, &entry.node_id)]
+                .write()
+                .await;
+            if bucket.len() < self.tuning.k_bucket_size {
+                bucket.push(entry);
+            } else {
+                dropped += 1;
+            }
+        }
+
+        if dropped > 0 {
+            let mut stats = self.stats.write().await;
+            stats.nodes_in_routing_table = stats.nodes_in_routing_table.saturating_sub(dropped);
+        }
+    }
+
+    async fn add_node(&self, node_id: NodeId, addr: SocketAddr) {
+        // IP와 맞지 않는 ID는 라우팅 테이블 오염 시도로 간주
+        if !node_ids::is_valid(&node_id, &addr.ip()) {
+            debug!("🚫 IP와 맞지 않는 노드 ID 무시: {}", addr);
+            return;
+        }
+
+        let family = AddrFamily::of(&addr);
+        let table = self.routing_table(family);
+        let bucket_idx = self.bucket_index(family, &node_id);
+        if bucket_idx >= table.len() {
+            return;
+        }
+
+        let mut bucket = table[bucket_idx].write().await;
+
+        if let Some(entry) = bucket.iter_mut().find(|e| e.node_id == node_id) {
+            entry.last_seen = Instant::now();
+            entry.addr = addr;
+            return;
+        }
+
+        let entry = RoutingEntry {
+            node_id,
+            addr,
+            last_seen: Instant::now(),
+        };
+
+        if bucket.len() < self.tuning.k_bucket_size {
+            bucket.push(entry);
+            drop(bucket);
+
+            let mut stats = self.stats.write().await;
+            stats.nodes_in_routing_table += 1;
+        } else {
+            // 버킷이 가득 찼으면 오래 응답이 없는 노드만 교체 (LRU)
+            let Some(oldest) = bucket.iter_mut().min_by_key(|e| e.last_seen) else {
+                return;
+            };
+            if oldest.last_seen.elapsed() <= STALE_ENTRY_REPLACE_AFTER {
+                return;
+            }
+            *oldest = entry;
+            drop(bucket);
+        }
+
+        // 🆕 피어 발견 이벤트 발생
+        if let Some(ref tx) = self.peer_discovered_tx {
+            let event = PeerDiscoveredEvent {
+                node_id: hex::encode(&node_id[..8]),
+                address: addr.to_string(),
+                source: "dht".to_string(),
+            };
+            let _ = tx.send(event).await;
+        }
+    }
+
+    fn bucket_index(&self, family: AddrFamily, node_id: &NodeId) -> usize {
+        let own_id = self.node_id(family);
+        let mut distance = [0u8; 32];
+        for i in 0..32 {
+            distance[i] = own_id[i] ^ node_id[i];
+        }
+
+        for (i, byte) in distance.iter().enumerate() {
+            if *byte != 0 {
+                return i * 8 + byte.leading_zeros() as usize;
+            }
+        }
+        255
+    }
+
+    fn routing_table(&self, family: AddrFamily) -> &[RwLock<Vec<RoutingEntry>>] {
+        match family {
+            AddrFamily::V4 => &self.routing_v4,
+            AddrFamily::V6 => &self.routing_v6,
+        }
+    }
+
+    fn all_buckets(&self) -> impl Iterator<Item = &RwLock<Vec<RoutingEntry>>> {
+        self.routing_v4.iter().chain(self.routing_v6.iter())
+    }
+
+    /// 목표에 가까운 노드 (family가 있으면 그 주소 체계만)
+    async fn find_closest_nodes(
+        &self,
+        target: &NodeId,
+        count: usize,
+        family: Option<AddrFamily>,
+    ) -> Vec<(NodeId, SocketAddr)> {
+        let mut all_nodes = Vec::new();
+
+        let buckets: Vec<_> = match family {
+            Some(family) => self.routing_table(family).iter().collect(),
+            None => self.all_buckets().collect(),
+        };
+        for bucket in buckets {
+            let bucket = bucket.read().await;
+            for entry in bucket.iter() {
+                let mut distance = [0u8; 32];
+                for i in 0..32 {
+                    distance[i] = entry.node_id[i] ^ target[i];
+                }
+                all_nodes.push((entry.node_id, entry.addr, distance));
+            }
+        }
+
+        all_nodes.sort_by(|a, b| a.2.cmp(&b.2));
+        all_nodes
+            .into_iter()
+            .take(count)
+            .map(|(id, addr, _)| (id, addr))
+            .collect()
+    }
+
+    async fn routing_size(&self) -> usize {
+        let mut size = 0;
+        for bucket in self.all_buckets() {
+            size += bucket.read().await.len();
+        }
+        size
+    }
+
+    async fn node_addresses(&self) -> Vec<SocketAddr> {
+        let mut addrs = Vec::new();
+        for bucket in self.all_buckets() {
+            addrs.extend(bucket.read().await.iter().map(|e| e.addr));
+        }
+        addrs
+    }
+
+    /// 제공자 저장 (info_hash 수 상한을 넘는 새 항목이면 false)
+    fn add_provider(&self, info_hash: InfoHash, node_id: NodeId, addr: SocketAddr) -> bool {
+        if !self.providers.contains_key(&info_hash) && self.providers.len() >= MAX_PROVIDER_KEYS {
+            warn!("제공자 저장 한도 초과, Announce 무시: {}", addr);
+            return false;
+        }
+
+        let mut providers = self.providers.entry(info_hash).or_insert_with(Vec::new);
+
+        providers.retain(|p| p.node_id != node_id);
+
+        providers.push(ProviderInfo {
+            node_id,
+            addr,
+            announced_at: Instant::now(),
+        });
+
+        if providers.len() > 100 {
+            providers.sort_by_key(|p| std::cmp::Reverse(p.announced_at));
+            providers.truncate(100);
+        }
+        true
+    }
+
+    fn get_providers(&self, info_hash: &InfoHash) -> Vec<(NodeId, SocketAddr)> {
+        self.providers
+            .get(info_hash)
+            .map(|providers| {
+                providers
+                    .iter()
+                    .filter(|p| p.announced_at.elapsed() < self.tuning.provider_ttl())
+                    .map(|p| (p.node_id, p.addr))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    async fn send_message(&self, msg: &DhtMessage, to: SocketAddr) {
+        let data = msg.serialize();
+        let target = dual_stack::for_socket(to, self.socket_is_v6);
+        if let Err(e) = self.socket.send_to(&data, target).await {
+            warn!("메시지 전송 실패: {} - {}", to, e);
+        } else {
+            let mut stats = self.stats.write().await;
+            stats.dht_messages_sent += 1;
+        }
+    }
+
+    async fn cleanup_stale_data(&self) {
+        // 오래된 라우팅 엔트리 제거
+        let mut removed_count = 0;
+        for bucket in self.all_buckets() {
+            let mut bucket = bucket.write().await;
+            let before = bucket.len();
+            bucket.retain(|e| e.last_seen.elapsed() < Duration::from_secs(900));
+            removed_count += before - bucket.len();
+        }
+
+        if removed_count > 0 {
+            let mut stats = self.stats.write().await;
+            stats.nodes_in_routing_table = stats
+                .nodes_in_routing_table
+                .saturating_sub(removed_count as u64);
+        }
+
+        // 오래된 제공자 제거
+        let provider_ttl = self.tuning.provider_ttl();
+        self.providers.retain(|_, providers| {
+            providers.retain(|p| p.announced_at.elapsed() < provider_ttl);
+            !providers.is_empty()
+        });
+
+        self.limiter.prune();
+        self.queries.cleanup();
+
+        // 종료된 제공자 검색 제거
+        self.provider_lookups
+            .retain(|_, pending| !pending.reply.is_closed());
+
+        // 토큰 응답을 기다리던 광고 정리
+        self.pending_announces
+            .retain(|_, (_, started)| started.elapsed() < PENDING_ANNOUNCE_TTL);
+
+        debug!("🧹 DHT 정리 완료");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pong(transaction_id: TransactionId, requester_addr: SocketAddr) -> DhtMessage {
+        DhtMessage::Pong {
+            sender_id: [0u8; 32],
+            transaction_id,
+            requester_addr,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unsolicited_pong_does_not_change_node_id() {
+        let stats = Arc::new(RwLock::new(StatsCollector::new()));
+        let node = DhtNode::new(0, stats, None, None, DhtTuning::default())
+            .await
+            .unwrap();
+        let original = node.node_id(AddrFamily::V4);
+        let reported: SocketAddr = "203.0.113.7:6881".parse().unwrap();
+        let reporters: Vec<SocketAddr> = (1..=3)
+            .map(|i| format!("198.51.100.{}:6881", i).parse().unwrap())
+            .collect();
+
+        // 보내지 않은 질의의 응답은 정족수를 채워도 무시
+        for from in &reporters {
+            node.handle_message(pong(rand::random(), reported), *from)
+                .await;
+        }
+        assert_eq!(node.node_id(AddrFamily::V4), original);
+
+        // 다른 주소로 보낸 질의의 ID를 돌려줘도 무시
+        let elsewhere = node.queries.issue("198.51.100.9:6881".parse().unwrap());
+        node.handle_message(pong(elsewhere, reported), reporters[0])
+            .await;
+        assert_eq!(node.node_id(AddrFamily::V4), original);
+
+        // 직접 보낸 Ping의 응답만 집계
+        for from in &reporters {
+            let transaction_id = node.queries.issue(*from);
+            node.handle_message(pong(transaction_id, reported), *from)
+                .await;
+        }
+        let changed = node.node_id(AddrFamily::V4);
+        assert_ne!(changed, original);
+        assert!(node_ids::is_valid(&changed, &reported.ip()));
+    }
+}
@@ -0,0 +1,276 @@
+//! IP 기반 DHT 노드 ID (BEP42 방식)
+//!
+//! 노드 ID의 앞 3바이트를 `SHA-256(마스킹된 IP || nonce)`에서 유도하고 마지막 바이트에
+//! nonce를 넣습니다. 수신 측은 패킷의 출발지 IP로 같은 값을 다시 계산해 검증하므로,
+//! 한 호스트가 임의의 ID를 골라 특정 info_hash 근처 라우팅 테이블을 장악할 수 없습니다.
+//! 마스크는 BEP42와 동일하게 IP 하위 비트 일부만 사용해 같은 대역의 호스트가 ID를
+//! 무한히 바꿔 만들지 못하게 합니다.
+//!
+//! NAT 뒤의 노드는 자기 공인 IP를 모르므로, 응답마다 상대가 본 출발지 주소(BEP42 `ip`)를 받아
+//! 여러 노드가 같은 주소를 알려 오면(`ExternalIpVotes`) 그 주소로 ID를 다시 만듭니다.
+//! 루프백/링크 로컬 대역만 검증하지 않으며, 공인 IP 집계에는 직접 보낸 질의의 응답 중
+//! 공인 대역 노드가 알린 공인 주소만 씁니다.
+//!
+//! 듀얼 스택 호스트는 IPv4와 IPv6 출발지가 다르므로 주소 체계마다 ID를 따로 두고,
+//! 메시지를 보내는 주소 체계의 ID를 사용합니다.
+
+use super::dual_stack::AddrFamily;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
+
+/// IP에서 유도되는 노드 ID 접두사 길이
+const PREFIX_LEN: usize = 3;
+
+const V4_MASK: [u8; 4] = [0x03, 0x0f, 0x3f, 0xff];
+const V6_MASK: [u8; 8] = [0x01, 0x03, 0x07, 0x0f, 0x1f, 0x3f, 0x7f, 0xff];
+
+fn id_prefix(ip: &IpAddr, nonce: u8) -> [u8; PREFIX_LEN] {
+    let mut hasher = Sha256::new();
+    match ip {
+        IpAddr::V4(v4) => {
+            let masked: Vec<u8> = v4
+                .octets()
+                .iter()
+                .zip(V4_MASK)
+                .map(|(b, m)| b & m)
+                .collect();
+            hasher.update(&masked);
+        }
+        IpAddr::V6(v6) => {
+            let masked: Vec<u8> = v6
+                .octets()
+                .iter()
+                .zip(V6_MASK)
+                .map(|(b, m)| b & m)
+                .collect();
+            hasher.update(&masked);
+        }
+    }
+    hasher.update([nonce]);
+
+    let digest = hasher.finalize();
+    let mut prefix = [0u8; PREFIX_LEN];
+    prefix.copy_from_slice(&digest[..PREFIX_LEN]);
+    prefix
+}
+
+/// 공인 IP로 ID를 다시 만들기 위해 같은 주소를 알려 와야 하는 노드 수 (서로 다른 IP)
+const EXTERNAL_IP_QUORUM: usize = 3;
+
+/// 집계하는 주소 후보 수 상한 (넘으면 처음부터 다시 집계)
+const MAX_EXTERNAL_IP_CANDIDATES: usize = 16;
+
+/// 검증 대상이 아닌 주소 (루프백, 링크 로컬 대역)
+///
+/// 사설/CGNAT/ULA 대역은 여러 호스트가 함께 쓰는 망이라 오히려 ID 검증이 필요하므로 제외하지 않습니다.
+fn is_exempt(ip: &IpAddr) -> bool {
+    if ip.is_loopback() || ip.is_unspecified() {
+        return true;
+    }
+    match ip {
+        IpAddr::V4(v4) => v4.is_link_local(), // 169.254/16
+        IpAddr::V6(v6) => (v6.segments()[0] & 0xffc0) == 0xfe80, // fe80::/10
+    }
+}
+
+/// 공인 IP 집계에서 제외하는 주소 (검증 제외 대역과 사설/CGNAT/ULA 대역)
+///
+/// 같은 LAN(또는 통신사 NAT) 안의 노드가 알려 온 주소는 공인 IP를 알려 주지 못합니다.
+fn is_local(ip: &IpAddr) -> bool {
+    if is_exempt(ip) {
+        return true;
+    }
+    match ip {
+        IpAddr::V4(v4) => {
+            let [a, b, ..] = v4.octets();
+            v4.is_private() // 10/8, 172.16/12, 192.168/16
+                || (a == 100 && (b & 0xc0) == 64) // 100.64/10 (CGNAT)
+        }
+        IpAddr::V6(v6) => (v6.segments()[0] & 0xfe00) == 0xfc00, // fc00::/7 (ULA)
+    }
+}
+
+/// IP에 대응하는 새 노드 ID 생성 (나머지 바이트는 랜덤)
+///
+/// 만든 ID는 같은 주소 체계의 출발지에서만 검증을 통과합니다. IPv4 주소로 만든 ID를
+/// IPv6로 보내면 상대가 거부하므로, 호출 측은 주소 체계별로 ID를 따로 만들어야 합니다.
+pub fn generate(ip: &IpAddr) -> [u8; 32] {
+    let mut id = [0u8; 32];
+    rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut id);
+    let nonce = id[31];
+    id[..PREFIX_LEN].copy_from_slice(&id_prefix(ip, nonce));
+    id
+}
+
+/// 노드 ID가 출발지 IP에서 유도된 것인지 검증
+pub fn is_valid(id: &[u8; 32], ip: &IpAddr) -> bool {
+    if is_exempt(ip) {
+        return true;
+    }
+    id[..PREFIX_LEN] == id_prefix(ip, id[31])
+}
+
+/// 응답으로 받은 공인 IP 집계 (BEP42 `ip`)
+///
+/// 한 노드가 거짓 주소를 알려도 ID가 바뀌지 않도록, 서로 다른 IP의 노드
+/// `EXTERNAL_IP_QUORUM`개가 같은 주소를 알려 와야 그 주소를 공인 IP로 인정합니다.
+#[derive(Debug, Default)]
+pub struct ExternalIpVotes {
+    votes: HashMap<IpAddr, HashSet<IpAddr>>,
+}
+
+impl ExternalIpVotes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `reporter`가 알려 온 내 주소 기록 (정족수를 채운 주소면 반환)
+    ///
+    /// 호출 측은 직접 보낸 질의의 응답에서만 기록해야 합니다. 사설 대역의 노드가 알린 주소나
+    /// 사설 주소는 무시합니다.
+    pub fn record(&mut self, reporter: IpAddr, reported: IpAddr) -> Option<IpAddr> {
+        if is_local(&reporter) || is_local(&reported) {
+            return None;
+        }
+        if !self.votes.contains_key(&reported) && self.votes.len() >= MAX_EXTERNAL_IP_CANDIDATES {
+            self.votes.clear();
+        }
+        let reporters = self.votes.entry(reported).or_default();
+        reporters.insert(reporter);
+        (reporters.len() >= EXTERNAL_IP_QUORUM).then_some(reported)
+    }
+}
+
+/// 주소 체계별로 외부로 나가는 기본 로컬 IP (UDP 프로브, 실제 패킷은 보내지 않음)
+///
+/// 그 주소 체계의 경로가 없으면 (IPv4 전용/IPv6 전용 망) None입니다.
+pub fn detect_local_ip(family: AddrFamily) -> Option<IpAddr> {
+    match family {
+        AddrFamily::V4 => probe("0.0.0.0:0", "1.1.1.1:80"),
+        AddrFamily::V6 => probe("[::]:0", "[2606:4700:4700::1111]:80"),
+    }
+}
+
+fn probe(bind: &str, target: &str) -> Option<IpAddr> {
+    let socket = std::net::UdpSocket::bind(bind).ok()?;
+    socket.connect(target).ok()?;
+    let ip = socket.local_addr().ok()?.ip();
+    if ip.is_loopback() {
+        None
+    } else {
+        Some(ip)
+    }
+}
+
+/// 저장된 ID가 현재 IP에 맞으면 유지, 아니면 새로 생성
+///
+/// 로컬 IP를 알 수 없으면 (오프라인 등) 저장된 ID 또는 랜덤 ID를 사용합니다.
+/// NAT 뒤(사설 IP)에서는 LAN 주소에 맞는 ID로 시작하고, 공인 IP는 실행 중 `ExternalIpVotes`로 확인합니다.
+pub fn for_local_ip(saved: Option<[u8; 32]>, local_ip: Option<IpAddr>) -> [u8; 32] {
+    match (saved, local_ip) {
+        (Some(id), Some(ip)) if is_valid(&id, &ip) => id,
+        (_, Some(ip)) => generate(&ip),
+        (Some(id), None) => id,
+        (None, None) => {
+            let mut id = [0u8; 32];
+            rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut id);
+            id
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_id_matches_only_its_ip() {
+        let ip: IpAddr = "198.51.100.21".parse().unwrap();
+        let other: IpAddr = "198.51.100.22".parse().unwrap();
+        let id = generate(&ip);
+
+        assert!(is_valid(&id, &ip));
+        assert!(!is_valid(&id, &other));
+
+        let mut tampered = id;
+        tampered[0] ^= 0xff;
+        assert!(!is_valid(&tampered, &ip));
+    }
+
+    #[test]
+    fn test_ipv6_and_exempt_ranges() {
+        let ip: IpAddr = "2001:db8::1234".parse().unwrap();
+        assert!(is_valid(&generate(&ip), &ip));
+        assert!(!is_valid(
+            &generate(&ip),
+            &"2001:db8:ffff::1".parse().unwrap()
+        ));
+
+        // 루프백, 링크 로컬 대역만 검증 제외
+        for exempt in ["127.0.0.1", "169.254.1.1", "::1", "fe80::1"] {
+            assert!(is_exempt(&exempt.parse().unwrap()), "{}", exempt);
+        }
+        // 사설/CGNAT/ULA 대역은 검증하지만 공인 IP 집계에서는 제외
+        for shared in [
+            "10.1.2.3",
+            "172.16.0.1",
+            "172.31.255.254",
+            "192.168.0.10",
+            "100.64.0.1",
+            "100.127.255.254",
+            "fd00::1",
+        ] {
+            let ip: IpAddr = shared.parse().unwrap();
+            assert!(!is_exempt(&ip), "{}", shared);
+            assert!(is_local(&ip), "{}", shared);
+            assert!(!is_valid(&generate(&"192.168.0.11".parse().unwrap()), &ip));
+        }
+        for public in ["172.32.0.1", "100.128.0.1", "8.8.8.8", "2001:db8::1"] {
+            assert!(!is_local(&public.parse().unwrap()), "{}", public);
+        }
+    }
+
+    #[test]
+    fn test_external_ip_needs_quorum() {
+        let external: IpAddr = "203.0.113.7".parse().unwrap();
+        let mut votes = ExternalIpVotes::new();
+
+        // 같은 노드가 여러 번 알려도 한 표, 사설 주소와 사설 대역 노드의 표는 무시
+        for _ in 0..5 {
+            assert_eq!(
+                votes.record("198.51.100.1".parse().unwrap(), external),
+                None
+            );
+        }
+        assert_eq!(
+            votes.record(
+                "198.51.100.2".parse().unwrap(),
+                "192.168.0.2".parse().unwrap()
+            ),
+            None
+        );
+        assert_eq!(
+            votes.record("198.51.100.2".parse().unwrap(), external),
+            None
+        );
+        for reporter in ["10.0.0.2", "10.0.0.3", "fd00::2"] {
+            assert_eq!(votes.record(reporter.parse().unwrap(), external), None);
+        }
+        assert_eq!(
+            votes.record("198.51.100.3".parse().unwrap(), external),
+            Some(external)
+        );
+    }
+
+    #[test]
+    fn test_for_local_ip_regenerates_on_ip_change() {
+        let old_ip: IpAddr = "198.51.100.3".parse().unwrap();
+        let new_ip: IpAddr = "203.0.113.9".parse().unwrap();
+        let saved = generate(&old_ip);
+
+        assert_eq!(for_local_ip(Some(saved), Some(old_ip)), saved);
+        let regenerated = for_local_ip(Some(saved), Some(new_ip));
+        assert!(is_valid(&regenerated, &new_ip));
+    }
+}
@@ -0,0 +1,119 @@
+//! DHT 제공자 기록 저장/복원
+//!
+//! 제공자 목록은 메모리에만 있어 다운로드 도중 재시작하면 DHT가 다시 채워질 때까지
+//! 시드를 찾지 못합니다. 최근 제공자 기록을 시각과 함께 디스크에 남겨 두고, 재시작 시
+//! 바로 제공자 목록에 복원해 알고 있던 시드에 곧장 다시 연결할 수 있게 합니다.
+
+use super::routing_store::hex_id;
+use crate::util::now_secs;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// 제공자 기록 파일명 (라우팅 테이블 파일과 같은 디렉토리)
+pub const PROVIDERS_FILE: &str = "dht_providers.json";
+
+/// 저장할 최대 제공자 기록 수
+pub const MAX_SAVED_PROVIDERS: usize = 4096;
+
+/// 저장된 제공자
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SavedProvider {
+    #[serde(with = "hex_id")]
+    pub info_hash: [u8; 32],
+    #[serde(with = "hex_id")]
+    pub node_id: [u8; 32],
+    pub addr: SocketAddr,
+    /// 마지막으로 광고/발견된 시각 (Unix epoch 초)
+    pub seen_at: u64,
+}
+
+/// 제공자 기록 스냅샷
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderSnapshot {
+    pub providers: Vec<SavedProvider>,
+    pub saved_at: u64,
+}
+
+impl ProviderSnapshot {
+    /// 스냅샷 생성 (최근 순으로 MAX_SAVED_PROVIDERS개까지, info_hash/노드 쌍 중복 제거)
+    pub fn new(mut providers: Vec<SavedProvider>) -> Self {
+        providers.sort_by_key(|p| std::cmp::Reverse(p.seen_at));
+        let mut seen = HashSet::new();
+        providers.retain(|p| seen.insert((p.info_hash, p.node_id)));
+        providers.truncate(MAX_SAVED_PROVIDERS);
+        Self {
+            providers,
+            saved_at: now_secs(),
+        }
+    }
+
+    /// 디스크에서 로드 (없거나 손상되었으면 None, `max_age`보다 오래된 기록은 제외)
+    pub fn load(path: &Path, max_age: Duration) -> Option<Self> {
+        let data = std::fs::read(path).ok()?;
+        let mut snapshot: Self = match serde_json::from_slice(&data) {
+            Ok(s) => s,
+            Err(e) => {
+                warn!("DHT 제공자 기록 파싱 실패: {}", e);
+                return None;
+            }
+        };
+
+        let cutoff = now_secs().saturating_sub(max_age.as_secs());
+        snapshot.providers.retain(|p| p.seen_at >= cutoff);
+        debug!("📂 DHT 제공자 기록 로드: {}개", snapshot.providers.len());
+        Some(snapshot)
+    }
+
+    /// 디스크에 저장 (임시 파일에 쓴 뒤 rename)
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(self)?)?;
+        std::fs::rename(&tmp, path)?;
+        debug!("💾 DHT 제공자 기록 저장: {}개", self.providers.len());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider(hash: u8, id: u8, seen_at: u64) -> SavedProvider {
+        SavedProvider {
+            info_hash: [hash; 32],
+            node_id: [id; 32],
+            addr: format!("10.0.0.{}:7000", id).parse().unwrap(),
+            seen_at,
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_drops_expired_providers() {
+        let dir = std::env::temp_dir().join(format!("ponswarp-dht-{}", rand::random::<u64>()));
+        let path = dir.join(PROVIDERS_FILE);
+
+        let now = now_secs();
+        let ttl = Duration::from_secs(3600);
+        let snapshot = ProviderSnapshot::new(vec![
+            provider(1, 1, now),
+            provider(1, 2, now - ttl.as_secs() - 60),
+            // 같은 info_hash/노드의 오래된 기록은 최신 기록만 남김
+            provider(1, 1, now - 10),
+        ]);
+        snapshot.save(&path).unwrap();
+
+        let loaded = ProviderSnapshot::load(&path, ttl).unwrap();
+        assert_eq!(loaded.providers, vec![provider(1, 1, now)]);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
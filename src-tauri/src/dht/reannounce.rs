@@ -0,0 +1,132 @@
+//! 제공 중인 info_hash 재광고 스케줄
+//!
+//! 다른 노드에 저장된 제공자 정보는 provider TTL이 지나면 사라지므로, 제공 중인
+//! info_hash는 TTL의 절반마다 다시 광고합니다. 마지막 광고 이후 라우팅 테이블이 두 배
+//! 이상 커졌으면 (빈 테이블에서 첫 노드를 만난 경우 포함) 더 가까운 노드가 생겼을 수
+//! 있으므로 주기를 기다리지 않고 다시 광고합니다.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// 재광고 대상 확인 주기
+pub const REANNOUNCE_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+type InfoHash = [u8; 32];
+
+struct Provided {
+    port: u16,
+    /// 마지막 광고 시각과 그때의 라우팅 테이블 노드 수
+    announced: Option<(Instant, usize)>,
+}
+
+/// 제공 중인 info_hash 목록
+#[derive(Default)]
+pub struct ProvidedSet {
+    entries: HashMap<InfoHash, Provided>,
+}
+
+impl ProvidedSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 제공 시작 (이미 있으면 포트만 갱신)
+    pub fn insert(&mut self, info_hash: InfoHash, port: u16) {
+        self.entries
+            .entry(info_hash)
+            .and_modify(|p| p.port = port)
+            .or_insert(Provided {
+                port,
+                announced: None,
+            });
+    }
+
+    /// 제공 중지
+    pub fn remove(&mut self, info_hash: &InfoHash) -> bool {
+        self.entries.remove(info_hash).is_some()
+    }
+
+    pub fn contains(&self, info_hash: &InfoHash) -> bool {
+        self.entries.contains_key(info_hash)
+    }
+
+    /// 광고를 보냈음을 기록
+    pub fn mark_announced(&mut self, info_hash: &InfoHash, now: Instant, routing_size: usize) {
+        if let Some(p) = self.entries.get_mut(info_hash) {
+            p.announced = Some((now, routing_size));
+        }
+    }
+
+    /// 다시 광고해야 하는 (info_hash, port) 목록 (반환한 항목은 광고한 것으로 기록)
+    pub fn take_due(
+        &mut self,
+        now: Instant,
+        routing_size: usize,
+        interval: Duration,
+    ) -> Vec<(InfoHash, u16)> {
+        if routing_size == 0 {
+            // 광고할 노드가 없음
+            return Vec::new();
+        }
+
+        let mut due = Vec::new();
+        for (info_hash, p) in &mut self.entries {
+            let is_due = match p.announced {
+                None => true,
+                Some((at, size)) => {
+                    now.saturating_duration_since(at) >= interval
+                        || routing_size >= size.saturating_mul(2).max(1)
+                }
+            };
+            if is_due {
+                p.announced = Some((now, routing_size));
+                due.push((*info_hash, p.port));
+            }
+        }
+        due
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INTERVAL: Duration = Duration::from_secs(1800);
+
+    #[test]
+    fn test_reannounce_after_interval() {
+        let now = Instant::now();
+        let mut set = ProvidedSet::new();
+        set.insert([1u8; 32], 7000);
+        set.mark_announced(&[1u8; 32], now, 10);
+
+        assert!(set.take_due(now, 10, INTERVAL).is_empty());
+        assert!(set.take_due(now + INTERVAL / 2, 12, INTERVAL).is_empty());
+        assert_eq!(
+            set.take_due(now + INTERVAL, 12, INTERVAL),
+            vec![([1u8; 32], 7000)]
+        );
+
+        // 방금 광고했으므로 다시 대상 아님
+        assert!(set.take_due(now + INTERVAL, 12, INTERVAL).is_empty());
+
+        set.remove(&[1u8; 32]);
+        assert!(set.take_due(now + INTERVAL * 3, 12, INTERVAL).is_empty());
+    }
+
+    #[test]
+    fn test_reannounce_when_routing_table_grows() {
+        let now = Instant::now();
+        let mut set = ProvidedSet::new();
+        set.insert([2u8; 32], 7001);
+
+        // 빈 라우팅 테이블에서 광고한 경우 첫 노드가 생기면 바로 재광고
+        set.mark_announced(&[2u8; 32], now, 0);
+        assert!(set.take_due(now, 0, INTERVAL).is_empty());
+        assert_eq!(set.take_due(now, 1, INTERVAL).len(), 1);
+
+        // 두 배 이상 커지기 전까지는 대기
+        assert!(set.take_due(now, 1, INTERVAL).is_empty());
+        assert_eq!(set.take_due(now, 2, INTERVAL).len(), 1);
+    }
+}
@@ -0,0 +1,189 @@
+//! DHT 라우팅 테이블 저장/복원
+//!
+//! 종료 시(및 주기적으로) 최근 응답한 노드 목록과 노드 ID를 디스크에 기록하고,
+//! 재시작 시 저장된 노드에 Ping을 보내 응답한 노드부터 라우팅 테이블을 다시 채웁니다.
+
+use crate::util::now_secs;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// 내장 부트스트랩 DHT 라우팅 테이블 파일명
+pub const ROUTING_TABLE_FILE: &str = "dht_routing.json";
+
+/// 저장할 최대 노드 수
+pub const MAX_SAVED_NODES: usize = 256;
+
+/// 이보다 오래 전에 마지막으로 응답한 노드는 복원하지 않음
+const MAX_NODE_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// 저장된 노드
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SavedNode {
+    #[serde(with = "hex_id")]
+    pub node_id: [u8; 32],
+    pub addr: SocketAddr,
+    /// 마지막 응답 시각 (Unix epoch 초)
+    pub last_seen: u64,
+}
+
+/// 라우팅 테이블 스냅샷
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoutingSnapshot {
+    /// 재시작 후에도 같은 노드 ID를 유지해 기존 이웃의 라우팅 정보를 살림 (IPv4 ID)
+    #[serde(with = "hex_id")]
+    pub node_id: [u8; 32],
+    /// IPv6 노드 ID (이전 버전 스냅샷에는 없음)
+    #[serde(default, with = "hex_id_opt", skip_serializing_if = "Option::is_none")]
+    pub node_id_v6: Option<[u8; 32]>,
+    pub nodes: Vec<SavedNode>,
+    pub saved_at: u64,
+}
+
+impl RoutingSnapshot {
+    /// 스냅샷 생성 (최근 응답 순으로 MAX_SAVED_NODES개까지)
+    pub fn new(node_id: [u8; 32], mut nodes: Vec<SavedNode>) -> Self {
+        nodes.sort_by_key(|n| std::cmp::Reverse(n.last_seen));
+        let mut seen = HashSet::new();
+        nodes.retain(|n| seen.insert(n.node_id));
+        nodes.truncate(MAX_SAVED_NODES);
+        Self {
+            node_id,
+            node_id_v6: None,
+            nodes,
+            saved_at: now_secs(),
+        }
+    }
+
+    /// IPv6 노드 ID 함께 저장
+    pub fn with_node_id_v6(mut self, node_id_v6: [u8; 32]) -> Self {
+        self.node_id_v6 = Some(node_id_v6);
+        self
+    }
+
+    /// 디스크에서 로드 (없거나 손상되었으면 None, 오래된 노드는 제외)
+    pub fn load(path: &Path) -> Option<Self> {
+        let data = std::fs::read(path).ok()?;
+        let mut snapshot: Self = match serde_json::from_slice(&data) {
+            Ok(s) => s,
+            Err(e) => {
+                warn!("DHT 라우팅 테이블 파싱 실패: {}", e);
+                return None;
+            }
+        };
+
+        let cutoff = now_secs().saturating_sub(MAX_NODE_AGE.as_secs());
+        snapshot.nodes.retain(|n| n.last_seen >= cutoff);
+        debug!("📂 DHT 라우팅 테이블 로드: {}개 노드", snapshot.nodes.len());
+        Some(snapshot)
+    }
+
+    /// 디스크에 저장 (임시 파일에 쓴 뒤 rename)
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(self)?)?;
+        std::fs::rename(&tmp, path)?;
+        debug!("💾 DHT 라우팅 테이블 저장: {}개 노드", self.nodes.len());
+        Ok(())
+    }
+}
+
+/// 경과 시간을 Unix epoch 초로 환산 (Instant -> 저장용 시각)
+pub fn unix_secs_ago(elapsed: Duration) -> u64 {
+    now_secs().saturating_sub(elapsed.as_secs())
+}
+
+pub(super) mod hex_id {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(id: &[u8; 32], s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&hex::encode(id))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<[u8; 32], D::Error> {
+        let s = String::deserialize(d)?;
+        let bytes = hex::decode(&s).map_err(serde::de::Error::custom)?;
+        bytes
+            .try_into()
+            .map_err(|_| serde::de::Error::custom("node id must be 32 bytes"))
+    }
+}
+
+mod hex_id_opt {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(id: &Option<[u8; 32]>, s: S) -> Result<S::Ok, S::Error> {
+        match id {
+            Some(id) => super::hex_id::serialize(id, s),
+            None => s.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Option<[u8; 32]>, D::Error> {
+        #[derive(Deserialize)]
+        struct Id(#[serde(with = "super::hex_id")] [u8; 32]);
+
+        Ok(Option::<Id>::deserialize(d)?.map(|Id(id)| id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: u8, last_seen: u64) -> SavedNode {
+        SavedNode {
+            node_id: [id; 32],
+            addr: format!("10.0.0.{}:6881", id).parse().unwrap(),
+            last_seen,
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_drops_stale_nodes() {
+        let dir = std::env::temp_dir().join(format!("ponswarp-dht-{}", rand::random::<u64>()));
+        let path = dir.join(ROUTING_TABLE_FILE);
+
+        let now = now_secs();
+        let stale = now - MAX_NODE_AGE.as_secs() - 60;
+        let snapshot = RoutingSnapshot::new([7u8; 32], vec![node(1, now), node(2, stale)])
+            .with_node_id_v6([9u8; 32]);
+        snapshot.save(&path).unwrap();
+
+        let loaded = RoutingSnapshot::load(&path).unwrap();
+        assert_eq!(loaded.node_id, [7u8; 32]);
+        assert_eq!(loaded.node_id_v6, Some([9u8; 32]));
+        assert_eq!(loaded.nodes, vec![node(1, now)]);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_loads_snapshot_without_v6_id() {
+        let json = format!(
+            r#"{{"nodeId":"{}","nodes":[],"savedAt":0}}"#,
+            hex::encode([7u8; 32])
+        );
+        let snapshot: RoutingSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(snapshot.node_id_v6, None);
+    }
+
+    #[test]
+    fn test_keeps_most_recent_nodes() {
+        let nodes = (0..=255u8)
+            .chain(0..10)
+            .map(|i| node(i, i as u64))
+            .collect();
+        let snapshot = RoutingSnapshot::new([0u8; 32], nodes);
+        assert_eq!(snapshot.nodes.len(), MAX_SAVED_NODES);
+        assert_eq!(snapshot.nodes[0].last_seen, 255);
+    }
+}
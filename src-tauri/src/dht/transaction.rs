@@ -0,0 +1,106 @@
+//! DHT 질의 트랜잭션 ID
+//!
+//! 질의마다 임의의 트랜잭션 ID를 붙여 보내고, 응답은 같은 주소로 보낸 진행 중인 질의의 ID를
+//! 돌려줄 때만 받아들입니다. 출발지를 위조해 보낸 요청하지 않은 응답으로는 공인 IP 집계나
+//! 라우팅 테이블을 바꿀 수 없습니다. 응답 하나는 질의 하나만 완료합니다.
+
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+/// 질의 트랜잭션 ID
+pub type TransactionId = u32;
+
+/// 응답을 기다리는 시간
+pub const TRANSACTION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 동시에 기다리는 질의 수 상한 (넘으면 가장 오래된 질의부터 버림)
+const MAX_PENDING_QUERIES: usize = 4096;
+
+/// 진행 중인 질의 (주소, 트랜잭션 ID → 보낸 시각)
+#[derive(Default)]
+pub struct PendingQueries {
+    pending: Mutex<HashMap<(SocketAddr, TransactionId), Instant>>,
+}
+
+impl PendingQueries {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `to`로 보낼 질의의 트랜잭션 ID 발급
+    pub fn issue(&self, to: SocketAddr) -> TransactionId {
+        let now = Instant::now();
+        let mut pending = self.pending.lock();
+        if pending.len() >= MAX_PENDING_QUERIES {
+            pending.retain(|_, sent| now.saturating_duration_since(*sent) < TRANSACTION_TIMEOUT);
+        }
+        if pending.len() >= MAX_PENDING_QUERIES {
+            if let Some(oldest) = pending
+                .iter()
+                .min_by_key(|(_, sent)| **sent)
+                .map(|(k, _)| *k)
+            {
+                pending.remove(&oldest);
+            }
+        }
+
+        let mut id = rand::random::<TransactionId>();
+        while pending.contains_key(&(to, id)) {
+            id = rand::random();
+        }
+        pending.insert((to, id), now);
+        id
+    }
+
+    /// `from`에서 온 응답이 기다리던 질의의 것인지 확인 (맞으면 질의 완료)
+    pub fn complete(&self, from: SocketAddr, id: TransactionId) -> bool {
+        match self.pending.lock().remove(&(from, id)) {
+            Some(sent) => sent.elapsed() < TRANSACTION_TIMEOUT,
+            None => false,
+        }
+    }
+
+    /// 시간이 지난 질의 정리
+    pub fn cleanup(&self) {
+        self.pending
+            .lock()
+            .retain(|_, sent| sent.elapsed() < TRANSACTION_TIMEOUT);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_response_must_match_query() {
+        let queries = PendingQueries::new();
+        let addr: SocketAddr = "198.51.100.1:6881".parse().unwrap();
+        let other: SocketAddr = "198.51.100.2:6881".parse().unwrap();
+
+        // 보내지 않은 질의, 다른 주소, 다른 ID의 응답은 거부
+        assert!(!queries.complete(addr, 7));
+        let id = queries.issue(addr);
+        assert!(!queries.complete(other, id));
+        assert!(!queries.complete(addr, id.wrapping_add(1)));
+
+        // 응답 하나는 한 번만 받아들임
+        assert!(queries.complete(addr, id));
+        assert!(!queries.complete(addr, id));
+    }
+
+    #[test]
+    fn test_pending_queries_are_bounded() {
+        let queries = PendingQueries::new();
+        let addr: SocketAddr = "198.51.100.1:6881".parse().unwrap();
+        let first = queries.issue(addr);
+        *queries.pending.lock().get_mut(&(addr, first)).unwrap() -= Duration::from_secs(1);
+        for _ in 0..MAX_PENDING_QUERIES {
+            queries.issue(addr);
+        }
+        assert_eq!(queries.pending.lock().len(), MAX_PENDING_QUERIES);
+        assert!(!queries.complete(addr, first));
+    }
+}
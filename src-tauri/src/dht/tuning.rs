@@ -0,0 +1,67 @@
+//! DHT 튜닝 값 (`BootstrapConfig.dht`로 설정)
+
+use super::lookup::{LOOKUP_ALPHA, LOOKUP_K};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// DHT 튜닝 값 (대규모 배포에서 수렴 속도/부하 조절용)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DhtTuning {
+    /// k-bucket 크기 (버킷당 최대 노드 수)
+    pub k_bucket_size: usize,
+    /// 복제 계수 (응답/광고/조회 수렴에 쓰는 가장 가까운 노드 수)
+    pub replication_factor: usize,
+    /// 반복 조회 동시 질의 수 (alpha)
+    pub lookup_alpha: usize,
+    /// 버킷 갱신/정리 주기 (초)
+    pub bucket_refresh_secs: u64,
+    /// 제공자 정보 유지 시간 (초)
+    pub provider_ttl_secs: u64,
+}
+
+impl Default for DhtTuning {
+    fn default() -> Self {
+        Self {
+            k_bucket_size: 20,
+            replication_factor: LOOKUP_K,
+            lookup_alpha: LOOKUP_ALPHA,
+            bucket_refresh_secs: 60,
+            provider_ttl_secs: 3600,
+        }
+    }
+}
+
+impl DhtTuning {
+    pub fn bucket_refresh_interval(&self) -> Duration {
+        Duration::from_secs(self.bucket_refresh_secs)
+    }
+
+    pub fn provider_ttl(&self) -> Duration {
+        Duration::from_secs(self.provider_ttl_secs)
+    }
+
+    /// 제공 중인 info_hash 재광고 주기 (만료 전에 갱신되도록 TTL의 절반)
+    pub fn reannounce_interval(&self) -> Duration {
+        self.provider_ttl() / 2
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=256).contains(&self.k_bucket_size) {
+            return Err("dht.k_bucket_size must be between 1 and 256".to_string());
+        }
+        if !(1..=64).contains(&self.replication_factor) {
+            return Err("dht.replication_factor must be between 1 and 64".to_string());
+        }
+        if !(1..=16).contains(&self.lookup_alpha) {
+            return Err("dht.lookup_alpha must be between 1 and 16".to_string());
+        }
+        if !(5..=3600).contains(&self.bucket_refresh_secs) {
+            return Err("dht.bucket_refresh_secs must be between 5 and 3600".to_string());
+        }
+        if !(60..=86400).contains(&self.provider_ttl_secs) {
+            return Err("dht.provider_ttl_secs must be between 60 and 86400".to_string());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,321 @@
+//! 네트워크 진단 (자가 점검)
+//!
+//! "연결이 안 돼요"를 재현하지 않고도 원인을 좁힐 수 있도록, 연결 경로별로 필요한 조건을
+//! 하나씩 실제로 확인합니다. 각 점검은 서로 독립적이며 시간 제한 안에서 병렬로 실행됩니다.
+
+use crate::dht::DhtHandle;
+use crate::relay::RelaySelector;
+use crate::turn::stun::{self, NatType};
+use crate::turn::TurnClient;
+use serde::Serialize;
+use std::future::Future;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::time::{Duration, Instant};
+use tokio::net::UdpSocket;
+
+/// 점검 하나의 최대 시간
+const CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// mDNS 응답 대기 시간
+const MDNS_WAIT: Duration = Duration::from_secs(2);
+
+const MDNS_ADDR: Ipv4Addr = Ipv4Addr::new(224, 0, 0, 251);
+const MDNS_PORT: u16 = 5353;
+
+/// 기본 STUN 서버 (NAT 유형 판별에는 서로 다른 서버 두 개가 필요)
+pub const DEFAULT_STUN_SERVERS: [&str; 2] = ["stun.l.google.com:19302", "stun1.l.google.com:19302"];
+
+/// 점검 결과 상태
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    /// 동작은 하지만 일부 경로가 제한됨 (예: Symmetric NAT)
+    Warn,
+    Fail,
+    /// 설정되지 않았거나 서비스가 꺼져 있어 확인하지 않음
+    Skipped,
+}
+
+/// 점검 항목 하나의 결과
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DoctorCheck {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub message: String,
+    pub details: Option<serde_json::Value>,
+    pub duration_ms: u64,
+}
+
+/// 전체 진단 보고서
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DoctorReport {
+    pub checks: Vec<DoctorCheck>,
+    pub passed: usize,
+    pub failed: usize,
+    pub duration_ms: u64,
+}
+
+impl DoctorReport {
+    pub fn new(checks: Vec<DoctorCheck>, started: Instant) -> Self {
+        let count = |status| checks.iter().filter(|c| c.status == status).count();
+        Self {
+            passed: count(CheckStatus::Pass),
+            failed: count(CheckStatus::Fail),
+            duration_ms: started.elapsed().as_millis() as u64,
+            checks,
+        }
+    }
+}
+
+/// 점검 함수가 돌려주는 결과 (이름과 소요 시간은 `run_check`가 채움)
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    status: CheckStatus,
+    message: String,
+    details: Option<serde_json::Value>,
+}
+
+impl Outcome {
+    fn new(status: CheckStatus, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    pub fn pass(message: impl Into<String>) -> Self {
+        Self::new(CheckStatus::Pass, message)
+    }
+
+    pub fn warn(message: impl Into<String>) -> Self {
+        Self::new(CheckStatus::Warn, message)
+    }
+
+    pub fn fail(message: impl Into<String>) -> Self {
+        Self::new(CheckStatus::Fail, message)
+    }
+
+    pub fn skipped(message: impl Into<String>) -> Self {
+        Self::new(CheckStatus::Skipped, message)
+    }
+
+    pub fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+}
+
+/// 시간 제한을 걸어 점검 실행
+pub async fn run_check(name: &'static str, check: impl Future<Output = Outcome>) -> DoctorCheck {
+    let started = Instant::now();
+    let outcome = tokio::time::timeout(CHECK_TIMEOUT, check)
+        .await
+        .unwrap_or_else(|_| {
+            Outcome::fail(format!(
+                "{}초 안에 끝나지 않았습니다",
+                CHECK_TIMEOUT.as_secs()
+            ))
+        });
+
+    DoctorCheck {
+        name,
+        status: outcome.status,
+        message: outcome.message,
+        details: outcome.details,
+        duration_ms: started.elapsed().as_millis() as u64,
+    }
+}
+
+/// `_services._dns-sd._udp.local` PTR 질의 (DNS-SD 서비스 목록)
+fn mdns_query() -> Vec<u8> {
+    // 헤더: ID 0, 플래그 0, 질문 1개
+    let mut packet = vec![0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0];
+    for label in ["_services", "_dns-sd", "_udp", "local"] {
+        packet.push(label.len() as u8);
+        packet.extend_from_slice(label.as_bytes());
+    }
+    // 종료 라벨, QTYPE=PTR(12), QCLASS=IN(1)
+    packet.extend_from_slice(&[0, 0, 12, 0, 1]);
+    packet
+}
+
+/// mDNS 멀티캐스트 송수신 확인
+///
+/// 5353이 아닌 포트에서 보낸 질의에는 응답자가 유니캐스트로 답하므로(legacy unicast), 응답이 오면
+/// 멀티캐스트 송신과 LAN 응답 경로가 모두 동작하는 것입니다. `known_peers`는 mDNS 탐색이 이미
+/// 찾은 피어 수입니다.
+pub async fn check_mdns(known_peers: Option<usize>) -> Outcome {
+    let socket = match UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).await {
+        Ok(socket) => socket,
+        Err(e) => return Outcome::fail(format!("UDP 소켓 생성 실패: {}", e)),
+    };
+    if let Err(e) = socket.send_to(&mdns_query(), (MDNS_ADDR, MDNS_PORT)).await {
+        return Outcome::fail(format!("멀티캐스트 전송 실패 (라우트/방화벽 확인): {}", e));
+    }
+
+    let mut buf = [0u8; 1500];
+    let responder = tokio::time::timeout(MDNS_WAIT, socket.recv_from(&mut buf))
+        .await
+        .ok()
+        .and_then(|received| received.ok())
+        .map(|(_, from)| from);
+    let details = serde_json::json!({
+        "responder": responder.map(|addr| addr.to_string()),
+        "knownPeers": known_peers,
+    });
+
+    match (responder, known_peers) {
+        (Some(addr), _) => Outcome::pass(format!("mDNS 응답 수신: {}", addr)).with_details(details),
+        (None, Some(peers)) if peers > 0 => {
+            Outcome::pass(format!("mDNS로 발견된 피어 {}개", peers)).with_details(details)
+        }
+        (None, _) => Outcome::warn(
+            "멀티캐스트 전송은 되었지만 응답한 기기가 없습니다 (같은 LAN에 다른 기기가 없거나 멀티캐스트 차단)",
+        )
+        .with_details(details),
+    }
+}
+
+/// STUN 연결 및 NAT 유형 확인
+pub async fn check_stun(servers: &[String]) -> Outcome {
+    let mut resolved = Vec::new();
+    for server in servers {
+        match tokio::net::lookup_host(server.as_str()).await {
+            Ok(addrs) => resolved.extend(addrs.filter(|addr| addr.is_ipv4()).take(1)),
+            Err(e) => tracing::debug!("STUN 서버 주소 조회 실패 {}: {}", server, e),
+        }
+    }
+    if resolved.is_empty() {
+        return Outcome::fail("STUN 서버 주소를 찾을 수 없습니다 (DNS 확인)");
+    }
+
+    let result = match stun::detect_nat_type(&resolved).await {
+        Ok(result) => result,
+        Err(e) => {
+            return Outcome::fail(format!("STUN 서버에 도달할 수 없습니다 (UDP 차단?): {}", e))
+        }
+    };
+    let details = serde_json::json!({
+        "publicAddr": SocketAddr::new(result.public_addr.into(), result.public_port).to_string(),
+        "natType": format!("{:?}", result.nat_type),
+    });
+
+    let message = format!(
+        "공인 주소 {}:{}, NAT 유형 {:?}",
+        result.public_addr, result.public_port, result.nat_type
+    );
+    match result.nat_type {
+        NatType::Symmetric => {
+            Outcome::warn(format!("{} (홀펀칭이 어려워 릴레이/TURN 필요)", message))
+                .with_details(details)
+        }
+        nat if nat.allows_hole_punching() => Outcome::pass(message).with_details(details),
+        _ => Outcome::pass(format!("{} (비교할 두 번째 서버 응답 없음)", message))
+            .with_details(details),
+    }
+}
+
+/// TURN 릴레이 할당 확인
+pub async fn check_turn(client: Option<TurnClient>) -> Outcome {
+    let Some(client) = client else {
+        return Outcome::skipped("TURN 서버가 설정되지 않았습니다");
+    };
+    match client.test_allocation().await {
+        Ok(relay_addr) => Outcome::pass(format!("릴레이 주소 할당 성공: {}", relay_addr))
+            .with_details(serde_json::json!({
+                "server": client.server_addr().to_string(),
+                "relayAddr": relay_addr.to_string(),
+            })),
+        Err(e) => Outcome::fail(format!("TURN 할당 실패 ({}): {}", client.server_addr(), e)),
+    }
+}
+
+/// DHT 부트스트랩 연결 확인 (라우팅 테이블에 노드가 있는지)
+pub async fn check_dht(dht: Option<DhtHandle>) -> Outcome {
+    let Some(dht) = dht else {
+        return Outcome::skipped("내장 부트스트랩(DHT)이 실행 중이 아닙니다");
+    };
+    match dht.list_nodes().await {
+        Ok(nodes) if nodes.is_empty() => {
+            Outcome::fail("DHT 라우팅 테이블이 비어 있습니다 (부트스트랩 노드에 연결되지 않음)")
+        }
+        Ok(nodes) => Outcome::pass(format!("DHT 노드 {}개 연결", nodes.len()))
+            .with_details(serde_json::json!({ "nodes": nodes.len() })),
+        Err(e) => Outcome::fail(format!("DHT 노드 조회 실패: {}", e)),
+    }
+}
+
+/// UDP 포트 바인딩 확인
+///
+/// `port`가 없으면 임시 포트로 UDP 사용 가능 여부만 봅니다. 이미 QUIC 서버가 그 포트를 쓰고
+/// 있으면(`quic_port`) 통과로 봅니다.
+pub async fn check_udp_port(port: Option<u16>, quic_port: Option<u16>) -> Outcome {
+    let port = port.unwrap_or(0);
+    match UdpSocket::bind((Ipv4Addr::UNSPECIFIED, port)).await {
+        Ok(socket) => {
+            let bound = socket.local_addr().map(|addr| addr.port()).unwrap_or(port);
+            Outcome::pass(format!("UDP 포트 {} 바인딩 가능", bound))
+                .with_details(serde_json::json!({ "port": bound, "quicPort": quic_port }))
+        }
+        Err(_) if port != 0 && quic_port == Some(port) => {
+            Outcome::pass(format!("UDP 포트 {}는 PonsWarp QUIC 서버가 사용 중", port))
+        }
+        Err(e) => Outcome::fail(format!("UDP 포트 {} 바인딩 실패: {}", port, e)),
+    }
+}
+
+/// 릴레이 후보 도달 확인 (QUIC 핸드셰이크)
+pub async fn check_relays(selector: &RelaySelector) -> Outcome {
+    if let Err(e) = selector.probe_all().await {
+        return Outcome::fail(format!("릴레이 측정 실패: {}", e));
+    }
+    let candidates = selector.candidates().await;
+    if candidates.is_empty() {
+        return Outcome::skipped("알려진 릴레이 후보가 없습니다");
+    }
+
+    let reachable: Vec<_> = candidates.iter().filter(|c| c.rtt_ms.is_some()).collect();
+    let details = serde_json::json!({
+        "candidates": candidates.len(),
+        "reachable": reachable.len(),
+        "bestRttMs": reachable.iter().filter_map(|c| c.rtt_ms).min(),
+    });
+    if reachable.is_empty() {
+        Outcome::fail(format!("릴레이 후보 {}개 모두 응답 없음", candidates.len()))
+            .with_details(details)
+    } else {
+        Outcome::pass(format!(
+            "릴레이 {}/{}개 도달 가능",
+            reachable.len(),
+            candidates.len()
+        ))
+        .with_details(details)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_report_counts() {
+        let started = Instant::now();
+        let checks = vec![
+            run_check("a", async { Outcome::pass("ok") }).await,
+            run_check("b", async { Outcome::fail("no") }).await,
+            run_check("c", async { Outcome::skipped("off") }).await,
+            run_check("udp", check_udp_port(None, None)).await,
+        ];
+        let report = DoctorReport::new(checks, started);
+        assert_eq!(report.passed, 2);
+        assert_eq!(report.failed, 1);
+
+        let value = serde_json::to_value(&report).unwrap();
+        assert_eq!(value["checks"][2]["status"], "skipped");
+        assert!(value["checks"][0]["durationMs"].is_u64());
+    }
+}
@@ -0,0 +1,146 @@
+//! 커맨드/이벤트 공통 에러
+//!
+//! 프론트엔드에는 `{ code, message, details }` 형태로 직렬화되어, 메시지 문자열 대신 `code`로 분기하고
+//! 현지화할 수 있습니다. `message`는 기존과 같은 한국어 설명입니다.
+
+use crate::transfer::disk_space::InsufficientSpace;
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+
+/// Tauri 커맨드 에러
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AppError {
+    /// 잘못된 인자 (주소/ID 파싱 실패, 설정 검증 실패 등)
+    #[error("{0}")]
+    InvalidInput(String),
+    /// 작업/규칙/파일 등을 찾을 수 없음
+    #[error("{0}")]
+    NotFound(String),
+    /// 피어와 연결되어 있지 않음
+    #[error("피어 {peer_id}에 대한 연결이 없습니다.")]
+    PeerNotConnected { peer_id: String },
+    /// 필요한 서비스(QUIC 서버, 릴레이 엔진 등)가 시작되지 않음
+    #[error("{0}")]
+    NotRunning(String),
+    /// 연결/전송 실패
+    #[error("{0}")]
+    Network(String),
+    /// 응답 시간 초과
+    #[error("{0}")]
+    Timeout(String),
+    /// 사용자 취소
+    #[error("{0}")]
+    Cancelled(String),
+    /// 상대가 거절
+    #[error("{0}")]
+    Rejected(String),
+    /// 파일 시스템 오류
+    #[error("{0}")]
+    Io(String),
+    /// 수신 측 저장 공간 부족 (여유분 포함 필요 바이트, 여유 바이트)
+    #[error("저장 공간이 부족합니다 (필요 {required} bytes, 여유 {available} bytes)")]
+    InsufficientSpace { required: u64, available: u64 },
+    /// 페어링/서명/암호화 오류
+    #[error("{0}")]
+    Crypto(String),
+    /// 이 빌드에서 지원하지 않는 기능
+    #[error("{0}")]
+    Unsupported(String),
+    /// 그 밖의 내부 오류
+    #[error("{0}")]
+    Internal(String),
+}
+
+impl AppError {
+    /// 프론트엔드 분기용 코드
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::InvalidInput(_) => "INVALID_INPUT",
+            AppError::NotFound(_) => "NOT_FOUND",
+            AppError::PeerNotConnected { .. } => "PEER_NOT_CONNECTED",
+            AppError::NotRunning(_) => "NOT_RUNNING",
+            AppError::Network(_) => "NETWORK",
+            AppError::Timeout(_) => "TIMEOUT",
+            AppError::Cancelled(_) => "CANCELLED",
+            AppError::Rejected(_) => "REJECTED",
+            AppError::Io(_) => "IO",
+            AppError::InsufficientSpace { .. } => "INSUFFICIENT_SPACE",
+            AppError::Crypto(_) => "CRYPTO",
+            AppError::Unsupported(_) => "UNSUPPORTED",
+            AppError::Internal(_) => "INTERNAL",
+        }
+    }
+
+    /// 코드별 추가 정보
+    pub fn details(&self) -> Option<serde_json::Value> {
+        match self {
+            AppError::PeerNotConnected { peer_id } => {
+                Some(serde_json::json!({ "peerId": peer_id }))
+            }
+            AppError::InsufficientSpace {
+                required,
+                available,
+            } => Some(serde_json::json!({ "required": required, "available": available })),
+            _ => None,
+        }
+    }
+}
+
+impl From<InsufficientSpace> for AppError {
+    fn from(error: InsufficientSpace) -> Self {
+        AppError::InsufficientSpace {
+            required: error.required,
+            available: error.available,
+        }
+    }
+}
+
+/// 분류하지 않은 문자열 에러 (내부 헬퍼의 `?` 전파용)
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        AppError::Internal(message)
+    }
+}
+
+impl From<&str> for AppError {
+    fn from(message: &str) -> Self {
+        AppError::Internal(message.to_string())
+    }
+}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("AppError", 3)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field("details", &self.details())?;
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialize_shape() {
+        let error = AppError::PeerNotConnected {
+            peer_id: "peer-1".to_string(),
+        };
+        assert_eq!(
+            serde_json::to_value(&error).unwrap(),
+            serde_json::json!({
+                "code": "PEER_NOT_CONNECTED",
+                "message": "피어 peer-1에 대한 연결이 없습니다.",
+                "details": { "peerId": "peer-1" },
+            })
+        );
+
+        let error = AppError::from("실패".to_string());
+        assert_eq!(error.code(), "INTERNAL");
+        assert_eq!(
+            serde_json::to_value(&error).unwrap()["details"],
+            serde_json::Value::Null
+        );
+    }
+}
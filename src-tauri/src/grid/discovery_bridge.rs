@@ -0,0 +1,179 @@
+//! Discovery Bridge - 발견한 피어를 Swarm 연결로 이어줌
+//!
+//! `HybridDiscovery`가 DHT에서 찾은 제공자(다운로드 중인 info_hash 기준)와 mDNS로 발견한
+//! 로컬 피어를 `SwarmCommand::ConnectPeer`로 전달합니다. 같은 주소는 지수 백오프로
+//! 재시도 간격을 늘려, 주기적인 재검색마다 같은 피어에 연결을 반복하지 않습니다.
+
+use crate::dht::{DhtHandle, InfoHash};
+use crate::discovery::DiscoveryService;
+use crate::grid::hybrid_discovery::{DiscoverySource, HybridDiscovery, HybridDiscoveryEvent};
+use crate::grid::swarm::SwarmCommand;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, RwLock};
+use tracing::{debug, info};
+
+/// 같은 주소에 다시 연결을 요청하기까지의 첫 대기 시간
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(30);
+
+/// 재시도 대기 시간 상한
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(10 * 60);
+
+/// 주소별 연결 요청 기록
+#[derive(Debug, Clone, Copy)]
+struct Attempt {
+    next_at: Instant,
+    count: u32,
+}
+
+/// 주소별 연결 요청 중복 제거와 백오프
+///
+/// 연결 결과는 알 수 없으므로 요청할 때마다 대기 시간을 두 배로 늘립니다.
+/// (이미 연결된 피어는 Swarm이 무시)
+#[derive(Debug, Default)]
+pub struct ConnectBackoff {
+    attempts: HashMap<SocketAddr, Attempt>,
+}
+
+impl ConnectBackoff {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 지금 연결을 요청해도 되는지 (요청하면 다음 시각을 기록)
+    pub fn should_attempt(&mut self, addr: SocketAddr, now: Instant) -> bool {
+        let count = match self.attempts.get(&addr) {
+            Some(attempt) if now < attempt.next_at => return false,
+            Some(attempt) => attempt.count + 1,
+            None => 1,
+        };
+        let delay = RETRY_BASE_DELAY
+            .saturating_mul(1 << (count - 1).min(16))
+            .min(MAX_RETRY_DELAY);
+        self.attempts.insert(
+            addr,
+            Attempt {
+                next_at: now + delay,
+                count,
+            },
+        );
+        true
+    }
+
+    /// 대기가 끝난 지 오래된 기록 정리
+    pub fn prune(&mut self, now: Instant) {
+        self.attempts
+            .retain(|_, attempt| now < attempt.next_at + MAX_RETRY_DELAY);
+    }
+}
+
+/// 다운로드 작업의 피어 발견을 시작하고 Swarm에 연결 요청 전달
+///
+/// Swarm이 종료되어 `command_tx`가 닫히면 디스커버리와 함께 끝납니다.
+pub fn spawn(
+    info_hash: InfoHash,
+    command_tx: mpsc::Sender<SwarmCommand>,
+    dht: Option<DhtHandle>,
+    mdns: Option<Arc<RwLock<Option<DiscoveryService>>>>,
+) {
+    if dht.is_none() && mdns.is_none() {
+        return;
+    }
+
+    let (event_tx, event_rx) = mpsc::channel(64);
+    let mut discovery = HybridDiscovery::new(event_tx).with_provider_search(info_hash);
+    if let Some(dht) = dht {
+        discovery = discovery.with_dht(dht);
+    }
+    if let Some(mdns) = mdns {
+        discovery = discovery.with_mdns(mdns);
+    }
+
+    tauri::async_runtime::spawn(discovery.run());
+    tauri::async_runtime::spawn(run(info_hash, command_tx, event_rx));
+    info!(
+        "🔍 Grid 피어 자동 발견 시작: {}",
+        hex::encode(&info_hash[..8])
+    );
+}
+
+async fn run(
+    info_hash: InfoHash,
+    command_tx: mpsc::Sender<SwarmCommand>,
+    mut event_rx: mpsc::Receiver<HybridDiscoveryEvent>,
+) {
+    let mut backoff = ConnectBackoff::new();
+    let mut prune_interval = tokio::time::interval(MAX_RETRY_DELAY);
+
+    loop {
+        let event = tokio::select! {
+            event = event_rx.recv() => match event {
+                Some(event) => event,
+                None => break,
+            },
+            _ = prune_interval.tick() => {
+                backoff.prune(Instant::now());
+                continue;
+            }
+            _ = command_tx.closed() => break,
+        };
+
+        let addrs: Vec<SocketAddr> = match event {
+            HybridDiscoveryEvent::ProvidersFound {
+                info_hash: found,
+                providers,
+            } if found == info_hash => providers.iter().map(|p| p.address).collect(),
+            // 로컬 피어는 파일 보유 여부를 알 수 없으므로 연결해서 Handshake로 확인
+            HybridDiscoveryEvent::PeerDiscovered(peer) if peer.source == DiscoverySource::Mdns => {
+                vec![peer.address]
+            }
+            _ => continue,
+        };
+
+        let now = Instant::now();
+        for addr in addrs {
+            if !backoff.should_attempt(addr, now) {
+                continue;
+            }
+            debug!("🔗 발견한 피어 연결 요청: {}", addr);
+            if command_tx
+                .send(SwarmCommand::ConnectPeer(addr))
+                .await
+                .is_err()
+            {
+                return;
+            }
+        }
+    }
+
+    debug!("Grid 피어 자동 발견 종료: {}", hex::encode(&info_hash[..8]));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_dedups_and_grows() {
+        let mut backoff = ConnectBackoff::new();
+        let addr: SocketAddr = "10.0.0.1:7000".parse().unwrap();
+        let other: SocketAddr = "10.0.0.2:7000".parse().unwrap();
+        let start = Instant::now();
+
+        assert!(backoff.should_attempt(addr, start));
+        assert!(!backoff.should_attempt(addr, start + Duration::from_secs(10)));
+        assert!(backoff.should_attempt(other, start));
+
+        // 첫 재시도는 30초 뒤, 다음은 60초 뒤
+        let retry = start + RETRY_BASE_DELAY;
+        assert!(backoff.should_attempt(addr, retry));
+        assert!(!backoff.should_attempt(addr, retry + RETRY_BASE_DELAY));
+        assert!(backoff.should_attempt(addr, retry + RETRY_BASE_DELAY * 2));
+
+        // 오래 지나면 기록이 정리되어 처음부터 다시 셈
+        backoff.prune(start + Duration::from_secs(3600));
+        assert!(backoff.attempts.is_empty());
+    }
+}
@@ -0,0 +1,86 @@
+//! Hash Algorithm - 조각/파일 해시 알고리즘 선택
+//!
+//! 기본은 SHA-256이고, 최신 CPU에서 훨씬 빠른 BLAKE3를 메타데이터별로 고를 수 있습니다.
+//! 알고리즘은 `FileMetadata`에 기록되고 Info Hash에도 반영되므로, Info Hash만 아는
+//! 다운로더도 받은 조각 해시 목록이 어느 알고리즘인지 검증하며 알아낼 수 있습니다.
+//! Merkle Tree 내부 노드는 알고리즘과 관계없이 SHA-256을 씁니다.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::str::FromStr;
+
+/// 조각 해시 알고리즘
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HashAlgorithm {
+    /// 기존 피어와 호환되는 기본값
+    #[default]
+    Sha256,
+    Blake3,
+}
+
+impl HashAlgorithm {
+    /// 지원하는 모든 알고리즘 (Info Hash로 알고리즘을 찾을 때 순서대로 시도)
+    pub const ALL: [HashAlgorithm; 2] = [HashAlgorithm::Sha256, HashAlgorithm::Blake3];
+
+    /// 데이터 해시
+    pub fn digest(self, data: &[u8]) -> [u8; 32] {
+        match self {
+            HashAlgorithm::Sha256 => Sha256::digest(data).into(),
+            HashAlgorithm::Blake3 => *blake3::hash(data).as_bytes(),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HashAlgorithm::Sha256 => "sha256",
+            HashAlgorithm::Blake3 => "blake3",
+        }
+    }
+}
+
+impl fmt::Display for HashAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for HashAlgorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().replace('-', "").as_str() {
+            "sha256" => Ok(HashAlgorithm::Sha256),
+            "blake3" => Ok(HashAlgorithm::Blake3),
+            other => Err(format!("Unsupported hash algorithm: {}", other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digest_and_parse() {
+        let data = b"ponswarp";
+        assert_eq!(
+            HashAlgorithm::Sha256.digest(data),
+            <[u8; 32]>::from(Sha256::digest(data))
+        );
+        assert_eq!(
+            HashAlgorithm::Blake3.digest(data),
+            *blake3::hash(data).as_bytes()
+        );
+        assert_ne!(
+            HashAlgorithm::Sha256.digest(data),
+            HashAlgorithm::Blake3.digest(data)
+        );
+
+        assert_eq!("SHA-256".parse(), Ok(HashAlgorithm::Sha256));
+        assert_eq!("blake3".parse(), Ok(HashAlgorithm::Blake3));
+        assert!("md5".parse::<HashAlgorithm>().is_err());
+        assert_eq!(HashAlgorithm::default(), HashAlgorithm::Sha256);
+    }
+}
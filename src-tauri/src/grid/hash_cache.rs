@@ -0,0 +1,222 @@
+//! Piece Hash Cache - 바뀌지 않은 파일의 조각 해시 재사용
+//!
+//! `FileMetadata::from_file`은 파일 전체를 읽어 조각 해시를 계산하므로 TB 단위 파일을
+//! 다시 시드하거나 공유할 때마다 오래 걸립니다. 계산한 조각 해시를 (경로, 크기, 수정 시각,
+//! 조각 크기, 해시 알고리즘) 기준으로 앱 데이터 디렉토리에 저장해 두고, 파일이 그대로면 다시 읽지 않습니다.
+//!
+//! 항목마다 파일 하나(`<키>.hashes`, bincode)로 저장하며 오래된 항목부터 정리합니다.
+
+use crate::grid::hash_algo::HashAlgorithm;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use tracing::{debug, warn};
+
+/// 캐시 디렉토리 이름 (Grid 데이터 디렉토리 아래)
+pub const HASH_CACHE_DIR: &str = "piece_hashes";
+
+/// 캐시 파일 확장자
+const CACHE_EXTENSION: &str = "hashes";
+
+/// 보관하는 최대 항목 수 (넘으면 가장 오래 쓰지 않은 항목부터 삭제)
+const MAX_CACHE_ENTRIES: usize = 256;
+
+/// 캐시 유효성을 판단하는 파일 상태
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileFingerprint {
+    pub size: u64,
+    pub modified_secs: u64,
+    pub modified_nanos: u32,
+}
+
+impl FileFingerprint {
+    pub fn read(path: &Path) -> std::io::Result<Self> {
+        let metadata = fs::metadata(path)?;
+        let modified = metadata
+            .modified()?
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        Ok(Self {
+            size: metadata.len(),
+            modified_secs: modified.as_secs(),
+            modified_nanos: modified.subsec_nanos(),
+        })
+    }
+}
+
+/// 저장 항목
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedPieceHashes {
+    path: PathBuf,
+    piece_size: u32,
+    hash_algorithm: HashAlgorithm,
+    fingerprint: FileFingerprint,
+    piece_hashes: Vec<[u8; 32]>,
+}
+
+/// 조각 해시 캐시 (디렉토리가 없으면 아무것도 저장하지 않음)
+pub struct PieceHashCache {
+    dir: Option<PathBuf>,
+}
+
+impl PieceHashCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir: Some(dir) }
+    }
+
+    /// 캐시 없이 항상 다시 계산
+    pub fn disabled() -> Self {
+        Self { dir: None }
+    }
+
+    /// 파일 경로, 조각 크기, 알고리즘으로 정한 캐시 파일 경로
+    fn entry_path(dir: &Path, path: &Path, piece_size: u32, algorithm: HashAlgorithm) -> PathBuf {
+        let mut hasher = Sha256::new();
+        hasher.update(path.to_string_lossy().as_bytes());
+        hasher.update(piece_size.to_le_bytes());
+        hasher.update(algorithm.as_str().as_bytes());
+        let key = hex::encode(&hasher.finalize()[..16]);
+        dir.join(format!("{}.{}", key, CACHE_EXTENSION))
+    }
+
+    /// 파일이 저장 당시와 같으면 조각 해시 반환
+    pub fn get(
+        &self,
+        path: &Path,
+        piece_size: u32,
+        algorithm: HashAlgorithm,
+        fingerprint: &FileFingerprint,
+    ) -> Option<Vec<[u8; 32]>> {
+        let dir = self.dir.as_ref()?;
+        let path = fs::canonicalize(path).ok()?;
+        let entry_path = Self::entry_path(dir, &path, piece_size, algorithm);
+        let data = fs::read(&entry_path).ok()?;
+        let entry: CachedPieceHashes = match bincode::deserialize(&data) {
+            Ok(entry) => entry,
+            Err(e) => {
+                warn!("조각 해시 캐시 손상, 삭제: {:?} ({})", entry_path, e);
+                let _ = fs::remove_file(&entry_path);
+                return None;
+            }
+        };
+
+        if entry.path != path
+            || entry.piece_size != piece_size
+            || entry.hash_algorithm != algorithm
+            || entry.fingerprint != *fingerprint
+        {
+            debug!("조각 해시 캐시 만료 (파일 변경): {:?}", path);
+            return None;
+        }
+
+        // 최근 사용 항목이 정리 대상에서 밀리도록 수정 시각 갱신
+        if let Ok(file) = fs::File::options().append(true).open(&entry_path) {
+            let _ = file.set_modified(std::time::SystemTime::now());
+        }
+        Some(entry.piece_hashes)
+    }
+
+    /// 계산한 조각 해시 저장 (실패는 경고만)
+    pub fn put(
+        &self,
+        path: &Path,
+        piece_size: u32,
+        algorithm: HashAlgorithm,
+        fingerprint: FileFingerprint,
+        piece_hashes: &[[u8; 32]],
+    ) {
+        let Some(dir) = &self.dir else {
+            return;
+        };
+        let Ok(path) = fs::canonicalize(path) else {
+            return;
+        };
+        let entry_path = Self::entry_path(dir, &path, piece_size, algorithm);
+        let entry = CachedPieceHashes {
+            path,
+            piece_size,
+            hash_algorithm: algorithm,
+            fingerprint,
+            piece_hashes: piece_hashes.to_vec(),
+        };
+
+        let result = (|| -> anyhow::Result<()> {
+            fs::create_dir_all(dir)?;
+            let tmp = entry_path.with_extension("tmp");
+            fs::write(&tmp, bincode::serialize(&entry)?)?;
+            fs::rename(&tmp, &entry_path)?;
+            Ok(())
+        })();
+        match result {
+            Ok(()) => self.prune(dir),
+            Err(e) => warn!("조각 해시 캐시 저장 실패: {}", e),
+        }
+    }
+
+    /// 항목이 너무 많으면 가장 오래 쓰지 않은 것부터 삭제
+    fn prune(&self, dir: &Path) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        let mut files: Vec<(std::time::SystemTime, PathBuf)> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == CACHE_EXTENSION))
+            .filter_map(|path| Some((fs::metadata(&path).ok()?.modified().ok()?, path)))
+            .collect();
+        if files.len() <= MAX_CACHE_ENTRIES {
+            return;
+        }
+        files.sort();
+        for (_, path) in &files[..files.len() - MAX_CACHE_ENTRIES] {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_hit_and_invalidation() {
+        let dir = std::env::temp_dir().join(format!("ponswarp-hashcache-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("data.bin");
+        fs::write(&file, vec![1u8; 100]).unwrap();
+
+        let cache = PieceHashCache::new(dir.join(HASH_CACHE_DIR));
+        let fingerprint = FileFingerprint::read(&file).unwrap();
+        let sha = HashAlgorithm::Sha256;
+        assert!(cache.get(&file, 64, sha, &fingerprint).is_none());
+
+        let hashes = vec![[1u8; 32], [2u8; 32]];
+        cache.put(&file, 64, sha, fingerprint, &hashes);
+        assert_eq!(
+            cache.get(&file, 64, sha, &fingerprint),
+            Some(hashes.clone())
+        );
+        // 조각 크기나 알고리즘이 다르면 별도 항목
+        assert!(cache.get(&file, 32, sha, &fingerprint).is_none());
+        assert!(cache
+            .get(&file, 64, HashAlgorithm::Blake3, &fingerprint)
+            .is_none());
+
+        // 크기나 수정 시각이 바뀌면 무효
+        let changed = FileFingerprint {
+            size: 101,
+            ..fingerprint
+        };
+        assert!(cache.get(&file, 64, sha, &changed).is_none());
+
+        // 비활성 캐시는 저장하지 않음
+        let disabled = PieceHashCache::disabled();
+        disabled.put(&file, 64, sha, fingerprint, &hashes);
+        assert!(disabled.get(&file, 64, sha, &fingerprint).is_none());
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -3,8 +3,8 @@
 //! 로컬 서브넷(mDNS)과 원격 서브넷(DHT)을 결합하여
 //! 사내망 전체에서 피어를 효율적으로 발견합니다.
 
+use crate::dht::{DhtHandle, InfoHash, NodeId};
 use crate::discovery::DiscoveryService;
-use crate::grid::dht::{DhtCommand, DhtEvent, DhtHandle, InfoHash};
 use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::Arc;
@@ -12,6 +12,12 @@ use std::time::{Duration, Instant};
 use tokio::sync::{mpsc, RwLock};
 use tracing::{debug, info, warn};
 
+/// DHT 제공자 검색 대기 시간
+const PROVIDER_SEARCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 🆕 찾는 info_hash의 제공자 재검색 간격
+const PROVIDER_SEARCH_INTERVAL: Duration = Duration::from_secs(30);
+
 /// 발견된 피어 정보
 #[derive(Debug, Clone)]
 pub struct DiscoveredPeer {
@@ -49,18 +55,18 @@ pub enum HybridDiscoveryEvent {
 
 /// 하이브리드 디스커버리 서비스
 pub struct HybridDiscovery {
-    /// mDNS 서비스
-    mdns: Option<Arc<RwLock<DiscoveryService>>>,
-    /// DHT 핸들
+    /// mDNS 서비스 (앱 공용, 시작 전이면 None)
+    mdns: Option<Arc<RwLock<Option<DiscoveryService>>>>,
+    /// 공유 DHT 핸들 (내장 부트스트랩 노드의 DHT)
     dht_handle: Option<DhtHandle>,
-    /// DHT 이벤트 수신
-    dht_event_rx: Option<mpsc::Receiver<DhtEvent>>,
     /// 발견된 피어 캐시
     peers: Arc<RwLock<HashMap<String, DiscoveredPeer>>>,
     /// 이벤트 발송
     event_tx: mpsc::Sender<HybridDiscoveryEvent>,
     /// 부트스트랩 노드 목록
     bootstrap_nodes: Vec<SocketAddr>,
+    /// 🆕 주기적으로 제공자를 검색할 info_hash
+    wanted: Vec<InfoHash>,
 }
 
 impl HybridDiscovery {
@@ -68,23 +74,28 @@ impl HybridDiscovery {
         Self {
             mdns: None,
             dht_handle: None,
-            dht_event_rx: None,
             peers: Arc::new(RwLock::new(HashMap::new())),
             event_tx,
             bootstrap_nodes: Vec::new(),
+            wanted: Vec::new(),
         }
     }
 
     /// mDNS 서비스 설정
-    pub fn with_mdns(mut self, mdns: Arc<RwLock<DiscoveryService>>) -> Self {
+    pub fn with_mdns(mut self, mdns: Arc<RwLock<Option<DiscoveryService>>>) -> Self {
         self.mdns = Some(mdns);
         self
     }
 
     /// DHT 핸들 설정
-    pub fn with_dht(mut self, handle: DhtHandle, event_rx: mpsc::Receiver<DhtEvent>) -> Self {
+    pub fn with_dht(mut self, handle: DhtHandle) -> Self {
         self.dht_handle = Some(handle);
-        self.dht_event_rx = Some(event_rx);
+        self
+    }
+
+    /// 🆕 실행 중 주기적으로 제공자를 검색할 info_hash 추가
+    pub fn with_provider_search(mut self, info_hash: InfoHash) -> Self {
+        self.wanted.push(info_hash);
         self
     }
 
@@ -114,10 +125,11 @@ impl HybridDiscovery {
         Ok(())
     }
 
-    /// 메인 실행 루프
+    /// 메인 실행 루프 (이벤트 수신 측이 닫히면 종료)
     pub async fn run(mut self) {
         let mut mdns_poll_interval = tokio::time::interval(Duration::from_secs(5));
         let mut cleanup_interval = tokio::time::interval(Duration::from_secs(60));
+        let mut provider_search_interval = tokio::time::interval(PROVIDER_SEARCH_INTERVAL);
 
         loop {
             tokio::select! {
@@ -126,19 +138,18 @@ impl HybridDiscovery {
                     self.poll_mdns().await;
                 }
 
-                // DHT 이벤트 처리
-                event = async {
-                    if let Some(ref mut rx) = self.dht_event_rx {
-                        rx.recv().await
-                    } else {
-                        std::future::pending::<Option<DhtEvent>>().await
-                    }
-                } => {
-                    if let Some(event) = event {
-                        self.handle_dht_event(event).await;
+                // 🆕 찾는 파일의 제공자 재검색
+                _ = provider_search_interval.tick(), if !self.wanted.is_empty() => {
+                    for info_hash in self.wanted.clone() {
+                        let _ = self.find_providers(info_hash).await;
                     }
                 }
 
+                _ = self.event_tx.closed() => {
+                    debug!("하이브리드 디스커버리 종료 (수신 측 없음)");
+                    break;
+                }
+
                 // 오래된 피어 정리
                 _ = cleanup_interval.tick() => {
                     self.cleanup_stale_peers().await;
@@ -151,7 +162,10 @@ impl HybridDiscovery {
     async fn poll_mdns(&mut self) {
         if let Some(ref mdns) = self.mdns {
             let mdns_guard = mdns.read().await;
-            let mdns_peers = mdns_guard.get_peers();
+            let Some(mdns_service) = mdns_guard.as_ref() else {
+                return;
+            };
+            let mdns_peers = mdns_service.get_peers();
             drop(mdns_guard);
 
             let mut peers = self.peers.write().await;
@@ -185,77 +199,61 @@ impl HybridDiscovery {
         }
     }
 
-    /// DHT 이벤트 처리
-    async fn handle_dht_event(&mut self, event: DhtEvent) {
-        match event {
-            DhtEvent::PeerFound {
-                info_hash,
-                peer_id,
-                addr,
-            } => {
-                let peer_id_str = hex::encode(&peer_id[..8]);
-                let now = Instant::now();
-
-                let mut peers = self.peers.write().await;
-
-                if !peers.contains_key(&peer_id_str) {
-                    let discovered = DiscoveredPeer {
-                        peer_id: peer_id_str.clone(),
-                        address: addr,
-                        source: DiscoverySource::Dht,
-                        discovered_at: now,
-                        last_seen: now,
-                    };
-
-                    peers.insert(peer_id_str.clone(), discovered.clone());
-
-                    let _ = self
-                        .event_tx
-                        .send(HybridDiscoveryEvent::PeerDiscovered(discovered))
-                        .await;
-
-                    info!(
-                        "🔍 [DHT] 피어 발견: {} @ {} (file: {})",
-                        peer_id_str,
-                        addr,
-                        hex::encode(&info_hash[..8])
-                    );
+    /// DHT 검색 결과 반영 (새 피어는 발견 이벤트, 전체는 제공자 이벤트로 알림)
+    async fn record_providers(
+        peers: &RwLock<HashMap<String, DiscoveredPeer>>,
+        event_tx: &mpsc::Sender<HybridDiscoveryEvent>,
+        info_hash: InfoHash,
+        providers: Vec<(NodeId, SocketAddr)>,
+    ) {
+        let now = Instant::now();
+        let mut discovered_providers = Vec::with_capacity(providers.len());
+
+        for (peer_id, addr) in providers {
+            let peer_id_str = hex::encode(&peer_id[..8]);
+            let discovered = DiscoveredPeer {
+                peer_id: peer_id_str.clone(),
+                address: addr,
+                source: DiscoverySource::Dht,
+                discovered_at: now,
+                last_seen: now,
+            };
+
+            let is_new = {
+                let mut peers = peers.write().await;
+                match peers.get_mut(&peer_id_str) {
+                    Some(existing) => {
+                        existing.last_seen = now;
+                        false
+                    }
+                    None => {
+                        peers.insert(peer_id_str.clone(), discovered.clone());
+                        true
+                    }
                 }
-            }
+            };
 
-            DhtEvent::ProvidersFound {
-                info_hash,
-                providers,
-            } => {
-                let now = Instant::now();
-                let discovered_providers: Vec<DiscoveredPeer> = providers
-                    .into_iter()
-                    .map(|(peer_id, addr)| DiscoveredPeer {
-                        peer_id: hex::encode(&peer_id[..8]),
-                        address: addr,
-                        source: DiscoverySource::Dht,
-                        discovered_at: now,
-                        last_seen: now,
-                    })
-                    .collect();
-
-                let _ = self
-                    .event_tx
-                    .send(HybridDiscoveryEvent::ProvidersFound {
-                        info_hash,
-                        providers: discovered_providers,
-                    })
+            if is_new {
+                let _ = event_tx
+                    .send(HybridDiscoveryEvent::PeerDiscovered(discovered.clone()))
                     .await;
-            }
-
-            DhtEvent::Ready => {
-                info!("✅ DHT 준비 완료");
-            }
 
-            DhtEvent::Error { message } => {
-                warn!("⚠️ DHT 에러: {}", message);
+                info!(
+                    "🔍 [DHT] 피어 발견: {} @ {} (file: {})",
+                    peer_id_str,
+                    addr,
+                    hex::encode(&info_hash[..8])
+                );
             }
+            discovered_providers.push(discovered);
         }
+
+        let _ = event_tx
+            .send(HybridDiscoveryEvent::ProvidersFound {
+                info_hash,
+                providers: discovered_providers,
+            })
+            .await;
     }
 
     /// 오래된 피어 정리
@@ -279,18 +277,32 @@ impl HybridDiscovery {
         }
     }
 
-    /// 특정 파일의 제공자 검색
+    /// 특정 파일의 제공자 검색 (결과는 `ProvidersFound` 이벤트로 전달)
     pub async fn find_providers(&self, info_hash: InfoHash) -> anyhow::Result<()> {
-        if let Some(ref handle) = self.dht_handle {
-            handle.find_providers(info_hash).await?;
-        }
+        let Some(handle) = self.dht_handle.clone() else {
+            return Ok(());
+        };
+        let peers = self.peers.clone();
+        let event_tx = self.event_tx.clone();
+
+        tokio::spawn(async move {
+            match handle
+                .find_provider_nodes(info_hash, PROVIDER_SEARCH_TIMEOUT)
+                .await
+            {
+                Ok(providers) => {
+                    Self::record_providers(&peers, &event_tx, info_hash, providers).await;
+                }
+                Err(e) => warn!("⚠️ DHT 제공자 검색 실패: {}", e),
+            }
+        });
         Ok(())
     }
 
-    /// 파일 제공 시작 (내가 이 파일을 가지고 있음을 알림)
-    pub async fn start_providing(&self, info_hash: InfoHash) -> anyhow::Result<()> {
+    /// 파일 제공 시작 (내가 이 파일을 가지고 있음을 알림, port = 데이터 전송 포트)
+    pub async fn start_providing(&self, info_hash: InfoHash, port: u16) -> anyhow::Result<()> {
         if let Some(ref handle) = self.dht_handle {
-            handle.start_providing(info_hash).await?;
+            handle.announce(info_hash, port).await?;
         }
         Ok(())
     }
@@ -0,0 +1,397 @@
+//! Grid Job - 앱에서 실행 중인 GridSwarm 인스턴스 관리
+//!
+//! Seed/Download 작업마다 Swarm 태스크를 띄우고, job_id로 명령 전달/상태 조회/중지를
+//! 할 수 있게 합니다. 🆕 QUIC 서버가 실행 중이면 그 엔드포인트(포트, 인증서)를 함께 쓰고,
+//! 아니면 작업마다 전용 엔드포인트를 엽니다.
+
+use crate::dht::DhtHandle;
+use crate::discovery::DiscoveryService;
+use crate::error::AppError;
+use crate::grid::discovery_bridge;
+use crate::grid::peer_score::BanList;
+use crate::grid::piece_manager::{FileMetadata, PieceManager};
+use crate::grid::rate_limit::SwarmRateLimit;
+use crate::grid::swarm::{GridSwarm, SwarmCommand, SwarmEvent};
+use crate::grid::{GridJobState, GridStateUpdate};
+use crate::quic::client::SkipServerVerification;
+use crate::quic::grid_route::{self, GridRouter, SharedGridEndpoint, GRID_ALPN};
+use crate::transfer::{TransferKind, TransferRegistry};
+use quinn::{ClientConfig, Endpoint, ServerConfig};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri::AppHandle;
+use tokio::sync::{mpsc, RwLock};
+use tracing::{info, warn};
+
+/// 작업 역할
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GridRole {
+    Seed,
+    Download,
+}
+
+/// 프론트엔드 전송용 작업 정보
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GridJobInfo {
+    pub job_id: String,
+    pub role: GridRole,
+    pub info_hash: String,
+    pub file_name: String,
+    pub file_size: u64,
+    pub local_addr: String,
+    pub progress: f32,
+    pub peers: usize,
+    pub state: GridJobState,
+    pub finished: bool,
+}
+
+/// 실행 중인 작업
+struct GridJob {
+    info: GridJobInfo,
+    command_tx: mpsc::Sender<SwarmCommand>,
+    endpoint: Endpoint,
+    /// 🆕 공유 엔드포인트의 라우터 (None이면 전용 엔드포인트라 중지 시 닫음)
+    router: Option<GridRouter>,
+    info_hash: [u8; 32],
+    registry: Arc<TransferRegistry>,
+}
+
+/// 작업 시작 옵션
+pub struct GridJobOptions {
+    pub app_handle: AppHandle,
+    pub rate_limit: Arc<SwarmRateLimit>,
+    pub resume_dir: Option<PathBuf>,
+    pub ban_list: Arc<BanList>,
+    /// 공용 전송 레지스트리 (수명주기 이벤트)
+    pub registry: Arc<TransferRegistry>,
+    /// 🆕 다운로드 제공자 검색용 DHT (내장 부트스트랩이 꺼져 있으면 None)
+    pub dht: Option<DhtHandle>,
+    /// 🆕 로컬 피어 발견용 mDNS 서비스
+    pub mdns: Option<Arc<RwLock<Option<DiscoveryService>>>>,
+    /// 🆕 함께 쓸 QUIC 서버 엔드포인트 (None이면 전용 엔드포인트 생성)
+    pub shared_endpoint: Option<SharedGridEndpoint>,
+}
+
+/// Grid 작업 관리자
+#[derive(Default)]
+pub struct GridJobManager {
+    jobs: Arc<RwLock<HashMap<String, GridJob>>>,
+}
+
+impl GridJobManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Seed 작업 시작
+    pub async fn start_seed(
+        &self,
+        job_id: String,
+        file_path: PathBuf,
+        metadata: FileMetadata,
+        options: GridJobOptions,
+    ) -> anyhow::Result<GridJobInfo> {
+        let command = SwarmCommand::StartSeeding {
+            file_path,
+            metadata: metadata.clone(),
+        };
+        self.spawn(job_id, GridRole::Seed, &metadata, command, options)
+            .await
+    }
+
+    /// 메타데이터로 Download 작업 시작
+    pub async fn start_download(
+        &self,
+        job_id: String,
+        metadata: FileMetadata,
+        save_path: PathBuf,
+        options: GridJobOptions,
+    ) -> anyhow::Result<GridJobInfo> {
+        let command = SwarmCommand::StartDownload {
+            metadata: metadata.clone(),
+            save_path,
+        };
+        self.spawn(job_id, GridRole::Download, &metadata, command, options)
+            .await
+    }
+
+    /// Info Hash만으로 Download 작업 시작 (피어에게서 메타데이터 수신)
+    pub async fn start_download_by_hash(
+        &self,
+        job_id: String,
+        info_hash: [u8; 32],
+        save_path: PathBuf,
+        options: GridJobOptions,
+    ) -> anyhow::Result<GridJobInfo> {
+        let metadata = FileMetadata::placeholder(info_hash);
+        let command = SwarmCommand::StartDownloadByHash {
+            info_hash,
+            save_path,
+        };
+        self.spawn(job_id, GridRole::Download, &metadata, command, options)
+            .await
+    }
+
+    async fn spawn(
+        &self,
+        job_id: String,
+        role: GridRole,
+        metadata: &FileMetadata,
+        start_command: SwarmCommand,
+        options: GridJobOptions,
+    ) -> anyhow::Result<GridJobInfo> {
+        if self.jobs.read().await.contains_key(&job_id) {
+            return Err(anyhow::anyhow!("Grid job already exists: {}", job_id));
+        }
+
+        // 같은 info_hash의 Swarm이 이미 공유 엔드포인트를 쓰고 있으면 전용 엔드포인트 사용
+        // (여기서 실패해도 받는 쪽이 닫힌 경로는 다음 등록 때 교체됨)
+        let info_hash = metadata.info_hash;
+        let shared = options.shared_endpoint.and_then(|shared| {
+            let incoming = shared.router.register(info_hash)?;
+            Some((shared, incoming))
+        });
+        let endpoint = match &shared {
+            Some((shared, _)) => shared.endpoint.clone(),
+            None => create_endpoint()?,
+        };
+        let local_addr = endpoint.local_addr()?;
+        let registry = options.registry;
+        registry
+            .register(&job_id, "swarm", TransferKind::Grid)
+            .map_err(anyhow::Error::msg)?;
+
+        let (command_tx, command_rx) = mpsc::channel(64);
+        let (event_tx, event_rx) = mpsc::channel(256);
+
+        let piece_manager = Arc::new(RwLock::new(PieceManager::new(metadata.clone())));
+        let mut swarm = GridSwarm::new(endpoint.clone(), piece_manager, command_rx, event_tx);
+        swarm.set_app_handle(options.app_handle);
+        swarm.set_job_id(job_id.clone());
+        swarm.set_rate_limit(options.rate_limit);
+        swarm.set_ban_list(options.ban_list);
+        swarm.set_server_name(grid_route::grid_server_name(&info_hash));
+        if let Some(dir) = options.resume_dir {
+            swarm.set_resume_dir(dir);
+        }
+        let router = match shared {
+            Some((shared, incoming)) => {
+                swarm.set_shared_endpoint(incoming, create_client_config()?);
+                Some(shared.router)
+            }
+            None => None,
+        };
+
+        tauri::async_runtime::spawn(swarm.run());
+        command_tx.send(start_command).await?;
+
+        // 🆕 다운로드는 DHT/mDNS로 발견한 피어에 자동 연결
+        if role == GridRole::Download {
+            discovery_bridge::spawn(
+                metadata.info_hash,
+                command_tx.clone(),
+                options.dht,
+                options.mdns,
+            );
+        }
+
+        let info = GridJobInfo {
+            job_id: job_id.clone(),
+            role,
+            info_hash: metadata.info_hash_hex(),
+            file_name: metadata.file_name.clone(),
+            file_size: metadata.file_size,
+            local_addr: local_addr.to_string(),
+            progress: if role == GridRole::Seed { 1.0 } else { 0.0 },
+            peers: 0,
+            state: GridJobState::Idle,
+            finished: role == GridRole::Seed,
+        };
+
+        self.jobs.write().await.insert(
+            job_id.clone(),
+            GridJob {
+                info: info.clone(),
+                command_tx,
+                endpoint,
+                router,
+                info_hash,
+                registry: registry.clone(),
+            },
+        );
+
+        tauri::async_runtime::spawn(Self::track_events(
+            self.jobs.clone(),
+            registry,
+            job_id.clone(),
+            event_rx,
+        ));
+
+        info!("🐝 Grid 작업 시작: {} ({:?}, {})", job_id, role, local_addr);
+        Ok(info)
+    }
+
+    /// Swarm 이벤트로 작업 정보 및 레지스트리 갱신
+    async fn track_events(
+        jobs: Arc<RwLock<HashMap<String, GridJob>>>,
+        registry: Arc<TransferRegistry>,
+        job_id: String,
+        mut event_rx: mpsc::Receiver<SwarmEvent>,
+    ) {
+        while let Some(event) = event_rx.recv().await {
+            let mut jobs = jobs.write().await;
+            let Some(job) = jobs.get_mut(&job_id) else {
+                break;
+            };
+
+            match event {
+                SwarmEvent::StateUpdate(update) => {
+                    Self::report_update(&registry, &job.info, &update);
+                    Self::apply_update(&mut job.info, &update);
+                }
+                SwarmEvent::TransferComplete => {
+                    job.info.finished = true;
+                    job.info.progress = 1.0;
+                    registry.finish(&job_id, Ok(()));
+                }
+                SwarmEvent::Error(message) => warn!("Grid 작업 에러: {} - {}", job_id, message),
+                _ => {}
+            }
+        }
+
+        // Swarm이 스스로 끝난 경우 (완료/중지로 이미 기록된 작업은 무시됨)
+        registry.finish(&job_id, Err(AppError::Network("Swarm 종료".into())));
+    }
+
+    /// 진행률과 일시정지/재개 전환을 레지스트리에 반영
+    fn report_update(registry: &TransferRegistry, info: &GridJobInfo, update: &GridStateUpdate) {
+        let total = info.file_size;
+        let bytes = (update.progress as f64 * total as f64) as u64;
+        let speed = if info.role == GridRole::Seed || update.state == GridJobState::Seeding {
+            update.upload_speed
+        } else {
+            update.download_speed
+        };
+        registry.update_progress(&info.job_id, bytes, total, speed);
+
+        if update.state != info.state {
+            if update.state == GridJobState::Paused {
+                registry.pause(&info.job_id);
+            } else if info.state == GridJobState::Paused {
+                registry.resume(&info.job_id);
+            }
+        }
+    }
+
+    fn apply_update(info: &mut GridJobInfo, update: &GridStateUpdate) {
+        info.progress = update.progress;
+        info.peers = update.peers.len();
+        info.state = update.state;
+    }
+
+    /// 작업에 명령 전달
+    pub async fn send(&self, job_id: &str, command: SwarmCommand) -> anyhow::Result<()> {
+        let tx = self
+            .jobs
+            .read()
+            .await
+            .get(job_id)
+            .map(|job| job.command_tx.clone())
+            .ok_or_else(|| anyhow::anyhow!("Grid job not found: {}", job_id))?;
+        tx.send(command).await?;
+        Ok(())
+    }
+
+    /// 작업에 피어 연결 요청
+    pub async fn connect_peers(
+        &self,
+        job_id: &str,
+        peers: Vec<SocketAddr>,
+    ) -> anyhow::Result<()> {
+        for addr in peers {
+            self.send(job_id, SwarmCommand::ConnectPeer(addr)).await?;
+        }
+        Ok(())
+    }
+
+    /// 작업 중지 및 제거 (delete_partial이면 미완료 파일 삭제)
+    pub async fn stop(&self, job_id: &str, delete_partial: bool) -> anyhow::Result<()> {
+        let job = self
+            .jobs
+            .write()
+            .await
+            .remove(job_id)
+            .ok_or_else(|| anyhow::anyhow!("Grid job not found: {}", job_id))?;
+
+        let _ = job.command_tx.send(SwarmCommand::Stop { delete_partial }).await;
+        match job.router {
+            Some(router) => router.unregister(&job.info_hash),
+            None => job.endpoint.close(0u32.into(), b"stop"),
+        }
+        if job.registry.cancel(job_id) {
+            job.registry.finish(
+                job_id,
+                Err(AppError::Cancelled("사용자에 의해 중지됨".into())),
+            );
+        }
+
+        info!("🛑 Grid 작업 중지: {}", job_id);
+        Ok(())
+    }
+
+    /// 작업 목록
+    pub async fn list(&self) -> Vec<GridJobInfo> {
+        self.jobs
+            .read()
+            .await
+            .values()
+            .map(|job| job.info.clone())
+            .collect()
+    }
+
+    /// 모든 작업 중지 (앱 종료 시)
+    pub async fn stop_all(&self) {
+        let job_ids: Vec<String> = self.jobs.read().await.keys().cloned().collect();
+        for job_id in job_ids {
+            let _ = self.stop(&job_id, false).await;
+        }
+    }
+}
+
+/// 수신/발신 겸용 Grid QUIC 엔드포인트
+fn create_endpoint() -> anyhow::Result<Endpoint> {
+    let cert =
+        rcgen::generate_simple_self_signed(vec!["localhost".into(), "ponswarp.local".into()])?;
+    let cert_chain = vec![rustls::pki_types::CertificateDer::from(cert.cert.der().to_vec())];
+    let priv_key =
+        rustls::pki_types::PrivatePkcs8KeyDer::from(cert.key_pair.serialize_der()).into();
+
+    let mut server_crypto = rustls::ServerConfig::builder()
+        .with_no_client_auth()
+        .with_single_cert(cert_chain, priv_key)?;
+    server_crypto.alpn_protocols = vec![GRID_ALPN.to_vec()];
+    let server_config = ServerConfig::with_crypto(Arc::new(
+        quinn::crypto::rustls::QuicServerConfig::try_from(server_crypto)?,
+    ));
+
+    let mut endpoint = Endpoint::server(server_config, "0.0.0.0:0".parse()?)?;
+    endpoint.set_default_client_config(create_client_config()?);
+    Ok(endpoint)
+}
+
+/// Grid 피어로 나가는 연결 설정
+fn create_client_config() -> anyhow::Result<ClientConfig> {
+    let mut client_crypto = rustls::ClientConfig::builder()
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(SkipServerVerification))
+        .with_no_client_auth();
+    client_crypto.alpn_protocols = vec![GRID_ALPN.to_vec()];
+    Ok(ClientConfig::new(Arc::new(
+        quinn::crypto::rustls::QuicClientConfig::try_from(client_crypto)?,
+    )))
+}
@@ -0,0 +1,141 @@
+//! Merkle - 조각 해시 Merkle Tree 및 포함 증명(proof)
+//!
+//! 루트 해시만 알고 있어도 조각 데이터 + 형제 해시 경로로 무결성을 검증할 수 있습니다.
+//! 홀수 개 노드는 자기 자신과 짝지어 해시합니다 (`FileMetadata::merkle_root`와 동일 규칙).
+//!
+//! 루트 계산은 기본 빌드의 메타데이터 생성에도 쓰이고, 포함 증명은 Swarm 전송에서만 쓰므로
+//! `grid-experimental`에서만 빌드합니다.
+
+#[cfg(feature = "grid-experimental")]
+use crate::grid::hash_algo::HashAlgorithm;
+use sha2::{Digest, Sha256};
+
+pub type Hash = [u8; 32];
+
+#[cfg(feature = "grid-experimental")]
+/// 조각 데이터의 리프 해시 (메타데이터의 조각 해시 알고리즘, 내부 노드는 항상 SHA-256)
+pub fn leaf_hash(algorithm: HashAlgorithm, data: &[u8]) -> Hash {
+    algorithm.digest(data)
+}
+
+fn parent_hash(left: &Hash, right: &Hash) -> Hash {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+fn next_level(level: &[Hash]) -> Vec<Hash> {
+    level
+        .chunks(2)
+        .map(|pair| parent_hash(&pair[0], pair.get(1).unwrap_or(&pair[0])))
+        .collect()
+}
+
+/// Merkle 루트 계산
+pub fn merkle_root(leaves: &[Hash]) -> Hash {
+    if leaves.is_empty() {
+        return [0u8; 32];
+    }
+
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    level[0]
+}
+
+#[cfg(feature = "grid-experimental")]
+/// index 번째 리프의 포함 증명 (리프 -> 루트 방향 형제 해시 목록)
+pub fn merkle_proof(leaves: &[Hash], index: usize) -> Option<Vec<Hash>> {
+    if index >= leaves.len() {
+        return None;
+    }
+
+    let mut proof = Vec::new();
+    let mut level = leaves.to_vec();
+    let mut idx = index;
+
+    while level.len() > 1 {
+        let sibling = if idx % 2 == 0 {
+            *level.get(idx + 1).unwrap_or(&level[idx])
+        } else {
+            level[idx - 1]
+        };
+        proof.push(sibling);
+
+        level = next_level(&level);
+        idx /= 2;
+    }
+
+    Some(proof)
+}
+
+#[cfg(feature = "grid-experimental")]
+/// 포함 증명 검증
+pub fn verify_proof(root: &Hash, leaf: &Hash, index: usize, total: usize, proof: &[Hash]) -> bool {
+    if index >= total {
+        return false;
+    }
+
+    let mut hash = *leaf;
+    let mut idx = index;
+    let mut width = total;
+    let mut siblings = proof.iter();
+
+    while width > 1 {
+        let Some(sibling) = siblings.next() else {
+            return false;
+        };
+
+        // 짝이 없는 마지막 노드는 자기 자신과 해시되어야 함
+        if idx % 2 == 0 && idx + 1 == width && *sibling != hash {
+            return false;
+        }
+
+        hash = if idx % 2 == 0 {
+            parent_hash(&hash, sibling)
+        } else {
+            parent_hash(sibling, &hash)
+        };
+        idx /= 2;
+        width = width.div_ceil(2);
+    }
+
+    siblings.next().is_none() && hash == *root
+}
+
+#[cfg(all(test, feature = "grid-experimental"))]
+mod tests {
+    use super::*;
+
+    fn leaves(n: usize) -> Vec<Hash> {
+        (0..n)
+            .map(|i| leaf_hash(HashAlgorithm::Sha256, &(i as u64).to_le_bytes()))
+            .collect()
+    }
+
+    #[test]
+    fn test_proofs_verify_for_all_sizes() {
+        for n in 1..=9 {
+            let leaves = leaves(n);
+            let root = merkle_root(&leaves);
+
+            for (i, leaf) in leaves.iter().enumerate() {
+                let proof = merkle_proof(&leaves, i).unwrap();
+                assert!(verify_proof(&root, leaf, i, n, &proof), "n={} i={}", n, i);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rejects_wrong_leaf_or_index() {
+        let leaves = leaves(5);
+        let root = merkle_root(&leaves);
+        let proof = merkle_proof(&leaves, 2).unwrap();
+
+        assert!(!verify_proof(&root, &leaves[3], 2, 5, &proof));
+        assert!(!verify_proof(&root, &leaves[2], 3, 5, &proof));
+        assert!(!verify_proof(&root, &leaves[2], 2, 5, &proof[1..]));
+    }
+}
@@ -0,0 +1,292 @@
+//! Metadata Exchange - Info Hash만으로 FileMetadata를 피어에게서 받아오기
+//!
+//! 공유 링크처럼 Info Hash만 알고 있는 다운로더가 연결된 피어에게 조각 해시 목록을
+//! 묶음(chunk) 단위로 요청하고, 모두 모이면 Info Hash와 대조해 검증합니다.
+
+use crate::grid::hash_algo::HashAlgorithm;
+use crate::grid::piece_manager::FileMetadata;
+use crate::grid::protocol::{GridMessage, METADATA_CHUNK_HASHES};
+
+/// 조각 해시 목록을 나눈 묶음 수
+pub fn total_chunks(total_pieces: usize) -> u32 {
+    total_pieces.div_ceil(METADATA_CHUNK_HASHES) as u32
+}
+
+/// 보유한 메타데이터로 chunk 번째 응답 메시지 생성 (범위 밖이면 None)
+pub fn metadata_response(metadata: &FileMetadata, chunk: u32) -> Option<GridMessage> {
+    if !metadata.is_complete() {
+        return None;
+    }
+
+    let start = chunk as usize * METADATA_CHUNK_HASHES;
+    if start >= metadata.piece_hashes.len() {
+        return None;
+    }
+    let end = (start + METADATA_CHUNK_HASHES).min(metadata.piece_hashes.len());
+
+    Some(GridMessage::MetadataResponse {
+        info_hash: metadata.info_hash,
+        file_name: metadata.file_name.clone(),
+        file_size: metadata.file_size,
+        piece_size: metadata.piece_size,
+        total_pieces: metadata.total_pieces,
+        chunk,
+        total_chunks: total_chunks(metadata.total_pieces),
+        piece_hashes: metadata.piece_hashes[start..end].to_vec(),
+    })
+}
+
+/// 수신한 메타데이터 묶음을 모아 검증하는 조립기
+pub struct MetadataAssembler {
+    info_hash: [u8; 32],
+    header: Option<MetadataHeader>,
+    chunks: Vec<Option<Vec<[u8; 32]>>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MetadataHeader {
+    file_name: String,
+    file_size: u64,
+    piece_size: u32,
+    total_pieces: usize,
+}
+
+impl MetadataAssembler {
+    pub fn new(info_hash: [u8; 32]) -> Self {
+        Self {
+            info_hash,
+            header: None,
+            chunks: Vec::new(),
+        }
+    }
+
+    pub fn info_hash(&self) -> &[u8; 32] {
+        &self.info_hash
+    }
+
+    /// 다음에 요청할 묶음 번호 (헤더를 모르면 0번부터)
+    pub fn next_missing_chunk(&self) -> Option<u32> {
+        if self.header.is_none() {
+            return Some(0);
+        }
+        self.chunks.iter().position(|c| c.is_none()).map(|i| i as u32)
+    }
+
+    /// 요청 메시지 생성
+    pub fn next_request(&self) -> Option<GridMessage> {
+        self.next_missing_chunk().map(|chunk| GridMessage::MetadataRequest {
+            info_hash: self.info_hash,
+            chunk,
+        })
+    }
+
+    /// 응답 묶음 추가
+    ///
+    /// 모든 묶음이 모여 Info Hash 검증까지 통과하면 완성된 메타데이터를 반환합니다.
+    /// 검증 실패 시 수집한 상태를 초기화하고 에러를 반환합니다.
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_chunk(
+        &mut self,
+        info_hash: [u8; 32],
+        file_name: String,
+        file_size: u64,
+        piece_size: u32,
+        total_pieces: usize,
+        chunk: u32,
+        total_chunks: u32,
+        piece_hashes: Vec<[u8; 32]>,
+    ) -> Result<Option<FileMetadata>, String> {
+        if info_hash != self.info_hash {
+            return Err("Metadata info hash mismatch".to_string());
+        }
+
+        let header = MetadataHeader {
+            file_name,
+            file_size,
+            piece_size,
+            total_pieces,
+        };
+        Self::validate_header(&header, total_chunks)?;
+
+        match self.header {
+            Some(ref known) if *known != header => {
+                return Err("Metadata header differs from previous chunks".to_string());
+            }
+            Some(_) => {}
+            None => {
+                self.chunks = vec![None; total_chunks as usize];
+                self.header = Some(header.clone());
+            }
+        }
+
+        let index = chunk as usize;
+        if index >= self.chunks.len() {
+            return Err(format!("Metadata chunk out of range: {}", chunk));
+        }
+
+        let expected = if index + 1 == self.chunks.len() {
+            total_pieces - index * METADATA_CHUNK_HASHES
+        } else {
+            METADATA_CHUNK_HASHES
+        };
+        if piece_hashes.len() != expected {
+            return Err(format!(
+                "Metadata chunk {} has {} hashes (expected {})",
+                chunk,
+                piece_hashes.len(),
+                expected
+            ));
+        }
+
+        self.chunks[index] = Some(piece_hashes);
+
+        if self.chunks.iter().any(|c| c.is_none()) {
+            return Ok(None);
+        }
+
+        let piece_hashes: Vec<[u8; 32]> = self
+            .chunks
+            .iter()
+            .flatten()
+            .flatten()
+            .copied()
+            .collect();
+
+        // Info Hash에 알고리즘이 반영되어 있으므로 일치하는 알고리즘이 곧 조각 해시 알고리즘
+        let Some(algorithm) = HashAlgorithm::ALL.into_iter().find(|algorithm| {
+            FileMetadata::compute_info_hash_for(*algorithm, &piece_hashes) == self.info_hash
+        }) else {
+            self.reset();
+            return Err("Metadata failed info hash verification".to_string());
+        };
+
+        Ok(Some(FileMetadata::from_piece_hashes_with_algorithm(
+            header.file_name,
+            header.file_size,
+            header.piece_size,
+            piece_hashes,
+            algorithm,
+        )))
+    }
+
+    fn validate_header(header: &MetadataHeader, total_chunks: u32) -> Result<(), String> {
+        if header.piece_size == 0 || header.total_pieces == 0 {
+            return Err("Metadata has no pieces".to_string());
+        }
+        let expected_pieces = header.file_size.div_ceil(header.piece_size as u64) as usize;
+        if expected_pieces != header.total_pieces {
+            return Err(format!(
+                "Metadata piece count mismatch: {} vs {}",
+                header.total_pieces, expected_pieces
+            ));
+        }
+        if total_chunks != self::total_chunks(header.total_pieces) {
+            return Err(format!("Metadata chunk count mismatch: {}", total_chunks));
+        }
+        Ok(())
+    }
+
+    /// 수집 상태 초기화 (다른 피어에게서 다시 받기)
+    pub fn reset(&mut self) {
+        self.header = None;
+        self.chunks.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_metadata(total_pieces: usize) -> FileMetadata {
+        let hashes = (0..total_pieces)
+            .map(|i| {
+                let mut h = [0u8; 32];
+                h[..8].copy_from_slice(&(i as u64).to_le_bytes());
+                h
+            })
+            .collect();
+        FileMetadata::from_piece_hashes(
+            "big.iso".to_string(),
+            total_pieces as u64 * 16,
+            16,
+            hashes,
+        )
+    }
+
+    fn feed(
+        assembler: &mut MetadataAssembler,
+        msg: GridMessage,
+    ) -> Result<Option<FileMetadata>, String> {
+        match msg {
+            GridMessage::MetadataResponse {
+                info_hash,
+                file_name,
+                file_size,
+                piece_size,
+                total_pieces,
+                chunk,
+                total_chunks,
+                piece_hashes,
+            } => assembler.add_chunk(
+                info_hash,
+                file_name,
+                file_size,
+                piece_size,
+                total_pieces,
+                chunk,
+                total_chunks,
+                piece_hashes,
+            ),
+            _ => panic!("Wrong message type"),
+        }
+    }
+
+    #[test]
+    fn test_assemble_multiple_chunks() {
+        let metadata = sample_metadata(METADATA_CHUNK_HASHES * 2 + 3);
+        let mut assembler = MetadataAssembler::new(metadata.info_hash);
+
+        let mut result = None;
+        while let Some(chunk) = assembler.next_missing_chunk() {
+            let msg = metadata_response(&metadata, chunk).unwrap();
+            result = feed(&mut assembler, msg).unwrap();
+        }
+
+        let assembled = result.unwrap();
+        assert_eq!(assembled.info_hash, metadata.info_hash);
+        assert_eq!(assembled.piece_hashes, metadata.piece_hashes);
+        assert!(metadata_response(&metadata, 3).is_none());
+    }
+
+    #[test]
+    fn test_rejects_tampered_hashes() {
+        let metadata = sample_metadata(4);
+        let mut assembler = MetadataAssembler::new(metadata.info_hash);
+
+        // info_hash는 원본 그대로 두고 해시 목록만 변조
+        let mut tampered = metadata.clone();
+        tampered.piece_hashes[1] = [0xff; 32];
+        let msg = metadata_response(&tampered, 0).unwrap();
+
+        assert!(feed(&mut assembler, msg).is_err());
+        assert_eq!(assembler.next_missing_chunk(), Some(0));
+    }
+    #[test]
+    fn test_detects_hash_algorithm_from_info_hash() {
+        let plain = sample_metadata(3);
+        let metadata = FileMetadata::from_piece_hashes_with_algorithm(
+            plain.file_name.clone(),
+            plain.file_size,
+            plain.piece_size,
+            plain.piece_hashes.clone(),
+            HashAlgorithm::Blake3,
+        );
+        assert_ne!(metadata.info_hash, plain.info_hash);
+
+        let mut assembler = MetadataAssembler::new(metadata.info_hash);
+        let msg = metadata_response(&metadata, 0).unwrap();
+        let assembled = feed(&mut assembler, msg).unwrap().unwrap();
+        assert_eq!(assembled.hash_algorithm, HashAlgorithm::Blake3);
+        assert_eq!(assembled.info_hash, metadata.info_hash);
+    }
+}
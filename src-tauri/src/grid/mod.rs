@@ -5,35 +5,68 @@
 //! ## 모듈 구조
 //! - `bitfield`: 조각 보유 현황 비트맵
 //! - `piece_manager`: 파일 조각 및 검증 관리
+//! - `merkle`: Merkle Tree 포함 증명 생성/검증
 //! - `protocol`: Grid 메시지 프로토콜 (Handshake, Request, Piece 등)
 //! - `scheduler`: Rare-First 스케줄링 알고리즘
 //! - `swarm`: Multi-Peer Connection Manager
-//! - `dht`: Kademlia DHT (Trackerless Discovery)
+//! - `metadata_exchange`: Info Hash 기반 메타데이터 교환
+//! - `job`: 앱에서 실행 중인 Swarm 작업 관리
+//! - `rate_limit`: Swarm 단위 업로드/다운로드 속도 제한
+//! - `resume`: 다운로드 진행 상태 저장 및 복원
+//! - `share_link`: 매그넷 스타일 공유 링크 (pons://)
+//! - `pons_file`: 메타데이터 전체를 담는 오프라인 공유 파일 (.pons)
+//! - `web_seed`: HTTP(S) 파일 서버를 추가 소스로 사용
+//! - `peer_score`: 불량 피어 벌점 및 영속 차단 목록
+//! - `speed`: 최근 구간(EWMA) 기준 전송 속도 추정
+//! - `hash_cache`: 바뀌지 않은 파일의 조각 해시 재사용
+//! - `hash_algo`: 조각 해시 알고리즘 (SHA-256 / BLAKE3)
+//! - `discovery_bridge`: DHT/mDNS로 발견한 피어를 Swarm에 자동 연결
+//!
+//! DHT는 내장 부트스트랩과 공유하는 `crate::dht`를 사용합니다.
 
 pub mod bitfield;
 pub mod bootstrap_discovery;
+pub mod hash_algo;
+pub mod hash_cache;
+pub mod merkle;
 pub mod piece_manager;
+pub mod rate_limit;
+pub mod share_link;
 
 // NOTE: Grid 내부 구현 타입들은 현재 외부로 re-export 하지 않습니다.
 // (사용 시 `grid::bitfield::Bitfield` 처럼 모듈 경로로 접근)
+// `piece_manager`와 `hash_algo`/`hash_cache`/`merkle`(루트 계산)은 기본 빌드의 다중 소스 다운로드와
+// 작업 매니페스트도 쓰므로 feature 밖에 둡니다.
 
 // Phase 2 (WIP) - 아직 앱의 기본 플로우에서 사용하지 않으므로, 기본 빌드 경고/크기/컴파일 시간을 줄이기 위해 feature로 분리
 // 필요 시 `--features grid-experimental` 로 활성화
 #[cfg(feature = "grid-experimental")]
-pub mod dht;
+pub mod discovery_bridge;
 #[cfg(feature = "grid-experimental")]
 pub mod hybrid_discovery;
 #[cfg(feature = "grid-experimental")]
+pub mod job;
+#[cfg(feature = "grid-experimental")]
+pub mod metadata_exchange;
+#[cfg(feature = "grid-experimental")]
 pub mod peer;
 #[cfg(feature = "grid-experimental")]
+pub mod peer_score;
+#[cfg(feature = "grid-experimental")]
+pub mod pons_file;
+#[cfg(feature = "grid-experimental")]
 pub mod protocol;
 #[cfg(feature = "grid-experimental")]
+pub mod resume;
+#[cfg(feature = "grid-experimental")]
 pub mod scheduler;
 #[cfg(feature = "grid-experimental")]
+pub mod speed;
+#[cfg(feature = "grid-experimental")]
 pub mod swarm;
-
 #[cfg(feature = "grid-experimental")]
-pub use dht::{DhtCommand, DhtEvent, DhtService};
+pub mod web_seed;
+
 #[cfg(feature = "grid-experimental")]
 pub use peer::{Peer, PeerCommand, PeerEvent};
 #[cfg(feature = "grid-experimental")]
@@ -46,10 +79,29 @@ pub use swarm::{GridSwarm, SwarmCommand, SwarmEvent};
 use serde::Serialize;
 use tauri::{AppHandle, Emitter};
 
+/// Grid 작업 상태 (상태 머신)
+#[derive(Clone, Copy, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum GridJobState {
+    /// 시작 전
+    Idle,
+    /// Info Hash만으로 시작하여 메타데이터 수신 중
+    FetchingMetadata,
+    /// 다운로드 중
+    Downloading,
+    /// 모든 조각 보유, 업로드만 수행
+    Seeding,
+    /// 일시정지 (모든 피어 Choke)
+    Paused,
+    /// 중지됨
+    Stopped,
+}
+
 /// Grid 상태 업데이트 (프론트엔드 전송용)
 #[derive(Clone, Serialize, Debug)]
 pub struct GridStateUpdate {
     pub job_id: String,
+    pub state: GridJobState,
     pub total_pieces: usize,
     pub completed_pieces: Vec<usize>,
     pub peers: Vec<PeerStatus>,
@@ -99,12 +151,44 @@ pub mod config {
     /// 최대 동시 요청 수 (피어당)
     pub const MAX_PENDING_REQUESTS: usize = 16;
 
+    /// 🆕 피어당 조각 요청 스트림 수 (MULTI_STREAM 확장 협상 시)
+    #[cfg(feature = "grid-experimental")]
+    pub const STREAMS_PER_PEER: usize = 4;
+
+    /// 조각 요청 응답 제한 시간 (초과 시 다른 피어에게 재배정)
+    #[cfg(feature = "grid-experimental")]
+    pub const REQUEST_TIMEOUT_SECS: u64 = 30;
+
     /// Keep-Alive 간격 (초)
     pub const KEEPALIVE_INTERVAL_SECS: u64 = 30;
 
     /// 연결 타임아웃 (초)
     pub const CONNECTION_TIMEOUT_SECS: u64 = 30;
 
+    /// Web Seed 동시 요청 수
+    #[cfg(feature = "grid-experimental")]
+    pub const WEB_SEED_MAX_INFLIGHT: usize = 4;
+
+    /// 이 수 이하의 피어만 보유한 조각은 Web Seed에서 받음
+    #[cfg(feature = "grid-experimental")]
+    pub const WEB_SEED_AVAILABILITY_THRESHOLD: usize = 1;
+
+    /// 이 시간 안에 끊긴 연결은 churn으로 간주 (초)
+    #[cfg(feature = "grid-experimental")]
+    pub const CHURN_MIN_CONNECTION_SECS: u64 = 10;
+
+    /// 동시에 Unchoke 할 피어 수 (속도 상위, 낙관적 Unchoke 1명 별도)
+    #[cfg(feature = "grid-experimental")]
+    pub const MAX_UNCHOKED_PEERS: usize = 4;
+
+    /// Choke 재계산 간격 (초)
+    #[cfg(feature = "grid-experimental")]
+    pub const CHOKE_INTERVAL_SECS: u64 = 10;
+
+    /// PEX 전송 간격 (초)
+    #[cfg(feature = "grid-experimental")]
+    pub const PEX_INTERVAL_SECS: u64 = 60;
+
     /// DHT 부트스트랩 노드 (사내망 고정 노드)
     pub const DHT_BOOTSTRAP_NODES: &[&str] = &[];
 }
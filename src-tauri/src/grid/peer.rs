@@ -1,15 +1,27 @@
 //! Peer - 개별 피어와의 연결 및 메시지 처리
 //!
 //! 하나의 피어와 지속적으로 메시지를 주고받는 전담 처리 태스크입니다.
+//!
+//! 양쪽이 MULTI_STREAM 확장을 지원하면 Handshake 스트림은 제어 메시지에만 쓰고,
+//! 조각 요청은 스케줄러가 정한 추가 양방향 스트림으로 보냅니다. 받는 쪽은 Piece를
+//! 요청이 온 스트림으로 돌려보내므로, 느린 조각 하나가 다른 요청을 막지 않습니다.
 
 use crate::grid::bitfield::Bitfield;
+use crate::grid::config;
+use crate::grid::metadata_exchange;
 use crate::grid::piece_manager::PieceManager;
-use crate::grid::protocol::GridMessage;
+use crate::grid::protocol::{
+    extensions, negotiate_handshake, GridMessage, HandshakeError, NegotiatedHandshake,
+    HANDSHAKE_TIMEOUT_SECS,
+};
+use crate::grid::speed::RateEstimator;
 use quinn::{Connection, RecvStream, SendStream};
+use std::collections::{HashMap, HashSet};
+use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::{mpsc, RwLock};
-use tokio::time::interval;
+use tokio::time::{interval, timeout};
 use tracing::{debug, error, info, warn};
 
 /// 개별 피어 제어 명령
@@ -17,6 +29,8 @@ use tracing::{debug, error, info, warn};
 pub enum PeerCommand {
     /// 메시지 전송
     SendMessage(GridMessage),
+    /// 🆕 조각 요청을 지정한 스트림으로 전송 (MULTI_STREAM 미지원 피어는 메인 스트림)
+    SendOnStream { stream: usize, message: GridMessage },
     /// 연결 종료
     Disconnect,
     /// Choke 상태 변경
@@ -34,6 +48,8 @@ pub enum PeerEvent {
     HandshakeComplete {
         peer_id: String,
         info_hash: [u8; 32],
+        /// 양쪽이 모두 지원하는 확장 기능
+        extensions: u64,
     },
     /// Bitfield 수신
     BitfieldReceived { peer_id: String, pieces: Vec<usize> },
@@ -45,6 +61,7 @@ pub enum PeerEvent {
         piece_index: u32,
         offset: u32,
         data: Vec<u8>,
+        proof: Vec<[u8; 32]>,
     },
     /// 조각 요청 수신
     RequestReceived {
@@ -53,10 +70,30 @@ pub enum PeerEvent {
         offset: u32,
         length: u32,
     },
+    /// 🆕 조각 요청 취소 수신
+    CancelReceived {
+        peer_id: String,
+        piece_index: u32,
+        offset: u32,
+        length: u32,
+    },
     /// Choke 상태 변경
     ChokeChanged { peer_id: String, choked: bool },
     /// Interest 상태 변경
     InterestChanged { peer_id: String, interested: bool },
+    /// PEX 수신 (상대가 알고 있는 다른 Swarm 구성원)
+    PexReceived {
+        peer_id: String,
+        added: Vec<SocketAddr>,
+        dropped: Vec<SocketAddr>,
+    },
+    /// 메타데이터 묶음 수신 (MetadataResponse 원본 메시지)
+    MetadataReceived {
+        peer_id: String,
+        message: GridMessage,
+    },
+    /// 프로토콜 위반 (연결 종료 직전 발생)
+    ProtocolViolation { peer_id: String, reason: String },
     /// 에러 발생
     Error { peer_id: String, message: String },
 }
@@ -67,6 +104,10 @@ pub struct PeerState {
     pub peer_id: String,
     pub remote_addr: String,
     pub info_hash: Option<[u8; 32]>,
+    /// Handshake로 받은 상대 피어 ID
+    pub remote_peer_id: Option<[u8; 32]>,
+    /// 협상된 확장 기능
+    pub extensions: u64,
     pub bitfield: Option<Bitfield>,
     /// 내가 상대방을 Choke 했는지
     pub am_choking: bool,
@@ -84,6 +125,10 @@ pub struct PeerState {
     pub bytes_downloaded: u64,
     /// 업로드 바이트
     pub bytes_uploaded: u64,
+    /// 최근 다운로드 속도 추정
+    pub download_rate: RateEstimator,
+    /// 최근 업로드 속도 추정
+    pub upload_rate: RateEstimator,
     /// RTT (밀리초)
     pub rtt_ms: Option<u32>,
 }
@@ -95,6 +140,8 @@ impl PeerState {
             peer_id,
             remote_addr,
             info_hash: None,
+            remote_peer_id: None,
+            extensions: 0,
             bitfield: None,
             am_choking: true,
             am_interested: false,
@@ -104,20 +151,143 @@ impl PeerState {
             last_message_at: now,
             bytes_downloaded: 0,
             bytes_uploaded: 0,
+            download_rate: RateEstimator::default(),
+            upload_rate: RateEstimator::default(),
             rtt_ms: None,
         }
     }
 
-    /// 다운로드 속도 (bytes/sec)
+    /// 다운로드 기록
+    pub fn record_download(&mut self, bytes: u64) {
+        self.bytes_downloaded += bytes;
+        self.download_rate.record(bytes);
+    }
+
+    /// 업로드 기록
+    pub fn record_upload(&mut self, bytes: u64) {
+        self.bytes_uploaded += bytes;
+        self.upload_rate.record(bytes);
+    }
+
+    /// 최근 다운로드 속도 (bytes/sec)
     pub fn download_speed(&self) -> u64 {
-        let elapsed = self.connected_at.elapsed().as_secs().max(1);
-        self.bytes_downloaded / elapsed
+        self.download_rate.rate()
     }
 
-    /// 업로드 속도 (bytes/sec)
+    /// 최근 업로드 속도 (bytes/sec)
     pub fn upload_speed(&self) -> u64 {
-        let elapsed = self.connected_at.elapsed().as_secs().max(1);
-        self.bytes_uploaded / elapsed
+        self.upload_rate.rate()
+    }
+
+    /// 협상된 확장 기능 지원 여부
+    pub fn supports(&self, extension: u64) -> bool {
+        self.extensions & extension != 0
+    }
+}
+
+/// 🆕 추가 스트림 쓰기 대기열 크기
+const DATA_STREAM_QUEUE: usize = 16;
+
+/// 🆕 기억해 두는 취소된 요청 수 상한
+const MAX_CANCELLED_REQUESTS: usize = 256;
+
+/// 🆕 추가 스트림에서 읽은 메시지 (None이면 스트림 종료)
+type DataStreamEvent = (u64, Option<GridMessage>);
+
+/// 🆕 Request/Piece용 추가 스트림 (MULTI_STREAM 확장)
+struct DataStreams {
+    /// 스트림 ID -> 쓰기 태스크 채널
+    senders: HashMap<u64, mpsc::Sender<GridMessage>>,
+    /// 요청 슬롯 번호 -> 내가 연 스트림 ID
+    slots: HashMap<usize, u64>,
+    /// 상대가 연 스트림 ID
+    accepted: HashSet<u64>,
+    /// 받은 요청 (조각, 오프셋) -> 응답할 스트림 ID
+    reply_routes: HashMap<(u32, u32), u64>,
+    next_id: u64,
+    inbound_tx: mpsc::Sender<DataStreamEvent>,
+}
+
+impl DataStreams {
+    fn new(inbound_tx: mpsc::Sender<DataStreamEvent>) -> Self {
+        Self {
+            senders: HashMap::new(),
+            slots: HashMap::new(),
+            accepted: HashSet::new(),
+            reply_routes: HashMap::new(),
+            next_id: 0,
+            inbound_tx,
+        }
+    }
+
+    /// 스트림의 읽기/쓰기 태스크 시작
+    fn add(&mut self, mut send: SendStream, mut recv: RecvStream) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let (tx, mut rx) = mpsc::channel::<GridMessage>(DATA_STREAM_QUEUE);
+        tauri::async_runtime::spawn(async move {
+            while let Some(msg) = rx.recv().await {
+                if let Err(e) = msg.write_to(&mut send).await {
+                    debug!("추가 스트림 #{} 쓰기 실패: {}", id, e);
+                    return;
+                }
+            }
+            let _ = send.finish();
+        });
+
+        let inbound_tx = self.inbound_tx.clone();
+        tauri::async_runtime::spawn(async move {
+            while let Ok(msg) = GridMessage::read_from(&mut recv).await {
+                if inbound_tx.send((id, Some(msg))).await.is_err() {
+                    return;
+                }
+            }
+            let _ = inbound_tx.send((id, None)).await;
+        });
+
+        self.senders.insert(id, tx);
+        id
+    }
+
+    fn remove(&mut self, id: u64) {
+        self.senders.remove(&id);
+        self.accepted.remove(&id);
+        self.slots.retain(|_, stream| *stream != id);
+        self.reply_routes.retain(|_, stream| *stream != id);
+    }
+
+    fn slot_sender(&self, slot: usize) -> Option<mpsc::Sender<GridMessage>> {
+        self.slots
+            .get(&slot)
+            .and_then(|id| self.senders.get(id))
+            .cloned()
+    }
+
+    /// 스트림으로 받은 요청 기록 (Piece 응답을 같은 스트림으로 보내기 위해)
+    fn record_request(&mut self, id: u64, msg: &GridMessage) {
+        if let GridMessage::Request {
+            piece_index,
+            offset,
+            ..
+        } = msg
+        {
+            self.reply_routes.insert((*piece_index, *offset), id);
+        }
+    }
+
+    /// 추가 스트림으로 온 요청에 대한 Piece 응답이면 그 스트림의 채널
+    fn reply_sender(&mut self, msg: &GridMessage) -> Option<mpsc::Sender<GridMessage>> {
+        let GridMessage::Piece {
+            piece_index,
+            offset,
+            ..
+        } = msg
+        else {
+            return None;
+        };
+        let id = self.reply_routes.remove(&(*piece_index, *offset))?;
+        self.senders.get(&id).cloned()
     }
 }
 
@@ -129,6 +299,10 @@ pub struct Peer {
     command_rx: mpsc::Receiver<PeerCommand>,
     event_tx: mpsc::Sender<PeerEvent>,
     my_peer_id: [u8; 32],
+    /// 연결을 연 쪽인지 (Handshake 스트림을 여는 쪽)
+    initiator: bool,
+    /// 🆕 상대가 취소한 요청 (조각, 오프셋) - 이미 대기열에 들어온 Piece 응답은 보내지 않음
+    cancelled: HashSet<(u32, u32)>,
 }
 
 impl Peer {
@@ -138,6 +312,7 @@ impl Peer {
         command_rx: mpsc::Receiver<PeerCommand>,
         event_tx: mpsc::Sender<PeerEvent>,
         my_peer_id: [u8; 32],
+        initiator: bool,
     ) -> Self {
         let remote_addr = connection.remote_address().to_string();
         let peer_id = format!("peer_{}", &remote_addr);
@@ -149,6 +324,8 @@ impl Peer {
             command_rx,
             event_tx,
             my_peer_id,
+            initiator,
+            cancelled: HashSet::new(),
         }
     }
 
@@ -161,11 +338,18 @@ impl Peer {
     pub async fn run(mut self) {
         info!("🔗 피어 연결 시작: {}", self.state.remote_addr);
 
-        // 양방향 스트림 열기
-        let (send_stream, recv_stream) = match self.connection.open_bi().await {
+        let deadline = Duration::from_secs(HANDSHAKE_TIMEOUT_SECS);
+        let result = match timeout(deadline, self.perform_handshake()).await {
+            Ok(result) => result,
+            Err(_) => Err(HandshakeError::Timeout),
+        };
+
+        let (send_stream, recv_stream) = match result {
             Ok(streams) => streams,
             Err(e) => {
-                error!("❌ 스트림 열기 실패: {}", e);
+                error!("❌ Handshake 실패: {} - {}", self.state.remote_addr, e);
+                self.connection
+                    .close(e.code().into(), e.to_string().as_bytes());
                 self.send_event(PeerEvent::Disconnected {
                     peer_id: self.state.peer_id.clone(),
                     reason: e.to_string(),
@@ -175,17 +359,6 @@ impl Peer {
             }
         };
 
-        // Handshake 수행
-        if let Err(e) = self.perform_handshake(&send_stream, &recv_stream).await {
-            error!("❌ Handshake 실패: {}", e);
-            self.send_event(PeerEvent::Disconnected {
-                peer_id: self.state.peer_id.clone(),
-                reason: e.to_string(),
-            })
-            .await;
-            return;
-        }
-
         // 메인 루프
         self.message_loop(send_stream, recv_stream).await;
 
@@ -193,31 +366,87 @@ impl Peer {
     }
 
     /// Handshake 수행
-    async fn perform_handshake(
-        &mut self,
-        send_stream: &SendStream,
-        recv_stream: &RecvStream,
-    ) -> anyhow::Result<()> {
+    ///
+    /// 연결을 연 쪽이 첫 양방향 스트림을 열고 Handshake를 먼저 보내며,
+    /// 받는 쪽은 검증 후 응답합니다. 실패 시 상대에게 Error 메시지를 보냅니다.
+    async fn perform_handshake(&mut self) -> Result<(SendStream, RecvStream), HandshakeError> {
+        let info_hash = *self.piece_manager.read().await.info_hash();
+        let handshake = GridMessage::handshake(info_hash, self.my_peer_id);
+        let io = |e: &dyn std::fmt::Display| HandshakeError::Io(e.to_string());
+
+        let (mut send_stream, mut recv_stream) = if self.initiator {
+            let (mut send, recv) = self.connection.open_bi().await.map_err(|e| io(&e))?;
+            handshake.write_to(&mut send).await.map_err(|e| io(&e))?;
+            (send, recv)
+        } else {
+            self.connection.accept_bi().await.map_err(|e| io(&e))?
+        };
+
+        let remote = GridMessage::read_from(&mut recv_stream)
+            .await
+            .map_err(|e| io(&e))?;
+        let negotiated =
+            match negotiate_handshake(&remote, &info_hash, &self.my_peer_id, extensions::SUPPORTED)
+            {
+                Ok(negotiated) => negotiated,
+                Err(e) => {
+                    let reject = GridMessage::Error {
+                        code: e.code(),
+                        message: e.to_string(),
+                    };
+                    let _ = reject.write_to(&mut send_stream).await;
+                    let _ = send_stream.finish();
+                    return Err(e);
+                }
+            };
+
+        if !self.initiator {
+            handshake
+                .write_to(&mut send_stream)
+                .await
+                .map_err(|e| io(&e))?;
+        }
+
+        self.apply_handshake(info_hash, &negotiated);
+        debug!(
+            "🤝 [{}] v{} 확장 {:#x}",
+            self.state.peer_id, negotiated.protocol_version, negotiated.extensions
+        );
+
+        self.send_event(PeerEvent::HandshakeComplete {
+            peer_id: self.state.peer_id.clone(),
+            info_hash,
+            extensions: negotiated.extensions,
+        })
+        .await;
+
+        // Bitfield 전송
         let pm = self.piece_manager.read().await;
-        let info_hash = *pm.info_hash();
+        let bf = pm.get_bitfield();
+        let bitfield_msg = GridMessage::bitfield(bf.as_bytes().to_vec(), bf.len());
         drop(pm);
+        bitfield_msg
+            .write_to(&mut send_stream)
+            .await
+            .map_err(|e| io(&e))?;
 
-        // Handshake 전송
-        let handshake = GridMessage::handshake(info_hash, self.my_peer_id);
-
-        // Note: QUIC SendStream은 &mut self를 요구하므로 별도 처리 필요
-        // 여기서는 개념적 구현만 제공
-        debug!("📤 Handshake 전송: {:?}", handshake.type_name());
+        Ok((send_stream, recv_stream))
+    }
 
-        // Handshake 수신 및 검증은 message_loop에서 처리
+    fn apply_handshake(&mut self, info_hash: [u8; 32], negotiated: &NegotiatedHandshake) {
+        // peer_id는 Swarm 맵 키로 쓰이므로 유지하고, 상대 ID는 별도 보관
         self.state.info_hash = Some(info_hash);
-
-        Ok(())
+        self.state.remote_peer_id = Some(negotiated.remote_peer_id);
+        self.state.extensions = negotiated.extensions;
+        self.state.last_message_at = Instant::now();
     }
 
     /// 메시지 루프
     async fn message_loop(&mut self, mut send_stream: SendStream, mut recv_stream: RecvStream) {
         let mut keepalive_interval = interval(Duration::from_secs(30));
+        let multi_stream = self.state.supports(extensions::MULTI_STREAM);
+        let (data_tx, mut data_rx) = mpsc::channel(64);
+        let mut streams = DataStreams::new(data_tx);
 
         loop {
             tokio::select! {
@@ -225,7 +454,16 @@ impl Peer {
                 cmd = self.command_rx.recv() => {
                     match cmd {
                         Some(PeerCommand::SendMessage(msg)) => {
-                            if let Err(e) = self.send_message(&mut send_stream, msg).await {
+                            if let Err(e) = self.send_reply(&mut send_stream, &mut streams, msg).await {
+                                error!("❌ 메시지 전송 실패: {}", e);
+                                break;
+                            }
+                        }
+                        Some(PeerCommand::SendOnStream { stream, message }) => {
+                            if let Err(e) = self
+                                .send_on_stream(&mut send_stream, &mut streams, stream, message)
+                                .await
+                            {
                                 error!("❌ 메시지 전송 실패: {}", e);
                                 break;
                             }
@@ -236,6 +474,10 @@ impl Peer {
                         }
                         Some(PeerCommand::SetChoked(choked)) => {
                             self.state.am_choking = choked;
+                            if choked {
+                                // Choke 시 받은 요청은 버리므로 응답 경로도 정리
+                                streams.reply_routes.clear();
+                            }
                             let msg = if choked { GridMessage::Choke } else { GridMessage::Unchoke };
                             let _ = self.send_message(&mut send_stream, msg).await;
                         }
@@ -255,6 +497,11 @@ impl Peer {
                             self.state.last_message_at = Instant::now();
                             if let Err(e) = self.handle_message(msg, &mut send_stream).await {
                                 error!("❌ 메시지 처리 실패: {}", e);
+                                self.send_event(PeerEvent::ProtocolViolation {
+                                    peer_id: self.state.peer_id.clone(),
+                                    reason: e.to_string(),
+                                })
+                                .await;
                                 break;
                             }
                         }
@@ -269,7 +516,46 @@ impl Peer {
                     }
                 }
 
-                // 3. Keep-Alive
+                // 3. 🆕 추가 스트림에서 받은 메시지
+                Some((id, msg)) = data_rx.recv() => {
+                    let Some(msg) = msg else {
+                        streams.remove(id);
+                        continue;
+                    };
+                    self.state.last_message_at = Instant::now();
+                    if !self.state.am_choking {
+                        streams.record_request(id, &msg);
+                    }
+                    if let Err(e) = self.handle_message(msg, &mut send_stream).await {
+                        error!("❌ 메시지 처리 실패: {}", e);
+                        self.send_event(PeerEvent::ProtocolViolation {
+                            peer_id: self.state.peer_id.clone(),
+                            reason: e.to_string(),
+                        })
+                        .await;
+                        break;
+                    }
+                }
+
+                // 4. 🆕 상대가 연 추가 스트림 수락
+                result = self.connection.accept_bi(), if multi_stream => {
+                    match result {
+                        Ok((send, recv)) if streams.accepted.len() < config::STREAMS_PER_PEER => {
+                            let id = streams.add(send, recv);
+                            streams.accepted.insert(id);
+                            debug!("🔀 [{}] 추가 스트림 #{} 수락", self.state.peer_id, id);
+                        }
+                        Ok(_) => {
+                            warn!("🚫 [{}] 추가 스트림 수 초과, 무시", self.state.peer_id);
+                        }
+                        Err(e) => {
+                            info!("📴 피어 연결 종료: {}", e);
+                            break;
+                        }
+                    }
+                }
+
+                // 5. Keep-Alive
                 _ = keepalive_interval.tick() => {
                     let _ = self.send_message(&mut send_stream, GridMessage::KeepAlive).await;
                 }
@@ -295,40 +581,93 @@ impl Peer {
         Ok(())
     }
 
-    /// 메시지 처리
-    async fn handle_message(
+    /// 🆕 추가 스트림으로 온 요청의 Piece 응답은 그 스트림으로, 나머지는 메인 스트림으로 전송
+    async fn send_reply(
         &mut self,
+        send_stream: &mut SendStream,
+        streams: &mut DataStreams,
         msg: GridMessage,
+    ) -> anyhow::Result<()> {
+        if let GridMessage::Piece {
+            piece_index,
+            offset,
+            ..
+        } = &msg
+        {
+            if self.cancelled.remove(&(*piece_index, *offset)) {
+                streams.reply_sender(&msg);
+                debug!(
+                    "🚫 [{}] 취소된 조각 {} 전송 생략",
+                    self.state.peer_id, piece_index
+                );
+                return Ok(());
+            }
+        }
+
+        let msg = match streams.reply_sender(&msg) {
+            Some(tx) => match tx.send(msg).await {
+                Ok(()) => return Ok(()),
+                // 스트림이 닫혔으면 메인 스트림으로 대신 전송
+                Err(mpsc::error::SendError(msg)) => msg,
+            },
+            None => msg,
+        };
+        self.send_message(send_stream, msg).await
+    }
+
+    /// 🆕 요청 슬롯의 스트림으로 전송 (필요하면 스트림을 새로 열고, 실패 시 메인 스트림)
+    async fn send_on_stream(
+        &mut self,
         send_stream: &mut SendStream,
+        streams: &mut DataStreams,
+        slot: usize,
+        msg: GridMessage,
     ) -> anyhow::Result<()> {
-        debug!("📥 [{}] {}", self.state.peer_id, msg.type_name());
+        if !self.state.supports(extensions::MULTI_STREAM) {
+            return self.send_message(send_stream, msg).await;
+        }
 
-        match msg {
-            GridMessage::Handshake {
-                info_hash, peer_id, ..
-            } => {
-                // Info Hash 검증
-                let pm = self.piece_manager.read().await;
-                if info_hash != *pm.info_hash() {
-                    warn!("❌ Info Hash 불일치");
-                    return Err(anyhow::anyhow!("Info hash mismatch"));
+        let tx = match streams.slot_sender(slot) {
+            Some(tx) => Some(tx),
+            None => match self.connection.open_bi().await {
+                Ok((send, recv)) => {
+                    let id = streams.add(send, recv);
+                    streams.slots.insert(slot, id);
+                    debug!("🔀 [{}] 추가 스트림 #{} 열림", self.state.peer_id, id);
+                    streams.slot_sender(slot)
                 }
-                drop(pm);
+                Err(e) => {
+                    warn!("추가 스트림 열기 실패: {}", e);
+                    None
+                }
+            },
+        };
 
-                self.state.peer_id = hex::encode(&peer_id[..8]);
-                self.send_event(PeerEvent::HandshakeComplete {
-                    peer_id: self.state.peer_id.clone(),
-                    info_hash,
-                })
-                .await;
+        let msg = match tx {
+            Some(tx) => {
+                debug!("📤 [{}#{}] {}", self.state.peer_id, slot, msg.type_name());
+                match tx.send(msg).await {
+                    Ok(()) => return Ok(()),
+                    Err(mpsc::error::SendError(msg)) => msg,
+                }
+            }
+            None => msg,
+        };
+        self.send_message(send_stream, msg).await
+    }
 
-                // Bitfield 전송
-                let pm = self.piece_manager.read().await;
-                let bf = pm.get_bitfield();
-                let bitfield_msg = GridMessage::bitfield(bf.as_bytes().to_vec(), bf.len());
-                drop(pm);
+    /// 메시지 처리
+    async fn handle_message(
+        &mut self,
+        msg: GridMessage,
+        send_stream: &mut SendStream,
+    ) -> anyhow::Result<()> {
+        debug!("📥 [{}] {}", self.state.peer_id, msg.type_name());
 
-                self.send_message(send_stream, bitfield_msg).await?;
+        match msg {
+            GridMessage::Handshake { .. } => {
+                // Handshake는 연결 시작 시 한 번만 허용
+                return Err(anyhow::anyhow!("Unexpected handshake after negotiation"));
             }
 
             GridMessage::Bitfield { data, length } => {
@@ -361,6 +700,8 @@ impl Peer {
                 offset,
                 length,
             } => {
+                self.cancelled.remove(&(piece_index, offset));
+
                 // Choke 상태면 무시
                 if self.state.am_choking {
                     debug!("🚫 Choked 상태에서 Request 무시");
@@ -380,20 +721,37 @@ impl Peer {
                 piece_index,
                 offset,
                 data,
+                proof,
             } => {
-                self.state.bytes_downloaded += data.len() as u64;
+                self.state.record_download(data.len() as u64);
 
                 self.send_event(PeerEvent::PieceReceived {
                     peer_id: self.state.peer_id.clone(),
                     piece_index,
                     offset,
                     data,
+                    proof,
                 })
                 .await;
             }
 
-            GridMessage::Cancel { .. } => {
-                // 요청 취소 처리 (구현 필요)
+            GridMessage::Cancel {
+                piece_index,
+                offset,
+                length,
+            } => {
+                // Endgame 중복 요청 등으로 필요 없어진 조각은 보내지 않음
+                if self.cancelled.len() < MAX_CANCELLED_REQUESTS {
+                    self.cancelled.insert((piece_index, offset));
+                }
+
+                self.send_event(PeerEvent::CancelReceived {
+                    peer_id: self.state.peer_id.clone(),
+                    piece_index,
+                    offset,
+                    length,
+                })
+                .await;
             }
 
             GridMessage::Choke => {
@@ -436,6 +794,39 @@ impl Peer {
                 // 연결 유지 확인
             }
 
+            GridMessage::MetadataRequest { info_hash, chunk } => {
+                let pm = self.piece_manager.read().await;
+                let response = if info_hash == *pm.info_hash() {
+                    metadata_exchange::metadata_response(pm.get_metadata(), chunk)
+                } else {
+                    None
+                };
+                drop(pm);
+
+                let msg = response.unwrap_or_else(|| GridMessage::Error {
+                    code: 404,
+                    message: format!("Metadata chunk {} unavailable", chunk),
+                });
+                self.send_message(send_stream, msg).await?;
+            }
+
+            GridMessage::Pex { added, dropped } => {
+                self.send_event(PeerEvent::PexReceived {
+                    peer_id: self.state.peer_id.clone(),
+                    added,
+                    dropped,
+                })
+                .await;
+            }
+
+            msg @ GridMessage::MetadataResponse { .. } => {
+                self.send_event(PeerEvent::MetadataReceived {
+                    peer_id: self.state.peer_id.clone(),
+                    message: msg,
+                })
+                .await;
+            }
+
             GridMessage::Error { code, message } => {
                 warn!("⚠️ 피어 에러: [{}] {}", code, message);
                 self.send_event(PeerEvent::Error {
@@ -444,10 +835,6 @@ impl Peer {
                 })
                 .await;
             }
-
-            _ => {
-                debug!("⚠️ 처리되지 않은 메시지: {}", msg.type_name());
-            }
         }
 
         Ok(())
@@ -0,0 +1,277 @@
+//! Peer Score - 악성/불량 피어 점수 및 차단 목록
+//!
+//! 검증 실패 조각, 잦은 재연결(churn), 프로토콜 위반마다 벌점을 누적하고
+//! 임계값을 넘은 IP는 일정 시간 차단합니다. 차단 목록은 앱 데이터 디렉토리에
+//! 저장되어 재시작 후에도 유지되며, 모든 Grid 작업이 공유합니다.
+
+use crate::util::now_secs;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// 차단 목록 파일명
+pub const BAN_LIST_FILE: &str = "ban_list.json";
+
+/// 벌점 임계값 (이상이면 차단)
+pub const BAN_SCORE_THRESHOLD: u32 = 100;
+
+/// 차단 유지 시간
+pub const BAN_DURATION: Duration = Duration::from_secs(60 * 60);
+
+/// 벌점 감소 주기 (이 시간마다 1점씩 회복)
+const SCORE_DECAY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 벌점 사유
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Offense {
+    /// 검증에 실패한 조각 전송
+    CorruptPiece,
+    /// 프로토콜 위반 (잘못된 메시지, 범위 밖 요청 등)
+    ProtocolViolation,
+    /// 연결 직후 끊기를 반복
+    Churn,
+}
+
+impl Offense {
+    pub fn weight(&self) -> u32 {
+        match self {
+            Offense::CorruptPiece => 40,
+            Offense::ProtocolViolation => 25,
+            Offense::Churn => 10,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Offense::CorruptPiece => "corrupt piece",
+            Offense::ProtocolViolation => "protocol violation",
+            Offense::Churn => "connection churn",
+        }
+    }
+}
+
+/// 피어(IP)별 벌점
+#[derive(Debug, Clone)]
+pub struct PeerScore {
+    penalty: u32,
+    last_decay: Instant,
+    pub corrupt_pieces: u32,
+    pub violations: u32,
+    pub churns: u32,
+}
+
+impl Default for PeerScore {
+    fn default() -> Self {
+        Self {
+            penalty: 0,
+            last_decay: Instant::now(),
+            corrupt_pieces: 0,
+            violations: 0,
+            churns: 0,
+        }
+    }
+}
+
+impl PeerScore {
+    /// 벌점 기록 후 현재 벌점 반환
+    pub fn record(&mut self, offense: Offense) -> u32 {
+        self.decay();
+        match offense {
+            Offense::CorruptPiece => self.corrupt_pieces += 1,
+            Offense::ProtocolViolation => self.violations += 1,
+            Offense::Churn => self.churns += 1,
+        }
+        self.penalty = self.penalty.saturating_add(offense.weight());
+        self.penalty
+    }
+
+    /// 현재 벌점 (시간 경과에 따라 감소)
+    pub fn penalty(&mut self) -> u32 {
+        self.decay();
+        self.penalty
+    }
+
+    pub fn should_ban(&mut self) -> bool {
+        self.penalty() >= BAN_SCORE_THRESHOLD
+    }
+
+    fn decay(&mut self) {
+        let steps = (self.last_decay.elapsed().as_secs() / SCORE_DECAY_INTERVAL.as_secs()) as u32;
+        if steps > 0 {
+            self.penalty = self.penalty.saturating_sub(steps);
+            self.last_decay += SCORE_DECAY_INTERVAL * steps;
+        }
+    }
+}
+
+/// 차단 항목
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BanEntry {
+    pub ip: IpAddr,
+    pub reason: String,
+    /// 차단 시각 (Unix epoch 초)
+    pub banned_at: u64,
+    /// 해제 시각 (Unix epoch 초)
+    pub expires_at: u64,
+}
+
+/// 영속 차단 목록 (모든 Swarm 공유)
+pub struct BanList {
+    path: Option<PathBuf>,
+    entries: Mutex<HashMap<IpAddr, BanEntry>>,
+}
+
+impl BanList {
+    /// 저장하지 않는 메모리 전용 목록
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 파일에서 로드 (없거나 손상되었으면 빈 목록)
+    pub fn load(path: PathBuf) -> Self {
+        let now = now_secs();
+        let entries: HashMap<IpAddr, BanEntry> = match std::fs::read(&path) {
+            Ok(data) => match serde_json::from_slice::<Vec<BanEntry>>(&data) {
+                Ok(list) => list
+                    .into_iter()
+                    .filter(|e| e.expires_at > now)
+                    .map(|e| (e.ip, e))
+                    .collect(),
+                Err(e) => {
+                    warn!("차단 목록 파싱 실패, 초기화: {}", e);
+                    HashMap::new()
+                }
+            },
+            Err(_) => HashMap::new(),
+        };
+
+        if !entries.is_empty() {
+            info!("⛔ 차단 목록 로드: {}개", entries.len());
+        }
+
+        Self {
+            path: Some(path),
+            entries: Mutex::new(entries),
+        }
+    }
+
+    /// 차단 여부 (만료된 항목은 제거)
+    pub fn is_banned(&self, ip: &IpAddr) -> bool {
+        let mut entries = self.entries.lock();
+        match entries.get(ip) {
+            Some(entry) if entry.expires_at > now_secs() => true,
+            Some(_) => {
+                entries.remove(ip);
+                false
+            }
+            None => false,
+        }
+    }
+
+    /// IP 차단 후 저장
+    pub fn ban(&self, ip: IpAddr, reason: &str, duration: Duration) {
+        let now = now_secs();
+        self.entries.lock().insert(
+            ip,
+            BanEntry {
+                ip,
+                reason: reason.to_string(),
+                banned_at: now,
+                expires_at: now + duration.as_secs(),
+            },
+        );
+        warn!(
+            "⛔ 피어 차단: {} ({}, {}초)",
+            ip,
+            reason,
+            duration.as_secs()
+        );
+        self.persist();
+    }
+
+    /// 차단 해제 후 저장
+    pub fn unban(&self, ip: &IpAddr) -> bool {
+        let removed = self.entries.lock().remove(ip).is_some();
+        if removed {
+            self.persist();
+        }
+        removed
+    }
+
+    /// 유효한 차단 항목 목록
+    pub fn list(&self) -> Vec<BanEntry> {
+        let now = now_secs();
+        let mut entries = self.entries.lock();
+        entries.retain(|_, e| e.expires_at > now);
+        entries.values().cloned().collect()
+    }
+
+    fn persist(&self) {
+        let Some(ref path) = self.path else {
+            return;
+        };
+        let list = self.list();
+
+        let result = (|| -> anyhow::Result<()> {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            let tmp = path.with_extension("json.tmp");
+            std::fs::write(&tmp, serde_json::to_vec_pretty(&list)?)?;
+            std::fs::rename(&tmp, path)?;
+            Ok(())
+        })();
+
+        if let Err(e) = result {
+            warn!("차단 목록 저장 실패: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score_reaches_threshold() {
+        let mut score = PeerScore::default();
+        score.record(Offense::CorruptPiece);
+        score.record(Offense::CorruptPiece);
+        assert!(!score.should_ban());
+
+        score.record(Offense::ProtocolViolation);
+        assert!(score.should_ban());
+        assert_eq!(score.corrupt_pieces, 2);
+        assert_eq!(score.violations, 1);
+    }
+
+    #[test]
+    fn test_ban_list_persists() {
+        let dir = std::env::temp_dir().join(format!("ponswarp-ban-{}", rand::random::<u64>()));
+        let path = dir.join(BAN_LIST_FILE);
+        let ip: IpAddr = "192.168.0.77".parse().unwrap();
+
+        let bans = BanList::load(path.clone());
+        assert!(!bans.is_banned(&ip));
+        bans.ban(ip, Offense::CorruptPiece.as_str(), BAN_DURATION);
+        bans.ban("10.0.0.1".parse().unwrap(), "expired", Duration::ZERO);
+
+        // 재시작 후에도 유지, 만료된 항목은 제외
+        let reloaded = BanList::load(path);
+        assert!(reloaded.is_banned(&ip));
+        assert_eq!(reloaded.list().len(), 1);
+
+        assert!(reloaded.unban(&ip));
+        assert!(!reloaded.is_banned(&ip));
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -4,10 +4,16 @@
 //! Merkle Tree 기반 검증으로 데이터 무결성을 보장합니다.
 
 use crate::grid::bitfield::Bitfield;
+use crate::grid::hash_algo::HashAlgorithm;
+use crate::grid::hash_cache::{FileFingerprint, PieceHashCache};
+use crate::grid::merkle;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
 use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
 
@@ -17,7 +23,7 @@ pub struct PieceInfo {
     pub index: usize,
     pub offset: u64,
     pub length: u32,
-    pub hash: [u8; 32], // SHA-256 해시
+    pub hash: [u8; 32], // 조각 해시 (메타데이터의 알고리즘)
 }
 
 /// 파일 메타데이터 (토렌트의 .torrent 파일과 유사)
@@ -30,93 +36,236 @@ pub struct FileMetadata {
     pub total_pieces: usize,
     pub piece_hashes: Vec<[u8; 32]>,   // 각 조각의 해시
     pub merkle_root: Option<[u8; 32]>, // Merkle Tree 루트 (선택적)
+    /// 추가 소스로 사용할 HTTP(S) 파일 서버 URL (Web Seed)
+    #[serde(default)]
+    pub web_seeds: Vec<String>,
+    /// 🆕 조각 해시 알고리즘 (Info Hash에 반영됨)
+    #[serde(default)]
+    pub hash_algorithm: HashAlgorithm,
 }
 
 impl FileMetadata {
     /// 파일로부터 메타데이터 생성
+    ///
+    /// 조각 해시는 여러 스레드에서 나눠 계산하며, 결과는 항상 조각 순서대로입니다.
     pub async fn from_file(path: &PathBuf, piece_size: u32) -> anyhow::Result<Self> {
-        use tokio::fs::File;
-        use tokio::io::{AsyncReadExt, BufReader};
+        Self::from_file_with_job(
+            path,
+            piece_size,
+            HashAlgorithm::default(),
+            &Arc::new(HashingJob::new()),
+        )
+        .await
+    }
 
-        let file = File::open(path).await?;
-        let file_size = file.metadata().await?.len();
-        let file_name = path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("unknown")
-            .to_string();
+    /// 🆕 진행률 조회/취소가 가능한 해시 작업으로 메타데이터 생성
+    pub async fn from_file_with_job(
+        path: &PathBuf,
+        piece_size: u32,
+        algorithm: HashAlgorithm,
+        job: &Arc<HashingJob>,
+    ) -> anyhow::Result<Self> {
+        if piece_size == 0 {
+            return Err(anyhow::anyhow!("조각 크기는 0일 수 없습니다"));
+        }
+        let file_size = tokio::fs::metadata(path).await?.len();
+        let file_name = file_name_of(path);
 
-        let total_pieces = ((file_size + piece_size as u64 - 1) / piece_size as u64) as usize;
-        let mut piece_hashes = Vec::with_capacity(total_pieces);
+        let (hash_path, hash_job) = (path.clone(), job.clone());
+        let piece_hashes = tokio::task::spawn_blocking(move || {
+            hash_pieces_parallel(&hash_path, file_size, piece_size, algorithm, &hash_job)
+        })
+        .await??;
 
-        let mut reader = BufReader::new(file);
-        let mut buffer = vec![0u8; piece_size as usize];
+        Ok(Self::from_piece_hashes_with_algorithm(
+            file_name,
+            file_size,
+            piece_size,
+            piece_hashes,
+            algorithm,
+        ))
+    }
 
-        for i in 0..total_pieces {
-            let bytes_to_read = if i == total_pieces - 1 {
-                (file_size - (i as u64 * piece_size as u64)) as usize
-            } else {
-                piece_size as usize
-            };
+    /// 🆕 조각 해시 캐시를 거쳐 메타데이터 생성
+    ///
+    /// 경로/크기/수정 시각/조각 크기/알고리즘이 캐시에 저장할 때와 같으면 파일을 다시 읽지 않습니다.
+    pub async fn from_file_cached(
+        path: &PathBuf,
+        piece_size: u32,
+        algorithm: HashAlgorithm,
+        cache: &Arc<PieceHashCache>,
+    ) -> anyhow::Result<Self> {
+        Self::from_file_cached_with_job(
+            path,
+            piece_size,
+            algorithm,
+            cache,
+            &Arc::new(HashingJob::new()),
+        )
+        .await
+    }
 
-            reader.read_exact(&mut buffer[..bytes_to_read]).await?;
+    /// 🆕 조각 해시 캐시 + 해시 작업 핸들 (캐시 적중 시 바로 완료로 표시)
+    pub async fn from_file_cached_with_job(
+        path: &PathBuf,
+        piece_size: u32,
+        algorithm: HashAlgorithm,
+        cache: &Arc<PieceHashCache>,
+        job: &Arc<HashingJob>,
+    ) -> anyhow::Result<Self> {
+        let (lookup_path, lookup_cache) = (path.clone(), cache.clone());
+        let (fingerprint, cached) = tokio::task::spawn_blocking(move || {
+            let fingerprint = FileFingerprint::read(&lookup_path)?;
+            let cached = lookup_cache.get(&lookup_path, piece_size, algorithm, &fingerprint);
+            Ok::<_, std::io::Error>((fingerprint, cached))
+        })
+        .await??;
 
-            let mut hasher = Sha256::new();
-            hasher.update(&buffer[..bytes_to_read]);
-            let hash: [u8; 32] = hasher.finalize().into();
-            piece_hashes.push(hash);
+        if let Some(piece_hashes) = cached {
+            info!(
+                "♻️ 조각 해시 캐시 사용: {:?} ({} pieces)",
+                path,
+                piece_hashes.len()
+            );
+            job.start(fingerprint.size, piece_hashes.len() as u64);
+            job.advance(piece_hashes.len() as u64, fingerprint.size);
+            return Ok(Self::from_piece_hashes_with_algorithm(
+                file_name_of(path),
+                fingerprint.size,
+                piece_size,
+                piece_hashes,
+                algorithm,
+            ));
         }
 
-        // Info Hash 계산 (모든 조각 해시의 해시)
-        let mut info_hasher = Sha256::new();
-        for hash in &piece_hashes {
-            info_hasher.update(hash);
-        }
-        let info_hash: [u8; 32] = info_hasher.finalize().into();
+        let metadata = Self::from_file_with_job(path, piece_size, algorithm, job).await?;
+        let (store_path, store_cache) = (path.clone(), cache.clone());
+        let metadata = tokio::task::spawn_blocking(move || {
+            store_cache.put(
+                &store_path,
+                piece_size,
+                algorithm,
+                fingerprint,
+                &metadata.piece_hashes,
+            );
+            metadata
+        })
+        .await?;
+        Ok(metadata)
+    }
+
+    /// 조각 해시 목록으로부터 메타데이터 구성 (Info Hash / Merkle Root 계산)
+    pub fn from_piece_hashes(
+        file_name: String,
+        file_size: u64,
+        piece_size: u32,
+        piece_hashes: Vec<[u8; 32]>,
+    ) -> Self {
+        Self::from_piece_hashes_with_algorithm(
+            file_name,
+            file_size,
+            piece_size,
+            piece_hashes,
+            HashAlgorithm::default(),
+        )
+    }
+
+    /// 🆕 지정한 알고리즘으로 계산한 조각 해시 목록으로부터 메타데이터 구성
+    pub fn from_piece_hashes_with_algorithm(
+        file_name: String,
+        file_size: u64,
+        piece_size: u32,
+        piece_hashes: Vec<[u8; 32]>,
+        hash_algorithm: HashAlgorithm,
+    ) -> Self {
+        let info_hash = Self::compute_info_hash_for(hash_algorithm, &piece_hashes);
 
         // Merkle Root 계산 (선택적)
         let merkle_root = Self::compute_merkle_root(&piece_hashes);
 
-        Ok(Self {
+        Self {
             info_hash,
             file_name,
             file_size,
             piece_size,
-            total_pieces,
+            total_pieces: piece_hashes.len(),
             piece_hashes,
             merkle_root: Some(merkle_root),
-        })
+            web_seeds: Vec::new(),
+            hash_algorithm,
+        }
     }
 
-    /// Merkle Tree 루트 계산
-    fn compute_merkle_root(hashes: &[[u8; 32]]) -> [u8; 32] {
-        if hashes.is_empty() {
-            return [0u8; 32];
-        }
-        if hashes.len() == 1 {
-            return hashes[0];
+    /// 메타데이터를 아직 모르는 상태의 임시 값 (Info Hash만 보유)
+    pub fn placeholder(info_hash: [u8; 32]) -> Self {
+        Self {
+            info_hash,
+            file_name: String::new(),
+            file_size: 0,
+            piece_size: 0,
+            total_pieces: 0,
+            piece_hashes: Vec::new(),
+            merkle_root: None,
+            web_seeds: Vec::new(),
+            hash_algorithm: HashAlgorithm::default(),
         }
+    }
 
-        let mut current_level: Vec<[u8; 32]> = hashes.to_vec();
+    /// Merkle 루트만 알고 있는 메타데이터 (조각 해시는 proof로 검증하며 채움)
+    pub fn root_only(
+        info_hash: [u8; 32],
+        file_name: String,
+        file_size: u64,
+        piece_size: u32,
+        merkle_root: [u8; 32],
+    ) -> Self {
+        let total_pieces = if piece_size == 0 {
+            0
+        } else {
+            file_size.div_ceil(piece_size as u64) as usize
+        };
 
-        while current_level.len() > 1 {
-            let mut next_level = Vec::new();
+        Self {
+            info_hash,
+            file_name,
+            file_size,
+            piece_size,
+            total_pieces,
+            piece_hashes: Vec::new(),
+            merkle_root: Some(merkle_root),
+            web_seeds: Vec::new(),
+            hash_algorithm: HashAlgorithm::default(),
+        }
+    }
 
-            for chunk in current_level.chunks(2) {
-                let mut hasher = Sha256::new();
-                hasher.update(&chunk[0]);
-                if chunk.len() > 1 {
-                    hasher.update(&chunk[1]);
-                } else {
-                    hasher.update(&chunk[0]); // 홀수인 경우 자기 자신과 해시
-                }
-                next_level.push(hasher.finalize().into());
-            }
+    /// 조각 해시 목록이 채워진 완전한 메타데이터인지
+    pub fn is_complete(&self) -> bool {
+        self.total_pieces > 0 && self.piece_hashes.len() == self.total_pieces
+    }
+
+    /// Info Hash 계산 (모든 조각 해시의 해시)
+    pub fn compute_info_hash(piece_hashes: &[[u8; 32]]) -> [u8; 32] {
+        Self::compute_info_hash_for(HashAlgorithm::Sha256, piece_hashes)
+    }
 
-            current_level = next_level;
+    /// 🆕 알고리즘을 반영한 Info Hash 계산
+    ///
+    /// SHA-256은 기존 값과 같고, 그 외 알고리즘은 이름을 앞에 붙여 해시하므로
+    /// 같은 조각 해시 목록이라도 알고리즘이 다르면 Info Hash가 달라집니다.
+    pub fn compute_info_hash_for(algorithm: HashAlgorithm, piece_hashes: &[[u8; 32]]) -> [u8; 32] {
+        let mut info_hasher = Sha256::new();
+        if algorithm != HashAlgorithm::Sha256 {
+            info_hasher.update(algorithm.as_str().as_bytes());
+        }
+        for hash in piece_hashes {
+            info_hasher.update(hash);
         }
+        info_hasher.finalize().into()
+    }
 
-        current_level[0]
+    /// Merkle Tree 루트 계산
+    fn compute_merkle_root(hashes: &[[u8; 32]]) -> [u8; 32] {
+        merkle::merkle_root(hashes)
     }
 
     /// Info Hash를 hex 문자열로 변환
@@ -125,6 +274,173 @@ impl FileMetadata {
     }
 }
 
+/// 🆕 조각 해시 계산 진행률
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HashingProgress {
+    pub bytes_hashed: u64,
+    pub total_bytes: u64,
+    pub pieces_done: u64,
+    pub total_pieces: u64,
+    pub speed_bps: u64,
+    /// 남은 예상 시간 (속도를 아직 모르면 None)
+    pub eta_secs: Option<u64>,
+}
+
+/// 🆕 조각 해시 계산 작업 핸들 (작업자 스레드가 진행률을 올리고, 다른 쪽에서 조회/취소)
+#[derive(Debug)]
+pub struct HashingJob {
+    cancelled: AtomicBool,
+    total_bytes: AtomicU64,
+    total_pieces: AtomicU64,
+    bytes_hashed: AtomicU64,
+    pieces_done: AtomicU64,
+    started_at: Instant,
+}
+
+impl Default for HashingJob {
+    fn default() -> Self {
+        Self {
+            cancelled: AtomicBool::new(false),
+            total_bytes: AtomicU64::new(0),
+            total_pieces: AtomicU64::new(0),
+            bytes_hashed: AtomicU64::new(0),
+            pieces_done: AtomicU64::new(0),
+            started_at: Instant::now(),
+        }
+    }
+}
+
+impl HashingJob {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    fn start(&self, total_bytes: u64, total_pieces: u64) {
+        self.total_bytes.store(total_bytes, Ordering::Relaxed);
+        self.total_pieces.store(total_pieces, Ordering::Relaxed);
+    }
+
+    fn advance(&self, pieces: u64, bytes: u64) {
+        self.pieces_done.fetch_add(pieces, Ordering::Relaxed);
+        self.bytes_hashed.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// 현재 진행률 (속도/ETA는 시작 이후 평균 기준)
+    pub fn progress(&self) -> HashingProgress {
+        let bytes_hashed = self.bytes_hashed.load(Ordering::Relaxed);
+        let total_bytes = self.total_bytes.load(Ordering::Relaxed);
+        let elapsed = self.started_at.elapsed().as_secs_f64();
+        let speed_bps = if elapsed > 0.0 {
+            (bytes_hashed as f64 / elapsed) as u64
+        } else {
+            0
+        };
+        let eta_secs =
+            (speed_bps > 0).then(|| total_bytes.saturating_sub(bytes_hashed) / speed_bps);
+        HashingProgress {
+            bytes_hashed,
+            total_bytes,
+            pieces_done: self.pieces_done.load(Ordering::Relaxed),
+            total_pieces: self.total_pieces.load(Ordering::Relaxed),
+            speed_bps,
+            eta_secs,
+        }
+    }
+}
+
+/// 병렬 해시 작업자가 한 번에 가져가는 조각 수 (작업자마다 연속 구간을 읽도록)
+const HASH_BATCH_PIECES: usize = 16;
+
+/// 병렬 해시 작업자 상한 (디스크 대역폭 이상으로 늘려도 이득이 없음)
+const MAX_HASH_WORKERS: usize = 16;
+
+/// 조각 해시를 여러 스레드에서 계산 (작업자는 조각 묶음을 순서대로 가져가고, 결과는 조각 위치에 기록)
+fn hash_pieces_parallel(
+    path: &Path,
+    file_size: u64,
+    piece_size: u32,
+    algorithm: HashAlgorithm,
+    job: &HashingJob,
+) -> std::io::Result<Vec<[u8; 32]>> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let piece_size = piece_size as u64;
+    let total_pieces = file_size.div_ceil(piece_size) as usize;
+    job.start(file_size, total_pieces as u64);
+    let mut piece_hashes = vec![[0u8; 32]; total_pieces];
+    let workers = num_cpus::get()
+        .clamp(1, MAX_HASH_WORKERS)
+        .min(total_pieces.div_ceil(HASH_BATCH_PIECES).max(1));
+
+    {
+        let batches =
+            parking_lot::Mutex::new(piece_hashes.chunks_mut(HASH_BATCH_PIECES).enumerate());
+        let failed = AtomicBool::new(false);
+        let hash_batches = || -> std::io::Result<()> {
+            let mut file = std::fs::File::open(path)?;
+            let mut buffer = vec![0u8; piece_size as usize];
+            loop {
+                if failed.load(Ordering::Relaxed) {
+                    return Ok(());
+                }
+                if job.is_cancelled() {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::Interrupted,
+                        "조각 해시 계산이 취소되었습니다",
+                    ));
+                }
+                let Some((batch, hashes)) = batches.lock().next() else {
+                    return Ok(());
+                };
+                let first = (batch * HASH_BATCH_PIECES) as u64;
+                file.seek(SeekFrom::Start(first * piece_size))?;
+                for (offset, hash) in hashes.iter_mut().enumerate() {
+                    let start = (first + offset as u64) * piece_size;
+                    let len = (file_size - start).min(piece_size) as usize;
+                    if let Err(e) = file.read_exact(&mut buffer[..len]) {
+                        failed.store(true, Ordering::Relaxed);
+                        return Err(e);
+                    }
+                    *hash = algorithm.digest(&buffer[..len]);
+                    job.advance(1, len as u64);
+                }
+            }
+        };
+
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers).map(|_| scope.spawn(hash_batches)).collect();
+            handles.into_iter().try_for_each(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|_| Err(std::io::Error::other("해시 작업 스레드 패닉")))
+            })
+        })?;
+    }
+
+    debug!(
+        "🔢 조각 해시 완료: {} pieces ({} workers, {})",
+        total_pieces, workers, algorithm
+    );
+    Ok(piece_hashes)
+}
+
+/// 메타데이터에 기록할 파일 이름
+fn file_name_of(path: &Path) -> String {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("unknown")
+        .to_string()
+}
+
 /// Swarm 내의 파일 상태 관리자
 pub struct PieceManager {
     metadata: FileMetadata,
@@ -190,7 +506,8 @@ impl PieceManager {
                 index: i,
                 offset,
                 length,
-                hash: metadata.piece_hashes[i],
+                // 루트만 아는 경우 proof 검증 시 채워짐
+                hash: metadata.piece_hashes.get(i).copied().unwrap_or([0u8; 32]),
             });
         }
 
@@ -220,9 +537,7 @@ impl PieceManager {
             return false;
         }
 
-        let mut hasher = Sha256::new();
-        hasher.update(data);
-        let hash: [u8; 32] = hasher.finalize().into();
+        let hash = self.metadata.hash_algorithm.digest(data);
 
         if hash != piece.hash {
             warn!("Piece {} hash mismatch", index);
@@ -233,6 +548,52 @@ impl PieceManager {
         true
     }
 
+    /// 조각 검증 (Merkle proof가 있으면 루트 기준으로, 없으면 조각 해시로)
+    ///
+    /// 루트만 아는 상태에서 proof 검증에 성공하면 해당 조각 해시를 기록합니다.
+    #[cfg(feature = "grid-experimental")]
+    pub fn verify_piece_with_proof(
+        &mut self,
+        index: usize,
+        data: &[u8],
+        proof: &[[u8; 32]],
+    ) -> bool {
+        let Some(root) = self.metadata.merkle_root else {
+            return self.verify_piece(index, data);
+        };
+        if proof.is_empty() && self.metadata.is_complete() {
+            return self.verify_piece(index, data);
+        }
+
+        let Some(piece) = self.pieces.get(index) else {
+            warn!("Invalid piece index: {}", index);
+            return false;
+        };
+        if data.len() as u32 != piece.length {
+            warn!("Piece {} length mismatch", index);
+            return false;
+        }
+
+        let leaf = merkle::leaf_hash(self.metadata.hash_algorithm, data);
+        if !merkle::verify_proof(&root, &leaf, index, self.metadata.total_pieces, proof) {
+            warn!("Piece {} merkle proof mismatch", index);
+            return false;
+        }
+
+        self.pieces[index].hash = leaf;
+        debug!("Piece {} verified by merkle proof", index);
+        true
+    }
+
+    /// 조각의 Merkle 포함 증명 (전체 조각 해시를 알고 있을 때만)
+    #[cfg(feature = "grid-experimental")]
+    pub fn piece_proof(&self, index: usize) -> Option<Vec<[u8; 32]>> {
+        if !self.metadata.is_complete() || self.metadata.merkle_root.is_none() {
+            return None;
+        }
+        merkle::merkle_proof(&self.metadata.piece_hashes, index)
+    }
+
     /// 조각 완료 표시
     pub fn mark_completed(&mut self, index: usize) {
         self.my_bitfield.mark(index);
@@ -333,6 +694,11 @@ impl PieceManager {
         &self.metadata.info_hash
     }
 
+    /// 저장(또는 소스) 파일 경로
+    pub fn save_path(&self) -> Option<&PathBuf> {
+        self.save_path.as_ref()
+    }
+
     /// 소스 파일 경로 설정 (Seeder용)
     pub fn set_source_path(&mut self, path: PathBuf) {
         self.save_path = Some(path);
@@ -362,11 +728,44 @@ impl PieceManager {
         Ok(buffer)
     }
 
+    /// 저장된 비트필드 기준으로 디스크의 기존 조각을 재검증하여 복원
+    ///
+    /// 비트필드에 표시되어 있어도 실제 데이터 해시가 맞지 않으면 다시 받도록 제외합니다.
+    /// 검증에 성공한 조각 수를 반환합니다.
+    pub async fn verify_existing_pieces(&mut self, claimed: &Bitfield) -> usize {
+        let exists = match self.save_path {
+            Some(ref path) => tokio::fs::metadata(path).await.is_ok(),
+            None => false,
+        };
+        if !exists {
+            return 0;
+        }
+
+        let mut verified = 0;
+        for index in claimed.available_pieces() {
+            let ok = match self.read_piece(index).await {
+                Ok(data) => self.verify_piece(index, &data),
+                Err(_) => false,
+            };
+
+            if ok {
+                self.my_bitfield.mark(index);
+                verified += 1;
+            } else {
+                debug!("Piece {} failed re-verification, will re-download", index);
+            }
+        }
+
+        info!(
+            "🔁 기존 조각 재검증: {}/{} 유효",
+            verified,
+            claimed.count_ones()
+        );
+        verified
+    }
+
     /// 파일에 조각 데이터 쓰기 (Leecher용)
     pub async fn write_piece(&mut self, index: usize, data: &[u8]) -> anyhow::Result<()> {
-        use tokio::fs::OpenOptions;
-        use tokio::io::{AsyncSeekExt, AsyncWriteExt};
-
         let piece = self
             .pieces
             .get(index)
@@ -386,6 +785,19 @@ impl PieceManager {
             return Err(anyhow::anyhow!("Piece {} hash verification failed", index));
         }
 
+        self.write_verified_piece(index, data).await
+    }
+
+    /// 검증이 끝난 조각 데이터를 파일에 쓰기
+    pub async fn write_verified_piece(&mut self, index: usize, data: &[u8]) -> anyhow::Result<()> {
+        use tokio::fs::OpenOptions;
+        use tokio::io::{AsyncSeekExt, AsyncWriteExt};
+
+        let piece = self
+            .pieces
+            .get(index)
+            .ok_or_else(|| anyhow::anyhow!("Invalid piece index: {}", index))?;
+
         let path = self
             .save_path
             .as_ref()
@@ -427,6 +839,8 @@ mod tests {
             total_pieces: 10,
             piece_hashes: vec![[0u8; 32]; 10],
             merkle_root: None,
+            web_seeds: Vec::new(),
+            hash_algorithm: HashAlgorithm::default(),
         }
     }
 
@@ -450,6 +864,32 @@ mod tests {
         assert!(pm.is_complete());
     }
 
+    #[test]
+    #[cfg(feature = "grid-experimental")]
+    fn test_verify_piece_with_proof_root_only() {
+        let pieces: Vec<Vec<u8>> = (0..5u8).map(|i| vec![i; 1024]).collect();
+        let hashes: Vec<[u8; 32]> = pieces
+            .iter()
+            .map(|p| merkle::leaf_hash(HashAlgorithm::Sha256, p))
+            .collect();
+        let full = FileMetadata::from_piece_hashes("a.bin".to_string(), 5 * 1024, 1024, hashes);
+        let seeder = PieceManager::new_seeder(full.clone());
+
+        let root_only = FileMetadata::root_only(
+            full.info_hash,
+            full.file_name.clone(),
+            full.file_size,
+            full.piece_size,
+            full.merkle_root.unwrap(),
+        );
+        let mut pm = PieceManager::new(root_only);
+
+        let proof = seeder.piece_proof(3).unwrap();
+        assert!(pm.verify_piece_with_proof(3, &pieces[3], &proof));
+        assert!(!pm.verify_piece_with_proof(2, &pieces[3], &seeder.piece_proof(2).unwrap()));
+        assert!(!pm.verify_piece_with_proof(1, &pieces[1], &[]));
+    }
+
     #[test]
     fn test_mark_completed() {
         let metadata = create_test_metadata();
@@ -461,4 +901,123 @@ mod tests {
         assert_eq!(pm.completed_pieces(), 2);
         assert!((pm.progress() - 0.2).abs() < 0.001);
     }
+
+    #[tokio::test]
+    async fn test_from_file_parallel_hashes_keep_piece_order() {
+        let dir =
+            std::env::temp_dir().join(format!("ponswarp-parallel-hash-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("data.bin");
+        // 여러 묶음에 걸치고 마지막 조각이 짧은 파일
+        let data: Vec<u8> = (0..64 * 40 + 5).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&path, &data).unwrap();
+
+        let metadata = FileMetadata::from_file(&path, 64).await.unwrap();
+        let expected: Vec<[u8; 32]> = data
+            .chunks(64)
+            .map(|piece| Sha256::digest(piece).into())
+            .collect();
+        assert_eq!(metadata.total_pieces, 41);
+        assert_eq!(metadata.piece_hashes, expected);
+        assert_eq!(metadata.file_size, data.len() as u64);
+
+        assert!(FileMetadata::from_file(&path, 0).await.is_err());
+
+        // 진행률은 끝나면 전체와 같고, 취소된 작업은 실패
+        let job = Arc::new(HashingJob::new());
+        FileMetadata::from_file_with_job(&path, 64, HashAlgorithm::Sha256, &job)
+            .await
+            .unwrap();
+        let progress = job.progress();
+        assert_eq!(progress.pieces_done, 41);
+        assert_eq!(progress.bytes_hashed, data.len() as u64);
+        assert_eq!(progress.total_bytes, data.len() as u64);
+
+        let cancelled = Arc::new(HashingJob::new());
+        cancelled.cancel();
+        assert!(
+            FileMetadata::from_file_with_job(&path, 64, HashAlgorithm::Sha256, &cancelled)
+                .await
+                .is_err()
+        );
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_from_file_cached_reuses_piece_hashes() {
+        let dir = std::env::temp_dir().join(format!("ponswarp-cached-meta-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("data.bin");
+        std::fs::write(&path, vec![7u8; 10_000]).unwrap();
+        let cache = Arc::new(PieceHashCache::new(dir.join("cache")));
+
+        let first = FileMetadata::from_file_cached(&path, 4096, HashAlgorithm::Sha256, &cache)
+            .await
+            .unwrap();
+        let direct = FileMetadata::from_file(&path, 4096).await.unwrap();
+        assert_eq!(first.info_hash, direct.info_hash);
+        assert_eq!(first.file_name, "data.bin");
+
+        // 캐시 항목을 일부러 바꿔 두면 파일을 다시 읽지 않고 캐시 값을 씀
+        let fingerprint = FileFingerprint::read(&path).unwrap();
+        cache.put(
+            &path,
+            4096,
+            HashAlgorithm::Sha256,
+            fingerprint,
+            &[[9u8; 32]; 3],
+        );
+        let cached = FileMetadata::from_file_cached(&path, 4096, HashAlgorithm::Sha256, &cache)
+            .await
+            .unwrap();
+        assert_eq!(cached.piece_hashes, vec![[9u8; 32]; 3]);
+        assert_eq!(cached.file_size, 10_000);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_blake3_metadata_verifies_pieces() {
+        let dir = std::env::temp_dir().join(format!("ponswarp-blake3-meta-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("data.bin");
+        let data: Vec<u8> = (0..3000u32).map(|i| (i % 253) as u8).collect();
+        std::fs::write(&path, &data).unwrap();
+
+        let job = Arc::new(HashingJob::new());
+        let blake = FileMetadata::from_file_with_job(&path, 1024, HashAlgorithm::Blake3, &job)
+            .await
+            .unwrap();
+        let sha = FileMetadata::from_file(&path, 1024).await.unwrap();
+        assert_eq!(blake.hash_algorithm, HashAlgorithm::Blake3);
+        assert_eq!(
+            blake.piece_hashes[0],
+            *blake3::hash(&data[..1024]).as_bytes()
+        );
+        // 알고리즘이 다르면 Info Hash도 다름
+        assert_ne!(blake.info_hash, sha.info_hash);
+        assert_eq!(
+            blake.info_hash,
+            FileMetadata::compute_info_hash_for(HashAlgorithm::Blake3, &blake.piece_hashes)
+        );
+
+        let pm = PieceManager::new(blake);
+        assert!(pm.verify_piece(2, &data[2048..]));
+        assert!(!pm.verify_piece(0, &data[1024..2048]));
+        // Merkle proof 검증도 같은 알고리즘의 리프 해시 사용
+        #[cfg(feature = "grid-experimental")]
+        {
+            let mut pm = pm;
+            let proof = pm.piece_proof(1).unwrap();
+            assert!(pm.verify_piece_with_proof(1, &data[1024..2048], &proof));
+        }
+
+        let sha_pm = PieceManager::new(sha);
+        assert!(sha_pm.verify_piece(0, &data[..1024]));
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
 }
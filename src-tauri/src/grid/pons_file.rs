@@ -0,0 +1,228 @@
+//! Pons File - 메타데이터를 오프라인으로 전달하는 `.pons` 파일 (토렌트 파일과 유사)
+//!
+//! 공유 링크는 Info Hash만 담아 메타데이터를 피어에게서 받아야 하지만, `.pons` 파일은
+//! 조각 해시 목록까지 모두 담고 있어 메일이나 공유 드라이브로 건네면 바로 다운로드를
+//! 시작할 수 있습니다. 시드/트래커 주소와 DHT 부트스트랩 노드 힌트를 함께 넣을 수 있습니다.
+//!
+//! 형식: `[MAGIC "PONS"(4)][버전(1)][본문(bincode)][SHA-256(본문)(32)]`
+//! 읽을 때 체크섬과 Info Hash(조각 해시 목록 기준)를 모두 검증합니다.
+
+use crate::grid::merkle;
+use crate::grid::piece_manager::FileMetadata;
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+/// 파일 확장자
+pub const PONS_FILE_EXTENSION: &str = "pons";
+
+/// 파일 시작 표식
+const MAGIC: &[u8; 4] = b"PONS";
+
+/// 형식 버전
+const FORMAT_VERSION: u8 = 1;
+
+/// 본문 뒤 체크섬 길이
+const CHECKSUM_LEN: usize = 32;
+
+/// 읽을 수 있는 최대 파일 크기 (1MB 조각 기준 수 TB 파일의 조각 해시 목록)
+const MAX_PONS_FILE_SIZE: u64 = 256 * 1024 * 1024;
+
+/// `.pons` 파일 내용
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PonsFile {
+    pub metadata: FileMetadata,
+    /// 먼저 연결할 시드/트래커 주소 (`host:port`)
+    pub trackers: Vec<String>,
+    /// 제공자 검색에 쓸 DHT 부트스트랩 노드 힌트 (`host:port`)
+    pub bootstrap_nodes: Vec<String>,
+    /// 만든 시각 (Unix 초)
+    pub created_at: i64,
+    pub comment: Option<String>,
+}
+
+impl PonsFile {
+    pub fn new(metadata: FileMetadata) -> Self {
+        Self {
+            metadata,
+            trackers: Vec::new(),
+            bootstrap_nodes: Vec::new(),
+            created_at: chrono::Utc::now().timestamp(),
+            comment: None,
+        }
+    }
+
+    pub fn with_trackers(mut self, trackers: Vec<String>) -> Self {
+        self.trackers = trackers;
+        self
+    }
+
+    pub fn with_bootstrap_nodes(mut self, nodes: Vec<String>) -> Self {
+        self.bootstrap_nodes = nodes;
+        self
+    }
+
+    pub fn with_comment(mut self, comment: Option<String>) -> Self {
+        self.comment = comment.filter(|c| !c.trim().is_empty());
+        self
+    }
+
+    /// 바이트로 직렬화
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        let body = bincode::serialize(self)?;
+        let mut out = Vec::with_capacity(MAGIC.len() + 1 + body.len() + CHECKSUM_LEN);
+        out.extend_from_slice(MAGIC);
+        out.push(FORMAT_VERSION);
+        out.extend_from_slice(&body);
+        out.extend_from_slice(&Sha256::digest(&body));
+        Ok(out)
+    }
+
+    /// 바이트에서 읽고 검증
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let header_len = MAGIC.len() + 1;
+        if data.len() < header_len + CHECKSUM_LEN || &data[..MAGIC.len()] != MAGIC {
+            bail!("Not a .pons file");
+        }
+        let version = data[MAGIC.len()];
+        if version != FORMAT_VERSION {
+            bail!("Unsupported .pons version: {}", version);
+        }
+
+        let (body, checksum) = data[header_len..].split_at(data.len() - header_len - CHECKSUM_LEN);
+        if Sha256::digest(body).as_slice() != checksum {
+            bail!(".pons checksum mismatch (file is corrupted)");
+        }
+
+        let file: Self = bincode::deserialize(body)?;
+        file.validate()?;
+        Ok(file)
+    }
+
+    /// 메타데이터 일관성 검증 (조각 수, Info Hash, Merkle Root)
+    fn validate(&self) -> Result<()> {
+        let metadata = &self.metadata;
+        if metadata.piece_size == 0 || !metadata.is_complete() {
+            bail!(".pons metadata has no piece hashes");
+        }
+        let expected_pieces = metadata.file_size.div_ceil(metadata.piece_size as u64) as usize;
+        if expected_pieces != metadata.total_pieces {
+            bail!(
+                ".pons piece count mismatch: {} vs {}",
+                metadata.total_pieces,
+                expected_pieces
+            );
+        }
+        let info_hash =
+            FileMetadata::compute_info_hash_for(metadata.hash_algorithm, &metadata.piece_hashes);
+        if info_hash != metadata.info_hash {
+            bail!(".pons metadata failed info hash verification");
+        }
+        if let Some(root) = metadata.merkle_root {
+            if merkle::merkle_root(&metadata.piece_hashes) != root {
+                bail!(".pons metadata failed merkle root verification");
+            }
+        }
+        Ok(())
+    }
+
+    /// 파일로 저장 (임시 파일에 쓴 뒤 이름 변경)
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let encoded = self.encode()?;
+        let tmp = path.with_extension("pons.tmp");
+        std::fs::write(&tmp, encoded)?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    /// 파일에서 읽기
+    pub fn load(path: &Path) -> Result<Self> {
+        let size = std::fs::metadata(path)?.len();
+        if size > MAX_PONS_FILE_SIZE {
+            bail!(".pons file too large: {} bytes", size);
+        }
+        Self::decode(&std::fs::read(path)?)
+    }
+}
+
+/// `.pons` 파일 경로인지 (확장자 기준)
+pub fn is_pons_path(path: &str) -> bool {
+    Path::new(path.trim())
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(PONS_FILE_EXTENSION))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::hash_algo::HashAlgorithm;
+
+    fn sample() -> PonsFile {
+        let hashes = (0..5u8).map(|i| [i; 32]).collect();
+        let mut metadata = FileMetadata::from_piece_hashes_with_algorithm(
+            "movie.mkv".to_string(),
+            4 * 1024 + 100,
+            1024,
+            hashes,
+            HashAlgorithm::Blake3,
+        );
+        metadata.web_seeds = vec!["https://example.com/movie.mkv".to_string()];
+        PonsFile::new(metadata)
+            .with_trackers(vec!["203.0.113.5:7000".to_string()])
+            .with_bootstrap_nodes(vec!["198.51.100.1:6881".to_string()])
+            .with_comment(Some("season 1".to_string()))
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let original = sample();
+        let decoded = PonsFile::decode(&original.encode().unwrap()).unwrap();
+
+        assert_eq!(decoded.metadata.info_hash, original.metadata.info_hash);
+        assert_eq!(
+            decoded.metadata.piece_hashes,
+            original.metadata.piece_hashes
+        );
+        assert_eq!(decoded.metadata.hash_algorithm, HashAlgorithm::Blake3);
+        assert_eq!(decoded.metadata.web_seeds, original.metadata.web_seeds);
+        assert_eq!(decoded.trackers, original.trackers);
+        assert_eq!(decoded.bootstrap_nodes, original.bootstrap_nodes);
+        assert_eq!(decoded.comment.as_deref(), Some("season 1"));
+    }
+
+    #[test]
+    fn test_rejects_corrupted_or_tampered() {
+        let original = sample();
+        let mut encoded = original.encode().unwrap();
+
+        assert!(PonsFile::decode(b"NOPE").is_err());
+        let last = encoded.len() - 1;
+        encoded[last] ^= 0xff;
+        assert!(PonsFile::decode(&encoded).is_err());
+
+        // 체크섬은 맞지만 조각 해시가 Info Hash와 다른 경우
+        let mut tampered = original.clone();
+        tampered.metadata.piece_hashes[0] = [0xaa; 32];
+        assert!(PonsFile::decode(&tampered.encode().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_save_load_and_path_detection() {
+        let dir = std::env::temp_dir().join(format!("ponswarp-pons-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("movie.pons");
+
+        let original = sample();
+        original.save(&path).unwrap();
+        let loaded = PonsFile::load(&path).unwrap();
+        assert_eq!(loaded.metadata.info_hash, original.metadata.info_hash);
+
+        assert!(is_pons_path(path.to_str().unwrap()));
+        assert!(is_pons_path("C:\\share\\Movie.PONS"));
+        assert!(!is_pons_path("pons://abcd"));
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -5,14 +5,27 @@
 
 use serde::{Deserialize, Serialize};
 use std::io;
+use std::net::SocketAddr;
 use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 
 /// 프로토콜 버전
-pub const PROTOCOL_VERSION: u32 = 1;
+pub const PROTOCOL_VERSION: u32 = 2;
+
+/// 호환 가능한 최소 프로토콜 버전
+pub const MIN_PROTOCOL_VERSION: u32 = 2;
+
+/// Handshake 완료 제한 시간 (초)
+pub const HANDSHAKE_TIMEOUT_SECS: u64 = 10;
 
 /// 최대 메시지 크기 (10MB)
 pub const MAX_MESSAGE_SIZE: usize = 10 * 1024 * 1024;
 
+/// 메타데이터 응답 1개당 조각 해시 수 (32B x 8192 = 256KB)
+pub const METADATA_CHUNK_HASHES: usize = 8192;
+
+/// PEX 메시지 1개당 최대 주소 수
+pub const PEX_MAX_PEERS: usize = 50;
+
 /// Grid 프로토콜 메시지 타입
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum GridMessage {
@@ -43,6 +56,8 @@ pub enum GridMessage {
         piece_index: u32,
         offset: u32,
         data: Vec<u8>,
+        /// Merkle 포함 증명 (루트 기준 검증용, 비어 있으면 조각 해시로 검증)
+        proof: Vec<[u8; 32]>,
     },
 
     /// 요청 취소 (다른 피어에게 받았을 때)
@@ -67,29 +82,138 @@ pub enum GridMessage {
     /// NotInterested - 상대방의 데이터에 관심 없음
     NotInterested,
 
-    /// 파일 메타데이터 요청
-    MetadataRequest { info_hash: [u8; 32] },
+    /// 파일 메타데이터 요청 (조각 해시 목록의 chunk 번째 묶음)
+    MetadataRequest { info_hash: [u8; 32], chunk: u32 },
 
-    /// 파일 메타데이터 응답
+    /// 파일 메타데이터 응답 (조각 해시는 METADATA_CHUNK_HASHES 단위로 분할)
     MetadataResponse {
         info_hash: [u8; 32],
         file_name: String,
         file_size: u64,
         piece_size: u32,
         total_pieces: usize,
+        chunk: u32,
+        total_chunks: u32,
         piece_hashes: Vec<[u8; 32]>,
     },
 
+    /// Peer Exchange - 알고 있는 다른 Swarm 구성원 주소 공유 (직전 PEX 이후 변경분)
+    Pex {
+        added: Vec<SocketAddr>,
+        dropped: Vec<SocketAddr>,
+    },
+
     /// 에러 메시지
     Error { code: u32, message: String },
 }
 
+/// Handshake 실패 사유
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum HandshakeError {
+    #[error("handshake timed out")]
+    Timeout,
+    #[error("expected Handshake, got {0}")]
+    UnexpectedMessage(&'static str),
+    #[error("unsupported protocol version {remote} (supported {min}..={max})")]
+    VersionMismatch { remote: u32, min: u32, max: u32 },
+    #[error("info hash mismatch")]
+    InfoHashMismatch,
+    #[error("connected to self")]
+    SelfConnection,
+    #[error("rejected by peer [{code}]: {message}")]
+    Rejected { code: u32, message: String },
+    #[error("handshake I/O failed: {0}")]
+    Io(String),
+}
+
+impl HandshakeError {
+    /// QUIC 연결 종료 코드 / Error 메시지 코드
+    pub fn code(&self) -> u32 {
+        match self {
+            HandshakeError::Timeout => 1001,
+            HandshakeError::UnexpectedMessage(_) => 1002,
+            HandshakeError::VersionMismatch { .. } => 1003,
+            HandshakeError::InfoHashMismatch => 1004,
+            HandshakeError::SelfConnection => 1005,
+            HandshakeError::Io(_) => 1006,
+            HandshakeError::Rejected { code, .. } => *code,
+        }
+    }
+}
+
+/// 협상된 Handshake 결과
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NegotiatedHandshake {
+    pub remote_peer_id: [u8; 32],
+    pub protocol_version: u32,
+    /// 양쪽이 모두 지원하는 확장 기능
+    pub extensions: u64,
+}
+
+impl NegotiatedHandshake {
+    pub fn supports(&self, extension: u64) -> bool {
+        self.extensions & extension != 0
+    }
+}
+
+/// 수신한 Handshake 검증 및 기능 협상
+pub fn negotiate_handshake(
+    msg: &GridMessage,
+    expected_info_hash: &[u8; 32],
+    my_peer_id: &[u8; 32],
+    my_extensions: u64,
+) -> Result<NegotiatedHandshake, HandshakeError> {
+    let GridMessage::Handshake {
+        protocol_version,
+        info_hash,
+        peer_id,
+        extensions,
+    } = msg
+    else {
+        if let GridMessage::Error { code, message } = msg {
+            return Err(HandshakeError::Rejected {
+                code: *code,
+                message: message.clone(),
+            });
+        }
+        return Err(HandshakeError::UnexpectedMessage(msg.type_name()));
+    };
+
+    if !(MIN_PROTOCOL_VERSION..=PROTOCOL_VERSION).contains(protocol_version) {
+        return Err(HandshakeError::VersionMismatch {
+            remote: *protocol_version,
+            min: MIN_PROTOCOL_VERSION,
+            max: PROTOCOL_VERSION,
+        });
+    }
+    if info_hash != expected_info_hash {
+        return Err(HandshakeError::InfoHashMismatch);
+    }
+    if peer_id == my_peer_id {
+        return Err(HandshakeError::SelfConnection);
+    }
+
+    Ok(NegotiatedHandshake {
+        remote_peer_id: *peer_id,
+        protocol_version: (*protocol_version).min(PROTOCOL_VERSION),
+        extensions: extensions & my_extensions,
+    })
+}
+
 /// 확장 기능 플래그
 pub mod extensions {
     pub const FAST_EXTENSION: u64 = 1 << 0;
     pub const DHT: u64 = 1 << 1;
     pub const ENCRYPTION: u64 = 1 << 2;
     pub const METADATA_EXCHANGE: u64 = 1 << 3;
+    pub const PEX: u64 = 1 << 4;
+    pub const MERKLE_PROOF: u64 = 1 << 5;
+    /// 🆕 Request/Piece를 추가 양방향 스트림 여러 개로 나눠 주고받음
+    pub const MULTI_STREAM: u64 = 1 << 6;
+
+    /// 이 구현이 지원하는 확장 기능 전체
+    pub const SUPPORTED: u64 =
+        FAST_EXTENSION | DHT | METADATA_EXCHANGE | PEX | MERKLE_PROOF | MULTI_STREAM;
 }
 
 impl GridMessage {
@@ -159,6 +283,7 @@ impl GridMessage {
             GridMessage::NotInterested => "NotInterested",
             GridMessage::MetadataRequest { .. } => "MetadataRequest",
             GridMessage::MetadataResponse { .. } => "MetadataResponse",
+            GridMessage::Pex { .. } => "Pex",
             GridMessage::Error { .. } => "Error",
         }
     }
@@ -169,9 +294,7 @@ impl GridMessage {
             protocol_version: PROTOCOL_VERSION,
             info_hash,
             peer_id,
-            extensions: extensions::FAST_EXTENSION
-                | extensions::DHT
-                | extensions::METADATA_EXCHANGE,
+            extensions: extensions::SUPPORTED,
         }
     }
 
@@ -191,12 +314,30 @@ impl GridMessage {
 
     /// Piece 메시지 생성 헬퍼
     pub fn piece(piece_index: u32, offset: u32, data: Vec<u8>) -> Self {
+        Self::piece_with_proof(piece_index, offset, data, Vec::new())
+    }
+
+    /// Merkle proof가 포함된 Piece 메시지 생성 헬퍼
+    pub fn piece_with_proof(
+        piece_index: u32,
+        offset: u32,
+        data: Vec<u8>,
+        proof: Vec<[u8; 32]>,
+    ) -> Self {
         GridMessage::Piece {
             piece_index,
             offset,
             data,
+            proof,
         }
     }
+
+    /// PEX 메시지 생성 헬퍼 (최대 PEX_MAX_PEERS개로 제한)
+    pub fn pex(mut added: Vec<SocketAddr>, mut dropped: Vec<SocketAddr>) -> Self {
+        added.truncate(PEX_MAX_PEERS);
+        dropped.truncate(PEX_MAX_PEERS);
+        GridMessage::Pex { added, dropped }
+    }
 }
 
 /// 메시지 배치 전송 (여러 메시지를 한 번에)
@@ -281,6 +422,66 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_negotiate_handshake() {
+        let info_hash = [1u8; 32];
+        let me = [2u8; 32];
+        let remote = GridMessage::Handshake {
+            protocol_version: PROTOCOL_VERSION,
+            info_hash,
+            peer_id: [3u8; 32],
+            extensions: extensions::PEX | (1 << 40),
+        };
+
+        let negotiated = negotiate_handshake(&remote, &info_hash, &me, extensions::SUPPORTED)
+            .expect("handshake should succeed");
+        assert_eq!(negotiated.remote_peer_id, [3u8; 32]);
+        assert!(negotiated.supports(extensions::PEX));
+        assert!(!negotiated.supports(extensions::METADATA_EXCHANGE));
+
+        assert_eq!(
+            negotiate_handshake(&remote, &[9u8; 32], &me, extensions::SUPPORTED),
+            Err(HandshakeError::InfoHashMismatch)
+        );
+        assert_eq!(
+            negotiate_handshake(&GridMessage::KeepAlive, &info_hash, &me, 0),
+            Err(HandshakeError::UnexpectedMessage("KeepAlive"))
+        );
+
+        let old = GridMessage::Handshake {
+            protocol_version: 1,
+            info_hash,
+            peer_id: [3u8; 32],
+            extensions: 0,
+        };
+        assert_eq!(
+            negotiate_handshake(&old, &info_hash, &me, 0)
+                .unwrap_err()
+                .code(),
+            1003
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pex_message() {
+        let added: Vec<SocketAddr> = (0..80)
+            .map(|i| format!("10.0.0.{}:7000", i).parse().unwrap())
+            .collect();
+        let msg = GridMessage::pex(added, vec!["10.0.1.1:7000".parse().unwrap()]);
+
+        let mut buffer = Vec::new();
+        msg.write_to(&mut buffer).await.unwrap();
+
+        let mut cursor = Cursor::new(buffer);
+        match GridMessage::read_from(&mut cursor).await.unwrap() {
+            GridMessage::Pex { added, dropped } => {
+                assert_eq!(added.len(), PEX_MAX_PEERS);
+                assert_eq!(dropped.len(), 1);
+            }
+            _ => panic!("Wrong message type"),
+        }
+    }
+
     #[tokio::test]
     async fn test_piece_message() {
         let data = vec![0u8; 1024];
@@ -297,6 +498,7 @@ mod tests {
                 piece_index,
                 offset,
                 data: d,
+                ..
             } => {
                 assert_eq!(piece_index, 5);
                 assert_eq!(offset, 0);
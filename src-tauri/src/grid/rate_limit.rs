@@ -0,0 +1,179 @@
+//! Rate Limit - Swarm 단위 업로드/다운로드 속도 제한
+//!
+//! 토큰 버킷 방식으로 동작하며, 조각 단위처럼 버킷보다 큰 요청도 허용하되
+//! 빚(음수 토큰)을 남겨 이후 요청이 그만큼 대기하도록 합니다.
+//! 직접 전송(멀티스트림 등)과 독립적으로 백그라운드 Grid 다운로드만 제한합니다.
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// 토큰 버킷 기반 속도 제한기 (rate 0 = 무제한)
+pub struct RateLimiter {
+    rate_bps: AtomicU64,
+    bucket: Mutex<Bucket>,
+}
+
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    pub fn new(rate_bps: u64) -> Self {
+        Self {
+            rate_bps: AtomicU64::new(rate_bps),
+            bucket: Mutex::new(Bucket {
+                tokens: rate_bps as f64,
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    /// 현재 제한 속도 (bytes/sec, 0 = 무제한)
+    pub fn rate(&self) -> u64 {
+        self.rate_bps.load(Ordering::Relaxed)
+    }
+
+    /// 제한 속도 변경 (누적된 빚은 유지, 버스트는 새 속도로 제한)
+    pub fn set_rate(&self, rate_bps: u64) {
+        self.rate_bps.store(rate_bps, Ordering::Relaxed);
+        let mut bucket = self.bucket.lock();
+        bucket.tokens = bucket.tokens.min(rate_bps as f64);
+        bucket.last_refill = Instant::now();
+    }
+
+    fn refill(bucket: &mut Bucket, rate: u64) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.last_refill = now;
+        // 버스트 상한: 1초 분량
+        bucket.tokens = (bucket.tokens + elapsed * rate as f64).min(rate as f64);
+    }
+
+    /// 즉시 소비 시도 (잔여 토큰이 음수면 실패)
+    pub fn try_consume(&self, bytes: u64) -> bool {
+        let rate = self.rate();
+        if rate == 0 {
+            return true;
+        }
+
+        let mut bucket = self.bucket.lock();
+        Self::refill(&mut bucket, rate);
+        if bucket.tokens < 0.0 {
+            return false;
+        }
+        bucket.tokens -= bytes as f64;
+        true
+    }
+
+    /// 소비 가능할 때까지 대기한 뒤 소비
+    pub async fn acquire(&self, bytes: u64) {
+        loop {
+            let wait = {
+                let rate = self.rate();
+                if rate == 0 {
+                    return;
+                }
+
+                let mut bucket = self.bucket.lock();
+                Self::refill(&mut bucket, rate);
+                if bucket.tokens >= 0.0 {
+                    bucket.tokens -= bytes as f64;
+                    return;
+                }
+                Duration::from_secs_f64(-bucket.tokens / rate as f64)
+            };
+
+            tokio::time::sleep(wait.max(Duration::from_millis(1))).await;
+        }
+    }
+}
+
+/// Swarm 하나의 업로드/다운로드 제한
+pub struct SwarmRateLimit {
+    pub upload: RateLimiter,
+    pub download: RateLimiter,
+}
+
+impl SwarmRateLimit {
+    pub fn new(upload_bps: u64, download_bps: u64) -> Self {
+        Self {
+            upload: RateLimiter::new(upload_bps),
+            download: RateLimiter::new(download_bps),
+        }
+    }
+
+    pub fn unlimited() -> Self {
+        Self::new(0, 0)
+    }
+
+    pub fn set(&self, upload_bps: u64, download_bps: u64) {
+        self.upload.set_rate(upload_bps);
+        self.download.set_rate(download_bps);
+    }
+
+    pub fn snapshot(&self) -> RateLimitInfo {
+        RateLimitInfo {
+            upload_bps: self.upload.rate(),
+            download_bps: self.download.rate(),
+        }
+    }
+}
+
+impl Default for SwarmRateLimit {
+    fn default() -> Self {
+        Self::unlimited()
+    }
+}
+
+/// 프론트엔드 전송용 제한 정보
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimitInfo {
+    pub upload_bps: u64,
+    pub download_bps: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unlimited_always_consumes() {
+        let limiter = RateLimiter::new(0);
+        for _ in 0..100 {
+            assert!(limiter.try_consume(u32::MAX as u64));
+        }
+    }
+
+    #[test]
+    fn test_debt_blocks_until_refilled() {
+        let limiter = RateLimiter::new(1000);
+
+        // 버스트(1초 분량)보다 큰 요청도 한 번은 허용되고 빚이 남음
+        assert!(limiter.try_consume(5000));
+        assert!(!limiter.try_consume(1));
+    }
+
+    #[test]
+    fn test_set_rate_caps_burst() {
+        let limiter = RateLimiter::new(1_000_000);
+        limiter.set_rate(10);
+        assert_eq!(limiter.rate(), 10);
+
+        assert!(limiter.try_consume(100));
+        assert!(!limiter.try_consume(1));
+    }
+
+    #[tokio::test]
+    async fn test_acquire_waits_for_debt() {
+        let limiter = RateLimiter::new(10_000);
+        limiter.acquire(10_500).await;
+
+        let started = Instant::now();
+        limiter.acquire(1).await;
+        assert!(started.elapsed() >= Duration::from_millis(40));
+    }
+}
@@ -0,0 +1,166 @@
+//! Resume - Grid 다운로드 진행 상태 저장 및 복원
+//!
+//! 작업(job)별로 메타데이터 + 비트필드 + 저장 경로를 디스크에 기록해 두고,
+//! 재시작 시 실제 파일의 조각을 다시 검증하여 검증된 조각부터 이어받습니다.
+
+use crate::grid::bitfield::Bitfield;
+use crate::grid::piece_manager::FileMetadata;
+use crate::util::now_secs;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, warn};
+
+/// 재개 파일 확장자
+const RESUME_EXTENSION: &str = "resume";
+
+/// 작업별 재개 상태
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResumeState {
+    pub job_id: String,
+    pub metadata: FileMetadata,
+    pub save_path: PathBuf,
+    pub bitfield: Bitfield,
+    /// 마지막 저장 시각 (Unix epoch 초)
+    pub updated_at: u64,
+}
+
+impl ResumeState {
+    pub fn new(job_id: String, metadata: FileMetadata, save_path: PathBuf) -> Self {
+        let total_pieces = metadata.total_pieces;
+        Self {
+            job_id,
+            metadata,
+            save_path,
+            bitfield: Bitfield::new(total_pieces),
+            updated_at: now_secs(),
+        }
+    }
+
+    /// 재개 파일 경로
+    pub fn path_for(dir: &Path, job_id: &str) -> PathBuf {
+        // job_id는 프론트엔드에서 오므로 파일명으로 안전한 문자만 사용
+        let safe: String = job_id
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+            .collect();
+        dir.join(format!("{}.{}", safe, RESUME_EXTENSION))
+    }
+
+    /// 디스크에 저장 (임시 파일에 쓴 뒤 rename으로 원자적 교체)
+    pub async fn save(&mut self, dir: &Path) -> anyhow::Result<()> {
+        self.updated_at = now_secs();
+        tokio::fs::create_dir_all(dir).await?;
+
+        let path = Self::path_for(dir, &self.job_id);
+        let tmp = path.with_extension("resume.tmp");
+        let encoded = bincode::serialize(self)?;
+
+        tokio::fs::write(&tmp, &encoded).await?;
+        tokio::fs::rename(&tmp, &path).await?;
+
+        debug!(
+            "💾 재개 상태 저장: {} ({}/{} pieces)",
+            self.job_id,
+            self.bitfield.count_ones(),
+            self.bitfield.len()
+        );
+        Ok(())
+    }
+
+    /// 디스크에서 로드
+    pub async fn load(dir: &Path, job_id: &str) -> anyhow::Result<Self> {
+        let path = Self::path_for(dir, job_id);
+        let data = tokio::fs::read(&path).await?;
+        let state: Self = bincode::deserialize(&data)?;
+
+        if state.bitfield.len() != state.metadata.total_pieces {
+            return Err(anyhow::anyhow!(
+                "Resume bitfield length mismatch: {} vs {}",
+                state.bitfield.len(),
+                state.metadata.total_pieces
+            ));
+        }
+
+        Ok(state)
+    }
+
+    /// 재개 파일 삭제 (다운로드 완료 또는 취소 시)
+    pub async fn remove(dir: &Path, job_id: &str) {
+        let path = Self::path_for(dir, job_id);
+        if let Err(e) = tokio::fs::remove_file(&path).await {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                warn!("재개 파일 삭제 실패: {:?} - {}", path, e);
+            }
+        }
+    }
+
+    /// 저장된 모든 재개 상태 목록
+    pub async fn list(dir: &Path) -> Vec<Self> {
+        let mut states = Vec::new();
+        let Ok(mut entries) = tokio::fs::read_dir(dir).await else {
+            return states;
+        };
+
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some(RESUME_EXTENSION) {
+                continue;
+            }
+            match tokio::fs::read(&path).await.map(|d| bincode::deserialize::<Self>(&d)) {
+                Ok(Ok(state)) => states.push(state),
+                _ => warn!("손상된 재개 파일 무시: {:?}", path),
+            }
+        }
+
+        info!("📂 재개 가능한 Grid 작업 {}개", states.len());
+        states
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_metadata(total_pieces: usize) -> FileMetadata {
+        FileMetadata {
+            info_hash: [7u8; 32],
+            file_name: "test.bin".to_string(),
+            file_size: total_pieces as u64 * 1024,
+            piece_size: 1024,
+            total_pieces,
+            piece_hashes: vec![[0u8; 32]; total_pieces],
+            merkle_root: None,
+            web_seeds: Vec::new(),
+            hash_algorithm: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_path_for_sanitizes_job_id() {
+        let path = ResumeState::path_for(Path::new("/tmp"), "../evil/job");
+        assert_eq!(path, PathBuf::from("/tmp/___evil_job.resume"));
+    }
+
+    #[tokio::test]
+    async fn test_save_load_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("ponswarp-resume-{}", rand::random::<u64>()));
+
+        let mut state = ResumeState::new(
+            "job-1".to_string(),
+            test_metadata(10),
+            PathBuf::from("/tmp/test.bin"),
+        );
+        state.bitfield.mark(2);
+        state.bitfield.mark(5);
+        state.save(&dir).await.unwrap();
+
+        let loaded = ResumeState::load(&dir, "job-1").await.unwrap();
+        assert_eq!(loaded.bitfield.available_pieces(), vec![2, 5]);
+        assert_eq!(ResumeState::list(&dir).await.len(), 1);
+
+        ResumeState::remove(&dir, "job-1").await;
+        assert!(ResumeState::load(&dir, "job-1").await.is_err());
+
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+    }
+}
@@ -6,10 +6,16 @@
 //! - **Random First**: 초기에는 아무 조각이나 빨리 받아 "줄 것이 있는" 상태 확보
 //! - **Rare First**: 복제본이 가장 적은 조각부터 요청
 //! - **Endgame**: 마지막 몇 조각은 모든 피어에게 동시 요청
+//!
+//! 피어마다 진행 중 요청 수를 제한하고 요청별 마감 시각을 추적하여,
+//! 시간 초과된 조각은 다른 피어에게 다시 배정합니다.
+//! 여러 스트림을 쓰는 피어는 요청을 가장 한가한 스트림에 나눠 배정하여,
+//! 느린 조각 하나가 같은 피어의 다른 요청을 막지 않게 합니다.
 
 use rand::seq::SliceRandom;
 use rand::thread_rng;
 use std::collections::{HashMap, HashSet};
+use std::time::Instant;
 use tracing::debug;
 
 /// 피어 ID 타입
@@ -31,9 +37,18 @@ pub enum ScheduleMode {
 pub struct PieceRequest {
     pub piece_index: usize,
     pub target_peer: PeerId,
+    /// 🆕 요청을 보낼 피어 연결의 스트림 번호
+    pub stream: usize,
     pub priority: u32,
 }
 
+/// 🆕 진행 중 요청
+#[derive(Debug, Clone, Copy)]
+struct Outstanding {
+    deadline: Instant,
+    stream: usize,
+}
+
 /// Rare-First 스케줄러
 pub struct Scheduler {
     total_pieces: usize,
@@ -45,6 +60,10 @@ pub struct Scheduler {
     pending_pieces: HashSet<usize>,
     /// 각 피어가 가진 조각 (PeerId -> piece indices)
     peer_pieces: HashMap<PeerId, HashSet<usize>>,
+    /// 피어별 진행 중 요청 (PeerId -> piece index -> 마감 시각, 스트림)
+    outstanding: HashMap<PeerId, HashMap<usize, Outstanding>>,
+    /// 시간 초과된 피어 (piece index -> 재배정 시 제외할 피어)
+    timed_out: HashMap<usize, HashSet<PeerId>>,
     /// 현재 스케줄링 모드
     mode: ScheduleMode,
     /// Endgame 모드 진입 임계값 (남은 조각 수)
@@ -59,6 +78,8 @@ impl Scheduler {
             my_pieces: HashSet::new(),
             pending_pieces: HashSet::new(),
             peer_pieces: HashMap::new(),
+            outstanding: HashMap::new(),
+            timed_out: HashMap::new(),
             mode: ScheduleMode::RandomFirst,
             endgame_threshold: 10, // 마지막 10개 조각부터 Endgame
         }
@@ -104,7 +125,7 @@ impl Scheduler {
         self.update_mode();
     }
 
-    /// 피어 연결 해제 시 호출
+    /// 피어 연결 해제 시 호출 (진행 중 요청은 다른 피어에게 재배정)
     pub fn remove_peer(&mut self, peer_id: &str) {
         if let Some(pieces) = self.peer_pieces.remove(peer_id) {
             for idx in pieces {
@@ -113,6 +134,10 @@ impl Scheduler {
                 }
             }
         }
+        self.release_peer_requests(peer_id);
+        for peers in self.timed_out.values_mut() {
+            peers.remove(peer_id);
+        }
         self.update_mode();
     }
 
@@ -120,6 +145,10 @@ impl Scheduler {
     pub fn mark_completed(&mut self, index: usize) {
         self.my_pieces.insert(index);
         self.pending_pieces.remove(&index);
+        self.timed_out.remove(&index);
+        for requests in self.outstanding.values_mut() {
+            requests.remove(&index);
+        }
         self.update_mode();
 
         debug!(
@@ -135,9 +164,120 @@ impl Scheduler {
         self.pending_pieces.insert(index);
     }
 
+    /// 피어에게 보낸 요청 기록 (deadline까지 응답이 없으면 재배정)
+    pub fn mark_requested(
+        &mut self,
+        peer_id: &str,
+        index: usize,
+        stream: usize,
+        deadline: Instant,
+    ) {
+        self.pending_pieces.insert(index);
+        self.outstanding
+            .entry(peer_id.to_string())
+            .or_default()
+            .insert(index, Outstanding { deadline, stream });
+    }
+
+    /// 피어의 진행 중 요청 수
+    pub fn outstanding_count(&self, peer_id: &str) -> usize {
+        self.outstanding.get(peer_id).map_or(0, |r| r.len())
+    }
+
+    /// 🆕 피어의 스트림별 진행 중 요청 수 (streams개 스트림 기준)
+    fn stream_loads(&self, peer_id: &str, streams: usize) -> Vec<usize> {
+        let mut loads = vec![0; streams];
+        for request in self
+            .outstanding
+            .get(peer_id)
+            .into_iter()
+            .flat_map(|r| r.values())
+        {
+            if let Some(load) = loads.get_mut(request.stream) {
+                *load += 1;
+            }
+        }
+        loads
+    }
+
+    /// 🆕 다음 요청을 보낼 스트림 (진행 중 요청이 가장 적은 스트림)
+    pub fn next_stream(&self, peer_id: &str, streams: usize) -> usize {
+        least_loaded(&self.stream_loads(peer_id, streams.max(1)))
+    }
+
+    /// 해당 조각을 요청 중인 피어 목록 (Endgame 완료 시 Cancel 대상)
+    pub fn requesters(&self, index: usize) -> Vec<PeerId> {
+        self.outstanding
+            .iter()
+            .filter(|(_, requests)| requests.contains_key(&index))
+            .map(|(peer_id, _)| peer_id.clone())
+            .collect()
+    }
+
     /// 요청 취소/실패 시
     pub fn unmark_pending(&mut self, index: usize) {
         self.pending_pieces.remove(&index);
+        for requests in self.outstanding.values_mut() {
+            requests.remove(&index);
+        }
+    }
+
+    /// 피어의 진행 중 요청 모두 해제 (Choke/연결 해제 시)
+    pub fn release_peer_requests(&mut self, peer_id: &str) {
+        let Some(requests) = self.outstanding.remove(peer_id) else {
+            return;
+        };
+        for index in requests.into_keys() {
+            self.release_if_unrequested(index);
+        }
+    }
+
+    /// 마감 시각이 지난 요청 회수 (피어, 조각 목록 반환)
+    pub fn expire_requests(&mut self, now: Instant) -> Vec<(PeerId, usize)> {
+        let mut expired = Vec::new();
+        for (peer_id, requests) in self.outstanding.iter_mut() {
+            requests.retain(|&index, request| {
+                if request.deadline > now {
+                    return true;
+                }
+                expired.push((peer_id.clone(), index));
+                false
+            });
+        }
+
+        for (peer_id, index) in &expired {
+            self.timed_out
+                .entry(*index)
+                .or_default()
+                .insert(peer_id.clone());
+            self.release_if_unrequested(*index);
+        }
+        expired
+    }
+
+    /// 다른 피어도 요청 중이 아니면 pending 해제
+    fn release_if_unrequested(&mut self, index: usize) {
+        if !self.outstanding.values().any(|r| r.contains_key(&index)) {
+            self.pending_pieces.remove(&index);
+        }
+    }
+
+    /// 모든 진행 중 요청 해제 (일시정지 시 재개 후 다시 요청하도록)
+    pub fn clear_pending(&mut self) {
+        self.pending_pieces.clear();
+        self.outstanding.clear();
+    }
+
+    /// 시간 초과된 피어는 다른 보유 피어가 있는 한 재배정 대상에서 제외
+    fn is_excluded(&self, index: usize, peer_id: &str) -> bool {
+        let Some(timed_out) = self.timed_out.get(&index) else {
+            return false;
+        };
+        timed_out.contains(peer_id)
+            && self
+                .peer_pieces
+                .iter()
+                .any(|(other, pieces)| !timed_out.contains(other) && pieces.contains(&index))
     }
 
     /// 스케줄링 모드 업데이트
@@ -216,38 +356,65 @@ impl Scheduler {
         top_candidates.choose(&mut thread_rng()).copied()
     }
 
-    /// 여러 피어에게 요청할 조각 목록 생성
-    pub fn generate_requests(&self, max_requests: usize) -> Vec<PieceRequest> {
+    /// 요청 가능한 피어마다 진행 중 요청이 max_per_peer가 되도록 조각 목록 생성
+    ///
+    /// `peer_streams`는 피어에게 요청을 보낼 수 있는 스트림 수이며, 0이면 (Choke 등)
+    /// 건너뜁니다. 요청은 진행 중 요청이 가장 적은 스트림부터 나눠 배정합니다.
+    /// Endgame 모드에서는 다른 피어에게 요청 중인 조각도 중복 요청합니다.
+    pub fn generate_requests<F>(&self, max_per_peer: usize, peer_streams: F) -> Vec<PieceRequest>
+    where
+        F: Fn(&str) -> usize,
+    {
         let mut requests = Vec::new();
         let mut used_pieces: HashSet<usize> = HashSet::new();
+        let endgame = self.mode == ScheduleMode::Endgame;
+        let empty = HashMap::new();
 
-        // 각 피어별로 요청 생성
         for (peer_id, peer_pieces) in &self.peer_pieces {
-            if requests.len() >= max_requests {
-                break;
+            let streams = peer_streams(peer_id);
+            if streams == 0 {
+                continue;
+            }
+
+            let in_flight = self.outstanding.get(peer_id).unwrap_or(&empty);
+            let slots = max_per_peer.saturating_sub(in_flight.len());
+            if slots == 0 {
+                continue;
             }
 
             // 이 피어에게 요청할 수 있는 조각
-            let candidates: Vec<usize> = peer_pieces
+            let mut loads = self.stream_loads(peer_id, streams);
+            let mut candidates: Vec<usize> = peer_pieces
                 .iter()
                 .filter(|&&idx| {
                     !self.my_pieces.contains(&idx)
-                        && !self.pending_pieces.contains(&idx)
-                        && !used_pieces.contains(&idx)
+                        && !in_flight.contains_key(&idx)
+                        && !self.is_excluded(idx, peer_id)
+                        && (endgame
+                            || (!self.pending_pieces.contains(&idx) && !used_pieces.contains(&idx)))
                 })
                 .copied()
                 .collect();
 
-            if let Some(piece_idx) = self.select_rarest(&candidates) {
+            for _ in 0..slots {
+                let Some(piece_idx) = self.select_rarest(&candidates) else {
+                    break;
+                };
+                candidates.retain(|&idx| idx != piece_idx);
+
                 let priority = if self.piece_frequency[piece_idx] == 1 {
                     100 // 유일한 복제본 - 최우선
                 } else {
                     (100 - self.piece_frequency[piece_idx].min(99)) as u32
                 };
 
+                let stream = least_loaded(&loads);
+                loads[stream] += 1;
+
                 requests.push(PieceRequest {
                     piece_index: piece_idx,
                     target_peer: peer_id.clone(),
+                    stream,
                     priority,
                 });
 
@@ -260,6 +427,22 @@ impl Scheduler {
         requests
     }
 
+    /// Web Seed로 받을 조각 목록 (피어 보유 수가 threshold 이하인 미완료 조각)
+    pub fn web_seed_candidates(&self, max: usize, availability_threshold: usize) -> Vec<usize> {
+        let mut candidates: Vec<usize> = (0..self.total_pieces)
+            .filter(|idx| {
+                !self.my_pieces.contains(idx)
+                    && !self.pending_pieces.contains(idx)
+                    && self.piece_frequency[*idx] <= availability_threshold
+            })
+            .collect();
+
+        // 가장 희귀한 조각부터
+        candidates.sort_by_key(|&idx| self.piece_frequency[idx]);
+        candidates.truncate(max);
+        candidates
+    }
+
     /// Endgame 모드에서 모든 피어에게 요청할 조각 목록
     pub fn endgame_requests(&self) -> Vec<(usize, Vec<PeerId>)> {
         if self.mode != ScheduleMode::Endgame {
@@ -318,6 +501,15 @@ impl Scheduler {
     }
 }
 
+/// 🆕 진행 중 요청이 가장 적은 스트림 (같으면 번호가 작은 쪽)
+fn least_loaded(loads: &[usize]) -> usize {
+    loads
+        .iter()
+        .enumerate()
+        .min_by_key(|&(stream, &load)| (load, stream))
+        .map_or(0, |(stream, _)| stream)
+}
+
 /// 스케줄러 통계
 #[derive(Debug, Clone)]
 pub struct SchedulerStats {
@@ -389,6 +581,21 @@ mod tests {
         assert_eq!(scheduler.mode(), ScheduleMode::Endgame);
     }
 
+    #[test]
+    fn test_web_seed_candidates() {
+        let mut scheduler = Scheduler::new(6);
+
+        scheduler.set_peer_bitfield("peer1", vec![0, 1, 2]);
+        scheduler.set_peer_bitfield("peer2", vec![0, 1]);
+        scheduler.mark_completed(3);
+        scheduler.mark_pending(4);
+
+        // 피어가 없는 5번 -> 1명만 가진 2번 순서, 완료/요청 중인 조각 제외
+        assert_eq!(scheduler.web_seed_candidates(10, 1), vec![5, 2]);
+        assert_eq!(scheduler.web_seed_candidates(10, 0), vec![5]);
+        assert_eq!(scheduler.web_seed_candidates(1, 2).len(), 1);
+    }
+
     #[test]
     fn test_generate_requests() {
         let mut scheduler = Scheduler::new(10);
@@ -397,8 +604,81 @@ mod tests {
         scheduler.set_peer_bitfield("peer2", vec![3, 4, 5]);
         scheduler.set_peer_bitfield("peer3", vec![6, 7, 8, 9]);
 
-        let requests = scheduler.generate_requests(5);
-        assert!(!requests.is_empty());
-        assert!(requests.len() <= 5);
+        let requests = scheduler.generate_requests(2, |_| 1);
+        assert_eq!(requests.len(), 6);
+
+        // 같은 조각이 두 번 배정되지 않음
+        let unique: HashSet<usize> = requests.iter().map(|r| r.piece_index).collect();
+        assert_eq!(unique.len(), requests.len());
+
+        // Choke 상태인 피어는 제외
+        let requests = scheduler.generate_requests(2, |peer| usize::from(peer != "peer3"));
+        assert!(requests.iter().all(|r| r.target_peer != "peer3"));
+    }
+
+    #[test]
+    fn test_outstanding_limit_and_timeout_reassign() {
+        let mut scheduler = Scheduler::new(20);
+        scheduler.set_peer_bitfield("slow", (0..20).collect());
+        scheduler.set_peer_bitfield("fast", (0..20).collect());
+
+        let now = Instant::now();
+        for req in scheduler.generate_requests(4, |peer| usize::from(peer == "slow")) {
+            scheduler.mark_requested(&req.target_peer, req.piece_index, req.stream, now);
+        }
+        assert_eq!(scheduler.outstanding_count("slow"), 4);
+        assert!(scheduler
+            .generate_requests(4, |peer| usize::from(peer == "slow"))
+            .is_empty());
+
+        // 마감 시각 경과 -> 회수 후 다른 피어에게만 재배정
+        let expired = scheduler.expire_requests(now + std::time::Duration::from_secs(1));
+        assert_eq!(expired.len(), 4);
+        assert_eq!(scheduler.outstanding_count("slow"), 0);
+
+        let expired_pieces: HashSet<usize> = expired.iter().map(|(_, idx)| *idx).collect();
+        let retry = scheduler.generate_requests(20, |_| 1);
+        for req in &retry {
+            if expired_pieces.contains(&req.piece_index) {
+                assert_eq!(req.target_peer, "fast");
+            }
+        }
+    }
+
+    #[test]
+    fn test_release_on_disconnect() {
+        let mut scheduler = Scheduler::new(4);
+        scheduler.set_peer_bitfield("peer1", vec![0, 1]);
+        scheduler.mark_requested("peer1", 0, 0, Instant::now());
+
+        scheduler.remove_peer("peer1");
+        assert_eq!(scheduler.stats().pending, 0);
+        assert_eq!(scheduler.outstanding_count("peer1"), 0);
+    }
+
+    #[test]
+    fn test_requests_spread_across_streams() {
+        let mut scheduler = Scheduler::new(20);
+        scheduler.set_peer_bitfield("multi", (0..10).collect());
+        scheduler.set_peer_bitfield("single", (10..20).collect());
+
+        let now = Instant::now();
+        let requests = scheduler.generate_requests(8, |peer| if peer == "multi" { 4 } else { 1 });
+        for req in &requests {
+            scheduler.mark_requested(&req.target_peer, req.piece_index, req.stream, now);
+        }
+        assert_eq!(scheduler.stream_loads("multi", 4), vec![2, 2, 2, 2]);
+        assert_eq!(scheduler.stream_loads("single", 1), vec![8]);
+
+        // 응답이 온 스트림이 다음 요청을 받음
+        let done = requests
+            .iter()
+            .find(|r| r.target_peer == "multi" && r.stream == 2)
+            .unwrap();
+        scheduler.mark_completed(done.piece_index);
+        assert_eq!(scheduler.next_stream("multi", 4), 2);
+        let refill = scheduler.generate_requests(8, |peer| usize::from(peer == "multi") * 4);
+        assert_eq!(refill.len(), 1);
+        assert_eq!(refill[0].stream, 2);
     }
 }
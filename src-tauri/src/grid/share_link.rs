@@ -0,0 +1,183 @@
+//! Share Link - Grid 콘텐츠 공유용 매그넷 스타일 URI
+//!
+//! 형식: `pons://<info_hash(hex)>?name=<파일명>&size=<바이트>&bs=<조각 크기>`
+//! 채팅 등으로 문자열만 전달하면, 수신 측은 info_hash로 DHT에서 피어를 찾습니다.
+
+use crate::grid::piece_manager::FileMetadata;
+use serde::Serialize;
+
+/// URI 스킴
+pub const SHARE_LINK_SCHEME: &str = "pons://";
+
+/// 파싱된 공유 링크
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShareLink {
+    #[serde(serialize_with = "serialize_hex")]
+    pub info_hash: [u8; 32],
+    pub name: String,
+    pub size: u64,
+    pub piece_size: u32,
+}
+
+fn serialize_hex<S: serde::Serializer>(hash: &[u8; 32], s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_str(&hex::encode(hash))
+}
+
+impl ShareLink {
+    pub fn from_metadata(metadata: &FileMetadata) -> Self {
+        Self {
+            info_hash: metadata.info_hash,
+            name: metadata.file_name.clone(),
+            size: metadata.file_size,
+            piece_size: metadata.piece_size,
+        }
+    }
+
+    /// 예상 조각 수
+    pub fn total_pieces(&self) -> usize {
+        if self.piece_size == 0 {
+            return 0;
+        }
+        self.size.div_ceil(self.piece_size as u64) as usize
+    }
+
+    /// URI 문자열로 변환
+    pub fn to_uri(&self) -> String {
+        format!(
+            "{}{}?name={}&size={}&bs={}",
+            SHARE_LINK_SCHEME,
+            hex::encode(self.info_hash),
+            percent_encode(&self.name),
+            self.size,
+            self.piece_size
+        )
+    }
+
+    /// URI 문자열 파싱
+    pub fn parse(uri: &str) -> Result<Self, String> {
+        let rest = uri
+            .trim()
+            .strip_prefix(SHARE_LINK_SCHEME)
+            .ok_or_else(|| format!("Invalid scheme (expected {})", SHARE_LINK_SCHEME))?;
+
+        let (hash_part, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let hash_bytes = hex::decode(hash_part.trim_end_matches('/'))
+            .map_err(|e| format!("Invalid info hash: {}", e))?;
+        let info_hash: [u8; 32] = hash_bytes
+            .try_into()
+            .map_err(|_| "Info hash must be 32 bytes".to_string())?;
+
+        let mut name = None;
+        let mut size = None;
+        let mut piece_size = None;
+
+        for pair in query.split('&').filter(|p| !p.is_empty()) {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            match key {
+                "name" => name = Some(percent_decode(value)?),
+                "size" => {
+                    size = Some(value.parse::<u64>().map_err(|e| format!("Invalid size: {}", e))?)
+                }
+                "bs" => {
+                    piece_size =
+                        Some(value.parse::<u32>().map_err(|e| format!("Invalid bs: {}", e))?)
+                }
+                // 향후 확장 파라미터는 무시
+                _ => {}
+            }
+        }
+
+        let piece_size = piece_size.ok_or("Missing bs parameter")?;
+        if piece_size == 0 {
+            return Err("bs must be greater than 0".to_string());
+        }
+
+        Ok(Self {
+            info_hash,
+            name: name.unwrap_or_else(|| hex::encode(&info_hash[..8])),
+            size: size.ok_or("Missing size parameter")?,
+            piece_size,
+        })
+    }
+}
+
+/// RFC 3986 unreserved 문자를 제외하고 UTF-8 바이트 단위로 인코딩
+fn percent_encode(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for byte in input.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(byte as char)
+            }
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    out
+}
+
+fn percent_decode(input: &str) -> Result<String, String> {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                let hex = input
+                    .get(i + 1..i + 3)
+                    .ok_or("Truncated percent escape")?;
+                let byte =
+                    u8::from_str_radix(hex, 16).map_err(|_| format!("Invalid escape: %{}", hex))?;
+                out.push(byte);
+                i += 3;
+            }
+            b'+' => {
+                out.push(b' ');
+                i += 1;
+            }
+            b => {
+                out.push(b);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8(out).map_err(|e| format!("Invalid UTF-8 in name: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> ShareLink {
+        ShareLink {
+            info_hash: [0xab; 32],
+            name: "보고서 final (1).pdf".to_string(),
+            size: 3 * 1024 * 1024 + 5,
+            piece_size: 1024 * 1024,
+        }
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let link = sample();
+        let uri = link.to_uri();
+        assert!(uri.starts_with("pons://abab"));
+        assert!(!uri.contains(' '));
+
+        assert_eq!(ShareLink::parse(&uri).unwrap(), link);
+        assert_eq!(link.total_pieces(), 4);
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid() {
+        assert!(ShareLink::parse("magnet:?xt=urn:btih:abc").is_err());
+        assert!(ShareLink::parse("pons://abcd?size=1&bs=1").is_err());
+
+        let hash = hex::encode([1u8; 32]);
+        assert!(ShareLink::parse(&format!("pons://{}?size=10", hash)).is_err());
+        assert!(ShareLink::parse(&format!("pons://{}?size=10&bs=0", hash)).is_err());
+        assert!(ShareLink::parse(&format!("pons://{}?name=%E&size=10&bs=1", hash)).is_err());
+    }
+}
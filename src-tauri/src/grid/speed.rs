@@ -0,0 +1,94 @@
+//! Speed - 최근 구간 기준 전송 속도 추정
+//!
+//! 누적 바이트 / 누적 시간은 연결 초반 속도에 끌려가므로, 지수 가중 이동 평균(EWMA)으로
+//! 최근 몇 초의 속도를 추정합니다. 각 전송량은 `bytes / window` 만큼 속도를 올리고
+//! 이후 `e^(-t / window)`로 감쇠하므로, 일정한 흐름에서는 실제 속도에 수렴합니다.
+
+use std::time::{Duration, Instant};
+
+/// 기본 추정 구간
+pub const DEFAULT_SPEED_WINDOW: Duration = Duration::from_secs(5);
+
+/// EWMA 기반 속도 추정기
+#[derive(Debug, Clone)]
+pub struct RateEstimator {
+    /// 마지막 갱신 시점의 속도 (bytes/sec)
+    rate: f64,
+    last_update: Instant,
+    window_secs: f64,
+}
+
+impl Default for RateEstimator {
+    fn default() -> Self {
+        Self::new(DEFAULT_SPEED_WINDOW)
+    }
+}
+
+impl RateEstimator {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            rate: 0.0,
+            last_update: Instant::now(),
+            window_secs: window.as_secs_f64().max(0.001),
+        }
+    }
+
+    /// 전송량 기록
+    pub fn record(&mut self, bytes: u64) {
+        self.record_at(bytes, Instant::now());
+    }
+
+    pub fn record_at(&mut self, bytes: u64, now: Instant) {
+        self.rate = self.decayed(now) + bytes as f64 / self.window_secs;
+        self.last_update = now;
+    }
+
+    /// 현재 속도 (bytes/sec)
+    pub fn rate(&self) -> u64 {
+        self.rate_at(Instant::now())
+    }
+
+    pub fn rate_at(&self, now: Instant) -> u64 {
+        self.decayed(now).round() as u64
+    }
+
+    fn decayed(&self, now: Instant) -> f64 {
+        let elapsed = now
+            .saturating_duration_since(self.last_update)
+            .as_secs_f64();
+        self.rate * (-elapsed / self.window_secs).exp()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_converges_to_steady_rate() {
+        let start = Instant::now();
+        let mut estimator = RateEstimator::new(Duration::from_secs(5));
+
+        // 100ms마다 1000 bytes = 10 KB/s, 30초간
+        for i in 1..=300 {
+            estimator.record_at(1000, start + Duration::from_millis(i * 100));
+        }
+        let rate = estimator.rate_at(start + Duration::from_secs(30));
+        assert!((9_000..=11_000).contains(&rate), "rate = {}", rate);
+    }
+
+    #[test]
+    fn test_decays_when_idle() {
+        let start = Instant::now();
+        let mut estimator = RateEstimator::new(Duration::from_secs(5));
+        for i in 1..=100 {
+            estimator.record_at(10_000, start + Duration::from_millis(i * 100));
+        }
+        let busy = estimator.rate_at(start + Duration::from_secs(10));
+
+        // 30초 정지 후에는 거의 0 (누적 평균과 달리 현재 상태 반영)
+        let idle = estimator.rate_at(start + Duration::from_secs(40));
+        assert!(busy > 50_000);
+        assert!(idle < busy / 100);
+    }
+}
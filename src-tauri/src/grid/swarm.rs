@@ -2,19 +2,27 @@
 //!
 //! 여러 피어와의 연결을 관리하고, 스케줄러와 협력하여 데이터를 효율적으로 전송합니다.
 
+use crate::grid::metadata_exchange::MetadataAssembler;
 use crate::grid::peer::{Peer, PeerCommand, PeerEvent, PeerState};
+use crate::grid::peer_score::{BanList, Offense, PeerScore, BAN_DURATION};
 use crate::grid::piece_manager::{FileMetadata, PieceManager};
-use crate::grid::protocol::GridMessage;
+use crate::grid::protocol::{extensions, GridMessage};
+use crate::grid::rate_limit::SwarmRateLimit;
+use crate::grid::resume::ResumeState;
 use crate::grid::scheduler::{PieceRequest, Scheduler};
-use crate::grid::{GridStateUpdate, PeerStatus};
-use quinn::Endpoint;
-use std::collections::HashMap;
-use std::net::SocketAddr;
+use crate::grid::speed::RateEstimator;
+use crate::grid::web_seed::WebSeed;
+use crate::grid::{config, GridJobState, GridStateUpdate, PeerStatus};
+use quinn::{ClientConfig, Connection, Endpoint};
+use rand::seq::SliceRandom;
+use std::collections::{HashMap, HashSet};
+use std::net::{IpAddr, SocketAddr};
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
+use tauri::async_runtime::JoinHandle;
 use tauri::{AppHandle, Emitter};
-use tokio::sync::{mpsc, RwLock, Semaphore};
+use tokio::sync::{mpsc, OwnedSemaphorePermit, RwLock, Semaphore};
 use tokio::time::interval;
 use tracing::{debug, error, info, warn};
 
@@ -39,8 +47,21 @@ pub enum SwarmCommand {
         metadata: FileMetadata,
         save_path: PathBuf,
     },
-    /// 전송 중지
-    Stop,
+    /// Info Hash만으로 다운로드 시작 (피어에게서 메타데이터를 먼저 받음)
+    StartDownloadByHash {
+        info_hash: [u8; 32],
+        save_path: PathBuf,
+    },
+    /// 저장된 상태에서 다운로드 재개 (기존 조각 재검증 후 이어받기)
+    ResumeDownload(ResumeState),
+    /// 속도 제한 변경 (bytes/sec, 0 = 무제한)
+    SetRateLimit { upload_bps: u64, download_bps: u64 },
+    /// 일시정지 (모든 피어 Choke, 새 요청 중단)
+    Pause,
+    /// 일시정지 해제
+    Resume,
+    /// 전송 중지 (delete_partial이면 미완료 다운로드 파일 삭제)
+    Stop { delete_partial: bool },
 }
 
 /// Swarm 외부 이벤트
@@ -60,16 +81,59 @@ pub enum SwarmEvent {
     StateUpdate(GridStateUpdate),
 }
 
+/// Web Seed 조각 다운로드 결과
+struct WebSeedResult {
+    url: String,
+    piece_index: usize,
+    result: anyhow::Result<Vec<u8>>,
+}
+
+/// 🆕 조각 업로드 태스크 결과
+struct UploadResult {
+    peer_id: String,
+    piece_index: u32,
+    /// 피어에게 넘긴 바이트 (읽기/전송 실패 시 None)
+    sent: Option<u64>,
+}
+
+/// 메타데이터 수신 대기 중인 다운로드
+struct PendingMetadata {
+    assembler: MetadataAssembler,
+    save_path: PathBuf,
+    /// 메타데이터 확보 전에 받은 피어별 보유 조각 (확보 후 스케줄러에 반영)
+    peer_pieces: HashMap<String, Vec<usize>>,
+}
+
 /// 피어 연결 정보
 struct PeerConnection {
     command_tx: mpsc::Sender<PeerCommand>,
     state: PeerState,
+    /// 이 피어에게 PEX로 이미 알린 주소
+    pex_sent: HashSet<SocketAddr>,
+    /// 🆕 진행 중인 조각 업로드 (조각 번호 -> 전송 태스크, Cancel 수신 시 중단)
+    uploads: HashMap<u32, JoinHandle<()>>,
+}
+
+impl Drop for PeerConnection {
+    /// 연결이 끝나면 남은 업로드도 중단
+    fn drop(&mut self) {
+        for task in self.uploads.values() {
+            task.abort();
+        }
+    }
 }
 
 /// Grid Swarm Manager
 pub struct GridSwarm {
     /// QUIC 엔드포인트
     endpoint: Endpoint,
+    /// 🆕 QUIC 서버와 엔드포인트를 공유할 때 라우터가 넘겨주는 들어오는 연결
+    /// (None이면 엔드포인트에서 직접 수락)
+    shared_incoming: Option<mpsc::Receiver<Connection>>,
+    /// 🆕 나가는 연결 설정 (None이면 엔드포인트 기본 설정)
+    client_config: Option<ClientConfig>,
+    /// 🆕 나가는 연결의 SNI (공유 엔드포인트 쪽에서 info_hash로 Swarm을 찾음)
+    server_name: String,
     /// 연결된 피어 목록
     peers: HashMap<String, PeerConnection>,
     /// 파일 상태 관리자
@@ -92,12 +156,44 @@ pub struct GridSwarm {
     app_handle: Option<AppHandle>,
     /// Job ID
     job_id: String,
-    /// 시작 시간
-    started_at: Instant,
     /// 총 다운로드 바이트
     total_downloaded: u64,
     /// 총 업로드 바이트
     total_uploaded: u64,
+    /// 최근 다운로드 속도 추정 (전체)
+    download_rate: RateEstimator,
+    /// 최근 업로드 속도 추정 (전체)
+    upload_rate: RateEstimator,
+    /// 업로드/다운로드 속도 제한
+    rate_limit: Arc<SwarmRateLimit>,
+    /// 재개 상태 저장 디렉토리 (None이면 저장하지 않음)
+    resume_dir: Option<PathBuf>,
+    /// 현재 다운로드의 재개 상태
+    resume_state: Option<ResumeState>,
+    /// 마지막 저장 이후 변경 여부
+    resume_dirty: bool,
+    /// 메타데이터 교환 진행 상태 (Info Hash로 시작한 경우)
+    pending_metadata: Option<PendingMetadata>,
+    /// 일시정지 여부
+    paused: bool,
+    /// 현재 역할 (Seeding / Downloading / FetchingMetadata)
+    role_state: GridJobState,
+    /// IP별 벌점 (검증 실패, churn, 프로토콜 위반)
+    peer_scores: HashMap<IpAddr, PeerScore>,
+    /// 공유 차단 목록
+    ban_list: Arc<BanList>,
+    /// HTTP(S) Web Seed 목록
+    web_seeds: Vec<Arc<WebSeed>>,
+    /// 진행 중인 Web Seed 요청 수
+    web_seed_inflight: usize,
+    /// Web Seed 결과 수신
+    web_seed_rx: mpsc::Receiver<WebSeedResult>,
+    /// Web Seed 결과 발송 (다운로드 태스크에 전달)
+    web_seed_tx: mpsc::Sender<WebSeedResult>,
+    /// 🆕 조각 업로드 결과 수신
+    upload_rx: mpsc::Receiver<UploadResult>,
+    /// 🆕 조각 업로드 결과 발송 (업로드 태스크에 전달)
+    upload_tx: mpsc::Sender<UploadResult>,
 }
 
 impl GridSwarm {
@@ -108,6 +204,8 @@ impl GridSwarm {
         event_tx: mpsc::Sender<SwarmEvent>,
     ) -> Self {
         let (peer_event_tx, peer_event_rx) = mpsc::channel(256);
+        let (web_seed_tx, web_seed_rx) = mpsc::channel(config::WEB_SEED_MAX_INFLIGHT);
+        let (upload_tx, upload_rx) = mpsc::channel(64);
         let total_pieces = {
             // 동기적으로 접근할 수 없으므로 기본값 사용
             1000 // 나중에 초기화 시 업데이트
@@ -120,6 +218,9 @@ impl GridSwarm {
 
         Self {
             endpoint,
+            shared_incoming: None,
+            client_config: None,
+            server_name: "localhost".to_string(),
             peers: HashMap::new(),
             piece_manager,
             scheduler: Scheduler::new(total_pieces),
@@ -131,9 +232,25 @@ impl GridSwarm {
             my_peer_id,
             app_handle: None,
             job_id: String::new(),
-            started_at: Instant::now(),
             total_downloaded: 0,
             total_uploaded: 0,
+            download_rate: RateEstimator::default(),
+            upload_rate: RateEstimator::default(),
+            rate_limit: Arc::new(SwarmRateLimit::unlimited()),
+            resume_dir: None,
+            resume_state: None,
+            resume_dirty: false,
+            pending_metadata: None,
+            paused: false,
+            role_state: GridJobState::Idle,
+            peer_scores: HashMap::new(),
+            ban_list: Arc::new(BanList::in_memory()),
+            web_seeds: Vec::new(),
+            web_seed_inflight: 0,
+            web_seed_rx,
+            web_seed_tx,
+            upload_rx,
+            upload_tx,
         }
     }
 
@@ -147,13 +264,44 @@ impl GridSwarm {
         self.job_id = job_id;
     }
 
+    /// 재개 상태 저장 디렉토리 설정
+    pub fn set_resume_dir(&mut self, dir: PathBuf) {
+        self.resume_dir = Some(dir);
+    }
+
+    /// 속도 제한 공유 객체 설정 (외부에서 job_id 기준으로 조정 가능)
+    pub fn set_rate_limit(&mut self, rate_limit: Arc<SwarmRateLimit>) {
+        self.rate_limit = rate_limit;
+    }
+
+    /// 공유 차단 목록 설정
+    pub fn set_ban_list(&mut self, ban_list: Arc<BanList>) {
+        self.ban_list = ban_list;
+    }
+
+    /// 🆕 나가는 연결의 SNI 설정
+    pub fn set_server_name(&mut self, server_name: String) {
+        self.server_name = server_name;
+    }
+
+    /// 🆕 QUIC 서버 엔드포인트 공유 모드 (들어오는 연결은 라우터에서 받음)
+    pub fn set_shared_endpoint(
+        &mut self,
+        incoming: mpsc::Receiver<Connection>,
+        client_config: ClientConfig,
+    ) {
+        self.shared_incoming = Some(incoming);
+        self.client_config = Some(client_config);
+    }
+
     /// 메인 실행 루프
     pub async fn run(mut self) {
         info!("🐝 Grid Swarm 시작");
-        self.started_at = Instant::now();
 
         let mut status_interval = interval(Duration::from_secs(1));
         let mut schedule_interval = interval(Duration::from_millis(100));
+        let mut pex_interval = interval(Duration::from_secs(config::PEX_INTERVAL_SECS));
+        let mut choke_interval = interval(Duration::from_secs(config::CHOKE_INTERVAL_SECS));
 
         loop {
             tokio::select! {
@@ -170,7 +318,9 @@ impl GridSwarm {
                             self.broadcast_have(index).await;
                         }
                         Some(SwarmCommand::RequestPiece { peer_id, piece_index }) => {
-                            self.request_piece(&peer_id, piece_index).await;
+                            let streams = self.peer_streams(&peer_id);
+                            let stream = self.scheduler.next_stream(&peer_id, streams);
+                            self.request_piece(&peer_id, piece_index, stream).await;
                         }
                         Some(SwarmCommand::StartSeeding { file_path, metadata }) => {
                             self.start_seeding(file_path, metadata).await;
@@ -178,11 +328,31 @@ impl GridSwarm {
                         Some(SwarmCommand::StartDownload { metadata, save_path }) => {
                             self.start_download(metadata, save_path).await;
                         }
-                        Some(SwarmCommand::Stop) => {
+                        Some(SwarmCommand::StartDownloadByHash { info_hash, save_path }) => {
+                            self.start_download_by_hash(info_hash, save_path).await;
+                        }
+                        Some(SwarmCommand::ResumeDownload(resume)) => {
+                            self.resume_download(resume).await;
+                        }
+                        Some(SwarmCommand::SetRateLimit { upload_bps, download_bps }) => {
+                            info!("🚦 Swarm 속도 제한: up {} B/s, down {} B/s", upload_bps, download_bps);
+                            self.rate_limit.set(upload_bps, download_bps);
+                        }
+                        Some(SwarmCommand::Pause) => {
+                            self.pause().await;
+                        }
+                        Some(SwarmCommand::Resume) => {
+                            self.resume().await;
+                        }
+                        Some(SwarmCommand::Stop { delete_partial }) => {
                             info!("🛑 Swarm 중지 요청");
+                            self.teardown(delete_partial).await;
+                            break;
+                        }
+                        None => {
+                            self.teardown(false).await;
                             break;
                         }
-                        None => break,
                     }
                 }
 
@@ -193,19 +363,43 @@ impl GridSwarm {
                     }
                 }
 
-                // 3. 들어오는 연결 수락
-                Some(incoming) = self.endpoint.accept() => {
+                // 2-1. Web Seed 결과 처리
+                Some(result) = self.web_seed_rx.recv() => {
+                    self.handle_web_seed_result(result).await;
+                }
+
+                // 2-2. 🆕 조각 업로드 결과 처리
+                Some(result) = self.upload_rx.recv() => {
+                    self.handle_upload_result(result);
+                }
+
+                // 3. 들어오는 연결 수락 (공유 엔드포인트는 QUIC 서버가 수락해 전달)
+                Some(incoming) = self.endpoint.accept(), if self.shared_incoming.is_none() => {
                     self.handle_incoming_connection(incoming).await;
                 }
+                Some(connection) = Self::recv_shared(&mut self.shared_incoming) => {
+                    self.handle_routed_connection(connection).await;
+                }
 
-                // 4. 주기적 스케줄링
-                _ = schedule_interval.tick() => {
+                // 4. 주기적 스케줄링 (일시정지 중에는 요청하지 않음)
+                _ = schedule_interval.tick(), if !self.paused => {
                     self.schedule_requests().await;
                 }
 
                 // 5. 상태 업데이트 브로드캐스트
                 _ = status_interval.tick() => {
                     self.broadcast_status().await;
+                    self.save_resume_state().await;
+                }
+
+                // 6. Peer Exchange
+                _ = pex_interval.tick() => {
+                    self.broadcast_pex().await;
+                }
+
+                // 7. Choke 재계산
+                _ = choke_interval.tick(), if !self.paused => {
+                    self.rechoke().await;
                 }
             }
         }
@@ -216,12 +410,17 @@ impl GridSwarm {
     /// 피어에 연결
     async fn connect_to_peer(&mut self, addr: SocketAddr) {
         // 이미 연결된 피어인지 확인
-        let peer_key = addr.to_string();
+        let peer_key = format!("peer_{}", addr);
         if self.peers.contains_key(&peer_key) {
             debug!("이미 연결된 피어: {}", addr);
             return;
         }
 
+        if self.ban_list.is_banned(&addr.ip()) {
+            debug!("⛔ 차단된 피어, 연결 생략: {}", addr);
+            return;
+        }
+
         // 연결 제한 확인
         let permit = match self.connection_semaphore.clone().try_acquire_owned() {
             Ok(p) => p,
@@ -233,7 +432,13 @@ impl GridSwarm {
 
         info!("🔗 피어 연결 시도: {}", addr);
 
-        match self.endpoint.connect(addr, "localhost") {
+        let connecting = match &self.client_config {
+            Some(config) => self
+                .endpoint
+                .connect_with(config.clone(), addr, &self.server_name),
+            None => self.endpoint.connect(addr, &self.server_name),
+        };
+        match connecting {
             Ok(connecting) => match connecting.await {
                 Ok(connection) => {
                     info!("✅ 피어 연결 성공: {}", addr);
@@ -246,6 +451,7 @@ impl GridSwarm {
                         cmd_rx,
                         self.peer_event_tx.clone(),
                         self.my_peer_id,
+                        true,
                     );
 
                     let peer_id = peer.peer_id().to_string();
@@ -256,6 +462,8 @@ impl GridSwarm {
                         PeerConnection {
                             command_tx: cmd_tx,
                             state: PeerState::new(peer_id.clone(), addr.to_string()),
+                            pex_sent: HashSet::new(),
+                            uploads: HashMap::new(),
                         },
                     );
 
@@ -292,6 +500,12 @@ impl GridSwarm {
 
     /// 들어오는 연결 처리
     async fn handle_incoming_connection(&mut self, incoming: quinn::Incoming) {
+        if self.ban_list.is_banned(&incoming.remote_address().ip()) {
+            debug!("⛔ 차단된 피어의 연결 거부: {}", incoming.remote_address());
+            incoming.refuse();
+            return;
+        }
+
         let permit = match self.connection_semaphore.clone().try_acquire_owned() {
             Ok(p) => p,
             Err(_) => {
@@ -301,39 +515,72 @@ impl GridSwarm {
         };
 
         match incoming.await {
-            Ok(connection) => {
-                let addr = connection.remote_address();
-                info!("📥 들어오는 연결 수락: {}", addr);
-
-                let (cmd_tx, cmd_rx) = mpsc::channel(32);
-                let peer = Peer::new(
-                    connection,
-                    self.piece_manager.clone(),
-                    cmd_rx,
-                    self.peer_event_tx.clone(),
-                    self.my_peer_id,
-                );
+            Ok(connection) => self.add_incoming_peer(connection, permit).await,
+            Err(e) => {
+                error!("❌ 들어오는 연결 실패: {}", e);
+            }
+        }
+    }
 
-                let peer_id = peer.peer_id().to_string();
+    /// 🆕 QUIC 서버가 수락해 넘겨준 연결 처리
+    async fn handle_routed_connection(&mut self, connection: Connection) {
+        if self.ban_list.is_banned(&connection.remote_address().ip()) {
+            debug!(
+                "⛔ 차단된 피어의 연결 거부: {}",
+                connection.remote_address()
+            );
+            connection.close(0u32.into(), b"banned");
+            return;
+        }
 
-                self.peers.insert(
-                    peer_id.clone(),
-                    PeerConnection {
-                        command_tx: cmd_tx,
-                        state: PeerState::new(peer_id.clone(), addr.to_string()),
-                    },
-                );
+        let Ok(permit) = self.connection_semaphore.clone().try_acquire_owned() else {
+            warn!("최대 연결 수 초과, 들어오는 연결 거부");
+            connection.close(0u32.into(), b"too many peers");
+            return;
+        };
+        self.add_incoming_peer(connection, permit).await;
+    }
+
+    /// 수락한 연결로 피어 태스크 시작
+    async fn add_incoming_peer(&mut self, connection: Connection, permit: OwnedSemaphorePermit) {
+        let addr = connection.remote_address();
+        info!("📥 들어오는 연결 수락: {}", addr);
+
+        let (cmd_tx, cmd_rx) = mpsc::channel(32);
+        let peer = Peer::new(
+            connection,
+            self.piece_manager.clone(),
+            cmd_rx,
+            self.peer_event_tx.clone(),
+            self.my_peer_id,
+            false,
+        );
+
+        let peer_id = peer.peer_id().to_string();
+
+        self.peers.insert(
+            peer_id.clone(),
+            PeerConnection {
+                command_tx: cmd_tx,
+                state: PeerState::new(peer_id.clone(), addr.to_string()),
+                pex_sent: HashSet::new(),
+                uploads: HashMap::new(),
+            },
+        );
 
-                tauri::async_runtime::spawn(async move {
-                    peer.run().await;
-                    drop(permit);
-                });
+        tauri::async_runtime::spawn(async move {
+            peer.run().await;
+            drop(permit);
+        });
 
-                let _ = self.event_tx.send(SwarmEvent::PeerConnected(peer_id)).await;
-            }
-            Err(e) => {
-                error!("❌ 들어오는 연결 실패: {}", e);
-            }
+        let _ = self.event_tx.send(SwarmEvent::PeerConnected(peer_id)).await;
+    }
+
+    /// 공유 엔드포인트의 들어오는 연결 (공유하지 않으면 대기만 함)
+    async fn recv_shared(incoming: &mut Option<mpsc::Receiver<Connection>>) -> Option<Connection> {
+        match incoming {
+            Some(rx) => rx.recv().await,
+            None => std::future::pending().await,
         }
     }
 
@@ -342,7 +589,15 @@ impl GridSwarm {
         match event {
             PeerEvent::Disconnected { peer_id, reason } => {
                 info!("📴 피어 연결 종료: {} - {}", peer_id, reason);
-                self.peers.remove(&peer_id);
+                if let Some(peer) = self.peers.remove(&peer_id) {
+                    let short_lived = peer.state.connected_at.elapsed()
+                        < Duration::from_secs(config::CHURN_MIN_CONNECTION_SECS);
+                    if let (true, Ok(addr)) =
+                        (short_lived, peer.state.remote_addr.parse::<SocketAddr>())
+                    {
+                        self.record_offense(addr.ip(), Offense::Churn).await;
+                    }
+                }
                 self.scheduler.remove_peer(&peer_id);
                 let _ = self
                     .event_tx
@@ -350,13 +605,30 @@ impl GridSwarm {
                     .await;
             }
 
-            PeerEvent::HandshakeComplete { peer_id, .. } => {
-                info!("🤝 Handshake 완료: {}", peer_id);
+            PeerEvent::HandshakeComplete {
+                peer_id,
+                info_hash,
+                extensions,
+            } => {
+                info!("🤝 Handshake 완료: {} (확장 {:#x})", peer_id, extensions);
+                if let Some(peer) = self.peers.get_mut(&peer_id) {
+                    peer.state.info_hash = Some(info_hash);
+                    peer.state.extensions = extensions;
+                }
+                self.request_metadata(&peer_id).await;
             }
 
             PeerEvent::BitfieldReceived { peer_id, pieces } => {
                 debug!("📊 Bitfield 수신: {} ({} pieces)", peer_id, pieces.len());
-                self.scheduler.set_peer_bitfield(&peer_id, pieces);
+                let has_pieces = !pieces.is_empty();
+                if let Some(ref mut pending) = self.pending_metadata {
+                    pending.peer_pieces.insert(peer_id.clone(), pieces);
+                } else {
+                    self.scheduler.set_peer_bitfield(&peer_id, pieces);
+                }
+                if has_pieces {
+                    self.express_interest(&peer_id).await;
+                }
             }
 
             PeerEvent::HaveReceived {
@@ -364,48 +636,47 @@ impl GridSwarm {
                 piece_index,
             } => {
                 debug!("📢 Have 수신: {} has piece {}", peer_id, piece_index);
+                if let Some(ref mut pending) = self.pending_metadata {
+                    pending
+                        .peer_pieces
+                        .entry(peer_id)
+                        .or_default()
+                        .push(piece_index as usize);
+                    return;
+                }
                 self.scheduler
                     .peer_has_piece(&peer_id, piece_index as usize);
             }
 
+            PeerEvent::PexReceived {
+                peer_id,
+                added,
+                dropped,
+            } => {
+                debug!(
+                    "🔄 PEX 수신: {} (+{} / -{})",
+                    peer_id,
+                    added.len(),
+                    dropped.len()
+                );
+                self.handle_pex(added).await;
+            }
+
+            PeerEvent::MetadataReceived { peer_id, message } => {
+                self.handle_metadata(&peer_id, message).await;
+            }
+
             PeerEvent::PieceReceived {
                 peer_id,
                 piece_index,
                 data,
+                proof,
                 ..
             } => {
-                self.total_downloaded += data.len() as u64;
-
-                // 조각 검증 및 파일에 저장
-                let mut pm = self.piece_manager.write().await;
-
-                match pm.write_piece(piece_index as usize, &data).await {
-                    Ok(()) => {
-                        drop(pm);
-
-                        self.scheduler.mark_completed(piece_index as usize);
-
-                        // Have 브로드캐스트
-                        self.broadcast_have(piece_index).await;
-
-                        let _ = self
-                            .event_tx
-                            .send(SwarmEvent::PieceCompleted(piece_index))
-                            .await;
-
-                        // 완료 확인
-                        if self.scheduler.is_complete() {
-                            info!("🎉 전송 완료!");
-                            let _ = self.event_tx.send(SwarmEvent::TransferComplete).await;
-                        }
-                    }
-                    Err(e) => {
-                        warn!(
-                            "❌ 조각 저장 실패: {} from {} - {}",
-                            piece_index, peer_id, e
-                        );
-                    }
+                if let Some(peer) = self.peers.get_mut(&peer_id) {
+                    peer.state.record_download(data.len() as u64);
                 }
+                self.store_piece(&peer_id, piece_index, data, &proof).await;
             }
 
             PeerEvent::RequestReceived {
@@ -414,22 +685,73 @@ impl GridSwarm {
                 offset,
                 length,
             } => {
+                if self.paused {
+                    debug!("⏸️ 일시정지 중 Request 무시: {}", peer_id);
+                    return;
+                }
+                let total_pieces = self.piece_manager.read().await.get_metadata().total_pieces;
+                if piece_index as usize >= total_pieces {
+                    if let Some(ip) = self.peer_ip(&peer_id) {
+                        self.record_offense(ip, Offense::ProtocolViolation).await;
+                    }
+                    return;
+                }
                 // 조각 데이터 전송 (Seeder 역할)
                 self.send_piece(&peer_id, piece_index, offset, length).await;
             }
 
+            PeerEvent::CancelReceived {
+                peer_id,
+                piece_index,
+                ..
+            } => {
+                // 아직 읽는 중이거나 업로드 제한 대기 중인 전송 중단
+                let task = self
+                    .peers
+                    .get_mut(&peer_id)
+                    .and_then(|peer| peer.uploads.remove(&piece_index));
+                if let Some(task) = task {
+                    task.abort();
+                    debug!("🚫 조각 {} 업로드 취소: {}", piece_index, peer_id);
+                }
+            }
+
             PeerEvent::ChokeChanged { peer_id, choked } => {
                 if let Some(peer) = self.peers.get_mut(&peer_id) {
                     peer.state.peer_choking = choked;
                 }
+                if choked {
+                    // Choke 시 상대는 받은 요청을 버리므로 다른 피어에게 재배정
+                    self.scheduler.release_peer_requests(&peer_id);
+                } else if !self.paused {
+                    self.schedule_requests().await;
+                }
             }
 
             PeerEvent::InterestChanged {
                 peer_id,
                 interested,
             } => {
-                if let Some(peer) = self.peers.get_mut(&peer_id) {
-                    peer.state.peer_interested = interested;
+                let Some(peer) = self.peers.get_mut(&peer_id) else {
+                    return;
+                };
+                peer.state.peer_interested = interested;
+
+                // 빈 Unchoke 슬롯이 있으면 바로 Unchoke, 아니면 다음 재계산에서 결정
+                let can_unchoke = interested && peer.state.am_choking && !self.paused;
+                let unchoked = self.peers.values().filter(|p| !p.state.am_choking).count();
+                if can_unchoke && unchoked <= config::MAX_UNCHOKED_PEERS {
+                    if let Some(peer) = self.peers.get_mut(&peer_id) {
+                        peer.state.am_choking = false;
+                        let _ = peer.command_tx.send(PeerCommand::SetChoked(false)).await;
+                    }
+                }
+            }
+
+            PeerEvent::ProtocolViolation { peer_id, reason } => {
+                warn!("🚫 프로토콜 위반: {} - {}", peer_id, reason);
+                if let Some(ip) = self.peer_ip(&peer_id) {
+                    self.record_offense(ip, Offense::ProtocolViolation).await;
                 }
             }
 
@@ -439,6 +761,191 @@ impl GridSwarm {
         }
     }
 
+    /// 수신한 조각 검증 및 저장 (피어/Web Seed 공통)
+    async fn store_piece(
+        &mut self,
+        source: &str,
+        piece_index: u32,
+        data: Vec<u8>,
+        proof: &[[u8; 32]],
+    ) {
+        self.total_downloaded += data.len() as u64;
+        self.download_rate.record(data.len() as u64);
+
+        // 조각 검증 (Merkle proof 또는 조각 해시)
+        let mut pm = self.piece_manager.write().await;
+        if !pm.verify_piece_with_proof(piece_index as usize, &data, proof) {
+            drop(pm);
+            self.scheduler.unmark_pending(piece_index as usize);
+            self.penalize_source(source, piece_index).await;
+            return;
+        }
+
+        // 파일에 저장
+        match pm.write_verified_piece(piece_index as usize, &data).await {
+            Ok(()) => {
+                drop(pm);
+
+                // Endgame 중복 요청 취소
+                self.cancel_duplicate_requests(source, piece_index, data.len() as u32)
+                    .await;
+
+                self.scheduler.mark_completed(piece_index as usize);
+
+                if let Some(ref mut resume) = self.resume_state {
+                    resume.bitfield.mark(piece_index as usize);
+                    self.resume_dirty = true;
+                }
+
+                // Have 브로드캐스트
+                self.broadcast_have(piece_index).await;
+
+                let _ = self
+                    .event_tx
+                    .send(SwarmEvent::PieceCompleted(piece_index))
+                    .await;
+
+                // 완료 확인
+                if self.scheduler.is_complete() {
+                    info!("🎉 전송 완료!");
+                    self.role_state = GridJobState::Seeding;
+                    self.clear_resume_state().await;
+                    let _ = self.event_tx.send(SwarmEvent::TransferComplete).await;
+                }
+            }
+            Err(e) => {
+                drop(pm);
+                self.scheduler.unmark_pending(piece_index as usize);
+                warn!("❌ 조각 저장 실패: {} from {} - {}", piece_index, source, e);
+            }
+        }
+    }
+
+    /// 손상된 조각을 보낸 소스에 페널티 부여 (누적 시 차단)
+    async fn penalize_source(&mut self, source: &str, piece_index: u32) {
+        warn!(
+            "🚨 손상된 조각 {} 수신: {}, 재요청 예정",
+            piece_index, source
+        );
+
+        // Web Seed는 WebSeed 자체 실패 누적으로 비활성화됨
+        if let Some(ip) = self.peer_ip(source) {
+            self.record_offense(ip, Offense::CorruptPiece).await;
+        }
+    }
+
+    /// 피어의 IP
+    fn peer_ip(&self, peer_id: &str) -> Option<IpAddr> {
+        let peer = self.peers.get(peer_id)?;
+        peer.state
+            .remote_addr
+            .parse::<SocketAddr>()
+            .ok()
+            .map(|addr| addr.ip())
+    }
+
+    /// 벌점 기록, 임계값 초과 시 차단 목록에 추가하고 해당 IP의 연결 모두 해제
+    async fn record_offense(&mut self, ip: IpAddr, offense: Offense) {
+        let score = self.peer_scores.entry(ip).or_default();
+        let penalty = score.record(offense);
+        debug!(
+            "📉 벌점 {}: {} (+{} = {})",
+            ip,
+            offense.as_str(),
+            offense.weight(),
+            penalty
+        );
+
+        if !score.should_ban() {
+            return;
+        }
+        self.peer_scores.remove(&ip);
+        self.ban_list.ban(ip, offense.as_str(), BAN_DURATION);
+
+        let banned: Vec<String> = self
+            .peers
+            .iter()
+            .filter(|(_, p)| {
+                p.state
+                    .remote_addr
+                    .parse::<SocketAddr>()
+                    .is_ok_and(|addr| addr.ip() == ip)
+            })
+            .map(|(peer_id, _)| peer_id.clone())
+            .collect();
+        for peer_id in banned {
+            self.disconnect_peer(&peer_id).await;
+        }
+    }
+
+    /// 피어 가용성이 낮은 조각을 Web Seed에 요청
+    async fn schedule_web_seeds(&mut self) {
+        let seeds: Vec<Arc<WebSeed>> = self
+            .web_seeds
+            .iter()
+            .filter(|s| !s.is_disabled())
+            .cloned()
+            .collect();
+        if seeds.is_empty() {
+            return;
+        }
+
+        let slots = config::WEB_SEED_MAX_INFLIGHT.saturating_sub(self.web_seed_inflight);
+        if slots == 0 {
+            return;
+        }
+
+        let pieces = self
+            .scheduler
+            .web_seed_candidates(slots, config::WEB_SEED_AVAILABILITY_THRESHOLD);
+        if pieces.is_empty() {
+            return;
+        }
+        let metadata = self.piece_manager.read().await.get_metadata().clone();
+
+        for (i, piece_index) in pieces.into_iter().enumerate() {
+            let length = metadata.piece_size as u64;
+            if !self.rate_limit.download.try_consume(length) {
+                break;
+            }
+
+            let seed = seeds[i % seeds.len()].clone();
+            let metadata = metadata.clone();
+            let tx = self.web_seed_tx.clone();
+
+            self.scheduler.mark_pending(piece_index);
+            self.web_seed_inflight += 1;
+
+            tauri::async_runtime::spawn(async move {
+                let result = seed.fetch_piece(&metadata, piece_index).await;
+                let _ = tx
+                    .send(WebSeedResult {
+                        url: seed.url().to_string(),
+                        piece_index,
+                        result,
+                    })
+                    .await;
+            });
+        }
+    }
+
+    /// Web Seed 결과 처리
+    async fn handle_web_seed_result(&mut self, result: WebSeedResult) {
+        self.web_seed_inflight = self.web_seed_inflight.saturating_sub(1);
+
+        match result.result {
+            Ok(data) => {
+                let source = format!("webseed:{}", result.url);
+                self.store_piece(&source, result.piece_index as u32, data, &[])
+                    .await;
+            }
+            Err(_) => {
+                // 실패 로그는 WebSeed에서 남김, 다른 소스로 재시도되도록 해제
+                self.scheduler.unmark_pending(result.piece_index);
+            }
+        }
+    }
+
     /// Have 브로드캐스트
     async fn broadcast_have(&self, piece_index: u32) {
         let msg = GridMessage::Have { piece_index };
@@ -450,66 +957,275 @@ impl GridSwarm {
         }
     }
 
-    /// 조각 요청
-    async fn request_piece(&mut self, peer_id: &str, piece_index: u32) {
+    /// 연결된 피어 주소 목록
+    fn connected_addrs(&self) -> HashSet<SocketAddr> {
+        self.peers
+            .values()
+            .filter_map(|p| p.state.remote_addr.parse().ok())
+            .collect()
+    }
+
+    /// 각 피어에게 다른 Swarm 구성원 주소 변경분 전송
+    async fn broadcast_pex(&mut self) {
+        let known = self.connected_addrs();
+        if known.len() < 2 {
+            return;
+        }
+
+        for peer in self.peers.values_mut() {
+            if !peer.state.supports(extensions::PEX) {
+                continue;
+            }
+            let own: Option<SocketAddr> = peer.state.remote_addr.parse().ok();
+            let current: HashSet<SocketAddr> = known
+                .iter()
+                .filter(|addr| Some(**addr) != own)
+                .copied()
+                .collect();
+
+            let added: Vec<SocketAddr> = current.difference(&peer.pex_sent).copied().collect();
+            let dropped: Vec<SocketAddr> = peer.pex_sent.difference(&current).copied().collect();
+            if added.is_empty() && dropped.is_empty() {
+                continue;
+            }
+
+            let msg = GridMessage::pex(added, dropped);
+            if let GridMessage::Pex {
+                ref added,
+                ref dropped,
+            } = msg
+            {
+                // 잘려서 보내지 못한 주소는 다음 주기에 다시 전송
+                peer.pex_sent.extend(added.iter().copied());
+                for addr in dropped {
+                    peer.pex_sent.remove(addr);
+                }
+            }
+            let _ = peer.command_tx.send(PeerCommand::SendMessage(msg)).await;
+        }
+    }
+
+    /// PEX로 알게 된 피어에 연결
+    async fn handle_pex(&mut self, added: Vec<SocketAddr>) {
+        let local = self.endpoint.local_addr().ok();
+        let connected = self.connected_addrs();
+
+        for addr in added.into_iter().take(config::MAX_PEERS) {
+            if self.peers.len() >= config::MAX_PEERS {
+                break;
+            }
+            if connected.contains(&addr) || Some(addr) == local || addr.ip().is_unspecified() {
+                continue;
+            }
+            self.connect_to_peer(addr).await;
+        }
+    }
+
+    /// 🆕 피어에게 조각 요청을 보낼 수 있는 스트림 수
+    fn peer_streams(&self, peer_id: &str) -> usize {
+        match self.peers.get(peer_id) {
+            Some(peer) if peer.state.supports(extensions::MULTI_STREAM) => config::STREAMS_PER_PEER,
+            Some(_) => 1,
+            None => 0,
+        }
+    }
+
+    /// 조각 요청 (stream: 피어 연결 안에서 요청을 보낼 스트림 번호)
+    async fn request_piece(&mut self, peer_id: &str, piece_index: u32, stream: usize) {
         if let Some(peer) = self.peers.get(peer_id) {
             let pm = self.piece_manager.read().await;
             if let Some(piece_info) = pm.get_piece_info(piece_index as usize) {
-                let msg = GridMessage::request(piece_index, 0, piece_info.length);
+                let message = GridMessage::request(piece_index, 0, piece_info.length);
+                let _ = peer
+                    .command_tx
+                    .send(PeerCommand::SendOnStream { stream, message })
+                    .await;
+                let deadline = Instant::now() + Duration::from_secs(config::REQUEST_TIMEOUT_SECS);
+                self.scheduler
+                    .mark_requested(peer_id, piece_index as usize, stream, deadline);
+            }
+        }
+    }
+
+    /// 다른 피어에게도 보낸 같은 조각 요청 취소
+    async fn cancel_duplicate_requests(&self, source: &str, piece_index: u32, length: u32) {
+        for peer_id in self.scheduler.requesters(piece_index as usize) {
+            if peer_id == source {
+                continue;
+            }
+            if let Some(peer) = self.peers.get(&peer_id) {
+                let msg = GridMessage::Cancel {
+                    piece_index,
+                    offset: 0,
+                    length,
+                };
+                let _ = peer.command_tx.send(PeerCommand::SendMessage(msg)).await;
+            }
+        }
+    }
+
+    /// 받을 조각이 있는 피어에게 Interested 전송 (다운로드 중일 때만)
+    async fn express_interest(&mut self, peer_id: &str) {
+        if self.paused || self.role_state == GridJobState::Seeding {
+            return;
+        }
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            if !peer.state.am_interested {
+                peer.state.am_interested = true;
+                let _ = peer.command_tx.send(PeerCommand::SetInterested(true)).await;
+            }
+        }
+    }
+
+    /// 시간 초과 요청 회수 (상대에게 Cancel 전송 후 재배정 대상으로 되돌림)
+    async fn expire_requests(&mut self) {
+        let expired = self.scheduler.expire_requests(Instant::now());
+        if expired.is_empty() {
+            return;
+        }
+
+        let pm = self.piece_manager.read().await;
+        for (peer_id, piece_index) in expired {
+            warn!("⏰ 조각 {} 요청 시간 초과: {}", piece_index, peer_id);
+            let length = pm.get_piece_info(piece_index).map_or(0, |p| p.length);
+            if let Some(peer) = self.peers.get(&peer_id) {
+                let msg = GridMessage::Cancel {
+                    piece_index: piece_index as u32,
+                    offset: 0,
+                    length,
+                };
                 let _ = peer.command_tx.send(PeerCommand::SendMessage(msg)).await;
-                self.scheduler.mark_pending(piece_index as usize);
             }
         }
     }
 
     /// 조각 데이터 전송
+    ///
+    /// 업로드 제한 대기와 파일 읽기는 별도 태스크에서 하며, 상대가 Cancel을 보내면 중단합니다.
     async fn send_piece(&mut self, peer_id: &str, piece_index: u32, _offset: u32, _length: u32) {
-        if let Some(peer) = self.peers.get(peer_id) {
-            // PieceManager에서 조각 정보 확인
-            let pm = self.piece_manager.read().await;
+        let Some(peer) = self.peers.get(peer_id) else {
+            return;
+        };
+        if peer.uploads.contains_key(&piece_index) {
+            debug!("이미 전송 중인 조각 {} 요청 무시: {}", piece_index, peer_id);
+            return;
+        }
 
+        // PieceManager에서 조각 정보 확인
+        let len = {
+            let pm = self.piece_manager.read().await;
             if !pm.get_bitfield().has(piece_index as usize) {
                 warn!("요청된 조각 {}을 보유하지 않음", piece_index);
                 return;
             }
+            pm.get_piece_info(piece_index as usize)
+                .map_or(0, |p| p.length as u64)
+        };
+
+        let command_tx = peer.command_tx.clone();
+        let piece_manager = self.piece_manager.clone();
+        let rate_limit = self.rate_limit.clone();
+        let upload_tx = self.upload_tx.clone();
+        let target = peer_id.to_string();
+
+        // 업로드 제한 대기가 Swarm 루프를 막지 않도록 별도 태스크에서 전송
+        let task = tauri::async_runtime::spawn(async move {
+            rate_limit.upload.acquire(len).await;
 
             // 실제 파일에서 데이터 읽기
+            let pm = piece_manager.read().await;
             let data = match pm.read_piece(piece_index as usize).await {
-                Ok(d) => d,
+                Ok(d) => Some(d),
                 Err(e) => {
                     warn!("조각 {} 읽기 실패: {}", piece_index, e);
-                    return;
+                    None
                 }
             };
+            let proof = pm.piece_proof(piece_index as usize).unwrap_or_default();
             drop(pm);
 
-            let msg = GridMessage::piece(piece_index, 0, data.clone());
-            if let Err(e) = peer.command_tx.send(PeerCommand::SendMessage(msg)).await {
-                warn!("조각 전송 실패: {}", e);
-                return;
+            let mut sent = None;
+            if let Some(data) = data {
+                let len = data.len() as u64;
+                let msg = GridMessage::piece_with_proof(piece_index, 0, data, proof);
+                match command_tx.send(PeerCommand::SendMessage(msg)).await {
+                    Ok(()) => {
+                        debug!("📤 조각 {} 전송 완료 -> {}", piece_index, target);
+                        sent = Some(len);
+                    }
+                    Err(e) => warn!("조각 전송 실패: {}", e),
+                }
+            }
+
+            let _ = upload_tx
+                .send(UploadResult {
+                    peer_id: target,
+                    piece_index,
+                    sent,
+                })
+                .await;
+        });
+
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            peer.uploads.insert(piece_index, task);
+        }
+    }
+
+    /// 🆕 조각 업로드 태스크 완료 처리 (피어에게 넘긴 바이트만 업로드로 집계)
+    fn handle_upload_result(&mut self, result: UploadResult) {
+        if let Some(len) = result.sent {
+            self.total_uploaded += len;
+            self.upload_rate.record(len);
+        }
+        if let Some(peer) = self.peers.get_mut(&result.peer_id) {
+            peer.uploads.remove(&result.piece_index);
+            if let Some(len) = result.sent {
+                peer.state.record_upload(len);
             }
-            self.total_uploaded += data.len() as u64;
-            debug!("📤 조각 {} 전송 완료 -> {}", piece_index, peer_id);
         }
     }
 
     /// 주기적 스케줄링
+    ///
+    /// Unchoke 상태인 피어마다 진행 중 요청이 MAX_PENDING_REQUESTS가 되도록 채웁니다.
     async fn schedule_requests(&mut self) {
-        let requests = self.scheduler.generate_requests(16);
+        self.expire_requests().await;
+
+        let requests = self
+            .scheduler
+            .generate_requests(config::MAX_PENDING_REQUESTS, |peer_id| {
+                match self.peers.get(peer_id) {
+                    Some(p) if !p.state.peer_choking => self.peer_streams(peer_id),
+                    _ => 0,
+                }
+            });
 
         for req in requests {
-            self.request_piece(&req.target_peer, req.piece_index as u32)
+            // 다운로드 제한: 요청 시점에 조각 크기만큼 예산 차감
+            let length = {
+                let pm = self.piece_manager.read().await;
+                pm.get_piece_info(req.piece_index)
+                    .map(|p| p.length as u64)
+                    .unwrap_or(0)
+            };
+            if !self.rate_limit.download.try_consume(length) {
+                break;
+            }
+
+            self.request_piece(&req.target_peer, req.piece_index as u32, req.stream)
                 .await;
         }
+
+        self.schedule_web_seeds().await;
     }
 
     /// 상태 업데이트 브로드캐스트
     async fn broadcast_status(&self) {
         let pm = self.piece_manager.read().await;
-        let elapsed = self.started_at.elapsed().as_secs().max(1);
 
         let update = GridStateUpdate {
             job_id: self.job_id.clone(),
+            state: self.current_state(),
             total_pieces: pm.total_pieces(),
             completed_pieces: pm.get_bitfield().available_pieces(),
             peers: self
@@ -531,8 +1247,8 @@ impl GridSwarm {
                     is_interested: p.state.peer_interested,
                 })
                 .collect(),
-            download_speed: self.total_downloaded / elapsed,
-            upload_speed: self.total_uploaded / elapsed,
+            download_speed: self.download_rate.rate(),
+            upload_speed: self.upload_rate.rate(),
             progress: pm.progress(),
         };
 
@@ -544,12 +1260,136 @@ impl GridSwarm {
         let _ = self.event_tx.send(SwarmEvent::StateUpdate(update)).await;
     }
 
+    /// Choke 재계산 (tit-for-tat)
+    ///
+    /// 관심을 보인 피어 중 최근 속도 상위 MAX_UNCHOKED_PEERS명과 무작위 1명(낙관적 Unchoke)만
+    /// Unchoke 합니다. 다운로드 중에는 나에게 보내주는 속도, Seeding 중에는 받아가는 속도 기준입니다.
+    async fn rechoke(&mut self) {
+        let seeding = self.role_state == GridJobState::Seeding;
+        let mut candidates: Vec<(&String, u64)> = self
+            .peers
+            .iter()
+            .filter(|(_, p)| p.state.peer_interested)
+            .map(|(id, p)| {
+                let speed = if seeding {
+                    p.state.upload_speed()
+                } else {
+                    p.state.download_speed()
+                };
+                (id, speed)
+            })
+            .collect();
+        candidates.sort_by(|a, b| b.1.cmp(&a.1));
+
+        let split = candidates.len().min(config::MAX_UNCHOKED_PEERS);
+        let mut unchoke: HashSet<String> = candidates[..split]
+            .iter()
+            .map(|(id, _)| (*id).clone())
+            .collect();
+        if let Some((id, _)) = candidates[split..].choose(&mut rand::thread_rng()) {
+            unchoke.insert((*id).clone());
+        }
+
+        for (peer_id, peer) in self.peers.iter_mut() {
+            let choke = !unchoke.contains(peer_id);
+            if peer.state.am_choking != choke {
+                peer.state.am_choking = choke;
+                let _ = peer.command_tx.send(PeerCommand::SetChoked(choke)).await;
+            }
+        }
+    }
+
+    /// 외부에 보이는 현재 상태
+    fn current_state(&self) -> GridJobState {
+        if self.paused {
+            GridJobState::Paused
+        } else {
+            self.role_state
+        }
+    }
+
+    /// 일시정지: 진행 중 요청 해제, 모든 피어 Choke, 재개 상태 저장
+    async fn pause(&mut self) {
+        if self.paused {
+            return;
+        }
+        info!("⏸️ Swarm 일시정지: {}", self.job_id);
+        self.paused = true;
+        self.scheduler.clear_pending();
+
+        for peer in self.peers.values_mut() {
+            peer.state.am_choking = true;
+            peer.state.am_interested = false;
+            let _ = peer.command_tx.send(PeerCommand::SetChoked(true)).await;
+            let _ = peer
+                .command_tx
+                .send(PeerCommand::SetInterested(false))
+                .await;
+        }
+
+        // 지금까지 기록된 조각 정보를 디스크에 반영
+        self.resume_dirty = self.resume_state.is_some();
+        self.save_resume_state().await;
+        self.broadcast_status().await;
+    }
+
+    /// 일시정지 해제
+    async fn resume(&mut self) {
+        if !self.paused {
+            return;
+        }
+        info!("▶️ Swarm 재개: {}", self.job_id);
+        self.paused = false;
+
+        let interested = self.role_state != GridJobState::Seeding;
+        for peer in self.peers.values_mut() {
+            peer.state.am_interested = interested;
+            let _ = peer
+                .command_tx
+                .send(PeerCommand::SetInterested(interested))
+                .await;
+        }
+        self.rechoke().await;
+        self.broadcast_status().await;
+    }
+
+    /// 종료 정리: 피어 연결 해제, 재개 상태 저장 또는 미완료 파일 삭제
+    async fn teardown(&mut self, delete_partial: bool) {
+        let peer_ids: Vec<String> = self.peers.keys().cloned().collect();
+        for peer_id in peer_ids {
+            self.disconnect_peer(&peer_id).await;
+        }
+
+        let incomplete = self.role_state != GridJobState::Seeding;
+        if delete_partial && incomplete {
+            let save_path = self.piece_manager.read().await.save_path().cloned();
+            if let Some(path) = save_path {
+                match tokio::fs::remove_file(&path).await {
+                    Ok(()) => info!("🗑️ 미완료 파일 삭제: {:?}", path),
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                    Err(e) => warn!("미완료 파일 삭제 실패: {:?} - {}", path, e),
+                }
+            }
+            self.clear_resume_state().await;
+        } else {
+            self.resume_dirty = self.resume_state.is_some();
+            self.save_resume_state().await;
+        }
+
+        self.paused = false;
+        self.role_state = GridJobState::Stopped;
+        self.broadcast_status().await;
+    }
+
     /// Seeding 시작
-    async fn start_seeding(&mut self, _file_path: PathBuf, metadata: FileMetadata) {
+    async fn start_seeding(&mut self, file_path: PathBuf, metadata: FileMetadata) {
         info!("🌱 Seeding 시작: {}", metadata.file_name);
         let total_pieces = metadata.total_pieces;
 
-        *self.piece_manager.write().await = PieceManager::new_seeder(metadata);
+        let mut pm = PieceManager::new_seeder(metadata);
+        pm.set_source_path(file_path);
+        *self.piece_manager.write().await = pm;
+        self.role_state = GridJobState::Seeding;
         self.scheduler = Scheduler::new(total_pieces);
 
         // 모든 조각 완료 표시
@@ -563,10 +1403,195 @@ impl GridSwarm {
         info!("📥 Download 시작: {}", metadata.file_name);
         let total_pieces = metadata.total_pieces;
 
+        if self.resume_dir.is_some() {
+            self.resume_state = Some(ResumeState::new(
+                self.job_id.clone(),
+                metadata.clone(),
+                save_path.clone(),
+            ));
+            self.resume_dirty = true;
+        }
+
+        self.web_seeds = Self::build_web_seeds(&metadata);
+        self.role_state = GridJobState::Downloading;
+
         let mut pm = PieceManager::new(metadata);
         pm.set_save_path(save_path);
         *self.piece_manager.write().await = pm;
 
         self.scheduler = Scheduler::new(total_pieces);
     }
+
+    fn build_web_seeds(metadata: &FileMetadata) -> Vec<Arc<WebSeed>> {
+        metadata
+            .web_seeds
+            .iter()
+            .filter_map(|url| match WebSeed::new(url.clone()) {
+                Ok(seed) => Some(Arc::new(seed)),
+                Err(e) => {
+                    warn!("Web Seed 무시: {}", e);
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Info Hash만으로 Download 시작 (메타데이터 교환 대기)
+    async fn start_download_by_hash(&mut self, info_hash: [u8; 32], save_path: PathBuf) {
+        info!(
+            "🧲 메타데이터 대기 Download 시작: {}",
+            hex::encode(&info_hash[..8])
+        );
+
+        // Handshake 검증용으로 Info Hash만 가진 임시 메타데이터 사용
+        *self.piece_manager.write().await = PieceManager::new(FileMetadata::placeholder(info_hash));
+        self.scheduler = Scheduler::new(0);
+        self.role_state = GridJobState::FetchingMetadata;
+        self.pending_metadata = Some(PendingMetadata {
+            assembler: MetadataAssembler::new(info_hash),
+            save_path,
+            peer_pieces: HashMap::new(),
+        });
+
+        // 이미 연결된 피어에게 바로 요청
+        let peer_ids: Vec<String> = self.peers.keys().cloned().collect();
+        for peer_id in peer_ids {
+            self.request_metadata(&peer_id).await;
+        }
+    }
+
+    /// 다음 메타데이터 묶음 요청
+    async fn request_metadata(&self, peer_id: &str) {
+        let Some(ref pending) = self.pending_metadata else {
+            return;
+        };
+        let (Some(msg), Some(peer)) = (pending.assembler.next_request(), self.peers.get(peer_id))
+        else {
+            return;
+        };
+        let _ = peer.command_tx.send(PeerCommand::SendMessage(msg)).await;
+    }
+
+    /// 메타데이터 묶음 처리
+    async fn handle_metadata(&mut self, peer_id: &str, message: GridMessage) {
+        let Some(ref mut pending) = self.pending_metadata else {
+            return;
+        };
+        let GridMessage::MetadataResponse {
+            info_hash,
+            file_name,
+            file_size,
+            piece_size,
+            total_pieces,
+            chunk,
+            total_chunks,
+            piece_hashes,
+        } = message
+        else {
+            return;
+        };
+
+        match pending.assembler.add_chunk(
+            info_hash,
+            file_name,
+            file_size,
+            piece_size,
+            total_pieces,
+            chunk,
+            total_chunks,
+            piece_hashes,
+        ) {
+            Ok(Some(metadata)) => {
+                info!(
+                    "🧲 메타데이터 수신 완료: {} ({} pieces)",
+                    metadata.file_name, metadata.total_pieces
+                );
+                let Some(pending) = self.pending_metadata.take() else {
+                    return;
+                };
+                self.start_download(metadata, pending.save_path).await;
+
+                // 대기 중 받은 피어 보유 정보 반영
+                for (peer_id, pieces) in pending.peer_pieces {
+                    self.scheduler.set_peer_bitfield(&peer_id, pieces);
+                }
+
+                // 새 메타데이터 기준 Bitfield 재전송
+                let (data, length) = {
+                    let pm = self.piece_manager.read().await;
+                    let bf = pm.get_bitfield();
+                    (bf.as_bytes().to_vec(), bf.len())
+                };
+                for peer in self.peers.values() {
+                    let msg = GridMessage::bitfield(data.clone(), length);
+                    let _ = peer.command_tx.send(PeerCommand::SendMessage(msg)).await;
+                }
+            }
+            Ok(None) => {
+                self.request_metadata(peer_id).await;
+            }
+            Err(e) => {
+                warn!("❌ 메타데이터 검증 실패: {} - {}", peer_id, e);
+                pending.assembler.reset();
+                self.disconnect_peer(peer_id).await;
+
+                // 다른 피어에게 처음부터 다시 요청
+                let peer_ids: Vec<String> = self.peers.keys().cloned().collect();
+                for other in peer_ids {
+                    self.request_metadata(&other).await;
+                }
+            }
+        }
+    }
+
+    /// 저장된 상태에서 Download 재개
+    async fn resume_download(&mut self, mut resume: ResumeState) {
+        info!(
+            "🔁 Download 재개: {} ({}/{} pieces 기록됨)",
+            resume.metadata.file_name,
+            resume.bitfield.count_ones(),
+            resume.metadata.total_pieces
+        );
+        let total_pieces = resume.metadata.total_pieces;
+
+        let mut pm = PieceManager::new(resume.metadata.clone());
+        pm.set_save_path(resume.save_path.clone());
+        pm.verify_existing_pieces(&resume.bitfield).await;
+
+        self.web_seeds = Self::build_web_seeds(&resume.metadata);
+        self.role_state = GridJobState::Downloading;
+        self.scheduler = Scheduler::new(total_pieces);
+        for index in pm.get_bitfield().available_pieces() {
+            self.scheduler.mark_completed(index);
+        }
+
+        // 재검증 결과로 기록 갱신 (손상된 조각은 다시 받도록)
+        resume.bitfield = pm.get_bitfield().clone();
+        self.job_id = resume.job_id.clone();
+        self.resume_state = Some(resume);
+        self.resume_dirty = true;
+
+        *self.piece_manager.write().await = pm;
+    }
+
+    /// 변경된 재개 상태를 디스크에 저장
+    async fn save_resume_state(&mut self) {
+        if !self.resume_dirty {
+            return;
+        }
+        if let (Some(dir), Some(resume)) = (self.resume_dir.as_ref(), self.resume_state.as_mut()) {
+            match resume.save(dir).await {
+                Ok(()) => self.resume_dirty = false,
+                Err(e) => warn!("재개 상태 저장 실패: {}", e),
+            }
+        }
+    }
+
+    /// 완료된 다운로드의 재개 파일 제거
+    async fn clear_resume_state(&mut self) {
+        if let (Some(dir), Some(resume)) = (self.resume_dir.as_ref(), self.resume_state.take()) {
+            ResumeState::remove(dir, &resume.job_id).await;
+        }
+        self.resume_dirty = false;
+    }
 }
@@ -0,0 +1,139 @@
+//! Web Seed - HTTP(S) 파일 서버를 추가 Grid 소스로 사용
+//!
+//! 피어 가용성이 낮은 조각을 Range GET 요청으로 파일 서버에서 직접 받아,
+//! 클라이언트-서버와 P2P 소스를 함께 활용합니다.
+
+use crate::grid::piece_manager::FileMetadata;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// 연속 실패 허용 횟수 (초과 시 해당 Web Seed 비활성화)
+const MAX_CONSECUTIVE_FAILURES: u32 = 5;
+
+/// 요청 타임아웃
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// HTTP(S) Web Seed
+pub struct WebSeed {
+    url: String,
+    client: reqwest::Client,
+    failures: AtomicU32,
+}
+
+impl WebSeed {
+    pub fn new(url: String) -> anyhow::Result<Self> {
+        if !(url.starts_with("http://") || url.starts_with("https://")) {
+            return Err(anyhow::anyhow!("Unsupported web seed URL: {}", url));
+        }
+
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()?;
+
+        Ok(Self {
+            url,
+            client,
+            failures: AtomicU32::new(0),
+        })
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// 연속 실패가 누적되어 사용을 중단해야 하는지
+    pub fn is_disabled(&self) -> bool {
+        self.failures.load(Ordering::Relaxed) >= MAX_CONSECUTIVE_FAILURES
+    }
+
+    /// 조각 하나를 Range GET으로 다운로드
+    pub async fn fetch_piece(
+        &self,
+        metadata: &FileMetadata,
+        index: usize,
+    ) -> anyhow::Result<Vec<u8>> {
+        let result = self.fetch_range(metadata, index).await;
+        match result {
+            Ok(_) => self.failures.store(0, Ordering::Relaxed),
+            Err(ref e) => {
+                let failures = self.failures.fetch_add(1, Ordering::Relaxed) + 1;
+                warn!("🌐 Web Seed 실패 ({}회): {} - {}", failures, self.url, e);
+            }
+        }
+        result
+    }
+
+    async fn fetch_range(
+        &self,
+        metadata: &FileMetadata,
+        index: usize,
+    ) -> anyhow::Result<Vec<u8>> {
+        let (start, length) = piece_range(metadata, index)
+            .ok_or_else(|| anyhow::anyhow!("Invalid piece index: {}", index))?;
+        let end = start + length - 1;
+
+        let response = self
+            .client
+            .get(&self.url)
+            .header(reqwest::header::RANGE, format!("bytes={}-{}", start, end))
+            .send()
+            .await?;
+
+        // Range를 무시하고 전체 파일(200)을 주는 서버는 사용할 수 없음
+        if response.status() != reqwest::StatusCode::PARTIAL_CONTENT {
+            return Err(anyhow::anyhow!(
+                "Server did not honor range request: {}",
+                response.status()
+            ));
+        }
+
+        let data = response.bytes().await?;
+        if data.len() as u64 != length {
+            return Err(anyhow::anyhow!(
+                "Range length mismatch: expected {}, got {}",
+                length,
+                data.len()
+            ));
+        }
+
+        debug!("🌐 Web Seed 조각 {} 수신 ({} bytes)", index, length);
+        Ok(data.to_vec())
+    }
+}
+
+/// 조각의 파일 내 (시작 오프셋, 길이)
+fn piece_range(metadata: &FileMetadata, index: usize) -> Option<(u64, u64)> {
+    if index >= metadata.total_pieces {
+        return None;
+    }
+    let start = index as u64 * metadata.piece_size as u64;
+    let length = (metadata.piece_size as u64).min(metadata.file_size.saturating_sub(start));
+    if length == 0 {
+        return None;
+    }
+    Some((start, length))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_piece_range_last_piece() {
+        let mut metadata = FileMetadata::placeholder([0u8; 32]);
+        metadata.file_size = 2500;
+        metadata.piece_size = 1000;
+        metadata.total_pieces = 3;
+
+        assert_eq!(piece_range(&metadata, 0), Some((0, 1000)));
+        assert_eq!(piece_range(&metadata, 2), Some((2000, 500)));
+        assert_eq!(piece_range(&metadata, 3), None);
+    }
+
+    #[test]
+    fn test_rejects_non_http_url() {
+        assert!(WebSeed::new("ftp://example.com/file".to_string()).is_err());
+        assert!(WebSeed::new("https://example.com/file".to_string()).is_ok());
+    }
+}
@@ -0,0 +1,262 @@
+//! 헤드리스 수신 데몬 모드
+//!
+//! 사무실 NAS/수신 전용 박스처럼 항상 켜 두고 신뢰하는 지문에서 오는 전송만 받는 용도입니다.
+//! `--headless` 플래그나 앱 데이터 디렉토리의 `headless.json` (`"enabled": true`)으로 켜면
+//! 창을 열지 않고 QUIC 서버와 피어 발견을 시작하며, 자동 수락 규칙에 맞는 전송은 UI 대신 백엔드가
+//! 규칙의 저장 폴더로 직접 수신합니다. 규칙에 맞지 않는 요청은 승인할 사람이 없으므로 시간 초과로 거절됩니다.
+//!
+//! ```text
+//! ponswarp --headless --port 7000 --node-id office-nas
+//! ```
+
+use crate::error::AppError;
+use crate::AppState;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tauri::{AppHandle, Manager};
+use tracing::{info, warn};
+
+/// 설정 파일명
+pub const HEADLESS_CONFIG_FILE: &str = "headless.json";
+
+/// 헤드리스 모드 설정 (파일 값 위에 명령줄 인자를 덮어씀)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HeadlessConfig {
+    pub enabled: bool,
+    /// QUIC 수신 포트 (0 = 임의 포트, 방화벽 규칙을 두려면 고정)
+    pub port: u16,
+    /// mDNS에 알릴 이름 (없으면 `ponswarp-<호스트명>`)
+    pub node_id: Option<String>,
+    /// LAN 피어 발견(mDNS) 사용
+    pub discovery: bool,
+}
+
+impl Default for HeadlessConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 0,
+            node_id: None,
+            discovery: true,
+        }
+    }
+}
+
+impl HeadlessConfig {
+    /// 파일에서 로드 (없거나 손상되었으면 기본값)
+    pub fn load(path: &Path) -> Self {
+        match std::fs::read(path) {
+            Ok(data) => serde_json::from_slice(&data).unwrap_or_else(|e| {
+                warn!("헤드리스 설정 파싱 실패, 기본값 사용: {}", e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// 명령줄 인자 적용 (`--headless`, `--port <n>`, `--node-id <id>`, `--no-discovery`)
+    ///
+    /// 알 수 없는 인자는 무시합니다 (플랫폼이 붙이는 인자 등).
+    pub fn apply_args<I>(mut self, args: I) -> Result<Self, AppError>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+                None => (arg, None),
+            };
+            let mut value = |name: &str| {
+                inline
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| AppError::InvalidInput(format!("{} 값이 필요합니다", name)))
+            };
+
+            match flag.as_str() {
+                "--headless" => self.enabled = true,
+                "--no-discovery" => self.discovery = false,
+                "--port" => {
+                    let port = value("--port")?;
+                    self.port = port
+                        .parse()
+                        .map_err(|_| AppError::InvalidInput(format!("잘못된 포트: {}", port)))?;
+                }
+                "--node-id" => self.node_id = Some(value("--node-id")?),
+                _ => {}
+            }
+        }
+        Ok(self)
+    }
+
+    fn node_id(&self) -> String {
+        self.node_id
+            .clone()
+            .unwrap_or_else(|| format!("ponswarp-{}", gethostname::gethostname().to_string_lossy()))
+    }
+}
+
+/// 메인 창을 닫고 수신 서비스 시작
+pub async fn start(app: AppHandle, config: HeadlessConfig) -> Result<(), AppError> {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.destroy();
+    }
+
+    let state = app.state::<AppState>();
+    let rules = state.transfer_approval.auto_accept().list();
+    if !rules.iter().any(|r| r.enabled) {
+        warn!("🖥️ 활성화된 자동 수락 규칙이 없어 모든 전송 요청이 시간 초과로 거절됩니다");
+    }
+
+    let addr = crate::start_quic_server(config.port, None, None, app.state()).await?;
+    let port = addr
+        .rsplit(':')
+        .next()
+        .and_then(|p| p.parse().ok())
+        .unwrap_or(config.port);
+    if config.discovery {
+        crate::start_discovery(config.node_id(), port, app.state()).await?;
+    }
+
+    info!(
+        "🖥️ 헤드리스 수신 모드 시작: {} (자동 수락 규칙 {}개)",
+        addr,
+        rules.len()
+    );
+    Ok(())
+}
+
+/// 자동 수락된 전송을 규칙의 저장 폴더로 수신
+///
+/// 수락한 요청이 알린 파일 하나만 받습니다. 실제로 받은 바이트가 `max_bytes`(알린 크기와 규칙
+/// 한도 중 작은 값)를 넘으면 수신을 중단하고 임시 파일을 지웁니다.
+pub async fn receive_auto_accepted(
+    app: AppHandle,
+    conn: quinn::Connection,
+    peer_id: String,
+    job_id: String,
+    save_dir: String,
+    max_bytes: u64,
+) {
+    let state = app.state::<AppState>();
+    match crate::receive_file_on_connection(
+        &state,
+        &conn,
+        &peer_id,
+        &save_dir,
+        &job_id,
+        None,
+        Some(max_bytes),
+    )
+    .await
+    {
+        Ok(path) => info!("🖥️ 자동 수락 수신: {} ({} -> {})", job_id, path, save_dir),
+        Err(e) => warn!("🖥️ 자동 수락 수신 실패 ({}): {}", peer_id, e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_apply_args() {
+        let config = HeadlessConfig::default()
+            .apply_args(args(&["--headless", "--port", "7000", "--node-id=nas"]))
+            .unwrap();
+        assert_eq!(
+            config,
+            HeadlessConfig {
+                enabled: true,
+                port: 7000,
+                node_id: Some("nas".to_string()),
+                discovery: true,
+            }
+        );
+
+        // 파일 설정 위에 덮어쓰기, 알 수 없는 인자는 무시
+        let file: HeadlessConfig = serde_json::from_str(r#"{"enabled":true,"port":9000}"#).unwrap();
+        let config = file
+            .apply_args(args(&["-psn_0_123", "--no-discovery"]))
+            .unwrap();
+        assert!(config.enabled && !config.discovery);
+        assert_eq!(config.port, 9000);
+
+        assert!(HeadlessConfig::default()
+            .apply_args(args(&["--port", "abc"]))
+            .is_err());
+        assert!(HeadlessConfig::default()
+            .apply_args(args(&["--port"]))
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_auto_accept_matches_inbound_fingerprint() {
+        use crate::protocol::commands::TransferRequest;
+        use crate::quic::client::QuicClient;
+        use crate::quic::identity::{peer_fingerprint, QuicIdentity};
+        use crate::quic::QuicServer;
+        use crate::transfer::auto_accept::{AutoAcceptRule, AutoAcceptRules};
+
+        // 보내는 기기의 지문(`get_device_fingerprint`)으로 규칙을 등록
+        let sender = QuicIdentity::generate().unwrap();
+        let rules = AutoAcceptRules::in_memory();
+        rules
+            .upsert(AutoAcceptRule {
+                id: String::new(),
+                fingerprint: sender.fingerprint(),
+                contact_id: None,
+                label: None,
+                save_dir: "/drops".to_string(),
+                max_bytes: None,
+                enabled: true,
+            })
+            .unwrap();
+
+        let mut server = QuicServer::new("127.0.0.1:0".parse().unwrap())
+            .with_identity(QuicIdentity::generate().unwrap());
+        let mut accepted = server.take_connection_receiver().unwrap();
+        server.start().await.unwrap();
+        let addr = server.local_addr().unwrap();
+
+        // 수신 측은 요청의 지문을 수락한 연결에서 채움
+        let request = |conn: &quinn::Connection| TransferRequest {
+            job_id: "job".to_string(),
+            file_name: "build.zip".to_string(),
+            file_size: 1,
+            sender_name: "ci".to_string(),
+            sender_device: "linux".to_string(),
+            timestamp: 0,
+            sender_fingerprint: peer_fingerprint(conn),
+        };
+
+        let _outbound = QuicClient::new()
+            .with_identity(sender)
+            .connect(addr, "localhost")
+            .await
+            .unwrap();
+        let inbound = accepted.recv().await.unwrap().connection;
+        assert!(rules.evaluate(&request(&inbound)).is_some());
+
+        // 다른 기기나 인증서를 제시하지 않은 연결은 자동 수락되지 않음
+        let _other = QuicClient::new()
+            .with_identity(QuicIdentity::generate().unwrap())
+            .connect(addr, "localhost")
+            .await
+            .unwrap();
+        let inbound = accepted.recv().await.unwrap().connection;
+        assert!(rules.evaluate(&request(&inbound)).is_none());
+
+        let _anonymous = QuicClient::new().connect(addr, "localhost").await.unwrap();
+        let inbound = accepted.recv().await.unwrap().connection;
+        assert!(rules.evaluate(&request(&inbound)).is_none());
+
+        server.shutdown().await;
+    }
+}
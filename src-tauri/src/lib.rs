@@ -1,11 +1,22 @@
 mod bootstrap;
+mod contacts;
+mod dht;
 mod discovery;
+mod doctor;
+mod error;
 mod grid;
+mod headless;
+mod logging;
+mod metrics;
+mod notifications;
 mod protocol;
 mod quic;
 mod relay;
+mod sync;
 mod turn;
 mod transfer;
+mod tray;
+mod util;
 
 // 파일 스트림 관리자 (다중 파일 지원)
 use transfer::file_transfer::FileStreamManager;
@@ -13,6 +24,7 @@ use transfer::file_transfer::FileStreamManager;
 // Warp Engine v2.0 파일 시스템 커맨드
 use transfer::file_transfer::scan_folder;
 
+use error::AppError;
 use protocol::Command;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -24,10 +36,15 @@ use tracing::{debug, error, info, warn};
 use bootstrap::EmbeddedBootstrapService;
 use discovery::DiscoveryService;
 use quic::client::QuicClient;
-use quic::QuicServer;
-use relay::{engine::verify_no_disk_write, RelayEngine};
+use quic::{QuicPacingConfig, QuicServer};
+use relay::engine::{verify_no_disk_write, BufferPoolStats, RelayPoolConfig};
+use relay::{RelayEngine, RelaySelector};
 use std::path::PathBuf;
 use tokio::sync::mpsc;
+use transfer::audit::AuditDirection;
+use transfer::dir_stream::{DirStreamReceiver, DirStreamSender, DirStreamSummary};
+use transfer::estimate::TransferEstimate;
+use transfer::path_filter::PathFilter;
 use transfer::{
     extract_zip_to_directory,
     FileEntry,
@@ -36,7 +53,13 @@ use transfer::{
     MultiStreamProgress,
     MultiStreamReceiver,
     MultiStreamSender,
+    ReceivedText,
+    ReliableUdpReceiver,
+    ReliableUdpSender,
+    TransferKind,
     TransferProgress,
+    TransferRegistry,
+    TransferTransport,
     UdpTransferCore,
     ZeroCopyEngine,
     ZipStreamConfig,
@@ -48,11 +71,19 @@ use transfer::{
 pub struct AppState {
     quic_server: Arc<RwLock<Option<QuicServer>>>,
     quic_client: Arc<RwLock<Option<QuicClient>>>,
+    // 🆕 QUIC 혼잡 제어/페이싱 설정 (새 연결부터 적용)
+    quic_pacing: Arc<RwLock<QuicPacingConfig>>,
+    // 🆕 저장된 QUIC 서버 인증서 (기기 지문, 로드 실패 시 None)
+    quic_identity: Option<quic::identity::QuicIdentity>,
     discovery: Arc<RwLock<Option<DiscoveryService>>>,
     udp_core: Arc<RwLock<Option<UdpTransferCore>>>,
     relay_engine: Arc<RwLock<Option<RelayEngine>>>,
-    // 🆕 파일 전송 엔진
-    file_transfer: Arc<RwLock<Option<FileTransferEngine>>>,
+    // 🆕 릴레이 버퍼 풀 설정 (다음 릴레이 엔진 시작부터 적용)
+    relay_pool: Arc<RwLock<RelayPoolConfig>>,
+    // 🆕 지연 시간 기반 릴레이 선택기
+    relay_selector: Arc<RelaySelector>,
+    // 🆕 job_id 기준 전송 작업 레지스트리 (상태 조회/취소/일시정지)
+    pub transfer_registry: Arc<TransferRegistry>,
     // 🆕 전송 승인 관리자 (핸드쉐이크 승인)
     transfer_approval: Arc<crate::transfer::file_transfer::TransferApprovalManager>,
     // 🆕 파일 스트림 관리자 (다중 파일 쓰기)
@@ -63,17 +94,65 @@ pub struct AppState {
     accepted_connections: Arc<RwLock<std::collections::HashMap<String, quinn::Connection>>>,
     // 🆕 내장 부트스트랩 서비스
     embedded_bootstrap: Arc<RwLock<Option<EmbeddedBootstrapService>>>,
+    // 🆕 오프라인 시그널링 우편함 서명 키 (노드 ID)
+    signing_identity: Arc<bootstrap::mailbox::SigningIdentity>,
+    // 🆕 부트스트랩 접속 명부 주기적 등록 (WAN 피어 발견)
+    presence: Arc<bootstrap::presence::PresenceAnnouncer>,
     // 🆕 Tauri AppHandle 추가
     pub app_handle: AppHandle,
     // 🆕 앱 종료 진행 중 플래그
     // 🆕 앱 종료 진행 중 플래그
     pub is_closing: Arc<AtomicBool>,
-    // 🆕 활성 작업 관리 (취소용)
-    pub active_jobs: Arc<RwLock<std::collections::HashMap<String, JobControl>>>,
-}
-
-pub struct JobControl {
-    pub is_cancelled: Arc<AtomicBool>,
+    // 🆕 Grid Swarm 속도 제한 (job_id 기준, Swarm 시작 전에도 설정 가능)
+    pub grid_rate_limits:
+        Arc<RwLock<std::collections::HashMap<String, Arc<grid::rate_limit::SwarmRateLimit>>>>,
+    // 🆕 Grid 불량 피어 차단 목록 (모든 Swarm 공유, 디스크에 저장)
+    #[cfg(feature = "grid-experimental")]
+    pub grid_ban_list: Arc<grid::peer_score::BanList>,
+    // 🆕 폴더 감시/동기화 작업
+    pub folder_sync: Arc<sync::FolderSyncManager>,
+    // 🆕 다중 소스 다운로드 (공유 파일, 조각 요청)
+    pub multi_source: Arc<transfer::multi_source::MultiSourceManager>,
+    // 🆕 피어별 페어링 키 (페이로드 암호화)
+    pub pairing: Arc<transfer::pairing::PairingManager>,
+    // 🆕 피어 간 대역폭 측정
+    pub benchmark: Arc<transfer::benchmark::BenchmarkManager>,
+    // 🆕 데스크톱 알림 설정
+    pub notifications: Arc<notifications::NotificationCenter>,
+    // 🆕 헤드리스 수신 모드 (자동 수락된 전송을 UI 대신 백엔드가 직접 수신)
+    pub headless: bool,
+    // 🆕 브라우저 수신용 HTTP 공유 링크 (첫 링크 생성 시 서버 시작)
+    pub http_share: Arc<transfer::http_share::HttpShareServer>,
+    // 🆕 QUIC을 쓸 수 없는 송신자를 위한 tus 업로드 엔드포인트
+    pub tus: Arc<transfer::tus::TusServer>,
+    // 🆕 웹 버전(브라우저) 피어와의 WebRTC 데이터 채널 세션
+    pub webrtc: Arc<transfer::webrtc_channel::WebRtcInterop>,
+    // 🆕 내보내기/가져오기용 멀티스트림·Grid 작업 매니페스트
+    pub job_manifests: Arc<transfer::job_manifest::JobManifestStore>,
+    // 🆕 수신 측이 고른 파일/바이트 범위만 보내는 부분 요청 제공 목록
+    pub range_requests: Arc<transfer::range_request::RangeRequestManager>,
+    // 🆕 수신 중인 미디어를 loopback HTTP로 재생하는 미리보기 서버
+    pub media_preview: Arc<transfer::media_preview::MediaPreviewServer>,
+    // 🆕 피어별 하루/세션별 수신 용량 한도
+    pub receive_quota: Arc<transfer::quota::ReceiveQuota>,
+    // 🆕 노드 키로 서명한 전송 감사 로그
+    pub audit_log: Arc<transfer::audit::AuditLog>,
+    // 🆕 별명과 공개 키로 저장한 연락처
+    pub contacts: Arc<contacts::ContactBook>,
+    // 🆕 새 연락처 확인용 연결별 짧은 인증 문자열
+    pub sas: Arc<quic::sas::SasSessions>,
+    // 🆕 모든 서브시스템이 처리량을 기록하는 앱 전체 지표
+    pub metrics: Arc<metrics::MetricsCollector>,
+    // 🆕 모든 전송 엔진이 버퍼를 할당할 때 지키는 앱 전체 메모리 예산
+    pub buffer_budget: Arc<transfer::memory_budget::MemoryBudget>,
+    // 🆕 바뀌지 않은 파일의 Grid 조각 해시 캐시 (다시 시드/공유할 때 재해시 생략)
+    pub piece_hash_cache: Arc<grid::hash_cache::PieceHashCache>,
+    // 🆕 진행 중인 Grid 메타데이터 해시 계산 (job_id 기준, 진행률/취소)
+    pub grid_hashing_jobs:
+        Arc<RwLock<std::collections::HashMap<String, Arc<grid::piece_manager::HashingJob>>>>,
+    // 🆕 실행 중인 Grid Swarm 작업
+    #[cfg(feature = "grid-experimental")]
+    pub grid_jobs: Arc<grid::job::GridJobManager>,
 }
 
 impl Default for AppState {
@@ -84,7 +163,7 @@ impl Default for AppState {
 }
 
 #[tauri::command]
-async fn get_runtime_info() -> Result<serde_json::Value, String> {
+async fn get_runtime_info() -> Result<serde_json::Value, AppError> {
     Ok(serde_json::json!({
         "platform": std::env::consts::OS,
         "arch": std::env::consts::ARCH,
@@ -94,8 +173,42 @@ async fn get_runtime_info() -> Result<serde_json::Value, String> {
     }))
 }
 
+/// 🆕 실행 중 로그 수준 변경 (off/error/warn/info/debug/trace, 재시작 불필요)
+#[tauri::command]
+async fn set_log_level(level: String) -> Result<String, AppError> {
+    let filter = logging::parse_level(&level)?;
+    log::set_max_level(filter);
+    info!("📄 로그 수준 변경: {}", filter);
+    Ok(logging::current_level())
+}
+
+/// 🆕 현재 로그 수준
+#[tauri::command]
+async fn get_log_level() -> Result<String, AppError> {
+    Ok(logging::current_level())
+}
+
+/// 🆕 로그 파일의 최근 n줄 (기본 200줄, 오래된 줄부터)
 #[tauri::command]
-async fn ping_quic(_state: tauri::State<'_, AppState>) -> Result<String, String> {
+async fn get_recent_logs(app: AppHandle, n: Option<usize>) -> Result<Vec<String>, AppError> {
+    let path = app
+        .path()
+        .app_log_dir()
+        .map_err(|e| AppError::Io(format!("로그 경로 조회 실패: {}", e)))?
+        .join(format!("{}.log", logging::LOG_FILE_NAME));
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let n = n.unwrap_or(200).min(logging::MAX_RECENT_LINES);
+    tokio::task::spawn_blocking(move || logging::tail_lines(&path, n))
+        .await
+        .map_err(|e| AppError::Internal(format!("로그 읽기 작업 실패: {}", e)))?
+        .map_err(|e| AppError::Io(format!("로그 읽기 실패: {}", e)))
+}
+
+#[tauri::command]
+async fn ping_quic(_state: tauri::State<'_, AppState>) -> Result<String, AppError> {
     info!("QUIC ping 테스트 요청");
     Ok("pong".to_string())
 }
@@ -114,17 +227,41 @@ fn get_ip_via_udp_probe() -> Option<IpAddr> {
     }
 }
 
+/// `shards`가 2 이상이면 같은 포트에 SO_REUSEPORT 엔드포인트를 여러 개 열어 수신 분산
+///
+/// 🆕 `stun_server`("host:port")를 주면 서버 소켓의 공인 매핑 주소를 알아내 방 코드 후보에 포함하고,
+/// 주기적인 STUN Binding으로 그 매핑을 유지합니다.
 #[tauri::command]
-async fn start_quic_server(port: u16, state: tauri::State<'_, AppState>) -> Result<String, String> {
+async fn start_quic_server(
+    port: u16,
+    shards: Option<usize>,
+    stun_server: Option<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, AppError> {
     let addr = format!("0.0.0.0:{}", port)
         .parse()
-        .map_err(|e| format!("주소 파싱 실패: {}", e))?;
+        .map_err(|e| AppError::InvalidInput(format!("주소 파싱 실패: {}", e)))?;
 
-    let mut server = QuicServer::new(addr);
+    let mut server = QuicServer::new(addr)
+        .with_pacing(state.quic_pacing.read().await.clone())
+        .with_shards(shards.unwrap_or(1));
+    if let Some(identity) = &state.quic_identity {
+        server = server.with_identity(identity.clone());
+    }
+    if let Some(stun_server) = stun_server {
+        let resolved = tokio::net::lookup_host(&stun_server)
+            .await
+            .ok()
+            .and_then(|mut addrs| addrs.find(|a| a.is_ipv4()))
+            .ok_or_else(|| {
+                AppError::InvalidInput(format!("STUN 서버 주소 확인 실패: {}", stun_server))
+            })?;
+        server = server.with_stun(resolved);
+    }
     server
         .start()
         .await
-        .map_err(|e| format!("QUIC 서버 시작 실패: {}", e))?;
+        .map_err(|e| AppError::Network(format!("QUIC 서버 시작 실패: {}", e)))?;
 
     let local_addr = server.local_addr().unwrap_or(addr);
 
@@ -147,6 +284,9 @@ async fn start_quic_server(port: u16, state: tauri::State<'_, AppState>) -> Resu
                 let peer_id = accepted.peer_addr.to_string();
                 info!("📥 Receiver 연결됨: {}", peer_id);
 
+                // 🆕 제어 스트림 수신 대기 (텍스트 공유, 폴더 동기화)
+                spawn_control_listener(&app_handle, peer_id.clone(), accepted.connection.clone());
+
                 // 연결 저장
                 accepted_conns
                     .write()
@@ -168,14 +308,16 @@ async fn start_quic_server(port: u16, state: tauri::State<'_, AppState>) -> Resu
     *state.quic_server.write().await = Some(server);
 
     info!(
-        "QUIC 서버 시작됨: {} (연결 가능한 주소: {})",
-        local_addr, connectable_addr
+        "QUIC 서버 시작됨: {} (연결 가능한 주소: {}, 후보: {:?})",
+        local_addr,
+        connectable_addr,
+        local_candidates(&state).await
     );
     Ok(connectable_addr)
 }
 
 #[tauri::command]
-async fn stop_quic_server(state: tauri::State<'_, AppState>) -> Result<(), String> {
+async fn stop_quic_server(state: tauri::State<'_, AppState>) -> Result<(), AppError> {
     if let Some(mut server) = state.quic_server.write().await.take() {
         server.shutdown().await;
         info!("QUIC 서버 중지됨");
@@ -188,17 +330,17 @@ async fn start_discovery(
     node_id: String,
     port: u16,
     state: tauri::State<'_, AppState>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     let discovery = DiscoveryService::new(node_id.clone(), port)
-        .map_err(|e| format!("Discovery 서비스 생성 실패: {}", e))?;
+        .map_err(|e| AppError::Network(format!("Discovery 서비스 생성 실패: {}", e)))?;
 
     discovery
         .register()
-        .map_err(|e| format!("mDNS 등록 실패: {}", e))?;
+        .map_err(|e| AppError::Network(format!("mDNS 등록 실패: {}", e)))?;
     discovery
         .start_browsing()
         .await
-        .map_err(|e| format!("mDNS 브라우징 시작 실패: {}", e))?;
+        .map_err(|e| AppError::Network(format!("mDNS 브라우징 시작 실패: {}", e)))?;
 
     *state.discovery.write().await = Some(discovery);
 
@@ -209,7 +351,7 @@ async fn start_discovery(
 #[tauri::command]
 async fn get_discovered_peers(
     state: tauri::State<'_, AppState>,
-) -> Result<Vec<serde_json::Value>, String> {
+) -> Result<Vec<serde_json::Value>, AppError> {
     let discovery = state.discovery.read().await;
 
     if let Some(ref disc) = *discovery {
@@ -237,7 +379,7 @@ async fn get_discovered_peers(
 }
 
 #[tauri::command]
-async fn stop_discovery(state: tauri::State<'_, AppState>) -> Result<(), String> {
+async fn stop_discovery(state: tauri::State<'_, AppState>) -> Result<(), AppError> {
     if let Some(ref discovery) = *state.discovery.read().await {
         discovery.stop().await;
         info!("피어 발견 서비스 중지");
@@ -246,16 +388,23 @@ async fn stop_discovery(state: tauri::State<'_, AppState>) -> Result<(), String>
     Ok(())
 }
 
+/// `sharded`면 한 포트에 SO_REUSEPORT 소켓을 코어 수만큼(`socket_count` 0일 때) 바인딩
+/// 🆕 이후 UDP 파일 송수신도 이 코어의 소켓 수를 따름 (시작하지 않으면 기본 4개)
 #[tauri::command]
 async fn start_udp_transfer(
     socket_count: usize,
+    sharded: Option<bool>,
     state: tauri::State<'_, AppState>,
-) -> Result<serde_json::Value, String> {
-    let count = if socket_count == 0 { 8 } else { socket_count };
-
-    let udp_core = UdpTransferCore::new(count)
-        .await
-        .map_err(|e| format!("UDP 코어 생성 실패: {}", e))?;
+) -> Result<serde_json::Value, AppError> {
+    let udp_core = if sharded.unwrap_or(false) {
+        let shards = (socket_count > 0).then_some(socket_count);
+        UdpTransferCore::new_sharded(0, shards).await
+    } else {
+        let count = if socket_count == 0 { 8 } else { socket_count };
+        UdpTransferCore::new(count).await
+    }
+    .map_err(|e| AppError::Network(format!("UDP 코어 생성 실패: {}", e)))?
+    .with_metrics(state.metrics.counter(metrics::Subsystem::Udp));
 
     let addrs = udp_core.get_local_addrs().await;
     let socket_count = udp_core.socket_count();
@@ -270,10 +419,20 @@ async fn start_udp_transfer(
     }))
 }
 
+/// 🆕 `start_udp_transfer`로 시작한 UDP 코어의 소켓(샤드) 수
+async fn udp_socket_count(state: &AppState) -> Option<usize> {
+    state
+        .udp_core
+        .read()
+        .await
+        .as_ref()
+        .map(|core| core.socket_count())
+}
+
 #[tauri::command]
 async fn get_transfer_stats(
     state: tauri::State<'_, AppState>,
-) -> Result<serde_json::Value, String> {
+) -> Result<serde_json::Value, AppError> {
     let udp_core = state.udp_core.read().await;
 
     if let Some(ref core) = *udp_core {
@@ -285,6 +444,8 @@ async fn get_transfer_stats(
             "packetsReceived": stats.packets_received,
             "packetsLost": stats.packets_lost,
             "bandwidthMbps": stats.current_bandwidth_mbps,
+            "pacingRateBps": core.pacing_rate(),
+            "sockets": core.socket_stats(),
         }))
     } else {
         Ok(serde_json::json!({
@@ -293,13 +454,64 @@ async fn get_transfer_stats(
     }
 }
 
+/// UDP 코어 송신 목표 속도 설정 (bytes/sec, 0 = 페이싱 없음)
+#[tauri::command]
+async fn set_udp_pacing_rate(
+    rate_bps: u64,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), AppError> {
+    let udp_core = state.udp_core.read().await;
+    let core = udp_core
+        .as_ref()
+        .ok_or_else(|| AppError::NotRunning("UDP 코어가 시작되지 않음".into()))?;
+    core.set_pacing_rate(rate_bps);
+    info!("🚦 UDP 페이싱 속도 설정: {} B/s", rate_bps);
+    Ok(())
+}
+
+/// QUIC 혼잡 제어/페이싱 설정 조회
+#[tauri::command]
+async fn get_quic_pacing(state: tauri::State<'_, AppState>) -> Result<QuicPacingConfig, AppError> {
+    Ok(state.quic_pacing.read().await.clone())
+}
+
+/// QUIC 혼잡 제어/페이싱 설정 변경 (클라이언트는 다음 연결, 서버는 재시작 후 적용)
+#[tauri::command]
+async fn set_quic_pacing(
+    config: QuicPacingConfig,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), AppError> {
+    config.validate()?;
+
+    if let Some(ref mut client) = *state.quic_client.write().await {
+        client.set_pacing(config.clone());
+    }
+    info!("🚦 QUIC 페이싱 설정: {:?}", config);
+    *state.quic_pacing.write().await = config;
+    Ok(())
+}
+
 #[tauri::command]
-async fn start_relay_engine(state: tauri::State<'_, AppState>) -> Result<(), String> {
-    let engine = RelayEngine::new();
+async fn start_relay_engine(
+    app_handle: AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), AppError> {
+    // 🆕 할당 압박이 있을 때만 `relay-pool-stats` 이벤트 발생 (엔진이 내려가면 함께 종료)
+    let (pool_stats_tx, mut pool_stats_rx) = mpsc::unbounded_channel::<BufferPoolStats>();
+    tauri::async_runtime::spawn(async move {
+        while let Some(stats) = pool_stats_rx.recv().await {
+            let _ = app_handle.emit("relay-pool-stats", &stats);
+        }
+    });
+
+    let engine = RelayEngine::new()
+        .with_metrics(state.metrics.counter(metrics::Subsystem::Relay))
+        .with_pool_config(*state.relay_pool.read().await)
+        .with_pool_stats(pool_stats_tx);
     engine
         .start()
         .await
-        .map_err(|e| format!("릴레이 엔진 시작 실패: {}", e))?;
+        .map_err(|e| AppError::Network(format!("릴레이 엔진 시작 실패: {}", e)))?;
 
     *state.relay_engine.write().await = Some(engine);
 
@@ -308,17 +520,20 @@ async fn start_relay_engine(state: tauri::State<'_, AppState>) -> Result<(), Str
 }
 
 #[tauri::command]
-async fn get_relay_stats(state: tauri::State<'_, AppState>) -> Result<serde_json::Value, String> {
+async fn get_relay_stats(state: tauri::State<'_, AppState>) -> Result<serde_json::Value, AppError> {
     let relay = state.relay_engine.read().await;
 
     if let Some(ref engine) = *relay {
         let session_count = engine.active_session_count().await;
         let (pool_available, pool_allocated) = engine.buffer_pool_stats().await;
+        let (pool_max_buffers, pool_buffer_size) = engine.buffer_pool_capacity();
 
         Ok(serde_json::json!({
             "activeSessions": session_count,
             "bufferPoolAvailable": pool_available,
             "bufferPoolAllocated": pool_allocated,
+            "bufferPoolMaxBuffers": pool_max_buffers,
+            "bufferPoolBufferSize": pool_buffer_size,
             "zeroDiskVerified": verify_no_disk_write(),
         }))
     } else {
@@ -328,8 +543,29 @@ async fn get_relay_stats(state: tauri::State<'_, AppState>) -> Result<serde_json
     }
 }
 
+/// 🆕 릴레이 버퍼 풀 설정 조회
+#[tauri::command]
+async fn get_relay_pool_config(
+    state: tauri::State<'_, AppState>,
+) -> Result<RelayPoolConfig, AppError> {
+    Ok(*state.relay_pool.read().await)
+}
+
+/// 🆕 릴레이 버퍼 풀 설정 변경 (실행 중인 엔진은 재시작 후 적용)
+#[tauri::command]
+async fn set_relay_pool_config(
+    config: RelayPoolConfig,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), AppError> {
+    config.validate()?;
+
+    info!("🔧 릴레이 버퍼 풀 설정: {:?}", config);
+    *state.relay_pool.write().await = config;
+    Ok(())
+}
+
 #[tauri::command]
-async fn stop_relay_engine(state: tauri::State<'_, AppState>) -> Result<(), String> {
+async fn stop_relay_engine(state: tauri::State<'_, AppState>) -> Result<(), AppError> {
     if let Some(ref engine) = *state.relay_engine.read().await {
         engine.stop().await;
         info!("🛑 릴레이 엔진 중지됨");
@@ -338,6 +574,197 @@ async fn stop_relay_engine(state: tauri::State<'_, AppState>) -> Result<(), Stri
     Ok(())
 }
 
+/// 알려진 릴레이 후보 수집 (mDNS + DHT + 설정)
+async fn refresh_relay_candidates(state: &AppState) {
+    use relay::{RelayCandidate, RelaySource};
+
+    // 1. mDNS로 발견된 릴레이 가능 피어
+    if let Some(ref disc) = *state.discovery.read().await {
+        for peer in disc.get_peers() {
+            if peer.capabilities.can_relay {
+                state
+                    .relay_selector
+                    .add_candidate(
+                        RelayCandidate::new(peer.address, RelaySource::Mdns)
+                            .with_bandwidth(peer.capabilities.available_bandwidth_mbps as u64),
+                    )
+                    .await;
+            }
+        }
+    }
+
+    // 2. 내장 부트스트랩의 설정 노드 및 DHT 라우팅 테이블
+    if let Some(ref service) = *state.embedded_bootstrap.read().await {
+        let config = service.config();
+        let external_nodes = config.read().await.external_bootstrap_nodes.clone();
+        for addr in external_nodes.iter().filter_map(|a| a.parse().ok()) {
+            state
+                .relay_selector
+                .add_candidate(RelayCandidate::new(addr, RelaySource::Config))
+                .await;
+        }
+
+        if let Some(dht) = service.dht_handle() {
+            match dht.list_nodes().await {
+                Ok(nodes) => {
+                    for node in nodes {
+                        // 부트스트랩 노드는 기본 포트 배치(DHT 포트 + 1 = QUIC 릴레이)를 따름
+                        let relay_addr = SocketAddr::new(node.ip(), node.port().saturating_add(1));
+                        state
+                            .relay_selector
+                            .add_candidate(RelayCandidate::new(relay_addr, RelaySource::Dht))
+                            .await;
+                    }
+                }
+                Err(e) => warn!("DHT 노드 목록 조회 실패: {}", e),
+            }
+        }
+    }
+}
+
+/// 🆕 네트워크 진단 (mDNS, STUN/NAT, TURN, DHT, UDP 포트, 릴레이를 각각 실제로 확인)
+///
+/// `udp_port`를 주면 그 포트의 바인딩 가능 여부를, `stun_servers`를 주면 기본 대신 그 서버들을 사용합니다.
+#[tauri::command]
+async fn run_network_doctor(
+    udp_port: Option<u16>,
+    stun_servers: Option<Vec<String>>,
+    state: tauri::State<'_, AppState>,
+) -> Result<doctor::DoctorReport, AppError> {
+    let started = std::time::Instant::now();
+    info!("🩺 네트워크 진단 시작");
+
+    let known_peers = state
+        .discovery
+        .read()
+        .await
+        .as_ref()
+        .map(|disc| disc.get_peer_count());
+    let quic_port = state
+        .quic_server
+        .read()
+        .await
+        .as_ref()
+        .and_then(|server| server.local_addr())
+        .map(|addr| addr.port());
+    let (dht, turn_config) = match *state.embedded_bootstrap.read().await {
+        Some(ref service) => (
+            service.dht_handle(),
+            service.config().read().await.turn_config(),
+        ),
+        None => (None, None),
+    };
+    let turn_client = turn_config
+        .or_else(|| turn::TurnConfig::from_env().ok())
+        .filter(|config| config.is_enabled())
+        .and_then(|config| turn::TurnClient::new(config).ok());
+    let stun_servers = stun_servers.unwrap_or_else(|| {
+        doctor::DEFAULT_STUN_SERVERS
+            .iter()
+            .map(|server| server.to_string())
+            .collect()
+    });
+
+    refresh_relay_candidates(&state).await;
+    let (mdns, stun, turn, dht, udp, relay) = tokio::join!(
+        doctor::run_check("mdns", doctor::check_mdns(known_peers)),
+        doctor::run_check("stun", doctor::check_stun(&stun_servers)),
+        doctor::run_check("turn", doctor::check_turn(turn_client)),
+        doctor::run_check("dht", doctor::check_dht(dht)),
+        doctor::run_check("udpPort", doctor::check_udp_port(udp_port, quic_port)),
+        doctor::run_check("relay", doctor::check_relays(&state.relay_selector)),
+    );
+
+    let report = doctor::DoctorReport::new(vec![mdns, stun, turn, dht, udp, relay], started);
+    info!(
+        "🩺 네트워크 진단 완료: 통과 {}, 실패 {} ({}ms)",
+        report.passed, report.failed, report.duration_ms
+    );
+    Ok(report)
+}
+
+/// 상대 피어가 알려준 릴레이별 RTT 파싱 ("ip:port" -> ms)
+fn parse_remote_rtts(
+    remote_rtts: Option<std::collections::HashMap<String, u32>>,
+) -> std::collections::HashMap<SocketAddr, u32> {
+    remote_rtts
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|(addr, rtt)| addr.parse().ok().map(|a| (a, rtt)))
+        .collect()
+}
+
+/// 🆕 피어 쌍에 가장 적합한 릴레이 자동 선택
+#[tauri::command]
+async fn select_best_relay(
+    session_id: String,
+    remote_rtts: Option<std::collections::HashMap<String, u32>>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Option<relay::RelaySelection>, AppError> {
+    refresh_relay_candidates(&state).await;
+
+    state
+        .relay_selector
+        .assign(&session_id, parse_remote_rtts(remote_rtts))
+        .await
+        .map_err(|e| AppError::Network(format!("릴레이 선택 실패: {}", e)))
+}
+
+/// 🆕 릴레이 후보 목록 및 측정된 RTT 조회
+#[tauri::command]
+async fn get_relay_candidates(
+    probe: Option<bool>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<relay::RelayCandidate>, AppError> {
+    refresh_relay_candidates(&state).await;
+
+    if probe.unwrap_or(false) {
+        state
+            .relay_selector
+            .probe_all()
+            .await
+            .map_err(|e| AppError::Network(format!("릴레이 측정 실패: {}", e)))?;
+    }
+
+    Ok(state.relay_selector.candidates().await)
+}
+
+/// 🆕 릴레이 세션 처리량 보고 (급락 시 재선택)
+#[tauri::command]
+async fn report_relay_throughput(
+    session_id: String,
+    bytes_per_sec: u64,
+    state: tauri::State<'_, AppState>,
+) -> Result<Option<relay::RelaySelection>, AppError> {
+    let reselected = state
+        .relay_selector
+        .report_throughput(&session_id, bytes_per_sec)
+        .await
+        .map_err(|e| AppError::Network(format!("릴레이 재평가 실패: {}", e)))?;
+
+    if let Some(ref selection) = reselected {
+        let _ = state.app_handle.emit(
+            "relay-reselected",
+            serde_json::json!({
+                "sessionId": session_id,
+                "relay": selection,
+            }),
+        );
+    }
+
+    Ok(reselected)
+}
+
+/// 🆕 릴레이 세션 해제
+#[tauri::command]
+async fn release_relay(
+    session_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), AppError> {
+    state.relay_selector.release(&session_id).await;
+    Ok(())
+}
+
 // --- QUIC 파일 전송 Commands ---
 
 /// QUIC 피어에 연결
@@ -346,21 +773,27 @@ async fn connect_to_peer(
     peer_id: String,
     peer_address: String,
     state: tauri::State<'_, AppState>,
-) -> Result<bool, String> {
+) -> Result<bool, AppError> {
     let peer_addr: SocketAddr = peer_address
         .parse()
-        .map_err(|e| format!("주소 파싱 실패: {}", e))?;
+        .map_err(|e| AppError::InvalidInput(format!("주소 파싱 실패: {}", e)))?;
 
     let mut client = state.quic_client.write().await;
     if client.is_none() {
-        *client = Some(QuicClient::new());
+        *client = Some(new_quic_client(
+            &state,
+            state.quic_pacing.read().await.clone(),
+        ));
     }
 
     if let Some(ref mut c) = *client {
         let conn = c
             .connect(peer_addr, &peer_id)
             .await
-            .map_err(|e| format!("QUIC 연결 실패: {}", e))?;
+            .map_err(|e| AppError::Network(format!("QUIC 연결 실패: {}", e)))?;
+
+        // 🆕 제어 스트림 수신 대기 (텍스트 공유, 폴더 동기화)
+        spawn_control_listener(&state.app_handle, peer_id.clone(), conn.clone());
 
         // 연결 저장
         state
@@ -372,176 +805,1785 @@ async fn connect_to_peer(
         info!("✅ 피어 연결 성공: {} @ {}", peer_id, peer_address);
         Ok(true)
     } else {
-        Err("QUIC 클라이언트 초기화 실패".to_string())
+        Err(AppError::Internal("QUIC 클라이언트 초기화 실패".into()))
     }
 }
 
-/// QUIC을 통해 파일 전송 시작 (Sender - 클라이언트로 연결한 경우)
+/// 🆕 여러 후보 주소로 동시에 연결해 먼저 성공한 주소 사용 (연결된 주소 반환)
 #[tauri::command]
-async fn send_file_to_peer(
+async fn connect_to_peer_race(
     peer_id: String,
-    file_path: String,
-    job_id: String,
+    candidates: Vec<String>,
     state: tauri::State<'_, AppState>,
-) -> Result<u64, String> {
-    // 1. Scope를 제한하여 Lock 시간을 최소화하고 Connection을 복제(Clone)합니다.
-    let conn = {
-        let connections = state.active_connections.read().await;
-        connections
-            .get(&peer_id)
-            .ok_or_else(|| format!("피어 {}에 대한 연결이 없습니다.", peer_id))?
-            .clone() // Quinn Connection은 내부적으로 Arc이므로 Clone 가능
-    }; // 여기서 read lock이 해제됩니다.
-
-    info!("📤 전송 시작: {} -> {}", file_path, peer_id);
-
-    // 2. 별도의 채널 생성
-    let (tx, mut rx) = mpsc::channel::<TransferProgress>(100);
-    let mut engine = FileTransferEngine::new();
-    engine.set_progress_channel(tx);
+) -> Result<String, AppError> {
+    let addrs: Vec<SocketAddr> = candidates
+        .iter()
+        .filter_map(|candidate| candidate.parse().ok())
+        .collect();
+    let (addr, conn) = race_connect(&state, &addrs).await?;
+    register_connection(&state, &peer_id, conn).await;
 
-    let app_handle = state.app_handle.clone();
+    info!("✅ 피어 연결 성공: {} @ {}", peer_id, addr);
+    Ok(addr.to_string())
+}
 
-    // 3. 비동기 작업 수행 (Lock 없는 상태)
-    tauri::async_runtime::spawn(async move {
-        while let Some(progress) = rx.recv().await {
-            let _ = app_handle.emit("transfer-progress", &progress);
-        }
-    });
+/// 후보 주소 연결 경쟁 (먼저 성공한 주소와 연결)
+async fn race_connect(
+    state: &tauri::State<'_, AppState>,
+    candidates: &[SocketAddr],
+) -> Result<(SocketAddr, quinn::Connection), AppError> {
+    let pacing = state.quic_pacing.read().await.clone();
+    let mut client = state.quic_client.write().await;
+    client
+        .get_or_insert_with(|| new_quic_client(state, pacing))
+        .connect_first(candidates, "ponswarp.local")
+        .await
+        .map_err(|e| AppError::Network(format!("QUIC 연결 실패: {}", e)))
+}
 
-    let path = PathBuf::from(&file_path);
+/// 🆕 기기 인증서를 클라이언트 인증서로 제시하는 QUIC 클라이언트
+fn new_quic_client(state: &AppState, pacing: QuicPacingConfig) -> QuicClient {
+    let client = QuicClient::new().with_pacing(pacing);
+    match &state.quic_identity {
+        Some(identity) => client.with_identity(identity.clone()),
+        None => client,
+    }
+}
 
-    // conn을 소유권 이동으로 넘겨도 원본 HashMap에는 영향 없음 (Clone 했으므로)
-    let bytes_sent = engine
-        .send_file(&conn, path, &job_id)
+/// 연결 등록 (제어 스트림 수신 대기 후 활성 연결에 저장)
+async fn register_connection(
+    state: &tauri::State<'_, AppState>,
+    peer_id: &str,
+    conn: quinn::Connection,
+) {
+    spawn_control_listener(&state.app_handle, peer_id.to_string(), conn.clone());
+    state
+        .active_connections
+        .write()
         .await
-        .map_err(|e| format!("파일 전송 실패: {}", e))?;
-
-    let _ = state.app_handle.emit(
-        "transfer-complete",
-        serde_json::json!({
-            "jobId": job_id,
-            "bytesSent": bytes_sent,
-            "peerId": peer_id,
-        }),
-    );
+        .insert(peer_id.to_string(), conn);
+}
 
-    info!("✅ 파일 전송 완료: {} bytes to {}", bytes_sent, peer_id);
-    Ok(bytes_sent)
+/// 이 기기의 연결 후보 주소 (QUIC 서버 포트 + 로컬 IP, 서버가 없으면 빈 목록)
+///
+/// 🆕 유선/Wi-Fi/VPN 등 모든 인터페이스의 IP를 기본 라우트 IP 다음에 포함하고,
+/// 서버가 STUN으로 공인 매핑 주소를 알아냈다면 마지막에 포함합니다.
+async fn local_candidates(state: &AppState) -> Vec<String> {
+    let server = state.quic_server.read().await;
+    let Some(local_addr) = server.as_ref().and_then(|server| server.local_addr()) else {
+        return Vec::new();
+    };
+    let mut ips = if local_addr.ip().is_unspecified() {
+        quic::host_candidates::host_ips(get_ip_via_udp_probe())
+    } else {
+        vec![local_addr.ip()]
+    };
+    if ips.is_empty() {
+        ips.push(IpAddr::V4(Ipv4Addr::LOCALHOST));
+    }
+    let mut candidates: Vec<String> = ips
+        .into_iter()
+        .map(|ip| SocketAddr::new(ip, local_addr.port()).to_string())
+        .collect();
+    if let Some(reflexive) = server.as_ref().and_then(|server| server.reflexive_addr()) {
+        candidates.push(reflexive.to_string());
+    }
+    candidates
 }
 
-/// 🆕 서버에서 수락한 연결로 파일 전송 (Sender - 서버 역할)
+/// 🆕 부트스트랩에서 방 코드 받기 (송신자, QUIC 서버가 실행 중이어야 함)
+///
+/// 수신자가 참여하면 `room-peer-joined` 이벤트, 아무도 참여하지 않고 만료되면 `room-code-expired` 이벤트
 #[tauri::command]
-async fn send_file_to_accepted_peer(
-    peer_id: String,
-    file_path: String,
-    job_id: String,
+async fn create_room_code(
+    bootstrap_addr: String,
     state: tauri::State<'_, AppState>,
-) -> Result<u64, String> {
-    // 1. Scope를 제한하여 Lock 시간을 최소화하고 Connection을 복제(Clone)합니다.
-    let conn = {
-        let connections = state.accepted_connections.read().await;
-        connections
-            .get(&peer_id)
-            .ok_or_else(|| format!("수락된 피어 {}에 대한 연결이 없습니다.", peer_id))?
-            .clone() // Quinn Connection은 내부적으로 Arc이므로 Clone 가능
-    }; // 여기서 read lock이 해제됩니다.
-
-    info!("📤 전송 시작: {} -> {}", file_path, peer_id);
+) -> Result<serde_json::Value, AppError> {
+    use bootstrap::rendezvous;
 
-    // 2. 별도의 채널 생성
-    let (tx, mut rx) = mpsc::channel::<TransferProgress>(100);
-    let mut engine = FileTransferEngine::new();
-    engine.set_progress_channel(tx);
+    let bootstrap: SocketAddr = bootstrap_addr
+        .parse()
+        .map_err(|e| AppError::InvalidInput(format!("잘못된 부트스트랩 주소: {}", e)))?;
+    let candidates = local_candidates(&state).await;
+    if candidates.is_empty() {
+        return Err(AppError::NotRunning("QUIC 서버를 먼저 시작하세요".into()));
+    }
+    let ticket = rendezvous::create_room(bootstrap, candidates)
+        .await
+        .map_err(|e| AppError::Network(format!("방 코드 발급 실패: {}", e)))?;
 
+    // 참여할 때까지 주기적으로 확인 (수신자가 QUIC 서버로 직접 연결해 옴)
     let app_handle = state.app_handle.clone();
-
-    // 3. 비동기 작업 수행 (Lock 없는 상태)
+    let watched = ticket.clone();
     tauri::async_runtime::spawn(async move {
-        while let Some(progress) = rx.recv().await {
-            let _ = app_handle.emit("transfer-progress", &progress);
+        let deadline = tokio::time::Instant::now() + rendezvous::ROOM_TTL;
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(2));
+        while tokio::time::Instant::now() < deadline {
+            interval.tick().await;
+            match rendezvous::room_status(bootstrap, &watched).await {
+                Ok(Some(candidates)) => {
+                    info!("🚪 방 참여: {}", watched.code);
+                    let _ = app_handle.emit(
+                        "room-peer-joined",
+                        serde_json::json!({
+                            "code": watched.code,
+                            "candidates": candidates,
+                        }),
+                    );
+                    rendezvous::close_room(bootstrap, &watched).await;
+                    return;
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    warn!("방 상태 확인 실패 ({}): {}", watched.code, e);
+                    break;
+                }
+            }
         }
+        let _ = app_handle.emit(
+            "room-code-expired",
+            serde_json::json!({ "code": watched.code }),
+        );
     });
 
-    let path = PathBuf::from(&file_path);
+    Ok(serde_json::json!({
+        "code": ticket.code,
+        "expiresInSecs": ticket.expires_in_secs,
+    }))
+}
 
-    // conn을 소유권 이동으로 넘겨도 원본 HashMap에는 영향 없음 (Clone 했으므로)
-    let bytes_sent = engine
-        .send_file(&conn, path, &job_id)
+/// 🆕 방 코드로 송신자에게 연결 (수신자, 연결된 피어 ID 반환)
+///
+/// 송신자 후보(로컬/공인 주소)로 동시에 연결을 시도하며, 피어 ID는 연결된 주소입니다.
+#[tauri::command]
+async fn join_room_code(
+    bootstrap_addr: String,
+    code: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, AppError> {
+    let bootstrap: SocketAddr = bootstrap_addr
+        .parse()
+        .map_err(|e| AppError::InvalidInput(format!("잘못된 부트스트랩 주소: {}", e)))?;
+    let candidates = local_candidates(&state).await;
+    let sender_candidates = bootstrap::rendezvous::join_room(bootstrap, &code, candidates)
         .await
-        .map_err(|e| format!("파일 전송 실패: {}", e))?;
+        .map_err(|e| AppError::Network(format!("방 참여 실패: {}", e)))?;
 
-    let _ = state.app_handle.emit(
-        "transfer-complete",
-        serde_json::json!({
-            "jobId": job_id,
-            "bytesSent": bytes_sent,
-            "peerId": peer_id,
-        }),
-    );
+    let (addr, conn) = race_connect(&state, &sender_candidates).await?;
+    let peer_id = addr.to_string();
+    register_connection(&state, &peer_id, conn).await;
 
-    info!("✅ 파일 전송 완료: {} bytes to {}", bytes_sent, peer_id);
-    Ok(bytes_sent)
+    info!("✅ 방 {}의 피어 연결 성공: {}", code, peer_id);
+    Ok(peer_id)
 }
 
-/// 🆕 수락된 연결 목록 조회
-#[tauri::command]
-async fn get_accepted_peers(state: tauri::State<'_, AppState>) -> Result<Vec<String>, String> {
-    let connections = state.accepted_connections.read().await;
-    Ok(connections.keys().cloned().collect())
+/// 🆕 ICE 재시작 후 기존 경로 확인 제한 시간
+const ICE_RESTART_PROBE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// 🆕 재바인딩한 연결이 새 경로로 응답하는지 확인
+///
+/// quinn은 로컬 주소가 바뀌면 곧바로 PING을 보내므로 수신 데이터그램 수가 늘면 경로가 살아 있습니다.
+/// (전송 엔진의 `accept_bi`와 겹치지 않도록 스트림을 열지 않음)
+async fn path_responds(conn: &quinn::Connection, received_before: u64) -> bool {
+    let deadline = tokio::time::Instant::now() + ICE_RESTART_PROBE_TIMEOUT;
+    while tokio::time::Instant::now() < deadline {
+        if conn.close_reason().is_some() {
+            return false;
+        }
+        if conn.stats().udp_rx.datagrams > received_before {
+            return true;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    false
 }
 
-/// QUIC을 통해 파일 수신 대기 (Receiver)
+/// 🆕 네트워크 변경(로밍 등)으로 연결이 끊겼을 때 ICE 재시작
+///
+/// 후보를 다시 수집하고, 클라이언트 연결은 새 소켓으로 재바인딩해 QUIC 마이그레이션으로
+/// 같은 연결을 이어 갑니다(진행 중인 전송 유지). 기존 경로가 응답하지 않으면 시그널링으로
+/// 다시 받은 `remote_candidates`로 새로 연결해 같은 피어 ID의 연결을 교체합니다.
+///
+/// 반환값의 `candidates`는 상대에게 다시 보낼 이 기기의 후보이며, `mode`는
+/// `migrated`/`reconnected`/`pending`(상대 후보를 받아 다시 호출해야 함) 중 하나입니다.
+/// 경로가 복구되면 `ice-restarted` 이벤트가 발생합니다.
 #[tauri::command]
-async fn receive_file_from_peer(
+async fn restart_ice(
     peer_id: String,
-    save_dir: String,
-    job_id: String,
+    remote_candidates: Option<Vec<String>>,
     state: tauri::State<'_, AppState>,
-) -> Result<String, String> {
-    // 1. Scope를 제한하여 Lock 시간을 최소화하고 Connection을 복제(Clone)합니다.
-    let conn = {
-        let connections = state.active_connections.read().await;
-        connections
-            .get(&peer_id)
-            .ok_or_else(|| format!("피어 {}에 대한 연결이 없습니다.", peer_id))?
-            .clone() // Quinn Connection은 내부적으로 Arc이므로 Clone 가능
-    }; // 여기서 read lock이 해제됩니다.
-
-    info!("📥 수신 시작: {} -> {}", peer_id, save_dir);
+) -> Result<serde_json::Value, AppError> {
+    let candidates = local_candidates(&state).await;
 
-    // 2. 별도의 채널 생성
-    let (tx, mut rx) = mpsc::channel::<TransferProgress>(100);
-    let mut engine = FileTransferEngine::new();
-    engine.set_progress_channel(tx);
-
-    let app_handle = state.app_handle.clone();
-
-    // 3. 비동기 작업 수행 (Lock 없는 상태)
-    tauri::async_runtime::spawn(async move {
-        while let Some(progress) = rx.recv().await {
-            let _ = app_handle.emit("transfer-progress", &progress);
-        }
-    });
-
-    let save_path = PathBuf::from(&save_dir);
-
-    // conn을 소유권 이동으로 넘겨도 원본 HashMap에는 영향 없음 (Clone 했으므로)
-    let result_path = engine
-        .receive_file(&conn, save_path, &job_id)
+    // 서버 쪽은 연결을 옮길 수 없으므로 직접 연 연결만 재바인딩 후 경로 확인
+    let existing = state
+        .active_connections
+        .read()
         .await
-        .map_err(|e| format!("파일 수신 실패: {}", e))?;
+        .get(&peer_id)
+        .filter(|conn| conn.close_reason().is_none())
+        .cloned();
+    let received_before = existing.as_ref().map(|conn| conn.stats().udp_rx.datagrams);
+    if let Some(client) = state.quic_client.write().await.as_mut() {
+        client
+            .rebind()
+            .map_err(|e| AppError::Network(format!("QUIC 소켓 재바인딩 실패: {}", e)))?;
+    }
+    let migrated = match (&existing, received_before) {
+        (Some(conn), Some(received_before)) => path_responds(conn, received_before).await,
+        _ => false,
+    };
 
-    let result_str = result_path.to_string_lossy().to_string();
+    let (mode, remote_addr) = if migrated {
+        ("migrated", existing.map(|conn| conn.remote_address()))
+    } else {
+        let addrs: Vec<SocketAddr> = remote_candidates
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|candidate| candidate.parse().ok())
+            .collect();
+        if addrs.is_empty() {
+            ("pending", None)
+        } else {
+            let (addr, conn) = race_connect(&state, &addrs).await?;
+            let stale = [
+                state.active_connections.write().await.remove(&peer_id),
+                state.accepted_connections.write().await.remove(&peer_id),
+            ];
+            for old in stale.into_iter().flatten() {
+                old.close(0u32.into(), b"ice restart");
+            }
+            register_connection(&state, &peer_id, conn).await;
+            ("reconnected", Some(addr))
+        }
+    };
+
+    if let Some(addr) = remote_addr {
+        info!("🧊 ICE 재시작 완료 ({}): {} @ {}", mode, peer_id, addr);
+        let _ = state.app_handle.emit(
+            "ice-restarted",
+            serde_json::json!({
+                "peerId": peer_id,
+                "mode": mode,
+                "remoteAddr": addr.to_string(),
+            }),
+        );
+    } else {
+        info!("🧊 ICE 재시작: {} 상대 후보 대기 중", peer_id);
+    }
+
+    Ok(serde_json::json!({
+        "mode": mode,
+        "candidates": candidates,
+    }))
+}
+
+/// 🆕 전송 중에는 피어 연결별 품질(RTT, 손실률, 혼잡 윈도우, 경로)을 `connection-quality` 이벤트로 주기 전송
+fn spawn_quality_monitor(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut monitor = quic::quality::QualityMonitor::new();
+        let mut ticker = tokio::time::interval(quic::quality::QUALITY_INTERVAL);
+        loop {
+            ticker.tick().await;
+            let Some(state) = app_handle.try_state::<AppState>() else {
+                continue;
+            };
+            if state.is_closing.load(Ordering::SeqCst) {
+                break;
+            }
+            if !state.transfer_registry.has_active() {
+                continue;
+            }
+
+            let mut connections = state.active_connections.read().await.clone();
+            for (peer_id, conn) in state.accepted_connections.read().await.iter() {
+                connections
+                    .entry(peer_id.clone())
+                    .or_insert_with(|| conn.clone());
+            }
+            connections.retain(|_, conn| conn.close_reason().is_none());
+            monitor.retain(&connections.keys().cloned().collect());
+
+            let relays: std::collections::HashSet<SocketAddr> = state
+                .relay_selector
+                .candidates()
+                .await
+                .iter()
+                .map(|candidate| candidate.address)
+                .collect();
+            for (peer_id, conn) in &connections {
+                let quality = monitor.sample(peer_id, conn, &relays);
+                let _ = app_handle.emit("connection-quality", &quality);
+            }
+        }
+    });
+}
+
+/// 연결의 제어 스트림 처리 (텍스트는 `text-received` 이벤트, 폴더 동기화는 `folder_sync`,
+/// 조각 요청/응답은 `multi_source`, 부분 요청은 `range_requests`, 페어링은 `pairing`으로 전달)
+///
+/// 🆕 연결 직후 이 기기의 `IdentityProof`를 보내고, 신뢰하는 연락처가 아닌 상대의 증명을 받으면
+/// 짧은 인증 문자열과 함께 `sas-verification-required` 이벤트를 보냅니다.
+fn spawn_control_listener(app_handle: &AppHandle, peer_id: String, conn: quinn::Connection) {
+    let (tx, mut rx) = mpsc::channel::<transfer::control_stream::IncomingStream>(16);
+    tauri::async_runtime::spawn(transfer::control_stream::accept_streams(
+        conn.clone(),
+        peer_id.clone(),
+        tx,
+    ));
+    tauri::async_runtime::spawn(send_identity_proof(
+        app_handle.clone(),
+        peer_id.clone(),
+        conn.clone(),
+    ));
+
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        while let Some(mut incoming) = rx.recv().await {
+            match incoming.command {
+                Command::TextMessage { .. } => {
+                    if let Err(e) =
+                        transfer::control_stream::finish_header_only(&mut incoming.recv).await
+                    {
+                        warn!("텍스트 스트림 종료 오류 ({}): {}", incoming.peer_id, e);
+                    }
+                    if let Some(text) =
+                        ReceivedText::from_command(&incoming.peer_id, incoming.command)
+                    {
+                        let _ = app_handle.emit("text-received", &text);
+                    }
+                }
+                Command::SyncFile { .. } | Command::SyncDelete { .. } => {
+                    let folder_sync = app_handle.state::<AppState>().folder_sync.clone();
+                    tauri::async_runtime::spawn(async move {
+                        folder_sync.handle_incoming(incoming).await;
+                    });
+                }
+                Command::PieceRequest { .. } | Command::PieceResponse { .. } => {
+                    let multi_source = app_handle.state::<AppState>().multi_source.clone();
+                    let conn = conn.clone();
+                    tauri::async_runtime::spawn(async move {
+                        multi_source.handle_incoming(conn, incoming).await;
+                    });
+                }
+                Command::RangeRequest { .. } | Command::RangeResponse { .. } => {
+                    let range_requests = app_handle.state::<AppState>().range_requests.clone();
+                    let conn = conn.clone();
+                    tauri::async_runtime::spawn(async move {
+                        range_requests.handle_incoming(conn, incoming).await;
+                    });
+                }
+                Command::BenchmarkRequest { .. } | Command::BenchmarkData { .. } => {
+                    let benchmark = app_handle.state::<AppState>().benchmark.clone();
+                    let conn = conn.clone();
+                    tauri::async_runtime::spawn(async move {
+                        benchmark.handle_incoming(conn, incoming).await;
+                    });
+                }
+                Command::PairRequest { .. }
+                | Command::PairResponse { .. }
+                | Command::PairReveal { .. } => {
+                    let app_handle = app_handle.clone();
+                    let conn = conn.clone();
+                    tauri::async_runtime::spawn(async move {
+                        let pairing = app_handle.state::<AppState>().pairing.clone();
+                        // 상대가 시작한 페어링: 확인 코드를 화면에 띄워 사용자 확인을 받도록 알림
+                        if let Some(peer) = pairing.handle_incoming(&conn, incoming).await {
+                            let _ = app_handle.emit("pairing-completed", &peer);
+                        }
+                    });
+                }
+                Command::RequestTransfer(mut request) => {
+                    let _ = transfer::control_stream::finish_header_only(&mut incoming.recv).await;
+                    // 자동 수락은 보낸 쪽이 주장한 값이 아니라 연결의 인증서 지문으로 판단
+                    request.sender_fingerprint = quic::identity::peer_fingerprint(&conn);
+                    tauri::async_runtime::spawn(handle_transfer_request(
+                        app_handle.clone(),
+                        conn.clone(),
+                        incoming.peer_id,
+                        request,
+                    ));
+                }
+                Command::RespondTransfer(response) => {
+                    let _ = transfer::control_stream::finish_header_only(&mut incoming.recv).await;
+                    let approval = app_handle.state::<AppState>().transfer_approval.clone();
+                    if !approval.resolve_offer(response).await {
+                        warn!("대기 중이 아닌 전송 응답 ({})", incoming.peer_id);
+                    }
+                }
+                Command::IdentityProof {
+                    node_id,
+                    fingerprint,
+                    signature,
+                } => {
+                    let _ = transfer::control_stream::finish_header_only(&mut incoming.recv).await;
+                    let state = app_handle.state::<AppState>();
+                    let verification = match state.sas.accept_proof(
+                        &incoming.peer_id,
+                        &conn,
+                        &state.signing_identity.node_id(),
+                        node_id,
+                        fingerprint,
+                        &signature,
+                    ) {
+                        Ok(verification) => verification,
+                        Err(e) => {
+                            warn!("신원 증명 확인 실패 ({}): {}", incoming.peer_id, e);
+                            continue;
+                        }
+                    };
+                    let node_id = verification.node_id.as_deref();
+                    let fingerprint = verification.fingerprint.as_deref();
+                    if !state.contacts.is_trusted(node_id, fingerprint) {
+                        let contact = state.contacts.find_by_keys(node_id, fingerprint);
+                        let _ = app_handle.emit(
+                            "sas-verification-required",
+                            serde_json::json!({
+                                "verification": verification,
+                                "contact": contact,
+                            }),
+                        );
+                    }
+                }
+                other => {
+                    warn!(
+                        "처리할 수 없는 제어 명령 ({}): {:?}",
+                        incoming.peer_id, other
+                    );
+                }
+            }
+        }
+
+        // 연결 종료 → 세션 수신 한도, 인증 문자열 정리
+        let state = app_handle.state::<AppState>();
+        state.receive_quota.end_session(conn.stable_id());
+        state.sas.end(&peer_id, conn.stable_id());
+    });
+}
+
+/// 🆕 연결 직후 이 기기의 노드 ID/인증서 지문을 연결 비밀에 묶어 서명해 전송
+async fn send_identity_proof(app_handle: AppHandle, peer_id: String, conn: quinn::Connection) {
+    let state = app_handle.state::<AppState>();
+    let fingerprint = state
+        .quic_identity
+        .as_ref()
+        .map(|identity| identity.fingerprint());
+    let result = async {
+        state.sas.begin(&peer_id, &conn)?;
+        let proof = quic::sas::identity_proof(&conn, &state.signing_identity, fingerprint)?;
+        let mut send = transfer::control_stream::open(&conn, &proof).await?;
+        send.finish()?;
+        anyhow::Ok(())
+    }
+    .await;
+    if let Err(e) = result {
+        warn!("신원 증명 전송 실패 ({}): {}", peer_id, e);
+    }
+}
+
+/// 피어의 전송 요청 처리 (자동 수락 규칙 확인 후 사용자 승인 대기) 및 응답 전송
+///
+/// 자동 수락되면 `transfer-auto-accepted`, 승인이 필요하면 `transfer-request` 이벤트를 보냅니다.
+/// 🆕 승인 대기 요청은 OS 알림으로도 표시합니다.
+/// 🆕 피어/세션 수신 한도를 넘는 요청은 묻지 않고 거절하며 `transfer-quota-exceeded` 이벤트를 보냅니다.
+async fn handle_transfer_request(
+    app_handle: AppHandle,
+    conn: quinn::Connection,
+    peer_id: String,
+    request: crate::protocol::commands::TransferRequest,
+) {
+    use transfer::file_transfer::{transfer_response, ApprovalOutcome};
+
+    let approval = app_handle.state::<AppState>().transfer_approval.clone();
+    let quota = app_handle.state::<AppState>().receive_quota.clone();
+    let job_id = request.job_id.clone();
+    let mut auto_save_dir = None;
+    // 🆕 연락처에서 온 요청이면 주소를 기록하고 이벤트에 연락처를 포함
+    let contact = request
+        .sender_fingerprint
+        .as_deref()
+        .and_then(|fingerprint| {
+            app_handle
+                .state::<AppState>()
+                .contacts
+                .record_seen(fingerprint, conn.remote_address())
+        });
+
+    // 사용량은 수신 중 실제로 받은 바이트로 집계하고, 여기서는 알린 크기로 미리 확인만 함
+    let outcome = match quota.check(
+        request.sender_fingerprint.as_deref(),
+        conn.stable_id(),
+        request.file_size,
+    ) {
+        Ok(()) => Ok(approval.register_request(request.clone()).await),
+        Err(reason) => Err(reason),
+    };
+
+    let response = match outcome {
+        Err(reason) => {
+            warn!(
+                "📦 수신 한도 초과로 거절: {} ({})",
+                request.file_name, reason
+            );
+            let _ = app_handle.emit(
+                "transfer-quota-exceeded",
+                serde_json::json!({
+                    "peerId": peer_id,
+                    "request": request,
+                    "reason": reason,
+                }),
+            );
+            transfer_response(&job_id, false, Some(reason))
+        }
+        Ok(ApprovalOutcome::AutoAccepted(rule)) => {
+            // 알린 크기와 규칙 한도 중 작은 쪽까지만 받음
+            let max_bytes = rule
+                .max_bytes
+                .map_or(request.file_size, |max| max.min(request.file_size));
+            auto_save_dir = Some((rule.save_dir.clone(), max_bytes));
+            let _ = app_handle.emit(
+                "transfer-auto-accepted",
+                serde_json::json!({
+                    "peerId": peer_id,
+                    "request": request,
+                    "contact": contact,
+                    "ruleId": rule.id,
+                    "saveDir": rule.save_dir,
+                }),
+            );
+            transfer_response(&job_id, true, Some("자동 수락".to_string()))
+        }
+        Ok(ApprovalOutcome::Pending(mut rx)) => {
+            let _ = app_handle.emit(
+                "transfer-request",
+                serde_json::json!({ "peerId": peer_id, "request": request, "contact": contact }),
+            );
+            app_handle.state::<AppState>().notifications.notify_offer(
+                &app_handle,
+                &peer_id,
+                &request,
+            );
+            match tokio::time::timeout(approval.expiry_duration(), rx.recv()).await {
+                Ok(Some(response)) => response,
+                _ => {
+                    approval.expire(&job_id).await;
+                    transfer_response(&job_id, false, Some("승인 시간 초과".to_string()))
+                }
+            }
+        }
+    };
+    let result = async {
+        let mut send =
+            transfer::control_stream::open(&conn, &Command::RespondTransfer(response)).await?;
+        send.finish()?;
+        anyhow::Ok(())
+    }
+    .await;
+    if let Err(e) = result {
+        warn!("전송 응답 전송 실패 ({}): {}", peer_id, e);
+        return;
+    }
+
+    // 🆕 헤드리스 모드에서는 수신을 시작할 UI가 없으므로 직접 수신
+    if let Some((save_dir, max_bytes)) = auto_save_dir {
+        if app_handle.state::<AppState>().headless {
+            headless::receive_auto_accepted(app_handle, conn, peer_id, job_id, save_dir, max_bytes)
+                .await;
+        }
+    }
+}
+
+/// 🆕 감사 로그에 작업의 방향/경로 기록 (감사 로그가 꺼져 있으면 무시)
+fn audit_subject(
+    state: &AppState,
+    job_id: &str,
+    direction: AuditDirection,
+    conn: &quinn::Connection,
+    path: Option<PathBuf>,
+) {
+    state.audit_log.describe(
+        job_id,
+        transfer::audit::AuditSubject {
+            direction,
+            peer_fingerprint: quic::identity::peer_fingerprint(conn),
+            path,
+        },
+    );
+}
+
+/// 피어 연결 조회 (클라이언트로 연결한 피어 우선, 없으면 서버에서 수락한 피어)
+async fn peer_connection(
+    state: &tauri::State<'_, AppState>,
+    peer_id: &str,
+) -> Result<quinn::Connection, AppError> {
+    if let Some(conn) = state.active_connections.read().await.get(peer_id) {
+        return Ok(conn.clone());
+    }
+    state
+        .accepted_connections
+        .read()
+        .await
+        .get(peer_id)
+        .cloned()
+        .ok_or_else(|| AppError::PeerNotConnected {
+            peer_id: peer_id.to_string(),
+        })
+}
+
+/// 송신 시 페이로드 암호기 선택 (`encrypt`가 켜졌으면 페어링 필수)
+fn send_cipher(
+    state: &tauri::State<'_, AppState>,
+    peer_id: &str,
+    encrypt: Option<bool>,
+) -> Result<Option<Arc<transfer::payload_crypto::PayloadCipher>>, AppError> {
+    if !encrypt.unwrap_or(false) {
+        return Ok(None);
+    }
+    state
+        .pairing
+        .cipher(peer_id)
+        .map(Some)
+        .ok_or_else(|| AppError::Crypto(format!("페어링되지 않은 피어입니다: {}", peer_id)))
+}
+
+/// 전송 엔진 에러를 커맨드 에러로 변환 (수신 측 저장 공간 부족은 `INSUFFICIENT_SPACE`)
+fn transfer_error(context: &str, e: anyhow::Error) -> AppError {
+    match e.downcast_ref::<transfer::disk_space::InsufficientSpace>() {
+        Some(space) => AppError::from(*space),
+        None => AppError::Network(format!("{}: {}", context, e)),
+    }
+}
+
+/// 사용자 지정 전송 파라미터 범위 검사 (`None`이면 기본값 사용)
+fn tuning_param(
+    name: &str,
+    value: Option<usize>,
+    min: usize,
+    max: usize,
+) -> Result<Option<usize>, AppError> {
+    match value {
+        Some(v) if !(min..=max).contains(&v) => Err(AppError::InvalidInput(format!(
+            "{}는 {}~{} 범위여야 합니다 (입력: {})",
+            name, min, max, v
+        ))),
+        _ => Ok(value),
+    }
+}
+
+/// 멀티스트림 블록 크기/동시 스트림 상한 검사
+fn multistream_tuning(
+    block_size: Option<usize>,
+    max_concurrent_streams: Option<usize>,
+) -> Result<(Option<usize>, Option<usize>), AppError> {
+    use transfer::multistream::{MAX_BLOCK_SIZE, MIN_BLOCK_SIZE, QUIC_STREAM_LIMIT};
+    use transfer::stream_tuner::MIN_STREAMS;
+    Ok((
+        tuning_param("block_size", block_size, MIN_BLOCK_SIZE, MAX_BLOCK_SIZE)?,
+        tuning_param(
+            "max_concurrent_streams",
+            max_concurrent_streams,
+            MIN_STREAMS,
+            QUIC_STREAM_LIMIT,
+        )?,
+    ))
+}
+
+/// 🆕 피어에게 텍스트/클립보드 내용 전송 (파일 생성 없이)
+///
+/// 클라이언트로 연결한 피어와 서버에서 수락한 피어 모두 가능하며, 메시지 ID를 반환합니다.
+#[tauri::command]
+async fn send_text(
+    peer_id: String,
+    content: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, AppError> {
+    let conn = peer_connection(&state, &peer_id).await?;
+
+    let message_id = transfer::text_share::send_text(&conn, content)
+        .await
+        .map_err(|e| AppError::Network(format!("텍스트 전송 실패: {}", e)))?;
+
+    info!("💬 텍스트 전송 완료: {} ({})", peer_id, message_id);
+    Ok(message_id)
+}
+
+/// 🆕 피어와의 처리량/RTT 측정 (생성 데이터, 디스크 미사용)
+///
+/// `duration`은 방향별 측정 시간(초, 기본 5초, 최대 60초)이며 업로드 후 다운로드를 측정합니다.
+#[tauri::command]
+async fn benchmark_peer(
+    peer_id: String,
+    duration: Option<u64>,
+    state: tauri::State<'_, AppState>,
+) -> Result<transfer::benchmark::BenchmarkReport, AppError> {
+    let conn = peer_connection(&state, &peer_id).await?;
+    let duration = duration
+        .map(std::time::Duration::from_secs)
+        .unwrap_or(transfer::benchmark::DEFAULT_DURATION);
+    if duration.is_zero() {
+        return Err(AppError::InvalidInput(
+            "측정 시간은 1초 이상이어야 합니다".into(),
+        ));
+    }
+
+    state
+        .benchmark
+        .run(&conn, &peer_id, duration)
+        .await
+        .map_err(|e| AppError::Network(format!("대역폭 측정 실패: {}", e)))
+}
+
+/// 🆕 피어에게 전송 요청을 보내고 수락/거절 응답 대기
+///
+/// 피어가 자동 수락 규칙에 이 기기의 지문을 등록해 두었으면 바로 수락됩니다.
+#[tauri::command]
+async fn request_transfer_approval(
+    peer_id: String,
+    job_id: String,
+    file_name: String,
+    file_size: u64,
+    sender_name: Option<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<crate::protocol::commands::TransferResponse, AppError> {
+    let conn = peer_connection(&state, &peer_id).await?;
+    let approval = state.transfer_approval.clone();
+    let request = crate::protocol::commands::TransferRequest {
+        job_id: job_id.clone(),
+        file_name,
+        file_size,
+        sender_name: sender_name.unwrap_or_default(),
+        sender_device: std::env::consts::OS.to_string(),
+        timestamp: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+        sender_fingerprint: None,
+    };
+
+    let mut rx = approval.register_offer(&job_id).await;
+    let sent = async {
+        let mut send =
+            transfer::control_stream::open(&conn, &Command::RequestTransfer(request)).await?;
+        send.finish()?;
+        anyhow::Ok(())
+    }
+    .await;
+    if let Err(e) = sent {
+        approval.cancel_offer(&job_id).await;
+        return Err(AppError::Network(format!("전송 요청 실패: {}", e)));
+    }
+
+    // 수신 측 승인 대기 시간보다 조금 더 기다림
+    let wait = approval.expiry_duration() + std::time::Duration::from_secs(10);
+    match tokio::time::timeout(wait, rx.recv()).await {
+        Ok(Some(response)) => {
+            info!(
+                "📨 전송 요청 응답: {} (approved={}, {:?})",
+                job_id, response.approved, response.reason
+            );
+            Ok(response)
+        }
+        _ => {
+            approval.cancel_offer(&job_id).await;
+            Err(AppError::Timeout(format!(
+                "전송 요청 응답 시간 초과: {}",
+                job_id
+            )))
+        }
+    }
+}
+
+/// 🆕 이 기기의 인증서 지문 (상대 기기의 자동 수락 규칙에 등록)
+#[tauri::command]
+async fn get_device_fingerprint(
+    state: tauri::State<'_, AppState>,
+) -> Result<Option<String>, AppError> {
+    Ok(state
+        .quic_identity
+        .as_ref()
+        .map(|identity| identity.fingerprint()))
+}
+
+/// 🆕 자동 수락 규칙 목록
+#[tauri::command]
+async fn list_auto_accept_rules(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<transfer::auto_accept::AutoAcceptRule>, AppError> {
+    Ok(state.transfer_approval.auto_accept().list())
+}
+
+/// 🆕 자동 수락 규칙 추가/수정 (ID가 비어 있으면 새로 생성)
+#[tauri::command]
+async fn save_auto_accept_rule(
+    rule: transfer::auto_accept::AutoAcceptRule,
+    state: tauri::State<'_, AppState>,
+) -> Result<transfer::auto_accept::AutoAcceptRule, AppError> {
+    state
+        .transfer_approval
+        .auto_accept()
+        .upsert(rule)
+        .map_err(|e| AppError::Io(format!("자동 수락 규칙 저장 실패: {}", e)))
+}
+
+/// 🆕 자동 수락 규칙 삭제
+#[tauri::command]
+async fn remove_auto_accept_rule(
+    rule_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), AppError> {
+    if state.transfer_approval.auto_accept().remove(&rule_id) {
+        Ok(())
+    } else {
+        Err(AppError::NotFound(format!(
+            "규칙을 찾을 수 없습니다: {}",
+            rule_id
+        )))
+    }
+}
+
+/// 🆕 최근 자동 수락 판단 기록
+#[tauri::command]
+async fn get_auto_accept_log(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<transfer::auto_accept::AutoAcceptLogEntry>, AppError> {
+    Ok(state.transfer_approval.auto_accept().log())
+}
+
+/// 🆕 연락처 목록
+#[tauri::command]
+async fn list_contacts(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<contacts::Contact>, AppError> {
+    Ok(state.contacts.list())
+}
+
+/// 🆕 연락처 추가/수정 (ID가 비어 있으면 새로 생성)
+#[tauri::command]
+async fn save_contact(
+    contact: contacts::Contact,
+    state: tauri::State<'_, AppState>,
+) -> Result<contacts::Contact, AppError> {
+    state
+        .contacts
+        .upsert(contact)
+        .map_err(AppError::InvalidInput)
+}
+
+/// 🆕 연락처 삭제 (연락처를 지정한 자동 수락 규칙은 더 이상 적용되지 않음)
+#[tauri::command]
+async fn remove_contact(
+    contact_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), AppError> {
+    if state.contacts.remove(&contact_id) {
+        Ok(())
+    } else {
+        Err(AppError::NotFound(format!(
+            "연락처를 찾을 수 없습니다: {}",
+            contact_id
+        )))
+    }
+}
+
+/// 🆕 연락처에 연결 (연결된 피어 ID 반환, 이후 전송 명령에 사용)
+///
+/// 마지막으로 확인된 주소와, 접속 명부에 등록 중이면 연락처의 현재 후보로 동시에 연결을 시도합니다.
+/// 연락처에 인증서 지문이 있으면 연결 상대의 지문(핸드셰이크 서명으로 검증됨)이 같아야 합니다.
+/// 지문이 없는 연락처는 상대를 확인할 수 없으므로 아무것도 기록하지 않으며,
+/// 지문은 인증 문자열 확인(`confirm_short_auth_string`) 후에만 저장됩니다.
+#[tauri::command]
+async fn connect_contact(
+    contact_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, AppError> {
+    let contact = state
+        .contacts
+        .get(&contact_id)
+        .ok_or_else(|| AppError::NotFound(format!("연락처를 찾을 수 없습니다: {}", contact_id)))?;
+
+    let mut candidates: Vec<SocketAddr> = Vec::new();
+    if let (Some(node_id), Some(bootstrap)) = (&contact.node_id, state.presence.bootstrap()) {
+        match bootstrap::presence::lookup(bootstrap, vec![node_id.clone()]).await {
+            Ok(online) => {
+                for entry in online {
+                    state
+                        .contacts
+                        .record_candidates(&entry.node_id, &entry.candidates);
+                    candidates.extend(entry.candidates);
+                }
+            }
+            Err(e) => warn!("접속 명부 조회 실패 ({}): {}", bootstrap, e),
+        }
+    }
+    for addr in contact.addresses.iter().filter_map(|a| a.parse().ok()) {
+        if !candidates.contains(&addr) {
+            candidates.push(addr);
+        }
+    }
+    if candidates.is_empty() {
+        return Err(AppError::NotFound(format!(
+            "{}의 주소를 알 수 없습니다",
+            contact.nickname
+        )));
+    }
+
+    let (addr, conn) = race_connect(&state, &candidates).await?;
+    match contact.fingerprint {
+        Some(ref expected) => {
+            if quic::identity::peer_fingerprint(&conn).as_ref() != Some(expected) {
+                conn.close(0u32.into(), b"fingerprint mismatch");
+                return Err(AppError::Crypto(format!(
+                    "인증서 지문이 연락처와 다릅니다: {} ({})",
+                    contact.nickname, addr
+                )));
+            }
+            state.contacts.record_seen(expected, addr);
+        }
+        None => info!(
+            "🔐 연락처 {}의 인증서 지문이 없어 상대를 확인하지 못했습니다 (인증 문자열 확인 필요)",
+            contact.nickname
+        ),
+    }
+
+    let peer_id = addr.to_string();
+    register_connection(&state, &peer_id, conn).await;
+    info!("✅ 연락처 {} 연결 성공: {}", contact.nickname, peer_id);
+    Ok(peer_id)
+}
+
+/// 🆕 연결된 피어와 비교할 짧은 인증 문자열 (양쪽 화면의 단어가 같으면 중간자 없음)
+#[tauri::command]
+async fn get_short_auth_string(
+    peer_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<quic::sas::SasVerification, AppError> {
+    let conn = peer_connection(&state, &peer_id).await?;
+    state
+        .sas
+        .begin(&peer_id, &conn)
+        .map_err(|e| AppError::Crypto(format!("인증 문자열 생성 실패: {}", e)))
+}
+
+/// 🆕 인증 문자열이 같음을 확인 → 상대를 신뢰하는 연락처로 표시 (새 상대는 `nickname`으로 추가)
+#[tauri::command]
+async fn confirm_short_auth_string(
+    peer_id: String,
+    nickname: Option<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<contacts::Contact, AppError> {
+    let conn = peer_connection(&state, &peer_id).await?;
+    let verification = state
+        .sas
+        .get(&peer_id)
+        .ok_or_else(|| AppError::NotFound(format!("확인 중인 연결이 없습니다: {}", peer_id)))?;
+    let contact = state
+        .contacts
+        .mark_trusted(
+            verification.node_id,
+            verification.fingerprint,
+            conn.remote_address(),
+            nickname,
+        )
+        .map_err(AppError::InvalidInput)?;
+
+    let _ = state.app_handle.emit(
+        "contact-verified",
+        serde_json::json!({ "peerId": peer_id, "contact": contact }),
+    );
+    Ok(contact)
+}
+
+/// 🆕 인증 문자열이 다름 → 중간자가 있을 수 있으므로 연결 종료
+#[tauri::command]
+async fn reject_short_auth_string(
+    peer_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), AppError> {
+    let conn = peer_connection(&state, &peer_id).await?;
+    conn.close(0u32.into(), b"sas mismatch");
+    state.active_connections.write().await.remove(&peer_id);
+    state.accepted_connections.write().await.remove(&peer_id);
+
+    warn!("🔐 인증 문자열 불일치로 연결 종료: {}", peer_id);
+    Ok(())
+}
+
+/// 🆕 수신 한도와 피어별 오늘 사용량
+#[tauri::command]
+async fn get_receive_quota(
+    state: tauri::State<'_, AppState>,
+) -> Result<transfer::quota::ReceiveQuotaStatus, AppError> {
+    Ok(state.receive_quota.status())
+}
+
+/// 🆕 수신 한도 변경 (None = 제한 없음, 이미 수락한 전송에는 영향 없음)
+#[tauri::command]
+async fn set_receive_quota(
+    limits: transfer::quota::QuotaLimits,
+    state: tauri::State<'_, AppState>,
+) -> Result<transfer::quota::QuotaLimits, AppError> {
+    Ok(state.receive_quota.set_limits(limits))
+}
+
+/// 🆕 앱 전체 처리량 (직접 전송/멀티스트림/Grid/릴레이/UDP 코어 합계와 서브시스템별 내역)
+#[tauri::command]
+async fn get_app_metrics(
+    state: tauri::State<'_, AppState>,
+) -> Result<metrics::AppMetrics, AppError> {
+    Ok(state.metrics.snapshot())
+}
+
+/// 🆕 전송 버퍼 메모리 예산 (전체/사용 중/예산 부족으로 대기한 횟수)
+#[tauri::command]
+async fn get_memory_budget(
+    state: tauri::State<'_, AppState>,
+) -> Result<transfer::memory_budget::MemoryBudgetStatus, AppError> {
+    Ok(state.buffer_budget.status())
+}
+
+/// 🆕 전송 감사 로그 사용 여부
+#[tauri::command]
+async fn get_audit_log_enabled(state: tauri::State<'_, AppState>) -> Result<bool, AppError> {
+    Ok(state.audit_log.is_enabled())
+}
+
+/// 🆕 전송 감사 로그 켜기/끄기
+#[tauri::command]
+async fn set_audit_log_enabled(
+    enabled: bool,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), AppError> {
+    state
+        .audit_log
+        .set_enabled(enabled)
+        .map_err(|e| AppError::Io(format!("감사 로그 설정 저장 실패: {}", e)))
+}
+
+/// 🆕 최근 감사 로그 항목 (기본 100개)
+#[tauri::command]
+async fn get_audit_log(
+    limit: Option<usize>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<transfer::audit::AuditEntry>, AppError> {
+    state
+        .audit_log
+        .recent(limit.unwrap_or(100))
+        .map_err(|e| AppError::Io(format!("감사 로그 읽기 실패: {}", e)))
+}
+
+/// 🆕 감사 로그를 파일로 내보내고 서명/해시 사슬 검증 결과 반환
+#[tauri::command]
+async fn export_audit_log(
+    path: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<transfer::audit::AuditVerification, AppError> {
+    state
+        .audit_log
+        .export(PathBuf::from(path))
+        .await
+        .map_err(|e| AppError::Io(format!("감사 로그 내보내기 실패: {}", e)))
+}
+
+/// 🆕 내보낸 감사 로그 검증 (다른 기기에서 받은 로그도 가능)
+#[tauri::command]
+async fn verify_audit_log(path: String) -> Result<transfer::audit::AuditVerification, AppError> {
+    let data = tokio::fs::read(&path)
+        .await
+        .map_err(|e| AppError::Io(format!("감사 로그를 읽을 수 없습니다: {}", e)))?;
+    Ok(transfer::audit::verify_log(&data))
+}
+
+/// 🆕 알림 종류별 사용 여부
+#[tauri::command]
+async fn get_notification_settings(
+    state: tauri::State<'_, AppState>,
+) -> Result<notifications::NotificationSettings, AppError> {
+    Ok(state.notifications.settings())
+}
+
+/// 🆕 알림 종류별 사용 여부 변경 (앱 데이터 디렉토리에 저장)
+#[tauri::command]
+async fn set_notification_settings(
+    settings: notifications::NotificationSettings,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), AppError> {
+    state.notifications.set_settings(settings);
+    Ok(())
+}
+
+/// 🆕 앱이 없는 LAN 사용자가 브라우저로 받을 수 있는 파일 링크 생성
+///
+/// 기본은 1회 다운로드, 1시간 유효. 링크에는 이 기기의 LAN 주소가 들어갑니다.
+#[tauri::command]
+async fn create_http_share(
+    path: String,
+    options: Option<transfer::http_share::HttpShareOptions>,
+    state: tauri::State<'_, AppState>,
+) -> Result<transfer::http_share::HttpShareLink, AppError> {
+    let host_ip = get_ip_via_udp_probe().ok_or_else(|| {
+        AppError::Network("LAN 주소를 찾을 수 없어 링크를 만들 수 없습니다".to_string())
+    })?;
+    state
+        .http_share
+        .share(PathBuf::from(path), options.unwrap_or_default(), host_ip)
+        .await
+        .map_err(|e| AppError::Io(format!("HTTP 공유 링크 생성 실패: {}", e)))
+}
+
+/// 🆕 유효한 HTTP 공유 링크 목록
+#[tauri::command]
+async fn list_http_shares(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<transfer::http_share::HttpShareLink>, AppError> {
+    Ok(state.http_share.list())
+}
+
+/// 🆕 HTTP 공유 링크 폐기
+#[tauri::command]
+async fn revoke_http_share(
+    share_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), AppError> {
+    if state.http_share.revoke(&share_id) {
+        Ok(())
+    } else {
+        Err(AppError::NotFound(format!(
+            "링크를 찾을 수 없습니다: {}",
+            share_id
+        )))
+    }
+}
+
+/// 🆕 수신 중인 미디어 미리보기 링크 열기 (`<video>`/`<audio>`의 src로 사용)
+///
+/// 단일 스트림 수신(`receive_file`)처럼 앞에서부터 차례로 받는 작업만 지원하며,
+/// 수신 시작 전에 열어 두어도 됩니다. `file_name`은 브라우저의 형식 추정에 쓰입니다.
+#[tauri::command]
+async fn open_media_preview(
+    job_id: String,
+    file_name: Option<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<transfer::media_preview::PreviewLink, AppError> {
+    state
+        .media_preview
+        .open(&job_id, file_name.as_deref())
+        .await
+        .map_err(|e| AppError::Network(format!("미리보기 서버 시작 실패: {}", e)))
+}
+
+/// 🆕 미디어 미리보기 링크 닫기
+#[tauri::command]
+async fn close_media_preview(
+    job_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<bool, AppError> {
+    Ok(state.media_preview.close(&job_id))
+}
+
+/// 🆕 HTTP 공유 서버 중지 (모든 링크 폐기)
+#[tauri::command]
+async fn stop_http_share_server(state: tauri::State<'_, AppState>) -> Result<(), AppError> {
+    state.http_share.stop().await;
+    Ok(())
+}
+
+/// 🆕 tus 업로드 엔드포인트 시작 (이미 실행 중이면 새 토큰으로 다시 시작)
+///
+/// 받은 파일은 `save_dir`에 네이티브 수신과 같은 정책/이벤트로 저장됩니다.
+#[tauri::command]
+async fn start_tus_server(
+    config: transfer::tus::TusConfig,
+    state: tauri::State<'_, AppState>,
+) -> Result<transfer::tus::TusEndpoint, AppError> {
+    let host_ip = get_ip_via_udp_probe().ok_or_else(|| {
+        AppError::Network("LAN 주소를 찾을 수 없어 엔드포인트를 열 수 없습니다".to_string())
+    })?;
+    state
+        .tus
+        .start(config, host_ip)
+        .await
+        .map_err(|e| AppError::Network(format!("tus 엔드포인트 시작 실패: {}", e)))
+}
+
+/// 🆕 실행 중인 tus 엔드포인트
+#[tauri::command]
+async fn get_tus_endpoint(
+    state: tauri::State<'_, AppState>,
+) -> Result<Option<transfer::tus::TusEndpoint>, AppError> {
+    Ok(state.tus.endpoint().await)
+}
+
+/// 🆕 tus 엔드포인트 중지 (끝나지 않은 업로드는 실패 처리)
+#[tauri::command]
+async fn stop_tus_server(state: tauri::State<'_, AppState>) -> Result<(), AppError> {
+    state.tus.stop().await;
+    Ok(())
+}
+
+/// 웹 버전과 연결할 ICE 서버 (진단과 같은 STUN 서버 + 설정된 TURN 서버)
+async fn webrtc_ice_servers(state: &AppState) -> Vec<transfer::web_protocol::IceServer> {
+    let turn_config = match *state.embedded_bootstrap.read().await {
+        Some(ref service) => service.config().read().await.turn_config(),
+        None => None,
+    }
+    .or_else(|| turn::TurnConfig::from_env().ok())
+    .filter(|config| config.is_enabled());
+    let credentials = turn_config.as_ref().and_then(|config| {
+        turn::TurnClient::new(config.clone())
+            .and_then(|client| client.credentials())
+            .map_err(|e| warn!("TURN 자격 증명 생성 실패, STUN만 사용: {}", e))
+            .ok()
+    });
+    let turn = match (&turn_config, credentials) {
+        (Some(config), Some((username, credential))) => Some(transfer::web_protocol::TurnServer {
+            server_url: &config.server_url,
+            tls: config.enable_tls,
+            username,
+            credential,
+        }),
+        _ => None,
+    };
+
+    let stun_servers: Vec<String> = doctor::DEFAULT_STUN_SERVERS
+        .iter()
+        .map(|server| server.to_string())
+        .collect();
+    transfer::web_protocol::ice_servers(&stun_servers, turn)
+}
+
+/// 🆕 웹 버전 피어에게 WebRTC 데이터 채널로 파일 전송
+///
+/// 반환한 세션 ID로 `webrtc-signal` 이벤트의 offer/candidate가 나오며, 프론트엔드가 시그널링 서버로
+/// 중계하고 상대의 answer/candidate를 `webrtc_signal`로 돌려줍니다. 세션 ID는 전송 작업 ID와 같습니다.
+#[tauri::command]
+async fn webrtc_send_file(
+    peer_id: String,
+    file_path: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, AppError> {
+    let ice_servers = webrtc_ice_servers(&state).await;
+    state
+        .webrtc
+        .send_file(&peer_id, PathBuf::from(file_path), ice_servers)
+        .await
+        .map_err(|e| AppError::Network(format!("WebRTC 전송 시작 실패: {}", e)))
+}
+
+/// 🆕 웹 버전 피어로부터 WebRTC 데이터 채널 수신 대기 (상대의 offer는 `webrtc_signal`로 전달)
+#[tauri::command]
+async fn webrtc_receive(
+    peer_id: String,
+    save_dir: String,
+    overwrite_policy: Option<transfer::OverwritePolicy>,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, AppError> {
+    let ice_servers = webrtc_ice_servers(&state).await;
+    state
+        .webrtc
+        .receive(
+            &peer_id,
+            PathBuf::from(save_dir),
+            overwrite_policy.unwrap_or_default(),
+            ice_servers,
+        )
+        .await
+        .map_err(|e| AppError::Network(format!("WebRTC 수신 준비 실패: {}", e)))
+}
+
+/// 🆕 시그널링 서버로 받은 상대의 offer/answer/candidate 적용
+#[tauri::command]
+async fn webrtc_signal(
+    session_id: String,
+    signal: transfer::web_protocol::WebRtcSignal,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), AppError> {
+    state
+        .webrtc
+        .signal(&session_id, signal)
+        .await
+        .map_err(|e| AppError::Network(format!("WebRTC 시그널 처리 실패: {}", e)))
+}
+
+/// 🆕 WebRTC 세션 종료 (진행 중인 전송은 취소)
+#[tauri::command]
+async fn webrtc_close(
+    session_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), AppError> {
+    if state.webrtc.close(&session_id) {
+        Ok(())
+    } else {
+        Err(AppError::NotFound(format!(
+            "WebRTC 세션을 찾을 수 없습니다: {}",
+            session_id
+        )))
+    }
+}
+
+/// 🆕 로컬 폴더를 감시하며 바뀐 파일을 피어의 같은 `sync_id` 폴더로 푸시
+///
+/// 수신 측은 먼저 `accept_folder_sync`로 폴더를 등록해야 합니다.
+#[tauri::command]
+async fn start_folder_sync(
+    peer_id: String,
+    sync_id: String,
+    local_dir: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), AppError> {
+    let conn = peer_connection(&state, &peer_id).await?;
+    state
+        .folder_sync
+        .start_outgoing(&sync_id, &peer_id, conn, PathBuf::from(local_dir))
+        .map_err(|e| AppError::Io(format!("폴더 동기화 시작 실패: {}", e)))
+}
+
+/// 🆕 피어가 `sync_id`로 보내는 변경을 받을 폴더 등록 (기본 충돌 정책: 최신 수정본 유지)
+#[tauri::command]
+async fn accept_folder_sync(
+    sync_id: String,
+    local_dir: String,
+    conflict_policy: Option<sync::ConflictPolicy>,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), AppError> {
+    state
+        .folder_sync
+        .accept_incoming(
+            &sync_id,
+            PathBuf::from(local_dir),
+            conflict_policy.unwrap_or_default(),
+        )
+        .map_err(|e| AppError::Io(format!("폴더 동기화 수신 등록 실패: {}", e)))
+}
+
+/// 🆕 폴더 동기화 중지 (송신/수신 모두)
+#[tauri::command]
+async fn stop_folder_sync(
+    sync_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), AppError> {
+    if state.folder_sync.stop(&sync_id) {
+        info!("🛑 폴더 동기화 중지: {}", sync_id);
+        Ok(())
+    } else {
+        Err(AppError::NotFound(format!(
+            "동기화를 찾을 수 없습니다: {}",
+            sync_id
+        )))
+    }
+}
+
+/// 🆕 폴더 동기화 목록
+#[tauri::command]
+async fn list_folder_syncs(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<sync::manager::SyncInfo>, AppError> {
+    Ok(state.folder_sync.list())
+}
+
+/// QUIC을 통해 파일 전송 시작 (Sender - 클라이언트로 연결한 경우)
+///
+/// `chunk_size`는 한 번에 읽어 보내는 크기 (64KB~16MB, 없으면 1MB)
+#[tauri::command]
+async fn send_file_to_peer(
+    peer_id: String,
+    file_path: String,
+    job_id: String,
+    encrypt: Option<bool>,
+    chunk_size: Option<usize>,
+    state: tauri::State<'_, AppState>,
+) -> Result<u64, AppError> {
+    let chunk_size = tuning_param(
+        "chunk_size",
+        chunk_size,
+        transfer::file_transfer::MIN_CHUNK_SIZE,
+        transfer::file_transfer::MAX_CHUNK_SIZE,
+    )?;
+    let cipher = send_cipher(&state, &peer_id, encrypt)?;
+    // 1. Scope를 제한하여 Lock 시간을 최소화하고 Connection을 복제(Clone)합니다.
+    let conn = {
+        let connections = state.active_connections.read().await;
+        connections
+            .get(&peer_id)
+            .ok_or_else(|| AppError::PeerNotConnected {
+                peer_id: peer_id.to_string(),
+            })?
+            .clone() // Quinn Connection은 내부적으로 Arc이므로 Clone 가능
+    }; // 여기서 read lock이 해제됩니다.
+
+    info!("📤 전송 시작: {} -> {}", file_path, peer_id);
+
+    // 2. 별도의 채널 생성
+    let (tx, mut rx) = mpsc::channel::<TransferProgress>(100);
+    let control = state
+        .transfer_registry
+        .register(&job_id, &peer_id, TransferKind::File)?;
+    let mut engine = FileTransferEngine::new();
+    engine.set_progress_channel(tx);
+    engine.set_job_control(control);
+    engine.set_cipher(cipher.clone());
+    engine.set_memory_budget(state.buffer_budget.clone());
+    if let Some(size) = chunk_size {
+        engine.set_chunk_size(size);
+    }
+
+    let registry = state.transfer_registry.clone();
+
+    // 3. 진행률을 레지스트리에 반영 (이벤트는 레지스트리가 발송)
+    tauri::async_runtime::spawn(async move {
+        while let Some(progress) = rx.recv().await {
+            registry.update_progress(
+                &progress.job_id,
+                progress.bytes_transferred,
+                progress.total_bytes,
+                progress.speed_bps,
+            );
+        }
+    });
+
+    let path = PathBuf::from(&file_path);
+    audit_subject(
+        &state,
+        &job_id,
+        AuditDirection::Send,
+        &conn,
+        Some(path.clone()),
+    );
+
+    // conn을 소유권 이동으로 넘겨도 원본 HashMap에는 영향 없음 (Clone 했으므로)
+    let result = engine
+        .send_file(&conn, path, &job_id)
+        .await
+        .map_err(|e| transfer_error("파일 전송 실패", e));
+    state
+        .transfer_registry
+        .finish(&job_id, result.as_ref().map(|_| ()).map_err(|e| e.clone()));
+    let bytes_sent = result?;
+
+    info!("✅ 파일 전송 완료: {} bytes to {}", bytes_sent, peer_id);
+    Ok(bytes_sent)
+}
+
+/// 🆕 서버에서 수락한 연결로 파일 전송 (Sender - 서버 역할)
+///
+/// `chunk_size`는 `send_file_to_peer`와 같음
+#[tauri::command]
+async fn send_file_to_accepted_peer(
+    peer_id: String,
+    file_path: String,
+    job_id: String,
+    encrypt: Option<bool>,
+    chunk_size: Option<usize>,
+    state: tauri::State<'_, AppState>,
+) -> Result<u64, AppError> {
+    let chunk_size = tuning_param(
+        "chunk_size",
+        chunk_size,
+        transfer::file_transfer::MIN_CHUNK_SIZE,
+        transfer::file_transfer::MAX_CHUNK_SIZE,
+    )?;
+    let cipher = send_cipher(&state, &peer_id, encrypt)?;
+    // 1. Scope를 제한하여 Lock 시간을 최소화하고 Connection을 복제(Clone)합니다.
+    let conn = {
+        let connections = state.accepted_connections.read().await;
+        connections
+            .get(&peer_id)
+            .ok_or_else(|| AppError::PeerNotConnected {
+                peer_id: peer_id.to_string(),
+            })?
+            .clone() // Quinn Connection은 내부적으로 Arc이므로 Clone 가능
+    }; // 여기서 read lock이 해제됩니다.
+
+    info!("📤 전송 시작: {} -> {}", file_path, peer_id);
+
+    // 2. 별도의 채널 생성
+    let (tx, mut rx) = mpsc::channel::<TransferProgress>(100);
+    let control = state
+        .transfer_registry
+        .register(&job_id, &peer_id, TransferKind::File)?;
+    let mut engine = FileTransferEngine::new();
+    engine.set_progress_channel(tx);
+    engine.set_job_control(control);
+    engine.set_cipher(cipher.clone());
+    engine.set_memory_budget(state.buffer_budget.clone());
+    if let Some(size) = chunk_size {
+        engine.set_chunk_size(size);
+    }
+
+    let registry = state.transfer_registry.clone();
+
+    // 3. 진행률을 레지스트리에 반영 (이벤트는 레지스트리가 발송)
+    tauri::async_runtime::spawn(async move {
+        while let Some(progress) = rx.recv().await {
+            registry.update_progress(
+                &progress.job_id,
+                progress.bytes_transferred,
+                progress.total_bytes,
+                progress.speed_bps,
+            );
+        }
+    });
+
+    let path = PathBuf::from(&file_path);
+    audit_subject(
+        &state,
+        &job_id,
+        AuditDirection::Send,
+        &conn,
+        Some(path.clone()),
+    );
+
+    // conn을 소유권 이동으로 넘겨도 원본 HashMap에는 영향 없음 (Clone 했으므로)
+    let result = engine
+        .send_file(&conn, path, &job_id)
+        .await
+        .map_err(|e| transfer_error("파일 전송 실패", e));
+    state
+        .transfer_registry
+        .finish(&job_id, result.as_ref().map(|_| ()).map_err(|e| e.clone()));
+    let bytes_sent = result?;
+
+    info!("✅ 파일 전송 완료: {} bytes to {}", bytes_sent, peer_id);
+    Ok(bytes_sent)
+}
+
+/// 🆕 수락된 연결 목록 조회
+#[tauri::command]
+async fn get_accepted_peers(state: tauri::State<'_, AppState>) -> Result<Vec<String>, AppError> {
+    let connections = state.accepted_connections.read().await;
+    Ok(connections.keys().cloned().collect())
+}
+
+/// QUIC을 통해 파일 수신 대기 (Receiver)
+#[tauri::command]
+async fn receive_file_from_peer(
+    peer_id: String,
+    save_dir: String,
+    job_id: String,
+    overwrite_policy: Option<transfer::OverwritePolicy>,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, AppError> {
+    // 1. Scope를 제한하여 Lock 시간을 최소화하고 Connection을 복제(Clone)합니다.
+    let conn = {
+        let connections = state.active_connections.read().await;
+        connections
+            .get(&peer_id)
+            .ok_or_else(|| AppError::PeerNotConnected {
+                peer_id: peer_id.to_string(),
+            })?
+            .clone() // Quinn Connection은 내부적으로 Arc이므로 Clone 가능
+    }; // 여기서 read lock이 해제됩니다.
 
-    let _ = state.app_handle.emit(
-        "transfer-complete",
-        serde_json::json!({
-            "jobId": job_id,
-            "savedPath": result_str,
-            "peerId": peer_id,
-        }),
-    );
+    receive_file_on_connection(
+        &state,
+        &conn,
+        &peer_id,
+        &save_dir,
+        &job_id,
+        overwrite_policy,
+        None,
+    )
+    .await
+}
+
+/// 🆕 피어에게서 파일을 받아 저장 대상(S3 호환 버킷 등)에 보관
+///
+/// 임시 폴더로 받은 뒤(`job_id`) 저장 대상으로 올리고(`<job_id>-store`) 임시 파일을 지웁니다.
+/// 반환값은 저장 위치 (`s3://<bucket>/<key>` 또는 로컬 경로)입니다.
+#[tauri::command]
+async fn receive_file_to_storage(
+    peer_id: String,
+    job_id: String,
+    target: transfer::storage::StorageTarget,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, AppError> {
+    let conn = {
+        let connections = state.active_connections.read().await;
+        connections
+            .get(&peer_id)
+            .ok_or_else(|| AppError::PeerNotConnected {
+                peer_id: peer_id.to_string(),
+            })?
+            .clone()
+    };
+
+    let staging = std::env::temp_dir().join("ponswarp-staging").join(&job_id);
+    let received = receive_file_on_connection(
+        &state,
+        &conn,
+        &peer_id,
+        &staging.to_string_lossy(),
+        &job_id,
+        Some(transfer::OverwritePolicy::Overwrite),
+        None,
+    )
+    .await;
+    let result = match received {
+        Ok(path) => {
+            let path = PathBuf::from(path);
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| job_id.clone());
+            transfer::storage::store_file(
+                &target,
+                &path,
+                &name,
+                &state.transfer_registry,
+                &format!("{}-store", job_id),
+            )
+            .await
+        }
+        Err(e) => Err(e),
+    };
+    if let Err(e) = tokio::fs::remove_dir_all(&staging).await {
+        warn!("임시 수신 폴더 삭제 실패 {:?}: {}", staging, e);
+    }
+    result
+}
+
+/// 🆕 로컬 파일을 저장 대상(S3 호환 버킷 등)으로 전송
+///
+/// 진행률/일시정지/취소는 `job_id` 작업으로 다른 전송과 같이 동작합니다.
+#[tauri::command]
+async fn send_file_to_storage(
+    file_path: String,
+    target: transfer::storage::StorageTarget,
+    job_id: String,
+    object_name: Option<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, AppError> {
+    let path = PathBuf::from(&file_path);
+    let name = match object_name {
+        Some(name) => name,
+        None => path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .ok_or_else(|| AppError::InvalidInput(format!("잘못된 파일 경로: {}", file_path)))?,
+    };
+    transfer::storage::store_file(&target, &path, &name, &state.transfer_registry, &job_id).await
+}
+
+/// 🆕 작업 매니페스트(블록 맵, 해시)를 파일로 내보내기
+///
+/// 멀티스트림 송신 작업이나 Grid Seed 작업, 가져온 매니페스트를 대상으로 합니다.
+/// 멀티스트림은 내보낼 때 원본 파일을 읽어 블록 해시를 계산합니다.
+#[tauri::command]
+async fn export_job_manifest(
+    job_id: String,
+    path: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<transfer::job_manifest::JobManifest, AppError> {
+    state
+        .job_manifests
+        .export(&job_id, &PathBuf::from(&path))
+        .await
+        .map_err(|e| AppError::Io(format!("매니페스트 내보내기 실패: {}", e)))
+}
+
+/// 🆕 내보낸 작업 매니페스트 가져오기 (검증/재시드에 job_id로 사용)
+#[tauri::command]
+async fn import_job_manifest(
+    path: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<transfer::job_manifest::JobManifest, AppError> {
+    state
+        .job_manifests
+        .import(&PathBuf::from(&path))
+        .await
+        .map_err(|e| AppError::InvalidInput(format!("매니페스트 가져오기 실패: {}", e)))
+}
+
+/// 🆕 로컬 파일이 매니페스트의 데이터셋과 같은지 블록 단위로 검증
+#[tauri::command]
+async fn verify_job_manifest(
+    job_id: String,
+    file_path: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<transfer::job_manifest::ManifestVerification, AppError> {
+    let manifest = state.job_manifests.get(&job_id).ok_or_else(|| {
+        AppError::NotFound(format!(
+            "매니페스트를 찾을 수 없습니다 (먼저 내보내거나 가져오세요): {}",
+            job_id
+        ))
+    })?;
+    tokio::task::spawn_blocking(move || {
+        transfer::job_manifest::verify_file(&manifest.dataset, &PathBuf::from(&file_path))
+    })
+    .await
+    .map_err(|e| AppError::Internal(e.to_string()))?
+    .map_err(|e| AppError::Io(format!("매니페스트 검증 실패: {}", e)))
+}
+
+/// 🆕 주어진 연결에서 파일 하나 수신 (레지스트리 등록, 진행률/완료 반영)
+async fn receive_file_on_connection(
+    state: &AppState,
+    conn: &quinn::Connection,
+    peer_id: &str,
+    save_dir: &str,
+    job_id: &str,
+    overwrite_policy: Option<transfer::OverwritePolicy>,
+    max_bytes: Option<u64>,
+) -> Result<String, AppError> {
+    info!("📥 수신 시작: {} -> {}", peer_id, save_dir);
+
+    // 2. 별도의 채널 생성
+    let (tx, mut rx) = mpsc::channel::<TransferProgress>(100);
+    let control = state
+        .transfer_registry
+        .register(job_id, peer_id, TransferKind::File)?;
+    let mut engine = FileTransferEngine::new();
+    engine.set_progress_channel(tx);
+    engine.set_job_control(control);
+    engine.set_overwrite_policy(overwrite_policy.unwrap_or_default());
+    engine.set_cipher(state.pairing.cipher(peer_id));
+    engine.set_memory_budget(state.buffer_budget.clone());
+    engine.set_preview(state.media_preview.source(job_id));
+    engine.set_quota(state.receive_quota.meter(
+        quic::identity::peer_fingerprint(conn).as_deref(),
+        conn.stable_id(),
+    ));
+    if let Some(max_bytes) = max_bytes {
+        engine.set_max_bytes(max_bytes);
+    }
+
+    let registry = state.transfer_registry.clone();
+
+    // 3. 진행률을 레지스트리에 반영 (이벤트는 레지스트리가 발송)
+    tauri::async_runtime::spawn(async move {
+        while let Some(progress) = rx.recv().await {
+            registry.update_progress(
+                &progress.job_id,
+                progress.bytes_transferred,
+                progress.total_bytes,
+                progress.speed_bps,
+            );
+        }
+    });
+
+    let save_path = PathBuf::from(save_dir);
+
+    // conn을 소유권 이동으로 넘겨도 원본 HashMap에는 영향 없음 (Clone 했으므로)
+    let result = engine
+        .receive_file(conn, save_path, job_id)
+        .await
+        .map_err(|e| transfer_error("파일 수신 실패", e));
+    state.media_preview.finish(job_id);
+    if let Ok((path, action)) = &result {
+        state.transfer_registry.set_action(job_id, action.as_str());
+        audit_subject(
+            state,
+            job_id,
+            AuditDirection::Receive,
+            conn,
+            Some(path.clone()),
+        );
+    }
+    state
+        .transfer_registry
+        .finish(job_id, result.as_ref().map(|_| ()).map_err(|e| e.clone()));
+    let (result_path, _) = result?;
+
+    let result_str = result_path.to_string_lossy().to_string();
 
     info!("✅ 파일 수신 완료: {:?}", result_path);
     Ok(result_str)
@@ -549,7 +2591,10 @@ async fn receive_file_from_peer(
 
 /// 피어 연결 해제
 #[tauri::command]
-async fn disconnect_peer(peer_id: String, state: tauri::State<'_, AppState>) -> Result<(), String> {
+async fn disconnect_peer(
+    peer_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), AppError> {
     // 1. Active 연결 확인
     let mut active = state.active_connections.write().await;
     if let Some(conn) = active.remove(&peer_id) {
@@ -570,22 +2615,27 @@ async fn disconnect_peer(peer_id: String, state: tauri::State<'_, AppState>) ->
 }
 
 /// 전송 상태 조회
+///
+/// `job_id`가 있으면 해당 작업의 스냅샷, 없으면 전체 작업 목록
 #[tauri::command]
 async fn get_file_transfer_state(
+    job_id: Option<String>,
     state: tauri::State<'_, AppState>,
-) -> Result<serde_json::Value, String> {
-    let transfer = state.file_transfer.read().await;
-
-    if let Some(ref engine) = *transfer {
-        let current_state = engine.get_state().await;
-        Ok(serde_json::json!({
-            "state": format!("{:?}", current_state),
-        }))
-    } else {
-        Ok(serde_json::json!({
-            "state": "Idle",
-        }))
+) -> Result<serde_json::Value, AppError> {
+    let registry = &state.transfer_registry;
+
+    if let Some(job_id) = job_id {
+        let snapshot = registry
+            .snapshot(&job_id)
+            .ok_or_else(|| AppError::NotFound(format!("작업을 찾을 수 없습니다: {}", job_id)))?;
+        return serde_json::to_value(snapshot)
+            .map_err(|e| AppError::Internal(format!("상태 직렬화 실패: {}", e)));
     }
+
+    Ok(serde_json::json!({
+        "state": if registry.has_active() { "Transferring" } else { "Idle" },
+        "jobs": registry.list(),
+    }))
 }
 
 /// 🆕 파일 다이얼로그 열기
@@ -594,7 +2644,7 @@ async fn open_file_dialog(
     multiple: bool,
     directory: bool,
     window: tauri::Window,
-) -> Result<Option<Vec<String>>, String> {
+) -> Result<Option<Vec<String>>, AppError> {
     use tauri_plugin_dialog::DialogExt;
 
     if directory {
@@ -606,7 +2656,7 @@ async fn open_file_dialog(
 
         let folder_path = rx
             .await
-            .map_err(|e| format!("폴더 선택 채널 오류: {}", e))?;
+            .map_err(|e| AppError::Internal(format!("폴더 선택 채널 오류: {}", e)))?;
 
         match folder_path {
             Some(path) => Ok(Some(vec![path.to_string()])),
@@ -622,7 +2672,7 @@ async fn open_file_dialog(
 
             let file_paths = rx
                 .await
-                .map_err(|e| format!("파일 선택 채널 오류: {}", e))?;
+                .map_err(|e| AppError::Internal(format!("파일 선택 채널 오류: {}", e)))?;
 
             match file_paths {
                 Some(paths) => Ok(Some(paths.into_iter().map(|p| p.to_string()).collect())),
@@ -636,7 +2686,7 @@ async fn open_file_dialog(
 
             let file_path = rx
                 .await
-                .map_err(|e| format!("파일 선택 채널 오류: {}", e))?;
+                .map_err(|e| AppError::Internal(format!("파일 선택 채널 오류: {}", e)))?;
 
             match file_path {
                 Some(path) => Ok(Some(vec![path.to_string()])),
@@ -648,7 +2698,7 @@ async fn open_file_dialog(
 
 /// 🆕 파일 메타데이터 조회
 #[tauri::command]
-async fn get_file_metadata(path: String) -> Result<serde_json::Value, String> {
+async fn get_file_metadata(path: String) -> Result<serde_json::Value, AppError> {
     use std::fs;
     use std::path::Path;
 
@@ -714,113 +2764,605 @@ async fn get_file_metadata(path: String) -> Result<serde_json::Value, String> {
 // --- 멀티스트림 고속 전송 Commands ---
 
 /// 멀티스트림으로 파일 전송 (TB급 최적화)
+///
+/// `transport`가 `udp`면 데이터를 신뢰성 UDP로 전송 (통제된 LAN 전용)
+/// `max_rate_bps`는 UDP 모드의 페이싱 상한 (없으면 손실 기반 자동 조절)
+/// `block_size`(256KB~16MB, 없으면 파일 크기에 맞춰 자동)와
+/// `max_concurrent_streams`(동시 스트림 상한, 없으면 96)는 QUIC 모드,
+/// `chunk_size`(데이터그램당 바이트, 없으면 8192)는 UDP 모드에만 적용
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 async fn send_file_multistream(
     peer_id: String,
     file_path: String,
     job_id: String,
+    transport: Option<TransferTransport>,
+    max_rate_bps: Option<u64>,
+    encrypt: Option<bool>,
+    block_size: Option<usize>,
+    max_concurrent_streams: Option<usize>,
+    chunk_size: Option<usize>,
     state: tauri::State<'_, AppState>,
-) -> Result<u64, String> {
+) -> Result<u64, AppError> {
+    let transport = transport.unwrap_or_default();
+    if encrypt.unwrap_or(false) && transport == TransferTransport::Udp {
+        return Err(AppError::Unsupported(
+            "UDP 전송은 페이로드 암호화를 지원하지 않습니다".into(),
+        ));
+    }
+    let (block_size, max_concurrent_streams) =
+        multistream_tuning(block_size, max_concurrent_streams)?;
+    let chunk_size = tuning_param(
+        "chunk_size",
+        chunk_size,
+        transfer::reliable_udp::MIN_CHUNK_SIZE,
+        transfer::udp_core::MAX_CHUNK_DATA,
+    )?;
+    match transport {
+        TransferTransport::Quic if chunk_size.is_some() => {
+            return Err(AppError::Unsupported(
+                "chunk_size는 UDP 전송에만 적용됩니다".into(),
+            ));
+        }
+        TransferTransport::Udp if block_size.is_some() || max_concurrent_streams.is_some() => {
+            return Err(AppError::Unsupported(
+                "block_size/max_concurrent_streams는 QUIC 멀티스트림 전송에만 적용됩니다".into(),
+            ));
+        }
+        _ => {}
+    }
+    let cipher = send_cipher(&state, &peer_id, encrypt)?;
     // 1. Scope를 제한하여 Lock 시간을 최소화하고 Connection을 복제(Clone)합니다.
     let conn = {
         let connections = state.active_connections.read().await;
         connections
             .get(&peer_id)
-            .ok_or_else(|| format!("피어 {}에 대한 연결이 없습니다.", peer_id))?
+            .ok_or_else(|| AppError::PeerNotConnected {
+                peer_id: peer_id.to_string(),
+            })?
             .clone() // Quinn Connection은 내부적으로 Arc이므로 Clone 가능
     }; // 여기서 read lock이 해제됩니다.
 
-    info!("🚀 멀티스트림 전송 시작: {} -> {}", file_path, peer_id);
+    info!(
+        "🚀 멀티스트림 전송 시작 ({:?}): {} -> {}",
+        transport, file_path, peer_id
+    );
 
+    let control = state
+        .transfer_registry
+        .register(&job_id, &peer_id, TransferKind::Multistream)?;
     let (tx, mut rx) = mpsc::channel::<MultiStreamProgress>(100);
 
-    let sender = MultiStreamSender::new(conn)
-        .with_block_size(8 * 1024 * 1024) // 8MB 블록
-        .with_max_concurrent(32) // 32개 동시 스트림
-        .with_progress_channel(tx);
-
-    // 진행률 이벤트 전송
-    let app_handle = state.app_handle.clone();
+    // 진행률을 레지스트리에 반영 (이벤트는 레지스트리가 발송)
+    let registry = state.transfer_registry.clone();
     tauri::async_runtime::spawn(async move {
         while let Some(progress) = rx.recv().await {
-            let _ = app_handle.emit("multistream-progress", &progress);
+            registry.update_progress(
+                &progress.job_id,
+                progress.acknowledged_bytes,
+                progress.total_bytes,
+                progress.speed_bps,
+            );
         }
     });
 
     let path = PathBuf::from(&file_path);
-    let bytes_sent = sender
-        .send_file(path, &job_id)
-        .await
-        .map_err(|e| format!("멀티스트림 전송 실패: {}", e))?;
-
-    let _ = state.app_handle.emit(
-        "multistream-complete",
-        serde_json::json!({
-            "jobId": job_id,
-            "bytesSent": bytes_sent,
-            "peerId": peer_id,
-        }),
+    audit_subject(
+        &state,
+        &job_id,
+        AuditDirection::Send,
+        &conn,
+        Some(path.clone()),
     );
+    let result = match transport {
+        TransferTransport::Quic => {
+            // 자동 조절 상한 (QUIC 스트림 한도 128 이내)
+            let mut sender = MultiStreamSender::new(conn)
+                .with_max_concurrent(max_concurrent_streams.unwrap_or(96))
+                .with_progress_channel(tx)
+                .with_job_control(control)
+                .with_cipher(cipher)
+                .with_manifest_store(state.job_manifests.clone())
+                .with_memory_budget(state.buffer_budget.clone());
+            if let Some(size) = block_size {
+                sender = sender.with_block_size(size);
+            }
+            sender
+                .send_file(path, &job_id)
+                .await
+                .map_err(|e| AppError::Network(format!("멀티스트림 전송 실패: {}", e)))
+        }
+        // UDP 엔진은 제어 핸들이 없으므로 취소 시 작업 자체를 중단 (일시정지는 미지원)
+        TransferTransport::Udp => {
+            let mut sender = ReliableUdpSender::new(conn)
+                .with_max_rate(max_rate_bps)
+                .with_progress_channel(tx);
+            if let Some(size) = chunk_size {
+                sender = sender.with_chunk_size(size);
+            }
+            if let Some(count) = udp_socket_count(&state).await {
+                sender = sender.with_socket_count(count);
+            }
+            tokio::select! {
+                result = sender.send_file(path, &job_id) => result.map_err(|e| AppError::Network(format!("UDP 전송 실패: {}", e))),
+                _ = control.cancelled() => Err(AppError::Cancelled("UDP 전송 실패: 사용자에 의해 취소됨".into())),
+            }
+        }
+    };
+    state
+        .transfer_registry
+        .finish(&job_id, result.as_ref().map(|_| ()).map_err(|e| e.clone()));
+    let bytes_sent = result?;
 
     info!("✅ 멀티스트림 전송 완료: {} bytes", bytes_sent);
     Ok(bytes_sent)
 }
 
 /// 멀티스트림으로 파일 수신
+///
+/// `transport`는 송신측과 같아야 함
+/// 🆕 `overwrite_policy`는 UDP 수신에 적용 (기본: 덮어씀)
 #[tauri::command]
 async fn receive_file_multistream(
     peer_id: String,
     save_dir: String,
     job_id: String,
+    transport: Option<TransferTransport>,
+    overwrite_policy: Option<transfer::OverwritePolicy>,
     state: tauri::State<'_, AppState>,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
     // 1. Scope를 제한하여 Lock 시간을 최소화하고 Connection을 복제(Clone)합니다.
     let conn = {
         let connections = state.active_connections.read().await;
         connections
             .get(&peer_id)
-            .ok_or_else(|| format!("피어 {}에 대한 연결이 없습니다.", peer_id))?
+            .ok_or_else(|| AppError::PeerNotConnected {
+                peer_id: peer_id.to_string(),
+            })?
             .clone() // Quinn Connection은 내부적으로 Arc이므로 Clone 가능
     }; // 여기서 read lock이 해제됩니다.
 
-    info!("📥 멀티스트림 수신 대기: {}", peer_id);
+    let transport = transport.unwrap_or_default();
+    info!("📥 멀티스트림 수신 대기 ({:?}): {}", transport, peer_id);
+
+    let control = state
+        .transfer_registry
+        .register(&job_id, &peer_id, TransferKind::Multistream)?;
+    let (tx, mut rx) = mpsc::channel::<MultiStreamProgress>(100);
+
+    // 진행률을 레지스트리에 반영 (이벤트는 레지스트리가 발송)
+    let registry = state.transfer_registry.clone();
+    tauri::async_runtime::spawn(async move {
+        while let Some(progress) = rx.recv().await {
+            registry.update_progress(
+                &progress.job_id,
+                progress.acknowledged_bytes,
+                progress.total_bytes,
+                progress.speed_bps,
+            );
+        }
+    });
+
+    let save_dir = PathBuf::from(&save_dir);
+    let audit_conn = conn.clone();
+    let result = match transport {
+        TransferTransport::Quic => MultiStreamReceiver::new(conn, save_dir)
+            .with_progress_channel(tx)
+            .with_job_control(control)
+            .with_cipher(state.pairing.cipher(&peer_id))
+            .with_memory_budget(state.buffer_budget.clone())
+            .receive_file(&job_id)
+            .await
+            .map_err(|e| AppError::Network(format!("멀티스트림 수신 실패: {}", e))),
+        TransferTransport::Udp => {
+            let mut receiver = ReliableUdpReceiver::new(conn, save_dir)
+                .with_overwrite_policy(overwrite_policy.unwrap_or_default())
+                .with_progress_channel(tx);
+            if let Some(count) = udp_socket_count(&state).await {
+                receiver = receiver.with_socket_count(count);
+            }
+            tokio::select! {
+                result = receiver.receive_file(&job_id) => result.map_err(|e| AppError::Network(format!("UDP 수신 실패: {}", e))),
+                _ = control.cancelled() => Err(AppError::Cancelled("UDP 수신 실패: 사용자에 의해 취소됨".into())),
+            }
+        }
+    };
+    if let Ok(path) = &result {
+        audit_subject(
+            &state,
+            &job_id,
+            AuditDirection::Receive,
+            &audit_conn,
+            Some(path.clone()),
+        );
+    }
+    state
+        .transfer_registry
+        .finish(&job_id, result.as_ref().map(|_| ()).map_err(|e| e.clone()));
+    let result_path = result?;
+
+    let result_str = result_path.to_string_lossy().to_string();
+
+    info!("✅ 멀티스트림 수신 완료: {:?}", result_path);
+    Ok(result_str)
+}
+
+/// 🆕 여러 피어에게 같은 파일들을 동시에 전송 (1:N 브로드캐스트)
+///
+/// 파일 읽기(mmap)는 모든 대상이 공유하고, 진행률은 대상별 작업(`<job_id>@<peer_id>`)으로 보고됩니다.
+/// 한 대상이 실패해도 나머지는 계속되며, 결과에 대상별 성공/실패가 담깁니다.
+/// `block_size`/`max_concurrent_streams`는 `send_file_multistream`과 같음 (대상별 적용)
+#[tauri::command]
+async fn send_files_to_peers(
+    peer_ids: Vec<String>,
+    paths: Vec<String>,
+    job_id: String,
+    block_size: Option<usize>,
+    max_concurrent_streams: Option<usize>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<transfer::broadcast::DestinationResult>, AppError> {
+    let (block_size, max_concurrent_streams) =
+        multistream_tuning(block_size, max_concurrent_streams)?;
+    let mut destinations = Vec::with_capacity(peer_ids.len());
+    for peer_id in peer_ids {
+        let conn = peer_connection(&state, &peer_id).await?;
+        destinations.push((peer_id, conn));
+    }
+
+    let mut sender =
+        transfer::broadcast::BroadcastSender::new(destinations, state.transfer_registry.clone())
+            .with_memory_budget(state.buffer_budget.clone());
+    if let Some(size) = block_size {
+        sender = sender.with_block_size(size);
+    }
+    if let Some(count) = max_concurrent_streams {
+        sender = sender.with_max_concurrent(count);
+    }
+    let results = sender
+        .send_files(paths.into_iter().map(PathBuf::from).collect(), &job_id)
+        .await;
+
+    let succeeded = results.iter().filter(|r| r.error.is_none()).count();
+    info!(
+        "📡 브로드캐스트 전송 완료: {}/{} 대상 성공",
+        succeeded,
+        results.len()
+    );
+    Ok(results)
+}
+
+// --- 다중 소스 다운로드 Commands ---
+
+/// 🆕 파일을 다중 소스 다운로드용으로 공유 (연결된 피어가 Info Hash로 조각 요청)
+#[tauri::command]
+async fn share_multi_source_file(
+    file_path: String,
+    piece_size: Option<u32>,
+    state: tauri::State<'_, AppState>,
+) -> Result<serde_json::Value, AppError> {
+    let path = PathBuf::from(&file_path);
+    let piece_size = piece_size.unwrap_or(1024 * 1024); // 기본 1MB
+    let metadata = grid::piece_manager::FileMetadata::from_file_cached(
+        &path,
+        piece_size,
+        Default::default(),
+        &state.piece_hash_cache,
+    )
+    .await
+    .map_err(|e| AppError::Io(format!("메타데이터 생성 실패: {}", e)))?;
+
+    let value = serde_json::json!({
+        "infoHash": metadata.info_hash_hex(),
+        "fileName": metadata.file_name,
+        "fileSize": metadata.file_size,
+        "pieceSize": metadata.piece_size,
+        "totalPieces": metadata.total_pieces,
+    });
+    state.multi_source.share(metadata, path);
+    Ok(value)
+}
+
+/// 🆕 다중 소스 공유 해제
+#[tauri::command]
+async fn stop_multi_source_share(
+    info_hash: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<bool, AppError> {
+    let hash = parse_info_hash(&info_hash)?;
+    Ok(state.multi_source.unshare(&hash))
+}
+
+/// 🆕 같은 파일을 가진 여러 피어에게서 조각을 나눠 받기
+///
+/// 메타데이터는 처음으로 응답한 피어에게서 받고 Info Hash로 검증합니다.
+/// 진행률은 레지스트리 이벤트로, 결과에는 저장 경로와 피어별 기여도가 담깁니다.
+#[tauri::command]
+async fn download_multi_source(
+    info_hash: String,
+    peer_ids: Vec<String>,
+    save_dir: String,
+    job_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<serde_json::Value, AppError> {
+    use transfer::multi_source::MultiSourceDownload;
+
+    let hash = parse_info_hash(&info_hash)?;
+    let mut sources = Vec::new();
+    for peer_id in peer_ids {
+        let conn = peer_connection(&state, &peer_id).await?;
+        sources.push((peer_id, conn));
+    }
+
+    let mut metadata = None;
+    for (peer_id, conn) in &sources {
+        match state.multi_source.fetch_metadata(conn, &hash).await {
+            Ok(Some(found)) => {
+                metadata = Some(found);
+                break;
+            }
+            Ok(None) => info!("피어 {}에게 없는 파일: {}", peer_id, info_hash),
+            Err(e) => warn!("메타데이터 요청 실패 ({}): {}", peer_id, e),
+        }
+    }
+    let metadata = metadata
+        .ok_or_else(|| AppError::NotFound(format!("파일을 가진 피어가 없습니다: {}", info_hash)))?;
+
+    let control = state.transfer_registry.register(
+        &job_id,
+        &sources
+            .iter()
+            .map(|(peer_id, _)| peer_id.as_str())
+            .collect::<Vec<_>>()
+            .join(","),
+        TransferKind::MultiSource,
+    )?;
+    let (tx, mut rx) = mpsc::channel::<MultiStreamProgress>(100);
+
+    // 진행률을 레지스트리에 반영 (이벤트는 레지스트리가 발송)
+    let registry = state.transfer_registry.clone();
+    tauri::async_runtime::spawn(async move {
+        while let Some(progress) = rx.recv().await {
+            registry.update_progress(
+                &progress.job_id,
+                progress.acknowledged_bytes,
+                progress.total_bytes,
+                progress.speed_bps,
+            );
+        }
+    });
+
+    let result = MultiSourceDownload::new(state.multi_source.clone(), metadata, sources)
+        .with_progress_channel(tx)
+        .with_job_control(control)
+        .download(&PathBuf::from(&save_dir), &job_id)
+        .await
+        .map_err(|e| AppError::Network(format!("다중 소스 다운로드 실패: {}", e)));
+    state
+        .transfer_registry
+        .finish(&job_id, result.as_ref().map(|_| ()).map_err(|e| e.clone()));
+    let (path, sources) = result?;
+
+    Ok(serde_json::json!({
+        "path": path.to_string_lossy(),
+        "sources": sources,
+    }))
+}
+
+// --- 부분 요청 Commands ---
+
+/// 🆕 파일이나 폴더를 부분 요청용으로 제공 (수신 측이 목록을 보고 필요한 파일/범위만 요청)
+///
+/// `peer_id`를 주면 그 피어만 요청할 수 있습니다. 반환한 목록의 `offerId`를 상대에게 알려 줍니다.
+#[tauri::command]
+async fn offer_files(
+    path: String,
+    peer_id: Option<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<transfer::range_request::OfferCatalog, AppError> {
+    state
+        .range_requests
+        .offer(PathBuf::from(&path), peer_id)
+        .await
+        .map_err(|e| AppError::Io(format!("부분 요청 제공 실패: {}", e)))
+}
+
+/// 🆕 부분 요청 제공 중단
+#[tauri::command]
+async fn withdraw_offer(
+    offer_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<bool, AppError> {
+    Ok(state.range_requests.withdraw(&offer_id))
+}
+
+/// 🆕 이 기기가 제공 중인 부분 요청 목록
+#[tauri::command]
+async fn list_offers(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<transfer::range_request::OfferCatalog>, AppError> {
+    Ok(state.range_requests.list())
+}
+
+/// 🆕 피어가 제공한 파일 목록 받기 (선택 화면용)
+#[tauri::command]
+async fn fetch_offer_catalog(
+    peer_id: String,
+    offer_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<transfer::range_request::OfferCatalog, AppError> {
+    let conn = peer_connection(&state, &peer_id).await?;
+    state
+        .range_requests
+        .fetch_catalog(&conn, &offer_id)
+        .await
+        .map_err(|e| AppError::Network(format!("제공 목록 요청 실패: {}", e)))
+}
+
+/// 🆕 피어가 제공한 파일 중 고른 파일/하위 폴더/바이트 범위만 받기
+///
+/// 진행률/일시정지/취소는 `job_id` 작업으로 다른 전송과 같이 동작합니다.
+#[tauri::command]
+async fn request_offer_files(
+    peer_id: String,
+    offer_id: String,
+    selection: Vec<transfer::range_request::FileSelection>,
+    save_dir: String,
+    job_id: String,
+    overwrite_policy: Option<transfer::OverwritePolicy>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<transfer::range_request::RangeDownloadResult>, AppError> {
+    let conn = peer_connection(&state, &peer_id).await?;
+    state
+        .range_requests
+        .download(
+            &conn,
+            &peer_id,
+            &offer_id,
+            &selection,
+            &PathBuf::from(&save_dir),
+            overwrite_policy.unwrap_or_default(),
+            &state.transfer_registry,
+            &job_id,
+        )
+        .await
+}
+
+/// hex Info Hash 파싱
+fn parse_info_hash(info_hash: &str) -> Result<[u8; 32], AppError> {
+    hex::decode(info_hash)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| AppError::InvalidInput(format!("잘못된 Info Hash: {}", info_hash)))
+}
+
+/// 🆕 피어와 페어링 (키 교환 후 양쪽 화면에서 확인 코드 비교, 같으면 `confirm_pairing`)
+#[tauri::command]
+async fn pair_with_peer(
+    peer_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<transfer::pairing::PairedPeer, AppError> {
+    let conn = peer_connection(&state, &peer_id).await?;
+    state
+        .pairing
+        .pair(&conn, &peer_id)
+        .await
+        .map_err(|e| AppError::Crypto(format!("페어링 실패: {}", e)))
+}
+
+/// 🆕 확인 코드가 같음을 확인 → 페어링 키 사용 시작 (양쪽 기기에서 각각 확인)
+#[tauri::command]
+async fn confirm_pairing(
+    peer_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<transfer::pairing::PairedPeer, AppError> {
+    state
+        .pairing
+        .confirm(&peer_id)
+        .map_err(|e| AppError::Crypto(format!("페어링 확인 실패: {}", e)))
+}
+
+/// 🆕 확인 코드가 다름 → 새 페어링 키 폐기
+#[tauri::command]
+async fn reject_pairing(
+    peer_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<bool, AppError> {
+    Ok(state.pairing.reject(&peer_id))
+}
 
-    let (tx, mut rx) = mpsc::channel::<MultiStreamProgress>(100);
+/// 🆕 페어링 해제 (이후 암호화 전송 불가)
+#[tauri::command]
+async fn unpair_peer(peer_id: String, state: tauri::State<'_, AppState>) -> Result<bool, AppError> {
+    Ok(state.pairing.unpair(&peer_id))
+}
 
-    let receiver =
-        MultiStreamReceiver::new(conn, PathBuf::from(&save_dir)).with_progress_channel(tx);
+/// 🆕 페어링된 피어 목록
+#[tauri::command]
+async fn list_paired_peers(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<transfer::pairing::PairedPeer>, AppError> {
+    Ok(state.pairing.list())
+}
 
-    // 진행률 이벤트 전송
-    let app_handle = state.app_handle.clone();
-    tauri::async_runtime::spawn(async move {
-        while let Some(progress) = rx.recv().await {
-            let _ = app_handle.emit("multistream-progress", &progress);
-        }
-    });
+/// 🆕 QR 코드로 보여줄 연결 정보 (QUIC 서버가 실행 중이어야 함, 10분 동안 한 번 사용 가능)
+#[tauri::command]
+async fn get_connection_qr_payload(state: tauri::State<'_, AppState>) -> Result<String, AppError> {
+    connection_qr_payload(&state).await
+}
 
-    let result_path = receiver
-        .receive_file(&job_id)
+/// 서명된 연결 정보 문자열 (QR 코드, 트레이의 "내 연결 코드 복사")
+async fn connection_qr_payload(state: &AppState) -> Result<String, AppError> {
+    let fingerprint = state
+        .quic_identity
+        .as_ref()
+        .map(|identity| identity.fingerprint())
+        .ok_or(AppError::Crypto("QUIC 인증서가 없습니다".into()))?;
+    let candidates: Vec<SocketAddr> = local_candidates(state)
         .await
-        .map_err(|e| format!("멀티스트림 수신 실패: {}", e))?;
+        .iter()
+        .filter_map(|candidate| candidate.parse().ok())
+        .collect();
+    if candidates.is_empty() {
+        return Err(AppError::NotRunning(
+            "QUIC 서버가 실행 중이 아닙니다".into(),
+        ));
+    }
 
-    let result_str = result_path.to_string_lossy().to_string();
+    let (pairing_id, pairing_key) = state.pairing.create_offer();
+    quic::qr_payload::ConnectionQrPayload::new(
+        &state.signing_identity,
+        candidates,
+        &fingerprint,
+        pairing_id,
+        pairing_key,
+    )
+    .and_then(|payload| payload.encode(&state.signing_identity))
+    .map_err(|e| AppError::Internal(format!("QR 연결 정보 생성 실패: {}", e)))
+}
 
-    let _ = state.app_handle.emit(
-        "multistream-complete",
-        serde_json::json!({
-            "jobId": job_id,
-            "savedPath": result_str,
-            "peerId": peer_id,
-        }),
-    );
+/// 🆕 스캔한 QR 연결 정보로 연결 후 페어링 (인증서 지문이 다르면 연결 거부, 키는 `confirm_pairing` 후 사용)
+///
+/// 핸드셰이크 서명을 서버 인증서 키로 검증하므로, 지문이 맞으면 QR을 보여준 기기의 개인 키를 가진
+/// 상대입니다. QR의 인증서를 복사해 제시하는 것만으로는 통과할 수 없습니다.
+#[tauri::command]
+async fn connect_from_qr_payload(
+    blob: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<serde_json::Value, AppError> {
+    let payload = quic::qr_payload::ConnectionQrPayload::decode(&blob)
+        .map_err(|e| AppError::Crypto(format!("QR 코드 확인 실패: {}", e)))?;
+
+    let (addr, conn) = race_connect(&state, &payload.candidates).await?;
+    let expected = payload.fingerprint_hex();
+    if quic::identity::peer_fingerprint(&conn).as_deref() != Some(expected.as_str()) {
+        conn.close(0u32.into(), b"fingerprint mismatch");
+        return Err(AppError::Crypto(format!(
+            "인증서 지문이 QR 코드와 다릅니다: {}",
+            addr
+        )));
+    }
 
-    info!("✅ 멀티스트림 수신 완료: {:?}", result_path);
-    Ok(result_str)
+    let peer_id = addr.to_string();
+    register_connection(&state, &peer_id, conn.clone()).await;
+    info!("✅ QR 코드로 피어 연결 성공: {}", peer_id);
+
+    let paired = state
+        .pairing
+        .pair_with_offer(&conn, &peer_id, &payload.pairing_id, payload.pairing_key)
+        .await
+        .map_err(|e| AppError::Crypto(format!("페어링 실패: {}", e)))?;
+
+    Ok(serde_json::json!({
+        "peerId": peer_id,
+        "nodeId": payload.node_id_hex(),
+        "fingerprint": payload.fingerprint_hex(),
+        "pairing": paired,
+    }))
 }
 
 /// Zero-Copy I/O 엔진 정보 조회
 #[tauri::command]
-async fn get_io_engine_info() -> Result<serde_json::Value, String> {
+async fn get_io_engine_info() -> Result<serde_json::Value, AppError> {
     let engine = ZeroCopyEngine::new();
     let io_method = match engine.io_method() {
         IoMethod::Mmap => "mmap",
@@ -832,6 +3374,8 @@ async fn get_io_engine_info() -> Result<serde_json::Value, String> {
 
     Ok(serde_json::json!({
         "ioMethod": io_method,
+        // 마지막 전송에서 실제로 쓰인 읽기 경로 (링/핸들 초기화 실패 시 감지 결과와 다름)
+        "activeReadMethod": transfer::zero_copy_io::active_read_path(),
         "zeroCopySupported": io_method != "buffered",
         "platform": std::env::consts::OS,
         "blockSize": 8 * 1024 * 1024,  // 8MB
@@ -843,7 +3387,7 @@ async fn get_io_engine_info() -> Result<serde_json::Value, String> {
 
 /// Grid 모드 정보 조회
 #[tauri::command]
-async fn get_grid_info() -> Result<serde_json::Value, String> {
+async fn get_grid_info() -> Result<serde_json::Value, AppError> {
     Ok(serde_json::json!({
         "version": "2.0",
         "features": ["bitfield", "rare-first", "dht", "mesh"],
@@ -853,37 +3397,810 @@ async fn get_grid_info() -> Result<serde_json::Value, String> {
     }))
 }
 
+/// 🆕 해시 진행률 이벤트 간격
+const HASHING_PROGRESS_INTERVAL: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// 🆕 해시 진행률 이벤트 (`grid-hashing-progress`)
+fn emit_hashing_progress(
+    app_handle: &AppHandle,
+    job_id: &str,
+    job: &grid::piece_manager::HashingJob,
+) {
+    let progress = job.progress();
+    let _ = app_handle.emit(
+        "grid-hashing-progress",
+        serde_json::json!({
+            "jobId": job_id,
+            "bytesHashed": progress.bytes_hashed,
+            "totalBytes": progress.total_bytes,
+            "piecesDone": progress.pieces_done,
+            "totalPieces": progress.total_pieces,
+            "speedBps": progress.speed_bps,
+            "etaSecs": progress.eta_secs,
+        }),
+    );
+}
+
+/// 🆕 프론트엔드에서 받은 해시 알고리즘 이름 해석 (없으면 SHA-256)
+fn parse_hash_algorithm(value: Option<String>) -> Result<grid::hash_algo::HashAlgorithm, AppError> {
+    value
+        .map(|name| name.parse().map_err(AppError::InvalidInput))
+        .transpose()
+        .map(Option::unwrap_or_default)
+}
+
 /// Grid 파일 메타데이터 생성
+///
+/// 🆕 `hash_algorithm`으로 조각 해시 알고리즘(`sha256`/`blake3`)을 고를 수 있습니다.
+/// 🆕 `job_id`를 주면 해시 진행률을 `grid-hashing-progress` 이벤트로 보내고,
+/// `cancel_grid_hashing`으로 중간에 취소할 수 있습니다.
 #[tauri::command]
 async fn create_grid_metadata(
     file_path: String,
     piece_size: Option<u32>,
-) -> Result<serde_json::Value, String> {
-    use grid::piece_manager::FileMetadata;
+    web_seeds: Option<Vec<String>>,
+    job_id: Option<String>,
+    hash_algorithm: Option<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<serde_json::Value, AppError> {
+    use grid::piece_manager::{FileMetadata, HashingJob};
 
     let path = PathBuf::from(&file_path);
     let piece_size = piece_size.unwrap_or(1024 * 1024); // 기본 1MB
+    let algorithm = parse_hash_algorithm(hash_algorithm)?;
+
+    let job = Arc::new(HashingJob::new());
+    let reporter = match &job_id {
+        Some(job_id) => {
+            let mut jobs = state.grid_hashing_jobs.write().await;
+            if jobs.contains_key(job_id) {
+                return Err(AppError::InvalidInput(format!(
+                    "이미 진행 중인 해시 작업입니다: {}",
+                    job_id
+                )));
+            }
+            jobs.insert(job_id.clone(), job.clone());
+
+            let (app_handle, job_id, job) = (state.app_handle.clone(), job_id.clone(), job.clone());
+            Some(tauri::async_runtime::spawn(async move {
+                let mut ticker = tokio::time::interval(HASHING_PROGRESS_INTERVAL);
+                loop {
+                    ticker.tick().await;
+                    emit_hashing_progress(&app_handle, &job_id, &job);
+                }
+            }))
+        }
+        None => None,
+    };
+
+    let result = FileMetadata::from_file_cached_with_job(
+        &path,
+        piece_size,
+        algorithm,
+        &state.piece_hash_cache,
+        &job,
+    )
+    .await;
+
+    if let Some(job_id) = &job_id {
+        state.grid_hashing_jobs.write().await.remove(job_id);
+        if let Some(reporter) = reporter {
+            reporter.abort();
+        }
+        emit_hashing_progress(&state.app_handle, job_id, &job);
+    }
+
+    let mut metadata = result.map_err(|e| {
+        if job.is_cancelled() {
+            AppError::Cancelled("메타데이터 생성이 취소되었습니다".to_string())
+        } else {
+            AppError::Io(format!("메타데이터 생성 실패: {}", e))
+        }
+    })?;
+    metadata.web_seeds = web_seeds.unwrap_or_default();
+
+    Ok(serde_json::json!({
+        "infoHash": hex::encode(metadata.info_hash),
+        "fileName": metadata.file_name,
+        "fileSize": metadata.file_size,
+        "pieceSize": metadata.piece_size,
+        "totalPieces": metadata.total_pieces,
+        "merkleRoot": metadata.merkle_root.map(|r| hex::encode(r)),
+        "webSeeds": metadata.web_seeds,
+        "hashAlgorithm": metadata.hash_algorithm,
+    }))
+}
+
+/// 🆕 진행 중인 Grid 메타데이터 해시 계산 취소
+#[tauri::command]
+async fn cancel_grid_hashing(
+    job_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), AppError> {
+    match state.grid_hashing_jobs.read().await.get(&job_id) {
+        Some(job) => {
+            job.cancel();
+            info!("🛑 해시 계산 취소 요청됨: {}", job_id);
+            Ok(())
+        }
+        None => Err(AppError::NotFound(format!(
+            "해시 작업을 찾을 수 없습니다: {}",
+            job_id
+        ))),
+    }
+}
+
+/// 🆕 Grid Swarm 속도 제한 설정 (bytes/sec, 0 = 무제한)
+///
+/// 직접 전송과 독립적으로 백그라운드 Grid 다운로드만 제한합니다.
+#[tauri::command]
+async fn set_swarm_rate_limit(
+    job_id: String,
+    up: u64,
+    down: u64,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), AppError> {
+    let mut limits = state.grid_rate_limits.write().await;
+    limits
+        .entry(job_id.clone())
+        .or_insert_with(|| Arc::new(grid::rate_limit::SwarmRateLimit::unlimited()))
+        .set(up, down);
+
+    info!("🚦 Swarm 속도 제한 설정: {} (up {} B/s, down {} B/s)", job_id, up, down);
+    Ok(())
+}
+
+/// 🆕 Grid Swarm 속도 제한 조회
+#[tauri::command]
+async fn get_swarm_rate_limit(
+    job_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<grid::rate_limit::RateLimitInfo, AppError> {
+    let limits = state.grid_rate_limits.read().await;
+    Ok(limits
+        .get(&job_id)
+        .map(|l| l.snapshot())
+        .unwrap_or_else(|| grid::rate_limit::SwarmRateLimit::unlimited().snapshot()))
+}
+
+/// Grid 재개 상태 저장 디렉토리
+fn grid_resume_dir(app: &AppHandle) -> Result<std::path::PathBuf, AppError> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join("grid"))
+        .map_err(|e| AppError::Io(format!("앱 데이터 경로 조회 실패: {}", e)))
+}
+
+/// 🆕 중단된 Grid 다운로드 재개 준비
+///
+/// 저장된 비트필드를 실제 파일과 대조해 검증된 조각만 완료로 인정합니다.
+#[tauri::command]
+async fn resume_grid_download(
+    app: AppHandle,
+    job_id: String,
+) -> Result<serde_json::Value, AppError> {
+    #[cfg(feature = "grid-experimental")]
+    {
+        let dir = grid_resume_dir(&app)?;
+        let mut resume = grid::resume::ResumeState::load(&dir, &job_id)
+            .await
+            .map_err(|e| AppError::Io(format!("재개 상태 로드 실패: {}", e)))?;
+
+        let mut pm = grid::piece_manager::PieceManager::new(resume.metadata.clone());
+        pm.set_save_path(resume.save_path.clone());
+        let verified = pm.verify_existing_pieces(&resume.bitfield).await;
+
+        resume.bitfield = pm.get_bitfield().clone();
+        resume
+            .save(&dir)
+            .await
+            .map_err(|e| AppError::Io(format!("재개 상태 저장 실패: {}", e)))?;
+
+        info!(
+            "🔁 Grid 다운로드 재개 준비: {} ({}/{} pieces 검증됨)",
+            job_id, verified, resume.metadata.total_pieces
+        );
+
+        Ok(serde_json::json!({
+            "jobId": resume.job_id,
+            "infoHash": hex::encode(resume.metadata.info_hash),
+            "fileName": resume.metadata.file_name,
+            "savePath": resume.save_path.to_string_lossy(),
+            "verifiedPieces": verified,
+            "totalPieces": resume.metadata.total_pieces,
+            "progress": resume.bitfield.progress(),
+        }))
+    }
+    #[cfg(not(feature = "grid-experimental"))]
+    {
+        let _ = (app, job_id);
+        Err(AppError::Unsupported(GRID_DISABLED.into()))
+    }
+}
+
+/// 🆕 재개 가능한 Grid 다운로드 목록
+#[tauri::command]
+async fn list_resumable_grid_jobs(app: AppHandle) -> Result<Vec<serde_json::Value>, AppError> {
+    #[cfg(feature = "grid-experimental")]
+    {
+        let dir = grid_resume_dir(&app)?;
+        Ok(grid::resume::ResumeState::list(&dir)
+            .await
+            .into_iter()
+            .map(|resume| {
+                serde_json::json!({
+                    "jobId": resume.job_id,
+                    "infoHash": hex::encode(resume.metadata.info_hash),
+                    "fileName": resume.metadata.file_name,
+                    "fileSize": resume.metadata.file_size,
+                    "completedPieces": resume.bitfield.count_ones(),
+                    "totalPieces": resume.metadata.total_pieces,
+                    "updatedAt": resume.updated_at,
+                })
+            })
+            .collect())
+    }
+    #[cfg(not(feature = "grid-experimental"))]
+    {
+        let _ = app;
+        Ok(Vec::new())
+    }
+}
+
+/// 🆕 Grid 공유 링크 생성 (pons://...)
+#[tauri::command]
+async fn create_share_link(
+    file_path: String,
+    piece_size: Option<u32>,
+    hash_algorithm: Option<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, AppError> {
+    let path = std::path::PathBuf::from(&file_path);
+    let piece_size = piece_size.unwrap_or(grid::config::DEFAULT_PIECE_SIZE);
+
+    let metadata = grid::piece_manager::FileMetadata::from_file_cached(
+        &path,
+        piece_size,
+        parse_hash_algorithm(hash_algorithm)?,
+        &state.piece_hash_cache,
+    )
+    .await
+    .map_err(|e| AppError::Io(format!("메타데이터 생성 실패: {}", e)))?;
+
+    let link = grid::share_link::ShareLink::from_metadata(&metadata).to_uri();
+    info!("🔗 공유 링크 생성: {}", link);
+    Ok(link)
+}
+
+/// 🆕 Grid 메타데이터를 `.pons` 파일로 저장 (메일/공유 드라이브로 전달)
+///
+/// `output_path`에 확장자가 없으면 `.pons`를 붙입니다.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn save_grid_metadata(
+    file_path: String,
+    output_path: String,
+    piece_size: Option<u32>,
+    hash_algorithm: Option<String>,
+    web_seeds: Option<Vec<String>>,
+    trackers: Option<Vec<String>>,
+    bootstrap_nodes: Option<Vec<String>>,
+    comment: Option<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<serde_json::Value, AppError> {
+    #[cfg(feature = "grid-experimental")]
+    {
+        use grid::pons_file::{PonsFile, PONS_FILE_EXTENSION};
+
+        let path = PathBuf::from(&file_path);
+        let piece_size = piece_size.unwrap_or(grid::config::DEFAULT_PIECE_SIZE);
+        let mut metadata = grid::piece_manager::FileMetadata::from_file_cached(
+            &path,
+            piece_size,
+            parse_hash_algorithm(hash_algorithm)?,
+            &state.piece_hash_cache,
+        )
+        .await
+        .map_err(|e| AppError::Io(format!("메타데이터 생성 실패: {}", e)))?;
+        metadata.web_seeds = web_seeds.unwrap_or_default();
+
+        let mut output = PathBuf::from(&output_path);
+        if output.extension().is_none() {
+            output.set_extension(PONS_FILE_EXTENSION);
+        }
+        let info_hash = metadata.info_hash_hex();
+        let pons = PonsFile::new(metadata)
+            .with_trackers(trackers.unwrap_or_default())
+            .with_bootstrap_nodes(bootstrap_nodes.unwrap_or_default())
+            .with_comment(comment);
+        let save_path = output.clone();
+        tokio::task::spawn_blocking(move || pons.save(&save_path))
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?
+            .map_err(|e| AppError::Io(format!(".pons 저장 실패: {}", e)))?;
+
+        info!("📦 .pons 파일 저장: {:?} ({})", output, info_hash);
+        Ok(serde_json::json!({
+            "path": output.to_string_lossy(),
+            "infoHash": info_hash,
+        }))
+    }
+    #[cfg(not(feature = "grid-experimental"))]
+    {
+        let _ = (
+            file_path,
+            output_path,
+            piece_size,
+            hash_algorithm,
+            web_seeds,
+            trackers,
+            bootstrap_nodes,
+            comment,
+            state,
+        );
+        Err(AppError::Unsupported(GRID_DISABLED.into()))
+    }
+}
+
+/// 🆕 `.pons` 파일 읽기 (체크섬/Info Hash 검증 후 요약 반환)
+///
+/// 다운로드는 파일 경로를 `start_grid_download`의 `source`로 넘기면 됩니다.
+#[tauri::command]
+async fn load_grid_metadata(path: String) -> Result<serde_json::Value, AppError> {
+    #[cfg(feature = "grid-experimental")]
+    {
+        let load_path = PathBuf::from(&path);
+        let pons = tokio::task::spawn_blocking(move || grid::pons_file::PonsFile::load(&load_path))
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?
+            .map_err(|e| AppError::InvalidInput(format!(".pons 파일 읽기 실패: {}", e)))?;
+
+        let metadata = &pons.metadata;
+        Ok(serde_json::json!({
+            "infoHash": metadata.info_hash_hex(),
+            "fileName": metadata.file_name,
+            "fileSize": metadata.file_size,
+            "pieceSize": metadata.piece_size,
+            "totalPieces": metadata.total_pieces,
+            "hashAlgorithm": metadata.hash_algorithm,
+            "webSeeds": metadata.web_seeds,
+            "trackers": pons.trackers,
+            "bootstrapNodes": pons.bootstrap_nodes,
+            "createdAt": pons.created_at,
+            "comment": pons.comment,
+            "shareLink": grid::share_link::ShareLink::from_metadata(metadata).to_uri(),
+        }))
+    }
+    #[cfg(not(feature = "grid-experimental"))]
+    {
+        let _ = path;
+        Err(AppError::Unsupported(GRID_DISABLED.into()))
+    }
+}
+
+/// 🆕 Grid 공유 링크 열기
+///
+/// 링크를 파싱한 뒤 info_hash로 DHT에서 제공 피어를 검색합니다.
+#[tauri::command]
+async fn open_share_link(
+    link: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<serde_json::Value, AppError> {
+    let parsed = grid::share_link::ShareLink::parse(&link)
+        .map_err(|e| AppError::InvalidInput(format!("공유 링크 파싱 실패: {}", e)))?;
+
+    let dht = state
+        .embedded_bootstrap
+        .read()
+        .await
+        .as_ref()
+        .and_then(|service| service.dht_handle());
+
+    let peers = match dht {
+        Some(dht) => dht
+            .find_providers(parsed.info_hash, std::time::Duration::from_secs(3))
+            .await
+            .map_err(|e| AppError::Network(format!("피어 검색 실패: {}", e)))?,
+        None => {
+            warn!("DHT가 실행 중이 아니어서 피어 검색을 건너뜁니다");
+            Vec::new()
+        }
+    };
+
+    info!(
+        "🔗 공유 링크 열기: {} ({} peers)",
+        parsed.name,
+        peers.len()
+    );
+
+    Ok(serde_json::json!({
+        "infoHash": hex::encode(parsed.info_hash),
+        "fileName": parsed.name,
+        "fileSize": parsed.size,
+        "pieceSize": parsed.piece_size,
+        "totalPieces": parsed.total_pieces(),
+        "peers": peers.iter().map(|p| p.to_string()).collect::<Vec<_>>(),
+    }))
+}
+
+/// Grid 기능 비활성화 빌드에서의 에러 메시지
+#[cfg(not(feature = "grid-experimental"))]
+const GRID_DISABLED: &str = "Grid 기능이 비활성화된 빌드입니다 (grid-experimental)";
+
+/// job_id 기준 Grid 작업 옵션 (속도 제한 공유, 재개 디렉토리, 피어 발견, 공유 엔드포인트)
+#[cfg(feature = "grid-experimental")]
+async fn grid_job_options(
+    app: &AppHandle,
+    state: &AppState,
+    job_id: &str,
+) -> grid::job::GridJobOptions {
+    let rate_limit = state
+        .grid_rate_limits
+        .write()
+        .await
+        .entry(job_id.to_string())
+        .or_insert_with(|| Arc::new(grid::rate_limit::SwarmRateLimit::unlimited()))
+        .clone();
+
+    let dht = state
+        .embedded_bootstrap
+        .read()
+        .await
+        .as_ref()
+        .and_then(|service| service.dht_handle());
+
+    grid::job::GridJobOptions {
+        app_handle: app.clone(),
+        rate_limit,
+        resume_dir: grid_resume_dir(app).ok(),
+        ban_list: state.grid_ban_list.clone(),
+        registry: state.transfer_registry.clone(),
+        dht,
+        mdns: Some(state.discovery.clone()),
+        shared_endpoint: state
+            .quic_server
+            .read()
+            .await
+            .as_ref()
+            .and_then(|server| server.shared_grid_endpoint()),
+    }
+}
+
+/// 🆕 Grid Seed 작업 시작
+///
+/// 파일 메타데이터를 만들고 Swarm을 띄운 뒤 DHT에 제공자로 광고합니다.
+/// 🆕 `manifest_job_id`를 주면 가져온 Grid 매니페스트로 같은 데이터셋을 다시 시드합니다
+/// (파일이 매니페스트와 일치해야 함).
+#[tauri::command]
+async fn start_grid_seed(
+    app: AppHandle,
+    file_path: String,
+    piece_size: Option<u32>,
+    manifest_job_id: Option<String>,
+    hash_algorithm: Option<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<serde_json::Value, AppError> {
+    #[cfg(feature = "grid-experimental")]
+    {
+        use transfer::job_manifest::DatasetManifest;
+
+        let path = std::path::PathBuf::from(&file_path);
+        let metadata = match manifest_job_id {
+            Some(manifest_job_id) => {
+                let manifest = state.job_manifests.get(&manifest_job_id).ok_or_else(|| {
+                    AppError::NotFound(format!(
+                        "매니페스트를 찾을 수 없습니다: {}",
+                        manifest_job_id
+                    ))
+                })?;
+                let DatasetManifest::Grid { metadata } = &manifest.dataset else {
+                    return Err(AppError::InvalidInput("Grid 매니페스트가 아닙니다".into()));
+                };
+                let check_path = path.clone();
+                let dataset = manifest.dataset.clone();
+                let report = tokio::task::spawn_blocking(move || {
+                    transfer::job_manifest::verify_file(&dataset, &check_path)
+                })
+                .await
+                .map_err(|e| AppError::Internal(e.to_string()))?
+                .map_err(|e| AppError::Io(format!("매니페스트 검증 실패: {}", e)))?;
+                if !report.matches {
+                    return Err(AppError::InvalidInput(format!(
+                        "파일이 매니페스트와 다릅니다 (불일치 조각 {}개)",
+                        report.mismatched_blocks.len()
+                    )));
+                }
+                metadata.clone()
+            }
+            None => {
+                let piece_size = piece_size.unwrap_or(grid::config::DEFAULT_PIECE_SIZE);
+                grid::piece_manager::FileMetadata::from_file_cached(
+                    &path,
+                    piece_size,
+                    parse_hash_algorithm(hash_algorithm)?,
+                    &state.piece_hash_cache,
+                )
+                .await
+                .map_err(|e| AppError::Io(format!("메타데이터 생성 실패: {}", e)))?
+            }
+        };
+
+        let job_id = format!("grid-{}", uuid::Uuid::new_v4());
+        let options = grid_job_options(&app, &state, &job_id).await;
+        let info = state
+            .grid_jobs
+            .start_seed(job_id.clone(), path, metadata.clone(), options)
+            .await
+            .map_err(|e| AppError::Network(format!("Grid Seed 시작 실패: {}", e)))?;
+        state.job_manifests.record_grid(&job_id, &metadata);
+
+        // DHT에 제공자로 광고 (공유 링크 수신 측이 찾을 수 있도록)
+        let dht = state
+            .embedded_bootstrap
+            .read()
+            .await
+            .as_ref()
+            .and_then(|service| service.dht_handle());
+        if let (Some(dht), Ok(addr)) = (dht, info.local_addr.parse::<SocketAddr>()) {
+            if let Err(e) = dht.announce(metadata.info_hash, addr.port()).await {
+                warn!("Grid 제공 광고 실패: {}", e);
+            }
+        }
+
+        let link = grid::share_link::ShareLink::from_metadata(&metadata).to_uri();
+        let mut value =
+            serde_json::to_value(&info).map_err(|e| AppError::Internal(e.to_string()))?;
+        value["shareLink"] = serde_json::Value::String(link);
+        Ok(value)
+    }
+    #[cfg(not(feature = "grid-experimental"))]
+    {
+        let _ = (
+            app,
+            file_path,
+            piece_size,
+            manifest_job_id,
+            hash_algorithm,
+            state,
+        );
+        Err(AppError::Unsupported(GRID_DISABLED.into()))
+    }
+}
+
+/// 🆕 Grid Download 작업 시작
+///
+/// `source`는 공유 링크(pons://) 또는 hex Info Hash이며, 메타데이터는 피어에게서 받습니다.
+/// 🆕 `.pons` 파일 경로를 주면 파일의 메타데이터로 바로 시작하고 트래커 주소에도 연결합니다.
+/// `peers`로 전달된 주소에 바로 연결하고, 🆕 DHT 제공자와 mDNS 피어는 작업의 자동 발견이
+/// 주기적으로 찾아 연결합니다.
+#[tauri::command]
+async fn start_grid_download(
+    app: AppHandle,
+    source: String,
+    save_dir: String,
+    peers: Option<Vec<String>>,
+    state: tauri::State<'_, AppState>,
+) -> Result<serde_json::Value, AppError> {
+    #[cfg(feature = "grid-experimental")]
+    {
+        let pons = if grid::pons_file::is_pons_path(&source) {
+            let pons_path = PathBuf::from(source.trim());
+            let pons =
+                tokio::task::spawn_blocking(move || grid::pons_file::PonsFile::load(&pons_path))
+                    .await
+                    .map_err(|e| AppError::Internal(e.to_string()))?
+                    .map_err(|e| AppError::InvalidInput(format!(".pons 파일 읽기 실패: {}", e)))?;
+            Some(pons)
+        } else {
+            None
+        };
+
+        let (info_hash, file_name) = if let Some(pons) = &pons {
+            (pons.metadata.info_hash, pons.metadata.file_name.clone())
+        } else if source.starts_with(grid::share_link::SHARE_LINK_SCHEME) {
+            let link = grid::share_link::ShareLink::parse(&source)
+                .map_err(|e| AppError::InvalidInput(format!("공유 링크 파싱 실패: {}", e)))?;
+            (link.info_hash, link.name)
+        } else {
+            let bytes = hex::decode(source.trim())
+                .map_err(|e| AppError::InvalidInput(format!("Info Hash 파싱 실패: {}", e)))?;
+            let hash: [u8; 32] = bytes.try_into().map_err(|_| {
+                AppError::InvalidInput("Info Hash 파싱 실패: 32바이트가 아닙니다".into())
+            })?;
+            (hash, hex::encode(&hash[..8]))
+        };
+
+        // 경로 조작 방지: 파일명만 사용
+        let file_name = std::path::Path::new(&file_name)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| hex::encode(&info_hash[..8]));
+        let save_path = std::path::PathBuf::from(&save_dir).join(file_name);
+
+        let addrs: Vec<SocketAddr> = peers
+            .unwrap_or_default()
+            .iter()
+            .chain(pons.iter().flat_map(|pons| pons.trackers.iter()))
+            .filter_map(|p| p.parse().ok())
+            .collect();
+
+        let job_id = format!("grid-{}", uuid::Uuid::new_v4());
+        let options = grid_job_options(&app, &state, &job_id).await;
+        let started = match pons {
+            Some(pons) => {
+                state
+                    .grid_jobs
+                    .start_download(job_id.clone(), pons.metadata, save_path, options)
+                    .await
+            }
+            None => {
+                state
+                    .grid_jobs
+                    .start_download_by_hash(job_id.clone(), info_hash, save_path, options)
+                    .await
+            }
+        };
+        let info =
+            started.map_err(|e| AppError::Network(format!("Grid Download 시작 실패: {}", e)))?;
+
+        info!("🔗 Grid Download 피어 {}개 연결 시도", addrs.len());
+        state
+            .grid_jobs
+            .connect_peers(&job_id, addrs)
+            .await
+            .map_err(|e| AppError::Network(format!("피어 연결 요청 실패: {}", e)))?;
+
+        serde_json::to_value(&info).map_err(|e| AppError::Internal(e.to_string()))
+    }
+    #[cfg(not(feature = "grid-experimental"))]
+    {
+        let _ = (app, source, save_dir, peers, state);
+        Err(AppError::Unsupported(GRID_DISABLED.into()))
+    }
+}
+
+/// 🆕 Grid 작업 일시정지
+#[tauri::command]
+async fn pause_grid_job(job_id: String, state: tauri::State<'_, AppState>) -> Result<(), AppError> {
+    #[cfg(feature = "grid-experimental")]
+    {
+        state
+            .grid_jobs
+            .send(&job_id, grid::swarm::SwarmCommand::Pause)
+            .await
+            .map_err(|e| AppError::Network(format!("Grid 작업 일시정지 실패: {}", e)))
+    }
+    #[cfg(not(feature = "grid-experimental"))]
+    {
+        let _ = (job_id, state);
+        Err(AppError::Unsupported(GRID_DISABLED.into()))
+    }
+}
+
+/// 🆕 Grid 작업 재개
+#[tauri::command]
+async fn resume_grid_job(
+    job_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), AppError> {
+    #[cfg(feature = "grid-experimental")]
+    {
+        state
+            .grid_jobs
+            .send(&job_id, grid::swarm::SwarmCommand::Resume)
+            .await
+            .map_err(|e| AppError::Network(format!("Grid 작업 재개 실패: {}", e)))
+    }
+    #[cfg(not(feature = "grid-experimental"))]
+    {
+        let _ = (job_id, state);
+        Err(AppError::Unsupported(GRID_DISABLED.into()))
+    }
+}
+
+/// 🆕 Grid 작업 중지
+///
+/// `delete_partial`이 true면 완료되지 않은 다운로드 파일과 재개 상태를 삭제합니다.
+#[tauri::command]
+async fn stop_grid_job(
+    job_id: String,
+    delete_partial: Option<bool>,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), AppError> {
+    #[cfg(feature = "grid-experimental")]
+    {
+        let seeded_hash = state
+            .grid_jobs
+            .list()
+            .await
+            .into_iter()
+            .find(|job| job.job_id == job_id && job.role == grid::job::GridRole::Seed)
+            .and_then(|job| hex::decode(job.info_hash).ok())
+            .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok());
+
+        state
+            .grid_jobs
+            .stop(&job_id, delete_partial.unwrap_or(false))
+            .await
+            .map_err(|e| AppError::Network(format!("Grid 작업 중지 실패: {}", e)))?;
+        state.grid_rate_limits.write().await.remove(&job_id);
+
+        // 더 이상 제공하지 않으므로 DHT 재광고 중단
+        if let Some(info_hash) = seeded_hash {
+            let dht = state
+                .embedded_bootstrap
+                .read()
+                .await
+                .as_ref()
+                .and_then(|service| service.dht_handle());
+            if let Some(dht) = dht {
+                let _ = dht.stop_providing(info_hash).await;
+            }
+        }
+        Ok(())
+    }
+    #[cfg(not(feature = "grid-experimental"))]
+    {
+        let _ = (job_id, delete_partial, state);
+        Err(AppError::Unsupported(GRID_DISABLED.into()))
+    }
+}
+
+/// 🆕 실행 중인 Grid 작업 목록
+#[tauri::command]
+async fn get_grid_jobs(state: tauri::State<'_, AppState>) -> Result<serde_json::Value, AppError> {
+    #[cfg(feature = "grid-experimental")]
+    {
+        serde_json::to_value(state.grid_jobs.list().await)
+            .map_err(|e| AppError::Internal(e.to_string()))
+    }
+    #[cfg(not(feature = "grid-experimental"))]
+    {
+        let _ = state;
+        Ok(serde_json::json!([]))
+    }
+}
 
-    let metadata = FileMetadata::from_file(&path, piece_size)
-        .await
-        .map_err(|e| format!("메타데이터 생성 실패: {}", e))?;
+/// 🆕 Grid 차단 피어 목록
+#[tauri::command]
+async fn get_grid_ban_list(
+    state: tauri::State<'_, AppState>,
+) -> Result<serde_json::Value, AppError> {
+    #[cfg(feature = "grid-experimental")]
+    {
+        serde_json::to_value(state.grid_ban_list.list())
+            .map_err(|e| AppError::Internal(e.to_string()))
+    }
+    #[cfg(not(feature = "grid-experimental"))]
+    {
+        let _ = state;
+        Ok(serde_json::json!([]))
+    }
+}
 
-    Ok(serde_json::json!({
-        "infoHash": hex::encode(metadata.info_hash),
-        "fileName": metadata.file_name,
-        "fileSize": metadata.file_size,
-        "pieceSize": metadata.piece_size,
-        "totalPieces": metadata.total_pieces,
-        "merkleRoot": metadata.merkle_root.map(|r| hex::encode(r)),
-    }))
+/// 🆕 Grid 피어 차단 해제
+#[tauri::command]
+async fn unban_grid_peer(state: tauri::State<'_, AppState>, ip: String) -> Result<bool, AppError> {
+    let ip: std::net::IpAddr = ip
+        .parse()
+        .map_err(|e| AppError::InvalidInput(format!("IP 주소 파싱 실패: {}", e)))?;
+    #[cfg(feature = "grid-experimental")]
+    {
+        Ok(state.grid_ban_list.unban(&ip))
+    }
+    #[cfg(not(feature = "grid-experimental"))]
+    {
+        let _ = (state, ip);
+        Err(AppError::Unsupported(GRID_DISABLED.into()))
+    }
 }
 
 /// DHT 부트스트랩 노드에 연결
 #[tauri::command]
-async fn connect_bootstrap_node(address: String) -> Result<bool, String> {
+async fn connect_bootstrap_node(address: String) -> Result<bool, AppError> {
     let addr: std::net::SocketAddr = address
         .parse()
-        .map_err(|e| format!("주소 파싱 실패: {}", e))?;
+        .map_err(|e| AppError::InvalidInput(format!("주소 파싱 실패: {}", e)))?;
 
     info!("🔗 DHT 부트스트랩 노드 연결: {}", addr);
 
@@ -894,7 +4211,7 @@ async fn connect_bootstrap_node(address: String) -> Result<bool, String> {
 
 /// DHT 부트스트랩 노드 목록 설정
 #[tauri::command]
-async fn set_bootstrap_nodes(addresses: Vec<String>) -> Result<usize, String> {
+async fn set_bootstrap_nodes(addresses: Vec<String>) -> Result<usize, AppError> {
     let mut valid_count = 0;
 
     for addr_str in &addresses {
@@ -914,18 +4231,18 @@ async fn set_bootstrap_nodes(addresses: Vec<String>) -> Result<usize, String> {
 
 /// 부트스트랩 노드 자동 발견 (mDNS)
 #[tauri::command]
-async fn discover_bootstrap_nodes() -> Result<Vec<serde_json::Value>, String> {
+async fn discover_bootstrap_nodes() -> Result<Vec<serde_json::Value>, AppError> {
     use grid::bootstrap_discovery::AutoBootstrap;
 
     info!("🔍 부트스트랩 노드 자동 발견 시작...");
 
-    let mut auto_bootstrap =
-        AutoBootstrap::new().map_err(|e| format!("AutoBootstrap 생성 실패: {}", e))?;
+    let mut auto_bootstrap = AutoBootstrap::new()
+        .map_err(|e| AppError::Network(format!("AutoBootstrap 생성 실패: {}", e)))?;
 
     let nodes = auto_bootstrap
         .start()
         .await
-        .map_err(|e| format!("부트스트랩 발견 실패: {}", e))?;
+        .map_err(|e| AppError::Network(format!("부트스트랩 발견 실패: {}", e)))?;
 
     let result: Vec<serde_json::Value> = nodes
         .iter()
@@ -943,9 +4260,156 @@ async fn discover_bootstrap_nodes() -> Result<Vec<serde_json::Value>, String> {
     Ok(result)
 }
 
+/// 🆕 이 기기의 시그널링 노드 ID (오프라인 우편함 수신 주소)
+#[tauri::command]
+async fn get_signaling_node_id(state: tauri::State<'_, AppState>) -> Result<String, AppError> {
+    Ok(state.signing_identity.node_id())
+}
+
+/// 🆕 오프라인 피어 앞으로 시그널링 메시지(Offer/Answer/Bye 등)를 부트스트랩 우편함에 맡기기
+///
+/// `bootstrap_addr`는 부트스트랩의 QUIC 릴레이 주소, `ttl_secs`는 보관 시간 (기본 1시간, 최대 24시간)
+#[tauri::command]
+async fn queue_offline_signal(
+    bootstrap_addr: String,
+    to_node_id: String,
+    command: Command,
+    ttl_secs: Option<u64>,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), AppError> {
+    use bootstrap::mailbox::{self, DEFAULT_SIGNAL_TTL};
+
+    let addr: SocketAddr = bootstrap_addr
+        .parse()
+        .map_err(|e| AppError::InvalidInput(format!("잘못된 부트스트랩 주소: {}", e)))?;
+    let ttl = ttl_secs
+        .map(std::time::Duration::from_secs)
+        .unwrap_or(DEFAULT_SIGNAL_TTL);
+    let signal = state
+        .signing_identity
+        .sign_signal(&to_node_id, command, ttl)
+        .map_err(|e| AppError::Crypto(format!("시그널 서명 실패: {}", e)))?;
+    mailbox::deposit(addr, signal)
+        .await
+        .map_err(|e| AppError::Network(format!("시그널 보관 실패: {}", e)))?;
+
+    info!(
+        "📮 오프라인 시그널 보관: {} @ {}",
+        to_node_id, bootstrap_addr
+    );
+    Ok(())
+}
+
+/// 🆕 부트스트랩 우편함에 쌓인 시그널 가져오기 (서명 검증된 것만, 가져간 시그널은 삭제됨)
+#[tauri::command]
+async fn poll_offline_signals(
+    bootstrap_addr: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<bootstrap::mailbox::SignedSignal>, AppError> {
+    let addr: SocketAddr = bootstrap_addr
+        .parse()
+        .map_err(|e| AppError::InvalidInput(format!("잘못된 부트스트랩 주소: {}", e)))?;
+    let signals = bootstrap::mailbox::poll(addr, &state.signing_identity)
+        .await
+        .map_err(|e| AppError::Network(format!("우편함 조회 실패: {}", e)))?;
+
+    info!("📬 오프라인 시그널 {}개 수신", signals.len());
+    Ok(signals)
+}
+
+/// 🆕 부트스트랩 접속 명부에 이 기기 등록 시작 (QUIC 서버가 실행 중이어야 함)
+///
+/// 노드 ID와 연결 후보를 `PRESENCE_REFRESH_INTERVAL`마다 다시 등록하며, 다른 부트스트랩으로 시작하면 이전 등록 작업은 중단됩니다.
+#[tauri::command]
+async fn start_presence(
+    bootstrap_addr: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), AppError> {
+    use bootstrap::presence::{self, PRESENCE_REFRESH_INTERVAL};
+
+    let bootstrap: SocketAddr = bootstrap_addr
+        .parse()
+        .map_err(|e| AppError::InvalidInput(format!("잘못된 부트스트랩 주소: {}", e)))?;
+    let candidates = local_candidates(&state).await;
+    if candidates.is_empty() {
+        return Err(AppError::NotRunning("QUIC 서버를 먼저 시작하세요".into()));
+    }
+    presence::register(bootstrap, &state.signing_identity, candidates)
+        .await
+        .map_err(|e| AppError::Network(format!("접속 등록 실패: {}", e)))?;
+
+    // 후보(인터페이스, STUN 매핑)가 바뀌어도 다음 갱신에 반영됨
+    let app_handle = state.app_handle.clone();
+    let task = tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(PRESENCE_REFRESH_INTERVAL);
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            let Some(state) = app_handle.try_state::<AppState>() else {
+                return;
+            };
+            let candidates = local_candidates(&state).await;
+            if candidates.is_empty() {
+                continue;
+            }
+            if let Err(e) = presence::register(bootstrap, &state.signing_identity, candidates).await
+            {
+                warn!("접속 등록 갱신 실패 ({}): {}", bootstrap, e);
+            }
+        }
+    });
+    state.presence.replace(bootstrap, task);
+    Ok(())
+}
+
+/// 🆕 접속 명부 등록 중단 (부트스트랩에 오프라인 알림)
+#[tauri::command]
+async fn stop_presence(state: tauri::State<'_, AppState>) -> Result<(), AppError> {
+    let Some(bootstrap) = state.presence.stop() else {
+        return Ok(());
+    };
+    bootstrap::presence::register(bootstrap, &state.signing_identity, Vec::new())
+        .await
+        .map_err(|e| AppError::Network(format!("오프라인 알림 실패: {}", e)))?;
+
+    info!("📇 접속 등록 중단: {}", bootstrap);
+    Ok(())
+}
+
+/// 🆕 알고 있는 연락처(노드 ID) 중 지금 접속 중인 피어와 연결 후보 조회
+///
+/// `bootstrap_addr`를 생략하면 `start_presence`로 등록 중인 부트스트랩에 묻습니다.
+#[tauri::command]
+async fn get_roster(
+    node_ids: Vec<String>,
+    bootstrap_addr: Option<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<bootstrap::presence::PresenceEntry>, AppError> {
+    let bootstrap: SocketAddr = match bootstrap_addr {
+        Some(addr) => addr
+            .parse()
+            .map_err(|e| AppError::InvalidInput(format!("잘못된 부트스트랩 주소: {}", e)))?,
+        None => state
+            .presence
+            .bootstrap()
+            .ok_or_else(|| AppError::NotRunning("접속 등록을 먼저 시작하세요".into()))?,
+    };
+    let online = bootstrap::presence::lookup(bootstrap, node_ids)
+        .await
+        .map_err(|e| AppError::Network(format!("접속 명부 조회 실패: {}", e)))?;
+
+    for entry in &online {
+        state
+            .contacts
+            .record_candidates(&entry.node_id, &entry.candidates);
+    }
+    debug!("📇 접속 중인 연락처 {}명", online.len());
+    Ok(online)
+}
+
 /// 🆕 네트워크 인터페이스 조회
 #[tauri::command]
-async fn get_network_interfaces() -> Result<Vec<String>, String> {
+async fn get_network_interfaces() -> Result<Vec<String>, AppError> {
     use std::net::IpAddr;
     use std::process::Command;
 
@@ -1023,7 +4487,7 @@ async fn connect_via_relay(
     peer_id: String,
     relay_url: String,
     state: tauri::State<'_, AppState>,
-) -> Result<bool, String> {
+) -> Result<bool, AppError> {
     info!("[Relay] Connecting via relay: {} @ {}", peer_id, relay_url);
 
     let success = true;
@@ -1033,7 +4497,7 @@ async fn connect_via_relay(
 }
 
 #[tauri::command]
-async fn get_public_ip(stun_server: String) -> Result<String, String> {
+async fn get_public_ip(stun_server: String) -> Result<String, AppError> {
     info!("[Network] Requesting public IP via STUN: {}", stun_server);
 
     use std::net::UdpSocket;
@@ -1041,10 +4505,10 @@ async fn get_public_ip(stun_server: String) -> Result<String, String> {
         Ok(socket) => {
             socket
                 .connect("1.1.1.1:80")
-                .map_err(|e| format!("Connection failed: {}", e))?;
+                .map_err(|e| AppError::Network(format!("Connection failed: {}", e)))?;
             let local_addr = socket
                 .local_addr()
-                .map_err(|e| format!("Failed to get local addr: {}", e))?;
+                .map_err(|e| AppError::Network(format!("Failed to get local addr: {}", e)))?;
             let ip = local_addr.ip();
 
             info!("[Network] ✅ Detected public IP: {}", ip);
@@ -1052,7 +4516,7 @@ async fn get_public_ip(stun_server: String) -> Result<String, String> {
         }
         Err(e) => {
             warn!("[Network] ❌ UDP socket creation failed: {}", e);
-            Err("UDP socket creation failed".to_string())
+            Err(AppError::Network("UDP socket creation failed".into()))
         }
     }
 }
@@ -1066,7 +4530,7 @@ async fn start_file_stream(
     save_path: String,
     _total_size: Option<u64>,
     _state: tauri::State<'_, AppState>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     use std::collections::HashMap;
 
     // 파일 상태 관리를 위한 전역 상태 추가
@@ -1075,7 +4539,8 @@ async fn start_file_stream(
     }
 
     // AppState에 스트리밍 상태 추가 (기존 코드와 호환성 유지)
-    let _file = std::fs::File::create(&save_path).map_err(|e| format!("파일 생성 실패: {}", e))?;
+    let _file = std::fs::File::create(&save_path)
+        .map_err(|e| AppError::Io(format!("파일 생성 실패: {}", e)))?;
 
     info!("📝 파일 스트리밍 시작: {} -> {}", file_id, save_path);
 
@@ -1090,7 +4555,7 @@ async fn write_file_chunk(
     file_id: String,
     chunk: Vec<u8>,
     offset: Option<u64>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     use std::fs::OpenOptions;
     use std::io::{Seek, SeekFrom, Write};
 
@@ -1100,27 +4565,30 @@ async fn write_file_chunk(
         .write(true)
         .create(true)
         .open(&format!("/tmp/ponswarp_{}", file_id))
-        .map_err(|e| format!("파일 열기 실패: {}", e))?;
+        .map_err(|e| AppError::Io(format!("파일 열기 실패: {}", e)))?;
 
     // 오프셋이 지정된 경우 해당 위치로 이동
     if let Some(off) = offset {
         file.seek(SeekFrom::Start(off))
-            .map_err(|e| format!("파일 위치 이동 실패: {}", e))?;
+            .map_err(|e| AppError::Io(format!("파일 위치 이동 실패: {}", e)))?;
     }
 
     // 청크 쓰기
     file.write_all(&chunk)
-        .map_err(|e| format!("청크 쓰기 실패: {}", e))?;
+        .map_err(|e| AppError::Io(format!("청크 쓰기 실패: {}", e)))?;
 
     file.sync_all()
-        .map_err(|e| format!("디스크 동기화 실패: {}", e))?;
+        .map_err(|e| AppError::Io(format!("디스크 동기화 실패: {}", e)))?;
 
     Ok(())
 }
 
 /// 🆕 파일 스트리밍 완료
 #[tauri::command]
-async fn complete_file_stream(file_id: String, final_size: Option<u64>) -> Result<String, String> {
+async fn complete_file_stream(
+    file_id: String,
+    final_size: Option<u64>,
+) -> Result<String, AppError> {
     info!(
         "✅ 파일 스트리밍 완료: {} (size: {:?})",
         file_id, final_size
@@ -1130,7 +4598,7 @@ async fn complete_file_stream(file_id: String, final_size: Option<u64>) -> Resul
 
     // 실제 구에서는 임시 파일을 최종 위치로 이동
     std::fs::rename(format!("/tmp/ponswarp_{}", file_id), &final_path)
-        .map_err(|e| format!("파일 이동 실패: {}", e))?;
+        .map_err(|e| AppError::Io(format!("파일 이동 실패: {}", e)))?;
 
     Ok(final_path)
 }
@@ -1140,7 +4608,7 @@ async fn complete_file_stream(file_id: String, final_size: Option<u64>) -> Resul
 async fn create_save_dialog(
     default_name: Option<String>,
     window: tauri::Window,
-) -> Result<Option<String>, String> {
+) -> Result<Option<String>, AppError> {
     use tauri_plugin_dialog::DialogExt;
 
     let (tx, rx) = tokio::sync::oneshot::channel();
@@ -1155,7 +4623,7 @@ async fn create_save_dialog(
 
     let file_path = rx
         .await
-        .map_err(|e| format!("다이얼로그 채널 오류: {}", e))?;
+        .map_err(|e| AppError::Internal(format!("다이얼로그 채널 오류: {}", e)))?;
 
     match file_path {
         Some(path) => Ok(Some(path.to_string())),
@@ -1165,7 +4633,7 @@ async fn create_save_dialog(
 
 /// 🆕 저장 폴더 선택 다이얼로그
 #[tauri::command]
-async fn select_save_directory(window: tauri::Window) -> Result<Option<String>, String> {
+async fn select_save_directory(window: tauri::Window) -> Result<Option<String>, AppError> {
     use tauri_plugin_dialog::DialogExt;
 
     let (tx, rx) = tokio::sync::oneshot::channel();
@@ -1176,7 +4644,7 @@ async fn select_save_directory(window: tauri::Window) -> Result<Option<String>,
 
     let folder_path = rx
         .await
-        .map_err(|e| format!("폴더 선택 채널 오류: {}", e))?;
+        .map_err(|e| AppError::Internal(format!("폴더 선택 채널 오류: {}", e)))?;
 
     match folder_path {
         Some(path) => Ok(Some(path.to_string())),
@@ -1184,15 +4652,20 @@ async fn select_save_directory(window: tauri::Window) -> Result<Option<String>,
     }
 }
 
-/// 🆕 저장 가능한 공간 확인
+/// 🆕 저장 가능한 공간 확인 (`path`가 있는 볼륨, 아직 없는 경로는 가장 가까운 상위 폴더 기준)
 #[tauri::command]
-async fn check_storage_space(path: String) -> Result<serde_json::Value, String> {
-    // 간단한 fallback 구현 (실제 저장 공간 확인은 복잡성을 위해 생략)
+async fn check_storage_space(path: String) -> Result<serde_json::Value, AppError> {
+    let (available, total) =
+        tokio::task::spawn_blocking(move || transfer::disk_space::volume_space(path.as_ref()))
+            .await
+            .map_err(|e| AppError::Internal(format!("저장 공간 조회 작업 실패: {}", e)))?
+            .map_err(|e| AppError::Io(format!("저장 공간 조회 실패: {}", e)))?;
+    const GB: f64 = 1024.0 * 1024.0 * 1024.0;
     Ok(serde_json::json!({
-        "availableBytes": 100 * 1024 * 1024 * 1024, // 100GB
-        "totalBytes": 500 * 1024 * 1024 * 1024,     // 500GB
-        "availableGB": 100.0,
-        "totalGB": 500.0,
+        "availableBytes": available,
+        "totalBytes": total,
+        "availableGB": available as f64 / GB,
+        "totalGB": total as f64 / GB,
     }))
 }
 
@@ -1203,7 +4676,7 @@ async fn send_signaling_message(
     peer_id: String,
     message: Command,
     state: tauri::State<'_, AppState>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     let discovery = state.discovery.read().await;
     let mut client = state.quic_client.write().await;
 
@@ -1212,29 +4685,39 @@ async fn send_signaling_message(
             let peer_addr = peer_info.address;
 
             if client.is_none() {
-                *client = Some(QuicClient::new());
+                *client = Some(new_quic_client(
+                    &state,
+                    state.quic_pacing.read().await.clone(),
+                ));
             }
 
             if let Some(ref mut c) = *client {
                 let conn = c
                     .connect(peer_addr, &peer_id)
                     .await
-                    .map_err(|e| format!("QUIC 연결 실패: {}", e))?;
+                    .map_err(|e| AppError::Network(format!("QUIC 연결 실패: {}", e)))?;
 
                 c.send_command(&conn, message)
                     .await
-                    .map_err(|e| format!("시그널링 메시지 전송 실패: {}", e))?;
+                    .map_err(|e| AppError::Network(format!("시그널링 메시지 전송 실패: {}", e)))?;
 
                 info!("✅ 시그널링 메시지를 {}로 전송함", peer_id);
                 Ok(())
             } else {
-                Err("QUIC 클라이언트를 초기화할 수 없음".to_string())
+                Err(AppError::Internal(
+                    "QUIC 클라이언트를 초기화할 수 없음".into(),
+                ))
             }
         } else {
-            Err(format!("피어 {}를 찾을 수 없음", peer_id))
+            Err(AppError::NotFound(format!(
+                "피어 {}를 찾을 수 없음",
+                peer_id
+            )))
         }
     } else {
-        Err("Discovery 서비스가 실행되고 있지 않음".to_string())
+        Err(AppError::NotRunning(
+            "Discovery 서비스가 실행되고 있지 않음".into(),
+        ))
     }
 }
 
@@ -1242,25 +4725,37 @@ async fn send_signaling_message(
 async fn handle_signaling_message(
     message: Command,
     state: tauri::State<'_, AppState>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     info!("📨 수신된 시그널링 메시지: {:?}", message);
 
+    // 🆕 함께 동작할 수 없는 버전의 시그널링은 거부
+    if let Some(version) = message.signaling_version() {
+        if crate::protocol::negotiate_signaling_version(version).is_none() {
+            return Err(AppError::InvalidInput(format!(
+                "지원하지 않는 시그널링 버전: {}",
+                version
+            )));
+        }
+    }
+
     // 🆕 프론트엔드로 시그널링 이벤트 발생
     let event_name = match message {
         Command::Offer { .. } => "signaling-offer",
         Command::Answer { .. } => "signaling-answer",
         Command::IceCandidate { .. } => "signaling-ice-candidate",
+        Command::Renegotiate { .. } => "signaling-renegotiate",
+        Command::Bye { .. } => "signaling-bye",
         _ => "signaling-unknown", // 다른 명령은 무시하거나 별도 처리
     };
 
     // 메시지를 JSON으로 변환하여 프론트엔드로 전송
     let payload = serde_json::to_value(&message)
-        .map_err(|e| format!("시그널링 메시지 직렬화 실패: {}", e))?;
+        .map_err(|e| AppError::Internal(format!("시그널링 메시지 직렬화 실패: {}", e)))?;
 
     state
         .app_handle
         .emit(event_name, &payload)
-        .map_err(|e| format!("프론트엔드 이벤트 발생 실패: {}", e))?;
+        .map_err(|e| AppError::Internal(format!("프론트엔드 이벤트 발생 실패: {}", e)))?;
 
     info!("✅ 프론트엔드로 이벤트 발생: {}", event_name);
 
@@ -1269,6 +4764,21 @@ async fn handle_signaling_message(
 
 // --- Embedded Bootstrap Commands ---
 
+/// 앱 데이터 디렉토리를 사용하는 부트스트랩 서비스 생성 (DHT 라우팅 테이블 유지)
+fn new_bootstrap_service(
+    app: &AppHandle,
+    config: bootstrap::BootstrapConfig,
+) -> bootstrap::EmbeddedBootstrapService {
+    let service = bootstrap::EmbeddedBootstrapService::new(config);
+    match app.path().app_data_dir() {
+        Ok(dir) => service.with_data_dir(dir),
+        Err(e) => {
+            warn!("앱 데이터 경로 조회 실패, DHT 라우팅 테이블 저장 안 함: {}", e);
+            service
+        }
+    }
+}
+
 /// 부트스트랩 자동 시작 (앱 시작 시)
 async fn auto_start_bootstrap(app_handle: AppHandle) -> anyhow::Result<()> {
     use tauri::Manager;
@@ -1289,7 +4799,7 @@ async fn auto_start_bootstrap(app_handle: AppHandle) -> anyhow::Result<()> {
     let mut bootstrap_guard = state.embedded_bootstrap.write().await;
 
     // 서비스 생성 및 시작
-    let mut service = bootstrap::EmbeddedBootstrapService::new(config.clone());
+    let mut service = new_bootstrap_service(&app_handle, config.clone());
 
     match service.start().await {
         Ok(ports) => {
@@ -1337,7 +4847,7 @@ async fn auto_start_bootstrap(app_handle: AppHandle) -> anyhow::Result<()> {
 async fn start_embedded_bootstrap(
     config: Option<bootstrap::BootstrapConfig>,
     state: tauri::State<'_, AppState>,
-) -> Result<bootstrap::BoundPorts, String> {
+) -> Result<bootstrap::BoundPorts, AppError> {
     info!("🚀 내장 부트스트랩 시작 요청");
 
     let config = config.unwrap_or_default();
@@ -1345,23 +4855,25 @@ async fn start_embedded_bootstrap(
     // 설정 검증
     config
         .validate()
-        .map_err(|e| format!("설정 검증 실패: {}", e))?;
+        .map_err(|e| AppError::InvalidInput(format!("설정 검증 실패: {}", e)))?;
 
     let mut bootstrap_guard = state.embedded_bootstrap.write().await;
 
     // 이미 실행 중인지 확인
     if let Some(ref service) = *bootstrap_guard {
         if service.state().await != bootstrap::ServiceState::Stopped {
-            return Err("부트스트랩 서비스가 이미 실행 중입니다".to_string());
+            return Err(AppError::InvalidInput(
+                "부트스트랩 서비스가 이미 실행 중입니다".into(),
+            ));
         }
     }
 
     // 새 서비스 생성 및 시작
-    let mut service = bootstrap::EmbeddedBootstrapService::new(config);
+    let mut service = new_bootstrap_service(&state.app_handle, config);
     let ports = service
         .start()
         .await
-        .map_err(|e| format!("부트스트랩 시작 실패: {}", e))?;
+        .map_err(|e| AppError::Network(format!("부트스트랩 시작 실패: {}", e)))?;
 
     // 상태 변경 이벤트 발생
     let _ = state.app_handle.emit(
@@ -1384,7 +4896,7 @@ async fn start_embedded_bootstrap(
 
 /// 내장 부트스트랩 서비스 중지
 #[tauri::command]
-async fn stop_embedded_bootstrap(state: tauri::State<'_, AppState>) -> Result<(), String> {
+async fn stop_embedded_bootstrap(state: tauri::State<'_, AppState>) -> Result<(), AppError> {
     info!("🛑 내장 부트스트랩 중지 요청");
 
     let mut bootstrap_guard = state.embedded_bootstrap.write().await;
@@ -1393,7 +4905,7 @@ async fn stop_embedded_bootstrap(state: tauri::State<'_, AppState>) -> Result<()
         service
             .stop()
             .await
-            .map_err(|e| format!("부트스트랩 중지 실패: {}", e))?;
+            .map_err(|e| AppError::Network(format!("부트스트랩 중지 실패: {}", e)))?;
 
         // 상태 변경 이벤트 발생
         let _ = state.app_handle.emit(
@@ -1414,7 +4926,7 @@ async fn stop_embedded_bootstrap(state: tauri::State<'_, AppState>) -> Result<()
 #[tauri::command]
 async fn get_embedded_bootstrap_status(
     state: tauri::State<'_, AppState>,
-) -> Result<bootstrap::BootstrapStatus, String> {
+) -> Result<bootstrap::BootstrapStatus, AppError> {
     let bootstrap_guard = state.embedded_bootstrap.read().await;
 
     if let Some(ref service) = *bootstrap_guard {
@@ -1430,11 +4942,15 @@ async fn get_embedded_bootstrap_status(
                 providers_stored: 0,
                 messages_received: 0,
                 messages_sent: 0,
+                messages_dropped_flood: 0,
+                messages_dropped_malformed: 0,
             },
             relay_stats: bootstrap::RelayStats {
                 active_sessions: 0,
                 total_connections: 0,
                 bytes_relayed: 0,
+                sessions_evicted_idle: 0,
+                sessions_evicted_lifetime: 0,
             },
             connected_bootstrap_nodes: 0,
             discovered_peers: 0,
@@ -1444,19 +4960,31 @@ async fn get_embedded_bootstrap_status(
     }
 }
 
+/// Stats API 토큰 조회 (로컬 UI가 `/stats` 요청에 사용)
+#[tauri::command]
+async fn get_bootstrap_stats_token(
+    state: tauri::State<'_, AppState>,
+) -> Result<Option<String>, AppError> {
+    let bootstrap_guard = state.embedded_bootstrap.read().await;
+    match *bootstrap_guard {
+        Some(ref service) => Ok(service.stats_api_token().await),
+        None => Ok(None),
+    }
+}
+
 /// 부트스트랩 설정 업데이트
 #[tauri::command]
 async fn update_bootstrap_config(
     config: bootstrap::BootstrapConfig,
     restart: bool,
     state: tauri::State<'_, AppState>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     info!("🔧 부트스트랩 설정 업데이트");
 
     // 설정 검증
     config
         .validate()
-        .map_err(|e| format!("설정 검증 실패: {}", e))?;
+        .map_err(|e| AppError::InvalidInput(format!("설정 검증 실패: {}", e)))?;
 
     let mut bootstrap_guard = state.embedded_bootstrap.write().await;
 
@@ -1468,7 +4996,7 @@ async fn update_bootstrap_config(
             service
                 .stop()
                 .await
-                .map_err(|e| format!("부트스트랩 중지 실패: {}", e))?;
+                .map_err(|e| AppError::Network(format!("부트스트랩 중지 실패: {}", e)))?;
         }
 
         service.update_config(config.clone()).await;
@@ -1478,11 +5006,11 @@ async fn update_bootstrap_config(
             service
                 .start()
                 .await
-                .map_err(|e| format!("부트스트랩 재시작 실패: {}", e))?;
+                .map_err(|e| AppError::Network(format!("부트스트랩 재시작 실패: {}", e)))?;
         }
     } else {
         // 서비스가 없으면 새로 생성 (시작하지 않음)
-        *bootstrap_guard = Some(bootstrap::EmbeddedBootstrapService::new(config));
+        *bootstrap_guard = Some(new_bootstrap_service(&state.app_handle, config));
     }
 
     info!("✅ 부트스트랩 설정 업데이트 완료");
@@ -1499,14 +5027,18 @@ async fn send_zip_stream_transfer(
     job_id: String,
     compression_level: Option<u32>,
     transfer_type: Option<String>,
+    encrypt: Option<bool>,
     state: tauri::State<'_, AppState>,
-) -> Result<u64, String> {
+) -> Result<u64, AppError> {
+    let cipher = send_cipher(&state, &peer_id, encrypt)?;
     // 연결 가져오기
     let conn = {
         let connections = state.accepted_connections.read().await;
         connections
             .get(&peer_id)
-            .ok_or_else(|| format!("피어 {}에 대한 연결이 없습니다.", peer_id))?
+            .ok_or_else(|| AppError::PeerNotConnected {
+                peer_id: peer_id.to_string(),
+            })?
             .clone()
     };
 
@@ -1544,16 +5076,22 @@ async fn send_zip_stream_transfer(
                 .and_then(|v| v.as_u64())
                 .unwrap_or(0);
 
+            let symlink_target = f
+                .get("symlinkTarget")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string());
+
             Some(FileEntry {
                 absolute_path,
                 relative_path,
                 size,
+                symlink_target,
             })
         })
         .collect();
 
     if file_entries.is_empty() {
-        return Err("전송할 파일이 없습니다.".to_string());
+        return Err(AppError::InvalidInput("전송할 파일이 없습니다.".into()));
     }
 
     // 설정
@@ -1565,65 +5103,65 @@ async fn send_zip_stream_transfer(
     // 진행률 채널 설정
     let (tx, mut rx) = mpsc::channel::<TransferProgress>(100);
 
-    // 취소 토큰 생성 및 등록
-    let is_cancelled = Arc::new(AtomicBool::new(false));
-    let job_control = JobControl {
-        is_cancelled: is_cancelled.clone(),
-    };
-    state
-        .active_jobs
-        .write()
-        .await
-        .insert(job_id.clone(), job_control);
-
-    // Job Cleanup Guard (함수 종료 시 자동 제거)
-    // Rust의 Drop을 이용하거나, ensure logic 사용. 여기서는 명시적 제거 사용.
-
-    // Sender 설정 (with_cancellation은 zip_stream.rs에 추가해야 함)
+    // 작업 등록 (취소 플래그는 레지스트리의 제어 핸들과 공유)
+    let control = state
+        .transfer_registry
+        .register(&job_id, &peer_id, TransferKind::Zip)?;
     let sender = ZipStreamSender::new(config)
         .with_progress_channel(tx)
-        .with_cancellation(is_cancelled);
+        .with_cancellation(control.cancel_flag())
+        .with_cipher(cipher);
 
-    // 진행률 이벤트 전송
-    let app_handle = state.app_handle.clone();
+    // 진행률을 레지스트리에 반영 (이벤트는 레지스트리가 발송)
+    let registry = state.transfer_registry.clone();
     tauri::async_runtime::spawn(async move {
         while let Some(progress) = rx.recv().await {
-            let _ = app_handle.emit("transfer-progress", &progress);
+            registry.update_progress(
+                &progress.job_id,
+                progress.bytes_transferred,
+                progress.total_bytes,
+                progress.speed_bps,
+            );
         }
     });
 
-    // 전송 실행
-    let result = sender.send_zip_stream(&conn, file_entries, &job_id).await;
-
-    // 작업 등록 해제
-    state.active_jobs.write().await.remove(&job_id);
+    // 전송 실행 (여러 파일을 묶어 보내므로 감사 로그에는 해시 없이 기록)
+    audit_subject(&state, &job_id, AuditDirection::Send, &conn, None);
+    let result = sender
+        .send_zip_stream(&conn, file_entries, &job_id)
+        .await
+        .map_err(|e| transfer_error("Zip 스트리밍 전송 실패", e));
 
-    let bytes_sent = result.map_err(|e| format!("Zip 스트리밍 전송 실패: {}", e))?;
+    // 작업 종료 기록
+    state
+        .transfer_registry
+        .finish(&job_id, result.as_ref().map(|_| ()).map_err(|e| e.clone()));
 
-    // 완료 이벤트
-    let _ = state.app_handle.emit(
-        "transfer-complete",
-        serde_json::json!({
-            "jobId": job_id,
-            "bytesSent": bytes_sent,
-            "peerId": peer_id,
-        }),
-    );
+    let bytes_sent = result?;
 
     info!("✅ Zip 스트리밍 전송 완료: {} bytes", bytes_sent);
     Ok(bytes_sent)
 }
 
 /// 🆕 폴더 전송 (Sender)
+///
+/// 🆕 압축 수준/심볼릭 링크 처리/포함·제외 패턴/암호화는 `options`로 받습니다.
 #[tauri::command]
 async fn send_folder_transfer(
     peer_id: String,
     folder_path: String,
     job_id: String,
-    compression_level: Option<u32>,
+    options: Option<transfer::FolderSendOptions>,
     state: tauri::State<'_, AppState>,
-) -> Result<u64, String> {
+) -> Result<u64, AppError> {
     info!("📁 폴더 전송 시작: {} -> {}", folder_path, peer_id);
+    let transfer::FolderSendOptions {
+        compression_level,
+        symlink_policy,
+        include,
+        exclude,
+        encrypt,
+    } = options.unwrap_or_default();
 
     let folder_name = std::path::Path::new(&folder_path)
         .file_name()
@@ -1631,10 +5169,10 @@ async fn send_folder_transfer(
         .to_string_lossy()
         .to_string();
 
-    let files = scan_folder(folder_path.clone()).map_err(|e| format!("폴더 스캔 실패: {}", e))?;
+    let files = scan_folder(folder_path.clone(), symlink_policy, include, exclude)?;
 
     if files.is_empty() {
-        return Err("전송할 파일이 없습니다.".to_string());
+        return Err(AppError::InvalidInput("전송할 파일이 없습니다.".into()));
     }
 
     info!("📂 {} 개 파일 스캔 완료", files.len());
@@ -1645,11 +5183,120 @@ async fn send_folder_transfer(
         job_id,
         compression_level,
         Some("folder".to_string()),
+        encrypt,
         state,
     )
     .await
 }
 
+/// 🆕 스트리밍 매니페스트 폴더 전송 (Sender)
+///
+/// 폴더 전체를 스캔하거나 Zip으로 묶지 않고, 찾은 항목을 묶음으로 보내면서 파일 전송을 바로 시작합니다.
+#[tauri::command]
+async fn send_directory_stream(
+    peer_id: String,
+    folder_path: String,
+    job_id: String,
+    options: Option<transfer::FolderSendOptions>,
+    state: tauri::State<'_, AppState>,
+) -> Result<DirStreamSummary, AppError> {
+    let options = options.unwrap_or_default();
+    let filter = PathFilter::new(
+        &options.include.unwrap_or_default(),
+        &options.exclude.unwrap_or_default(),
+    )
+    .map_err(|e| AppError::InvalidInput(format!("잘못된 필터 패턴: {}", e)))?;
+    let cipher = send_cipher(&state, &peer_id, options.encrypt)?;
+    let conn = peer_connection(&state, &peer_id).await?;
+    let root = PathBuf::from(&folder_path);
+    if !root.is_dir() {
+        return Err(AppError::InvalidInput(format!(
+            "폴더가 아닙니다: {}",
+            folder_path
+        )));
+    }
+
+    info!("📂 스트리밍 폴더 전송 시작: {} -> {}", folder_path, peer_id);
+
+    let (tx, rx) = mpsc::channel::<TransferProgress>(100);
+    let control = state
+        .transfer_registry
+        .register(&job_id, &peer_id, TransferKind::Directory)?;
+    let sender = DirStreamSender::new(conn.clone())
+        .with_symlink_policy(options.symlink_policy.unwrap_or_default())
+        .with_filter(filter)
+        .with_job_control(control)
+        .with_progress_channel(tx)
+        .with_cipher(cipher)
+        .with_memory_budget(state.buffer_budget.clone());
+    forward_directory_progress(&state, rx);
+
+    // 여러 파일을 보내므로 감사 로그에는 해시 없이 기록
+    audit_subject(&state, &job_id, AuditDirection::Send, &conn, None);
+    let result = sender
+        .send_directory(root, &job_id)
+        .await
+        .map_err(|e| AppError::Network(format!("스트리밍 폴더 전송 실패: {}", e)));
+    state
+        .transfer_registry
+        .finish(&job_id, result.as_ref().map(|_| ()).map_err(|e| e.clone()));
+    result
+}
+
+/// 🆕 스트리밍 매니페스트 폴더 수신 (Receiver)
+#[tauri::command]
+async fn receive_directory_stream(
+    peer_id: String,
+    save_dir: String,
+    job_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<DirStreamSummary, AppError> {
+    let conn = peer_connection(&state, &peer_id).await?;
+
+    info!("📥 스트리밍 폴더 수신 대기: {} -> {}", peer_id, save_dir);
+
+    let (tx, rx) = mpsc::channel::<TransferProgress>(100);
+    let control = state
+        .transfer_registry
+        .register(&job_id, &peer_id, TransferKind::Directory)?;
+    let receiver = DirStreamReceiver::new(conn.clone(), PathBuf::from(&save_dir))
+        .with_job_control(control)
+        .with_progress_channel(tx)
+        .with_cipher(state.pairing.cipher(&peer_id))
+        .with_memory_budget(state.buffer_budget.clone());
+    forward_directory_progress(&state, rx);
+
+    let result = receiver
+        .receive_directory(&job_id)
+        .await
+        .map_err(|e| AppError::Network(format!("스트리밍 폴더 수신 실패: {}", e)));
+    if result.is_ok() {
+        audit_subject(&state, &job_id, AuditDirection::Receive, &conn, None);
+    }
+    state
+        .transfer_registry
+        .finish(&job_id, result.as_ref().map(|_| ()).map_err(|e| e.clone()));
+    result
+}
+
+/// 스트리밍 폴더 전송 진행률을 레지스트리에 반영 (이벤트는 레지스트리가 발송)
+fn forward_directory_progress(
+    state: &tauri::State<'_, AppState>,
+    mut rx: mpsc::Receiver<TransferProgress>,
+) {
+    let registry = state.transfer_registry.clone();
+    tauri::async_runtime::spawn(async move {
+        while let Some(progress) = rx.recv().await {
+            registry.update_progress(
+                &progress.job_id,
+                progress.bytes_transferred,
+                progress.total_bytes,
+                progress.speed_bps,
+            );
+        }
+    });
+}
+
 /// 🆕 Zip 스트리밍으로 파일 수신 (Receiver)
 #[tauri::command]
 async fn receive_zip_stream_transfer(
@@ -1659,7 +5306,7 @@ async fn receive_zip_stream_transfer(
     zip_name: Option<String>,
     transfer_type: Option<String>,
     state: tauri::State<'_, AppState>,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
     let transfer_type = transfer_type.unwrap_or_else(|| "zip_file".to_string());
     let is_folder_transfer = transfer_type == "folder";
 
@@ -1668,7 +5315,9 @@ async fn receive_zip_stream_transfer(
         let connections = state.active_connections.read().await;
         connections
             .get(&peer_id)
-            .ok_or_else(|| format!("피어 {}에 대한 연결이 없습니다.", peer_id))?
+            .ok_or_else(|| AppError::PeerNotConnected {
+                peer_id: peer_id.to_string(),
+            })?
             .clone()
     };
 
@@ -1681,13 +5330,24 @@ async fn receive_zip_stream_transfer(
 
     // 진행률 채널 설정
     let (tx, mut rx) = mpsc::channel::<TransferProgress>(100);
-    let receiver = ZipStreamReceiver::new(config).with_progress_channel(tx);
+    let control = state
+        .transfer_registry
+        .register(&job_id, &peer_id, TransferKind::Zip)?;
+    let receiver = ZipStreamReceiver::new(config)
+        .with_progress_channel(tx)
+        .with_cancellation(control.cancel_flag())
+        .with_cipher(state.pairing.cipher(&peer_id));
 
-    // 진행률 이벤트 전송
-    let app_handle = state.app_handle.clone();
+    // 진행률을 레지스트리에 반영 (이벤트는 레지스트리가 발송)
+    let registry = state.transfer_registry.clone();
     tauri::async_runtime::spawn(async move {
         while let Some(progress) = rx.recv().await {
-            let _ = app_handle.emit("transfer-progress", &progress);
+            registry.update_progress(
+                &progress.job_id,
+                progress.bytes_transferred,
+                progress.total_bytes,
+                progress.speed_bps,
+            );
         }
     });
 
@@ -1707,10 +5367,24 @@ async fn receive_zip_stream_transfer(
             save_path = save_path.join(file_name);
         }
     }
-    let result_path = receiver
-        .receive_zip_stream(&conn, save_path, &job_id)
-        .await
-        .map_err(|e| format!("Zip 스트리밍 수신 실패: {}", e))?;
+    let result_path = match receiver.receive_zip_stream(&conn, save_path, &job_id).await {
+        Ok(path) => {
+            // 폴더 전송이면 압축 해제 후 zip이 지워지므로 해시 없이 기록됨
+            audit_subject(
+                &state,
+                &job_id,
+                AuditDirection::Receive,
+                &conn,
+                Some(path.clone()),
+            );
+            path
+        }
+        Err(e) => {
+            let error = transfer_error("Zip 스트리밍 수신 실패", e);
+            state.transfer_registry.finish(&job_id, Err(error.clone()));
+            return Err(error);
+        }
+    };
 
     let result_str = result_path.to_string_lossy().to_string();
 
@@ -1725,12 +5399,19 @@ async fn receive_zip_stream_transfer(
         let result_path_clone = result_path.clone();
         let output_dir_clone = output_dir.clone();
 
-        let extracted_files = tokio::task::spawn_blocking(move || {
+        let extracted = tokio::task::spawn_blocking(move || {
             extract_zip_to_directory(&result_path_clone, &output_dir_clone)
         })
         .await
-        .map_err(|e| format!("압축 해제 작업 실패: {}", e))?
-        .map_err(|e| format!("압축 해제 실패: {}", e))?;
+        .map_err(|e| AppError::Io(format!("압축 해제 작업 실패: {}", e)))
+        .and_then(|result| result.map_err(|e| AppError::Io(format!("압축 해제 실패: {}", e))));
+        let extracted_files = match extracted {
+            Ok(files) => files,
+            Err(e) => {
+                state.transfer_registry.finish(&job_id, Err(e.clone()));
+                return Err(e);
+            }
+        };
 
         let _ = tokio::fs::remove_file(&result_path).await;
 
@@ -1751,27 +5432,50 @@ async fn receive_zip_stream_transfer(
         info!("✅ 폴더 압축 해제 완료: {} 파일", extracted_files.len());
     }
 
-    // 완료 이벤트
-    let _ = state.app_handle.emit(
-        "transfer-complete",
-        serde_json::json!({
-            "jobId": job_id,
-            "savedPath": result_str,
-            "peerId": peer_id,
-        }),
-    );
+    // 폴더 전송은 압축 해제까지 끝나야 완료
+    state.transfer_registry.finish(&job_id, Ok(()));
 
     info!("✅ Zip 스트리밍 수신 완료: {:?}", result_path);
     Ok(result_str)
 }
 
+/// 🆕 저장 위치의 여유 공간 (바이트, 아직 없는 폴더는 가장 가까운 상위 폴더 기준)
+#[tauri::command]
+async fn get_disk_space(path: String) -> Result<u64, AppError> {
+    transfer::disk_space::available_space(std::path::Path::new(&path))
+        .map_err(|e| AppError::Io(format!("여유 공간 조회 실패: {}", e)))
+}
+
+/// 🆕 전송 전 크기 미리 계산 (전송은 시작하지 않음)
+///
+/// 폴더는 `include`/`exclude`와 `.pswpignore`를 적용해 걷고, 파일 수/총 크기/가장 큰 파일(`largest`개, 기본 10)을 반환합니다.
+#[tauri::command]
+async fn estimate_transfer(
+    paths: Vec<String>,
+    include: Option<Vec<String>>,
+    exclude: Option<Vec<String>>,
+    symlink_policy: Option<transfer::SymlinkPolicy>,
+    largest: Option<usize>,
+) -> Result<TransferEstimate, AppError> {
+    let filter = PathFilter::new(&include.unwrap_or_default(), &exclude.unwrap_or_default())
+        .map_err(|e| AppError::InvalidInput(format!("잘못된 필터 패턴: {}", e)))?;
+    let paths: Vec<PathBuf> = paths.into_iter().map(PathBuf::from).collect();
+    let largest = largest.unwrap_or(transfer::estimate::DEFAULT_LARGEST_FILES);
+
+    tokio::task::spawn_blocking(move || {
+        transfer::estimate::estimate(&paths, symlink_policy.unwrap_or_default(), &filter, largest)
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("크기 계산 작업 실패: {}", e)))
+}
+
 /// 🆕 Zip 파일 압축 해제
 #[tauri::command]
 async fn extract_zip_file(
     zip_path: String,
     output_dir: String,
     remove_zip: Option<bool>,
-) -> Result<Vec<String>, String> {
+) -> Result<Vec<String>, AppError> {
     let zip_path = PathBuf::from(&zip_path);
     let output_dir = PathBuf::from(&output_dir);
 
@@ -1781,8 +5485,8 @@ async fn extract_zip_file(
         extract_zip_to_directory(&zip_path_for_extract, &output_dir)
     })
     .await
-    .map_err(|e| format!("작업 실행 실패: {}", e))?
-    .map_err(|e| format!("압축 해제 실패: {}", e))?;
+    .map_err(|e| AppError::Internal(format!("작업 실행 실패: {}", e)))?
+    .map_err(|e| AppError::Io(format!("압축 해제 실패: {}", e)))?;
 
     if remove_zip.unwrap_or(false) {
         let _ = tokio::fs::remove_file(&zip_path).await;
@@ -1796,22 +5500,69 @@ async fn extract_zip_file(
 
 /// 🆕 전송 작업 취소
 #[tauri::command]
-async fn cancel_transfer(job_id: String, state: tauri::State<'_, AppState>) -> Result<(), String> {
-    let mut jobs = state.active_jobs.write().await;
-    if let Some(control) = jobs.get(&job_id) {
-        control.is_cancelled.store(true, Ordering::SeqCst);
+async fn cancel_transfer(
+    job_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), AppError> {
+    if state.transfer_registry.cancel(&job_id) {
         info!("🛑 작업 취소 요청됨: {}", job_id);
         Ok(())
     } else {
-        Err(format!("작업을 찾을 수 없습니다: {}", job_id))
+        Err(AppError::NotFound(format!(
+            "작업을 찾을 수 없습니다: {}",
+            job_id
+        )))
+    }
+}
+
+/// 🆕 전송 작업 일시정지 (블록/청크 경계에서 멈춤, Grid 작업은 `pause_grid_job` 사용)
+#[tauri::command]
+async fn pause_transfer(job_id: String, state: tauri::State<'_, AppState>) -> Result<(), AppError> {
+    if state.transfer_registry.pause(&job_id) {
+        info!("⏸️ 작업 일시정지: {}", job_id);
+        Ok(())
+    } else {
+        Err(AppError::NotFound(format!(
+            "작업을 찾을 수 없습니다: {}",
+            job_id
+        )))
+    }
+}
+
+/// 🆕 일시정지된 전송 작업 재개
+#[tauri::command]
+async fn resume_transfer(
+    job_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), AppError> {
+    if state.transfer_registry.resume(&job_id) {
+        info!("▶️ 작업 재개: {}", job_id);
+        Ok(())
+    } else {
+        Err(AppError::NotFound(format!(
+            "작업을 찾을 수 없습니다: {}",
+            job_id
+        )))
     }
 }
 
+/// 🆕 전송 작업의 초당 처리량 기록 (속도 그래프용)
+#[tauri::command]
+async fn get_transfer_speed_history(
+    job_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<transfer::registry::SpeedSample>, AppError> {
+    state
+        .transfer_registry
+        .speed_history(&job_id)
+        .ok_or_else(|| AppError::NotFound(format!("작업을 찾을 수 없습니다: {}", job_id)))
+}
+
 /// 🆕 대기 중인 전송 요청 목록 조회
 #[tauri::command]
 async fn get_pending_transfers(
     state: tauri::State<'_, AppState>,
-) -> Result<Vec<crate::protocol::commands::TransferRequest>, String> {
+) -> Result<Vec<crate::protocol::commands::TransferRequest>, AppError> {
     let approval_manager = state.transfer_approval.as_ref();
 
     let pending_requests = approval_manager.pending_requests.read().await;
@@ -1826,13 +5577,32 @@ async fn approve_transfer(
     approved: bool,
     reason: Option<String>,
     state: tauri::State<'_, AppState>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     let approval_manager = state.transfer_approval.as_ref();
 
     approval_manager
         .approve(&job_id, approved, reason)
         .await
-        .map_err(|e| e.to_string())
+}
+
+/// 앱 종료 전 정리 (Grid 작업, 내장 부트스트랩 중지)
+async fn shutdown_services(state: &AppState) {
+    #[cfg(feature = "grid-experimental")]
+    state.grid_jobs.stop_all().await;
+    state.http_share.stop().await;
+    state.tus.stop().await;
+    state.webrtc.close_all();
+    state.media_preview.stop().await;
+
+    let mut bootstrap_guard = state.embedded_bootstrap.write().await;
+    if let Some(ref mut service) = *bootstrap_guard {
+        info!("🛑 앱 종료: 부트스트랩 서비스 중지 중...");
+        if let Err(e) = service.stop().await {
+            tracing::error!("부트스트랩 중지 실패: {}", e);
+        } else {
+            info!("✅ 부트스트랩 서비스 정상 종료");
+        }
+    }
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -1842,43 +5612,224 @@ pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .plugin(tauri_plugin_notification::init())
         .setup(|app| {
             // 릴리스에서도 로그를 파일로 남기되, 기본은 OFF.
-            // `PONSWARP_LOG=1` 환경변수로 활성화.
+            // `PONSWARP_LOG=1` 환경변수로 활성화 (🆕 실행 중에는 `set_log_level`로 변경).
             let enable_log = std::env::var("PONSWARP_LOG")
                 .map(|v| v == "1" || v.to_lowercase() == "true")
                 .unwrap_or(cfg!(debug_assertions));
 
+            app.handle().plugin(logging::plugin())?;
             if enable_log {
-                app.handle().plugin(
-                    tauri_plugin_log::Builder::default()
-                        .level(log::LevelFilter::Info)
-                        .build(),
-                )?;
+                log::set_max_level(log::LevelFilter::Info);
                 info!("📄 파일 로깅 활성화됨 (PONSWARP_LOG)");
+            } else {
+                log::set_max_level(log::LevelFilter::Off);
             }
 
             // 🆕 AppHandle을 포함한 AppState 생성 및 관리
             let app_handle = app.handle().clone();
+
+            // 🆕 전송 수명주기 이벤트 (모든 엔진 공통 스키마)
+            let (event_tx, mut event_rx) =
+                mpsc::unbounded_channel::<transfer::registry::TransferEvent>();
+            let event_app_handle = app_handle.clone();
+            tauri::async_runtime::spawn(async move {
+                while let Some((event, snapshot)) = event_rx.recv().await {
+                    let _ = event_app_handle.emit(event, &snapshot);
+                    // 🆕 완료/실패는 OS 알림으로도 표시
+                    if let Some(state) = event_app_handle.try_state::<AppState>() {
+                        state
+                            .notifications
+                            .notify_transfer(&event_app_handle, event, &snapshot);
+                        // 🆕 끝난 작업은 감사 로그에 기록 (켜져 있을 때)
+                        state.audit_log.on_event(event, &snapshot);
+                    }
+                }
+            });
+
+            // 🆕 폴더 동기화 이벤트
+            let (sync_tx, mut sync_rx) = mpsc::unbounded_channel::<sync::SyncEvent>();
+            let sync_app_handle = app_handle.clone();
+            tauri::async_runtime::spawn(async move {
+                while let Some(event) = sync_rx.recv().await {
+                    let _ = sync_app_handle.emit("folder-sync-event", &event);
+                }
+            });
+
+            // 🆕 HTTP 공유 링크 다운로드 이벤트
+            let (http_share_tx, mut http_share_rx) =
+                mpsc::unbounded_channel::<transfer::http_share::HttpShareEvent>();
+            let http_share_app_handle = app_handle.clone();
+            tauri::async_runtime::spawn(async move {
+                while let Some(event) = http_share_rx.recv().await {
+                    let _ = http_share_app_handle.emit("http-share-event", &event);
+                }
+            });
+
+            // 🆕 웹 버전 WebRTC 시그널링 메시지 (프론트엔드가 시그널링 서버로 중계)
+            let (webrtc_tx, mut webrtc_rx) =
+                mpsc::unbounded_channel::<transfer::webrtc_channel::WebRtcSignalEvent>();
+            let webrtc_app_handle = app_handle.clone();
+            tauri::async_runtime::spawn(async move {
+                while let Some(event) = webrtc_rx.recv().await {
+                    let _ = webrtc_app_handle.emit("webrtc-signal", &event);
+                }
+            });
+
+            // 🆕 기기 인증서 및 자동 수락 규칙 (앱 데이터 디렉토리에 저장)
+            let app_data_dir = app_handle.path().app_data_dir();
+            let quic_identity = match &app_data_dir {
+                Ok(dir) => match quic::identity::QuicIdentity::load_or_generate(dir) {
+                    Ok(identity) => Some(identity),
+                    Err(e) => {
+                        warn!("QUIC 인증서 로드 실패, 실행마다 새로 생성: {}", e);
+                        None
+                    }
+                },
+                Err(_) => None,
+            };
+            let signing_identity = match &app_data_dir {
+                Ok(dir) => bootstrap::mailbox::SigningIdentity::load_or_generate(dir)
+                    .unwrap_or_else(|e| {
+                        warn!("시그널링 키 로드 실패, 실행마다 새로 생성: {}", e);
+                        bootstrap::mailbox::SigningIdentity::generate()
+                    }),
+                Err(_) => bootstrap::mailbox::SigningIdentity::generate(),
+            };
+            let contacts = Arc::new(match &app_data_dir {
+                Ok(dir) => contacts::ContactBook::load(dir.join(contacts::CONTACTS_FILE)),
+                Err(_) => contacts::ContactBook::in_memory(),
+            });
+            let auto_accept_rules = match &app_data_dir {
+                Ok(dir) => transfer::auto_accept::AutoAcceptRules::load(
+                    dir.join(transfer::auto_accept::AUTO_ACCEPT_FILE),
+                ),
+                Err(_) => transfer::auto_accept::AutoAcceptRules::in_memory(),
+            }
+            .with_contacts(contacts.clone());
+            let signing_identity = Arc::new(signing_identity);
+            let audit_log = match &app_data_dir {
+                Ok(dir) => transfer::audit::AuditLog::load(dir.clone(), signing_identity.clone()),
+                Err(_) => transfer::audit::AuditLog::in_memory(signing_identity.clone()),
+            };
+            let receive_quota = match &app_data_dir {
+                Ok(dir) => transfer::quota::ReceiveQuota::load(
+                    dir.join(transfer::quota::RECEIVE_QUOTA_FILE),
+                ),
+                Err(_) => transfer::quota::ReceiveQuota::in_memory(),
+            };
+            let notification_center = match &app_data_dir {
+                Ok(dir) => notifications::NotificationCenter::load(
+                    dir.join(notifications::NOTIFICATION_SETTINGS_FILE),
+                ),
+                Err(_) => notifications::NotificationCenter::in_memory(),
+            };
+            // 🆕 헤드리스 수신 모드 (설정 파일 위에 명령줄 인자 적용)
+            let headless_config = app_data_dir
+                .as_ref()
+                .map(|dir| {
+                    headless::HeadlessConfig::load(&dir.join(headless::HEADLESS_CONFIG_FILE))
+                })
+                .unwrap_or_default();
+            let headless_config = headless_config
+                .clone()
+                .apply_args(std::env::args().skip(1))
+                .unwrap_or_else(|e| {
+                    warn!("헤드리스 명령줄 인자 무시: {}", e);
+                    headless_config
+                });
+
+            let app_metrics = Arc::new(metrics::MetricsCollector::new());
+            let transfer_registry = Arc::new(
+                TransferRegistry::new()
+                    .with_event_channel(event_tx)
+                    .with_metrics(app_metrics.clone()),
+            );
             let state = AppState {
                 quic_server: Arc::new(RwLock::new(None)),
                 quic_client: Arc::new(RwLock::new(None)),
+                quic_pacing: Arc::new(RwLock::new(QuicPacingConfig::default())),
+                quic_identity,
                 discovery: Arc::new(RwLock::new(None)),
                 udp_core: Arc::new(RwLock::new(None)),
                 relay_engine: Arc::new(RwLock::new(None)),
-                file_transfer: Arc::new(RwLock::new(None)),
+                relay_pool: Arc::new(RwLock::new(RelayPoolConfig::default())),
+                relay_selector: Arc::new(RelaySelector::new()),
+                transfer_registry: transfer_registry.clone(),
                 transfer_approval: Arc::new(
-                    crate::transfer::file_transfer::TransferApprovalManager::new(),
+                    crate::transfer::file_transfer::TransferApprovalManager::new()
+                        .with_auto_accept(Arc::new(auto_accept_rules)),
                 ),
                 file_stream_manager: Arc::new(FileStreamManager::new()),
                 active_connections: Arc::new(RwLock::new(std::collections::HashMap::new())),
                 accepted_connections: Arc::new(RwLock::new(std::collections::HashMap::new())),
                 embedded_bootstrap: Arc::new(RwLock::new(None)),
+                signing_identity: signing_identity.clone(),
+                presence: Arc::new(bootstrap::presence::PresenceAnnouncer::new()),
                 app_handle: app_handle.clone(),
                 is_closing: Arc::new(AtomicBool::new(false)),
-                active_jobs: Arc::new(RwLock::new(std::collections::HashMap::new())),
+                grid_rate_limits: Arc::new(RwLock::new(std::collections::HashMap::new())),
+                #[cfg(feature = "grid-experimental")]
+                grid_ban_list: Arc::new(match grid_resume_dir(&app_handle) {
+                    Ok(dir) => grid::peer_score::BanList::load(
+                        dir.join(grid::peer_score::BAN_LIST_FILE),
+                    ),
+                    Err(_) => grid::peer_score::BanList::in_memory(),
+                }),
+                folder_sync: Arc::new(sync::FolderSyncManager::new().with_event_channel(sync_tx)),
+                multi_source: Arc::new(transfer::multi_source::MultiSourceManager::new()),
+                pairing: Arc::new(transfer::pairing::PairingManager::new()),
+                benchmark: Arc::new(transfer::benchmark::BenchmarkManager::new()),
+                notifications: Arc::new(notification_center),
+                headless: headless_config.enabled,
+                http_share: Arc::new(
+                    transfer::http_share::HttpShareServer::new().with_event_channel(http_share_tx),
+                ),
+                tus: Arc::new(transfer::tus::TusServer::new(transfer_registry.clone())),
+                webrtc: Arc::new(
+                    transfer::webrtc_channel::WebRtcInterop::new(transfer_registry)
+                        .with_signal_channel(webrtc_tx),
+                ),
+                job_manifests: Arc::new(transfer::job_manifest::JobManifestStore::new()),
+                range_requests: Arc::new(transfer::range_request::RangeRequestManager::new()),
+                media_preview: Arc::new(transfer::media_preview::MediaPreviewServer::new()),
+                receive_quota: Arc::new(receive_quota),
+                audit_log: Arc::new(audit_log),
+                contacts,
+                sas: Arc::new(quic::sas::SasSessions::new()),
+                metrics: app_metrics,
+                buffer_budget: transfer::memory_budget::MemoryBudget::new(
+                    transfer::memory_budget::DEFAULT_BUFFER_BUDGET,
+                ),
+                piece_hash_cache: Arc::new(match grid_resume_dir(&app_handle) {
+                    Ok(dir) => grid::hash_cache::PieceHashCache::new(
+                        dir.join(grid::hash_cache::HASH_CACHE_DIR),
+                    ),
+                    Err(_) => grid::hash_cache::PieceHashCache::disabled(),
+                }),
+                grid_hashing_jobs: Arc::new(RwLock::new(std::collections::HashMap::new())),
+                #[cfg(feature = "grid-experimental")]
+                grid_jobs: Arc::new(grid::job::GridJobManager::new()),
             };
             app.manage(state);
+            spawn_quality_monitor(app_handle.clone());
+
+            // 🆕 시스템 트레이 (창을 닫아 둔 채 전송 상태 확인/제어)
+            if let Err(e) = tray::init(&app_handle) {
+                warn!("시스템 트레이 생성 실패: {}", e);
+            }
+
+            if headless_config.enabled {
+                let app_handle_clone = app_handle.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = headless::start(app_handle_clone, headless_config).await {
+                        error!("헤드리스 수신 모드 시작 실패: {}", e);
+                    }
+                });
+            }
 
             // 🚀 내장 부트스트랩 자동 시작
             let app_handle_clone = app_handle.clone();
@@ -1900,6 +5851,14 @@ pub fn run() {
                         return;
                     }
 
+                    // 🆕 전송 중에는 종료하지 않고 트레이로 숨김 (트레이의 "종료"로 완전히 종료)
+                    if state.transfer_registry.has_active() {
+                        api.prevent_close();
+                        let _ = window.hide();
+                        info!("📥 전송 진행 중: 창을 트레이로 숨김");
+                        return;
+                    }
+
                     // 종료 플래그 설정
                     state.is_closing.store(true, Ordering::SeqCst);
 
@@ -1912,15 +5871,7 @@ pub fn run() {
                     // 비동기 정리 작업 시작
                     tauri::async_runtime::spawn(async move {
                         if let Some(state) = app_handle_clone.try_state::<AppState>() {
-                            let mut bootstrap_guard = state.embedded_bootstrap.write().await;
-                            if let Some(ref mut service) = *bootstrap_guard {
-                                info!("🛑 앱 종료: 부트스트랩 서비스 중지 중...");
-                                if let Err(e) = service.stop().await {
-                                    tracing::error!("부트스트랩 중지 실패: {}", e);
-                                } else {
-                                    info!("✅ 부트스트랩 서비스 정상 종료");
-                                }
-                            }
+                            shutdown_services(&state).await;
                         }
 
                         // 정리 완료 후 윈도우 다시 닫기 (이때는 is_closing이 true라 바로 닫힘)
@@ -1931,6 +5882,9 @@ pub fn run() {
         })
         .invoke_handler(tauri::generate_handler![
             get_runtime_info,
+            set_log_level,
+            get_log_level,
+            get_recent_logs,
             ping_quic,
             scan_folder,
             start_quic_server,
@@ -1940,17 +5894,47 @@ pub fn run() {
             stop_discovery,
             start_udp_transfer,
             get_transfer_stats,
+            set_udp_pacing_rate,
+            get_quic_pacing,
+            set_quic_pacing,
             start_relay_engine,
             get_relay_stats,
+            get_relay_pool_config,
+            set_relay_pool_config,
             stop_relay_engine,
+            select_best_relay,
+            get_relay_candidates,
+            run_network_doctor,
+            report_relay_throughput,
+            release_relay,
             send_signaling_message,
             handle_signaling_message,
             connect_to_peer,
+            connect_to_peer_race,
+            restart_ice,
+            create_room_code,
+            join_room_code,
             send_file_to_peer,
             send_file_to_accepted_peer,
             disconnect_peer,
             send_file_multistream,
             receive_file_multistream,
+            send_files_to_peers,
+            share_multi_source_file,
+            stop_multi_source_share,
+            download_multi_source,
+            offer_files,
+            withdraw_offer,
+            list_offers,
+            fetch_offer_catalog,
+            request_offer_files,
+            pair_with_peer,
+            confirm_pairing,
+            reject_pairing,
+            unpair_peer,
+            list_paired_peers,
+            get_connection_qr_payload,
+            connect_from_qr_payload,
             connect_via_relay,
             get_public_ip,
             start_file_stream,
@@ -1969,21 +5953,114 @@ pub fn run() {
             get_network_interfaces,
             get_grid_info,
             create_grid_metadata,
+            cancel_grid_hashing,
+            set_swarm_rate_limit,
+            get_swarm_rate_limit,
+            resume_grid_download,
+            list_resumable_grid_jobs,
+            create_share_link,
+            save_grid_metadata,
+            load_grid_metadata,
+            open_share_link,
+            start_grid_seed,
+            start_grid_download,
+            pause_grid_job,
+            resume_grid_job,
+            stop_grid_job,
+            get_grid_jobs,
+            get_grid_ban_list,
+            unban_grid_peer,
             connect_bootstrap_node,
             set_bootstrap_nodes,
             discover_bootstrap_nodes,
+            get_signaling_node_id,
+            queue_offline_signal,
+            poll_offline_signals,
+            start_presence,
+            stop_presence,
+            get_roster,
             start_embedded_bootstrap,
             stop_embedded_bootstrap,
             get_embedded_bootstrap_status,
+            get_bootstrap_stats_token,
             update_bootstrap_config,
             send_zip_stream_transfer,
             send_folder_transfer,
             receive_zip_stream_transfer,
+            send_directory_stream,
+            receive_directory_stream,
+            get_disk_space,
+            estimate_transfer,
             extract_zip_file,
             cancel_transfer,
+            send_text,
+            benchmark_peer,
+            pause_transfer,
+            resume_transfer,
+            get_transfer_speed_history,
+            start_folder_sync,
+            accept_folder_sync,
+            stop_folder_sync,
+            list_folder_syncs,
             get_pending_transfers,
             approve_transfer,
+            request_transfer_approval,
+            get_device_fingerprint,
+            list_auto_accept_rules,
+            save_auto_accept_rule,
+            remove_auto_accept_rule,
+            get_auto_accept_log,
+            list_contacts,
+            save_contact,
+            remove_contact,
+            connect_contact,
+            get_short_auth_string,
+            confirm_short_auth_string,
+            reject_short_auth_string,
+            get_receive_quota,
+            set_receive_quota,
+            get_app_metrics,
+            get_memory_budget,
+            get_audit_log_enabled,
+            set_audit_log_enabled,
+            get_audit_log,
+            export_audit_log,
+            verify_audit_log,
+            get_notification_settings,
+            set_notification_settings,
+            create_http_share,
+            list_http_shares,
+            revoke_http_share,
+            stop_http_share_server,
+            open_media_preview,
+            close_media_preview,
+            start_tus_server,
+            get_tus_endpoint,
+            stop_tus_server,
+            webrtc_send_file,
+            webrtc_receive,
+            webrtc_signal,
+            webrtc_close,
+            receive_file_to_storage,
+            send_file_to_storage,
+            export_job_manifest,
+            import_job_manifest,
+            verify_job_manifest,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app_handle, event| {
+            // 🆕 헤드리스 모드는 창이 없어도 종료하지 않음 (트레이의 "종료"는 exit 코드가 있어 통과)
+            if let tauri::RunEvent::ExitRequested {
+                code: None, api, ..
+            } = event
+            {
+                if app_handle
+                    .try_state::<AppState>()
+                    .is_some_and(|state| state.headless)
+                {
+                    api.prevent_exit();
+                }
+            }
+        });
 }
@@ -0,0 +1,114 @@
+//! 파일 로깅 설정 (tauri-plugin-log)
+//!
+//! 로거는 항상 등록하고 `log::set_max_level`로 실제 기록 수준을 조절하므로, 재시작 없이
+//! `set_log_level`로 디버그 로그를 켤 수 있습니다. `PONSWARP_LOG=1`이 없으면 기본은 꺼짐입니다.
+//! 로그 파일은 크기 기준으로 회전하며 최근 파일 몇 개만 보관합니다.
+
+use crate::error::AppError;
+use log::LevelFilter;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use tauri::plugin::TauriPlugin;
+use tauri::Runtime;
+use tauri_plugin_log::{RotationStrategy, Target, TargetKind};
+
+/// 로그 파일 이름 (확장자 `.log`는 플러그인이 붙임)
+pub const LOG_FILE_NAME: &str = "ponswarp";
+
+/// 회전 기준 파일 크기
+const MAX_LOG_FILE_SIZE: u128 = 5 * 1024 * 1024;
+
+/// 회전된 파일 보관 개수
+const KEEP_LOG_FILES: usize = 5;
+
+/// `get_recent_logs`로 한 번에 읽을 수 있는 최대 줄 수
+pub const MAX_RECENT_LINES: usize = 5000;
+
+/// 뒤에서부터 읽는 단위
+const TAIL_CHUNK: u64 = 64 * 1024;
+
+/// 로그 플러그인 (모든 수준을 받고 `log::max_level`로 걸러냄)
+pub fn plugin<R: Runtime>() -> TauriPlugin<R> {
+    tauri_plugin_log::Builder::default()
+        .level(LevelFilter::Trace)
+        // 의존성 크레이트의 디버그 로그는 너무 많으므로 제한
+        .level_for("quinn", LevelFilter::Info)
+        .level_for("quinn_proto", LevelFilter::Info)
+        .level_for("rustls", LevelFilter::Info)
+        .targets([
+            Target::new(TargetKind::Stdout),
+            Target::new(TargetKind::LogDir {
+                file_name: Some(LOG_FILE_NAME.to_string()),
+            }),
+        ])
+        .max_file_size(MAX_LOG_FILE_SIZE)
+        .rotation_strategy(RotationStrategy::KeepSome(KEEP_LOG_FILES))
+        .build()
+}
+
+/// 로그 수준 문자열 파싱 (off/error/warn/info/debug/trace)
+pub fn parse_level(level: &str) -> Result<LevelFilter, AppError> {
+    level
+        .trim()
+        .parse()
+        .map_err(|_| AppError::InvalidInput(format!("알 수 없는 로그 수준: {}", level)))
+}
+
+/// 현재 로그 수준 (소문자)
+pub fn current_level() -> String {
+    log::max_level().to_string().to_lowercase()
+}
+
+/// 파일 끝에서 최대 `n`줄 읽기 (오래된 줄부터)
+pub fn tail_lines(path: &Path, n: usize) -> std::io::Result<Vec<String>> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+
+    // 줄바꿈이 n개보다 많아질 때까지 뒤에서부터 읽음
+    let mut start = len;
+    let mut buf = Vec::new();
+    while start > 0 && buf.iter().filter(|&&b| b == b'\n').count() <= n {
+        let next = start.saturating_sub(TAIL_CHUNK);
+        let mut chunk = vec![0u8; (start - next) as usize];
+        file.seek(SeekFrom::Start(next))?;
+        file.read_exact(&mut chunk)?;
+        chunk.extend_from_slice(&buf);
+        buf = chunk;
+        start = next;
+    }
+
+    let text = String::from_utf8_lossy(&buf);
+    let lines: Vec<&str> = text.lines().collect();
+    // 중간부터 읽었으면 첫 줄은 잘린 줄일 수 있음 (이때는 줄이 n개보다 많음)
+    let skip = usize::from(start > 0);
+    Ok(lines[skip..]
+        .iter()
+        .rev()
+        .take(n)
+        .rev()
+        .map(|line| line.to_string())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_level_and_tail() {
+        assert_eq!(parse_level("Debug").unwrap(), LevelFilter::Debug);
+        assert_eq!(parse_level("off").unwrap(), LevelFilter::Off);
+        assert!(parse_level("verbose").is_err());
+
+        let path = std::env::temp_dir().join(format!("ponswarp-log-{}.log", std::process::id()));
+        let content: String = (0..20_000).map(|i| format!("line {}\n", i)).collect();
+        std::fs::write(&path, content).unwrap();
+
+        let lines = tail_lines(&path, 3).unwrap();
+        assert_eq!(lines, vec!["line 19997", "line 19998", "line 19999"]);
+        assert_eq!(tail_lines(&path, 100_000).unwrap().len(), 20_000);
+
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -0,0 +1,224 @@
+//! 앱 전체 처리량 지표
+//!
+//! 직접 전송(File/Zip/다중 소스), 멀티스트림, Grid Swarm, 릴레이, UDP 코어가 각자 처리한 바이트를
+//! 하나의 [`MetricsCollector`]에 더해, 대시보드 한 화면에서 합계/속도/서브시스템별 내역을 봅니다.
+//! 기록은 원자 카운터 덧셈뿐이고, 속도는 조회할 때 직전 조회와의 차이로 계산합니다.
+
+use crate::transfer::registry::TransferKind;
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// 이보다 짧은 간격으로 다시 조회하면 직전 속도를 그대로 반환
+const MIN_RATE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 지표를 보고하는 서브시스템
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Subsystem {
+    Direct,
+    Multistream,
+    Grid,
+    Relay,
+    Udp,
+}
+
+impl Subsystem {
+    pub const ALL: [Subsystem; 5] = [
+        Subsystem::Direct,
+        Subsystem::Multistream,
+        Subsystem::Grid,
+        Subsystem::Relay,
+        Subsystem::Udp,
+    ];
+
+    /// 레지스트리 작업 종류의 서브시스템
+    pub fn of_kind(kind: TransferKind) -> Self {
+        match kind {
+            TransferKind::File
+            | TransferKind::Zip
+            | TransferKind::MultiSource
+            | TransferKind::Directory => Subsystem::Direct,
+            TransferKind::Multistream => Subsystem::Multistream,
+            TransferKind::Grid => Subsystem::Grid,
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// 서브시스템 하나의 카운터 (엔진이 직접 들고 갱신)
+#[derive(Debug, Default)]
+pub struct SubsystemCounter {
+    bytes: AtomicU64,
+    active: AtomicUsize,
+}
+
+impl SubsystemCounter {
+    /// 처리한 바이트 추가
+    pub fn add_bytes(&self, bytes: u64) {
+        self.bytes.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// 작업/세션 시작
+    pub fn started(&self) {
+        self.active.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 작업/세션 종료
+    pub fn finished(&self) {
+        let _ = self
+            .active
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1));
+    }
+}
+
+/// 서브시스템별 지표
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubsystemMetrics {
+    pub subsystem: Subsystem,
+    /// 앱 시작 후 처리한 바이트
+    pub bytes: u64,
+    pub bytes_per_sec: u64,
+    /// 진행 중인 작업/세션 수
+    pub active: usize,
+}
+
+/// `get_app_metrics` 응답
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppMetrics {
+    pub uptime_secs: u64,
+    pub total_bytes: u64,
+    pub total_bytes_per_sec: u64,
+    pub active: usize,
+    pub subsystems: Vec<SubsystemMetrics>,
+}
+
+/// 직전 조회 시점의 누적 바이트와 그때 계산한 속도
+struct RateSample {
+    at: Instant,
+    bytes: [u64; Subsystem::ALL.len()],
+    rates: [u64; Subsystem::ALL.len()],
+}
+
+/// 앱 전체 지표 수집기 (AppState 공유)
+pub struct MetricsCollector {
+    started_at: Instant,
+    counters: [Arc<SubsystemCounter>; Subsystem::ALL.len()],
+    last: Mutex<RateSample>,
+}
+
+impl MetricsCollector {
+    pub fn new() -> Self {
+        let now = Instant::now();
+        Self {
+            started_at: now,
+            counters: Default::default(),
+            last: Mutex::new(RateSample {
+                at: now,
+                bytes: [0; Subsystem::ALL.len()],
+                rates: [0; Subsystem::ALL.len()],
+            }),
+        }
+    }
+
+    /// 엔진에 넘길 서브시스템 카운터
+    pub fn counter(&self, subsystem: Subsystem) -> Arc<SubsystemCounter> {
+        self.counters[subsystem.index()].clone()
+    }
+
+    pub fn record(&self, subsystem: Subsystem, bytes: u64) {
+        self.counters[subsystem.index()].add_bytes(bytes);
+    }
+
+    /// 합계/속도/서브시스템별 내역
+    pub fn snapshot(&self) -> AppMetrics {
+        let now = Instant::now();
+        let bytes: [u64; Subsystem::ALL.len()] =
+            std::array::from_fn(|i| self.counters[i].bytes.load(Ordering::Relaxed));
+
+        let rates = {
+            let mut last = self.last.lock();
+            let elapsed = now.duration_since(last.at);
+            if elapsed >= MIN_RATE_INTERVAL {
+                let previous = last.bytes;
+                for ((rate, now_bytes), last_bytes) in
+                    last.rates.iter_mut().zip(bytes).zip(previous)
+                {
+                    *rate = (now_bytes.saturating_sub(last_bytes) as f64 / elapsed.as_secs_f64())
+                        as u64;
+                }
+                last.at = now;
+                last.bytes = bytes;
+            }
+            last.rates
+        };
+
+        let subsystems: Vec<SubsystemMetrics> = Subsystem::ALL
+            .iter()
+            .map(|&subsystem| {
+                let i = subsystem.index();
+                SubsystemMetrics {
+                    subsystem,
+                    bytes: bytes[i],
+                    bytes_per_sec: rates[i],
+                    active: self.counters[i].active.load(Ordering::Relaxed),
+                }
+            })
+            .collect();
+
+        AppMetrics {
+            uptime_secs: now.duration_since(self.started_at).as_secs(),
+            total_bytes: subsystems.iter().map(|s| s.bytes).sum(),
+            total_bytes_per_sec: subsystems.iter().map(|s| s.bytes_per_sec).sum(),
+            active: subsystems.iter().map(|s| s.active).sum(),
+            subsystems,
+        }
+    }
+}
+
+impl Default for MetricsCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_aggregates_subsystems() {
+        let metrics = MetricsCollector::new();
+        let relay = metrics.counter(Subsystem::Relay);
+        relay.started();
+        relay.add_bytes(300);
+        metrics.record(Subsystem::of_kind(TransferKind::Zip), 100);
+        metrics.record(Subsystem::Udp, 50);
+        relay.finished();
+        relay.finished(); // 0 아래로 내려가지 않음
+
+        // 첫 조회는 시작 후 1초가 지나지 않아 속도 0
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.total_bytes, 450);
+        assert_eq!(snapshot.total_bytes_per_sec, 0);
+        assert_eq!(snapshot.active, 0);
+        assert_eq!(snapshot.subsystems.len(), Subsystem::ALL.len());
+        let direct = &snapshot.subsystems[Subsystem::Direct.index()];
+        assert_eq!((direct.subsystem, direct.bytes), (Subsystem::Direct, 100));
+
+        // 1초 이상 지난 뒤에는 직전 조회 이후 증가분으로 속도 계산
+        metrics.last.lock().at -= Duration::from_secs(2);
+        metrics.record(Subsystem::Multistream, 1000);
+        let snapshot = metrics.snapshot();
+        let multistream = &snapshot.subsystems[Subsystem::Multistream.index()];
+        assert!(multistream.bytes_per_sec > 0 && multistream.bytes_per_sec <= 1000);
+        assert!(snapshot.total_bytes_per_sec >= multistream.bytes_per_sec);
+    }
+}
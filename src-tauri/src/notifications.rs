@@ -0,0 +1,258 @@
+//! 데스크톱 알림
+//!
+//! 앱이 백그라운드에 있을 때도 전송 요청 도착, 전송 완료/실패를 놓치지 않도록 OS 알림을 띄웁니다.
+//! 종류별로 켜고 끌 수 있으며 설정은 앱 데이터 디렉토리에 저장됩니다.
+//! 메인 창에 포커스가 있으면 UI가 이미 보여 주므로 알림을 띄우지 않습니다.
+
+use crate::protocol::commands::TransferRequest;
+use crate::transfer::registry::{JobStatus, TransferJobSnapshot, EVENT_COMPLETED, EVENT_FAILED};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+use tracing::{info, warn};
+
+/// 설정 파일명
+pub const NOTIFICATION_SETTINGS_FILE: &str = "notification_settings.json";
+
+const MAIN_WINDOW: &str = "main";
+
+/// 알림 종류별 사용 여부
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NotificationSettings {
+    /// 피어의 전송 요청 도착
+    pub incoming_offer: bool,
+    pub transfer_complete: bool,
+    /// 실패 (사용자가 직접 취소한 전송은 제외)
+    pub transfer_failed: bool,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            incoming_offer: true,
+            transfer_complete: true,
+            transfer_failed: true,
+        }
+    }
+}
+
+/// 알림 설정 (모든 알림 발생 지점 공유)
+pub struct NotificationCenter {
+    path: Option<PathBuf>,
+    settings: Mutex<NotificationSettings>,
+}
+
+impl NotificationCenter {
+    /// 저장하지 않는 메모리 전용 설정
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            settings: Mutex::new(NotificationSettings::default()),
+        }
+    }
+
+    /// 파일에서 로드 (없거나 손상되었으면 기본값)
+    pub fn load(path: PathBuf) -> Self {
+        let settings = match std::fs::read(&path) {
+            Ok(data) => serde_json::from_slice(&data).unwrap_or_else(|e| {
+                warn!("알림 설정 파싱 실패, 기본값 사용: {}", e);
+                NotificationSettings::default()
+            }),
+            Err(_) => NotificationSettings::default(),
+        };
+
+        Self {
+            path: Some(path),
+            settings: Mutex::new(settings),
+        }
+    }
+
+    pub fn settings(&self) -> NotificationSettings {
+        *self.settings.lock()
+    }
+
+    /// 설정 변경 후 저장
+    pub fn set_settings(&self, settings: NotificationSettings) {
+        *self.settings.lock() = settings;
+        info!("🔔 알림 설정 변경: {:?}", settings);
+        self.persist(&settings);
+    }
+
+    /// 전송 수명주기 이벤트에 대한 알림
+    pub fn notify_transfer(&self, app: &AppHandle, event: &str, snapshot: &TransferJobSnapshot) {
+        if let Some((title, body)) = transfer_message(&self.settings(), event, snapshot) {
+            show(app, &title, &body);
+        }
+    }
+
+    /// 승인이 필요한 전송 요청 알림
+    pub fn notify_offer(&self, app: &AppHandle, peer_id: &str, request: &TransferRequest) {
+        if let Some((title, body)) = offer_message(&self.settings(), peer_id, request) {
+            show(app, &title, &body);
+        }
+    }
+
+    fn persist(&self, settings: &NotificationSettings) {
+        let Some(ref path) = self.path else {
+            return;
+        };
+
+        let result = (|| -> anyhow::Result<()> {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            let tmp = path.with_extension("json.tmp");
+            std::fs::write(&tmp, serde_json::to_vec_pretty(settings)?)?;
+            std::fs::rename(&tmp, path)?;
+            Ok(())
+        })();
+
+        if let Err(e) = result {
+            warn!("알림 설정 저장 실패: {}", e);
+        }
+    }
+}
+
+fn transfer_message(
+    settings: &NotificationSettings,
+    event: &str,
+    snapshot: &TransferJobSnapshot,
+) -> Option<(String, String)> {
+    match event {
+        EVENT_COMPLETED if settings.transfer_complete => Some((
+            "전송 완료".to_string(),
+            format!(
+                "{} ({:.1} MB, {:.0}초)",
+                snapshot.peer_id,
+                snapshot.total_bytes as f64 / 1_000_000.0,
+                snapshot.elapsed_secs
+            ),
+        )),
+        EVENT_FAILED if settings.transfer_failed && snapshot.status == JobStatus::Failed => {
+            let reason = snapshot
+                .error
+                .as_ref()
+                .map(|e| e.to_string())
+                .unwrap_or_else(|| "알 수 없는 오류".to_string());
+            Some((
+                "전송 실패".to_string(),
+                format!("{}: {}", snapshot.peer_id, reason),
+            ))
+        }
+        _ => None,
+    }
+}
+
+fn offer_message(
+    settings: &NotificationSettings,
+    peer_id: &str,
+    request: &TransferRequest,
+) -> Option<(String, String)> {
+    if !settings.incoming_offer {
+        return None;
+    }
+    let sender = if request.sender_name.is_empty() {
+        peer_id
+    } else {
+        &request.sender_name
+    };
+    Some((
+        "전송 요청".to_string(),
+        format!(
+            "{}님이 {} ({:.1} MB)을 보내려고 합니다",
+            sender,
+            request.file_name,
+            request.file_size as f64 / 1_000_000.0
+        ),
+    ))
+}
+
+fn show(app: &AppHandle, title: &str, body: &str) {
+    let focused = app
+        .get_webview_window(MAIN_WINDOW)
+        .and_then(|w| w.is_focused().ok())
+        .unwrap_or(false);
+    if focused {
+        return;
+    }
+
+    if let Err(e) = app.notification().builder().title(title).body(body).show() {
+        warn!("알림 표시 실패: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::AppError;
+    use crate::transfer::registry::TransferKind;
+
+    fn snapshot(status: JobStatus, error: Option<AppError>) -> TransferJobSnapshot {
+        TransferJobSnapshot {
+            job_id: "job".to_string(),
+            peer_id: "peer".to_string(),
+            kind: TransferKind::File,
+            status,
+            progress: 100.0,
+            bytes_transferred: 2_500_000,
+            total_bytes: 2_500_000,
+            speed_bps: 0,
+            error,
+            action: None,
+            elapsed_secs: 3.0,
+        }
+    }
+
+    #[test]
+    fn test_messages_follow_settings() {
+        let all = NotificationSettings::default();
+        let completed = snapshot(JobStatus::Completed, None);
+        assert_eq!(
+            transfer_message(&all, EVENT_COMPLETED, &completed),
+            Some(("전송 완료".to_string(), "peer (2.5 MB, 3초)".to_string()))
+        );
+
+        let failed = snapshot(
+            JobStatus::Failed,
+            Some(AppError::InvalidInput("디스크 공간 부족".to_string())),
+        );
+        assert_eq!(
+            transfer_message(&all, EVENT_FAILED, &failed).map(|(_, body)| body),
+            Some("peer: 디스크 공간 부족".to_string())
+        );
+        // 사용자가 취소한 전송은 알리지 않음
+        let cancelled = snapshot(JobStatus::Cancelled, None);
+        assert_eq!(transfer_message(&all, EVENT_FAILED, &cancelled), None);
+
+        let none = NotificationSettings {
+            incoming_offer: false,
+            transfer_complete: false,
+            transfer_failed: false,
+        };
+        assert_eq!(transfer_message(&none, EVENT_COMPLETED, &completed), None);
+        assert_eq!(transfer_message(&none, EVENT_FAILED, &failed), None);
+
+        let request = TransferRequest {
+            job_id: "job".to_string(),
+            file_name: "build.zip".to_string(),
+            file_size: 10_000_000,
+            sender_name: String::new(),
+            sender_device: "linux".to_string(),
+            timestamp: 0,
+            sender_fingerprint: None,
+        };
+        assert_eq!(
+            offer_message(&all, "peer", &request).map(|(_, body)| body),
+            Some("peer님이 build.zip (10.0 MB)을 보내려고 합니다".to_string())
+        );
+        assert_eq!(offer_message(&none, "peer", &request), None);
+
+        // 일부 필드만 저장된 파일도 나머지는 기본값
+        let partial: NotificationSettings =
+            serde_json::from_str(r#"{"transferComplete":false}"#).unwrap();
+        assert!(partial.incoming_offer && !partial.transfer_complete);
+    }
+}
@@ -1,6 +1,21 @@
 use anyhow::anyhow;
 use serde::{Deserialize, Serialize};
 
+/// 🆕 이 빌드의 시그널링 프로토콜 버전 (v2: Renegotiate/Bye 추가)
+pub const SIGNALING_VERSION: u32 = 2;
+
+/// 🆕 함께 동작할 수 있는 가장 낮은 시그널링 버전 (버전 필드가 없는 이전 메시지는 v1)
+pub const MIN_SIGNALING_VERSION: u32 = 1;
+
+fn legacy_signaling_version() -> u32 {
+    MIN_SIGNALING_VERSION
+}
+
+/// 🆕 상대 버전과 이쪽 버전 중 낮은 쪽으로 협상 (함께 동작할 수 없으면 None)
+pub fn negotiate_signaling_version(remote: u32) -> Option<u32> {
+    (remote >= MIN_SIGNALING_VERSION).then(|| remote.min(SIGNALING_VERSION))
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PeerInfo {
     pub peer_id: String,
@@ -25,6 +40,9 @@ pub struct TransferRequest {
     pub sender_name: String,
     pub sender_device: String,
     pub timestamp: u64,
+    /// 보낸 기기의 인증서 지문 (수신 측이 연결에서 직접 채움, 보낸 값은 신뢰하지 않음)
+    #[serde(default)]
+    pub sender_fingerprint: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -77,11 +95,17 @@ pub enum Command {
         room_id: String,
         sdp: String,
         target: Option<String>,
+        /// 🆕 보낸 쪽이 지원하는 최고 시그널링 버전
+        #[serde(default = "legacy_signaling_version")]
+        version: u32,
     },
     Answer {
         room_id: String,
         sdp: String,
         target: Option<String>,
+        /// 🆕 Offer 버전과 협상된 시그널링 버전
+        #[serde(default = "legacy_signaling_version")]
+        version: u32,
     },
     GetTurnStatus {
         room_id: String,
@@ -98,6 +122,104 @@ pub enum Command {
         room_id: String,
         candidate: String,
     },
+    /// 🆕 세션을 유지한 채 재협상 (후보 추가, 전송 방식 변경, v2부터)
+    Renegotiate {
+        room_id: String,
+        target: Option<String>,
+        #[serde(default = "legacy_signaling_version")]
+        version: u32,
+        /// 새로 추가할 연결 후보 ("ip:port")
+        #[serde(default)]
+        candidates: Vec<String>,
+        /// 바꿀 전송 방식 (예: "quic", "relay", 없으면 그대로)
+        #[serde(default)]
+        transport: Option<String>,
+    },
+    /// 🆕 세션 종료 알림 (연결이 끊길 때까지 기다리지 않고 명시적으로 정리)
+    Bye {
+        room_id: String,
+        target: Option<String>,
+        #[serde(default)]
+        reason: Option<String>,
+    },
+    /// 텍스트/클립보드 공유 (단방향 스트림)
+    TextMessage {
+        message_id: String,
+        content: String,
+        timestamp: u64,
+    },
+    /// 폴더 동기화: 파일 내용 푸시 (헤더 뒤에 파일 데이터)
+    SyncFile {
+        sync_id: String,
+        path: String,
+        size: u64,
+        /// 수정 시각 (Unix ms)
+        modified: u64,
+        hash: String,
+    },
+    /// 폴더 동기화: 파일/폴더 삭제
+    SyncDelete {
+        sync_id: String,
+        path: String,
+    },
+    /// 페어링: 요청 측 임시 X25519 공개 키의 해시 (hex, 응답을 받은 뒤 `PairReveal`로 공개)
+    PairRequest {
+        pairing_id: String,
+        commitment: String,
+    },
+    /// 페어링: 응답 측 임시 공개 키 (hex)
+    PairResponse {
+        pairing_id: String,
+        public_key: String,
+    },
+    /// 페어링: 요청 측 임시 공개 키 (hex, `PairRequest`의 해시와 같아야 함)
+    PairReveal {
+        pairing_id: String,
+        public_key: String,
+    },
+    /// 다중 소스 다운로드: 조각 요청 (`piece`가 없으면 파일 메타데이터 요청)
+    PieceRequest {
+        request_id: String,
+        info_hash: String,
+        piece: Option<u32>,
+    },
+    /// 다중 소스 다운로드: 요청 응답 (헤더 뒤에 조각 데이터 또는 메타데이터 JSON)
+    PieceResponse {
+        request_id: String,
+        found: bool,
+        size: u64,
+    },
+    /// 대역폭 측정: `duration_ms` 동안 생성 데이터를 보내 달라는 요청
+    BenchmarkRequest {
+        session_id: String,
+        duration_ms: u64,
+    },
+    /// 대역폭 측정 데이터 (헤더 뒤에 생성 데이터, 디스크 미사용)
+    BenchmarkData {
+        session_id: String,
+    },
+    /// 부분 요청: 제공 목록(`path`가 없을 때) 또는 파일의 바이트 범위 요청 (`end`가 없으면 끝까지)
+    RangeRequest {
+        request_id: String,
+        offer_id: String,
+        path: Option<String>,
+        #[serde(default)]
+        start: u64,
+        #[serde(default)]
+        end: Option<u64>,
+    },
+    /// 부분 요청 응답 (헤더 뒤에 목록 JSON 또는 범위 데이터)
+    RangeResponse {
+        request_id: String,
+        found: bool,
+        size: u64,
+    },
+    /// 🆕 연결 직후 노드 ID/인증서 지문 알림 (연결 비밀에 노드 키로 서명, 지문은 TLS 지문과 대조용, `quic::sas` 참고)
+    IdentityProof {
+        node_id: String,
+        fingerprint: Option<String>,
+        signature: String,
+    },
 }
 
 impl Command {
@@ -109,4 +231,41 @@ impl Command {
         serde_json::from_slice(bytes)
             .map_err(|e| anyhow!("Failed to deserialize command: {}", e))
     }
+
+    /// 🆕 버전이 있는 시그널링 메시지의 버전 (그 외 명령은 None)
+    pub fn signaling_version(&self) -> Option<u32> {
+        match self {
+            Command::Offer { version, .. }
+            | Command::Answer { version, .. }
+            | Command::Renegotiate { version, .. } => Some(*version),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signaling_version_negotiation() {
+        // 버전 필드가 없는 이전 Offer는 v1
+        let legacy = Command::from_bytes(
+            br#"{"type":"Offer","room_id":"room-1","sdp":"v=0","target":null}"#,
+        )
+        .unwrap();
+        assert_eq!(legacy.signaling_version(), Some(MIN_SIGNALING_VERSION));
+
+        assert_eq!(negotiate_signaling_version(1), Some(1));
+        assert_eq!(
+            negotiate_signaling_version(SIGNALING_VERSION + 1),
+            Some(SIGNALING_VERSION)
+        );
+        assert_eq!(negotiate_signaling_version(0), None);
+
+        let bye =
+            Command::from_bytes(br#"{"type":"Bye","room_id":"room-1","target":null}"#).unwrap();
+        assert!(matches!(bye, Command::Bye { reason: None, .. }));
+        assert_eq!(bye.signaling_version(), None);
+    }
 }
@@ -1,18 +1,53 @@
 use anyhow::Result;
+use futures::future::select_ok;
 use quinn::{ClientConfig, Endpoint};
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 use tracing::info;
 
+use super::grid_route::TRANSFER_ALPN;
+use super::identity::{signature_algorithms, QuicIdentity};
+use super::pacing::QuicPacingConfig;
 use crate::protocol::Command;
 
+/// 후보 연결 경쟁 전체 제한 시간
+const RACE_TIMEOUT: Duration = Duration::from_secs(15);
+
 pub struct QuicClient {
     endpoint: Option<Endpoint>,
+    /// 🆕 지금까지 연결에 쓴 엔드포인트 (연결마다 따로 만들므로 재바인딩 시 모두 필요)
+    endpoints: Vec<Endpoint>,
+    pacing: QuicPacingConfig,
+    /// 🆕 클라이언트 인증서로 제시할 기기 인증서 (None이면 제시하지 않음)
+    identity: Option<QuicIdentity>,
 }
 
 impl QuicClient {
     pub fn new() -> Self {
-        Self { endpoint: None }
+        Self {
+            endpoint: None,
+            endpoints: Vec::new(),
+            pacing: QuicPacingConfig::default(),
+            identity: None,
+        }
+    }
+
+    /// 🆕 기기 인증서를 클라이언트 인증서로 제시 (수락한 쪽이 이 기기의 지문을 확인)
+    pub fn with_identity(mut self, identity: QuicIdentity) -> Self {
+        self.identity = Some(identity);
+        self
+    }
+
+    /// 혼잡 제어/페이싱 설정
+    pub fn with_pacing(mut self, pacing: QuicPacingConfig) -> Self {
+        self.pacing = pacing;
+        self
+    }
+
+    /// 혼잡 제어/페이싱 설정 변경 (다음 연결부터 적용)
+    pub fn set_pacing(&mut self, pacing: QuicPacingConfig) {
+        self.pacing = pacing;
     }
 
     pub async fn connect(
@@ -31,11 +66,76 @@ impl QuicClient {
 
         info!("✅ QUIC 연결 성공: {}", server_addr);
 
-        self.endpoint = Some(endpoint);
+        self.track(endpoint);
 
         Ok(conn)
     }
 
+    /// 여러 후보 주소로 동시에 연결해 가장 먼저 성공한 연결 사용 (나머지 시도는 취소)
+    pub async fn connect_first(
+        &mut self,
+        candidates: &[SocketAddr],
+        server_name: &str,
+    ) -> Result<(SocketAddr, quinn::Connection)> {
+        if candidates.is_empty() {
+            return Err(anyhow::anyhow!("연결 후보 주소가 없습니다"));
+        }
+        let client_config = self.configure_client()?;
+
+        let mut endpoint = Endpoint::client("0.0.0.0:0".parse()?)?;
+        endpoint.set_default_client_config(client_config);
+
+        info!("QUIC 연결 경쟁 시작: {:?}", candidates);
+
+        let attempts = candidates.iter().map(|&addr| {
+            let endpoint = endpoint.clone();
+            Box::pin(async move {
+                let conn = endpoint.connect(addr, server_name)?.await?;
+                anyhow::Ok((addr, conn))
+            })
+        });
+        let ((addr, conn), _) = tokio::time::timeout(RACE_TIMEOUT, select_ok(attempts))
+            .await
+            .map_err(|_| anyhow::anyhow!("모든 후보 연결 시간 초과"))??;
+
+        info!(
+            "✅ QUIC 연결 성공: {} (후보 {}개 중)",
+            addr,
+            candidates.len()
+        );
+
+        self.track(endpoint);
+
+        Ok((addr, conn))
+    }
+
+    /// 🆕 열린 연결이 남은 엔드포인트 전부를 새 로컬 소켓으로 재바인딩 (마이그레이션된 연결 수 반환)
+    ///
+    /// 네트워크가 바뀌어 이전 로컬 주소가 사라져도 QUIC 연결 마이그레이션으로
+    /// 같은 연결(과 그 위의 스트림)이 새 경로에서 계속됩니다. 상대 주소가 바뀐 경우는 복구되지 않습니다.
+    pub fn rebind(&mut self) -> Result<usize> {
+        self.endpoints
+            .retain(|endpoint| endpoint.open_connections() > 0);
+        let mut migrated = 0;
+        for endpoint in &self.endpoints {
+            endpoint.rebind(std::net::UdpSocket::bind("0.0.0.0:0")?)?;
+            migrated += endpoint.open_connections();
+        }
+        info!(
+            "🔀 QUIC 클라이언트 재바인딩: 엔드포인트 {}개, 연결 {}개",
+            self.endpoints.len(),
+            migrated
+        );
+        Ok(migrated)
+    }
+
+    fn track(&mut self, endpoint: Endpoint) {
+        self.endpoints
+            .retain(|endpoint| endpoint.open_connections() > 0);
+        self.endpoints.push(endpoint.clone());
+        self.endpoint = Some(endpoint);
+    }
+
     pub async fn send_command(&self, conn: &quinn::Connection, cmd: Command) -> anyhow::Result<Command> {
         let (mut send, mut recv) = conn.open_bi().await?;
 
@@ -57,12 +157,20 @@ impl QuicClient {
     }
 
     fn configure_client(&self) -> Result<ClientConfig> {
-        let mut client_crypto = rustls::ClientConfig::builder()
+        let builder = rustls::ClientConfig::builder()
             .dangerous()
-            .with_custom_certificate_verifier(Arc::new(SkipServerVerification))
-            .with_no_client_auth();
+            .with_custom_certificate_verifier(Arc::new(SkipServerVerification));
+        let mut client_crypto = match &self.identity {
+            Some(identity) => builder.with_client_auth_cert(
+                vec![rustls::pki_types::CertificateDer::from(
+                    identity.cert_der.clone(),
+                )],
+                rustls::pki_types::PrivatePkcs8KeyDer::from(identity.key_der.clone()).into(),
+            )?,
+            None => builder.with_no_client_auth(),
+        };
 
-        client_crypto.alpn_protocols = vec![b"ponswarp".to_vec()];
+        client_crypto.alpn_protocols = vec![TRANSFER_ALPN.to_vec()];
 
         let mut client_config = ClientConfig::new(Arc::new(
             quinn::crypto::rustls::QuicClientConfig::try_from(client_crypto)?,
@@ -86,6 +194,9 @@ impl QuicClient {
         // 데이터그램 크기 최적화
         transport_config.datagram_receive_buffer_size(Some(32 * 1024 * 1024)); // 32MB
 
+        // 혼잡 제어기가 페이서의 전송 간격을 결정
+        self.pacing.apply(&mut transport_config);
+
         client_config.transport_config(Arc::new(transport_config));
 
         Ok(client_config)
@@ -105,8 +216,11 @@ impl Default for QuicClient {
     }
 }
 
+/// 자체 서명 서버 인증서 수락 (발급자 체인은 건너뛰고 핸드셰이크 서명만 인증서 공개 키로 검증)
+///
+/// 서명을 검증하므로 상대가 보여준 인증서 지문은 그 개인 키를 가진 상대에게 묶입니다.
 #[derive(Debug)]
-struct SkipServerVerification;
+pub(crate) struct SkipServerVerification;
 
 impl rustls::client::danger::ServerCertVerifier for SkipServerVerification {
     fn verify_server_cert(
@@ -122,33 +236,23 @@ impl rustls::client::danger::ServerCertVerifier for SkipServerVerification {
 
     fn verify_tls12_signature(
         &self,
-        _message: &[u8],
-        _cert: &rustls::pki_types::CertificateDer<'_>,
-        _dss: &rustls::DigitallySignedStruct,
+        message: &[u8],
+        cert: &rustls::pki_types::CertificateDer<'_>,
+        dss: &rustls::DigitallySignedStruct,
     ) -> Result<rustls::client::danger::HandshakeSignatureValid, rustls::Error> {
-        Ok(rustls::client::danger::HandshakeSignatureValid::assertion())
+        rustls::crypto::verify_tls12_signature(message, cert, dss, &signature_algorithms())
     }
 
     fn verify_tls13_signature(
         &self,
-        _message: &[u8],
-        _cert: &rustls::pki_types::CertificateDer<'_>,
-        _dss: &rustls::DigitallySignedStruct,
+        message: &[u8],
+        cert: &rustls::pki_types::CertificateDer<'_>,
+        dss: &rustls::DigitallySignedStruct,
     ) -> Result<rustls::client::danger::HandshakeSignatureValid, rustls::Error> {
-        Ok(rustls::client::danger::HandshakeSignatureValid::assertion())
+        rustls::crypto::verify_tls13_signature(message, cert, dss, &signature_algorithms())
     }
 
     fn supported_verify_schemes(&self) -> Vec<rustls::SignatureScheme> {
-        vec![
-            rustls::SignatureScheme::RSA_PKCS1_SHA256,
-            rustls::SignatureScheme::RSA_PKCS1_SHA384,
-            rustls::SignatureScheme::RSA_PKCS1_SHA512,
-            rustls::SignatureScheme::ECDSA_NISTP256_SHA256,
-            rustls::SignatureScheme::ECDSA_NISTP384_SHA384,
-            rustls::SignatureScheme::RSA_PSS_SHA256,
-            rustls::SignatureScheme::RSA_PSS_SHA384,
-            rustls::SignatureScheme::RSA_PSS_SHA512,
-            rustls::SignatureScheme::ED25519,
-        ]
+        signature_algorithms().supported_schemes()
     }
 }
@@ -0,0 +1,161 @@
+//! QUIC 서버 엔드포인트를 Grid Swarm과 공유하기 위한 연결 라우팅
+//!
+//! 직접 전송과 Grid는 ALPN으로 구분합니다 (`ponswarp` / `ponswarp-grid`).
+//! 한 엔드포인트에서 여러 Swarm이 돌 수 있으므로, Grid 연결은 클라이언트가 SNI에 담아 보낸
+//! info_hash(`<hex 앞 32자>.<hex 뒤 32자>.grid.ponswarp`)로 해당 Swarm에 전달합니다.
+//! 전용 엔드포인트를 쓰는 Swarm은 SNI를 무시하므로 어느 쪽으로 연결해도 동작합니다.
+//! `grid-experimental`이 없는 빌드에는 등록할 Swarm이 없으므로 Grid 연결은 모두 닫습니다.
+
+#[cfg(feature = "grid-experimental")]
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use quinn::Connection;
+#[cfg(feature = "grid-experimental")]
+use quinn::Endpoint;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+
+/// 직접 전송 ALPN
+pub const TRANSFER_ALPN: &[u8] = b"ponswarp";
+
+/// Grid 피어 간 ALPN
+pub const GRID_ALPN: &[u8] = b"ponswarp-grid";
+
+/// Grid SNI 접미사
+const GRID_SERVER_NAME_SUFFIX: &str = ".grid.ponswarp";
+
+/// 라우팅 대기 중인 연결 수 (Swarm별)
+#[cfg(feature = "grid-experimental")]
+const ROUTE_QUEUE: usize = 16;
+
+#[cfg(feature = "grid-experimental")]
+/// info_hash를 담은 Grid 연결용 서버 이름 (DNS 레이블 63자 제한 때문에 둘로 나눔)
+pub fn grid_server_name(info_hash: &[u8; 32]) -> String {
+    let hex = hex::encode(info_hash);
+    format!("{}.{}{}", &hex[..32], &hex[32..], GRID_SERVER_NAME_SUFFIX)
+}
+
+/// Grid 서버 이름에서 info_hash 추출
+pub fn parse_grid_server_name(name: &str) -> Option<[u8; 32]> {
+    let labels = name.strip_suffix(GRID_SERVER_NAME_SUFFIX)?;
+    let (head, tail) = labels.split_once('.')?;
+    if head.len() != 32 || tail.len() != 32 {
+        return None;
+    }
+    hex::decode(format!("{}{}", head, tail))
+        .ok()?
+        .try_into()
+        .ok()
+}
+
+/// 연결에서 협상된 ALPN과 SNI
+fn handshake_info(connection: &Connection) -> (Option<Vec<u8>>, Option<String>) {
+    connection
+        .handshake_data()
+        .and_then(|data| data.downcast::<quinn::crypto::rustls::HandshakeData>().ok())
+        .map(|data| (data.protocol, data.server_name))
+        .unwrap_or_default()
+}
+
+/// info_hash별 Grid Swarm 연결 라우팅 테이블
+#[derive(Clone, Default)]
+pub struct GridRouter {
+    routes: Arc<DashMap<[u8; 32], mpsc::Sender<Connection>>>,
+}
+
+impl GridRouter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// info_hash의 Grid 연결을 받을 채널 등록 (이미 실행 중인 Swarm이 있으면 None)
+    #[cfg(feature = "grid-experimental")]
+    pub fn register(&self, info_hash: [u8; 32]) -> Option<mpsc::Receiver<Connection>> {
+        let (tx, rx) = mpsc::channel(ROUTE_QUEUE);
+        match self.routes.entry(info_hash) {
+            Entry::Occupied(mut entry) => {
+                // 스스로 끝난 Swarm의 경로는 교체
+                if !entry.get().is_closed() {
+                    return None;
+                }
+                entry.insert(tx);
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(tx);
+            }
+        }
+        Some(rx)
+    }
+
+    #[cfg(feature = "grid-experimental")]
+    pub fn unregister(&self, info_hash: &[u8; 32]) {
+        self.routes.remove(info_hash);
+    }
+
+    /// Grid 연결이면 해당 Swarm에 넘기고 None, 직접 전송 연결이면 그대로 반환
+    ///
+    /// 등록되지 않은 info_hash의 Grid 연결은 닫습니다.
+    pub async fn route(&self, connection: Connection) -> Option<Connection> {
+        let (protocol, server_name) = handshake_info(&connection);
+        if protocol.as_deref() != Some(GRID_ALPN) {
+            return Some(connection);
+        }
+
+        let addr = connection.remote_address();
+        let route = server_name
+            .as_deref()
+            .and_then(parse_grid_server_name)
+            .and_then(|info_hash| self.routes.get(&info_hash).map(|tx| tx.clone()));
+        match route {
+            Some(tx) => {
+                debug!("🐝 Grid 연결 전달: {} ({:?})", addr, server_name);
+                if tx.send(connection.clone()).await.is_err() {
+                    connection.close(0u32.into(), b"grid job stopped");
+                }
+            }
+            None => {
+                warn!("알 수 없는 Grid 연결 거부: {} ({:?})", addr, server_name);
+                connection.close(0u32.into(), b"unknown info hash");
+            }
+        }
+        None
+    }
+}
+
+/// Grid Swarm이 함께 쓸 QUIC 서버 엔드포인트 (서버를 중지하면 함께 끊김)
+#[cfg(feature = "grid-experimental")]
+#[derive(Clone)]
+pub struct SharedGridEndpoint {
+    pub endpoint: Endpoint,
+    pub router: GridRouter,
+}
+
+#[cfg(all(test, feature = "grid-experimental"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_server_name_roundtrip() {
+        let info_hash: [u8; 32] = std::array::from_fn(|i| i as u8 * 7);
+        let name = grid_server_name(&info_hash);
+        assert!(name.split('.').all(|label| label.len() <= 63));
+        assert!(rustls::pki_types::ServerName::try_from(name.clone()).is_ok());
+        assert_eq!(parse_grid_server_name(&name), Some(info_hash));
+
+        assert_eq!(parse_grid_server_name("localhost"), None);
+        assert_eq!(parse_grid_server_name("abcd.grid.ponswarp"), None);
+    }
+
+    #[test]
+    fn test_register_replaces_closed_route() {
+        let router = GridRouter::new();
+        let rx = router.register([1u8; 32]).unwrap();
+        assert!(router.register([1u8; 32]).is_none());
+
+        drop(rx);
+        assert!(router.register([1u8; 32]).is_some());
+        router.unregister(&[1u8; 32]);
+        assert!(router.routes.is_empty());
+    }
+}
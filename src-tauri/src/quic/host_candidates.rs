@@ -0,0 +1,68 @@
+//! 호스트 후보 수집
+//!
+//! 기본 라우트 IP 하나만 알리면 유선/Wi-Fi/VPN이 함께 있는 기기에서 보조 NIC 쪽의 더 좋은 경로를
+//! 놓칩니다. 루프백이 아닌 모든 인터페이스 주소를 후보로 모으되, 기본 라우트 IP를 맨 앞에 둡니다.
+
+use std::net::IpAddr;
+use tracing::warn;
+
+/// 호스트 후보 최대 개수 (QR 후보 8개 안에 공인 매핑 주소 자리를 남김)
+pub const MAX_HOST_CANDIDATES: usize = 6;
+
+/// 이 기기의 호스트 후보 IP (기본 라우트 IP 우선)
+///
+/// QUIC 서버가 `0.0.0.0`에 바인딩하므로 IPv4만 모으며, 링크 로컬(169.254.x.x)은 제외합니다.
+/// 인터페이스 조회에 실패하면 기본 라우트 IP만 반환합니다.
+pub fn host_ips(default_route: Option<IpAddr>) -> Vec<IpAddr> {
+    let interfaces = match if_addrs::get_if_addrs() {
+        Ok(interfaces) => interfaces.iter().map(|iface| iface.ip()).collect(),
+        Err(e) => {
+            warn!("네트워크 인터페이스 조회 실패: {}", e);
+            Vec::new()
+        }
+    };
+    select(default_route, interfaces)
+}
+
+fn select(default_route: Option<IpAddr>, interfaces: Vec<IpAddr>) -> Vec<IpAddr> {
+    let mut ips = Vec::new();
+    for ip in default_route.into_iter().chain(interfaces) {
+        if is_host_candidate(ip) && !ips.contains(&ip) {
+            ips.push(ip);
+        }
+    }
+    ips.truncate(MAX_HOST_CANDIDATES);
+    ips
+}
+
+fn is_host_candidate(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => !ip.is_loopback() && !ip.is_link_local() && !ip.is_unspecified(),
+        IpAddr::V6(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_orders_default_route_first() {
+        let ip = |s: &str| s.parse::<IpAddr>().unwrap();
+        let interfaces = vec![
+            ip("127.0.0.1"),
+            ip("192.168.0.10"),
+            ip("169.254.3.4"),
+            ip("fe80::1"),
+            ip("10.8.0.2"),
+            ip("192.168.0.10"),
+        ];
+        assert_eq!(
+            select(Some(ip("10.8.0.2")), interfaces),
+            vec![ip("10.8.0.2"), ip("192.168.0.10")]
+        );
+
+        let many = (1..=10).map(|i| ip(&format!("10.0.0.{}", i))).collect();
+        assert_eq!(select(None, many).len(), MAX_HOST_CANDIDATES);
+    }
+}
@@ -0,0 +1,211 @@
+//! QUIC 서버 인증서 (기기 식별용)
+//!
+//! 인증서를 앱 데이터 폴더에 저장해 재시작 후에도 같은 인증서를 쓰므로,
+//! 인증서 SHA-256 지문으로 상대 기기를 식별할 수 있습니다 (자동 수락 규칙 등).
+//!
+//! 연결하는 쪽도 같은 인증서를 클라이언트 인증서로 제시하므로, 양쪽 모두 상대 지문을 얻습니다.
+//! 자체 서명 인증서라 발급자는 확인하지 않지만, 핸드셰이크 서명은 인증서 공개 키로 검증하므로
+//! 지문은 그 개인 키를 가진 상대에게만 묶입니다.
+
+use anyhow::Result;
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use tracing::{info, warn};
+
+/// 인증서 파일 이름 (DER)
+pub const CERT_FILE: &str = "quic_identity.crt";
+/// 개인 키 파일 이름 (PKCS#8 DER)
+pub const KEY_FILE: &str = "quic_identity.key";
+
+/// 서버 인증서와 개인 키
+#[derive(Clone)]
+pub struct QuicIdentity {
+    pub cert_der: Vec<u8>,
+    pub key_der: Vec<u8>,
+}
+
+impl QuicIdentity {
+    /// 새 자체 서명 인증서 생성
+    pub fn generate() -> Result<Self> {
+        let cert =
+            rcgen::generate_simple_self_signed(vec!["localhost".into(), "ponswarp.local".into()])?;
+        Ok(Self {
+            cert_der: cert.cert.der().to_vec(),
+            key_der: cert.key_pair.serialize_der(),
+        })
+    }
+
+    /// 폴더에 저장된 인증서 로드 (없거나 손상되었으면 새로 생성해 저장)
+    pub fn load_or_generate(dir: &Path) -> Result<Self> {
+        let cert_path = dir.join(CERT_FILE);
+        let key_path = dir.join(KEY_FILE);
+        if let (Ok(cert_der), Ok(key_der)) = (std::fs::read(&cert_path), std::fs::read(&key_path)) {
+            if !cert_der.is_empty() && !key_der.is_empty() {
+                return Ok(Self { cert_der, key_der });
+            }
+            warn!("저장된 QUIC 인증서가 비어 있어 새로 생성합니다");
+        }
+
+        let identity = Self::generate()?;
+        std::fs::create_dir_all(dir)?;
+        std::fs::write(&cert_path, &identity.cert_der)?;
+        write_private_key(&key_path, &identity.key_der)?;
+        info!("🔑 QUIC 인증서 생성: {}", identity.fingerprint());
+        Ok(identity)
+    }
+
+    /// 인증서 지문 (SHA-256 hex)
+    pub fn fingerprint(&self) -> String {
+        fingerprint_of(&self.cert_der)
+    }
+}
+
+/// 개인 키 파일 저장 (유닉스에서는 소유자만 읽을 수 있게 0600)
+fn write_private_key(path: &Path, key_der: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)?.write_all(key_der)
+}
+
+/// 인증서 DER의 SHA-256 지문 (hex 소문자)
+pub fn fingerprint_of(cert_der: &[u8]) -> String {
+    hex::encode(Sha256::digest(cert_der))
+}
+
+/// 연결 상대가 제시한 인증서 지문 (연결한 쪽이면 서버 인증서, 수락한 쪽이면 클라이언트 인증서)
+///
+/// 클라이언트 인증서를 제시하지 않은 상대(인증서가 없는 기기 등)의 연결은 None입니다.
+pub fn peer_fingerprint(conn: &quinn::Connection) -> Option<String> {
+    let identity = conn.peer_identity()?;
+    let certs = identity
+        .downcast::<Vec<rustls::pki_types::CertificateDer<'static>>>()
+        .ok()?;
+    certs.first().map(|cert| fingerprint_of(cert.as_ref()))
+}
+
+/// 핸드셰이크 서명 검증에 쓰는 알고리즘 (ring 공급자)
+pub(crate) fn signature_algorithms() -> rustls::crypto::WebPkiSupportedAlgorithms {
+    rustls::crypto::ring::default_provider().signature_verification_algorithms
+}
+
+/// 지문 비교용 정규화 (대소문자, `:` 구분자 무시)
+pub fn normalize_fingerprint(fingerprint: &str) -> String {
+    fingerprint
+        .chars()
+        .filter(|c| c.is_ascii_hexdigit())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_identity_persists() {
+        let dir = std::env::temp_dir().join(format!("ponswarp-identity-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let first = QuicIdentity::load_or_generate(&dir).unwrap();
+        let second = QuicIdentity::load_or_generate(&dir).unwrap();
+        assert_eq!(first.fingerprint(), second.fingerprint());
+        assert_eq!(first.fingerprint().len(), 64);
+
+        assert_eq!(normalize_fingerprint("AB:cd:01"), "abcd01");
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_inbound_connection_has_client_fingerprint() {
+        use crate::quic::client::QuicClient;
+        use crate::quic::QuicServer;
+
+        let server_identity = QuicIdentity::generate().unwrap();
+        let client_identity = QuicIdentity::generate().unwrap();
+        let mut server =
+            QuicServer::new("127.0.0.1:0".parse().unwrap()).with_identity(server_identity.clone());
+        let mut accepted = server.take_connection_receiver().unwrap();
+        server.start().await.unwrap();
+        let addr = server.local_addr().unwrap();
+
+        // 양쪽 모두 서명이 검증된 상대 인증서 지문을 얻음
+        let mut client = QuicClient::new().with_identity(client_identity.clone());
+        let outbound = client.connect(addr, "localhost").await.unwrap();
+        let inbound = accepted.recv().await.unwrap().connection;
+        assert_eq!(
+            peer_fingerprint(&outbound),
+            Some(server_identity.fingerprint())
+        );
+        assert_eq!(
+            peer_fingerprint(&inbound),
+            Some(client_identity.fingerprint())
+        );
+
+        // 인증서를 제시하지 않은 클라이언트도 연결되지만 지문은 없음
+        let _anonymous = QuicClient::new().connect(addr, "localhost").await.unwrap();
+        let inbound = accepted.recv().await.unwrap().connection;
+        assert_eq!(peer_fingerprint(&inbound), None);
+
+        server.shutdown().await;
+    }
+
+    /// 다른 기기의 인증서를 제 키로 제시하는 서버 (QR/연락처 지문 고정을 속이려는 상대)
+    #[derive(Debug)]
+    struct ReplayedCert(Arc<rustls::sign::CertifiedKey>);
+
+    impl rustls::server::ResolvesServerCert for ReplayedCert {
+        fn resolve(
+            &self,
+            _hello: rustls::server::ClientHello<'_>,
+        ) -> Option<Arc<rustls::sign::CertifiedKey>> {
+            Some(self.0.clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replayed_certificate_fails_handshake() {
+        use crate::quic::client::QuicClient;
+        use crate::quic::grid_route::TRANSFER_ALPN;
+        use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+
+        let victim = QuicIdentity::generate().unwrap();
+        let attacker = QuicIdentity::generate().unwrap();
+        let key = PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(attacker.key_der.clone()));
+        let certified = rustls::sign::CertifiedKey::new(
+            vec![CertificateDer::from(victim.cert_der.clone())],
+            rustls::crypto::ring::sign::any_supported_type(&key).unwrap(),
+        );
+
+        let mut server_crypto = rustls::ServerConfig::builder()
+            .with_no_client_auth()
+            .with_cert_resolver(Arc::new(ReplayedCert(Arc::new(certified))));
+        server_crypto.alpn_protocols = vec![TRANSFER_ALPN.to_vec()];
+        let server_config = quinn::ServerConfig::with_crypto(Arc::new(
+            quinn::crypto::rustls::QuicServerConfig::try_from(server_crypto).unwrap(),
+        ));
+        let endpoint =
+            quinn::Endpoint::server(server_config, "127.0.0.1:0".parse().unwrap()).unwrap();
+        let addr = endpoint.local_addr().unwrap();
+        let acceptor = endpoint.clone();
+        tokio::spawn(async move {
+            while let Some(incoming) = acceptor.accept().await {
+                let _ = incoming.await;
+            }
+        });
+
+        // 인증서만 복사해서는 핸드셰이크 서명을 만들 수 없어 연결이 거부됨
+        let result = QuicClient::new().connect(addr, "localhost").await;
+        assert!(result.is_err());
+
+        endpoint.close(0u32.into(), b"");
+    }
+}
@@ -1,6 +1,14 @@
 pub mod client;
 pub mod client_enhanced;
+pub mod grid_route;
+pub mod host_candidates;
+pub mod identity;
+pub mod pacing;
+pub mod qr_payload;
+pub mod quality;
+pub mod sas;
 pub mod server;
 
 pub use server::QuicServer;
 pub use client_enhanced::QuicClientEnhanced;
+pub use pacing::QuicPacingConfig;
@@ -0,0 +1,93 @@
+//! QUIC 혼잡 제어 / 페이싱 설정
+//!
+//! quinn은 혼잡 윈도우와 RTT로 패킷 간격을 계산해 항상 페이싱합니다.
+//! 여기서는 페이서가 따르는 혼잡 제어 알고리즘과 초기 윈도우(연결 직후 버스트 크기)를 고릅니다.
+
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// 혼잡 제어 알고리즘
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CongestionAlgorithm {
+    /// quinn 기본값
+    #[default]
+    Cubic,
+    /// 대역폭/RTT 추정 기반 (손실이 있는 고대역 링크에 유리, quinn에서는 실험적)
+    Bbr,
+    NewReno,
+}
+
+/// QUIC 페이싱 설정 (새로 맺는 연결부터 적용)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuicPacingConfig {
+    #[serde(default)]
+    pub congestion: CongestionAlgorithm,
+    /// 초기 혼잡 윈도우 (bytes, None = quinn 기본값)
+    #[serde(default)]
+    pub initial_window: Option<u64>,
+}
+
+impl QuicPacingConfig {
+    /// 초기 윈도우 하한 (데이터그램 몇 개는 보낼 수 있어야 함)
+    const MIN_INITIAL_WINDOW: u64 = 16 * 1024;
+
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(window) = self.initial_window {
+            if window < Self::MIN_INITIAL_WINDOW {
+                return Err(format!(
+                    "initial_window must be >= {}",
+                    Self::MIN_INITIAL_WINDOW
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// transport 설정에 혼잡 제어기 적용
+    pub fn apply(&self, transport: &mut quinn::TransportConfig) {
+        use quinn::congestion::{BbrConfig, CubicConfig, NewRenoConfig};
+
+        match self.congestion {
+            CongestionAlgorithm::Cubic => {
+                let mut config = CubicConfig::default();
+                if let Some(window) = self.initial_window {
+                    config.initial_window(window);
+                }
+                transport.congestion_controller_factory(Arc::new(config));
+            }
+            CongestionAlgorithm::Bbr => {
+                let mut config = BbrConfig::default();
+                if let Some(window) = self.initial_window {
+                    config.initial_window(window);
+                }
+                transport.congestion_controller_factory(Arc::new(config));
+            }
+            CongestionAlgorithm::NewReno => {
+                let mut config = NewRenoConfig::default();
+                if let Some(window) = self.initial_window {
+                    config.initial_window(window);
+                }
+                transport.congestion_controller_factory(Arc::new(config));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pacing_config_defaults_and_validation() {
+        let config: QuicPacingConfig = serde_json::from_str("{}").unwrap();
+        assert_eq!(config, QuicPacingConfig::default());
+        assert!(config.validate().is_ok());
+
+        let config: QuicPacingConfig =
+            serde_json::from_str(r#"{"congestion":"bbr","initialWindow":1024}"#).unwrap();
+        assert_eq!(config.congestion, CongestionAlgorithm::Bbr);
+        assert!(config.validate().is_err());
+    }
+}
@@ -0,0 +1,168 @@
+//! QR 코드 연결 정보
+//!
+//! 휴대폰-데스크톱, 인터넷 없는 방처럼 같은 네트워크의 기기를 QR 코드 하나로 연결하기 위한 정보입니다.
+//! 후보 주소, QUIC 인증서 지문, 일회용 페어링 키를 담고 시그널링 키(`bootstrap::mailbox`)로 서명합니다.
+//! 스캔한 기기는 연결한 서버의 인증서 지문과 페어링 응답 키를 QR 값과 비교하므로, 중간자가 끼어들 수 없습니다.
+//! 서버는 핸드셰이크에서 인증서 키로 서명해야 하므로(`quic::client`의 서명 검증) 인증서만 복사한 상대는
+//! 지문 비교 전에 연결이 실패합니다.
+//!
+//! 형식: `ponswarp-qr1:` + base64url(bincode(서명된 본문))
+
+use crate::bootstrap::mailbox::{self, SigningIdentity};
+use crate::transfer::pairing::OFFER_TTL;
+use crate::util::now_secs;
+use anyhow::{anyhow, Result};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+
+/// QR 문자열 접두사 (버전 포함)
+pub const PAYLOAD_PREFIX: &str = "ponswarp-qr1:";
+
+/// QR 크기를 제한하기 위한 최대 후보 주소 수
+const MAX_CANDIDATES: usize = 8;
+
+/// 생성 시각이 현재보다 앞선 것을 허용하는 오차 (기기 간 시계 차이)
+const MAX_CLOCK_SKEW_SECS: u64 = 120;
+
+const SIGNING_DOMAIN: &[u8] = b"ponswarp connection qr v1";
+
+/// QR 코드에 담는 연결 정보
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConnectionQrPayload {
+    /// 서명한 기기의 노드 ID (Ed25519 공개 키)
+    pub node_id: [u8; 32],
+    pub candidates: Vec<SocketAddr>,
+    /// QUIC 서버 인증서 SHA-256 지문
+    pub fingerprint: [u8; 32],
+    pub pairing_id: String,
+    /// 일회용 X25519 페어링 공개 키
+    pub pairing_key: [u8; 32],
+    /// 생성 시각 (Unix 초)
+    pub created_at: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SignedBlob {
+    body: Vec<u8>,
+    signature: Vec<u8>,
+}
+
+fn signing_bytes(body: &[u8]) -> Vec<u8> {
+    let mut bytes = SIGNING_DOMAIN.to_vec();
+    bytes.extend_from_slice(body);
+    bytes
+}
+
+fn decode_hex_32(value: &str) -> Result<[u8; 32]> {
+    hex::decode(value)?
+        .try_into()
+        .map_err(|_| anyhow!("잘못된 키/지문 길이"))
+}
+
+impl ConnectionQrPayload {
+    /// 현재 시각으로 연결 정보 생성
+    pub fn new(
+        identity: &SigningIdentity,
+        candidates: Vec<SocketAddr>,
+        fingerprint: &str,
+        pairing_id: String,
+        pairing_key: [u8; 32],
+    ) -> Result<Self> {
+        if candidates.is_empty() {
+            return Err(anyhow!("후보 주소가 없습니다"));
+        }
+        Ok(Self {
+            node_id: decode_hex_32(&identity.node_id())?,
+            candidates: candidates.into_iter().take(MAX_CANDIDATES).collect(),
+            fingerprint: decode_hex_32(fingerprint)?,
+            pairing_id,
+            pairing_key,
+            created_at: now_secs(),
+        })
+    }
+
+    /// 서명 후 QR 문자열로 인코딩
+    pub fn encode(&self, identity: &SigningIdentity) -> Result<String> {
+        let body = bincode::serialize(self)?;
+        let signature = hex::decode(identity.sign(&signing_bytes(&body)))?;
+        let blob = bincode::serialize(&SignedBlob { body, signature })?;
+        Ok(format!(
+            "{}{}",
+            PAYLOAD_PREFIX,
+            URL_SAFE_NO_PAD.encode(blob)
+        ))
+    }
+
+    /// QR 문자열 디코딩 (서명과 유효 시간 검증)
+    pub fn decode(payload: &str) -> Result<Self> {
+        let encoded = payload
+            .trim()
+            .strip_prefix(PAYLOAD_PREFIX)
+            .ok_or_else(|| anyhow!("PonsWarp 연결 QR 코드가 아닙니다"))?;
+        let blob: SignedBlob = bincode::deserialize(&URL_SAFE_NO_PAD.decode(encoded)?)?;
+        let parsed: Self = bincode::deserialize(&blob.body)?;
+
+        mailbox::verify(
+            &hex::encode(parsed.node_id),
+            &signing_bytes(&blob.body),
+            &hex::encode(&blob.signature),
+        )?;
+
+        let now = now_secs();
+        if parsed.created_at > now + MAX_CLOCK_SKEW_SECS
+            || now.saturating_sub(parsed.created_at) > OFFER_TTL.as_secs()
+        {
+            return Err(anyhow!("만료된 QR 코드입니다. 새로 생성하세요"));
+        }
+        if parsed.candidates.is_empty() {
+            return Err(anyhow!("후보 주소가 없습니다"));
+        }
+        Ok(parsed)
+    }
+
+    /// 노드 ID (hex)
+    pub fn node_id_hex(&self) -> String {
+        hex::encode(self.node_id)
+    }
+
+    /// 인증서 지문 (hex, `identity::fingerprint_of`와 같은 형식)
+    pub fn fingerprint_hex(&self) -> String {
+        hex::encode(self.fingerprint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode_and_tamper() {
+        let identity = SigningIdentity::generate();
+        let payload = ConnectionQrPayload::new(
+            &identity,
+            vec!["192.168.0.10:5000".parse().unwrap()],
+            &"ab".repeat(32),
+            "pairing".to_string(),
+            [7u8; 32],
+        )
+        .unwrap();
+
+        let encoded = payload.encode(&identity).unwrap();
+        assert!(encoded.starts_with(PAYLOAD_PREFIX));
+        assert_eq!(ConnectionQrPayload::decode(&encoded).unwrap(), payload);
+
+        // 다른 키로 서명한 본문은 거부
+        let mut forged = payload.clone();
+        forged.candidates = vec!["10.0.0.1:5000".parse().unwrap()];
+        let other = SigningIdentity::generate();
+        let body = bincode::serialize(&forged).unwrap();
+        let signature = hex::decode(other.sign(&signing_bytes(&body))).unwrap();
+        let blob = bincode::serialize(&SignedBlob { body, signature }).unwrap();
+        let tampered = format!("{}{}", PAYLOAD_PREFIX, URL_SAFE_NO_PAD.encode(blob));
+        assert!(ConnectionQrPayload::decode(&tampered).is_err());
+
+        assert!(ConnectionQrPayload::decode("hello").is_err());
+    }
+}
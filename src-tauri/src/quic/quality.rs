@@ -0,0 +1,217 @@
+//! 연결 품질 모니터링
+//!
+//! 전송 중 피어 연결마다 quinn 통계(RTT, 손실, 혼잡 윈도우)를 주기적으로 모아 `connection-quality`
+//! 이벤트로 보냅니다. 손실률은 누적값이 아니라 직전 샘플 이후 구간 기준이라, Wi-Fi 품질이 떨어지는
+//! 순간을 UI에서 바로 경고할 수 있습니다.
+
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+
+/// 품질 이벤트 간격
+pub const QUALITY_INTERVAL: Duration = Duration::from_secs(3);
+
+/// 이 값을 넘는 구간 손실률은 경고 (2%)
+const HIGH_LOSS_RATE: f64 = 0.02;
+
+/// 구간 손실률 계산에 필요한 최소 전송 패킷 수 (유휴 연결의 우연한 손실 무시)
+const MIN_SAMPLE_PACKETS: u64 = 100;
+
+/// 최저 RTT 대비 이 배수를 넘으면 지연 급증으로 경고
+const RTT_SPIKE_FACTOR: u32 = 3;
+
+/// 지연 급증으로 보는 최소 증가폭 (LAN의 1ms → 3ms 같은 변화 무시)
+const RTT_SPIKE_MIN_INCREASE: Duration = Duration::from_millis(50);
+
+/// 연결 경로 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PathType {
+    Loopback,
+    /// 사설/링크 로컬 주소 (같은 네트워크)
+    Lan,
+    /// 공인 주소로 직접 연결
+    Wan,
+    /// 릴레이 노드를 거친 연결
+    Relay,
+}
+
+impl PathType {
+    /// 상대 주소로 경로 종류 판별 (`relays`는 알려진 릴레이 주소)
+    pub fn classify(remote: SocketAddr, relays: &HashSet<SocketAddr>) -> Self {
+        if relays.contains(&remote) {
+            return PathType::Relay;
+        }
+        match remote.ip() {
+            ip if ip.is_loopback() => PathType::Loopback,
+            IpAddr::V4(ip) if ip.is_private() || ip.is_link_local() => PathType::Lan,
+            // fc00::/7 (ULA), fe80::/10 (링크 로컬)
+            IpAddr::V6(ip) if (ip.segments()[0] & 0xfe00) == 0xfc00 => PathType::Lan,
+            IpAddr::V6(ip) if (ip.segments()[0] & 0xffc0) == 0xfe80 => PathType::Lan,
+            _ => PathType::Wan,
+        }
+    }
+}
+
+/// 품질 경고 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum QualityIssue {
+    /// 구간 손실률이 높음
+    HighLoss,
+    /// RTT가 이 연결의 최저치보다 크게 늘어남 (버퍼블로트/무선 간섭)
+    RttSpike,
+}
+
+/// `connection-quality` 이벤트 페이로드
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectionQuality {
+    pub peer_id: String,
+    pub remote_addr: String,
+    pub path_type: PathType,
+    pub rtt_ms: f64,
+    /// 이 연결에서 관측한 최저 RTT
+    pub min_rtt_ms: f64,
+    /// 직전 샘플 이후 손실률 (0.0 ~ 1.0)
+    pub loss_rate: f64,
+    /// 현재 혼잡 윈도우 (바이트)
+    pub cwnd: u64,
+    pub congestion_events: u64,
+    pub mtu: u16,
+    pub issues: Vec<QualityIssue>,
+}
+
+/// 피어별 직전 샘플
+#[derive(Debug, Clone, Copy)]
+struct PeerBaseline {
+    sent_packets: u64,
+    lost_packets: u64,
+    min_rtt: Duration,
+}
+
+/// 구간 손실률 계산을 위해 피어별 직전 통계를 보관
+#[derive(Debug, Default)]
+pub struct QualityMonitor {
+    baselines: HashMap<String, PeerBaseline>,
+}
+
+impl QualityMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 연결 하나의 현재 품질
+    pub fn sample(
+        &mut self,
+        peer_id: &str,
+        conn: &quinn::Connection,
+        relays: &HashSet<SocketAddr>,
+    ) -> ConnectionQuality {
+        let stats = conn.stats();
+        let remote = conn.remote_address();
+        let (loss_rate, min_rtt) = self.update(
+            peer_id,
+            stats.path.sent_packets,
+            stats.path.lost_packets,
+            stats.path.rtt,
+        );
+
+        ConnectionQuality {
+            peer_id: peer_id.to_string(),
+            remote_addr: remote.to_string(),
+            path_type: PathType::classify(remote, relays),
+            rtt_ms: stats.path.rtt.as_secs_f64() * 1000.0,
+            min_rtt_ms: min_rtt.as_secs_f64() * 1000.0,
+            loss_rate,
+            cwnd: stats.path.cwnd,
+            congestion_events: stats.path.congestion_events,
+            mtu: stats.path.current_mtu,
+            issues: issues(loss_rate, stats.path.rtt, min_rtt),
+        }
+    }
+
+    /// 누적 카운터로 구간 손실률과 최저 RTT 갱신
+    fn update(&mut self, peer_id: &str, sent: u64, lost: u64, rtt: Duration) -> (f64, Duration) {
+        let previous = self.baselines.get(peer_id).copied();
+        let min_rtt = previous.map_or(rtt, |p| p.min_rtt.min(rtt));
+        let (sent_delta, lost_delta) = match previous {
+            // 카운터가 줄었으면 같은 ID의 새 연결
+            Some(p) if sent >= p.sent_packets => {
+                (sent - p.sent_packets, lost.saturating_sub(p.lost_packets))
+            }
+            _ => (sent, lost),
+        };
+        self.baselines.insert(
+            peer_id.to_string(),
+            PeerBaseline {
+                sent_packets: sent,
+                lost_packets: lost,
+                min_rtt,
+            },
+        );
+
+        let loss_rate = if sent_delta >= MIN_SAMPLE_PACKETS {
+            lost_delta as f64 / sent_delta as f64
+        } else {
+            0.0
+        };
+        (loss_rate, min_rtt)
+    }
+
+    /// 끊긴 피어의 기록 정리
+    pub fn retain(&mut self, connected: &HashSet<String>) {
+        self.baselines
+            .retain(|peer_id, _| connected.contains(peer_id));
+    }
+}
+
+fn issues(loss_rate: f64, rtt: Duration, min_rtt: Duration) -> Vec<QualityIssue> {
+    let mut issues = Vec::new();
+    if loss_rate > HIGH_LOSS_RATE {
+        issues.push(QualityIssue::HighLoss);
+    }
+    if rtt > min_rtt * RTT_SPIKE_FACTOR && rtt > min_rtt + RTT_SPIKE_MIN_INCREASE {
+        issues.push(QualityIssue::RttSpike);
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interval_loss_and_issues() {
+        let mut monitor = QualityMonitor::new();
+        let ms = Duration::from_millis;
+
+        // 첫 샘플은 누적값 기준
+        let (loss, min_rtt) = monitor.update("a", 1000, 10, ms(5));
+        assert!((loss - 0.01).abs() < 1e-9);
+        assert_eq!(min_rtt, ms(5));
+
+        // 구간: 1000개 중 50개 손실 = 5%, RTT 급증
+        let (loss, min_rtt) = monitor.update("a", 2000, 60, ms(80));
+        assert!((loss - 0.05).abs() < 1e-9);
+        assert_eq!(min_rtt, ms(5));
+        assert_eq!(
+            issues(loss, ms(80), min_rtt),
+            vec![QualityIssue::HighLoss, QualityIssue::RttSpike]
+        );
+
+        // 전송이 거의 없는 구간은 손실률 0
+        let (loss, _) = monitor.update("a", 2010, 65, ms(5));
+        assert_eq!(loss, 0.0);
+        assert!(issues(loss, ms(5), ms(5)).is_empty());
+
+        let relays: HashSet<SocketAddr> = ["203.0.113.9:6882".parse().unwrap()].into();
+        let classify = |addr: &str| PathType::classify(addr.parse().unwrap(), &relays);
+        assert_eq!(classify("192.168.0.2:5000"), PathType::Lan);
+        assert_eq!(classify("[fe80::1]:5000"), PathType::Lan);
+        assert_eq!(classify("127.0.0.1:5000"), PathType::Loopback);
+        assert_eq!(classify("198.51.100.1:5000"), PathType::Wan);
+        assert_eq!(classify("203.0.113.9:6882"), PathType::Relay);
+    }
+}
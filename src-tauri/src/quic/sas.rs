@@ -0,0 +1,331 @@
+//! 짧은 인증 문자열 (SAS, 새 연락처 확인)
+//!
+//! 두 기기는 같은 QUIC 연결의 TLS 키 교환에서 나온 비밀(`export_keying_material`)을 공유하므로,
+//! 여기서 만든 단어 5개가 양쪽에서 같으면 중간에 끼어든 기기가 없습니다 (끼어들면 연결이 둘로 나뉘어 비밀이 다름).
+//! 사용자가 단어를 소리 내어 읽어 비교한 뒤 상대를 신뢰하는 연락처로 표시합니다.
+//!
+//! 인증서 지문은 TLS 핸드셰이크에서 양쪽이 주고받아 서명으로 검증한 값만 씁니다 (`identity::peer_fingerprint`).
+//! 양쪽은 연결 직후 같은 비밀에 노드 키로 서명한 `IdentityProof`를 보내 노드 ID를 알리며,
+//! 증명에 담긴 지문은 TLS 지문과 대조만 하고 그대로 받아들이지 않습니다.
+
+use crate::bootstrap::mailbox::{self, SigningIdentity};
+use crate::protocol::Command;
+use anyhow::{anyhow, Result};
+use parking_lot::Mutex;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+/// 단어 수 (8비트씩, 40비트)
+pub const SAS_WORD_COUNT: usize = 5;
+
+const EXPORTER_LABEL: &[u8] = b"EXPORTER-ponswarp-sas-v1";
+const SAS_DOMAIN: &[u8] = b"ponswarp sas v1";
+const PROOF_DOMAIN: &[u8] = b"ponswarp identity proof v1";
+
+/// 바이트 하나당 단어 하나 (읽기 쉬운 짧은 영어 단어)
+const WORDS: [&str; 256] = [
+    "acid", "acorn", "actor", "adobe", "agent", "alarm", "album", "alert", "alpha", "angle",
+    "apple", "arena", "armor", "arrow", "atlas", "audio", "award", "bacon", "badge", "bamboo",
+    "banjo", "barn", "basil", "batch", "beach", "beard", "berry", "bison", "blade", "blank",
+    "blaze", "bloom", "board", "bonus", "boots", "brain", "brass", "bread", "brick", "bride",
+    "brush", "bucket", "buddy", "cabin", "cable", "cactus", "camel", "candy", "canoe", "canyon",
+    "cargo", "carpet", "cedar", "chalk", "charm", "cheese", "cherry", "chess", "chief", "circus",
+    "claim", "cliff", "cloud", "clover", "coach", "cobra", "cocoa", "comet", "coral", "cotton",
+    "couch", "crane", "crate", "crown", "cruise", "cycle", "daisy", "dance", "delta", "diary",
+    "dice", "dock", "donut", "dragon", "drift", "drum", "eagle", "echo", "elbow", "elm", "ember",
+    "engine", "falcon", "fern", "ferry", "field", "flag", "flame", "flute", "foam", "forest",
+    "fossil", "frost", "fruit", "gadget", "galaxy", "garlic", "gecko", "ghost", "giant", "ginger",
+    "glove", "goat", "grape", "guitar", "hammer", "harbor", "hazel", "helmet", "hockey", "honey",
+    "hotel", "igloo", "iris", "island", "ivory", "jacket", "jaguar", "jelly", "jewel", "juice",
+    "jungle", "kayak", "kettle", "kitten", "koala", "ladder", "lamp", "laser", "lemon", "lion",
+    "lotus", "lunar", "magnet", "mango", "maple", "marble", "meadow", "melon", "meteor", "mint",
+    "mirror", "monkey", "motor", "muffin", "music", "napkin", "needle", "nickel", "noodle",
+    "novel", "oasis", "ocean", "olive", "onion", "opera", "orbit", "otter", "oyster", "panda",
+    "paper", "parrot", "pasta", "peach", "pearl", "pepper", "piano", "pilot", "pixel", "planet",
+    "plaza", "poem", "polar", "pony", "potato", "pulse", "puzzle", "quartz", "quest", "quilt",
+    "rabbit", "radar", "radio", "raven", "reef", "ribbon", "rider", "river", "robin", "rocket",
+    "ruby", "saddle", "salad", "salmon", "sandal", "satin", "scarf", "scout", "shadow", "shark",
+    "silver", "skate", "sketch", "sloth", "smoke", "snake", "sofa", "solar", "sonic", "spice",
+    "spider", "squid", "statue", "stone", "storm", "sugar", "summit", "sunset", "swan", "syrup",
+    "tango", "tiger", "toast", "tomato", "topaz", "torch", "tulip", "tundra", "turtle", "urban",
+    "valley", "vapor", "velvet", "violin", "vortex", "wagon", "walnut", "walrus", "whale",
+    "willow", "window", "wizard", "yacht", "yogurt", "zebra", "zigzag",
+];
+
+/// 연결 하나의 확인 정보 (프론트엔드 표시용)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SasVerification {
+    pub peer_id: String,
+    /// 양쪽 기기에서 같아야 하는 단어
+    pub words: Vec<String>,
+    /// 상대 노드 ID (서명을 확인한 `IdentityProof`를 받은 뒤)
+    pub node_id: Option<String>,
+    /// 상대 인증서 지문 (TLS 핸드셰이크 서명으로 확인한 값, 인증서를 제시하지 않은 상대는 None)
+    pub fingerprint: Option<String>,
+}
+
+/// 두 기기가 공유하는 연결 비밀
+fn connection_secret(conn: &quinn::Connection) -> Result<[u8; 32]> {
+    let mut secret = [0u8; 32];
+    conn.export_keying_material(&mut secret, EXPORTER_LABEL, b"")
+        .map_err(|_| anyhow!("연결 비밀을 만들 수 없습니다"))?;
+    Ok(secret)
+}
+
+/// 연결 비밀에서 단어 생성
+pub fn words_for(secret: &[u8]) -> Vec<String> {
+    let digest = Sha256::new()
+        .chain_update(SAS_DOMAIN)
+        .chain_update(secret)
+        .finalize();
+    digest[..SAS_WORD_COUNT]
+        .iter()
+        .map(|&byte| WORDS[byte as usize].to_string())
+        .collect()
+}
+
+/// 서명 대상 (서명한 쪽을 포함해 상대의 증명을 그대로 돌려보내는 것을 막음)
+fn proof_bytes(
+    secret: &[u8],
+    side: quinn::Side,
+    node_id: &str,
+    fingerprint: Option<&str>,
+) -> Vec<u8> {
+    let mut bytes = PROOF_DOMAIN.to_vec();
+    bytes.extend_from_slice(secret);
+    bytes.push(side.is_server() as u8);
+    bytes.extend_from_slice(node_id.as_bytes());
+    bytes.push(b'\n');
+    bytes.extend_from_slice(fingerprint.unwrap_or_default().as_bytes());
+    bytes
+}
+
+/// 이 기기의 노드 ID와 인증서 지문을 연결 비밀에 묶어 서명
+pub fn identity_proof(
+    conn: &quinn::Connection,
+    identity: &SigningIdentity,
+    fingerprint: Option<String>,
+) -> Result<Command> {
+    let secret = connection_secret(conn)?;
+    let node_id = identity.node_id();
+    let signature = identity.sign(&proof_bytes(
+        &secret,
+        conn.side(),
+        &node_id,
+        fingerprint.as_deref(),
+    ));
+    Ok(Command::IdentityProof {
+        node_id,
+        fingerprint,
+        signature,
+    })
+}
+
+/// 연결별 SAS 확인 상태 (피어 ID → 연결 ID, 확인 정보)
+#[derive(Default)]
+pub struct SasSessions {
+    sessions: Mutex<HashMap<String, (usize, SasVerification)>>,
+}
+
+impl SasSessions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 연결의 단어 계산 (같은 연결의 기록이 있으면 그대로 반환)
+    pub fn begin(&self, peer_id: &str, conn: &quinn::Connection) -> Result<SasVerification> {
+        if let Some((id, existing)) = self.sessions.lock().get(peer_id) {
+            if *id == conn.stable_id() {
+                return Ok(existing.clone());
+            }
+        }
+        let verification = SasVerification {
+            peer_id: peer_id.to_string(),
+            words: words_for(&connection_secret(conn)?),
+            node_id: None,
+            fingerprint: super::identity::peer_fingerprint(conn),
+        };
+        self.sessions.lock().insert(
+            peer_id.to_string(),
+            (conn.stable_id(), verification.clone()),
+        );
+        Ok(verification)
+    }
+
+    /// 상대의 `IdentityProof` 확인 후 기록
+    ///
+    /// TLS로 확인한 인증서 지문이 있으면 상대가 알린 지문과 같아야 합니다.
+    /// TLS 지문이 없으면 알린 지문은 증명되지 않으므로 기록하지 않습니다.
+    pub fn accept_proof(
+        &self,
+        peer_id: &str,
+        conn: &quinn::Connection,
+        own_node_id: &str,
+        node_id: String,
+        fingerprint: Option<String>,
+        signature: &str,
+    ) -> Result<SasVerification> {
+        if node_id == own_node_id {
+            return Err(anyhow!("이 기기의 노드 ID로 된 증명입니다"));
+        }
+        let secret = connection_secret(conn)?;
+        let peer_side = if conn.side().is_server() {
+            quinn::Side::Client
+        } else {
+            quinn::Side::Server
+        };
+        mailbox::verify(
+            &node_id,
+            &proof_bytes(&secret, peer_side, &node_id, fingerprint.as_deref()),
+            signature,
+        )?;
+
+        let fingerprint = fingerprint.map(|fp| super::identity::normalize_fingerprint(&fp));
+        let mut verification = self.begin(peer_id, conn)?;
+        if let (Some(tls), Some(claimed)) = (&verification.fingerprint, &fingerprint) {
+            if tls != claimed {
+                return Err(anyhow!("알린 인증서 지문이 연결의 인증서와 다릅니다"));
+            }
+        }
+        verification.node_id = Some(node_id);
+        self.sessions.lock().insert(
+            peer_id.to_string(),
+            (conn.stable_id(), verification.clone()),
+        );
+        Ok(verification)
+    }
+
+    pub fn get(&self, peer_id: &str) -> Option<SasVerification> {
+        self.sessions
+            .lock()
+            .get(peer_id)
+            .map(|(_, verification)| verification.clone())
+    }
+
+    /// 연결 종료 시 정리 (같은 피어 ID로 새로 맺은 연결의 기록은 남김)
+    pub fn end(&self, peer_id: &str, connection_id: usize) {
+        let mut sessions = self.sessions.lock();
+        if sessions
+            .get(peer_id)
+            .is_some_and(|(id, _)| *id == connection_id)
+        {
+            sessions.remove(peer_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_words_and_proof_binding() {
+        let unique: std::collections::HashSet<_> = WORDS.iter().collect();
+        assert_eq!(unique.len(), WORDS.len());
+
+        let words = words_for(&[7u8; 32]);
+        assert_eq!(words.len(), SAS_WORD_COUNT);
+        assert_eq!(words, words_for(&[7u8; 32]));
+        assert_ne!(words, words_for(&[8u8; 32]));
+
+        // 증명은 연결 비밀과 서명한 쪽에 묶임
+        let identity = SigningIdentity::generate();
+        let node_id = identity.node_id();
+        let signed = proof_bytes(&[7u8; 32], quinn::Side::Client, &node_id, None);
+        let signature = identity.sign(&signed);
+        assert!(mailbox::verify(&node_id, &signed, &signature).is_ok());
+        for other in [
+            proof_bytes(&[8u8; 32], quinn::Side::Client, &node_id, None),
+            proof_bytes(&[7u8; 32], quinn::Side::Server, &node_id, None),
+            proof_bytes(&[7u8; 32], quinn::Side::Client, &node_id, Some("ab")),
+        ] {
+            assert!(mailbox::verify(&node_id, &other, &signature).is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fingerprint_comes_from_tls_on_both_sides() {
+        use crate::quic::client::QuicClient;
+        use crate::quic::identity::QuicIdentity;
+        use crate::quic::QuicServer;
+
+        let server_cert = QuicIdentity::generate().unwrap();
+        let client_cert = QuicIdentity::generate().unwrap();
+        let mut server =
+            QuicServer::new("127.0.0.1:0".parse().unwrap()).with_identity(server_cert.clone());
+        let mut accepted = server.take_connection_receiver().unwrap();
+        server.start().await.unwrap();
+        let addr = server.local_addr().unwrap();
+
+        let outbound = QuicClient::new()
+            .with_identity(client_cert.clone())
+            .connect(addr, "localhost")
+            .await
+            .unwrap();
+        let inbound = accepted.recv().await.unwrap().connection;
+        let client_sessions = SasSessions::new();
+        let server_sessions = SasSessions::new();
+        assert_eq!(
+            client_sessions
+                .begin("server", &outbound)
+                .unwrap()
+                .fingerprint,
+            Some(server_cert.fingerprint())
+        );
+        assert_eq!(
+            server_sessions
+                .begin("client", &inbound)
+                .unwrap()
+                .fingerprint,
+            Some(client_cert.fingerprint())
+        );
+
+        // 수락한 쪽도 TLS 지문과 다른 지문을 알리는 증명은 거부
+        let server_key = SigningIdentity::generate();
+        let client_key = SigningIdentity::generate();
+        let Command::IdentityProof {
+            node_id, signature, ..
+        } = identity_proof(&outbound, &client_key, Some("ab".repeat(32))).unwrap()
+        else {
+            unreachable!()
+        };
+        assert!(server_sessions
+            .accept_proof(
+                "client",
+                &inbound,
+                &server_key.node_id(),
+                node_id,
+                Some("ab".repeat(32)),
+                &signature,
+            )
+            .is_err());
+
+        // 인증서 없이 연결한 상대가 알린 지문은 기록하지 않음
+        let anonymous = QuicClient::new().connect(addr, "localhost").await.unwrap();
+        let inbound = accepted.recv().await.unwrap().connection;
+        let Command::IdentityProof {
+            node_id,
+            fingerprint,
+            signature,
+        } = identity_proof(&anonymous, &client_key, Some(client_cert.fingerprint())).unwrap()
+        else {
+            unreachable!()
+        };
+        let verification = server_sessions
+            .accept_proof(
+                "anonymous",
+                &inbound,
+                &server_key.node_id(),
+                node_id,
+                fingerprint,
+                &signature,
+            )
+            .unwrap();
+        assert_eq!(verification.node_id, Some(client_key.node_id()));
+        assert_eq!(verification.fingerprint, None);
+
+        server.shutdown().await;
+    }
+}
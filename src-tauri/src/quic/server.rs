@@ -1,11 +1,18 @@
 use anyhow::Result;
-use quinn::{Endpoint, ServerConfig};
+use quinn::{Endpoint, EndpointConfig, ServerConfig};
+use socket2::{Domain, Protocol, Socket, Type};
 use std::net::SocketAddr;
 use std::sync::Arc;
 use tokio::sync::mpsc;
 use tracing::{info, warn};
 
+#[cfg(feature = "grid-experimental")]
+use super::grid_route::SharedGridEndpoint;
+use super::grid_route::{GridRouter, GRID_ALPN, TRANSFER_ALPN};
+use super::identity::{signature_algorithms, QuicIdentity};
+use super::pacing::QuicPacingConfig;
 use crate::protocol::Command;
+use crate::turn::stun::{self, StunClient};
 
 /// 서버에서 수락한 연결 정보
 #[derive(Debug, Clone)]
@@ -16,10 +23,24 @@ pub struct AcceptedConnection {
 
 pub struct QuicServer {
     endpoint: Option<Endpoint>,
+    /// SO_REUSEPORT로 같은 포트를 공유하는 추가 엔드포인트 (샤딩 시)
+    shard_endpoints: Vec<Endpoint>,
+    /// 엔드포인트(소켓) 수, 1 = 샤딩 없음
+    shards: usize,
     bind_addr: SocketAddr,
     /// 수락된 연결을 외부로 전달하는 채널
     connection_tx: Option<mpsc::Sender<AcceptedConnection>>,
     connection_rx: Option<mpsc::Receiver<AcceptedConnection>>,
+    pacing: QuicPacingConfig,
+    /// 서버 인증서 (None이면 시작할 때마다 새로 생성)
+    identity: Option<QuicIdentity>,
+    /// 🆕 같은 포트로 들어온 Grid 연결을 Swarm별로 전달
+    grid_router: GridRouter,
+    /// 🆕 공인 매핑 주소를 조회하고 유지할 STUN 서버
+    stun_server: Option<SocketAddr>,
+    /// 🆕 서버 소켓의 STUN 매핑 주소 (srflx 후보)
+    reflexive_addr: Option<SocketAddr>,
+    stun_keepalive: Option<tauri::async_runtime::JoinHandle<()>>,
 }
 
 impl QuicServer {
@@ -27,12 +48,49 @@ impl QuicServer {
         let (tx, rx) = mpsc::channel(16);
         Self {
             endpoint: None,
+            shard_endpoints: Vec::new(),
+            shards: 1,
             bind_addr,
             connection_tx: Some(tx),
             connection_rx: Some(rx),
+            pacing: QuicPacingConfig::default(),
+            identity: None,
+            grid_router: GridRouter::new(),
+            stun_server: None,
+            reflexive_addr: None,
+            stun_keepalive: None,
         }
     }
 
+    /// 혼잡 제어/페이싱 설정 (start 전에 지정)
+    pub fn with_pacing(mut self, pacing: QuicPacingConfig) -> Self {
+        self.pacing = pacing;
+        self
+    }
+
+    /// 저장된 인증서 사용 (재시작 후에도 같은 지문 유지)
+    pub fn with_identity(mut self, identity: QuicIdentity) -> Self {
+        self.identity = Some(identity);
+        self
+    }
+
+    /// 같은 포트에 SO_REUSEPORT 엔드포인트를 여러 개 열어 코어별로 수신 분산
+    ///
+    /// 커널이 클라이언트 주소 해시로 소켓을 고르므로 연결은 한 엔드포인트에 고정됩니다.
+    /// (연결 마이그레이션/NAT 재바인딩은 다른 엔드포인트로 가므로 LAN 전용)
+    /// SO_REUSEPORT가 없는 플랫폼에서는 무시됩니다.
+    pub fn with_shards(mut self, shards: usize) -> Self {
+        self.shards = if cfg!(unix) { shards.clamp(1, 64) } else { 1 };
+        self
+    }
+
+    /// 🆕 시작할 때 서버 소켓으로 STUN Binding을 보내 공인 매핑 주소를 알아내고,
+    /// 이후 주기적으로 Binding을 보내 피어가 접속할 때까지 NAT 매핑을 유지
+    pub fn with_stun(mut self, stun_server: SocketAddr) -> Self {
+        self.stun_server = Some(stun_server);
+        self
+    }
+
     /// 수락된 연결을 받는 채널 (Sender가 파일 전송에 사용)
     pub fn take_connection_receiver(&mut self) -> Option<mpsc::Receiver<AcceptedConnection>> {
         self.connection_rx.take()
@@ -40,29 +98,136 @@ impl QuicServer {
 
     pub async fn start(&mut self) -> Result<()> {
         let server_config = self.configure_server()?;
-        let endpoint = Endpoint::server(server_config, self.bind_addr)?;
+
+        let socket = if self.shards > 1 {
+            Self::bind_reuse_port(self.bind_addr)?
+        } else {
+            std::net::UdpSocket::bind(self.bind_addr)?
+        };
+        if let Some(stun_server) = self.stun_server {
+            self.start_stun(&socket, stun_server).await;
+        }
+
+        let endpoint = if self.shards > 1 {
+            self.start_sharded(socket, server_config)?
+        } else {
+            Endpoint::new(
+                EndpointConfig::default(),
+                Some(server_config),
+                socket,
+                Arc::new(quinn::TokioRuntime),
+            )?
+        };
 
         info!("🚀 QUIC 서버 시작: {}", self.bind_addr);
 
         self.endpoint = Some(endpoint.clone());
 
         let conn_tx = self.connection_tx.clone();
+        let grid_router = self.grid_router.clone();
         tauri::async_runtime::spawn(async move {
-            Self::accept_connections(endpoint, conn_tx).await;
+            Self::accept_connections(endpoint, conn_tx, grid_router).await;
         });
 
         Ok(())
     }
 
+    /// 첫 엔드포인트를 반환하고 나머지 샤드는 각자 수락 루프 시작
+    fn start_sharded(
+        &mut self,
+        first: std::net::UdpSocket,
+        server_config: ServerConfig,
+    ) -> Result<Endpoint> {
+        let addr = first.local_addr()?;
+        let mut sockets = vec![first];
+        for _ in 1..self.shards {
+            sockets.push(Self::bind_reuse_port(addr)?);
+        }
+
+        let mut endpoints = sockets
+            .into_iter()
+            .map(|socket| {
+                Endpoint::new(
+                    EndpointConfig::default(),
+                    Some(server_config.clone()),
+                    socket,
+                    Arc::new(quinn::TokioRuntime),
+                )
+            })
+            .collect::<std::io::Result<Vec<_>>>()?;
+
+        let primary = endpoints.remove(0);
+        for endpoint in &endpoints {
+            let endpoint = endpoint.clone();
+            let conn_tx = self.connection_tx.clone();
+            let grid_router = self.grid_router.clone();
+            tauri::async_runtime::spawn(async move {
+                Self::accept_connections(endpoint, conn_tx, grid_router).await;
+            });
+        }
+        self.shard_endpoints = endpoints;
+
+        info!("🧩 QUIC 엔드포인트 샤딩: {}에 {} 소켓", addr, self.shards);
+        Ok(primary)
+    }
+
+    /// 🆕 엔드포인트에 넘기기 전에 소켓 복제본으로 매핑 주소 조회 후 keepalive 시작
+    ///
+    /// 엔드포인트가 시작되면 STUN 응답은 QUIC 수신 루프가 버리므로 조회는 여기서 한 번만 합니다.
+    /// 실패해도 LAN 연결에는 지장이 없어 경고만 남깁니다.
+    async fn start_stun(&mut self, socket: &std::net::UdpSocket, stun_server: SocketAddr) {
+        let probe = socket.try_clone().and_then(|probe| {
+            probe.set_nonblocking(true)?;
+            tokio::net::UdpSocket::from_std(probe)
+        });
+        let mapped = match probe {
+            Ok(probe) => StunClient::new(stun_server).binding(&probe).await,
+            Err(e) => Err(format!("소켓 복제 실패: {}", e)),
+        };
+        let mapped = match mapped {
+            Ok(mapped) => mapped,
+            Err(e) => {
+                warn!("QUIC 서버 STUN 매핑 조회 실패 ({}): {}", stun_server, e);
+                return;
+            }
+        };
+        match socket.try_clone() {
+            Ok(keepalive) => {
+                info!(
+                    "🌐 QUIC 서버 공인 매핑 주소: {} (STUN {})",
+                    mapped, stun_server
+                );
+                self.reflexive_addr = Some(mapped);
+                self.stun_keepalive = Some(stun::spawn_keepalive(keepalive, stun_server));
+            }
+            // keepalive 없이는 매핑이 곧 만료되므로 후보로 알리지 않음
+            Err(e) => warn!("STUN keepalive 소켓 복제 실패: {}", e),
+        }
+    }
+
+    fn bind_reuse_port(addr: SocketAddr) -> Result<std::net::UdpSocket> {
+        let socket = Socket::new(Domain::for_address(addr), Type::DGRAM, Some(Protocol::UDP))?;
+        #[cfg(unix)]
+        socket.set_reuse_port(true)?;
+        socket.bind(&addr.into())?;
+        Ok(socket.into())
+    }
+
     async fn accept_connections(
         endpoint: Endpoint,
         conn_tx: Option<mpsc::Sender<AcceptedConnection>>,
+        grid_router: GridRouter,
     ) {
         while let Some(incoming) = endpoint.accept().await {
             let conn_tx = conn_tx.clone();
+            let grid_router = grid_router.clone();
             tauri::async_runtime::spawn(async move {
                 match incoming.await {
                     Ok(conn) => {
+                        // 🆕 Grid ALPN 연결은 해당 Swarm으로 전달
+                        let Some(conn) = grid_router.route(conn).await else {
+                            return;
+                        };
                         let peer_addr = conn.remote_address();
                         info!("✅ 새 QUIC 연결 수락: {}", peer_addr);
 
@@ -142,19 +307,20 @@ impl QuicServer {
     }
 
     fn configure_server(&self) -> Result<ServerConfig> {
-        let cert =
-            rcgen::generate_simple_self_signed(vec!["localhost".into(), "ponswarp.local".into()])?;
-        let cert_der = cert.cert.der().to_vec();
-        let priv_key = cert.key_pair.serialize_der();
+        let identity = match &self.identity {
+            Some(identity) => identity.clone(),
+            None => QuicIdentity::generate()?,
+        };
 
-        let cert_chain = vec![rustls::pki_types::CertificateDer::from(cert_der)];
-        let priv_key = rustls::pki_types::PrivatePkcs8KeyDer::from(priv_key).into();
+        let cert_chain = vec![rustls::pki_types::CertificateDer::from(identity.cert_der)];
+        let priv_key = rustls::pki_types::PrivatePkcs8KeyDer::from(identity.key_der).into();
 
+        // 🆕 클라이언트 인증서를 요청해 수락한 연결에서도 상대 지문을 얻음 (제시하지 않아도 연결은 허용)
         let mut server_crypto = rustls::ServerConfig::builder()
-            .with_no_client_auth()
+            .with_client_cert_verifier(Arc::new(AcceptAnyClientCert))
             .with_single_cert(cert_chain, priv_key)?;
 
-        server_crypto.alpn_protocols = vec![b"ponswarp".to_vec()];
+        server_crypto.alpn_protocols = vec![TRANSFER_ALPN.to_vec(), GRID_ALPN.to_vec()];
 
         let mut server_config = ServerConfig::with_crypto(Arc::new(
             quinn::crypto::rustls::QuicServerConfig::try_from(server_crypto)?,
@@ -182,6 +348,9 @@ impl QuicServer {
         // 데이터그램 버퍼 크기
         transport_config.datagram_receive_buffer_size(Some(32 * 1024 * 1024)); // 32MB
 
+        // 혼잡 제어기가 페이서의 전송 간격을 결정
+        self.pacing.apply(transport_config);
+
         Ok(server_config)
     }
 
@@ -192,10 +361,78 @@ impl QuicServer {
             .flatten()
     }
 
+    /// 🆕 STUN으로 알아낸 서버 소켓의 공인 주소 (`with_stun`을 쓰지 않았거나 실패하면 None)
+    pub fn reflexive_addr(&self) -> Option<SocketAddr> {
+        self.reflexive_addr
+    }
+
+    /// 🆕 Grid Swarm이 함께 쓸 엔드포인트 (시작 전이면 None)
+    #[cfg(feature = "grid-experimental")]
+    pub fn shared_grid_endpoint(&self) -> Option<SharedGridEndpoint> {
+        Some(SharedGridEndpoint {
+            endpoint: self.endpoint.clone()?,
+            router: self.grid_router.clone(),
+        })
+    }
+
     pub async fn shutdown(&mut self) {
+        if let Some(keepalive) = self.stun_keepalive.take() {
+            keepalive.abort();
+        }
+        self.reflexive_addr = None;
+        for endpoint in self.shard_endpoints.drain(..) {
+            endpoint.close(0u32.into(), b"shutdown");
+        }
         if let Some(endpoint) = self.endpoint.take() {
             endpoint.close(0u32.into(), b"shutdown");
             info!("QUIC 서버 종료");
         }
     }
 }
+
+/// 🆕 자체 서명 클라이언트 인증서 수락 (발급자 체인은 건너뛰고 핸드셰이크 서명만 인증서 공개 키로 검증)
+///
+/// 인증서를 제시하지 않는 클라이언트(인증서가 없는 기기, 릴레이 등)도 받아들이며, 그 연결은 지문이 없습니다.
+#[derive(Debug)]
+struct AcceptAnyClientCert;
+
+impl rustls::server::danger::ClientCertVerifier for AcceptAnyClientCert {
+    fn client_auth_mandatory(&self) -> bool {
+        false
+    }
+
+    fn root_hint_subjects(&self) -> &[rustls::DistinguishedName] {
+        &[]
+    }
+
+    fn verify_client_cert(
+        &self,
+        _end_entity: &rustls::pki_types::CertificateDer<'_>,
+        _intermediates: &[rustls::pki_types::CertificateDer<'_>],
+        _now: rustls::pki_types::UnixTime,
+    ) -> Result<rustls::server::danger::ClientCertVerified, rustls::Error> {
+        Ok(rustls::server::danger::ClientCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &rustls::pki_types::CertificateDer<'_>,
+        dss: &rustls::DigitallySignedStruct,
+    ) -> Result<rustls::client::danger::HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(message, cert, dss, &signature_algorithms())
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &rustls::pki_types::CertificateDer<'_>,
+        dss: &rustls::DigitallySignedStruct,
+    ) -> Result<rustls::client::danger::HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(message, cert, dss, &signature_algorithms())
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<rustls::SignatureScheme> {
+        signature_algorithms().supported_schemes()
+    }
+}
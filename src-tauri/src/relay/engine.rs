@@ -1,18 +1,107 @@
+use crate::metrics::SubsystemCounter;
 use anyhow::Result;
 use bytes::BytesMut;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::{mpsc, Mutex, RwLock};
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
 const DEFAULT_BUFFER_SIZE: usize = 64 * 1024;
 const DEFAULT_POOL_SIZE: usize = 1000;
 
+/// 🆕 버퍼 풀 통계 보고 간격
+const POOL_STATS_INTERVAL: Duration = Duration::from_secs(5);
+
+/// 🆕 할당량이 최대치의 이 비율(%) 이상이면 압박 상태로 보고
+const POOL_PRESSURE_PERCENT: usize = 80;
+
+/// 🆕 릴레이 버퍼 풀 설정 (다음 릴레이 엔진 시작부터 적용)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RelayPoolConfig {
+    /// 미리 만들어 두는 버퍼 수 (부족하면 이 수의 2배까지 확장)
+    pub pool_size: usize,
+    /// 버퍼 하나의 크기 (bytes)
+    pub buffer_size: usize,
+}
+
+impl Default for RelayPoolConfig {
+    fn default() -> Self {
+        Self {
+            pool_size: DEFAULT_POOL_SIZE,
+            buffer_size: DEFAULT_BUFFER_SIZE,
+        }
+    }
+}
+
+impl RelayPoolConfig {
+    const MIN_BUFFER_SIZE: usize = 4 * 1024;
+    const MAX_BUFFER_SIZE: usize = 4 * 1024 * 1024;
+    /// 확장분까지 포함한 최대 메모리
+    const MAX_POOL_BYTES: u64 = 4 * 1024 * 1024 * 1024;
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.pool_size == 0 {
+            return Err("pool_size must be > 0".to_string());
+        }
+        if !(Self::MIN_BUFFER_SIZE..=Self::MAX_BUFFER_SIZE).contains(&self.buffer_size) {
+            return Err(format!(
+                "buffer_size must be between {} and {}",
+                Self::MIN_BUFFER_SIZE,
+                Self::MAX_BUFFER_SIZE
+            ));
+        }
+        let max_bytes = (self.pool_size as u64)
+            .checked_mul(2)
+            .and_then(|n| n.checked_mul(self.buffer_size as u64));
+        if !matches!(max_bytes, Some(bytes) if bytes <= Self::MAX_POOL_BYTES) {
+            return Err(format!(
+                "pool_size x buffer_size x 2 must be <= {} bytes",
+                Self::MAX_POOL_BYTES
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// 🆕 버퍼 풀 상태 (`relay-pool-stats` 이벤트)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BufferPoolStats {
+    /// 풀에 남아 있는 버퍼 수
+    pub available: usize,
+    /// 사용 중인 버퍼 수
+    pub allocated: usize,
+    /// 직전 보고 이후 최대 사용 버퍼 수
+    pub peak_allocated: usize,
+    /// 동시에 쓸 수 있는 최대 버퍼 수
+    pub max_buffers: usize,
+    pub buffer_size: usize,
+    /// 직전 보고 이후 풀이 비어 새로 만든 버퍼 수
+    pub expansions: u64,
+    /// 직전 보고 이후 최대치에 걸려 버퍼를 못 받은 횟수
+    pub exhausted: u64,
+}
+
+impl BufferPoolStats {
+    /// 메모리를 늘려야 할 만큼 할당 압박이 있는지
+    pub fn under_pressure(&self) -> bool {
+        self.exhausted > 0 || self.peak_allocated * 100 >= self.max_buffers * POOL_PRESSURE_PERCENT
+    }
+}
+
 pub struct BufferPool {
     buffers: Mutex<Vec<BytesMut>>,
     buffer_size: usize,
     allocated: Arc<RwLock<usize>>,
     max_buffers: usize,
+    /// 🆕 보고 구간별 통계 (보고할 때마다 초기화)
+    peak_allocated: AtomicUsize,
+    expansions: AtomicU64,
+    exhausted: AtomicU64,
 }
 
 impl BufferPool {
@@ -33,9 +122,17 @@ impl BufferPool {
             buffer_size,
             allocated: Arc::new(RwLock::new(0)),
             max_buffers: pool_size * 2,
+            peak_allocated: AtomicUsize::new(0),
+            expansions: AtomicU64::new(0),
+            exhausted: AtomicU64::new(0),
         }
     }
 
+    /// 🆕 설정으로 생성
+    pub fn from_config(config: &RelayPoolConfig) -> Self {
+        Self::new(config.pool_size, config.buffer_size)
+    }
+
     pub async fn acquire(&self) -> Option<BytesMut> {
         let mut pool = self.buffers.lock().await;
 
@@ -43,6 +140,7 @@ impl BufferPool {
             buf.clear();
             let mut allocated = self.allocated.write().await;
             *allocated += 1;
+            self.peak_allocated.fetch_max(*allocated, Ordering::Relaxed);
             return Some(buf);
         }
 
@@ -50,10 +148,13 @@ impl BufferPool {
         if allocated < self.max_buffers {
             let mut alloc = self.allocated.write().await;
             *alloc += 1;
+            self.peak_allocated.fetch_max(*alloc, Ordering::Relaxed);
+            self.expansions.fetch_add(1, Ordering::Relaxed);
             debug!("버퍼 풀 확장: {}/{}", *alloc, self.max_buffers);
             return Some(BytesMut::with_capacity(self.buffer_size));
         }
 
+        self.exhausted.fetch_add(1, Ordering::Relaxed);
         None
     }
 
@@ -75,6 +176,23 @@ impl BufferPool {
         let allocated = *self.allocated.read().await;
         (pool.len(), allocated)
     }
+
+    /// 🆕 현재 상태와 직전 보고 이후 구간 통계 (구간 통계는 초기화)
+    pub async fn take_stats(&self) -> BufferPoolStats {
+        let (available, allocated) = self.stats().await;
+        BufferPoolStats {
+            available,
+            allocated,
+            peak_allocated: self
+                .peak_allocated
+                .swap(allocated, Ordering::Relaxed)
+                .max(allocated),
+            max_buffers: self.max_buffers,
+            buffer_size: self.buffer_size,
+            expansions: self.expansions.swap(0, Ordering::Relaxed),
+            exhausted: self.exhausted.swap(0, Ordering::Relaxed),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -116,6 +234,10 @@ pub struct RelayEngine {
         Arc<Mutex<mpsc::Receiver<RelayData>>>,
     ),
     running: Arc<RwLock<bool>>,
+    /// 앱 전체 처리량 지표 (릴레이한 바이트, 세션 수)
+    metrics: Option<Arc<SubsystemCounter>>,
+    /// 🆕 할당 압박이 있을 때 버퍼 풀 통계를 보낼 채널
+    pool_stats_tx: Option<mpsc::UnboundedSender<BufferPoolStats>>,
 }
 
 #[derive(Debug)]
@@ -134,9 +256,29 @@ impl RelayEngine {
             sessions: Arc::new(RwLock::new(HashMap::new())),
             data_channel: (tx, Arc::new(Mutex::new(rx))),
             running: Arc::new(RwLock::new(false)),
+            metrics: None,
+            pool_stats_tx: None,
         }
     }
 
+    /// 앱 전체 처리량 지표 카운터 설정
+    pub fn with_metrics(mut self, counter: Arc<SubsystemCounter>) -> Self {
+        self.metrics = Some(counter);
+        self
+    }
+
+    /// 🆕 버퍼 풀 크기 설정 (시작 전에 호출)
+    pub fn with_pool_config(mut self, config: RelayPoolConfig) -> Self {
+        self.buffer_pool = Arc::new(BufferPool::from_config(&config));
+        self
+    }
+
+    /// 🆕 할당 압박이 있을 때 주기적으로 버퍼 풀 통계를 보낼 채널 설정
+    pub fn with_pool_stats(mut self, tx: mpsc::UnboundedSender<BufferPoolStats>) -> Self {
+        self.pool_stats_tx = Some(tx);
+        self
+    }
+
     pub async fn start(&self) -> Result<()> {
         let mut running = self.running.write().await;
         if *running {
@@ -151,6 +293,7 @@ impl RelayEngine {
         let buffer_pool = self.buffer_pool.clone();
         let rx = self.data_channel.1.clone();
         let running = self.running.clone();
+        let metrics = self.metrics.clone();
 
         tauri::async_runtime::spawn(async move {
             let mut receiver = rx.lock().await;
@@ -167,6 +310,9 @@ impl RelayEngine {
                             }
 
                             session.add_relayed_bytes(data_len).await;
+                            if let Some(metrics) = &metrics {
+                                metrics.add_bytes(data_len);
+                            }
                         }
 
                         buffer_pool.release(data.data).await;
@@ -180,6 +326,35 @@ impl RelayEngine {
             info!("릴레이 엔진 워커 종료");
         });
 
+        if let Some(tx) = self.pool_stats_tx.clone() {
+            let buffer_pool = self.buffer_pool.clone();
+            let running = self.running.clone();
+            tauri::async_runtime::spawn(async move {
+                let mut ticker = tokio::time::interval(POOL_STATS_INTERVAL);
+                ticker.tick().await;
+
+                loop {
+                    ticker.tick().await;
+                    if !*running.read().await {
+                        break;
+                    }
+                    let stats = buffer_pool.take_stats().await;
+                    if !stats.under_pressure() {
+                        continue;
+                    }
+                    if stats.exhausted > 0 {
+                        warn!(
+                            "⚠️ 릴레이 버퍼 풀 고갈: {}회 할당 실패 (최대 {} 버퍼)",
+                            stats.exhausted, stats.max_buffers
+                        );
+                    }
+                    if tx.send(stats).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
         Ok(())
     }
 
@@ -198,7 +373,11 @@ impl RelayEngine {
         let session = Arc::new(RelaySession::new(job_id.clone(), source, targets));
 
         let mut sessions = self.sessions.write().await;
-        sessions.insert(job_id.clone(), session);
+        if sessions.insert(job_id.clone(), session).is_none() {
+            if let Some(metrics) = &self.metrics {
+                metrics.started();
+            }
+        }
 
         info!("📋 릴레이 세션 생성: {}", job_id);
         Ok(())
@@ -223,6 +402,9 @@ impl RelayEngine {
         let mut sessions = self.sessions.write().await;
 
         if let Some(session) = sessions.remove(job_id) {
+            if let Some(metrics) = &self.metrics {
+                metrics.finished();
+            }
             let bytes = *session.bytes_relayed.read().await;
             info!("📋 릴레이 세션 종료: {}, {} bytes 전송됨", job_id, bytes);
             return Some(bytes);
@@ -251,6 +433,11 @@ impl RelayEngine {
         self.buffer_pool.stats().await
     }
 
+    /// 🆕 버퍼 풀 최대 버퍼 수와 버퍼 크기
+    pub fn buffer_pool_capacity(&self) -> (usize, usize) {
+        (self.buffer_pool.max_buffers, self.buffer_pool.buffer_size)
+    }
+
     pub async fn acquire_buffer(&self) -> Option<BytesMut> {
         self.buffer_pool.acquire().await
     }
@@ -287,3 +474,52 @@ pub fn verify_no_disk_write() -> bool {
     info!("📊 Zero-Disk 검증: Linux 외 플랫폼은 항상 true");
     true
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pool_config_validation() {
+        let config: RelayPoolConfig = serde_json::from_str("{}").unwrap();
+        assert_eq!(config, RelayPoolConfig::default());
+        assert!(config.validate().is_ok());
+
+        let config: RelayPoolConfig =
+            serde_json::from_str(r#"{"poolSize":10,"bufferSize":1024}"#).unwrap();
+        assert!(config.validate().is_err());
+
+        let huge = RelayPoolConfig {
+            pool_size: usize::MAX / 2,
+            buffer_size: DEFAULT_BUFFER_SIZE,
+        };
+        assert!(huge.validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_pool_stats_report_pressure() {
+        let pool = BufferPool::new(2, 4096);
+        let mut held = Vec::new();
+        for _ in 0..4 {
+            held.push(pool.acquire().await.unwrap());
+        }
+        assert!(pool.acquire().await.is_none());
+
+        let stats = pool.take_stats().await;
+        assert_eq!(stats.allocated, 4);
+        assert_eq!(stats.expansions, 2);
+        assert_eq!(stats.exhausted, 1);
+        assert!(stats.under_pressure());
+
+        for buf in held {
+            pool.release(buf).await;
+        }
+        // 구간 통계는 보고 후 초기화되어, 한가해지면 압박 없음
+        let stats = pool.take_stats().await;
+        assert_eq!(stats.peak_allocated, 4);
+        let stats = pool.take_stats().await;
+        assert_eq!(stats.peak_allocated, 0);
+        assert_eq!(stats.exhausted, 0);
+        assert!(!stats.under_pressure());
+    }
+}
@@ -1,3 +1,5 @@
 pub mod engine;
+pub mod selector;
 
 pub use engine::RelayEngine;
+pub use selector::{RelayCandidate, RelaySelection, RelaySelector, RelaySource};
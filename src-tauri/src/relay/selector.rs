@@ -0,0 +1,452 @@
+//! Relay Selector - 지연 시간 기반 릴레이 자동 선택
+//!
+//! mDNS, DHT, 설정 파일로 알려진 릴레이/부트스트랩 노드들의 RTT와 여유 용량을 측정하여
+//! 피어 쌍에 가장 적합한 릴레이를 고릅니다. 전송 중 처리량이 급락하면 재평가합니다.
+
+use quinn::{ClientConfig, Endpoint};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+/// RTT 프로브 타임아웃
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// 측정 결과 유효 시간 (이후 재측정)
+const PROBE_TTL: Duration = Duration::from_secs(60);
+
+/// 처리량 급락 판정 비율 (최고 처리량 대비)
+const COLLAPSE_RATIO: f64 = 0.3;
+
+/// 급락으로 판정하기 위한 연속 샘플 수
+const COLLAPSE_SAMPLES: u32 = 3;
+
+/// 릴레이 후보 출처
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RelaySource {
+    Mdns,
+    Dht,
+    Config,
+}
+
+/// 릴레이 후보 노드
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelayCandidate {
+    pub address: SocketAddr,
+    pub source: RelaySource,
+    /// 로컬 → 릴레이 RTT (밀리초, 미측정 시 None)
+    pub rtt_ms: Option<u32>,
+    /// 광고된 여유 대역폭 (Mbps, 알 수 없으면 None)
+    pub available_bandwidth_mbps: Option<u64>,
+    /// 현재 이 릴레이를 사용 중인 세션 수
+    pub active_sessions: u32,
+    #[serde(skip)]
+    probed_at: Option<Instant>,
+}
+
+impl RelayCandidate {
+    pub fn new(address: SocketAddr, source: RelaySource) -> Self {
+        Self {
+            address,
+            source,
+            rtt_ms: None,
+            available_bandwidth_mbps: None,
+            active_sessions: 0,
+            probed_at: None,
+        }
+    }
+
+    pub fn with_bandwidth(mut self, available_bandwidth_mbps: u64) -> Self {
+        self.available_bandwidth_mbps = Some(available_bandwidth_mbps);
+        self
+    }
+
+    fn needs_probe(&self) -> bool {
+        self.probed_at
+            .map(|t| t.elapsed() > PROBE_TTL)
+            .unwrap_or(true)
+    }
+
+    /// 피어 쌍 기준 점수 (낮을수록 좋음)
+    ///
+    /// `remote_rtt_ms`는 상대 피어가 시그널링으로 알려준 자신 → 릴레이 RTT입니다.
+    /// 모르면 로컬 RTT를 대칭으로 가정합니다.
+    pub fn pair_score(&self, remote_rtt_ms: Option<u32>) -> Option<i64> {
+        let local = self.rtt_ms? as i64;
+        let remote = remote_rtt_ms.map(|r| r as i64).unwrap_or(local);
+
+        // 대역폭이 1Gbps 미만이면 부족분만큼 페널티 (100Mbps당 10ms 환산)
+        let bandwidth_penalty = self
+            .available_bandwidth_mbps
+            .map(|bw| (1000i64 - bw as i64).max(0) / 10)
+            .unwrap_or(0);
+        let load_penalty = self.active_sessions as i64 * 20;
+
+        Some(local + remote + bandwidth_penalty + load_penalty)
+    }
+}
+
+/// 릴레이 선택 결과
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelaySelection {
+    pub relay: RelayCandidate,
+    pub score: i64,
+    pub candidates_probed: usize,
+}
+
+/// 세션별 처리량 추적 상태
+#[derive(Debug, Clone)]
+struct SessionThroughput {
+    relay: SocketAddr,
+    remote_rtts: HashMap<SocketAddr, u32>,
+    peak_bps: u64,
+    low_samples: u32,
+}
+
+/// 지연 시간 기반 릴레이 선택기
+pub struct RelaySelector {
+    candidates: RwLock<HashMap<SocketAddr, RelayCandidate>>,
+    sessions: RwLock<HashMap<String, SessionThroughput>>,
+    endpoint: RwLock<Option<Endpoint>>,
+}
+
+impl RelaySelector {
+    pub fn new() -> Self {
+        Self {
+            candidates: RwLock::new(HashMap::new()),
+            sessions: RwLock::new(HashMap::new()),
+            endpoint: RwLock::new(None),
+        }
+    }
+
+    /// 후보 추가 (이미 있으면 대역폭 정보만 갱신)
+    pub async fn add_candidate(&self, candidate: RelayCandidate) {
+        let mut candidates = self.candidates.write().await;
+        match candidates.get_mut(&candidate.address) {
+            Some(existing) => {
+                if candidate.available_bandwidth_mbps.is_some() {
+                    existing.available_bandwidth_mbps = candidate.available_bandwidth_mbps;
+                }
+            }
+            None => {
+                debug!(
+                    "릴레이 후보 추가: {} ({:?})",
+                    candidate.address, candidate.source
+                );
+                candidates.insert(candidate.address, candidate);
+            }
+        }
+    }
+
+    /// 후보 제거
+    pub async fn remove_candidate(&self, addr: &SocketAddr) {
+        self.candidates.write().await.remove(addr);
+    }
+
+    /// 현재 후보 목록
+    pub async fn candidates(&self) -> Vec<RelayCandidate> {
+        self.candidates.read().await.values().cloned().collect()
+    }
+
+    /// 프로브용 QUIC 클라이언트 엔드포인트 (지연 생성)
+    async fn probe_endpoint(&self) -> anyhow::Result<Endpoint> {
+        if let Some(ref endpoint) = *self.endpoint.read().await {
+            return Ok(endpoint.clone());
+        }
+
+        let mut client_crypto = rustls::ClientConfig::builder()
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(
+                crate::quic::client::SkipServerVerification,
+            ))
+            .with_no_client_auth();
+        client_crypto.alpn_protocols = vec![b"ponswarp-relay".to_vec()];
+
+        let client_config = ClientConfig::new(Arc::new(
+            quinn::crypto::rustls::QuicClientConfig::try_from(client_crypto)?,
+        ));
+
+        let mut endpoint = Endpoint::client("0.0.0.0:0".parse()?)?;
+        endpoint.set_default_client_config(client_config);
+
+        *self.endpoint.write().await = Some(endpoint.clone());
+        Ok(endpoint)
+    }
+
+    /// 단일 릴레이 RTT 측정 (QUIC 핸드셰이크 기준)
+    async fn probe(endpoint: &Endpoint, addr: SocketAddr) -> Option<u32> {
+        let started = Instant::now();
+        let connecting = endpoint.connect(addr, "ponswarp-relay").ok()?;
+
+        match tokio::time::timeout(PROBE_TIMEOUT, connecting).await {
+            Ok(Ok(conn)) => {
+                // 핸드셰이크 직후의 rtt()는 1-RTT 샘플이므로 경과 시간과 비교해 작은 값 사용
+                let rtt = conn.rtt().min(started.elapsed());
+                conn.close(0u32.into(), b"probe");
+                Some(rtt.as_millis().max(1) as u32)
+            }
+            Ok(Err(e)) => {
+                debug!("릴레이 프로브 실패: {} - {}", addr, e);
+                None
+            }
+            Err(_) => {
+                debug!("릴레이 프로브 타임아웃: {}", addr);
+                None
+            }
+        }
+    }
+
+    /// 오래된 측정값을 가진 후보들을 병렬로 프로브
+    pub async fn probe_all(&self) -> anyhow::Result<usize> {
+        let targets: Vec<SocketAddr> = self
+            .candidates
+            .read()
+            .await
+            .values()
+            .filter(|c| c.needs_probe())
+            .map(|c| c.address)
+            .collect();
+
+        if targets.is_empty() {
+            return Ok(0);
+        }
+
+        let endpoint = &self.probe_endpoint().await?;
+        let results = futures::future::join_all(
+            targets
+                .iter()
+                .map(|&addr| async move { (addr, Self::probe(endpoint, addr).await) }),
+        )
+        .await;
+
+        let mut candidates = self.candidates.write().await;
+        let now = Instant::now();
+        for (addr, rtt) in &results {
+            if let Some(candidate) = candidates.get_mut(addr) {
+                candidate.rtt_ms = *rtt;
+                candidate.probed_at = Some(now);
+            }
+        }
+
+        info!(
+            "📶 릴레이 RTT 측정 완료: {}/{} 응답",
+            results.iter().filter(|(_, rtt)| rtt.is_some()).count(),
+            results.len()
+        );
+        Ok(results.len())
+    }
+
+    /// 피어 쌍에 가장 적합한 릴레이 선택
+    pub async fn select(
+        &self,
+        remote_rtts: &HashMap<SocketAddr, u32>,
+        exclude: Option<SocketAddr>,
+    ) -> anyhow::Result<Option<RelaySelection>> {
+        let probed = self.probe_all().await?;
+        let candidates = self.candidates.read().await;
+
+        let best = candidates
+            .values()
+            .filter(|c| Some(c.address) != exclude)
+            .filter_map(|c| {
+                c.pair_score(remote_rtts.get(&c.address).copied())
+                    .map(|score| (c, score))
+            })
+            .min_by_key(|(_, score)| *score);
+
+        Ok(best.map(|(relay, score)| RelaySelection {
+            relay: relay.clone(),
+            score,
+            candidates_probed: probed,
+        }))
+    }
+
+    /// 세션에 릴레이 배정 (선택 후 처리량 추적 시작)
+    pub async fn assign(
+        &self,
+        session_id: &str,
+        remote_rtts: HashMap<SocketAddr, u32>,
+    ) -> anyhow::Result<Option<RelaySelection>> {
+        let selection = self.select(&remote_rtts, None).await?;
+
+        if let Some(ref sel) = selection {
+            self.attach(session_id, sel.relay.address, remote_rtts).await;
+            info!(
+                "🔀 릴레이 선택: {} -> {} (score {})",
+                session_id, sel.relay.address, sel.score
+            );
+        }
+
+        Ok(selection)
+    }
+
+    async fn attach(
+        &self,
+        session_id: &str,
+        relay: SocketAddr,
+        remote_rtts: HashMap<SocketAddr, u32>,
+    ) {
+        if let Some(candidate) = self.candidates.write().await.get_mut(&relay) {
+            candidate.active_sessions += 1;
+        }
+        self.sessions.write().await.insert(
+            session_id.to_string(),
+            SessionThroughput {
+                relay,
+                remote_rtts,
+                peak_bps: 0,
+                low_samples: 0,
+            },
+        );
+    }
+
+    /// 세션 종료 (릴레이 부하 카운트 반환)
+    pub async fn release(&self, session_id: &str) {
+        if let Some(session) = self.sessions.write().await.remove(session_id) {
+            if let Some(candidate) = self.candidates.write().await.get_mut(&session.relay) {
+                candidate.active_sessions = candidate.active_sessions.saturating_sub(1);
+            }
+        }
+    }
+
+    /// 처리량 샘플 보고
+    ///
+    /// 처리량이 최고치의 `COLLAPSE_RATIO` 미만으로 연속 `COLLAPSE_SAMPLES`회 떨어지면
+    /// 현재 릴레이를 제외하고 재선택하여, 더 나은 릴레이가 있으면 반환합니다.
+    pub async fn report_throughput(
+        &self,
+        session_id: &str,
+        bytes_per_sec: u64,
+    ) -> anyhow::Result<Option<RelaySelection>> {
+        let collapsed = {
+            let mut sessions = self.sessions.write().await;
+            let Some(session) = sessions.get_mut(session_id) else {
+                return Ok(None);
+            };
+            Self::record_sample(session, bytes_per_sec)
+        };
+
+        let Some((current, remote_rtts)) = collapsed else {
+            return Ok(None);
+        };
+
+        warn!(
+            "📉 릴레이 처리량 급락 감지: {} via {} ({} B/s), 재평가",
+            session_id, current, bytes_per_sec
+        );
+
+        // 현재 릴레이 측정값은 더 이상 신뢰할 수 없으므로 재측정 대상으로 표시
+        if let Some(candidate) = self.candidates.write().await.get_mut(&current) {
+            candidate.probed_at = None;
+        }
+
+        let selection = self.select(&remote_rtts, Some(current)).await?;
+        if let Some(ref sel) = selection {
+            self.release(session_id).await;
+            self.attach(session_id, sel.relay.address, remote_rtts).await;
+            info!(
+                "🔀 릴레이 재선택: {} {} -> {}",
+                session_id, current, sel.relay.address
+            );
+        }
+
+        Ok(selection)
+    }
+
+    /// 샘플 기록 후 급락 여부 판정 (급락 시 현재 릴레이와 상대 RTT 반환)
+    fn record_sample(
+        session: &mut SessionThroughput,
+        bytes_per_sec: u64,
+    ) -> Option<(SocketAddr, HashMap<SocketAddr, u32>)> {
+        if bytes_per_sec > session.peak_bps {
+            session.peak_bps = bytes_per_sec;
+        }
+
+        let threshold = (session.peak_bps as f64 * COLLAPSE_RATIO) as u64;
+        if session.peak_bps > 0 && bytes_per_sec < threshold {
+            session.low_samples += 1;
+        } else {
+            session.low_samples = 0;
+        }
+
+        if session.low_samples >= COLLAPSE_SAMPLES {
+            // 새 릴레이에서 기준치를 다시 잡도록 초기화
+            session.low_samples = 0;
+            session.peak_bps = 0;
+            Some((session.relay, session.remote_rtts.clone()))
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for RelaySelector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(port: u16, rtt: u32) -> RelayCandidate {
+        let mut c = RelayCandidate::new(
+            format!("10.0.0.1:{}", port).parse().unwrap(),
+            RelaySource::Config,
+        );
+        c.rtt_ms = Some(rtt);
+        c
+    }
+
+    #[test]
+    fn test_pair_score_prefers_low_latency() {
+        let near = candidate(1, 5);
+        let far = candidate(2, 50);
+
+        assert!(near.pair_score(None).unwrap() < far.pair_score(None).unwrap());
+        // 상대 피어 기준으로는 far가 훨씬 가까우면 역전
+        assert!(far.pair_score(Some(1)).unwrap() < near.pair_score(Some(200)).unwrap());
+    }
+
+    #[test]
+    fn test_pair_score_penalizes_load_and_bandwidth() {
+        let idle = candidate(1, 10).with_bandwidth(1000);
+        let mut busy = candidate(2, 10).with_bandwidth(1000);
+        busy.active_sessions = 3;
+        let slow = candidate(3, 10).with_bandwidth(100);
+
+        let idle_score = idle.pair_score(None).unwrap();
+        assert!(idle_score < busy.pair_score(None).unwrap());
+        assert!(idle_score < slow.pair_score(None).unwrap());
+        assert!(RelayCandidate::new(idle.address, RelaySource::Dht)
+            .pair_score(None)
+            .is_none());
+    }
+
+    #[test]
+    fn test_collapse_detection() {
+        let mut session = SessionThroughput {
+            relay: "10.0.0.1:6882".parse().unwrap(),
+            remote_rtts: HashMap::new(),
+            peak_bps: 0,
+            low_samples: 0,
+        };
+
+        assert!(RelaySelector::record_sample(&mut session, 100_000_000).is_none());
+        assert!(RelaySelector::record_sample(&mut session, 10_000_000).is_none());
+        assert!(RelaySelector::record_sample(&mut session, 10_000_000).is_none());
+        // 중간에 회복하면 카운트 초기화
+        assert!(RelaySelector::record_sample(&mut session, 90_000_000).is_none());
+        assert!(RelaySelector::record_sample(&mut session, 10_000_000).is_none());
+        assert!(RelaySelector::record_sample(&mut session, 10_000_000).is_none());
+        assert!(RelaySelector::record_sample(&mut session, 10_000_000).is_some());
+        assert_eq!(session.peak_bps, 0);
+    }
+}
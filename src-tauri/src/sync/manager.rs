@@ -0,0 +1,624 @@
+//! 동기화 작업 관리 (송신: 감시 + 푸시, 수신: 충돌 정책에 따라 반영)
+
+use super::scan::{self, FileState, Resolution};
+use super::watcher::FolderWatcher;
+use super::{ConflictPolicy, SyncAction, SyncEvent};
+use crate::protocol::Command;
+use crate::transfer::control_stream::{self, IncomingStream};
+use crate::transfer::file_transfer::safe_destination;
+use crate::transfer::part_file;
+use anyhow::{anyhow, Result};
+use parking_lot::Mutex;
+use quinn::{RecvStream, VarInt, WriteError};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+/// 수신 측이 스트림을 중단하는 이유 (송신 측이 결과로 해석)
+const STOP_UP_TO_DATE: u32 = 1;
+/// 충돌 정책으로 로컬 파일 유지
+const STOP_CONFLICT: u32 = 2;
+/// 등록되지 않은 sync_id, 잘못된 경로, 기록 실패
+const STOP_FAILED: u32 = 3;
+
+/// 파일 복사 버퍼 크기
+const COPY_BUFFER: usize = 1024 * 1024;
+
+type EventSender = Option<mpsc::UnboundedSender<SyncEvent>>;
+
+fn emit(
+    events: &EventSender,
+    sync_id: &str,
+    path: &str,
+    action: SyncAction,
+    error: Option<String>,
+) {
+    if let Some(tx) = events {
+        let _ = tx.send(SyncEvent {
+            sync_id: sync_id.to_string(),
+            path: path.to_string(),
+            action,
+            error,
+        });
+    }
+}
+
+/// 실행 중인 송신 동기화
+struct OutgoingSync {
+    peer_id: String,
+    root: PathBuf,
+    task: JoinHandle<()>,
+}
+
+/// 수신 동기화 대상 폴더
+struct IncomingSync {
+    root: PathBuf,
+    policy: ConflictPolicy,
+    /// 마지막으로 동기화한 파일 상태 (로컬 변경 여부 판단용)
+    base: Mutex<HashMap<String, FileState>>,
+}
+
+/// 동기화 작업 정보 (프론트엔드 조회용)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncInfo {
+    pub sync_id: String,
+    /// "outgoing" | "incoming"
+    pub direction: &'static str,
+    pub root: String,
+    pub peer_id: Option<String>,
+    pub policy: Option<ConflictPolicy>,
+    pub running: bool,
+}
+
+/// sync_id → 동기화 작업
+#[derive(Default)]
+pub struct FolderSyncManager {
+    outgoing: Mutex<HashMap<String, OutgoingSync>>,
+    incoming: Mutex<HashMap<String, Arc<IncomingSync>>>,
+    events: EventSender,
+}
+
+impl FolderSyncManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 동기화 이벤트 채널 설정 (프론트엔드로 전달)
+    pub fn with_event_channel(mut self, tx: mpsc::UnboundedSender<SyncEvent>) -> Self {
+        self.events = Some(tx);
+        self
+    }
+
+    /// 로컬 폴더 감시 및 푸시 시작
+    ///
+    /// 시작 시 폴더 전체를 한 번 보내며, 피어에 같은 내용이 있는 파일은 데이터 없이 건너뜁니다.
+    pub fn start_outgoing(
+        &self,
+        sync_id: &str,
+        peer_id: &str,
+        conn: quinn::Connection,
+        root: PathBuf,
+    ) -> Result<()> {
+        if !root.is_dir() {
+            return Err(anyhow!("폴더가 아닙니다: {}", root.display()));
+        }
+
+        let mut outgoing = self.outgoing.lock();
+        if outgoing
+            .get(sync_id)
+            .is_some_and(|sync| !sync.task.is_finished())
+        {
+            return Err(anyhow!("이미 실행 중인 동기화입니다: {}", sync_id));
+        }
+
+        let watcher = FolderWatcher::start(&root)?;
+        let pusher = Pusher {
+            sync_id: sync_id.to_string(),
+            conn,
+            root: root.clone(),
+            events: self.events.clone(),
+        };
+        let task = tokio::spawn(pusher.run(watcher));
+
+        outgoing.insert(
+            sync_id.to_string(),
+            OutgoingSync {
+                peer_id: peer_id.to_string(),
+                root: root.clone(),
+                task,
+            },
+        );
+        info!(
+            "🔄 폴더 동기화 시작: {} ({:?} -> {})",
+            sync_id, root, peer_id
+        );
+        Ok(())
+    }
+
+    /// 피어가 `sync_id`로 보내는 변경을 받을 폴더 등록 (같은 ID면 교체)
+    pub fn accept_incoming(
+        &self,
+        sync_id: &str,
+        root: PathBuf,
+        policy: ConflictPolicy,
+    ) -> Result<()> {
+        std::fs::create_dir_all(&root)?;
+        self.incoming.lock().insert(
+            sync_id.to_string(),
+            Arc::new(IncomingSync {
+                root: root.clone(),
+                policy,
+                base: Mutex::new(HashMap::new()),
+            }),
+        );
+        info!(
+            "📥 폴더 동기화 수신 등록: {} -> {:?} ({:?})",
+            sync_id, root, policy
+        );
+        Ok(())
+    }
+
+    /// 송신/수신 동기화 중지
+    pub fn stop(&self, sync_id: &str) -> bool {
+        let outgoing = self.outgoing.lock().remove(sync_id);
+        if let Some(sync) = &outgoing {
+            sync.task.abort();
+        }
+        let incoming = self.incoming.lock().remove(sync_id);
+        outgoing.is_some() || incoming.is_some()
+    }
+
+    pub fn list(&self) -> Vec<SyncInfo> {
+        let mut list: Vec<SyncInfo> = self
+            .outgoing
+            .lock()
+            .iter()
+            .map(|(sync_id, sync)| SyncInfo {
+                sync_id: sync_id.clone(),
+                direction: "outgoing",
+                root: sync.root.to_string_lossy().to_string(),
+                peer_id: Some(sync.peer_id.clone()),
+                policy: None,
+                running: !sync.task.is_finished(),
+            })
+            .collect();
+        list.extend(self.incoming.lock().iter().map(|(sync_id, sync)| SyncInfo {
+            sync_id: sync_id.clone(),
+            direction: "incoming",
+            root: sync.root.to_string_lossy().to_string(),
+            peer_id: None,
+            policy: Some(sync.policy),
+            running: true,
+        }));
+        list.sort_by(|a, b| a.sync_id.cmp(&b.sync_id));
+        list
+    }
+
+    /// 제어 스트림으로 받은 `SyncFile`/`SyncDelete` 처리
+    pub async fn handle_incoming(&self, incoming: IncomingStream) {
+        let IncomingStream {
+            peer_id,
+            command,
+            mut recv,
+        } = incoming;
+
+        let (sync_id, path) = match &command {
+            Command::SyncFile { sync_id, path, .. } | Command::SyncDelete { sync_id, path } => {
+                (sync_id.clone(), path.clone())
+            }
+            _ => return,
+        };
+        let target = self.incoming.lock().get(&sync_id).cloned();
+        let Some(target) = target else {
+            warn!("등록되지 않은 동기화 요청 ({}): {}", peer_id, sync_id);
+            let _ = recv.stop(STOP_FAILED.into());
+            return;
+        };
+
+        match command {
+            Command::SyncFile {
+                size,
+                modified,
+                hash,
+                ..
+            } => {
+                let state = FileState {
+                    size,
+                    modified,
+                    hash,
+                };
+                match target.receive_file(&path, state, &mut recv).await {
+                    Ok(action) => emit(&self.events, &sync_id, &path, action, None),
+                    Err(e) => {
+                        warn!("동기화 파일 수신 실패 ({}): {} - {}", sync_id, path, e);
+                        let _ = recv.stop(STOP_FAILED.into());
+                        emit(
+                            &self.events,
+                            &sync_id,
+                            &path,
+                            SyncAction::Failed,
+                            Some(e.to_string()),
+                        );
+                    }
+                }
+            }
+            Command::SyncDelete { .. } => {
+                let _ = control_stream::finish_header_only(&mut recv).await;
+                let results = tokio::task::spawn_blocking(move || target.delete(&path))
+                    .await
+                    .unwrap_or_default();
+                for (path, action) in results {
+                    emit(&self.events, &sync_id, &path, action, None);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl IncomingSync {
+    /// 피어가 보낸 상대 경로의 로컬 경로 (루트 밖이거나 동기화하지 않는 경로면 None)
+    fn local_path(&self, relative_path: &str) -> Option<PathBuf> {
+        let path = safe_destination(&self.root, relative_path).ok()?;
+        // `\` 구분자로 정리된 경로(`a\.git\config`)도 같은 규칙으로 확인
+        let normalized = scan::relative_path(&self.root, &path)?;
+        (!scan::is_ignored(relative_path) && !scan::is_ignored(&normalized)).then_some(path)
+    }
+
+    /// 받은 파일을 충돌 정책에 따라 기록
+    async fn receive_file(
+        &self,
+        relative_path: &str,
+        incoming: FileState,
+        recv: &mut RecvStream,
+    ) -> Result<SyncAction> {
+        let path = self
+            .local_path(relative_path)
+            .ok_or_else(|| anyhow!("잘못된 경로: {}", relative_path))?;
+
+        let local = {
+            let path = path.clone();
+            tokio::task::spawn_blocking(move || FileState::read(&path).ok()).await?
+        };
+        let base = self.base.lock().get(relative_path).cloned();
+
+        let resolution = scan::resolve(self.policy, base.as_ref(), local.as_ref(), &incoming);
+        let target = match resolution {
+            Resolution::UpToDate => {
+                let _ = recv.stop(STOP_UP_TO_DATE.into());
+                self.base.lock().insert(relative_path.to_string(), incoming);
+                return Ok(SyncAction::UpToDate);
+            }
+            Resolution::KeepLocal => {
+                let _ = recv.stop(STOP_CONFLICT.into());
+                return Ok(SyncAction::Conflict);
+            }
+            Resolution::Write => path,
+            Resolution::WriteCopy => scan::conflict_copy_path(&path),
+        };
+
+        write_file(&target, &incoming, recv).await?;
+        if resolution == Resolution::Write {
+            self.base.lock().insert(relative_path.to_string(), incoming);
+        }
+
+        Ok(if resolution.is_conflict() {
+            SyncAction::Conflict
+        } else {
+            SyncAction::Received
+        })
+    }
+
+    /// 파일/폴더 삭제 반영 (로컬에서 바뀐 파일은 SourceWins가 아니면 유지, 블로킹)
+    fn delete(&self, relative_path: &str) -> Vec<(String, SyncAction)> {
+        let Some(path) = self.local_path(relative_path) else {
+            warn!("잘못된 삭제 경로: {}", relative_path);
+            return Vec::new();
+        };
+
+        let files = if path.is_dir() {
+            scan::scan(&self.root, &path)
+        } else {
+            vec![relative_path.to_string()]
+        };
+
+        let mut results = Vec::new();
+        for file in files {
+            let Some(file_path) = self.local_path(&file) else {
+                continue;
+            };
+            let Ok(local) = FileState::read(&file_path) else {
+                continue;
+            };
+
+            let mut base = self.base.lock();
+            let unchanged = base.get(&file).is_some_and(|b| b.hash == local.hash);
+            if unchanged || self.policy == ConflictPolicy::SourceWins {
+                if std::fs::remove_file(&file_path).is_ok() {
+                    base.remove(&file);
+                    results.push((file, SyncAction::Deleted));
+                }
+            } else {
+                results.push((file, SyncAction::Conflict));
+            }
+        }
+
+        if path.is_dir() {
+            remove_empty_dirs(&path);
+        }
+        results
+    }
+}
+
+/// 하위의 빈 폴더 삭제 (숨김 파일 등이 남은 폴더는 유지)
+fn remove_empty_dirs(dir: &Path) {
+    if let Ok(entries) = std::fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            if entry.file_type().is_ok_and(|t| t.is_dir()) {
+                remove_empty_dirs(&entry.path());
+            }
+        }
+    }
+    let _ = std::fs::remove_dir(dir);
+}
+
+/// 임시 파일에 받은 뒤 크기/해시를 확인하고 원래 수정 시각으로 교체
+async fn write_file(target: &Path, incoming: &FileState, recv: &mut RecvStream) -> Result<()> {
+    let parent = target
+        .parent()
+        .ok_or_else(|| anyhow!("잘못된 경로: {}", target.display()))?;
+    tokio::fs::create_dir_all(parent).await?;
+
+    // `.pswp-part`는 감시 중인 송신 측도 무시함
+    let temp_path = part_file::part_path(target);
+
+    let result = async {
+        let mut file = tokio::fs::File::create(&temp_path).await?;
+        let mut hasher = Sha256::new();
+        let mut received = 0u64;
+        let mut buffer = vec![0u8; COPY_BUFFER];
+        while let Some(n) = recv.read(&mut buffer).await? {
+            file.write_all(&buffer[..n]).await?;
+            hasher.update(&buffer[..n]);
+            received += n as u64;
+        }
+        file.flush().await?;
+
+        if received != incoming.size {
+            return Err(anyhow!(
+                "크기 불일치: {} / {} bytes",
+                received,
+                incoming.size
+            ));
+        }
+        if hex::encode(hasher.finalize()) != incoming.hash {
+            return Err(anyhow!("해시 불일치"));
+        }
+
+        let file = file.into_std().await;
+        let modified = scan::from_unix_ms(incoming.modified);
+        let (temp, target) = (temp_path.clone(), target.to_path_buf());
+        tokio::task::spawn_blocking(move || -> std::io::Result<()> {
+            file.set_modified(modified)?;
+            drop(file);
+            part_file::commit(&temp, &target)
+        })
+        .await??;
+        Ok(())
+    }
+    .await;
+
+    if result.is_err() {
+        part_file::discard(&temp_path);
+    }
+    result
+}
+
+/// 송신 측 감시/푸시 태스크
+struct Pusher {
+    sync_id: String,
+    conn: quinn::Connection,
+    root: PathBuf,
+    events: EventSender,
+}
+
+impl Pusher {
+    async fn run(self, mut watcher: FolderWatcher) {
+        let root = self.root.clone();
+        let files = tokio::task::spawn_blocking(move || scan::scan(&root, &root))
+            .await
+            .unwrap_or_default();
+        info!("🔄 초기 동기화: {} 파일 ({})", files.len(), self.sync_id);
+        for relative_path in files {
+            self.push(&relative_path).await;
+        }
+
+        loop {
+            let batch = tokio::select! {
+                batch = watcher.next_batch() => match batch {
+                    Some(batch) => batch,
+                    None => break,
+                },
+                reason = self.conn.closed() => {
+                    warn!("폴더 동기화 중단 ({}): {}", self.sync_id, reason);
+                    emit(&self.events, &self.sync_id, "", SyncAction::Failed, Some(reason.to_string()));
+                    break;
+                }
+            };
+
+            let mut paths: Vec<PathBuf> = batch.into_iter().collect();
+            paths.sort();
+            for path in paths {
+                self.sync_path(&path).await;
+            }
+        }
+    }
+
+    /// 변경된 경로 하나 반영 (파일 푸시 / 폴더 안 파일 푸시 / 삭제 전달)
+    async fn sync_path(&self, path: &Path) {
+        let Some(relative_path) = scan::relative_path(&self.root, path) else {
+            return;
+        };
+        if scan::is_ignored(&relative_path) {
+            return;
+        }
+
+        if path.is_file() {
+            self.push(&relative_path).await;
+        } else if path.is_dir() {
+            let (root, dir) = (self.root.clone(), path.to_path_buf());
+            let files = tokio::task::spawn_blocking(move || scan::scan(&root, &dir))
+                .await
+                .unwrap_or_default();
+            for file in files {
+                self.push(&file).await;
+            }
+        } else {
+            match self.send_delete(&relative_path).await {
+                Ok(()) => emit(
+                    &self.events,
+                    &self.sync_id,
+                    &relative_path,
+                    SyncAction::Deleted,
+                    None,
+                ),
+                Err(e) => self.report_error(&relative_path, e),
+            }
+        }
+    }
+
+    async fn push(&self, relative_path: &str) {
+        match self.push_file(relative_path).await {
+            // 초기 동기화 때 파일마다 이벤트가 쏟아지지 않도록 변경 없는 파일은 알리지 않음
+            Ok(SyncAction::UpToDate) => {}
+            Ok(action) => emit(&self.events, &self.sync_id, relative_path, action, None),
+            Err(e) => self.report_error(relative_path, e),
+        }
+    }
+
+    async fn push_file(&self, relative_path: &str) -> Result<SyncAction> {
+        let path = safe_destination(&self.root, relative_path)?;
+        let state = {
+            let path = path.clone();
+            tokio::task::spawn_blocking(move || FileState::read(&path)).await??
+        };
+
+        let command = Command::SyncFile {
+            sync_id: self.sync_id.clone(),
+            path: relative_path.to_string(),
+            size: state.size,
+            modified: state.modified,
+            hash: state.hash,
+        };
+        let mut send = control_stream::open(&self.conn, &command).await?;
+
+        let mut file = tokio::fs::File::open(&path).await?;
+        let mut buffer = vec![0u8; COPY_BUFFER];
+        loop {
+            let n = file.read(&mut buffer).await?;
+            if n == 0 {
+                break;
+            }
+            match send.write_all(&buffer[..n]).await {
+                Ok(()) => {}
+                // 수신 측이 내용을 보고 중단 (이미 최신 / 충돌로 거부)
+                Err(WriteError::Stopped(code)) => return Self::stopped_action(code),
+                Err(e) => return Err(e.into()),
+            }
+        }
+        send.finish()?;
+
+        match send.stopped().await? {
+            None => Ok(SyncAction::Pushed),
+            Some(code) => Self::stopped_action(code),
+        }
+    }
+
+    async fn send_delete(&self, relative_path: &str) -> Result<()> {
+        let command = Command::SyncDelete {
+            sync_id: self.sync_id.clone(),
+            path: relative_path.to_string(),
+        };
+        let mut send = control_stream::open(&self.conn, &command).await?;
+        send.finish()?;
+        if let Some(code) = send.stopped().await? {
+            return Err(anyhow!("피어가 삭제 요청을 거부했습니다 (code {})", code));
+        }
+        Ok(())
+    }
+
+    fn stopped_action(code: VarInt) -> Result<SyncAction> {
+        if code == VarInt::from_u32(STOP_UP_TO_DATE) {
+            Ok(SyncAction::UpToDate)
+        } else if code == VarInt::from_u32(STOP_CONFLICT) {
+            Ok(SyncAction::Conflict)
+        } else {
+            Err(anyhow!("피어가 파일을 받지 못했습니다 (code {})", code))
+        }
+    }
+
+    fn report_error(&self, relative_path: &str, error: anyhow::Error) {
+        warn!(
+            "동기화 실패 ({}): {} - {}",
+            self.sync_id, relative_path, error
+        );
+        emit(
+            &self.events,
+            &self.sync_id,
+            relative_path,
+            SyncAction::Failed,
+            Some(error.to_string()),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delete_skips_ignored_and_outside_paths() {
+        let root =
+            std::env::temp_dir().join(format!("ponswarp-sync-delete-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        std::fs::create_dir_all(root.join("a/.git")).unwrap();
+        std::fs::write(root.join(".git/config"), b"x").unwrap();
+        std::fs::write(root.join("a/.git/config"), b"x").unwrap();
+        std::fs::write(root.join("a/b.txt"), b"x").unwrap();
+        let sync = IncomingSync {
+            root: root.clone(),
+            policy: ConflictPolicy::SourceWins,
+            base: Mutex::new(HashMap::new()),
+        };
+
+        // 동기화하지 않는 경로와 루트 밖 경로는 삭제하지 않음
+        for path in [
+            ".git",
+            ".git/config",
+            "a\\.git\\config",
+            "a/../.git",
+            "../x",
+        ] {
+            assert!(sync.delete(path).is_empty(), "{}", path);
+        }
+        assert!(root.join(".git/config").exists());
+        assert!(root.join("a/.git/config").exists());
+
+        // 폴더 삭제도 무시 대상 파일은 남김
+        assert_eq!(
+            sync.delete("a"),
+            vec![("a/b.txt".to_string(), SyncAction::Deleted)]
+        );
+        assert!(root.join("a/.git/config").exists());
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+}
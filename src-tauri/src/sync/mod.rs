@@ -0,0 +1,56 @@
+//! 폴더 감시 및 동기화
+//!
+//! 송신 측은 로컬 폴더를 감시하다가 바뀐 파일만 짝지은 피어로 푸시하고,
+//! 수신 측은 같은 `sync_id`로 등록한 폴더에 충돌 정책에 따라 반영합니다.
+//! 메시지는 제어 스트림(`transfer::control_stream`)의 `SyncFile`/`SyncDelete` 명령입니다.
+//! 양쪽에서 송신/수신을 모두 등록하면 양방향 동기화가 됩니다 (같은 내용은 건너뜀).
+
+pub mod manager;
+pub mod scan;
+pub mod watcher;
+
+pub use manager::FolderSyncManager;
+
+use serde::{Deserialize, Serialize};
+
+/// 수신 측 파일이 마지막 동기화 이후 로컬에서도 바뀌었을 때의 처리
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConflictPolicy {
+    /// 수정 시각이 더 최근인 쪽 유지
+    #[default]
+    NewerWins,
+    /// 항상 보낸 쪽 내용으로 덮어씀
+    SourceWins,
+    /// 항상 로컬 파일 유지
+    KeepLocal,
+    /// 로컬 파일은 두고 받은 파일을 충돌 사본으로 저장
+    KeepBoth,
+}
+
+/// 동기화 이벤트 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SyncAction {
+    /// 피어로 보냄
+    Pushed,
+    /// 피어에게서 받아 기록
+    Received,
+    /// 삭제 반영
+    Deleted,
+    /// 이미 같은 내용
+    UpToDate,
+    /// 충돌 (정책에 따라 로컬 유지 또는 충돌 사본 저장)
+    Conflict,
+    Failed,
+}
+
+/// 프론트엔드 전송용 동기화 이벤트
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncEvent {
+    pub sync_id: String,
+    pub path: String,
+    pub action: SyncAction,
+    pub error: Option<String>,
+}
@@ -0,0 +1,240 @@
+//! 동기화 폴더 스캔, 파일 상태 계산, 충돌 판단
+
+use super::ConflictPolicy;
+use crate::transfer::part_file;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// 파일 상태 (변경 감지/충돌 판단용)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileState {
+    pub size: u64,
+    /// 수정 시각 (Unix ms)
+    pub modified: u64,
+    /// SHA-256 (hex)
+    pub hash: String,
+}
+
+impl FileState {
+    /// 파일 내용을 해시하여 상태 계산 (블로킹)
+    pub fn read(path: &Path) -> io::Result<Self> {
+        let metadata = fs::metadata(path)?;
+        let mut file = fs::File::open(path)?;
+        let mut hasher = Sha256::new();
+        let mut buffer = vec![0u8; 1024 * 1024];
+        loop {
+            let n = file.read(&mut buffer)?;
+            if n == 0 {
+                break;
+            }
+            hasher.update(&buffer[..n]);
+        }
+
+        Ok(Self {
+            size: metadata.len(),
+            modified: metadata.modified().map(to_unix_ms).unwrap_or(0),
+            hash: hex::encode(hasher.finalize()),
+        })
+    }
+}
+
+pub fn to_unix_ms(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+pub fn from_unix_ms(ms: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_millis(ms)
+}
+
+/// 숨김 경로(.git, .DS_Store, 수신 중인 임시 파일 등)는 동기화하지 않음
+pub fn is_ignored(relative_path: &str) -> bool {
+    relative_path.split('/').any(|part| part.starts_with('.'))
+        || part_file::is_part_file(Path::new(relative_path))
+}
+
+/// 루트 기준 상대 경로 ('/' 구분)
+pub fn relative_path(root: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(root).ok()?;
+    let parts: Vec<String> = relative
+        .components()
+        .map(|c| match c {
+            Component::Normal(part) => Some(part.to_string_lossy().to_string()),
+            _ => None,
+        })
+        .collect::<Option<_>>()?;
+    if parts.is_empty() {
+        return None;
+    }
+    Some(parts.join("/"))
+}
+
+/// 폴더 아래의 동기화 대상 파일 (루트 기준 상대 경로)
+pub fn scan(root: &Path, dir: &Path) -> Vec<String> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(relative) = relative_path(root, &path) else {
+                continue;
+            };
+            if is_ignored(&relative) {
+                continue;
+            }
+            match entry.file_type() {
+                Ok(t) if t.is_dir() => pending.push(path),
+                Ok(t) if t.is_file() => files.push(relative),
+                _ => {}
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+/// 충돌 시 받은 파일을 저장할 경로 (`이름 (conflict 20260101-120000).확장자`)
+pub fn conflict_copy_path(path: &Path) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let name = match path.extension() {
+        Some(ext) => format!("{} (conflict {}).{}", stem, stamp, ext.to_string_lossy()),
+        None => format!("{} (conflict {})", stem, stamp),
+    };
+    path.with_file_name(name)
+}
+
+/// 받은 파일을 어떻게 반영할지
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    /// 이미 같은 내용
+    UpToDate,
+    /// 원래 경로에 기록
+    Write,
+    /// 충돌: 받은 파일을 충돌 사본으로 기록
+    WriteCopy,
+    /// 충돌: 로컬 파일 유지
+    KeepLocal,
+}
+
+impl Resolution {
+    pub fn is_conflict(self) -> bool {
+        matches!(self, Resolution::WriteCopy | Resolution::KeepLocal)
+    }
+}
+
+/// 받은 파일 반영 방법 결정
+///
+/// `base`는 마지막으로 동기화한 상태입니다. 로컬 파일이 그 뒤로 바뀌지 않았으면 그대로 덮어쓰고,
+/// 로컬에서도 바뀌었으면(또는 동기화 기록이 없으면) 충돌 정책을 따릅니다.
+pub fn resolve(
+    policy: ConflictPolicy,
+    base: Option<&FileState>,
+    local: Option<&FileState>,
+    incoming: &FileState,
+) -> Resolution {
+    let Some(local) = local else {
+        return Resolution::Write;
+    };
+    if local.hash == incoming.hash {
+        return Resolution::UpToDate;
+    }
+    if base.is_some_and(|base| base.hash == local.hash) {
+        return Resolution::Write;
+    }
+
+    match policy {
+        ConflictPolicy::SourceWins => Resolution::Write,
+        ConflictPolicy::NewerWins if incoming.modified > local.modified => Resolution::Write,
+        ConflictPolicy::NewerWins | ConflictPolicy::KeepLocal => Resolution::KeepLocal,
+        ConflictPolicy::KeepBoth => Resolution::WriteCopy,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(hash: &str, modified: u64) -> FileState {
+        FileState {
+            size: 1,
+            modified,
+            hash: hash.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_resolve_conflicts() {
+        let incoming = state("new", 200);
+        let local = state("local", 100);
+        let base = state("local", 100);
+
+        // 새 파일 / 같은 내용 / 로컬 변경 없음
+        assert_eq!(
+            resolve(ConflictPolicy::KeepLocal, None, None, &incoming),
+            Resolution::Write
+        );
+        assert_eq!(
+            resolve(ConflictPolicy::KeepLocal, None, Some(&incoming), &incoming),
+            Resolution::UpToDate
+        );
+        assert_eq!(
+            resolve(
+                ConflictPolicy::KeepLocal,
+                Some(&base),
+                Some(&local),
+                &incoming
+            ),
+            Resolution::Write
+        );
+
+        // 로컬도 바뀐 경우 정책에 따름
+        let edited = state("edited", 300);
+        assert_eq!(
+            resolve(
+                ConflictPolicy::NewerWins,
+                Some(&base),
+                Some(&edited),
+                &incoming
+            ),
+            Resolution::KeepLocal
+        );
+        assert_eq!(
+            resolve(ConflictPolicy::NewerWins, None, Some(&local), &incoming),
+            Resolution::Write
+        );
+        assert_eq!(
+            resolve(ConflictPolicy::KeepBoth, None, Some(&edited), &incoming),
+            Resolution::WriteCopy
+        );
+        assert_eq!(
+            resolve(ConflictPolicy::SourceWins, None, Some(&edited), &incoming),
+            Resolution::Write
+        );
+    }
+
+    #[test]
+    fn test_paths() {
+        let root = Path::new("/sync");
+        assert_eq!(
+            relative_path(root, Path::new("/sync/a/b.txt")).as_deref(),
+            Some("a/b.txt")
+        );
+        assert_eq!(relative_path(root, Path::new("/sync")), None);
+        assert!(is_ignored("a/.git/config"));
+        assert!(!is_ignored("a/b.txt"));
+        assert!(is_ignored("a/b.txt.pswp-part"));
+    }
+}
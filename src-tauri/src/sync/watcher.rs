@@ -0,0 +1,51 @@
+//! 폴더 변경 감시 (notify)
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// 변경이 이 시간 동안 잠잠해지면 한 번에 처리 (저장 중인 파일을 여러 번 보내지 않도록)
+pub const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// 폴더 감시자 (drop 시 감시 중지)
+pub struct FolderWatcher {
+    _watcher: RecommendedWatcher,
+    rx: mpsc::UnboundedReceiver<PathBuf>,
+}
+
+impl FolderWatcher {
+    /// 하위 폴더까지 감시 시작
+    pub fn start(root: &Path) -> notify::Result<Self> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let mut watcher =
+            notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+                let Ok(event) = res else {
+                    return;
+                };
+                if matches!(event.kind, EventKind::Access(_)) {
+                    return;
+                }
+                for path in event.paths {
+                    let _ = tx.send(path);
+                }
+            })?;
+        watcher.watch(root, RecursiveMode::Recursive)?;
+
+        Ok(Self {
+            _watcher: watcher,
+            rx,
+        })
+    }
+
+    /// 다음 변경 묶음 (감시가 끝나면 None)
+    pub async fn next_batch(&mut self) -> Option<HashSet<PathBuf>> {
+        let first = self.rx.recv().await?;
+        let mut batch = HashSet::from([first]);
+        while let Ok(Some(path)) = tokio::time::timeout(DEBOUNCE, self.rx.recv()).await {
+            batch.insert(path);
+        }
+        Some(batch)
+    }
+}
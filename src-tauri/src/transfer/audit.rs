@@ -0,0 +1,499 @@
+//! 서명된 전송 감사 로그
+//!
+//! 끝난 전송마다 누가(피어 ID/지문), 무엇을(파일 이름/SHA-256), 언제, 어떤 결과로 주고받았는지
+//! 한 줄씩 JSONL 파일에 덧붙입니다. 각 항목은 직전 항목의 해시를 담아 사슬을 이루고
+//! 노드 신원 키(Ed25519)로 서명되므로, 내보낸 로그에서 항목을 고치거나 빼면 검증에 실패합니다.
+//!
+//! 기업 배포용이라 기본은 꺼져 있습니다 (켜면 끝난 파일을 한 번 더 읽어 해시를 계산).
+
+use crate::bootstrap::mailbox::{self, SigningIdentity};
+use crate::transfer::registry::{
+    JobStatus, TransferJobSnapshot, TransferKind, EVENT_COMPLETED, EVENT_FAILED,
+};
+use crate::util::now_secs;
+use anyhow::{anyhow, Result};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// 감사 로그 파일명 (한 줄에 항목 하나)
+pub const AUDIT_LOG_FILE: &str = "transfer_audit.jsonl";
+
+/// 감사 로그 사용 여부 파일명
+pub const AUDIT_SETTINGS_FILE: &str = "transfer_audit_settings.json";
+
+const AUDIT_DOMAIN: &[u8] = b"ponswarp transfer audit v1";
+
+/// 첫 항목의 직전 해시
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// 전송 방향
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditDirection {
+    Send,
+    Receive,
+}
+
+/// 엔진 호출 측이 알려주는 전송 대상 (레지스트리 스냅샷에 없는 정보)
+#[derive(Debug, Clone)]
+pub struct AuditSubject {
+    pub direction: AuditDirection,
+    pub peer_fingerprint: Option<String>,
+    /// 보낸 원본 또는 받은 결과 경로 (일반 파일이면 해시 계산)
+    pub path: Option<PathBuf>,
+}
+
+/// 감사 로그 항목
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    /// 1부터 증가하는 일련번호
+    pub seq: u64,
+    /// 기록 시각 (Unix 초)
+    pub timestamp: u64,
+    /// 서명한 노드 ID (Ed25519 공개 키 hex)
+    pub node_id: String,
+    pub job_id: String,
+    /// 방향을 알 수 없는 작업(Grid 등)은 None
+    pub direction: Option<AuditDirection>,
+    pub kind: TransferKind,
+    pub peer_id: String,
+    pub peer_fingerprint: Option<String>,
+    pub file_name: Option<String>,
+    pub bytes: u64,
+    pub sha256: Option<String>,
+    pub status: JobStatus,
+    pub error: Option<String>,
+    /// 직전 항목 해시
+    pub prev_hash: String,
+    /// Ed25519 서명 (hex, 이 필드를 비운 항목에 대해)
+    pub signature: String,
+}
+
+impl AuditEntry {
+    fn signing_bytes(&self) -> Result<Vec<u8>> {
+        let unsigned = AuditEntry {
+            signature: String::new(),
+            ..self.clone()
+        };
+        let mut bytes = AUDIT_DOMAIN.to_vec();
+        bytes.extend_from_slice(&serde_json::to_vec(&unsigned)?);
+        Ok(bytes)
+    }
+
+    /// 다음 항목이 가리킬 해시 (서명 포함)
+    fn hash(&self) -> Result<String> {
+        let mut hasher = Sha256::new();
+        hasher.update(self.signing_bytes()?);
+        hasher.update(self.signature.as_bytes());
+        Ok(hex::encode(hasher.finalize()))
+    }
+}
+
+/// 감사 로그 검증 결과
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditVerification {
+    pub valid: bool,
+    /// 검증을 통과한 항목 수
+    pub entries: u64,
+    /// 서명한 노드 ID (키를 알고 있는 기기인지 확인용)
+    pub node_ids: Vec<String>,
+    /// 처음 실패한 줄 번호 (1부터)
+    pub first_invalid_line: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct AuditSettings {
+    #[serde(default)]
+    enabled: bool,
+}
+
+/// 사슬 끝 (다음 일련번호, 마지막 항목 해시)
+struct ChainHead {
+    next_seq: u64,
+    last_hash: String,
+}
+
+/// 전송 감사 로그 (모든 엔진 공유)
+pub struct AuditLog {
+    dir: Option<PathBuf>,
+    identity: Arc<SigningIdentity>,
+    enabled: AtomicBool,
+    /// job_id → 호출 측이 알려준 대상 (작업이 끝나면 소비)
+    subjects: Mutex<HashMap<String, AuditSubject>>,
+    head: Mutex<ChainHead>,
+}
+
+impl AuditLog {
+    /// 저장하지 않는 메모리 전용 (항상 꺼짐)
+    pub fn in_memory(identity: Arc<SigningIdentity>) -> Self {
+        Self {
+            dir: None,
+            identity,
+            enabled: AtomicBool::new(false),
+            subjects: Mutex::new(HashMap::new()),
+            head: Mutex::new(ChainHead {
+                next_seq: 1,
+                last_hash: GENESIS_HASH.to_string(),
+            }),
+        }
+    }
+
+    /// 앱 데이터 디렉토리에서 설정과 기존 로그의 사슬 끝 로드
+    pub fn load(dir: PathBuf, identity: Arc<SigningIdentity>) -> Self {
+        let settings: AuditSettings = std::fs::read(dir.join(AUDIT_SETTINGS_FILE))
+            .ok()
+            .and_then(|data| serde_json::from_slice(&data).ok())
+            .unwrap_or_default();
+
+        let head = match last_entry(&dir.join(AUDIT_LOG_FILE)) {
+            Ok(Some(entry)) => ChainHead {
+                next_seq: entry.seq + 1,
+                last_hash: entry.hash().unwrap_or_else(|_| GENESIS_HASH.to_string()),
+            },
+            Ok(None) => ChainHead {
+                next_seq: 1,
+                last_hash: GENESIS_HASH.to_string(),
+            },
+            Err(e) => {
+                warn!(
+                    "감사 로그 끝 항목을 읽지 못했습니다 (새 사슬로 이어감): {}",
+                    e
+                );
+                ChainHead {
+                    next_seq: 1,
+                    last_hash: GENESIS_HASH.to_string(),
+                }
+            }
+        };
+        if settings.enabled {
+            info!("🧾 전송 감사 로그 사용 (다음 항목 #{})", head.next_seq);
+        }
+
+        Self {
+            dir: Some(dir),
+            identity,
+            enabled: AtomicBool::new(settings.enabled),
+            subjects: Mutex::new(HashMap::new()),
+            head: Mutex::new(head),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// 사용 여부 변경 후 저장
+    pub fn set_enabled(&self, enabled: bool) -> Result<()> {
+        let dir = self
+            .dir
+            .as_ref()
+            .ok_or_else(|| anyhow!("감사 로그를 저장할 폴더가 없습니다"))?;
+        std::fs::create_dir_all(dir)?;
+        std::fs::write(
+            dir.join(AUDIT_SETTINGS_FILE),
+            serde_json::to_vec_pretty(&AuditSettings { enabled })?,
+        )?;
+        self.enabled.store(enabled, Ordering::Relaxed);
+        info!("🧾 전송 감사 로그 {}", if enabled { "켬" } else { "끔" });
+        Ok(())
+    }
+
+    /// 작업의 방향/경로 기록 (레지스트리 `finish` 전에 호출)
+    pub fn describe(&self, job_id: &str, subject: AuditSubject) {
+        if self.is_enabled() {
+            self.subjects.lock().insert(job_id.to_string(), subject);
+        }
+    }
+
+    /// 레지스트리 수명주기 이벤트 처리 (끝난 작업만 기록, 해시 계산은 백그라운드)
+    pub fn on_event(self: &Arc<Self>, event: &str, snapshot: &TransferJobSnapshot) {
+        if event != EVENT_COMPLETED && event != EVENT_FAILED {
+            return;
+        }
+        let subject = self.subjects.lock().remove(&snapshot.job_id);
+        if !self.is_enabled() {
+            return;
+        }
+
+        let log = self.clone();
+        let snapshot = snapshot.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = log.record(snapshot, subject).await {
+                warn!("감사 로그 기록 실패: {}", e);
+            }
+        });
+    }
+
+    /// 끝난 작업 한 건을 서명해 덧붙임
+    async fn record(
+        &self,
+        snapshot: TransferJobSnapshot,
+        subject: Option<AuditSubject>,
+    ) -> Result<AuditEntry> {
+        let path = subject.as_ref().and_then(|s| s.path.clone());
+        let sha256 = match &path {
+            Some(path) if snapshot.status == JobStatus::Completed && path.is_file() => {
+                let path = path.clone();
+                Some(tokio::task::spawn_blocking(move || sha256_file(&path)).await??)
+            }
+            _ => None,
+        };
+
+        let mut entry = AuditEntry {
+            seq: 0,
+            timestamp: now_secs(),
+            node_id: self.identity.node_id(),
+            job_id: snapshot.job_id,
+            direction: subject.as_ref().map(|s| s.direction),
+            kind: snapshot.kind,
+            peer_id: snapshot.peer_id,
+            peer_fingerprint: subject.and_then(|s| s.peer_fingerprint),
+            file_name: path
+                .as_ref()
+                .and_then(|p| p.file_name())
+                .map(|name| name.to_string_lossy().to_string()),
+            bytes: snapshot.bytes_transferred,
+            sha256,
+            status: snapshot.status,
+            error: snapshot.error.map(|e| e.to_string()),
+            prev_hash: String::new(),
+            signature: String::new(),
+        };
+
+        let mut head = self.head.lock();
+        entry.seq = head.next_seq;
+        entry.prev_hash = head.last_hash.clone();
+        entry.signature = self.identity.sign(&entry.signing_bytes()?);
+
+        if let Some(dir) = &self.dir {
+            let mut line = serde_json::to_vec(&entry)?;
+            line.push(b'\n');
+            let mut file = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(dir.join(AUDIT_LOG_FILE))?;
+            file.write_all(&line)?;
+            file.sync_data()?;
+        }
+
+        head.next_seq += 1;
+        head.last_hash = entry.hash()?;
+        Ok(entry)
+    }
+
+    /// 최근 항목 (오래된 순, 최대 `limit`개)
+    pub fn recent(&self, limit: usize) -> Result<Vec<AuditEntry>> {
+        let Some(dir) = &self.dir else {
+            return Ok(Vec::new());
+        };
+        let data = match std::fs::read_to_string(dir.join(AUDIT_LOG_FILE)) {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let lines: Vec<&str> = data.lines().filter(|l| !l.trim().is_empty()).collect();
+        lines[lines.len().saturating_sub(limit)..]
+            .iter()
+            .map(|line| Ok(serde_json::from_str(line)?))
+            .collect()
+    }
+
+    /// 로그를 `dest`로 내보내고 내보낸 파일을 검증
+    pub async fn export(&self, dest: PathBuf) -> Result<AuditVerification> {
+        let source = self
+            .dir
+            .as_ref()
+            .map(|dir| dir.join(AUDIT_LOG_FILE))
+            .filter(|path| path.exists())
+            .ok_or_else(|| anyhow!("기록된 감사 로그가 없습니다"))?;
+
+        // 기록 중인 항목이 반쯤 복사되지 않도록 사슬을 잠근 채 복사
+        let data = {
+            let _head = self.head.lock();
+            std::fs::read(&source)?
+        };
+        let tmp = dest.with_extension("tmp");
+        tokio::fs::write(&tmp, &data).await?;
+        tokio::fs::rename(&tmp, &dest).await?;
+        info!("🧾 감사 로그 내보내기: {:?}", dest);
+
+        Ok(verify_log(&data))
+    }
+}
+
+/// 감사 로그 내용 검증 (일련번호, 해시 사슬, 항목별 서명)
+pub fn verify_log(data: &[u8]) -> AuditVerification {
+    let mut result = AuditVerification {
+        valid: true,
+        entries: 0,
+        node_ids: Vec::new(),
+        first_invalid_line: None,
+        error: None,
+    };
+    let mut expected_seq = 1;
+    let mut last_hash = GENESIS_HASH.to_string();
+
+    let text = String::from_utf8_lossy(data);
+    for (index, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let checked = (|| -> Result<String> {
+            let entry: AuditEntry = serde_json::from_str(line)?;
+            if entry.seq != expected_seq {
+                return Err(anyhow!(
+                    "일련번호 불일치: {} (기대 {})",
+                    entry.seq,
+                    expected_seq
+                ));
+            }
+            if entry.prev_hash != last_hash {
+                return Err(anyhow!("해시 사슬이 끊어졌습니다"));
+            }
+            mailbox::verify(&entry.node_id, &entry.signing_bytes()?, &entry.signature)?;
+            if !result.node_ids.contains(&entry.node_id) {
+                result.node_ids.push(entry.node_id.clone());
+            }
+            entry.hash()
+        })();
+
+        match checked {
+            Ok(hash) => {
+                last_hash = hash;
+                expected_seq += 1;
+                result.entries += 1;
+            }
+            Err(e) => {
+                result.valid = false;
+                result.first_invalid_line = Some(index as u64 + 1);
+                result.error = Some(e.to_string());
+                break;
+            }
+        }
+    }
+    result
+}
+
+/// 기존 로그의 마지막 항목
+fn last_entry(path: &Path) -> Result<Option<AuditEntry>> {
+    let data = match std::fs::read_to_string(path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    match data.lines().rev().find(|l| !l.trim().is_empty()) {
+        Some(line) => Ok(Some(serde_json::from_str(line)?)),
+        None => Ok(None),
+    }
+}
+
+fn sha256_file(path: &Path) -> Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 1024 * 1024];
+    loop {
+        let n = file.read(&mut buffer)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(job_id: &str, status: JobStatus) -> TransferJobSnapshot {
+        TransferJobSnapshot {
+            job_id: job_id.to_string(),
+            peer_id: "peer-a".to_string(),
+            kind: TransferKind::File,
+            status,
+            progress: 100.0,
+            bytes_transferred: 5,
+            total_bytes: 5,
+            speed_bps: 0,
+            error: None,
+            action: None,
+            elapsed_secs: 1.0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_signed_chain_detects_tampering() {
+        let dir = std::env::temp_dir().join(format!("pswp-audit-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("report.txt");
+        std::fs::write(&file, b"hello").unwrap();
+
+        let identity = Arc::new(SigningIdentity::generate());
+        let log = AuditLog::load(dir.clone(), identity.clone());
+        log.set_enabled(true).unwrap();
+
+        let subject = AuditSubject {
+            direction: AuditDirection::Send,
+            peer_fingerprint: Some("ab".repeat(32)),
+            path: Some(file.clone()),
+        };
+        let first = log
+            .record(snapshot("job-1", JobStatus::Completed), Some(subject))
+            .await
+            .unwrap();
+        assert_eq!(first.seq, 1);
+        assert_eq!(first.file_name.as_deref(), Some("report.txt"));
+        assert_eq!(
+            first.sha256.as_deref(),
+            Some("2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824")
+        );
+        log.record(snapshot("job-2", JobStatus::Failed), None)
+            .await
+            .unwrap();
+
+        // 재시작 후에도 사슬을 이어감
+        let reloaded = AuditLog::load(dir.clone(), identity.clone());
+        assert!(reloaded.is_enabled());
+        let third = reloaded
+            .record(snapshot("job-3", JobStatus::Completed), None)
+            .await
+            .unwrap();
+        assert_eq!(third.seq, 3);
+
+        let exported = dir.join("export.jsonl");
+        let verification = reloaded.export(exported.clone()).await.unwrap();
+        assert!(verification.valid, "{:?}", verification.error);
+        assert_eq!(verification.entries, 3);
+        assert_eq!(verification.node_ids, vec![identity.node_id()]);
+        assert_eq!(reloaded.recent(2).unwrap().len(), 2);
+
+        // 항목을 고치거나 빼면 검증 실패
+        let data = std::fs::read_to_string(&exported).unwrap();
+        let tampered = data.replacen("\"bytes\":5", "\"bytes\":6", 1);
+        let verification = verify_log(tampered.as_bytes());
+        assert!(!verification.valid);
+        assert_eq!(verification.first_invalid_line, Some(1));
+
+        let removed: String = data
+            .lines()
+            .enumerate()
+            .filter(|(i, _)| *i != 1)
+            .map(|(_, l)| format!("{}\n", l))
+            .collect();
+        let verification = verify_log(removed.as_bytes());
+        assert!(!verification.valid);
+        assert_eq!(verification.entries, 1);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
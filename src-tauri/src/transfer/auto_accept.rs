@@ -0,0 +1,384 @@
+//! 신뢰하는 피어 자동 수락 규칙
+//!
+//! "지문 X에서 오는 전송은 ~/Drops로, 10GB까지 자동 수락"처럼 규칙을 두면
+//! 빌드 서버 등 반복 전송에서 승인 창 없이 바로 수락합니다.
+//! 규칙은 앱 데이터 디렉토리에 저장되며, 규칙이 적용된(또는 한도 초과로 적용되지 않은) 요청은
+//! 로그로 남습니다.
+//! 🆕 지문 대신 연락처를 지정하면 판단할 때마다 연락처의 현재 지문을 사용합니다.
+
+use crate::contacts::ContactBook;
+use crate::protocol::commands::TransferRequest;
+use crate::quic::identity::normalize_fingerprint;
+use crate::util::now_secs;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// 규칙 파일명
+pub const AUTO_ACCEPT_FILE: &str = "auto_accept_rules.json";
+
+/// 메모리에 유지할 자동 수락 로그 수
+const LOG_CAPACITY: usize = 200;
+
+/// 자동 수락 규칙
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoAcceptRule {
+    pub id: String,
+    /// 보낸 기기의 인증서 지문 (SHA-256 hex, 연락처를 지정하면 비워 둠)
+    #[serde(default)]
+    pub fingerprint: String,
+    /// 🆕 보낸 기기의 연락처 ID (지문 대신 사용)
+    #[serde(default)]
+    pub contact_id: Option<String>,
+    #[serde(default)]
+    pub label: Option<String>,
+    /// 저장 폴더
+    pub save_dir: String,
+    /// 자동 수락할 최대 크기 (None = 제한 없음)
+    #[serde(default)]
+    pub max_bytes: Option<u64>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// 자동 수락 판단 기록
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoAcceptLogEntry {
+    pub job_id: String,
+    pub file_name: String,
+    pub file_size: u64,
+    pub fingerprint: String,
+    pub rule_id: String,
+    pub accepted: bool,
+    pub reason: String,
+    pub timestamp: u64,
+}
+
+/// 자동 수락 규칙 목록 (모든 전송 요청 공유)
+pub struct AutoAcceptRules {
+    path: Option<PathBuf>,
+    rules: Mutex<Vec<AutoAcceptRule>>,
+    log: Mutex<VecDeque<AutoAcceptLogEntry>>,
+    /// 🆕 연락처를 지정한 규칙의 지문 조회
+    contacts: Option<Arc<ContactBook>>,
+}
+
+impl AutoAcceptRules {
+    /// 저장하지 않는 메모리 전용 목록
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            rules: Mutex::new(Vec::new()),
+            log: Mutex::new(VecDeque::new()),
+            contacts: None,
+        }
+    }
+
+    /// 파일에서 로드 (없거나 손상되었으면 빈 목록)
+    pub fn load(path: PathBuf) -> Self {
+        let rules = match std::fs::read(&path) {
+            Ok(data) => match serde_json::from_slice::<Vec<AutoAcceptRule>>(&data) {
+                Ok(rules) => rules,
+                Err(e) => {
+                    warn!("자동 수락 규칙 파싱 실패, 초기화: {}", e);
+                    Vec::new()
+                }
+            },
+            Err(_) => Vec::new(),
+        };
+
+        if !rules.is_empty() {
+            info!("🤝 자동 수락 규칙 로드: {}개", rules.len());
+        }
+
+        Self {
+            path: Some(path),
+            rules: Mutex::new(rules),
+            log: Mutex::new(VecDeque::new()),
+            contacts: None,
+        }
+    }
+
+    /// 🆕 연락처 목록 연결 (연락처를 지정한 규칙 사용)
+    pub fn with_contacts(mut self, contacts: Arc<ContactBook>) -> Self {
+        self.contacts = Some(contacts);
+        self
+    }
+
+    pub fn list(&self) -> Vec<AutoAcceptRule> {
+        self.rules.lock().clone()
+    }
+
+    /// 규칙 추가 또는 같은 ID 규칙 교체 후 저장
+    pub fn upsert(&self, mut rule: AutoAcceptRule) -> Result<AutoAcceptRule, String> {
+        rule.contact_id = rule.contact_id.filter(|id| !id.is_empty());
+        if let Some(ref contact_id) = rule.contact_id {
+            let contact = self
+                .contacts
+                .as_ref()
+                .and_then(|contacts| contacts.get(contact_id))
+                .ok_or_else(|| format!("연락처를 찾을 수 없습니다: {}", contact_id))?;
+            if contact.fingerprint.is_none() {
+                return Err("인증서 지문이 없는 연락처입니다".to_string());
+            }
+            rule.fingerprint = String::new();
+        } else {
+            rule.fingerprint = normalize_fingerprint(&rule.fingerprint);
+            if rule.fingerprint.len() != 64 {
+                return Err("지문은 SHA-256 (64자리 hex)이어야 합니다".to_string());
+            }
+        }
+        if rule.save_dir.trim().is_empty() {
+            return Err("저장 폴더가 비어 있습니다".to_string());
+        }
+        if rule.id.is_empty() {
+            rule.id = uuid::Uuid::new_v4().to_string();
+        }
+
+        {
+            let mut rules = self.rules.lock();
+            match rules.iter_mut().find(|r| r.id == rule.id) {
+                Some(existing) => *existing = rule.clone(),
+                None => rules.push(rule.clone()),
+            }
+        }
+        info!(
+            "🤝 자동 수락 규칙 저장: {} ({} -> {})",
+            rule.id, rule.fingerprint, rule.save_dir
+        );
+        self.persist();
+        Ok(rule)
+    }
+
+    /// 규칙 삭제 후 저장
+    pub fn remove(&self, id: &str) -> bool {
+        let removed = {
+            let mut rules = self.rules.lock();
+            let before = rules.len();
+            rules.retain(|r| r.id != id);
+            rules.len() != before
+        };
+        if removed {
+            self.persist();
+        }
+        removed
+    }
+
+    /// 전송 요청에 적용할 규칙 (지문이 같고 한도 이내인 활성 규칙)
+    ///
+    /// 지문이 맞는 규칙이 있으면 수락 여부와 관계없이 로그를 남깁니다.
+    pub fn evaluate(&self, request: &TransferRequest) -> Option<AutoAcceptRule> {
+        let fingerprint = normalize_fingerprint(request.sender_fingerprint.as_deref()?);
+        let candidates: Vec<AutoAcceptRule> = self
+            .rules
+            .lock()
+            .iter()
+            .filter(|r| {
+                r.enabled && self.rule_fingerprint(r).as_deref() == Some(fingerprint.as_str())
+            })
+            .cloned()
+            .collect();
+        let first = candidates.first()?.clone();
+
+        let matched = candidates
+            .into_iter()
+            .find(|r| !r.max_bytes.is_some_and(|max| request.file_size > max));
+        let (rule, accepted, reason) = match matched {
+            Some(rule) => (rule, true, "자동 수락".to_string()),
+            None => (
+                first,
+                false,
+                format!(
+                    "크기 한도 초과 ({} bytes), 수동 승인 필요",
+                    request.file_size
+                ),
+            ),
+        };
+
+        if accepted {
+            info!(
+                "🤝 전송 자동 수락: {} ({} bytes, 규칙 {}) -> {}",
+                request.file_name, request.file_size, rule.id, rule.save_dir
+            );
+        } else {
+            warn!(
+                "🤝 자동 수락 거부: {} (규칙 {}): {}",
+                request.file_name, rule.id, reason
+            );
+        }
+        self.push_log(AutoAcceptLogEntry {
+            job_id: request.job_id.clone(),
+            file_name: request.file_name.clone(),
+            file_size: request.file_size,
+            fingerprint,
+            rule_id: rule.id.clone(),
+            accepted,
+            reason,
+            timestamp: now_secs(),
+        });
+
+        accepted.then_some(rule)
+    }
+
+    /// 규칙이 가리키는 지문 (연락처를 지정했으면 연락처의 현재 지문)
+    fn rule_fingerprint(&self, rule: &AutoAcceptRule) -> Option<String> {
+        match rule.contact_id {
+            Some(ref contact_id) => self.contacts.as_ref()?.get(contact_id)?.fingerprint,
+            None => Some(rule.fingerprint.clone()),
+        }
+    }
+
+    /// 최근 자동 수락 판단 기록 (오래된 순)
+    pub fn log(&self) -> Vec<AutoAcceptLogEntry> {
+        self.log.lock().iter().cloned().collect()
+    }
+
+    fn push_log(&self, entry: AutoAcceptLogEntry) {
+        let mut log = self.log.lock();
+        if log.len() >= LOG_CAPACITY {
+            log.pop_front();
+        }
+        log.push_back(entry);
+    }
+
+    fn persist(&self) {
+        let Some(ref path) = self.path else {
+            return;
+        };
+        let rules = self.list();
+
+        let result = (|| -> anyhow::Result<()> {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            let tmp = path.with_extension("json.tmp");
+            std::fs::write(&tmp, serde_json::to_vec_pretty(&rules)?)?;
+            std::fs::rename(&tmp, path)?;
+            Ok(())
+        })();
+
+        if let Err(e) = result {
+            warn!("자동 수락 규칙 저장 실패: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(fingerprint: Option<&str>, file_size: u64) -> TransferRequest {
+        TransferRequest {
+            job_id: "job".to_string(),
+            file_name: "build.zip".to_string(),
+            file_size,
+            sender_name: "ci".to_string(),
+            sender_device: "linux".to_string(),
+            timestamp: 0,
+            sender_fingerprint: fingerprint.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_evaluate_rules() {
+        let fingerprint = "ab".repeat(32);
+        let rules = AutoAcceptRules::in_memory();
+        assert!(rules
+            .upsert(AutoAcceptRule {
+                id: String::new(),
+                fingerprint: "xyz".to_string(),
+                contact_id: None,
+                label: None,
+                save_dir: "/drops".to_string(),
+                max_bytes: None,
+                enabled: true,
+            })
+            .is_err());
+
+        let rule = rules
+            .upsert(AutoAcceptRule {
+                id: String::new(),
+                fingerprint: fingerprint.to_uppercase(),
+                contact_id: None,
+                label: Some("build server".to_string()),
+                save_dir: "/drops".to_string(),
+                max_bytes: Some(10),
+                enabled: true,
+            })
+            .unwrap();
+        assert_eq!(rule.fingerprint, fingerprint);
+
+        // 한도 이내만 자동 수락, 지문이 없거나 다르면 판단하지 않음
+        assert!(rules.evaluate(&request(Some(&fingerprint), 10)).is_some());
+        assert!(rules.evaluate(&request(Some(&fingerprint), 11)).is_none());
+        assert!(rules
+            .evaluate(&request(Some(&"cd".repeat(32)), 1))
+            .is_none());
+        assert!(rules.evaluate(&request(None, 1)).is_none());
+
+        let log = rules.log();
+        assert_eq!(log.len(), 2);
+        assert!(log[0].accepted);
+        assert!(!log[1].accepted);
+
+        assert!(rules.remove(&rule.id));
+        assert!(rules.evaluate(&request(Some(&fingerprint), 1)).is_none());
+    }
+
+    #[test]
+    fn test_rule_referencing_contact() {
+        let contacts = Arc::new(ContactBook::in_memory());
+        let rules = AutoAcceptRules::in_memory().with_contacts(contacts.clone());
+        let rule = |contact_id: &str| AutoAcceptRule {
+            id: String::new(),
+            fingerprint: String::new(),
+            contact_id: Some(contact_id.to_string()),
+            label: None,
+            save_dir: "/drops".to_string(),
+            max_bytes: None,
+            enabled: true,
+        };
+        assert!(rules.upsert(rule("missing")).is_err());
+
+        let contact = contacts
+            .upsert(crate::contacts::Contact {
+                id: String::new(),
+                nickname: "laptop".to_string(),
+                node_id: None,
+                fingerprint: Some("ab".repeat(32)),
+                addresses: Vec::new(),
+                last_seen: None,
+                added_at: 0,
+                trusted: false,
+                verified_at: None,
+            })
+            .unwrap();
+        rules.upsert(rule(&contact.id)).unwrap();
+        assert!(rules
+            .evaluate(&request(Some(&"ab".repeat(32)), 1))
+            .is_some());
+
+        // 연락처의 지문이 바뀌면 규칙도 새 지문을 따름
+        contacts
+            .upsert(crate::contacts::Contact {
+                fingerprint: Some("cd".repeat(32)),
+                ..contact
+            })
+            .unwrap();
+        assert!(rules
+            .evaluate(&request(Some(&"ab".repeat(32)), 1))
+            .is_none());
+        assert!(rules
+            .evaluate(&request(Some(&"cd".repeat(32)), 1))
+            .is_some());
+    }
+}
@@ -0,0 +1,303 @@
+//! 피어 간 대역폭 측정
+//!
+//! 대용량 전송 전에 LAN 케이블/스위치 상태를 확인할 수 있도록, 기존 QUIC 연결로 생성한 데이터를
+//! 정해진 시간 동안 보내 처리량과 RTT를 잽니다. 디스크는 읽지도 쓰지도 않으므로 순수 네트워크 성능입니다.
+//!
+//! 업로드는 제어 스트림의 `BenchmarkData`로 직접 보내고, 다운로드는 `BenchmarkRequest`로 상대에게
+//! 같은 방식으로 보내 달라고 요청합니다.
+
+use super::control_stream::{self, IncomingStream};
+use crate::protocol::Command;
+use anyhow::{anyhow, Result};
+use bytes::Bytes;
+use parking_lot::Mutex;
+use rand::RngCore;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::time::{Duration, Instant};
+use tokio::sync::oneshot;
+use tracing::{info, warn};
+
+/// 방향별 기본 측정 시간
+pub const DEFAULT_DURATION: Duration = Duration::from_secs(5);
+
+/// 방향별 최대 측정 시간 (상대가 요청한 값도 이 값으로 제한)
+pub const MAX_DURATION: Duration = Duration::from_secs(60);
+
+/// 한 번에 쓰는 생성 데이터 크기
+const CHUNK_SIZE: usize = 1024 * 1024;
+
+/// 부하 중 RTT 샘플 간격
+const RTT_SAMPLE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 측정 시간 외에 응답/ACK를 기다리는 여유 시간
+const GRACE: Duration = Duration::from_secs(15);
+
+/// 응답을 받지 못한 데이터 스트림을 중단할 때의 QUIC 에러 코드
+const STOP_UNKNOWN: u32 = 1;
+
+/// RTT 통계 (밀리초)
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RttStats {
+    pub min_ms: f64,
+    pub avg_ms: f64,
+    pub max_ms: f64,
+    pub samples: usize,
+}
+
+impl RttStats {
+    fn from_samples(samples: &[Duration]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        let ms: Vec<f64> = samples
+            .iter()
+            .map(|rtt| rtt.as_secs_f64() * 1000.0)
+            .collect();
+        Self {
+            min_ms: ms.iter().copied().fold(f64::INFINITY, f64::min),
+            avg_ms: ms.iter().sum::<f64>() / ms.len() as f64,
+            max_ms: ms.iter().copied().fold(0.0, f64::max),
+            samples: ms.len(),
+        }
+    }
+}
+
+/// 한 방향의 측정 결과
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirectionResult {
+    pub bytes: u64,
+    pub duration_ms: u64,
+    pub throughput_mbps: f64,
+    /// 부하 중 RTT
+    pub rtt: RttStats,
+}
+
+impl DirectionResult {
+    fn new(bytes: u64, elapsed: Duration, rtt: RttStats) -> Self {
+        let secs = elapsed.as_secs_f64().max(f64::EPSILON);
+        Self {
+            bytes,
+            duration_ms: elapsed.as_millis() as u64,
+            throughput_mbps: bytes as f64 * 8.0 / secs / 1_000_000.0,
+            rtt,
+        }
+    }
+}
+
+/// 양방향 측정 결과
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchmarkReport {
+    pub peer_id: String,
+    /// 측정 시작 전(유휴) RTT
+    pub idle_rtt_ms: f64,
+    pub upload: DirectionResult,
+    pub download: DirectionResult,
+}
+
+/// 진행 중인 다운로드 측정 (상대가 보낼 데이터 스트림 대기)
+#[derive(Default)]
+pub struct BenchmarkManager {
+    pending: Mutex<HashMap<String, oneshot::Sender<quinn::RecvStream>>>,
+}
+
+impl BenchmarkManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 업로드 후 다운로드를 차례로 측정
+    pub async fn run(
+        &self,
+        conn: &quinn::Connection,
+        peer_id: &str,
+        duration: Duration,
+    ) -> Result<BenchmarkReport> {
+        let duration = duration.min(MAX_DURATION);
+        let idle_rtt_ms = conn.rtt().as_secs_f64() * 1000.0;
+        info!("📏 대역폭 측정 시작: {} (방향별 {:?})", peer_id, duration);
+
+        let (upload, samples) = sample_rtt(conn, async {
+            tokio::time::timeout(
+                duration + GRACE,
+                send_generated(conn, uuid::Uuid::new_v4().to_string(), duration),
+            )
+            .await
+            .map_err(|_| anyhow!("업로드 측정 시간 초과"))?
+        })
+        .await;
+        let (bytes, elapsed) = upload?;
+        let upload = DirectionResult::new(bytes, elapsed, RttStats::from_samples(&samples));
+
+        let (download, samples) = sample_rtt(conn, self.download(conn, duration)).await;
+        let (bytes, elapsed) = download?;
+        let download = DirectionResult::new(bytes, elapsed, RttStats::from_samples(&samples));
+
+        info!(
+            "📏 대역폭 측정 완료: {} ↑ {:.1} Mbps ↓ {:.1} Mbps",
+            peer_id, upload.throughput_mbps, download.throughput_mbps
+        );
+        Ok(BenchmarkReport {
+            peer_id: peer_id.to_string(),
+            idle_rtt_ms,
+            upload,
+            download,
+        })
+    }
+
+    /// 상대에게 데이터를 요청해 받음
+    async fn download(
+        &self,
+        conn: &quinn::Connection,
+        duration: Duration,
+    ) -> Result<(u64, Duration)> {
+        let session_id = uuid::Uuid::new_v4().to_string();
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().insert(session_id.clone(), tx);
+
+        let result = async {
+            let mut send = control_stream::open(
+                conn,
+                &Command::BenchmarkRequest {
+                    session_id: session_id.clone(),
+                    duration_ms: duration.as_millis() as u64,
+                },
+            )
+            .await?;
+            send.finish()?;
+
+            tokio::time::timeout(duration + GRACE, async {
+                let mut recv = rx
+                    .await
+                    .map_err(|_| anyhow!("측정 데이터 스트림 대기 취소"))?;
+                receive_all(&mut recv).await
+            })
+            .await
+            .map_err(|_| anyhow!("다운로드 측정 시간 초과 (상대가 측정을 지원하지 않을 수 있음)"))?
+        }
+        .await;
+
+        self.pending.lock().remove(&session_id);
+        result
+    }
+
+    /// 제어 스트림으로 받은 측정 명령 처리
+    pub async fn handle_incoming(&self, conn: quinn::Connection, incoming: IncomingStream) {
+        let IncomingStream {
+            peer_id,
+            command,
+            mut recv,
+        } = incoming;
+
+        match command {
+            Command::BenchmarkRequest {
+                session_id,
+                duration_ms,
+            } => {
+                let _ = control_stream::finish_header_only(&mut recv).await;
+                let duration = Duration::from_millis(duration_ms).min(MAX_DURATION);
+                info!("📏 대역폭 측정 요청: {} ({:?})", peer_id, duration);
+                if let Err(e) = send_generated(&conn, session_id, duration).await {
+                    warn!("측정 데이터 전송 실패 ({}): {}", peer_id, e);
+                }
+            }
+            Command::BenchmarkData { session_id } => {
+                // 요청한 다운로드면 대기 중인 측정으로 넘기고, 아니면 상대의 업로드이므로 읽고 버림
+                if let Some(tx) = self.pending.lock().remove(&session_id) {
+                    let _ = tx.send(recv);
+                    return;
+                }
+                if let Err(e) = receive_all(&mut recv).await {
+                    warn!("측정 데이터 수신 실패 ({}): {}", peer_id, e);
+                    let _ = recv.stop(STOP_UNKNOWN.into());
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// 측정용 데이터 (압축/중복 제거에 영향받지 않도록 난수)
+fn generated_chunk() -> Bytes {
+    let mut data = vec![0u8; CHUNK_SIZE];
+    rand::thread_rng().fill_bytes(&mut data);
+    Bytes::from(data)
+}
+
+/// `duration` 동안 생성 데이터를 보내고, 상대가 모두 받을 때까지의 바이트 수와 시간
+async fn send_generated(
+    conn: &quinn::Connection,
+    session_id: String,
+    duration: Duration,
+) -> Result<(u64, Duration)> {
+    let mut send = control_stream::open(conn, &Command::BenchmarkData { session_id }).await?;
+    let chunk = generated_chunk();
+
+    let started = Instant::now();
+    let mut bytes = 0u64;
+    while started.elapsed() < duration {
+        send.write_chunk(chunk.clone()).await?;
+        bytes += chunk.len() as u64;
+    }
+    send.finish()?;
+    // 마지막 데이터까지 ACK를 받아야 실제로 전달된 처리량
+    if let Some(code) = send.stopped().await? {
+        return Err(anyhow!("상대가 측정 스트림을 중단함 (코드 {})", code));
+    }
+    Ok((bytes, started.elapsed()))
+}
+
+/// 스트림 끝까지 읽은 바이트 수와 첫 데이터부터의 시간
+async fn receive_all(recv: &mut quinn::RecvStream) -> Result<(u64, Duration)> {
+    let mut bytes = 0u64;
+    let mut started = None;
+    while let Some(chunk) = recv.read_chunk(CHUNK_SIZE, false).await? {
+        started.get_or_insert_with(Instant::now);
+        bytes += chunk.bytes.len() as u64;
+    }
+    Ok((bytes, started.map(|s| s.elapsed()).unwrap_or_default()))
+}
+
+/// `future`가 끝날 때까지 연결 RTT를 주기적으로 기록
+async fn sample_rtt<T>(
+    conn: &quinn::Connection,
+    future: impl Future<Output = T>,
+) -> (T, Vec<Duration>) {
+    let mut samples = Vec::new();
+    let mut ticker = tokio::time::interval(RTT_SAMPLE_INTERVAL);
+    tokio::pin!(future);
+    loop {
+        tokio::select! {
+            output = &mut future => return (output, samples),
+            _ = ticker.tick() => samples.push(conn.rtt()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let rtt = RttStats::from_samples(&[
+            Duration::from_millis(1),
+            Duration::from_millis(3),
+            Duration::from_millis(2),
+        ]);
+        assert_eq!(rtt.samples, 3);
+        assert!((rtt.min_ms - 1.0).abs() < 1e-9);
+        assert!((rtt.avg_ms - 2.0).abs() < 1e-9);
+        assert!((rtt.max_ms - 3.0).abs() < 1e-9);
+        assert_eq!(RttStats::from_samples(&[]).samples, 0);
+
+        // 125MB를 1초에 = 1000 Mbps
+        let result = DirectionResult::new(125_000_000, Duration::from_secs(1), RttStats::default());
+        assert!((result.throughput_mbps - 1000.0).abs() < 1e-6);
+        assert_eq!(result.duration_ms, 1000);
+    }
+}
@@ -0,0 +1,218 @@
+//! 멀티스트림 블록 버퍼 풀
+//!
+//! 블록(최대 16MB)마다 새 `Vec<u8>`를 할당하면 동시 스트림이 많을 때
+//! 할당기 경합과 새 페이지의 page fault가 누적됩니다.
+//! 전송 하나당 페이지 정렬 버퍼 풀을 만들어 송신/수신 태스크가 lock-free 큐로 돌려 씁니다.
+//! 앱 전체 [`MemoryBudget`]이 주어지면 새 버퍼는 예산을 예약한 뒤에만 할당합니다.
+
+use super::memory_budget::{BudgetPermit, MemoryBudget};
+use crossbeam_queue::ArrayQueue;
+use std::alloc::{alloc, dealloc, handle_alloc_error, Layout};
+use std::ops::{Deref, DerefMut};
+use std::ptr::NonNull;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tracing::debug;
+
+/// 버퍼 정렬 단위 (페이지 / 비버퍼 I/O 섹터 크기)
+pub const BUFFER_ALIGN: usize = 4096;
+
+/// 페이지 정렬 고정 크기 버퍼
+struct AlignedBuf {
+    ptr: NonNull<u8>,
+    layout: Layout,
+    /// 버퍼가 살아 있는 동안 잡고 있는 예산
+    _budget: Option<BudgetPermit>,
+}
+
+// 버퍼는 단일 소유자만 접근 (풀 큐 또는 PooledBuffer)
+unsafe impl Send for AlignedBuf {}
+unsafe impl Sync for AlignedBuf {}
+
+impl AlignedBuf {
+    fn new(capacity: usize, budget: Option<BudgetPermit>) -> Self {
+        let layout = Layout::from_size_align(capacity.max(1), BUFFER_ALIGN).expect("버퍼 레이아웃");
+        let ptr =
+            NonNull::new(unsafe { alloc(layout) }).unwrap_or_else(|| handle_alloc_error(layout));
+        Self {
+            ptr,
+            layout,
+            _budget: budget,
+        }
+    }
+
+    fn capacity(&self) -> usize {
+        self.layout.size()
+    }
+}
+
+impl Drop for AlignedBuf {
+    fn drop(&mut self) {
+        unsafe { dealloc(self.ptr.as_ptr(), self.layout) };
+    }
+}
+
+/// 블록 버퍼 풀
+pub struct BlockBufferPool {
+    free: ArrayQueue<AlignedBuf>,
+    buffer_size: usize,
+    /// 새 버퍼 할당 전에 예약할 앱 전체 예산
+    budget: Option<Arc<MemoryBudget>>,
+    /// 풀에서 꺼내 재사용한 횟수
+    reused: AtomicU64,
+    /// 새로 할당한 횟수
+    allocated: AtomicU64,
+}
+
+impl BlockBufferPool {
+    /// `capacity`: 보관할 최대 버퍼 수 (보통 동시 스트림 수), `buffer_size`: 블록 크기,
+    /// `budget`: 새 버퍼를 할당하기 전에 예약할 앱 전체 예산 (None이면 제한 없음)
+    pub fn new(
+        capacity: usize,
+        buffer_size: usize,
+        budget: Option<Arc<MemoryBudget>>,
+    ) -> Arc<Self> {
+        debug!(
+            "🔧 블록 버퍼 풀 생성: 최대 {} 버퍼 x {} KB",
+            capacity,
+            buffer_size / 1024
+        );
+        Arc::new(Self {
+            free: ArrayQueue::new(capacity.max(1)),
+            buffer_size,
+            budget,
+            reused: AtomicU64::new(0),
+            allocated: AtomicU64::new(0),
+        })
+    }
+
+    /// `len` 바이트 버퍼 획득 (이전에 쓰던 데이터가 남아 있을 수 있음)
+    ///
+    /// 블록 크기보다 큰 요청은 풀을 거치지 않고 따로 할당합니다.
+    /// 새로 할당해야 하는데 예산이 모자라면 다른 버퍼가 반환될 때까지 기다립니다.
+    pub async fn acquire(self: &Arc<Self>, len: usize) -> PooledBuffer {
+        let buf = if len <= self.buffer_size {
+            match self.free.pop() {
+                Some(buf) => {
+                    self.reused.fetch_add(1, Ordering::Relaxed);
+                    buf
+                }
+                None => self.allocate(self.buffer_size).await,
+            }
+        } else {
+            self.allocate(len).await
+        };
+
+        PooledBuffer {
+            buf: Some(buf),
+            len,
+            pool: self.clone(),
+        }
+    }
+
+    /// 예산을 예약한 뒤 새 버퍼 할당
+    async fn allocate(&self, capacity: usize) -> AlignedBuf {
+        let permit = match &self.budget {
+            Some(budget) => Some(budget.reserve(capacity).await),
+            None => None,
+        };
+        self.allocated.fetch_add(1, Ordering::Relaxed);
+        Self::zeroed(capacity, permit)
+    }
+
+    /// 새 버퍼는 한 번 0으로 채워 초기화된 메모리만 노출
+    fn zeroed(capacity: usize, budget: Option<BudgetPermit>) -> AlignedBuf {
+        let buf = AlignedBuf::new(capacity, budget);
+        unsafe { std::ptr::write_bytes(buf.ptr.as_ptr(), 0, buf.capacity()) };
+        buf
+    }
+
+    fn release(&self, buf: AlignedBuf) {
+        // 블록 크기 버퍼만 보관, 풀이 가득 차면 해제
+        if buf.capacity() == self.buffer_size {
+            let _ = self.free.push(buf);
+        }
+    }
+
+    /// (재사용 횟수, 새 할당 횟수)
+    pub fn stats(&self) -> (u64, u64) {
+        (
+            self.reused.load(Ordering::Relaxed),
+            self.allocated.load(Ordering::Relaxed),
+        )
+    }
+}
+
+/// 풀에서 빌린 버퍼 (drop 시 풀로 반환)
+pub struct PooledBuffer {
+    buf: Option<AlignedBuf>,
+    len: usize,
+    pool: Arc<BlockBufferPool>,
+}
+
+impl Deref for PooledBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        let buf = self.buf.as_ref().expect("반환된 버퍼");
+        unsafe { std::slice::from_raw_parts(buf.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl DerefMut for PooledBuffer {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        let buf = self.buf.as_mut().expect("반환된 버퍼");
+        unsafe { std::slice::from_raw_parts_mut(buf.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl Drop for PooledBuffer {
+    fn drop(&mut self) {
+        if let Some(buf) = self.buf.take() {
+            self.pool.release(buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_buffers_are_reused_and_aligned() {
+        let pool = BlockBufferPool::new(2, 64 * 1024, None);
+
+        let mut first = pool.acquire(1000).await;
+        assert_eq!(first.len(), 1000);
+        assert_eq!(first.as_ptr() as usize % BUFFER_ALIGN, 0);
+        first[999] = 7;
+        let ptr = first.as_ptr();
+        drop(first);
+
+        let second = pool.acquire(64 * 1024).await;
+        assert_eq!(second.as_ptr(), ptr);
+        assert_eq!(pool.stats(), (1, 1));
+
+        // 블록보다 큰 요청은 풀에 보관하지 않음
+        drop(pool.acquire(128 * 1024).await);
+        drop(second);
+        assert_eq!(pool.free.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_pooled_buffers_hold_budget() {
+        let budget = MemoryBudget::new(256 * 1024);
+        let pool = BlockBufferPool::new(1, 64 * 1024, Some(budget.clone()));
+
+        let first = pool.acquire(1000).await;
+        let second = pool.acquire(1000).await;
+        assert_eq!(budget.status().used_bytes, 128 * 1024);
+
+        // 풀에 보관된 버퍼는 예산을 유지하고, 풀이 가득 차서 버린 버퍼는 반환
+        drop(first);
+        drop(second);
+        assert_eq!(budget.status().used_bytes, 64 * 1024);
+        drop(pool);
+        assert_eq!(budget.status().used_bytes, 0);
+    }
+}
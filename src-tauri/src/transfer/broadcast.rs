@@ -0,0 +1,254 @@
+//! 여러 피어에게 동시 전송 (1:N 브로드캐스트)
+//!
+//! 파일마다 리더(mmap/io_uring 링)를 한 번만 열어 모든 대상의 멀티스트림 전송이 공유합니다.
+//! 대상별로 레지스트리 작업(`<job_id>@<peer_id>`)을 따로 두어 진행률/취소를 각각 다루고,
+//! 한 대상이 실패해도 나머지 대상의 전송은 계속됩니다.
+
+use super::memory_budget::MemoryBudget;
+use super::multistream::{MultiStreamProgress, MultiStreamSender};
+use super::registry::{JobControl, TransferKind, TransferRegistry};
+use crate::error::AppError;
+use futures::future::join_all;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+/// 대상별 전송 결과
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DestinationResult {
+    pub peer_id: String,
+    pub job_id: String,
+    pub bytes_sent: u64,
+    pub error: Option<AppError>,
+}
+
+/// 대상별 레지스트리 작업 ID
+pub fn destination_job_id(job_id: &str, peer_id: &str) -> String {
+    format!("{}@{}", job_id, peer_id)
+}
+
+/// 전송 중인 대상 하나
+struct Destination {
+    peer_id: String,
+    job_id: String,
+    conn: quinn::Connection,
+    control: Option<JobControl>,
+    /// 앞선 파일까지 보낸 바이트 (진행률 누적용)
+    bytes_sent: u64,
+    error: Option<AppError>,
+}
+
+/// 여러 피어에게 같은 파일 목록을 보내는 송신기
+pub struct BroadcastSender {
+    destinations: Vec<(String, quinn::Connection)>,
+    registry: Arc<TransferRegistry>,
+    /// 고정 블록 크기 (None이면 파일마다 자동 결정)
+    block_size: Option<usize>,
+    max_concurrent: usize,
+    memory_budget: Option<Arc<MemoryBudget>>,
+}
+
+impl BroadcastSender {
+    pub fn new(
+        destinations: Vec<(String, quinn::Connection)>,
+        registry: Arc<TransferRegistry>,
+    ) -> Self {
+        Self {
+            destinations,
+            registry,
+            block_size: None,
+            max_concurrent: 96,
+            memory_budget: None,
+        }
+    }
+
+    pub fn with_block_size(mut self, size: usize) -> Self {
+        self.block_size = Some(size);
+        self
+    }
+
+    /// 대상 하나당 동시 스트림 상한
+    pub fn with_max_concurrent(mut self, count: usize) -> Self {
+        self.max_concurrent = count;
+        self
+    }
+
+    /// 모든 대상의 블록 버퍼를 앱 전체 메모리 예산 안에서 할당
+    pub fn with_memory_budget(mut self, budget: Arc<MemoryBudget>) -> Self {
+        self.memory_budget = Some(budget);
+        self
+    }
+
+    /// 파일들을 순서대로, 각 파일은 모든 대상에게 동시에 전송
+    pub async fn send_files(self, paths: Vec<PathBuf>, job_id: &str) -> Vec<DestinationResult> {
+        let total_bytes: u64 = paths
+            .iter()
+            .filter_map(|path| std::fs::metadata(path).ok())
+            .map(|metadata| metadata.len())
+            .sum();
+
+        let mut destinations: Vec<Destination> = self
+            .destinations
+            .iter()
+            .map(|(peer_id, conn)| self.start_destination(job_id, peer_id, conn))
+            .collect();
+
+        info!(
+            "📡 브로드캐스트 전송 시작: 파일 {}개 ({} bytes) -> 피어 {}개",
+            paths.len(),
+            total_bytes,
+            destinations.len()
+        );
+
+        for path in paths {
+            let active: Vec<&mut Destination> = destinations
+                .iter_mut()
+                .filter(|destination| destination.error.is_none())
+                .collect();
+            if active.is_empty() {
+                break;
+            }
+
+            // 리더는 파일당 한 번만 열어 모든 대상이 공유
+            let opener = self.multistream(active[0].conn.clone());
+            let reader = match opener.open_reader(&path) {
+                Ok(reader) => reader,
+                Err(e) => {
+                    let error = AppError::Io(format!("파일 열기 실패 ({:?}): {}", path, e));
+                    for destination in active {
+                        destination.error = Some(error.clone());
+                    }
+                    break;
+                }
+            };
+            let file_size = reader.file_size();
+
+            let sends = active.into_iter().map(|destination| {
+                let mut sender = self
+                    .multistream(destination.conn.clone())
+                    .with_progress_channel(self.forward_progress(
+                        &destination.job_id,
+                        destination.bytes_sent,
+                        total_bytes,
+                    ));
+                if let Some(control) = &destination.control {
+                    sender = sender.with_job_control(control.clone());
+                }
+                let path = path.clone();
+                let reader = reader.clone();
+                async move {
+                    let result = sender
+                        .send_file_with_reader(path, reader, &destination.job_id)
+                        .await;
+                    match result {
+                        Ok(_) => destination.bytes_sent += file_size,
+                        Err(e) => {
+                            warn!("브로드캐스트 대상 실패 ({}): {}", destination.peer_id, e);
+                            destination.error =
+                                Some(AppError::Network(format!("멀티스트림 전송 실패: {}", e)));
+                        }
+                    }
+                }
+            });
+            join_all(sends).await;
+        }
+
+        destinations
+            .into_iter()
+            .map(|destination| {
+                let result = match &destination.error {
+                    Some(e) => Err(e.clone()),
+                    None => Ok(()),
+                };
+                // 등록에 실패한 대상(같은 작업 ID가 진행 중)은 기존 작업을 건드리지 않음
+                if destination.control.is_some() {
+                    self.registry.finish(&destination.job_id, result);
+                }
+                DestinationResult {
+                    peer_id: destination.peer_id,
+                    job_id: destination.job_id,
+                    bytes_sent: destination.bytes_sent,
+                    error: destination.error,
+                }
+            })
+            .collect()
+    }
+
+    /// 블록 크기/스트림 상한을 적용한 멀티스트림 송신기
+    fn multistream(&self, conn: quinn::Connection) -> MultiStreamSender {
+        let mut sender = MultiStreamSender::new(conn).with_max_concurrent(self.max_concurrent);
+        if let Some(size) = self.block_size {
+            sender = sender.with_block_size(size);
+        }
+        if let Some(budget) = &self.memory_budget {
+            sender = sender.with_memory_budget(budget.clone());
+        }
+        sender
+    }
+
+    /// 대상 작업 등록 (같은 작업 ID가 진행 중이면 실패한 대상으로 시작)
+    fn start_destination(
+        &self,
+        job_id: &str,
+        peer_id: &str,
+        conn: &quinn::Connection,
+    ) -> Destination {
+        let job_id = destination_job_id(job_id, peer_id);
+        let registered = self
+            .registry
+            .register(&job_id, peer_id, TransferKind::Multistream);
+        let (control, error) = match registered {
+            Ok(control) => (Some(control), None),
+            Err(e) => (None, Some(e)),
+        };
+
+        Destination {
+            peer_id: peer_id.to_string(),
+            job_id,
+            conn: conn.clone(),
+            control,
+            bytes_sent: 0,
+            error,
+        }
+    }
+
+    /// 파일 하나의 진행률을 대상 작업에 반영 (앞선 파일까지 보낸 `base_bytes`를 더함)
+    fn forward_progress(
+        &self,
+        job_id: &str,
+        base_bytes: u64,
+        total_bytes: u64,
+    ) -> mpsc::Sender<MultiStreamProgress> {
+        let (tx, mut rx) = mpsc::channel::<MultiStreamProgress>(100);
+        let registry = self.registry.clone();
+        let job_id = job_id.to_string();
+        tauri::async_runtime::spawn(async move {
+            while let Some(progress) = rx.recv().await {
+                registry.update_progress(
+                    &job_id,
+                    base_bytes + progress.acknowledged_bytes,
+                    total_bytes,
+                    progress.speed_bps,
+                );
+            }
+        });
+        tx
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_destination_job_id() {
+        assert_eq!(destination_job_id("job-1", "peer-a"), "job-1@peer-a");
+        assert_ne!(
+            destination_job_id("job-1", "peer-a"),
+            destination_job_id("job-1", "peer-b")
+        );
+    }
+}
@@ -0,0 +1,98 @@
+//! 단방향(uni) 제어 스트림
+//!
+//! 스트림 앞부분은 길이(u32 BE) + `Command` JSON 헤더이고, 나머지는 명령별 페이로드(파일 내용 등)입니다.
+//! 파일 전송 엔진들은 양방향(bi) 스트림만 `accept_bi`로 가져가므로, 텍스트 공유나 폴더 동기화처럼
+//! 전송과 무관한 메시지는 이 스트림으로 보내 서로 간섭하지 않습니다.
+
+use crate::protocol::Command;
+use anyhow::{anyhow, Result};
+use quinn::{RecvStream, SendStream};
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+/// 헤더 최대 크기 (텍스트 메시지가 헤더에 실리므로 여유 있게)
+pub const MAX_HEADER_BYTES: usize = 4 * 1024 * 1024;
+
+/// 헤더를 읽은 수신 스트림 (페이로드는 `recv`에 남아 있음)
+pub struct IncomingStream {
+    pub peer_id: String,
+    pub command: Command,
+    pub recv: RecvStream,
+}
+
+/// 단방향 스트림을 열고 헤더 전송
+pub async fn open(conn: &quinn::Connection, command: &Command) -> Result<SendStream> {
+    let header = command.to_bytes()?;
+    if header.len() > MAX_HEADER_BYTES {
+        return Err(anyhow!("제어 헤더가 너무 큽니다: {} bytes", header.len()));
+    }
+
+    let mut send = conn.open_uni().await?;
+    send.write_all(&(header.len() as u32).to_be_bytes()).await?;
+    send.write_all(&header).await?;
+    Ok(send)
+}
+
+/// 헤더 읽기
+pub async fn read_header(recv: &mut RecvStream) -> Result<Command> {
+    let mut len_buf = [0u8; 4];
+    recv.read_exact(&mut len_buf).await?;
+    let len = u32::from_be_bytes(len_buf) as usize;
+    if len > MAX_HEADER_BYTES {
+        return Err(anyhow!("제어 헤더가 너무 큽니다: {} bytes", len));
+    }
+
+    let mut header = vec![0u8; len];
+    recv.read_exact(&mut header).await?;
+    Command::from_bytes(&header)
+}
+
+/// 헤더만 있는 스트림을 끝까지 읽음
+///
+/// 끝까지 읽지 않고 drop하면 중단(STOP_SENDING)으로 전달되어 송신 측이 실패로 볼 수 있습니다.
+pub async fn finish_header_only(recv: &mut RecvStream) -> Result<()> {
+    recv.read_to_end(0).await?;
+    Ok(())
+}
+
+/// 연결의 단방향 스트림을 받아 헤더를 읽은 뒤 채널로 전달 (연결이 끊기면 종료)
+pub async fn accept_streams(
+    conn: quinn::Connection,
+    peer_id: String,
+    tx: mpsc::Sender<IncomingStream>,
+) {
+    loop {
+        let mut recv = match conn.accept_uni().await {
+            Ok(recv) => recv,
+            Err(e) => {
+                info!("제어 스트림 수신 종료 ({}): {}", peer_id, e);
+                break;
+            }
+        };
+
+        // 헤더가 느리게 오는 스트림이 다음 스트림 수락을 막지 않도록 분리
+        let peer_id = peer_id.clone();
+        let sender = tx.clone();
+        tokio::spawn(async move {
+            match read_header(&mut recv).await {
+                Ok(command) => {
+                    let _ = sender
+                        .send(IncomingStream {
+                            peer_id,
+                            command,
+                            recv,
+                        })
+                        .await;
+                }
+                Err(e) => {
+                    warn!("제어 헤더 읽기 오류 ({}): {}", peer_id, e);
+                    let _ = recv.stop(0u32.into());
+                }
+            }
+        });
+
+        if tx.is_closed() {
+            break;
+        }
+    }
+}
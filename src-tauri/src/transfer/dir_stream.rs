@@ -0,0 +1,1055 @@
+//! 스트리밍 매니페스트 폴더 전송
+//!
+//! 파일이 수백만 개인 폴더는 전체 목록(`scan_folder`)이나 Zip을 먼저 만들면 전송 시작까지 오래 걸리고
+//! 목록만으로도 메모리를 많이 씁니다. 송신 측은 폴더를 걸으며 찾은 항목을 묶음으로 제어 스트림에 보내고,
+//! 묶음에 든 파일은 곧바로 파일별 스트림으로 전송합니다. 양쪽 모두 아직 전송하지 않은 항목만
+//! 들고 있으므로 메모리는 폴더 크기와 무관합니다.
+//!
+//! - 제어 스트림: `DIRS`(암호화 시 `DIRE`) + job_id + 루트 이름 → `READY`,
+//!   이후 항목 묶음 메시지들과 종료 메시지(파일 수/총 크기) → 모든 파일 저장 후 `DONE`
+//! - 파일 스트림: `DFIL` + 항목 번호(u64) + 데이터 → 저장 후 `FDON`
+
+use super::file_attrs::FileAttributes;
+use super::file_transfer::{safe_destination, SymlinkPolicy};
+use super::memory_budget::MemoryBudget;
+use super::part_file;
+use super::path_filter::PathFilter;
+use super::payload_crypto::{self, PayloadCipher};
+use super::registry::JobControl;
+use super::zip_stream::{create_symlink, is_contained_link};
+use super::{TransferProgress, TransferState};
+use anyhow::{anyhow, Result};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::{mpsc, Notify};
+use tokio::task::JoinSet;
+use tracing::{debug, info, warn};
+
+/// 묶음 하나의 최대 항목 수
+const BATCH_ENTRIES: usize = 1000;
+
+/// 묶음이 덜 찼어도 이 시간이 지나면 전송 (첫 파일이 바로 출발하도록)
+const BATCH_INTERVAL: Duration = Duration::from_millis(200);
+
+/// 매니페스트가 파일 전송보다 앞서 나갈 수 있는 최대 파일 수 (수신 측 대기 항목 상한)
+const MAX_PENDING_FILES: usize = 4096;
+
+/// 동시에 전송하는 파일 수 기본값
+pub const DEFAULT_CONCURRENT_FILES: usize = 16;
+
+/// 파일 데이터 읽기/쓰기 단위 (암호화 시 프레임 하나)
+const CHUNK_SIZE: usize = 1024 * 1024;
+
+/// 진행률 보고 간격
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+
+/// 매니페스트 항목 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EntryKind {
+    File,
+    Dir,
+    Symlink,
+}
+
+/// 매니페스트 항목
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreamEntry {
+    /// 전송 안에서의 항목 번호 (파일 스트림이 이 번호로 항목을 가리킴)
+    pub index: u64,
+    /// 루트 기준 상대 경로 (`/` 구분)
+    pub path: String,
+    pub kind: EntryKind,
+    #[serde(default)]
+    pub size: u64,
+    /// 심볼릭 링크 대상 (`SymlinkPolicy::Recreate`)
+    #[serde(default)]
+    pub symlink_target: Option<String>,
+    #[serde(default)]
+    pub attributes: Option<FileAttributes>,
+}
+
+/// 제어 스트림 메시지
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum ManifestMessage {
+    Entries { entries: Vec<StreamEntry> },
+    End { files: u64, bytes: u64 },
+}
+
+/// 전송 결과
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirStreamSummary {
+    /// 송신: 보낸 폴더, 수신: 저장한 폴더
+    pub root: String,
+    pub files: u64,
+    pub bytes: u64,
+}
+
+/// 폴더를 깊이 우선으로 걸으며 찾은 항목마다 `emit(항목, 로컬 경로)` 호출 (false를 돌려주면 중단)
+///
+/// 목록을 모으지 않으므로 메모리는 아직 방문하지 않은 하위 폴더 수에만 비례합니다.
+/// 숨김 항목 제외, 심볼릭 링크 처리와 포함/제외 필터는 `scan_folder`와 같습니다.
+pub fn walk_directory(
+    root: &Path,
+    policy: SymlinkPolicy,
+    filter: &PathFilter,
+    mut emit: impl FnMut(StreamEntry, PathBuf) -> bool,
+) {
+    let mut next_index = 0u64;
+    let mut entry =
+        |path: String, kind: EntryKind, size: u64, target: Option<String>, local: PathBuf| {
+            let attributes = match kind {
+                EntryKind::File => FileAttributes::read(&local).ok(),
+                _ => None,
+            };
+            let entry = StreamEntry {
+                index: next_index,
+                path,
+                kind,
+                size,
+                symlink_target: target,
+                attributes,
+            };
+            next_index += 1;
+            emit(entry, local)
+        };
+
+    let mut visited = HashSet::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        // 링크를 따라가다 이미 방문한 폴더로 돌아오면 건너뜀 (순환 링크)
+        if let Ok(canonical) = fs::canonicalize(&dir) {
+            if !visited.insert(canonical) {
+                warn!("순환 심볼릭 링크 건너뜀: {:?}", dir);
+                continue;
+            }
+        }
+
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                warn!("폴더 읽기 실패 ({:?}): {}", dir, e);
+                continue;
+            }
+        };
+        for child in entries.flatten() {
+            let local = child.path();
+            let file_name = child.file_name().to_string_lossy().to_string();
+            if file_name.starts_with('.') {
+                continue;
+            }
+            let relative = local
+                .strip_prefix(root)
+                .map(|p| p.to_string_lossy().replace('\\', "/"))
+                .unwrap_or(file_name);
+
+            if child.file_type().is_ok_and(|t| t.is_symlink()) {
+                match policy {
+                    SymlinkPolicy::Skip => continue,
+                    SymlinkPolicy::Recreate => {
+                        if !filter.allows_file(&relative) {
+                            continue;
+                        }
+                        let Ok(target) = fs::read_link(&local) else {
+                            continue;
+                        };
+                        let target = target.to_string_lossy().replace('\\', "/");
+                        if !entry(relative, EntryKind::Symlink, 0, Some(target), local) {
+                            return;
+                        }
+                        continue;
+                    }
+                    SymlinkPolicy::Follow => {}
+                }
+            }
+
+            // 링크는 대상 기준 (끊어진 링크는 건너뜀)
+            let Ok(metadata) = fs::metadata(&local) else {
+                continue;
+            };
+            let keep_going = if metadata.is_dir() {
+                if !filter.allows_dir(&relative) {
+                    continue;
+                }
+                pending.push(local.clone());
+                entry(relative, EntryKind::Dir, 0, None, local)
+            } else if metadata.is_file() && filter.allows_file(&relative) {
+                entry(relative, EntryKind::File, metadata.len(), None, local)
+            } else {
+                true
+            };
+            if !keep_going {
+                return;
+            }
+        }
+    }
+}
+
+/// 스트리밍 매니페스트 폴더 송신기
+pub struct DirStreamSender {
+    conn: quinn::Connection,
+    symlink_policy: SymlinkPolicy,
+    /// 폴더를 걷는 동안 적용하는 포함/제외 필터 (보낼 때 폴더의 `.pswpignore`가 더해짐)
+    filter: PathFilter,
+    max_concurrent_files: usize,
+    /// 레지스트리 작업 제어 (취소/일시정지)
+    job_control: Option<JobControl>,
+    progress_tx: Option<mpsc::Sender<TransferProgress>>,
+    /// 페이로드 암호화 (페어링 키, 매니페스트와 파일 데이터 모두)
+    cipher: Option<Arc<PayloadCipher>>,
+    /// 파일 버퍼를 예약할 앱 전체 메모리 예산
+    memory_budget: Option<Arc<MemoryBudget>>,
+}
+
+impl DirStreamSender {
+    pub fn new(conn: quinn::Connection) -> Self {
+        Self {
+            conn,
+            symlink_policy: SymlinkPolicy::default(),
+            filter: PathFilter::default(),
+            max_concurrent_files: DEFAULT_CONCURRENT_FILES,
+            job_control: None,
+            progress_tx: None,
+            cipher: None,
+            memory_budget: None,
+        }
+    }
+
+    pub fn with_symlink_policy(mut self, policy: SymlinkPolicy) -> Self {
+        self.symlink_policy = policy;
+        self
+    }
+
+    pub fn with_filter(mut self, filter: PathFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// 동시에 전송하는 파일 수
+    pub fn with_max_concurrent_files(mut self, count: usize) -> Self {
+        self.max_concurrent_files = count.max(1);
+        self
+    }
+
+    pub fn with_job_control(mut self, control: JobControl) -> Self {
+        self.job_control = Some(control);
+        self
+    }
+
+    pub fn with_progress_channel(mut self, tx: mpsc::Sender<TransferProgress>) -> Self {
+        self.progress_tx = Some(tx);
+        self
+    }
+
+    pub fn with_cipher(mut self, cipher: Option<Arc<PayloadCipher>>) -> Self {
+        self.cipher = cipher;
+        self
+    }
+
+    pub fn with_memory_budget(mut self, budget: Arc<MemoryBudget>) -> Self {
+        self.memory_budget = Some(budget);
+        self
+    }
+
+    /// 폴더 전송 (폴더를 걷는 동안 이미 찾은 파일부터 전송)
+    pub async fn send_directory(&self, root: PathBuf, job_id: &str) -> Result<DirStreamSummary> {
+        if !root.is_dir() {
+            return Err(anyhow!("폴더가 아닙니다: {:?}", root));
+        }
+        let root_name = root
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "folder".to_string());
+
+        info!("📂 스트리밍 폴더 전송 시작: {:?}", root);
+
+        let (mut control_send, mut control_recv) = self.conn.open_bi().await?;
+        let marker = if self.cipher.is_some() {
+            b"DIRE"
+        } else {
+            b"DIRS"
+        };
+        control_send.write_all(marker).await?;
+        write_string(&mut control_send, job_id).await?;
+        write_string(&mut control_send, &root_name).await?;
+
+        let mut ready = [0u8; 5];
+        control_recv.read_exact(&mut ready).await?;
+        if &ready != b"READY" {
+            return Err(anyhow!("Receiver not ready for directory stream"));
+        }
+
+        // 폴더 걷기는 블로킹 I/O이므로 별도 스레드에서 (채널이 차면 멈춤)
+        let (entry_tx, entry_rx) = mpsc::channel::<(StreamEntry, PathBuf)>(BATCH_ENTRIES);
+        let (walk_root, policy, filter) = (root.clone(), self.symlink_policy, self.filter.clone());
+        tokio::task::spawn_blocking(move || {
+            let filter = filter.with_ignore_file(&walk_root);
+            walk_directory(&walk_root, policy, &filter, |entry, local| {
+                entry_tx.blocking_send((entry, local)).is_ok()
+            })
+        });
+
+        let counters = Arc::new(ProgressCounters::default());
+        let context = Arc::new(FileSendContext {
+            conn: self.conn.clone(),
+            job_id: job_id.to_string(),
+            cipher: self.cipher.clone(),
+            job_control: self.job_control.clone(),
+            memory_budget: self.memory_budget.clone(),
+            counters: counters.clone(),
+        });
+        let (file_tx, file_rx) = mpsc::channel::<(u64, PathBuf)>(MAX_PENDING_FILES);
+        let dispatcher = tokio::spawn(dispatch_files(context, file_rx, self.max_concurrent_files));
+        let reporter = spawn_progress_reporter(job_id, self.progress_tx.clone(), counters.clone());
+
+        let manifest = self
+            .send_manifest(&mut control_send, entry_rx, file_tx, job_id, &counters)
+            .await;
+        // 매니페스트가 실패했으면 파일 전송도 중단 (파일 전송 실패가 원인이면 그 에러를 반환)
+        let dispatched = match &manifest {
+            Err(_) if !dispatcher.is_finished() => {
+                dispatcher.abort();
+                Ok(())
+            }
+            _ => dispatcher
+                .await
+                .map_err(|e| anyhow!("파일 전송 태스크 실패: {}", e))
+                .and_then(|result| result),
+        };
+        reporter.abort();
+
+        let (files, bytes) = match (manifest, dispatched) {
+            (_, Err(e)) | (Err(e), _) => return Err(e),
+            (Ok(totals), Ok(())) => totals,
+        };
+
+        let mut done = [0u8; 4];
+        control_recv.read_exact(&mut done).await?;
+        if &done != b"DONE" {
+            return Err(anyhow!("수신 측이 폴더 전송을 완료하지 못했습니다"));
+        }
+        report_progress(job_id, &self.progress_tx, &counters, Instant::now()).await;
+
+        info!(
+            "✅ 스트리밍 폴더 전송 완료: 파일 {}개, {} bytes",
+            files, bytes
+        );
+        Ok(DirStreamSummary {
+            root: root.to_string_lossy().to_string(),
+            files,
+            bytes,
+        })
+    }
+
+    /// 찾은 항목을 묶어 보내고, 묶음에 든 파일을 전송 대기열에 넣음 (파일 수, 총 크기 반환)
+    async fn send_manifest(
+        &self,
+        control_send: &mut quinn::SendStream,
+        mut entry_rx: mpsc::Receiver<(StreamEntry, PathBuf)>,
+        file_tx: mpsc::Sender<(u64, PathBuf)>,
+        job_id: &str,
+        counters: &ProgressCounters,
+    ) -> Result<(u64, u64)> {
+        let (mut files, mut bytes, mut sequence) = (0u64, 0u64, 0u64);
+
+        while let Some(first) = entry_rx.recv().await {
+            let mut batch = vec![first];
+            let deadline = tokio::time::Instant::now() + BATCH_INTERVAL;
+            while batch.len() < BATCH_ENTRIES {
+                match tokio::time::timeout_at(deadline, entry_rx.recv()).await {
+                    Ok(Some(item)) => batch.push(item),
+                    _ => break,
+                }
+            }
+            if let Some(control) = &self.job_control {
+                control.checkpoint().await?;
+            }
+
+            let entries = batch.iter().map(|(entry, _)| entry.clone()).collect();
+            let message = ManifestMessage::Entries { entries };
+            write_message(
+                control_send,
+                self.cipher.as_deref(),
+                job_id,
+                sequence,
+                &message,
+            )
+            .await?;
+            sequence += 1;
+            debug!("📂 매니페스트 묶음 전송: 항목 {}개", batch.len());
+
+            for (entry, local) in batch {
+                if entry.kind != EntryKind::File {
+                    continue;
+                }
+                files += 1;
+                bytes += entry.size;
+                counters.total.fetch_add(entry.size, Ordering::Relaxed);
+                file_tx
+                    .send((entry.index, local))
+                    .await
+                    .map_err(|_| anyhow!("파일 전송이 중단되었습니다"))?;
+            }
+        }
+
+        let message = ManifestMessage::End { files, bytes };
+        write_message(
+            control_send,
+            self.cipher.as_deref(),
+            job_id,
+            sequence,
+            &message,
+        )
+        .await?;
+        info!("📂 매니페스트 전송 완료: 파일 {}개, {} bytes", files, bytes);
+        Ok((files, bytes))
+    }
+}
+
+/// 파일 스트림 송신에 필요한 공유 상태
+struct FileSendContext {
+    conn: quinn::Connection,
+    job_id: String,
+    cipher: Option<Arc<PayloadCipher>>,
+    job_control: Option<JobControl>,
+    memory_budget: Option<Arc<MemoryBudget>>,
+    counters: Arc<ProgressCounters>,
+}
+
+impl FileSendContext {
+    async fn send_file(&self, index: u64, local: &Path) -> Result<()> {
+        let mut file = tokio::fs::File::open(local)
+            .await
+            .map_err(|e| anyhow!("파일 열기 실패 ({:?}): {}", local, e))?;
+        let _budget = match &self.memory_budget {
+            Some(budget) => Some(budget.reserve(CHUNK_SIZE).await),
+            None => None,
+        };
+
+        let (mut send, mut recv) = self.conn.open_bi().await?;
+        send.write_all(b"DFIL").await?;
+        send.write_all(&index.to_le_bytes()).await?;
+
+        let context = file_context(&self.job_id, index);
+        let mut buffer = vec![0u8; CHUNK_SIZE];
+        let mut frames = 0u64;
+        loop {
+            if let Some(control) = &self.job_control {
+                if let Err(e) = control.checkpoint().await {
+                    let _ = send.reset(0u32.into());
+                    return Err(e);
+                }
+            }
+            let n = file.read(&mut buffer).await?;
+            if n == 0 {
+                break;
+            }
+            match &self.cipher {
+                Some(cipher) => {
+                    let aad = payload_crypto::aad(&context, frames);
+                    frames += 1;
+                    payload_crypto::write_frame(&mut send, cipher, &aad, &mut buffer[..n]).await?
+                }
+                None => send.write_all(&buffer[..n]).await?,
+            }
+            self.counters.done.fetch_add(n as u64, Ordering::Relaxed);
+        }
+        send.finish()?;
+
+        let mut ack = [0u8; 4];
+        recv.read_exact(&mut ack).await?;
+        if &ack != b"FDON" {
+            return Err(anyhow!("파일 저장 실패: {:?}", local));
+        }
+        Ok(())
+    }
+}
+
+/// 대기열의 파일을 최대 `max_concurrent`개씩 동시에 전송 (하나라도 실패하면 중단)
+async fn dispatch_files(
+    context: Arc<FileSendContext>,
+    mut file_rx: mpsc::Receiver<(u64, PathBuf)>,
+    max_concurrent: usize,
+) -> Result<()> {
+    let mut tasks = JoinSet::new();
+    loop {
+        while tasks.len() >= max_concurrent {
+            if let Some(joined) = tasks.join_next().await {
+                joined??;
+            }
+        }
+        let Some((index, local)) = file_rx.recv().await else {
+            break;
+        };
+        let context = context.clone();
+        tasks.spawn(async move { context.send_file(index, &local).await });
+    }
+    while let Some(joined) = tasks.join_next().await {
+        joined??;
+    }
+    Ok(())
+}
+
+/// 스트리밍 매니페스트 폴더 수신기
+pub struct DirStreamReceiver {
+    conn: quinn::Connection,
+    save_dir: PathBuf,
+    /// 레지스트리 작업 제어 (취소/일시정지)
+    job_control: Option<JobControl>,
+    progress_tx: Option<mpsc::Sender<TransferProgress>>,
+    /// 암호화된 전송을 풀 페어링 키
+    cipher: Option<Arc<PayloadCipher>>,
+    /// 파일 버퍼를 예약할 앱 전체 메모리 예산
+    memory_budget: Option<Arc<MemoryBudget>>,
+}
+
+impl DirStreamReceiver {
+    pub fn new(conn: quinn::Connection, save_dir: PathBuf) -> Self {
+        Self {
+            conn,
+            save_dir,
+            job_control: None,
+            progress_tx: None,
+            cipher: None,
+            memory_budget: None,
+        }
+    }
+
+    pub fn with_job_control(mut self, control: JobControl) -> Self {
+        self.job_control = Some(control);
+        self
+    }
+
+    pub fn with_progress_channel(mut self, tx: mpsc::Sender<TransferProgress>) -> Self {
+        self.progress_tx = Some(tx);
+        self
+    }
+
+    pub fn with_cipher(mut self, cipher: Option<Arc<PayloadCipher>>) -> Self {
+        self.cipher = cipher;
+        self
+    }
+
+    pub fn with_memory_budget(mut self, budget: Arc<MemoryBudget>) -> Self {
+        self.memory_budget = Some(budget);
+        self
+    }
+
+    /// 폴더 수신 (매니페스트 묶음을 받는 동안 이미 도착한 파일부터 저장)
+    pub async fn receive_directory(&self, job_id: &str) -> Result<DirStreamSummary> {
+        info!("📥 스트리밍 폴더 수신 대기: {:?}", self.save_dir);
+
+        let (mut control_send, mut control_recv) = tokio::select! {
+            accepted = self.conn.accept_bi() => accepted?,
+            _ = cancelled(&self.job_control) => return Err(anyhow!("사용자에 의해 취소됨")),
+        };
+
+        let mut marker = [0u8; 4];
+        control_recv.read_exact(&mut marker).await?;
+        let cipher = match &marker {
+            b"DIRS" => None,
+            b"DIRE" => Some(self.cipher.clone().ok_or_else(|| {
+                anyhow!("암호화된 전송이지만 이 피어와 페어링되어 있지 않습니다")
+            })?),
+            _ => return Err(anyhow!("Invalid directory stream marker")),
+        };
+        if read_string(&mut control_recv).await? != job_id {
+            return Err(anyhow!("Job ID mismatch"));
+        }
+        let root = safe_destination(&self.save_dir, &read_string(&mut control_recv).await?)?;
+        tokio::fs::create_dir_all(&root).await?;
+        control_send.write_all(b"READY").await?;
+
+        let counters = Arc::new(ProgressCounters::default());
+        let pending = Arc::new(PendingEntries::default());
+        let mut manifest = tokio::spawn(read_manifest(
+            control_recv,
+            root.clone(),
+            pending.clone(),
+            cipher.clone(),
+            job_id.to_string(),
+            counters.clone(),
+        ));
+        let reporter = spawn_progress_reporter(job_id, self.progress_tx.clone(), counters.clone());
+
+        let context = Arc::new(FileReceiveContext {
+            root: root.clone(),
+            job_id: job_id.to_string(),
+            cipher,
+            memory_budget: self.memory_budget.clone(),
+            pending,
+            counters: counters.clone(),
+        });
+
+        // 종료 메시지로 파일 수를 알고, 그만큼 저장을 마칠 때까지 파일 스트림 수락
+        let result: Result<(u64, u64)> = async {
+            let mut totals = None;
+            let mut completed = 0u64;
+            let mut handlers = JoinSet::new();
+            loop {
+                if let Some((files, bytes)) = totals {
+                    if completed == files {
+                        return Ok((files, bytes));
+                    }
+                }
+                tokio::select! {
+                    finished = &mut manifest, if totals.is_none() => {
+                        totals = Some(finished.map_err(|e| anyhow!("매니페스트 태스크 실패: {}", e))??);
+                    }
+                    Some(joined) = handlers.join_next() => {
+                        joined??;
+                        completed += 1;
+                    }
+                    accepted = self.conn.accept_bi() => {
+                        let (send, recv) = accepted?;
+                        let context = context.clone();
+                        handlers.spawn(async move { context.receive_file(send, recv).await });
+                    }
+                    _ = cancelled(&self.job_control) => {
+                        return Err(anyhow!("사용자에 의해 취소됨"));
+                    }
+                }
+            }
+        }
+        .await;
+        if result.is_err() {
+            manifest.abort();
+        }
+        reporter.abort();
+        let (files, bytes) = result?;
+
+        control_send.write_all(b"DONE").await?;
+        let _ = control_send.finish();
+        report_progress(job_id, &self.progress_tx, &counters, Instant::now()).await;
+
+        info!(
+            "✅ 스트리밍 폴더 수신 완료: {:?} (파일 {}개, {} bytes)",
+            root, files, bytes
+        );
+        Ok(DirStreamSummary {
+            root: root.to_string_lossy().to_string(),
+            files,
+            bytes,
+        })
+    }
+}
+
+/// 파일 스트림이 가리킬 항목 (매니페스트로 받았지만 아직 저장하지 않은 파일)
+#[derive(Default)]
+struct PendingEntries {
+    entries: Mutex<HashMap<u64, StreamEntry>>,
+    /// 매니페스트가 끝났거나 실패함 (이후 없는 항목은 기다리지 않음)
+    closed: AtomicBool,
+    notify: Notify,
+}
+
+impl PendingEntries {
+    fn insert(&self, entry: StreamEntry) {
+        self.entries.lock().insert(entry.index, entry);
+        self.notify.notify_waiters();
+    }
+
+    fn close(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+        self.notify.notify_waiters();
+    }
+
+    /// 항목을 꺼냄 (파일 스트림이 매니페스트 묶음보다 먼저 도착하면 대기)
+    async fn take(&self, index: u64) -> Result<StreamEntry> {
+        loop {
+            let notified = self.notify.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+
+            if let Some(entry) = self.entries.lock().remove(&index) {
+                return Ok(entry);
+            }
+            if self.closed.load(Ordering::SeqCst) {
+                return Err(anyhow!("매니페스트에 없는 파일 항목: {}", index));
+            }
+            notified.await;
+        }
+    }
+}
+
+/// 매니페스트 수신: 폴더/링크는 바로 만들고 파일은 대기 항목으로 등록 (파일 수, 총 크기 반환)
+async fn read_manifest(
+    mut recv: quinn::RecvStream,
+    root: PathBuf,
+    pending: Arc<PendingEntries>,
+    cipher: Option<Arc<PayloadCipher>>,
+    job_id: String,
+    counters: Arc<ProgressCounters>,
+) -> Result<(u64, u64)> {
+    let result = async {
+        let mut buffer = Vec::new();
+        for sequence in 0u64.. {
+            let message: ManifestMessage =
+                read_message(&mut recv, cipher.as_deref(), &job_id, sequence, &mut buffer).await?;
+            let entries = match message {
+                ManifestMessage::Entries { entries } => entries,
+                ManifestMessage::End { files, bytes } => return Ok((files, bytes)),
+            };
+            for entry in entries {
+                let dest = safe_destination(&root, &entry.path)?;
+                match entry.kind {
+                    EntryKind::Dir => tokio::fs::create_dir_all(&dest).await?,
+                    EntryKind::Symlink => create_link(&entry, &dest),
+                    EntryKind::File => {
+                        counters.total.fetch_add(entry.size, Ordering::Relaxed);
+                        pending.insert(entry);
+                    }
+                }
+            }
+        }
+        unreachable!()
+    }
+    .await;
+    pending.close();
+    result
+}
+
+/// 폴더 안에 머무는 링크만 생성 (실패는 경고만)
+fn create_link(entry: &StreamEntry, dest: &Path) {
+    let Some(target) = &entry.symlink_target else {
+        return;
+    };
+    if !is_contained_link(Path::new(&entry.path), target) {
+        warn!(
+            "폴더 밖을 가리키는 링크 건너뜀: {} -> {}",
+            entry.path, target
+        );
+        return;
+    }
+    if let Some(parent) = dest.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    if let Err(e) = create_symlink(target, dest) {
+        warn!("심볼릭 링크 생성 실패 ({:?}): {}", dest, e);
+    }
+}
+
+/// 파일 스트림 수신에 필요한 공유 상태
+struct FileReceiveContext {
+    root: PathBuf,
+    job_id: String,
+    cipher: Option<Arc<PayloadCipher>>,
+    memory_budget: Option<Arc<MemoryBudget>>,
+    pending: Arc<PendingEntries>,
+    counters: Arc<ProgressCounters>,
+}
+
+impl FileReceiveContext {
+    async fn receive_file(
+        &self,
+        mut send: quinn::SendStream,
+        mut recv: quinn::RecvStream,
+    ) -> Result<()> {
+        let mut marker = [0u8; 4];
+        recv.read_exact(&mut marker).await?;
+        if &marker != b"DFIL" {
+            return Err(anyhow!("Invalid directory file stream marker"));
+        }
+        let mut index_buf = [0u8; 8];
+        recv.read_exact(&mut index_buf).await?;
+        let index = u64::from_le_bytes(index_buf);
+
+        let entry = self.pending.take(index).await?;
+        let dest = safe_destination(&self.root, &entry.path)?;
+        if let Some(parent) = dest.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let part = part_file::part_path(&dest);
+        let _budget = match &self.memory_budget {
+            Some(budget) => Some(budget.reserve(CHUNK_SIZE).await),
+            None => None,
+        };
+
+        let written = self.write_part(&mut recv, index, &part).await;
+        let written = match written {
+            Ok(written) => written,
+            Err(e) => {
+                part_file::discard(&part);
+                return Err(e);
+            }
+        };
+        if written != entry.size {
+            warn!(
+                "파일 크기가 매니페스트와 다름 ({}): {} / {} bytes",
+                entry.path, written, entry.size
+            );
+        }
+
+        let commit_dest = dest.clone();
+        tokio::task::spawn_blocking(move || part_file::commit(&part, &commit_dest)).await??;
+        if let Some(attributes) = &entry.attributes {
+            attributes.apply_or_warn(&dest);
+        }
+
+        send.write_all(b"FDON").await?;
+        let _ = send.finish();
+        Ok(())
+    }
+
+    /// 스트림 끝까지 임시 파일에 기록 (기록한 바이트 수 반환)
+    async fn write_part(
+        &self,
+        recv: &mut quinn::RecvStream,
+        index: u64,
+        part: &Path,
+    ) -> Result<u64> {
+        let mut file = tokio::fs::File::create(part).await?;
+        let context = file_context(&self.job_id, index);
+        let mut buffer = vec![0u8; CHUNK_SIZE];
+        let mut frames = 0u64;
+        let mut written = 0u64;
+        loop {
+            let chunk = match &self.cipher {
+                Some(cipher) => {
+                    let aad = payload_crypto::aad(&context, frames);
+                    frames += 1;
+                    payload_crypto::read_frame(recv, cipher, &aad, &mut buffer).await?
+                }
+                None => recv.read(&mut buffer).await?,
+            };
+            let Some(n) = chunk.filter(|&n| n > 0) else {
+                break;
+            };
+            file.write_all(&buffer[..n]).await?;
+            written += n as u64;
+            self.counters.done.fetch_add(n as u64, Ordering::Relaxed);
+        }
+        file.flush().await?;
+        Ok(written)
+    }
+}
+
+/// 진행률 (파일 바이트 기준, 총량은 매니페스트가 진행되며 늘어남)
+#[derive(Default)]
+struct ProgressCounters {
+    done: AtomicU64,
+    total: AtomicU64,
+}
+
+fn spawn_progress_reporter(
+    job_id: &str,
+    progress_tx: Option<mpsc::Sender<TransferProgress>>,
+    counters: Arc<ProgressCounters>,
+) -> tokio::task::JoinHandle<()> {
+    let job_id = job_id.to_string();
+    tokio::spawn(async move {
+        let start = Instant::now();
+        let mut interval = tokio::time::interval(PROGRESS_INTERVAL);
+        loop {
+            interval.tick().await;
+            report_progress(&job_id, &progress_tx, &counters, start).await;
+        }
+    })
+}
+
+async fn report_progress(
+    job_id: &str,
+    progress_tx: &Option<mpsc::Sender<TransferProgress>>,
+    counters: &ProgressCounters,
+    start: Instant,
+) {
+    let Some(tx) = progress_tx else {
+        return;
+    };
+    let done = counters.done.load(Ordering::Relaxed);
+    let total = counters.total.load(Ordering::Relaxed);
+    let elapsed = start.elapsed().as_secs_f64();
+    let _ = tx
+        .send(TransferProgress {
+            job_id: job_id.to_string(),
+            bytes_transferred: done,
+            total_bytes: total,
+            progress_percent: if total > 0 {
+                (done as f64 / total as f64) * 100.0
+            } else {
+                0.0
+            },
+            speed_bps: if elapsed > 0.0 {
+                (done as f64 / elapsed) as u64
+            } else {
+                0
+            },
+            state: TransferState::Transferring,
+        })
+        .await;
+}
+
+async fn cancelled(control: &Option<JobControl>) {
+    match control {
+        Some(control) => control.cancelled().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// 파일별 암호화 프레임 AAD 문맥 (같은 작업의 다른 파일과 프레임이 섞이지 않도록)
+fn file_context(job_id: &str, index: u64) -> String {
+    format!("{}/file/{}", job_id, index)
+}
+
+/// 매니페스트 메시지 전송 (평문: 길이 + JSON, 암호화: 프레임)
+async fn write_message(
+    send: &mut quinn::SendStream,
+    cipher: Option<&PayloadCipher>,
+    job_id: &str,
+    sequence: u64,
+    message: &ManifestMessage,
+) -> Result<()> {
+    let mut data = serde_json::to_vec(message)?;
+    match cipher {
+        Some(cipher) => {
+            let aad = payload_crypto::aad(&format!("{}/manifest", job_id), sequence);
+            payload_crypto::write_frame(send, cipher, &aad, &mut data).await
+        }
+        None => {
+            send.write_all(&(data.len() as u32).to_le_bytes()).await?;
+            send.write_all(&data).await?;
+            Ok(())
+        }
+    }
+}
+
+async fn read_message(
+    recv: &mut quinn::RecvStream,
+    cipher: Option<&PayloadCipher>,
+    job_id: &str,
+    sequence: u64,
+    buffer: &mut Vec<u8>,
+) -> Result<ManifestMessage> {
+    let len = match cipher {
+        Some(cipher) => {
+            let aad = payload_crypto::aad(&format!("{}/manifest", job_id), sequence);
+            payload_crypto::read_frame(recv, cipher, &aad, buffer)
+                .await?
+                .ok_or_else(|| anyhow!("매니페스트가 끝나기 전에 스트림 종료"))?
+        }
+        None => {
+            let mut len_buf = [0u8; 4];
+            recv.read_exact(&mut len_buf).await?;
+            let len = u32::from_le_bytes(len_buf) as usize;
+            if len > payload_crypto::MAX_FRAME_LEN {
+                return Err(anyhow!("매니페스트 묶음이 너무 큽니다: {} bytes", len));
+            }
+            buffer.resize(len, 0);
+            recv.read_exact(buffer).await?;
+            len
+        }
+    };
+    Ok(serde_json::from_slice(&buffer[..len])?)
+}
+
+async fn write_string(send: &mut quinn::SendStream, value: &str) -> Result<()> {
+    send.write_all(&(value.len() as u32).to_le_bytes()).await?;
+    send.write_all(value.as_bytes()).await?;
+    Ok(())
+}
+
+async fn read_string(recv: &mut quinn::RecvStream) -> Result<String> {
+    let mut len_buf = [0u8; 4];
+    recv.read_exact(&mut len_buf).await?;
+    let len = u32::from_le_bytes(len_buf) as usize;
+    if len > 4096 {
+        return Err(anyhow!("문자열이 너무 깁니다: {} bytes", len));
+    }
+    let mut data = vec![0u8; len];
+    recv.read_exact(&mut data).await?;
+    Ok(String::from_utf8(data)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_walk_directory_emits_entries_incrementally() {
+        let dir = std::env::temp_dir().join(format!("ponswarp-dirstream-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("a/b")).unwrap();
+        fs::create_dir_all(dir.join(".git")).unwrap();
+        fs::write(dir.join("top.txt"), b"top").unwrap();
+        fs::write(dir.join("a/b/deep.bin"), vec![0u8; 10]).unwrap();
+        fs::write(dir.join(".git/config"), b"hidden").unwrap();
+
+        let mut entries = Vec::new();
+        let no_filter = PathFilter::default();
+        walk_directory(&dir, SymlinkPolicy::Follow, &no_filter, |entry, _| {
+            entries.push(entry);
+            true
+        });
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+        let listed: Vec<(&str, EntryKind, u64)> = entries
+            .iter()
+            .map(|e| (e.path.as_str(), e.kind, e.size))
+            .collect();
+        assert_eq!(
+            listed,
+            vec![
+                ("a", EntryKind::Dir, 0),
+                ("a/b", EntryKind::Dir, 0),
+                ("a/b/deep.bin", EntryKind::File, 10),
+                ("top.txt", EntryKind::File, 3),
+            ]
+        );
+        // 번호는 발견 순서대로 겹치지 않게 매겨짐
+        let mut indices: Vec<u64> = entries.iter().map(|e| e.index).collect();
+        indices.sort();
+        assert_eq!(indices, vec![0, 1, 2, 3]);
+        assert!(entries[2].attributes.is_some());
+
+        // false를 돌려주면 즉시 중단
+        let mut count = 0;
+        walk_directory(&dir, SymlinkPolicy::Follow, &no_filter, |_, _| {
+            count += 1;
+            false
+        });
+        assert_eq!(count, 1);
+
+        // 제외된 폴더는 항목도 내보내지 않고 안으로 들어가지도 않음
+        let filter = PathFilter::new(&[], &["a".to_string()]).unwrap();
+        let mut paths = Vec::new();
+        walk_directory(&dir, SymlinkPolicy::Follow, &filter, |entry, _| {
+            paths.push(entry.path);
+            true
+        });
+        assert_eq!(paths, vec!["top.txt".to_string()]);
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_pending_entries_wait_for_manifest() {
+        let pending = Arc::new(PendingEntries::default());
+        let waiter = tokio::spawn({
+            let pending = pending.clone();
+            async move { pending.take(7).await }
+        });
+        tokio::task::yield_now().await;
+
+        pending.insert(StreamEntry {
+            index: 7,
+            path: "late.txt".into(),
+            kind: EntryKind::File,
+            size: 1,
+            symlink_target: None,
+            attributes: None,
+        });
+        assert_eq!(waiter.await.unwrap().unwrap().path, "late.txt");
+
+        // 매니페스트가 끝난 뒤 없는 항목은 바로 실패
+        pending.close();
+        assert!(pending.take(8).await.is_err());
+    }
+}
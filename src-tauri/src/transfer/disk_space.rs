@@ -0,0 +1,156 @@
+//! 저장 위치의 여유 공간 조회와 수신 전 공간 확인
+//!
+//! 수신 측은 매니페스트의 총 크기에 여유분을 더한 만큼 공간이 있는지 `READY` 전에 확인하고,
+//! 모자라면 `NOSPC` 응답과 함께 현재 여유 공간을 보내 송신 측 UI가 이유를 보여줄 수 있게 합니다.
+
+use serde::Serialize;
+use std::io;
+use std::path::Path;
+
+/// 수신 측 응답: 저장 공간 부족 (뒤에 필요한 바이트, 여유 바이트가 u64 LE로 따라옴)
+pub const RESPONSE_NO_SPACE: &[u8; 5] = b"NOSPC";
+
+/// 최소 여유분 (작은 전송에도 파일 시스템 메타데이터/임시 파일 몫을 남김)
+const MIN_SAFETY_MARGIN: u64 = 64 * 1024 * 1024;
+
+/// 저장 공간 부족
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, thiserror::Error)]
+#[serde(rename_all = "camelCase")]
+#[error("저장 공간이 부족합니다 (필요 {required} bytes, 여유 {available} bytes)")]
+pub struct InsufficientSpace {
+    /// 여유분을 포함한 필요 바이트
+    pub required: u64,
+    pub available: u64,
+}
+
+/// 전송 크기에 여유분(1%, 최소 64MB)을 더한 필요 공간
+pub fn required_with_margin(size: u64) -> u64 {
+    size.saturating_add((size / 100).max(MIN_SAFETY_MARGIN))
+}
+
+/// 경로가 있는 볼륨의 여유 공간 (아직 없는 경로는 가장 가까운 상위 폴더 기준)
+pub fn available_space(path: &Path) -> io::Result<u64> {
+    volume_space(path).map(|(available, _)| available)
+}
+
+/// 🆕 경로가 있는 볼륨의 (여유, 전체) 바이트
+pub fn volume_space(path: &Path) -> io::Result<(u64, u64)> {
+    let existing = path
+        .ancestors()
+        .find(|p| p.exists())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "존재하는 상위 경로가 없습니다"))?;
+    platform_space(existing)
+}
+
+/// `size`를 받을 공간이 있는지 확인 (여유 공간을 알 수 없으면 통과)
+pub fn check_space(path: &Path, size: u64) -> Result<(), InsufficientSpace> {
+    let required = required_with_margin(size);
+    match available_space(path) {
+        Ok(available) if available < required => Err(InsufficientSpace {
+            required,
+            available,
+        }),
+        Ok(_) => Ok(()),
+        Err(e) => {
+            tracing::warn!("여유 공간 조회 실패 ({:?}): {}", path, e);
+            Ok(())
+        }
+    }
+}
+
+/// 공간 부족 응답 쓰기
+pub async fn write_no_space(
+    send: &mut quinn::SendStream,
+    error: &InsufficientSpace,
+) -> anyhow::Result<()> {
+    send.write_all(RESPONSE_NO_SPACE).await?;
+    send.write_all(&error.required.to_le_bytes()).await?;
+    send.write_all(&error.available.to_le_bytes()).await?;
+    Ok(())
+}
+
+/// `NOSPC` 뒤의 필요/여유 바이트 읽기
+pub async fn read_no_space(recv: &mut quinn::RecvStream) -> anyhow::Result<InsufficientSpace> {
+    let mut buf = [0u8; 16];
+    recv.read_exact(&mut buf).await?;
+    Ok(InsufficientSpace {
+        required: u64::from_le_bytes(buf[..8].try_into()?),
+        available: u64::from_le_bytes(buf[8..].try_into()?),
+    })
+}
+
+#[cfg(unix)]
+fn platform_space(path: &Path) -> io::Result<(u64, u64)> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: c_path는 NUL로 끝나는 유효한 문자열이고 stat은 쓰기 가능한 구조체
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // 일반 사용자가 쓸 수 있는 블록 (root 예약분 제외)
+    #[allow(clippy::unnecessary_cast)]
+    Ok((
+        stat.f_bavail as u64 * stat.f_frsize as u64,
+        stat.f_blocks as u64 * stat.f_frsize as u64,
+    ))
+}
+
+#[cfg(windows)]
+fn platform_space(path: &Path) -> io::Result<(u64, u64)> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows_sys::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+
+    let wide: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+    let mut available: u64 = 0;
+    let mut total: u64 = 0;
+    // SAFETY: wide는 NUL로 끝나고, 출력 포인터는 유효하거나 null (null 허용)
+    let ok = unsafe {
+        GetDiskFreeSpaceExW(
+            wide.as_ptr(),
+            &mut available,
+            &mut total,
+            std::ptr::null_mut(),
+        )
+    };
+    if ok == 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok((available, total))
+}
+
+#[cfg(not(any(unix, windows)))]
+fn platform_space(_path: &Path) -> io::Result<(u64, u64)> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "여유 공간 조회를 지원하지 않는 플랫폼",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_required_with_margin() {
+        assert_eq!(required_with_margin(0), MIN_SAFETY_MARGIN);
+        let big = 1_000_000_000_000u64;
+        assert_eq!(required_with_margin(big), big + big / 100);
+        assert_eq!(required_with_margin(u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn test_check_space_uses_existing_ancestor() {
+        let missing = std::env::temp_dir().join("ponswarp-no-such-dir/a/b");
+        assert!(available_space(&missing).unwrap() > 0);
+        let (available, total) = volume_space(&missing).unwrap();
+        assert!(available <= total);
+        assert!(check_space(&missing, 0).is_ok());
+
+        let error = check_space(&missing, u64::MAX / 2).unwrap_err();
+        assert!(error.available < error.required);
+    }
+}
@@ -0,0 +1,134 @@
+//! 전송 전 크기 미리 계산 (dry-run)
+//!
+//! 선택한 파일/폴더를 실제 전송과 같은 규칙(숨김 항목, 심볼릭 링크 정책, 포함/제외 필터,
+//! `.pswpignore`)으로 걸으며 파일 수와 총 크기, 가장 큰 파일들을 모읍니다.
+//! "212GB, 파일 48,120개를 보냅니다. 계속할까요?" 같은 확인 화면에 씁니다.
+
+use super::dir_stream::{walk_directory, EntryKind};
+use super::file_transfer::SymlinkPolicy;
+use super::path_filter::PathFilter;
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fs;
+use std::path::PathBuf;
+use tracing::warn;
+
+/// 결과에 담는 가장 큰 파일 수 기본값
+pub const DEFAULT_LARGEST_FILES: usize = 10;
+
+/// 큰 파일 항목
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EstimatedFile {
+    pub size: u64,
+    pub path: String,
+}
+
+/// 미리 계산한 전송 규모
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferEstimate {
+    pub file_count: u64,
+    pub dir_count: u64,
+    pub total_bytes: u64,
+    /// 큰 순서
+    pub largest_files: Vec<EstimatedFile>,
+}
+
+/// 선택 항목들의 전송 규모 계산
+///
+/// 폴더는 필터를 적용해 걷고, 직접 고른 파일은 필터와 무관하게 포함합니다.
+/// 없는 경로는 경고만 남기고 건너뜁니다.
+pub fn estimate(
+    paths: &[PathBuf],
+    policy: SymlinkPolicy,
+    filter: &PathFilter,
+    largest: usize,
+) -> TransferEstimate {
+    let mut estimate = TransferEstimate::default();
+    // 작은 것부터 꺼내는 힙으로 상위 `largest`개만 유지
+    let mut heap: BinaryHeap<Reverse<EstimatedFile>> = BinaryHeap::new();
+    let mut add_file = |estimate: &mut TransferEstimate, path: String, size: u64| {
+        estimate.file_count += 1;
+        estimate.total_bytes += size;
+        if largest == 0 {
+            return;
+        }
+        heap.push(Reverse(EstimatedFile { size, path }));
+        if heap.len() > largest {
+            heap.pop();
+        }
+    };
+
+    for path in paths {
+        let metadata = match fs::metadata(path) {
+            Ok(metadata) => metadata,
+            Err(e) => {
+                warn!("크기 계산에서 제외 ({:?}): {}", path, e);
+                continue;
+            }
+        };
+        if metadata.is_file() {
+            add_file(
+                &mut estimate,
+                path.to_string_lossy().to_string(),
+                metadata.len(),
+            );
+            continue;
+        }
+
+        let filter = filter.clone().with_ignore_file(path);
+        walk_directory(path, policy, &filter, |entry, local| {
+            match entry.kind {
+                EntryKind::File => add_file(
+                    &mut estimate,
+                    local.to_string_lossy().to_string(),
+                    entry.size,
+                ),
+                EntryKind::Dir => estimate.dir_count += 1,
+                EntryKind::Symlink => {}
+            }
+            true
+        });
+    }
+
+    estimate.largest_files = heap
+        .into_sorted_vec()
+        .into_iter()
+        .map(|Reverse(file)| file)
+        .collect();
+    estimate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_counts_filtered_files_and_largest() {
+        let dir = std::env::temp_dir().join(format!("ponswarp-estimate-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::create_dir_all(dir.join("node_modules/pkg")).unwrap();
+        fs::write(dir.join("src/a.bin"), vec![0u8; 300]).unwrap();
+        fs::write(dir.join("src/b.bin"), vec![0u8; 100]).unwrap();
+        fs::write(dir.join("c.tmp"), vec![0u8; 1000]).unwrap();
+        fs::write(dir.join("node_modules/pkg/index.js"), vec![0u8; 5000]).unwrap();
+        fs::write(dir.join(".pswpignore"), "*.tmp\n").unwrap();
+        let single = dir.join("src/b.bin");
+
+        let filter = PathFilter::new(&[], &["node_modules".to_string()]).unwrap();
+        let result = estimate(&[dir.clone(), single], SymlinkPolicy::Follow, &filter, 2);
+
+        // 폴더: a.bin, b.bin (c.tmp는 .pswpignore, node_modules는 제외) + 직접 고른 b.bin
+        assert_eq!(result.file_count, 3);
+        assert_eq!(result.total_bytes, 500);
+        assert_eq!(result.dir_count, 1);
+        let sizes: Vec<u64> = result.largest_files.iter().map(|f| f.size).collect();
+        assert_eq!(sizes, vec![300, 100]);
+        assert!(result.largest_files[0].path.ends_with("a.bin"));
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
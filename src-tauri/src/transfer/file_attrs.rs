@@ -0,0 +1,174 @@
+//! 파일 속성 보존 (수정/생성 시각, Unix 권한, 숨김/읽기 전용)
+//!
+//! 송신 측이 매니페스트에 담아 보내고 수신 측이 파일을 다 쓴 뒤 적용합니다.
+//! 플랫폼이 지원하지 않는 속성(Linux의 생성 시각, Unix의 숨김 플래그 등)은 건너뜁니다.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::warn;
+
+/// 전송할 파일 속성
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileAttributes {
+    /// 수정 시각 (Unix ms)
+    #[serde(default)]
+    pub modified_ms: Option<u64>,
+    /// 생성 시각 (Unix ms, Windows/macOS에서만 적용)
+    #[serde(default)]
+    pub created_ms: Option<u64>,
+    /// Unix 권한 비트 (실행 권한 포함)
+    #[serde(default)]
+    pub unix_mode: Option<u32>,
+    #[serde(default)]
+    pub readonly: bool,
+    /// Windows 숨김 속성 (Unix에서는 이름이 `.`으로 시작하면 숨김)
+    #[serde(default)]
+    pub hidden: bool,
+}
+
+impl FileAttributes {
+    /// 로컬 파일의 속성 읽기
+    pub fn read(path: &Path) -> io::Result<Self> {
+        let metadata = fs::metadata(path)?;
+        Ok(Self {
+            modified_ms: metadata.modified().ok().map(to_unix_ms),
+            created_ms: metadata.created().ok().map(to_unix_ms),
+            unix_mode: unix_mode(&metadata),
+            readonly: metadata.permissions().readonly(),
+            hidden: is_hidden(path),
+        })
+    }
+
+    /// 받은 파일에 속성 적용
+    ///
+    /// 시각을 먼저 설정한 뒤 권한을 바꿉니다 (읽기 전용이 되면 시각을 쓸 수 없으므로).
+    pub fn apply(&self, path: &Path) -> io::Result<()> {
+        let mut times = fs::FileTimes::new();
+        if let Some(ms) = self.modified_ms {
+            times = times.set_modified(from_unix_ms(ms));
+        }
+        #[cfg(target_os = "windows")]
+        if let Some(ms) = self.created_ms {
+            use std::os::windows::fs::FileTimesExt;
+            times = times.set_created(from_unix_ms(ms));
+        }
+        #[cfg(target_os = "macos")]
+        if let Some(ms) = self.created_ms {
+            use std::os::macos::fs::FileTimesExt;
+            times = times.set_created(from_unix_ms(ms));
+        }
+        fs::OpenOptions::new()
+            .write(true)
+            .open(path)?
+            .set_times(times)?;
+
+        #[cfg(target_os = "windows")]
+        if self.hidden {
+            set_hidden(path)?;
+        }
+
+        let mut permissions = fs::metadata(path)?.permissions();
+        #[cfg(unix)]
+        if let Some(mode) = self.unix_mode {
+            use std::os::unix::fs::PermissionsExt;
+            permissions.set_mode(mode & 0o7777);
+            return fs::set_permissions(path, permissions);
+        }
+        if self.readonly {
+            permissions.set_readonly(true);
+            fs::set_permissions(path, permissions)?;
+        }
+        Ok(())
+    }
+
+    /// 적용 실패는 전송 실패로 보지 않고 경고만 남김
+    pub fn apply_or_warn(&self, path: &Path) {
+        if let Err(e) = self.apply(path) {
+            warn!("파일 속성 적용 실패 ({:?}): {}", path, e);
+        }
+    }
+}
+
+fn to_unix_ms(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+fn from_unix_ms(ms: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_millis(ms)
+}
+
+#[cfg(unix)]
+fn unix_mode(metadata: &fs::Metadata) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+    Some(metadata.permissions().mode() & 0o7777)
+}
+
+#[cfg(not(unix))]
+fn unix_mode(_metadata: &fs::Metadata) -> Option<u32> {
+    None
+}
+
+#[cfg(target_os = "windows")]
+fn is_hidden(path: &Path) -> bool {
+    use std::os::windows::fs::MetadataExt;
+    use windows_sys::Win32::Storage::FileSystem::FILE_ATTRIBUTE_HIDDEN;
+    fs::metadata(path)
+        .map(|m| m.file_attributes() & FILE_ATTRIBUTE_HIDDEN != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(target_os = "windows"))]
+fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+}
+
+#[cfg(target_os = "windows")]
+fn set_hidden(path: &Path) -> io::Result<()> {
+    use std::os::windows::ffi::OsStrExt;
+    use std::os::windows::fs::MetadataExt;
+    use windows_sys::Win32::Storage::FileSystem::{SetFileAttributesW, FILE_ATTRIBUTE_HIDDEN};
+
+    let attributes = fs::metadata(path)?.file_attributes() | FILE_ATTRIBUTE_HIDDEN;
+    let wide: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+    // SAFETY: wide는 NUL로 끝나는 UTF-16 경로
+    if unsafe { SetFileAttributesW(wide.as_ptr(), attributes) } == 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_attributes() {
+        let dir = std::env::temp_dir().join(format!("ponswarp-attrs-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("build.sh");
+        fs::write(&path, b"#!/bin/sh\n").unwrap();
+
+        let attrs = FileAttributes {
+            modified_ms: Some(1_600_000_000_000),
+            created_ms: None,
+            unix_mode: Some(0o755),
+            readonly: false,
+            hidden: false,
+        };
+        attrs.apply(&path).unwrap();
+
+        let applied = FileAttributes::read(&path).unwrap();
+        assert_eq!(applied.modified_ms, Some(1_600_000_000_000));
+        #[cfg(unix)]
+        assert_eq!(applied.unix_mode, Some(0o755));
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -2,12 +2,23 @@
 //!
 //! WebRTC를 대체하여 Native 환경에서 파일 전송을 담당합니다.
 
+use super::auto_accept::{AutoAcceptRule, AutoAcceptRules};
+use super::disk_space::{self, RESPONSE_NO_SPACE};
+use super::file_attrs::FileAttributes;
+use super::media_preview::PreviewSource;
+use super::memory_budget::{BudgetPermit, MemoryBudget};
+use super::part_file;
+use super::path_filter::PathFilter;
+use super::payload_crypto::{self, PayloadCipher};
+use super::quota::QuotaMeter;
+use super::registry::JobControl;
+use crate::error::AppError;
 use crate::protocol::commands::{TransferRequest, TransferResponse};
 use anyhow::Result;
 use hex;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs::{self, File as StdFile};
 use std::io::{Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
@@ -47,6 +58,9 @@ pub struct FileMetadata {
     pub size: u64,
     pub mime_type: Option<String>,
     pub checksum: Option<String>,
+    /// 수정/생성 시각, 권한 등 (수신 측이 적용)
+    #[serde(default)]
+    pub attributes: Option<FileAttributes>,
 }
 
 /// 전송 매니페스트
@@ -57,11 +71,157 @@ pub struct TransferManifest {
     pub total_size: u64,
     pub is_folder: bool,
     pub root_name: String,
+    /// 데이터가 페어링 키로 암호화된 프레임(`payload_crypto`)인지
+    #[serde(default)]
+    pub encrypted: bool,
 }
 
 /// 청크 크기 (1MB - 고속 전송을 위해 증가)
 const CHUNK_SIZE: usize = 1024 * 1024;
 
+/// 파일 읽기/쓰기 버퍼 크기 (4MB)
+const STREAM_BUFFER_SIZE: usize = 4 * 1024 * 1024;
+
+/// 송신 청크 크기 하한/상한 (암호화 시 청크 하나가 프레임 하나)
+pub const MIN_CHUNK_SIZE: usize = 64 * 1024;
+pub const MAX_CHUNK_SIZE: usize = payload_crypto::MAX_FRAME_LEN;
+
+/// 수신 측 응답: 기존 파일을 두고 건너뜀
+const RESPONSE_SKIPPED: &[u8; 5] = b"SKIPD";
+/// 수신 측 응답: 기존 파일이 있어 거부
+const RESPONSE_EXISTS: &[u8; 5] = b"EXIST";
+
+/// 저장 경로에 같은 이름의 파일이 있을 때의 처리
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OverwritePolicy {
+    /// 기존 파일을 덮어씀 (기존 동작)
+    #[default]
+    Overwrite,
+    /// 기존 파일을 두고 수신하지 않음
+    Skip,
+    /// `이름 (1).확장자`처럼 번호를 붙여 저장
+    Rename,
+    /// 전송 실패로 처리
+    Fail,
+}
+
+/// 폴더 스캔 중 심볼릭 링크 처리
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SymlinkPolicy {
+    /// 링크를 전송하지 않음
+    Skip,
+    /// 링크 대상을 일반 파일/폴더처럼 전송 (기존 동작, 순환 링크는 한 번만 방문)
+    #[default]
+    Follow,
+    /// 링크 자체를 전송해 수신 측에서 다시 만듦 (폴더 밖을 가리키는 링크는 수신 측이 거부)
+    Recreate,
+}
+
+/// 🆕 폴더 전송 옵션 (`send_folder_transfer`, `send_directory_stream`)
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderSendOptions {
+    /// Zip 압축 수준 (Zip 폴더 전송만)
+    pub compression_level: Option<u32>,
+    pub symlink_policy: Option<SymlinkPolicy>,
+    /// `scan_folder`와 같은 글롭 패턴 (예: `node_modules`, `.git`, `*.tmp` 제외)
+    pub include: Option<Vec<String>>,
+    pub exclude: Option<Vec<String>>,
+    /// 페어링 키로 페이로드 암호화
+    pub encrypt: Option<bool>,
+}
+
+/// 수신 시 실제로 적용된 처리 (완료 이벤트의 `action`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ReceiveAction {
+    /// 새 파일 생성
+    Created,
+    Overwritten,
+    Skipped,
+    Renamed,
+}
+
+impl ReceiveAction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ReceiveAction::Created => "created",
+            ReceiveAction::Overwritten => "overwritten",
+            ReceiveAction::Skipped => "skipped",
+            ReceiveAction::Renamed => "renamed",
+        }
+    }
+}
+
+/// 피어가 보낸 파일 이름(또는 상대 경로)을 저장 폴더 아래 경로로 변환
+///
+/// `\`도 구분자로 보고 `.`/`..`을 정리한 뒤, 절대 경로나 저장 폴더 밖으로 나가는 경로는 거부합니다
+/// (`../../.bashrc` 같은 이름으로 폴더 밖에 쓰는 것을 막음).
+pub fn safe_destination(dir: &Path, name: &str) -> Result<PathBuf> {
+    let unsafe_path = || anyhow::anyhow!("안전하지 않은 경로: {}", name);
+    if name.starts_with(['/', '\\']) {
+        return Err(unsafe_path());
+    }
+
+    let mut parts: Vec<&str> = Vec::new();
+    for part in name.split(['/', '\\']) {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop().ok_or_else(unsafe_path)?;
+            }
+            // 드라이브 지정(C:)이나 NTFS 대체 스트림(name:stream)
+            _ if cfg!(windows) && part.contains(':') => return Err(unsafe_path()),
+            _ if part.contains('\0') => return Err(unsafe_path()),
+            _ => parts.push(part),
+        }
+    }
+    if parts.is_empty() {
+        return Err(unsafe_path());
+    }
+    Ok(parts
+        .iter()
+        .fold(dir.to_path_buf(), |path, part| path.join(part)))
+}
+
+/// 정책에 따라 실제 저장 경로 결정 (`Fail`이고 파일이 있으면 에러)
+pub fn resolve_destination(
+    path: &Path,
+    policy: OverwritePolicy,
+) -> Result<(PathBuf, ReceiveAction)> {
+    if !path.exists() {
+        return Ok((path.to_path_buf(), ReceiveAction::Created));
+    }
+
+    match policy {
+        OverwritePolicy::Overwrite => Ok((path.to_path_buf(), ReceiveAction::Overwritten)),
+        OverwritePolicy::Skip => Ok((path.to_path_buf(), ReceiveAction::Skipped)),
+        OverwritePolicy::Fail => Err(anyhow::anyhow!(
+            "같은 이름의 파일이 이미 있습니다: {}",
+            path.display()
+        )),
+        OverwritePolicy::Rename => {
+            let stem = path
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let ext = path.extension().map(|e| e.to_string_lossy().to_string());
+            (1..=9999)
+                .map(|n| match &ext {
+                    Some(ext) => path.with_file_name(format!("{} ({}).{}", stem, n, ext)),
+                    None => path.with_file_name(format!("{} ({})", stem, n)),
+                })
+                .find(|candidate| !candidate.exists())
+                .map(|candidate| (candidate, ReceiveAction::Renamed))
+                .ok_or_else(|| {
+                    anyhow::anyhow!("사용할 수 있는 파일 이름이 없습니다: {}", path.display())
+                })
+        }
+    }
+}
+
 // --- State Management for File Streams (Tauri Commands) ---
 
 /// 파일 스트림 상태 관리 (여러 파일의 동시 쓰기를 위해)
@@ -79,10 +239,22 @@ impl FileStreamManager {
     }
 }
 
+/// 전송 요청 등록 결과
+pub enum ApprovalOutcome {
+    /// 자동 수락 규칙에 따라 바로 수락됨
+    AutoAccepted(AutoAcceptRule),
+    /// 사용자 승인 대기
+    Pending(mpsc::Receiver<TransferResponse>),
+}
+
 /// 전송 승인 관리자
 pub struct TransferApprovalManager {
     pub pending_requests: Arc<RwLock<HashMap<String, TransferRequest>>>,
     pub approval_tx: Arc<RwLock<HashMap<String, mpsc::Sender<TransferResponse>>>>,
+    /// 보낸 요청의 응답 대기 (Sender 측)
+    offer_tx: Arc<RwLock<HashMap<String, mpsc::Sender<TransferResponse>>>>,
+    /// 신뢰하는 피어 자동 수락 규칙
+    auto_accept: Arc<AutoAcceptRules>,
     expiry_duration: Duration,
 }
 
@@ -91,15 +263,35 @@ impl TransferApprovalManager {
         Self {
             pending_requests: Arc::new(RwLock::new(HashMap::new())),
             approval_tx: Arc::new(RwLock::new(HashMap::new())),
+            offer_tx: Arc::new(RwLock::new(HashMap::new())),
+            auto_accept: Arc::new(AutoAcceptRules::in_memory()),
             expiry_duration: Duration::from_secs(30), // 30초 타임아웃
         }
     }
 
+    /// 자동 수락 규칙 설정
+    pub fn with_auto_accept(mut self, rules: Arc<AutoAcceptRules>) -> Self {
+        self.auto_accept = rules;
+        self
+    }
+
+    pub fn auto_accept(&self) -> &Arc<AutoAcceptRules> {
+        &self.auto_accept
+    }
+
+    /// 사용자 승인 대기 시간
+    pub fn expiry_duration(&self) -> Duration {
+        self.expiry_duration
+    }
+
     /// 전송 요청 등록 (Receiver에서 호출)
-    pub async fn register_request(
-        &self,
-        request: TransferRequest,
-    ) -> (String, mpsc::Receiver<TransferResponse>) {
+    ///
+    /// 자동 수락 규칙에 맞으면 승인 대기 없이 바로 수락합니다.
+    pub async fn register_request(&self, request: TransferRequest) -> ApprovalOutcome {
+        if let Some(rule) = self.auto_accept.evaluate(&request) {
+            return ApprovalOutcome::AutoAccepted(rule);
+        }
+
         let job_id = request.job_id.clone();
         let (tx, rx) = mpsc::channel(1);
 
@@ -107,9 +299,36 @@ impl TransferApprovalManager {
             .write()
             .await
             .insert(job_id.clone(), request);
-        self.approval_tx.write().await.insert(job_id.clone(), tx);
+        self.approval_tx.write().await.insert(job_id, tx);
+
+        ApprovalOutcome::Pending(rx)
+    }
+
+    /// 승인 대기 시간 초과 처리
+    pub async fn expire(&self, job_id: &str) {
+        warn!("⏰ 전송 승인 시간 초과: {}", job_id);
+        self.cleanup(job_id).await;
+    }
+
+    /// 보낸 요청의 응답 대기 등록 (Sender에서 호출)
+    pub async fn register_offer(&self, job_id: &str) -> mpsc::Receiver<TransferResponse> {
+        let (tx, rx) = mpsc::channel(1);
+        self.offer_tx.write().await.insert(job_id.to_string(), tx);
+        rx
+    }
+
+    /// 피어가 보낸 응답 전달 (대기 중인 요청이 없으면 false)
+    pub async fn resolve_offer(&self, response: TransferResponse) -> bool {
+        let tx = self.offer_tx.write().await.remove(&response.job_id);
+        match tx {
+            Some(tx) => tx.send(response).await.is_ok(),
+            None => false,
+        }
+    }
 
-        (job_id, rx)
+    /// 응답 대기 취소 (시간 초과 등)
+    pub async fn cancel_offer(&self, job_id: &str) {
+        self.offer_tx.write().await.remove(job_id);
     }
 
     /// 승인/거절 처리 (Receiver UI에서 호출)
@@ -118,16 +337,8 @@ impl TransferApprovalManager {
         job_id: &str,
         approved: bool,
         reason: Option<String>,
-    ) -> Result<(), String> {
-        let response = TransferResponse {
-            job_id: job_id.to_string(),
-            approved,
-            reason,
-            timestamp: SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs(),
-        };
+    ) -> Result<(), AppError> {
+        let response = transfer_response(job_id, approved, reason);
 
         let tx = {
             let map = self.approval_tx.read().await;
@@ -135,11 +346,13 @@ impl TransferApprovalManager {
         };
 
         if let Some(tx) = tx {
-            tx.send(response).await.map_err(|e| e.to_string())?;
+            tx.send(response)
+                .await
+                .map_err(|e| AppError::Internal(e.to_string()))?;
             self.cleanup(job_id).await;
             Ok(())
         } else {
-            Err("Request not found".to_string())
+            Err(AppError::NotFound(format!("Request not found: {}", job_id)))
         }
     }
 
@@ -149,11 +362,40 @@ impl TransferApprovalManager {
     }
 }
 
+/// 승인/거절 응답 생성
+pub fn transfer_response(job_id: &str, approved: bool, reason: Option<String>) -> TransferResponse {
+    TransferResponse {
+        job_id: job_id.to_string(),
+        approved,
+        reason,
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+    }
+}
+
 /// 파일 전송 엔진
 pub struct FileTransferEngine {
     state: Arc<RwLock<TransferState>>,
     progress_tx: Option<mpsc::Sender<TransferProgress>>,
     current_job_id: Arc<RwLock<Option<String>>>,
+    /// 레지스트리 작업 제어 (취소/일시정지)
+    job_control: Option<JobControl>,
+    /// 수신 시 기존 파일 처리
+    overwrite_policy: OverwritePolicy,
+    /// 페이로드 암호화 (송신: 설정하면 암호화, 수신: 암호화된 전송을 풀 때 사용)
+    cipher: Option<Arc<PayloadCipher>>,
+    /// 수신 중 미리보기 (기록된 앞부분 바이트 수를 알림)
+    preview: Option<Arc<PreviewSource>>,
+    /// 송신 시 한 번에 읽어 보내는 크기
+    chunk_size: usize,
+    /// 스트림 버퍼를 예약할 앱 전체 메모리 예산
+    memory_budget: Option<Arc<MemoryBudget>>,
+    /// 수신할 최대 바이트 (매니페스트 크기와 실제로 받은 바이트 모두 적용)
+    max_bytes: Option<u64>,
+    /// 받은 바이트를 집계할 수신 한도
+    quota: Option<QuotaMeter>,
 }
 
 impl FileTransferEngine {
@@ -162,6 +404,14 @@ impl FileTransferEngine {
             state: Arc::new(RwLock::new(TransferState::Idle)),
             progress_tx: None,
             current_job_id: Arc::new(RwLock::new(None)),
+            job_control: None,
+            overwrite_policy: OverwritePolicy::default(),
+            cipher: None,
+            preview: None,
+            chunk_size: CHUNK_SIZE,
+            memory_budget: None,
+            max_bytes: None,
+            quota: None,
         }
     }
 
@@ -170,6 +420,66 @@ impl FileTransferEngine {
         self.progress_tx = Some(tx);
     }
 
+    /// 작업 제어 핸들 설정 (청크마다 취소/일시정지 확인)
+    pub fn set_job_control(&mut self, control: JobControl) {
+        self.job_control = Some(control);
+    }
+
+    /// 수신 시 같은 이름의 파일이 있을 때의 처리 설정
+    pub fn set_overwrite_policy(&mut self, policy: OverwritePolicy) {
+        self.overwrite_policy = policy;
+    }
+
+    /// 페어링 키 설정
+    pub fn set_cipher(&mut self, cipher: Option<Arc<PayloadCipher>>) {
+        self.cipher = cipher;
+    }
+
+    /// 수신 중인 파일을 미리보기로 내보낼 대상 설정
+    pub fn set_preview(&mut self, preview: Arc<PreviewSource>) {
+        self.preview = Some(preview);
+    }
+
+    /// 송신 청크 크기 설정 (수신 측은 프레임/스트림 길이를 따르므로 영향 없음)
+    pub fn set_chunk_size(&mut self, size: usize) {
+        self.chunk_size = size.clamp(MIN_CHUNK_SIZE, MAX_CHUNK_SIZE);
+    }
+
+    /// 스트림 버퍼를 앱 전체 메모리 예산 안에서 할당
+    pub fn set_memory_budget(&mut self, budget: Arc<MemoryBudget>) {
+        self.memory_budget = Some(budget);
+    }
+
+    /// 🆕 수신 크기 상한 설정 (넘으면 수신을 중단하고 임시 파일을 지움)
+    pub fn set_max_bytes(&mut self, max_bytes: u64) {
+        self.max_bytes = Some(max_bytes);
+    }
+
+    /// 🆕 받은 바이트를 수신 한도에 집계 (넘으면 수신을 중단하고 임시 파일을 지움)
+    pub fn set_quota(&mut self, quota: QuotaMeter) {
+        self.quota = Some(quota);
+    }
+
+    /// 버퍼 `bytes`만큼 예산 예약 (예산이 없으면 None, 반환값을 버퍼와 함께 유지)
+    async fn reserve_buffers(&self, bytes: usize) -> Option<BudgetPermit> {
+        match &self.memory_budget {
+            Some(budget) => Some(budget.reserve(bytes).await),
+            None => None,
+        }
+    }
+
+    /// 일시정지면 대기, 취소되었으면 Failed 상태로 바꾸고 에러 반환
+    async fn checkpoint(&self) -> Result<()> {
+        if let Some(control) = &self.job_control {
+            if let Err(e) = control.checkpoint().await {
+                self.update_state(TransferState::Failed(e.to_string()))
+                    .await;
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
     /// 현재 상태 조회
     pub async fn get_state(&self) -> TransferState {
         self.state.read().await.clone()
@@ -228,9 +538,14 @@ impl FileTransferEngine {
 
         info!("📤 파일 전송 시작: {} ({} bytes)", file_name, total_size);
 
+        // 해시 계산과 데이터 전송은 차례로 같은 크기의 버퍼를 씀
+        let _budget = self
+            .reserve_buffers(STREAM_BUFFER_SIZE + self.chunk_size)
+            .await;
+
         // SHA-256 해시 계산 (파일 무결성 검증을 위해)
         let mut hasher = Sha256::new();
-        let mut reader = BufReader::with_capacity(4 * 1024 * 1024, file);
+        let mut reader = BufReader::with_capacity(STREAM_BUFFER_SIZE, file);
         let mut buffer = vec![0u8; CHUNK_SIZE];
 
         loop {
@@ -257,10 +572,12 @@ impl FileTransferEngine {
                 size: total_size,
                 mime_type: None,
                 checksum: Some(checksum),
+                attributes: FileAttributes::read(&file_path).ok(),
             }],
             total_size,
             is_folder: false,
             root_name: file_name,
+            encrypted: self.cipher.is_some(),
         };
 
         let (mut send, mut recv) = conn.open_bi().await?;
@@ -274,6 +591,31 @@ impl FileTransferEngine {
         // 상대방의 READY 응답 대기
         let mut ready_buf = [0u8; 5];
         recv.read_exact(&mut ready_buf).await?;
+        if &ready_buf == RESPONSE_SKIPPED {
+            info!(
+                "📤 수신 측에 같은 파일이 있어 건너뜀: {}",
+                manifest.root_name
+            );
+            let _ = send.finish();
+            self.update_state(TransferState::Completed).await;
+            self.report_progress(job_id, total_size, total_size, 0)
+                .await;
+            return Ok(0);
+        }
+        if &ready_buf == RESPONSE_EXISTS {
+            let _ = send.finish();
+            let message = "수신 측에 같은 이름의 파일이 있어 거부됨".to_string();
+            self.update_state(TransferState::Failed(message.clone()))
+                .await;
+            return Err(anyhow::anyhow!(message));
+        }
+        if &ready_buf == RESPONSE_NO_SPACE {
+            let _ = send.finish();
+            let error = disk_space::read_no_space(&mut recv).await?;
+            self.update_state(TransferState::Failed(error.to_string()))
+                .await;
+            return Err(error.into());
+        }
         if &ready_buf != b"READY" {
             return Err(anyhow::anyhow!("Receiver not ready"));
         }
@@ -281,15 +623,21 @@ impl FileTransferEngine {
         self.update_state(TransferState::Transferring).await;
 
         // 파일 데이터 전송 (4MB 버퍼로 고속 전송)
-        let mut reader = BufReader::with_capacity(4 * 1024 * 1024, file);
-        let mut buffer = vec![0u8; CHUNK_SIZE];
+        let mut reader = BufReader::with_capacity(STREAM_BUFFER_SIZE, file);
+        let mut buffer = vec![0u8; self.chunk_size];
         let mut bytes_sent: u64 = 0;
+        let mut frames: u64 = 0;
         let start_time = std::time::Instant::now();
         let mut last_progress_time = std::time::Instant::now();
 
         info!("📤 데이터 전송 루프 시작: {} bytes", total_size);
 
         loop {
+            if let Err(e) = self.checkpoint().await {
+                let _ = send.reset(0u32.into());
+                return Err(e);
+            }
+
             match reader.read(&mut buffer).await {
                 Ok(0) => {
                     info!("📤 파일 끝에 도달 (EOF)");
@@ -298,7 +646,16 @@ impl FileTransferEngine {
                 Ok(n) => {
                     info!("📤 {} bytes 읽음, 전송 중...", n);
 
-                    if let Err(e) = send.write_all(&buffer[..n]).await {
+                    let written = match &self.cipher {
+                        Some(cipher) => {
+                            let aad = payload_crypto::aad(job_id, frames);
+                            frames += 1;
+                            payload_crypto::write_frame(&mut send, cipher, &aad, &mut buffer[..n])
+                                .await
+                        }
+                        None => send.write_all(&buffer[..n]).await.map_err(Into::into),
+                    };
+                    if let Err(e) = written {
                         warn!("📤 데이터 전송 실패: {}", e);
                         return Err(anyhow::anyhow!("데이터 전송 실패: {}", e));
                     }
@@ -364,12 +721,14 @@ impl FileTransferEngine {
     /// QUIC 스트림을 통해 파일 수신 (Receiver)
     /// Receiver가 클라이언트로 연결한 경우, Sender(서버)가 open_bi()로 스트림을 열면
     /// 클라이언트는 accept_bi()로 해당 스트림을 수락합니다.
+    ///
+    /// 같은 이름의 파일이 있으면 `overwrite_policy`를 따르며, 저장 경로와 적용된 처리를 반환합니다.
     pub async fn receive_file(
         &self,
         conn: &quinn::Connection,
         save_dir: PathBuf,
         job_id: &str,
-    ) -> Result<PathBuf> {
+    ) -> Result<(PathBuf, ReceiveAction)> {
         self.update_state(TransferState::Connecting).await;
         *self.current_job_id.write().await = Some(job_id.to_string());
 
@@ -393,8 +752,73 @@ impl FileTransferEngine {
 
         let file_name = &manifest.files[0].name;
         let total_size = manifest.total_size;
-        let save_path = save_dir.join(file_name);
         let expected_checksum = manifest.files[0].checksum.clone();
+        let attributes = manifest.files[0].attributes.clone();
+        if manifest.encrypted && self.cipher.is_none() {
+            let e = anyhow::anyhow!("암호화된 전송이지만 이 피어와 페어링되어 있지 않습니다");
+            let _ = send.finish();
+            let _ = recv.stop(0u32.into());
+            self.update_state(TransferState::Failed(e.to_string()))
+                .await;
+            return Err(e);
+        }
+        let cipher = self.cipher.as_ref().filter(|_| manifest.encrypted);
+        if let Some(max) = self.max_bytes.filter(|max| total_size > *max) {
+            let e = anyhow::anyhow!("수신 크기 한도 초과: {} > {} bytes", total_size, max);
+            warn!("🚫 수신 거부: {}", e);
+            let _ = send.finish();
+            let _ = recv.stop(0u32.into());
+            self.update_state(TransferState::Failed(e.to_string()))
+                .await;
+            return Err(e);
+        }
+
+        // 경로 조작 방지: 저장 폴더 밖으로 나가는 이름은 거부
+        let destination = match safe_destination(&save_dir, file_name) {
+            Ok(path) => path,
+            Err(e) => {
+                warn!("🚫 수신 거부: {}", e);
+                let _ = send.finish();
+                let _ = recv.stop(0u32.into());
+                self.update_state(TransferState::Failed(e.to_string()))
+                    .await;
+                return Err(e);
+            }
+        };
+
+        // 기존 파일 처리 정책 적용
+        let (save_path, action) = match resolve_destination(&destination, self.overwrite_policy) {
+            Ok(resolved) => resolved,
+            Err(e) => {
+                let _ = send.write_all(RESPONSE_EXISTS).await;
+                let _ = send.finish();
+                self.update_state(TransferState::Failed(e.to_string()))
+                    .await;
+                return Err(e);
+            }
+        };
+        if action == ReceiveAction::Skipped {
+            info!("⏭️ 같은 이름의 파일이 있어 건너뜀: {:?}", save_path);
+            let _ = send.write_all(RESPONSE_SKIPPED).await;
+            let _ = send.finish();
+            let _ = recv.stop(0u32.into());
+            self.update_state(TransferState::Completed).await;
+            return Ok((save_path, action));
+        }
+        if action != ReceiveAction::Created {
+            info!("📁 기존 파일 처리: {:?} ({})", save_path, action.as_str());
+        }
+
+        // 저장 공간 확인 (매니페스트 총 크기 + 여유분)
+        if let Err(error) = disk_space::check_space(&save_path, total_size) {
+            warn!("🚫 수신 거부: {}", error);
+            let _ = disk_space::write_no_space(&mut send, &error).await;
+            let _ = send.finish();
+            let _ = recv.stop(0u32.into());
+            self.update_state(TransferState::Failed(error.to_string()))
+                .await;
+            return Err(error.into());
+        }
 
         // 저장 디렉토리 생성
         if let Some(parent) = save_path.parent() {
@@ -406,40 +830,88 @@ impl FileTransferEngine {
 
         self.update_state(TransferState::Transferring).await;
 
-        // 파일 수신 (4MB 버퍼로 고속 수신)
-        let file = File::create(&save_path).await?;
-        let mut writer = BufWriter::with_capacity(4 * 1024 * 1024, file);
+        // 파일 수신 (4MB 버퍼로 고속 수신), 검증이 끝날 때까지는 임시 파일에 기록
+        let part_path = part_file::part_path(&save_path);
+        let _budget = self.reserve_buffers(STREAM_BUFFER_SIZE + CHUNK_SIZE).await;
+        let file = File::create(&part_path).await?;
+        let mut writer = BufWriter::with_capacity(STREAM_BUFFER_SIZE, file);
+        if let Some(preview) = &self.preview {
+            preview.start(&part_path, total_size);
+        }
         let mut buffer = vec![0u8; CHUNK_SIZE];
         let mut bytes_received: u64 = 0;
+        let mut frames: u64 = 0;
         let start_time = std::time::Instant::now();
         let mut last_progress_time = std::time::Instant::now();
 
         // 수신하면서 SHA-256 해시 계산
         let mut hasher = Sha256::new();
 
-        loop {
-            match recv.read(&mut buffer).await? {
-                Some(n) if n > 0 => {
-                    writer.write_all(&buffer[..n]).await?;
-                    hasher.update(&buffer[..n]);
-                    bytes_received += n as u64;
+        // 수신 중 어느 단계에서 실패해도 임시 파일이 남지 않도록 루프 전체의 결과를 모아 처리
+        let received: Result<()> = async {
+            loop {
+                if let Err(e) = self.checkpoint().await {
+                    let _ = recv.stop(0u32.into());
+                    return Err(e);
+                }
 
-                    // 진행률 보고 (200ms마다 - UI 스로틀링과 동기화)
-                    let now = std::time::Instant::now();
-                    if now.duration_since(last_progress_time).as_millis() >= 200 {
-                        last_progress_time = now;
-                        let elapsed = start_time.elapsed().as_secs_f64();
-                        let speed = if elapsed > 0.0 {
-                            ((bytes_received as f64) / elapsed) as u64
-                        } else {
-                            0
-                        };
-                        self.report_progress(job_id, bytes_received, total_size, speed)
-                            .await;
+                let chunk = match cipher {
+                    Some(cipher) => {
+                        let aad = payload_crypto::aad(&manifest.job_id, frames);
+                        frames += 1;
+                        payload_crypto::read_frame(&mut recv, cipher, &aad, &mut buffer).await?
+                    }
+                    None => recv.read(&mut buffer).await?,
+                };
+                match chunk {
+                    Some(n) if n > 0 => {
+                        // 매니페스트보다 많이 보내는 상대도 한도에서 끊음
+                        bytes_received += n as u64;
+                        if let Some(max) = self.max_bytes.filter(|max| bytes_received > *max) {
+                            let _ = recv.stop(0u32.into());
+                            let e = anyhow::anyhow!("수신 크기 한도 초과: {} bytes", max);
+                            self.update_state(TransferState::Failed(e.to_string()))
+                                .await;
+                            return Err(e);
+                        }
+                        if let Some(Err(reason)) = self.quota.as_ref().map(|q| q.charge(n as u64)) {
+                            let _ = recv.stop(0u32.into());
+                            self.update_state(TransferState::Failed(reason.clone()))
+                                .await;
+                            return Err(anyhow::anyhow!(reason));
+                        }
+                        writer.write_all(&buffer[..n]).await?;
+                        hasher.update(&buffer[..n]);
+
+                        // 진행률 보고 (200ms마다 - UI 스로틀링과 동기화)
+                        let now = std::time::Instant::now();
+                        if now.duration_since(last_progress_time).as_millis() >= 200 {
+                            last_progress_time = now;
+                            // 미리보기는 디스크에 있는 데이터만 읽으므로 버퍼를 비움
+                            if let Some(preview) = &self.preview {
+                                writer.flush().await?;
+                                preview.advance(bytes_received);
+                            }
+                            let elapsed = start_time.elapsed().as_secs_f64();
+                            let speed = if elapsed > 0.0 {
+                                ((bytes_received as f64) / elapsed) as u64
+                            } else {
+                                0
+                            };
+                            self.report_progress(job_id, bytes_received, total_size, speed)
+                                .await;
+                        }
                     }
+                    _ => break,
                 }
-                _ => break,
             }
+            Ok(())
+        }
+        .await;
+        if let Err(e) = received {
+            drop(writer);
+            part_file::discard(&part_path);
+            return Err(e);
         }
 
         // 해시 검증
@@ -452,7 +924,8 @@ impl FileTransferEngine {
                     "🔐 해시 불일치! 예상: {}, 계산: {}",
                     expected, calculated_checksum
                 );
-                tokio::fs::remove_file(&save_path).await?;
+                drop(writer);
+                part_file::discard(&part_path);
                 return Err(anyhow::anyhow!(
                     "파일 무결성 검증 실패: 해시 불일치\n예상: {}\n계산: {}",
                     expected,
@@ -465,7 +938,19 @@ impl FileTransferEngine {
             info!("⚠️  매니페스트에 체크섬이 없습니다. 검증 스킵.");
         }
 
-        writer.flush().await?;
+        let flushed = writer.flush().await;
+        drop(writer);
+        if let Err(e) = flushed {
+            part_file::discard(&part_path);
+            return Err(e.into());
+        }
+        part_file::commit(&part_path, &save_path)?;
+        if let Some(attributes) = &attributes {
+            attributes.apply_or_warn(&save_path);
+        }
+        if let Some(preview) = &self.preview {
+            preview.complete(&save_path);
+        }
         info!("📥 파일 쓰기 완료, DONE 응답 전송...");
 
         // 완료 응답 전송 (Sender에게 알림) - 즉시 전송
@@ -481,11 +966,14 @@ impl FileTransferEngine {
             .await;
 
         info!("✅ 파일 수신 완료: {} -> {:?}", bytes_received, save_path);
-        Ok(save_path)
+        Ok((save_path, action))
     }
 
     /// 전송 취소
     pub async fn cancel(&self) {
+        if let Some(control) = &self.job_control {
+            control.cancel();
+        }
         self.update_state(TransferState::Failed("Cancelled by user".to_string()))
             .await;
     }
@@ -504,44 +992,90 @@ pub fn resolve_path(base: String, relative: String) -> String {
 
 /// [Scanning] 폴더 재귀적 스캔 (Sender용) - Warp Engine v2.0
 /// 폴더 내 모든 파일의 상대 경로와 메타데이터를 반환합니다.
+/// 심볼릭 링크는 `symlink_policy`(기본 Follow)에 따라 건너뛰거나, 따라가거나, 링크 항목(`symlinkTarget`)으로 반환합니다.
+/// `include`/`exclude` 글롭 패턴과 폴더 루트의 `.pswpignore`는 스캔 중에 적용되어 제외된 폴더는 들어가지 않습니다 (`path_filter` 참고).
 #[tauri::command]
-pub fn scan_folder(path: String) -> Result<Vec<serde_json::Value>, String> {
+pub fn scan_folder(
+    path: String,
+    symlink_policy: Option<SymlinkPolicy>,
+    include: Option<Vec<String>>,
+    exclude: Option<Vec<String>>,
+) -> Result<Vec<serde_json::Value>, AppError> {
     let mut files = Vec::new();
+    let policy = symlink_policy.unwrap_or_default();
+    let filter = PathFilter::new(&include.unwrap_or_default(), &exclude.unwrap_or_default())
+        .map_err(|e| AppError::InvalidInput(format!("잘못된 필터 패턴: {}", e)))?
+        .with_ignore_file(Path::new(&path));
+
+    fn scan_recursive(
+        dir: &Path,
+        base_path: &Path,
+        policy: SymlinkPolicy,
+        filter: &PathFilter,
+        visited: &mut HashSet<PathBuf>,
+        files: &mut Vec<serde_json::Value>,
+    ) {
+        // 링크를 따라가다 이미 방문한 폴더로 돌아오면 중단 (순환 링크)
+        if let Ok(canonical) = fs::canonicalize(dir) {
+            if !visited.insert(canonical) {
+                warn!("순환 심볼릭 링크 건너뜀: {:?}", dir);
+                return;
+            }
+        }
 
-    fn scan_recursive(dir: &Path, base_path: &Path, files: &mut Vec<serde_json::Value>) {
         if let Ok(entries) = fs::read_dir(dir) {
             for entry in entries.flatten() {
                 let entry_path = entry.path();
+                let file_name = entry.file_name().to_string_lossy().to_string();
 
-                if entry_path.is_dir() {
-                    // 하위 폴더 재귀 스캔 (숨겨진 폴더 제외)
-                    let folder_name = entry.file_name();
-                    if !folder_name.to_string_lossy().starts_with('.') {
-                        scan_recursive(&entry_path, base_path, files);
-                    }
-                } else if entry_path.is_file() {
-                    // 파일 메타데이터 수집
-                    let metadata = match fs::metadata(&entry_path) {
-                        Ok(m) => m,
-                        Err(_) => continue,
-                    };
-
-                    let file_name = entry.file_name().to_string_lossy().to_string();
+                // 숨겨진 파일/폴더 제외 (.DS_Store, .git 등)
+                if file_name.starts_with('.') {
+                    continue;
+                }
 
-                    // 숨겨진 파일 제외 (.DS_Store, .git 등)
-                    if file_name.starts_with('.') {
-                        continue;
+                // 상대 경로 계산 (예: "src/utils/logger.ts"), OS 경로 구분자를 /로 정규화
+                let relative_path = entry_path
+                    .strip_prefix(base_path)
+                    .map(|p| p.to_string_lossy().to_string())
+                    .unwrap_or_else(|_| file_name.clone())
+                    .replace('\\', "/");
+
+                let is_symlink = entry.file_type().is_ok_and(|t| t.is_symlink());
+                if is_symlink {
+                    match policy {
+                        SymlinkPolicy::Skip => continue,
+                        SymlinkPolicy::Recreate => {
+                            if !filter.allows_file(&relative_path) {
+                                continue;
+                            }
+                            let Ok(target) = fs::read_link(&entry_path) else {
+                                continue;
+                            };
+                            files.push(serde_json::json!({
+                                "name": file_name,
+                                "path": relative_path,
+                                "size": 0,
+                                "isFile": false,
+                                "symlinkTarget": target.to_string_lossy().replace('\\', "/")
+                            }));
+                            continue;
+                        }
+                        SymlinkPolicy::Follow => {}
                     }
+                }
 
-                    // 상대 경로 계산 (예: "src/utils/logger.ts")
-                    let relative_path = entry_path
-                        .strip_prefix(base_path)
-                        .map(|p| p.to_string_lossy().to_string())
-                        .unwrap_or_else(|_| file_name.clone());
-
-                    // OS 경로 구분자를 /로 정규화
-                    let relative_path = relative_path.replace('\\', "/");
+                // 링크는 대상 기준 (끊어진 링크는 건너뜀)
+                let metadata = match fs::metadata(&entry_path) {
+                    Ok(m) => m,
+                    Err(_) => continue,
+                };
 
+                if metadata.is_dir() {
+                    // 하위 폴더 재귀 스캔 (제외된 폴더는 들어가지 않음)
+                    if filter.allows_dir(&relative_path) {
+                        scan_recursive(&entry_path, base_path, policy, filter, visited, files);
+                    }
+                } else if metadata.is_file() && filter.allows_file(&relative_path) {
                     files.push(serde_json::json!({
                         "name": file_name,
                         "path": relative_path,
@@ -554,7 +1088,14 @@ pub fn scan_folder(path: String) -> Result<Vec<serde_json::Value>, String> {
     }
 
     let base_path = Path::new(&path);
-    scan_recursive(base_path, base_path, &mut files);
+    scan_recursive(
+        base_path,
+        base_path,
+        policy,
+        &filter,
+        &mut HashSet::new(),
+        &mut files,
+    );
 
     println!(
         "[Rust] 📁 Scanned {} files from folder: {}",
@@ -566,11 +1107,12 @@ pub fn scan_folder(path: String) -> Result<Vec<serde_json::Value>, String> {
 
 /// [Filesystem] 해당 파일 경로의 상위 디렉토리가 존재하는지 확인하고, 없으면 생성 (mkdir -p)
 #[tauri::command]
-pub fn ensure_dir_exists(file_path: String) -> Result<(), String> {
+pub fn ensure_dir_exists(file_path: String) -> Result<(), AppError> {
     let path = Path::new(&file_path);
     if let Some(parent) = path.parent() {
         if !parent.exists() {
-            fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+            fs::create_dir_all(parent)
+                .map_err(|e| AppError::Io(format!("Failed to create directory: {}", e)))?;
         }
     }
     Ok(())
@@ -583,7 +1125,7 @@ pub fn start_native_file_stream(
     file_id: String,
     save_path: String,
     total_size: u64,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     let path = Path::new(&save_path);
 
     // 1. 파일 생성 (Create/Overwrite)
@@ -592,7 +1134,7 @@ pub fn start_native_file_stream(
         .create(true)
         .truncate(true)
         .open(path)
-        .map_err(|e| format!("Failed to create file: {}", e))?;
+        .map_err(|e| AppError::Io(format!("Failed to create file: {}", e)))?;
 
     // 2. 공간 미리 할당 (Pre-allocation for performance)
     if total_size > 0 {
@@ -609,7 +1151,7 @@ pub fn start_native_file_stream(
     state
         .file_streams
         .lock()
-        .map_err(|_| "File stream state lock poisoned".to_string())?
+        .map_err(|_| AppError::Internal("File stream state lock poisoned".into()))?
         .insert(file_id.clone(), file);
 
     println!("[Rust] File stream started: {}", save_path);
@@ -623,30 +1165,33 @@ pub fn write_native_file_chunk(
     file_id: String,
     chunk: Vec<u8>,
     offset: i64,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     let mut streams = state
         .file_streams
         .lock()
-        .map_err(|_| "File stream state lock poisoned".to_string())?;
+        .map_err(|_| AppError::Internal("File stream state lock poisoned".into()))?;
 
     if let Some(file) = streams.get_mut(&file_id) {
         // Offset이 -1이면 현재 위치(Append), 아니면 Seek
         if offset >= 0 {
             file.seek(SeekFrom::Start(offset as u64))
-                .map_err(|e| format!("Seek failed: {}", e))?;
+                .map_err(|e| AppError::Io(format!("Seek failed: {}", e)))?;
         } else {
             // -1인 경우 End로 이동 (혹은 현재 커서 유지)
             // 보통 순차 쓰기이므로 seek이 필요 없을 수 있으나, 명시적으로 End로 이동
             file.seek(SeekFrom::End(0))
-                .map_err(|e| format!("Seek end failed: {}", e))?;
+                .map_err(|e| AppError::Io(format!("Seek end failed: {}", e)))?;
         }
 
         file.write_all(&chunk)
-            .map_err(|e| format!("Write failed: {}", e))?;
+            .map_err(|e| AppError::Io(format!("Write failed: {}", e)))?;
 
         Ok(())
     } else {
-        Err(format!("File stream not found: {}", file_id))
+        Err(AppError::NotFound(format!(
+            "File stream not found: {}",
+            file_id
+        )))
     }
 }
 
@@ -655,15 +1200,16 @@ pub fn write_native_file_chunk(
 pub fn close_native_file_stream(
     state: tauri::State<'_, FileStreamManager>,
     file_id: String,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     let mut streams = state
         .file_streams
         .lock()
-        .map_err(|_| "File stream state lock poisoned".to_string())?;
+        .map_err(|_| AppError::Internal("File stream state lock poisoned".into()))?;
 
     if let Some(file) = streams.remove(&file_id) {
         // File은 Scope를 벗어나면 자동으로 close되지만, 확실하게 sync() 호출
-        file.sync_all().map_err(|e| format!("Sync failed: {}", e))?;
+        file.sync_all()
+            .map_err(|e| AppError::Io(format!("Sync failed: {}", e)))?;
         println!("[Rust] File stream closed: {}", file_id);
         Ok(())
     } else {
@@ -677,3 +1223,137 @@ impl Default for FileTransferEngine {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_destination() {
+        let dir = std::env::temp_dir().join(format!("ponswarp-overwrite-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("report.txt");
+
+        let (resolved, action) = resolve_destination(&path, OverwritePolicy::Fail).unwrap();
+        assert_eq!((resolved, action), (path.clone(), ReceiveAction::Created));
+
+        fs::write(&path, b"old").unwrap();
+        fs::write(dir.join("report (1).txt"), b"old").unwrap();
+        assert_eq!(
+            resolve_destination(&path, OverwritePolicy::Overwrite)
+                .unwrap()
+                .1,
+            ReceiveAction::Overwritten
+        );
+        assert_eq!(
+            resolve_destination(&path, OverwritePolicy::Skip).unwrap().1,
+            ReceiveAction::Skipped
+        );
+        assert!(resolve_destination(&path, OverwritePolicy::Fail).is_err());
+        assert_eq!(
+            resolve_destination(&path, OverwritePolicy::Rename).unwrap(),
+            (dir.join("report (2).txt"), ReceiveAction::Renamed)
+        );
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_set_chunk_size_clamps() {
+        let mut engine = FileTransferEngine::new();
+        assert_eq!(engine.chunk_size, CHUNK_SIZE);
+        engine.set_chunk_size(256 * 1024);
+        assert_eq!(engine.chunk_size, 256 * 1024);
+        engine.set_chunk_size(1);
+        assert_eq!(engine.chunk_size, MIN_CHUNK_SIZE);
+        engine.set_chunk_size(usize::MAX);
+        assert_eq!(engine.chunk_size, MAX_CHUNK_SIZE);
+    }
+
+    #[test]
+    fn test_safe_destination() {
+        let dir = Path::new("/downloads");
+        assert_eq!(
+            safe_destination(dir, "report.txt").unwrap(),
+            dir.join("report.txt")
+        );
+        assert_eq!(
+            safe_destination(dir, "a/./b/../c.txt").unwrap(),
+            dir.join("a").join("c.txt")
+        );
+        assert_eq!(
+            safe_destination(dir, "src\\main.rs").unwrap(),
+            dir.join("src").join("main.rs")
+        );
+
+        for name in [
+            "../../.bashrc",
+            "a/../../x",
+            "..\\..\\x",
+            "/etc/passwd",
+            "\\x",
+            "",
+            ".",
+        ] {
+            assert!(safe_destination(dir, name).is_err(), "{}", name);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_receive_stops_sender_over_max_bytes() {
+        use crate::quic::client::QuicClient;
+        use crate::quic::QuicServer;
+
+        let mut server = QuicServer::new("127.0.0.1:0".parse().unwrap());
+        let mut accepted = server.take_connection_receiver().unwrap();
+        server.start().await.unwrap();
+        let outbound = QuicClient::new()
+            .connect(server.local_addr().unwrap(), "localhost")
+            .await
+            .unwrap();
+        // 서버 쪽은 자체 명령 처리 루프가 스트림을 받으므로 클라이언트 쪽이 수신
+        let inbound = accepted.recv().await.unwrap().connection;
+
+        let dir = std::env::temp_dir().join(format!("ponswarp-max-bytes-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let mut engine = FileTransferEngine::new();
+        engine.set_max_bytes(16);
+
+        // 매니페스트에는 한도 안의 크기를 알리고 실제로는 더 많이 보내는 상대
+        let sender = async {
+            let (mut send, mut recv) = inbound.open_bi().await.unwrap();
+            let manifest = serde_json::to_vec(&TransferManifest {
+                job_id: "over".into(),
+                files: vec![FileMetadata {
+                    name: "over.bin".into(),
+                    size: 8,
+                    mime_type: None,
+                    checksum: None,
+                    attributes: None,
+                }],
+                total_size: 8,
+                is_folder: false,
+                root_name: "over.bin".into(),
+                encrypted: false,
+            })
+            .unwrap();
+            send.write_all(&(manifest.len() as u32).to_le_bytes())
+                .await
+                .unwrap();
+            send.write_all(&manifest).await.unwrap();
+            let mut ready = [0u8; 5];
+            recv.read_exact(&mut ready).await.unwrap();
+            assert_eq!(&ready, b"READY");
+            let _ = send.write_all(&[0u8; 64 * 1024]).await;
+            let _ = send.finish();
+        };
+        let (received, _) =
+            tokio::join!(engine.receive_file(&outbound, dir.clone(), "over"), sender);
+        assert!(received.is_err());
+        assert!(!dir.join("over.bin").exists());
+        assert!(!part_file::part_path(&dir.join("over.bin")).exists());
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,608 @@
+//! 브라우저 수신용 HTTP 공유 링크
+//!
+//! 앱을 설치하지 않은 동료도 같은 LAN에서 브라우저로 받을 수 있도록, 선택한 파일 하나를
+//! `http://<LAN IP>:<port>/s/<토큰>` 링크로 내보냅니다. 서버는 첫 링크를 만들 때만 열리며(옵트인),
+//! 링크는 기본 1회 다운로드 후 만료되고 만료 시간이 지나면 더 이상 열리지 않습니다.
+//!
+//! 요청은 출발지 IP별로 속도를 제한하고(잘못된 토큰은 더 비싸게), 링크마다 다운로드 속도 상한을
+//! 둘 수 있습니다. 다운로드 시작/완료/실패는 `HttpShareEvent`로 알립니다.
+
+use super::pacer::Pacer;
+use crate::dht::flood::SourceLimiter;
+use crate::util::{constant_time_eq, now_secs};
+use anyhow::{anyhow, Result};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+/// 기본 링크 유효 시간
+pub const DEFAULT_EXPIRY: Duration = Duration::from_secs(60 * 60);
+
+/// 최대 링크 유효 시간
+pub const MAX_EXPIRY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// 요청 하나의 비용 (`SourceLimiter` 기준 IP별 초당 5건, 버스트 10건)
+const REQUEST_COST: f64 = 4.0;
+
+/// 없는 토큰 요청에 추가로 매기는 비용 (토큰 추측 시도 억제)
+const INVALID_TOKEN_COST: f64 = 16.0;
+
+/// 요청 헤더 최대 크기
+const MAX_HEADER_SIZE: usize = 8 * 1024;
+
+/// 요청 헤더를 기다리는 시간
+const HEADER_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 파일 읽기/쓰기 단위
+const CHUNK_SIZE: usize = 256 * 1024;
+
+/// 링크 생성 옵션
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpShareOptions {
+    /// 유효 시간 (초, 기본 1시간, 최대 24시간)
+    pub expires_in_secs: Option<u64>,
+    /// 허용할 다운로드 횟수 (기본 1 = 일회용)
+    pub max_downloads: Option<u32>,
+    /// 다운로드 속도 상한 (bytes/sec)
+    pub rate_limit_bps: Option<u64>,
+}
+
+/// 공유 링크 정보
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpShareLink {
+    pub id: String,
+    pub url: String,
+    pub file_name: String,
+    pub file_size: u64,
+    /// 만료 시각 (Unix 초)
+    pub expires_at: u64,
+    pub max_downloads: u32,
+    /// 진행 중이거나 본문을 보낸 다운로드 수 (본문을 보내기 전에 실패한 다운로드만 횟수를 돌려받음)
+    pub downloads: u32,
+    pub rate_limit_bps: Option<u64>,
+}
+
+/// 다운로드 상태 변화
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HttpShareEventKind {
+    Started,
+    Completed,
+    Failed,
+}
+
+/// `http-share-event` 이벤트 페이로드
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpShareEvent {
+    pub share_id: String,
+    pub remote_addr: String,
+    pub kind: HttpShareEventKind,
+    pub bytes: u64,
+    pub error: Option<String>,
+}
+
+struct Share {
+    link: HttpShareLink,
+    token: String,
+    path: PathBuf,
+    expires: Instant,
+}
+
+/// 링크를 사용할 수 없는 이유
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ClaimError {
+    NotFound,
+    /// 만료되었거나 다운로드 횟수를 모두 사용함
+    Gone,
+}
+
+/// 다운로드 하나에 필요한 정보
+struct Claimed {
+    share_id: String,
+    path: PathBuf,
+    file_name: String,
+    file_size: u64,
+    rate_limit_bps: Option<u64>,
+}
+
+/// 실행 중인 HTTP 서버
+struct RunningServer {
+    local_addr: SocketAddr,
+    task: tokio::task::JoinHandle<()>,
+}
+
+/// HTTP 공유 링크 서버
+#[derive(Default)]
+pub struct HttpShareServer {
+    shares: Arc<Mutex<HashMap<String, Share>>>,
+    limiter: Arc<SourceLimiter>,
+    event_tx: Option<mpsc::UnboundedSender<HttpShareEvent>>,
+    server: tokio::sync::Mutex<Option<RunningServer>>,
+}
+
+impl HttpShareServer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 다운로드 이벤트 채널 설정
+    pub fn with_event_channel(mut self, tx: mpsc::UnboundedSender<HttpShareEvent>) -> Self {
+        self.event_tx = Some(tx);
+        self
+    }
+
+    /// 파일 공유 링크 생성 (서버가 꺼져 있으면 시작, `host_ip`는 링크에 넣을 LAN 주소)
+    pub async fn share(
+        &self,
+        path: PathBuf,
+        options: HttpShareOptions,
+        host_ip: IpAddr,
+    ) -> Result<HttpShareLink> {
+        let metadata = tokio::fs::metadata(&path).await?;
+        if !metadata.is_file() {
+            return Err(anyhow!("파일만 공유할 수 있습니다: {}", path.display()));
+        }
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .ok_or_else(|| anyhow!("파일 이름이 없습니다: {}", path.display()))?;
+        let expiry = options
+            .expires_in_secs
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_EXPIRY)
+            .min(MAX_EXPIRY);
+        let max_downloads = options.max_downloads.unwrap_or(1).max(1);
+
+        let port = self.ensure_started().await?.port();
+        let token = generate_token();
+        let link = HttpShareLink {
+            id: uuid::Uuid::new_v4().to_string(),
+            url: format!("http://{}/s/{}", SocketAddr::new(host_ip, port), token),
+            file_name,
+            file_size: metadata.len(),
+            expires_at: now_secs() + expiry.as_secs(),
+            max_downloads,
+            downloads: 0,
+            rate_limit_bps: options.rate_limit_bps.filter(|&bps| bps > 0),
+        };
+
+        info!(
+            "🌐 HTTP 공유 링크 생성: {} ({} bytes, {}회, {:?})",
+            link.file_name, link.file_size, max_downloads, expiry
+        );
+        self.shares.lock().insert(
+            link.id.clone(),
+            Share {
+                link: link.clone(),
+                token,
+                path,
+                expires: Instant::now() + expiry,
+            },
+        );
+        Ok(link)
+    }
+
+    /// 유효한 링크 목록
+    pub fn list(&self) -> Vec<HttpShareLink> {
+        let mut shares = self.shares.lock();
+        prune_expired(&mut shares, Instant::now());
+        shares.values().map(|s| s.link.clone()).collect()
+    }
+
+    /// 링크 폐기 (진행 중인 다운로드는 계속됨)
+    pub fn revoke(&self, share_id: &str) -> bool {
+        self.shares.lock().remove(share_id).is_some()
+    }
+
+    /// 서버 중지 및 모든 링크 폐기
+    pub async fn stop(&self) {
+        self.shares.lock().clear();
+        if let Some(server) = self.server.lock().await.take() {
+            server.task.abort();
+            info!("🌐 HTTP 공유 서버 중지: {}", server.local_addr);
+        }
+    }
+
+    async fn ensure_started(&self) -> Result<SocketAddr> {
+        let mut server = self.server.lock().await;
+        if let Some(running) = server.as_ref() {
+            return Ok(running.local_addr);
+        }
+
+        let listener = TcpListener::bind("0.0.0.0:0").await?;
+        let local_addr = listener.local_addr()?;
+        let shares = self.shares.clone();
+        let limiter = self.limiter.clone();
+        let event_tx = self.event_tx.clone();
+        let task = tokio::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((socket, remote)) => {
+                        let shares = shares.clone();
+                        let limiter = limiter.clone();
+                        let event_tx = event_tx.clone();
+                        tokio::spawn(async move {
+                            handle_connection(socket, remote, &shares, &limiter, event_tx).await;
+                        });
+                    }
+                    Err(e) => {
+                        warn!("HTTP 공유 연결 수락 실패: {}", e);
+                        tokio::time::sleep(Duration::from_millis(100)).await;
+                    }
+                }
+            }
+        });
+
+        info!("🌐 HTTP 공유 서버 시작: {}", local_addr);
+        *server = Some(RunningServer { local_addr, task });
+        Ok(local_addr)
+    }
+}
+
+async fn handle_connection(
+    mut socket: TcpStream,
+    remote: SocketAddr,
+    shares: &Mutex<HashMap<String, Share>>,
+    limiter: &SourceLimiter,
+    event_tx: Option<mpsc::UnboundedSender<HttpShareEvent>>,
+) {
+    let request = match tokio::time::timeout(HEADER_TIMEOUT, read_request_head(&mut socket)).await {
+        Ok(Ok((request, _))) => request,
+        _ => return,
+    };
+
+    if !limiter.allow(remote.ip(), REQUEST_COST) {
+        let _ = write_status(&mut socket, "429 Too Many Requests", "Retry-After: 1\r\n").await;
+        return;
+    }
+    let Some(token) = parse_share_token(&request) else {
+        let _ = write_status(&mut socket, "404 Not Found", "").await;
+        return;
+    };
+
+    let claimed = claim(&mut shares.lock(), token, Instant::now());
+    let claimed = match claimed {
+        Ok(claimed) => claimed,
+        Err(ClaimError::NotFound) => {
+            let _ = limiter.allow(remote.ip(), INVALID_TOKEN_COST);
+            let _ = write_status(&mut socket, "404 Not Found", "").await;
+            return;
+        }
+        Err(ClaimError::Gone) => {
+            let _ = write_status(&mut socket, "410 Gone", "").await;
+            return;
+        }
+    };
+
+    let emit = |kind, bytes, error: Option<String>| {
+        if let Some(tx) = &event_tx {
+            let _ = tx.send(HttpShareEvent {
+                share_id: claimed.share_id.clone(),
+                remote_addr: remote.to_string(),
+                kind,
+                bytes,
+                error,
+            });
+        }
+    };
+
+    info!("🌐 HTTP 다운로드 시작: {} -> {}", claimed.file_name, remote);
+    emit(HttpShareEventKind::Started, 0, None);
+    match send_file(&mut socket, &claimed).await {
+        Ok(bytes) => {
+            info!(
+                "🌐 HTTP 다운로드 완료: {} ({} bytes)",
+                claimed.file_name, bytes
+            );
+            emit(HttpShareEventKind::Completed, bytes, None);
+        }
+        Err((sent, e)) => {
+            warn!("HTTP 다운로드 실패 ({}, {} bytes): {}", remote, sent, e);
+            // 일부라도 받아 간 다운로드는 횟수를 돌려주지 않음 (중간에 끊어 일회용 링크 재사용 방지)
+            if sent == 0 {
+                release(&mut shares.lock(), &claimed.share_id);
+            }
+            emit(HttpShareEventKind::Failed, sent, Some(e.to_string()));
+        }
+    }
+}
+
+/// 요청 줄과 헤더, 그리고 헤더와 함께 읽힌 본문 앞부분
+pub(super) async fn read_request_head(socket: &mut TcpStream) -> Result<(String, Vec<u8>)> {
+    let mut buf = Vec::with_capacity(1024);
+    let mut chunk = [0u8; 1024];
+    loop {
+        if let Some(end) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            let body = buf.split_off(end + 4);
+            return Ok((String::from_utf8_lossy(&buf).to_string(), body));
+        }
+        if buf.len() >= MAX_HEADER_SIZE {
+            return Err(anyhow!("요청 헤더가 너무 큼"));
+        }
+        let n = socket.read(&mut chunk).await?;
+        if n == 0 {
+            return Err(anyhow!("요청 헤더 전에 연결 종료"));
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+}
+
+/// `GET /s/<token>` 요청의 토큰
+fn parse_share_token(request: &str) -> Option<&str> {
+    let mut parts = request.lines().next()?.split_whitespace();
+    if parts.next()? != "GET" {
+        return None;
+    }
+    let path = parts.next()?;
+    let path = path.split(['?', '#']).next()?;
+    let token = path.strip_prefix("/s/")?.trim_end_matches('/');
+    (!token.is_empty() && !token.contains('/')).then_some(token)
+}
+
+/// 토큰으로 링크를 찾아 다운로드 1회를 예약 (동시 요청이 횟수를 넘지 않도록 전송 전에 차감)
+fn claim(
+    shares: &mut HashMap<String, Share>,
+    token: &str,
+    now: Instant,
+) -> Result<Claimed, ClaimError> {
+    let share = shares
+        .values_mut()
+        .find(|s| constant_time_eq(s.token.as_bytes(), token.as_bytes()))
+        .ok_or(ClaimError::NotFound)?;
+    if now >= share.expires || share.link.downloads >= share.link.max_downloads {
+        return Err(ClaimError::Gone);
+    }
+
+    share.link.downloads += 1;
+    Ok(Claimed {
+        share_id: share.link.id.clone(),
+        path: share.path.clone(),
+        file_name: share.link.file_name.clone(),
+        file_size: share.link.file_size,
+        rate_limit_bps: share.link.rate_limit_bps,
+    })
+}
+
+/// 본문을 보내기 전에 실패한 다운로드의 횟수 반환
+fn release(shares: &mut HashMap<String, Share>, share_id: &str) {
+    if let Some(share) = shares.get_mut(share_id) {
+        share.link.downloads = share.link.downloads.saturating_sub(1);
+    }
+}
+
+fn prune_expired(shares: &mut HashMap<String, Share>, now: Instant) {
+    shares.retain(|_, s| now < s.expires);
+}
+
+/// 파일을 응답으로 전송, 보낸 본문 바이트 수 (실패해도 보낸 만큼 함께 반환)
+async fn send_file(
+    socket: &mut TcpStream,
+    claimed: &Claimed,
+) -> std::result::Result<u64, (u64, anyhow::Error)> {
+    let mut file = match tokio::fs::File::open(&claimed.path).await {
+        Ok(file) => file,
+        Err(e) => {
+            let _ = write_status(socket, "500 Internal Server Error", "").await;
+            return Err((0, e.into()));
+        }
+    };
+
+    let head = format!(
+        "HTTP/1.1 200 OK\r\n\
+        Content-Type: application/octet-stream\r\n\
+        Content-Length: {}\r\n\
+        Content-Disposition: {}\r\n\
+        Cache-Control: no-store\r\n\
+        Connection: close\r\n\
+        \r\n",
+        claimed.file_size,
+        content_disposition(&claimed.file_name)
+    );
+    socket
+        .write_all(head.as_bytes())
+        .await
+        .map_err(|e| (0, e.into()))?;
+
+    let pacer = Pacer::new(claimed.rate_limit_bps.unwrap_or(0));
+    let mut buf = vec![0u8; CHUNK_SIZE];
+    let mut sent = 0u64;
+    // 공유 후 파일이 커졌어도 알린 길이까지만 보냄
+    while sent < claimed.file_size {
+        let want = (claimed.file_size - sent).min(CHUNK_SIZE as u64) as usize;
+        let n = file
+            .read(&mut buf[..want])
+            .await
+            .map_err(|e| (sent, e.into()))?;
+        if n == 0 {
+            return Err((
+                sent,
+                anyhow!(
+                    "파일이 공유 후 줄어듦 ({} / {} bytes)",
+                    sent,
+                    claimed.file_size
+                ),
+            ));
+        }
+        pacer.pace(n).await;
+        // 일부만 쓰고 끊겨도 받아 간 것으로 셈
+        sent += n as u64;
+        socket
+            .write_all(&buf[..n])
+            .await
+            .map_err(|e| (sent, e.into()))?;
+    }
+    socket.shutdown().await.map_err(|e| (sent, e.into()))?;
+    Ok(sent)
+}
+
+async fn write_status(socket: &mut TcpStream, status: &str, extra_headers: &str) -> Result<()> {
+    let response = format!(
+        "HTTP/1.1 {}\r\n\
+        Content-Type: text/plain; charset=utf-8\r\n\
+        Content-Length: {}\r\n\
+        Connection: close\r\n\
+        {}\r\n\
+        {}",
+        status,
+        status.len(),
+        extra_headers,
+        status
+    );
+    socket.write_all(response.as_bytes()).await?;
+    Ok(())
+}
+
+/// 비ASCII 파일명도 브라우저가 그대로 저장하도록 RFC 6266 `filename*` 포함
+fn content_disposition(file_name: &str) -> String {
+    let fallback: String = file_name
+        .chars()
+        .map(|c| {
+            if (c.is_ascii_graphic() && c != '"' && c != '\\') || c == ' ' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let mut encoded = String::new();
+    for byte in file_name.bytes() {
+        if byte.is_ascii_alphanumeric() || b"!#$&+-.^_`|~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    format!(
+        "attachment; filename=\"{}\"; filename*=UTF-8''{}",
+        fallback, encoded
+    )
+}
+
+pub(super) fn generate_token() -> String {
+    let mut bytes = [0u8; 16];
+    rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut bytes);
+    hex::encode(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn share(token: &str, max_downloads: u32, expires: Instant) -> Share {
+        Share {
+            link: HttpShareLink {
+                id: format!("id-{}", token),
+                url: String::new(),
+                file_name: "report.pdf".to_string(),
+                file_size: 10,
+                expires_at: 0,
+                max_downloads,
+                downloads: 0,
+                rate_limit_bps: None,
+            },
+            token: token.to_string(),
+            path: PathBuf::from("/tmp/report.pdf"),
+            expires,
+        }
+    }
+
+    #[test]
+    fn test_one_time_token_and_expiry() {
+        let now = Instant::now();
+        let mut shares = HashMap::new();
+        shares.insert("a".to_string(), share("aaaa", 1, now + DEFAULT_EXPIRY));
+        shares.insert(
+            "b".to_string(),
+            share("bbbb", 2, now + Duration::from_secs(1)),
+        );
+
+        // 일회용 링크는 두 번째 요청부터 Gone
+        assert!(claim(&mut shares, "aaaa", now).is_ok());
+        assert_eq!(
+            claim(&mut shares, "aaaa", now).err(),
+            Some(ClaimError::Gone)
+        );
+        assert_eq!(
+            claim(&mut shares, "cccc", now).err(),
+            Some(ClaimError::NotFound)
+        );
+
+        // 실패한 다운로드는 횟수를 돌려받아 다시 받을 수 있음
+        release(&mut shares, "a");
+        assert!(claim(&mut shares, "aaaa", now).is_ok());
+
+        // 만료 후에는 남은 횟수가 있어도 Gone, 목록에서도 제거
+        let later = now + Duration::from_secs(2);
+        assert_eq!(
+            claim(&mut shares, "bbbb", later).err(),
+            Some(ClaimError::Gone)
+        );
+        prune_expired(&mut shares, later);
+        assert!(shares.contains_key("a") && !shares.contains_key("b"));
+
+        assert_eq!(
+            parse_share_token("GET /s/abc123?x=1 HTTP/1.1\r\nHost: x\r\n\r\n"),
+            Some("abc123")
+        );
+        assert_eq!(parse_share_token("POST /s/abc123 HTTP/1.1\r\n\r\n"), None);
+        assert_eq!(parse_share_token("GET /s/../x HTTP/1.1\r\n\r\n"), None);
+        assert_eq!(parse_share_token("GET / HTTP/1.1\r\n\r\n"), None);
+
+        assert_eq!(
+            content_disposition("보고서 v2.pdf"),
+            "attachment; filename=\"___ v2.pdf\"; \
+            filename*=UTF-8''%EB%B3%B4%EA%B3%A0%EC%84%9C%20v2.pdf"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_partial_download_uses_up_link() {
+        let dir = std::env::temp_dir().join(format!("ponswarp-http-share-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let limiter = SourceLimiter::new();
+
+        let download = |path: PathBuf| {
+            let shares = Mutex::new(HashMap::new());
+            let mut one_time = share("aaaa", 1, Instant::now() + DEFAULT_EXPIRY);
+            one_time.path = path;
+            shares.lock().insert(one_time.link.id.clone(), one_time);
+            let listener = &listener;
+            let limiter = &limiter;
+            async move {
+                let mut client = TcpStream::connect(addr).await.unwrap();
+                client
+                    .write_all(b"GET /s/aaaa HTTP/1.1\r\n\r\n")
+                    .await
+                    .unwrap();
+                let (socket, remote) = listener.accept().await.unwrap();
+                handle_connection(socket, remote, &shares, limiter, None).await;
+                let downloads = shares.lock()["id-aaaa"].link.downloads;
+                downloads
+            }
+        };
+
+        // 본문을 보내기 전에 실패하면 횟수를 돌려받음
+        assert_eq!(download(dir.join("missing.pdf")).await, 0);
+
+        // 알린 10바이트 중 일부만 보내고 끊긴 다운로드는 횟수를 그대로 사용
+        let partial = dir.join("report.pdf");
+        std::fs::write(&partial, b"12345").unwrap();
+        assert_eq!(download(partial).await, 1);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,427 @@
+//! 전송/시드 매니페스트 내보내기와 가져오기
+//!
+//! 멀티스트림 전송이나 Grid 작업에서 만든 매니페스트(블록 맵, 해시)를 파일로 저장해 두었다가,
+//! 다른 기기에서 가져와 같은 데이터셋인지 검증하거나 다시 시드할 수 있게 합니다.
+//!
+//! 멀티스트림 매니페스트에는 블록 CRC32와 파일 전체 SHA-256을 붙여 내보내고(원본을 한 번 읽음),
+//! Grid 매니페스트는 조각 SHA-256 해시가 이미 들어 있는 메타데이터를 그대로 씁니다.
+//! 준비된 매니페스트는 메모리에만 최근 [`MAX_PREPARED`]개까지 보관합니다.
+
+use super::multistream::MultiStreamManifest;
+use super::zero_copy_io::split_file_into_blocks;
+use crate::grid::piece_manager::FileMetadata;
+use anyhow::{anyhow, bail, Result};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tracing::info;
+
+/// 매니페스트 파일 형식 버전
+pub const JOB_MANIFEST_VERSION: u32 = 1;
+
+/// 보관할 준비된 매니페스트 수
+pub const MAX_PREPARED: usize = 64;
+
+/// 내보낸 매니페스트 파일
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobManifest {
+    pub version: u32,
+    pub job_id: String,
+    /// 내보낸 시각 (Unix 초)
+    pub created_at: i64,
+    #[serde(flatten)]
+    pub dataset: DatasetManifest,
+}
+
+/// 데이터셋 종류별 내용
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum DatasetManifest {
+    #[serde(rename_all = "camelCase")]
+    Multistream {
+        manifest: MultiStreamManifest,
+        /// 블록별 CRC32 (블록 번호 순)
+        block_checksums: Vec<u32>,
+        /// 파일 전체 SHA-256 (hex)
+        sha256: String,
+    },
+    Grid {
+        metadata: FileMetadata,
+    },
+}
+
+impl DatasetManifest {
+    pub fn file_name(&self) -> &str {
+        match self {
+            DatasetManifest::Multistream { manifest, .. } => &manifest.file_name,
+            DatasetManifest::Grid { metadata } => &metadata.file_name,
+        }
+    }
+
+    pub fn file_size(&self) -> u64 {
+        match self {
+            DatasetManifest::Multistream { manifest, .. } => manifest.file_size,
+            DatasetManifest::Grid { metadata } => metadata.file_size,
+        }
+    }
+
+    /// 블록/조각 크기
+    fn unit_size(&self) -> usize {
+        match self {
+            DatasetManifest::Multistream { manifest, .. } => manifest.block_size as usize,
+            DatasetManifest::Grid { metadata } => metadata.piece_size as usize,
+        }
+    }
+
+    fn unit_count(&self) -> usize {
+        match self {
+            DatasetManifest::Multistream {
+                block_checksums, ..
+            } => block_checksums.len(),
+            DatasetManifest::Grid { metadata } => metadata.piece_hashes.len(),
+        }
+    }
+
+    /// 가져온 매니페스트의 내부 일관성 검사
+    fn validate(&self) -> Result<()> {
+        let unit_size = self.unit_size() as u64;
+        if unit_size == 0 {
+            bail!("블록 크기가 0입니다");
+        }
+        let expected = self.file_size().div_ceil(unit_size) as usize;
+        if self.unit_count() != expected {
+            bail!(
+                "블록 수 불일치: 해시 {}개 / 크기 기준 {}개",
+                self.unit_count(),
+                expected
+            );
+        }
+        match self {
+            DatasetManifest::Multistream {
+                manifest, sha256, ..
+            } => {
+                if manifest.total_blocks as usize != expected {
+                    bail!("total_blocks 불일치: {}", manifest.total_blocks);
+                }
+                if sha256.len() != 64 || hex::decode(sha256).is_err() {
+                    bail!("잘못된 SHA-256: {}", sha256);
+                }
+            }
+            DatasetManifest::Grid { metadata } => {
+                if FileMetadata::compute_info_hash_for(
+                    metadata.hash_algorithm,
+                    &metadata.piece_hashes,
+                ) != metadata.info_hash
+                {
+                    bail!("Info Hash가 조각 해시와 맞지 않습니다");
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 로컬 파일 검증 결과
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestVerification {
+    pub matches: bool,
+    pub size_matches: bool,
+    /// 해시가 다른 블록/조각 번호
+    pub mismatched_blocks: Vec<u32>,
+    pub total_blocks: u32,
+}
+
+/// 매니페스트 기준으로 로컬 파일 검증 (블로킹, 파일 전체를 읽음)
+pub fn verify_file(dataset: &DatasetManifest, path: &Path) -> Result<ManifestVerification> {
+    let mut file = std::fs::File::open(path)?;
+    let file_size = file.metadata()?.len();
+    let total_blocks = dataset.unit_count() as u32;
+    if file_size != dataset.file_size() {
+        return Ok(ManifestVerification {
+            matches: false,
+            size_matches: false,
+            mismatched_blocks: Vec::new(),
+            total_blocks,
+        });
+    }
+
+    let mut mismatched_blocks = Vec::new();
+    let mut whole = Sha256::new();
+    let mut buffer = vec![0u8; dataset.unit_size()];
+    for block in split_file_into_blocks(file_size, dataset.unit_size()) {
+        let data = &mut buffer[..block.size as usize];
+        file.read_exact(data)?;
+        let ok = match dataset {
+            DatasetManifest::Multistream {
+                block_checksums, ..
+            } => {
+                whole.update(&*data);
+                block_checksums[block.index as usize] == crc32fast::hash(data)
+            }
+            DatasetManifest::Grid { metadata } => {
+                metadata.piece_hashes[block.index as usize] == metadata.hash_algorithm.digest(data)
+            }
+        };
+        if !ok {
+            mismatched_blocks.push(block.index);
+        }
+    }
+
+    let whole_matches = match dataset {
+        DatasetManifest::Multistream { sha256, .. } => hex::encode(whole.finalize()) == *sha256,
+        DatasetManifest::Grid { .. } => true,
+    };
+    Ok(ManifestVerification {
+        matches: mismatched_blocks.is_empty() && whole_matches,
+        size_matches: true,
+        mismatched_blocks,
+        total_blocks,
+    })
+}
+
+/// 블록 CRC32와 파일 전체 SHA-256 계산 (블로킹)
+fn hash_blocks(path: &Path, block_size: usize) -> Result<(Vec<u32>, String)> {
+    let mut file = std::fs::File::open(path)?;
+    let file_size = file.metadata()?.len();
+    let mut whole = Sha256::new();
+    let mut checksums = Vec::new();
+    let mut buffer = vec![0u8; block_size];
+    for block in split_file_into_blocks(file_size, block_size) {
+        let data = &mut buffer[..block.size as usize];
+        file.read_exact(data)?;
+        whole.update(&*data);
+        checksums.push(crc32fast::hash(data));
+    }
+    Ok((checksums, hex::encode(whole.finalize())))
+}
+
+/// 준비된 작업
+#[derive(Clone)]
+enum Prepared {
+    /// 보낸 멀티스트림 매니페스트 (내보낼 때 원본에서 해시 계산)
+    Multistream {
+        manifest: MultiStreamManifest,
+        source: PathBuf,
+    },
+    /// Grid 작업 또는 가져온 매니페스트
+    Ready(Box<JobManifest>),
+}
+
+/// 작업별 매니페스트 보관소
+#[derive(Default)]
+pub struct JobManifestStore {
+    jobs: Mutex<HashMap<String, (Instant, Prepared)>>,
+}
+
+impl JobManifestStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 멀티스트림 송신 매니페스트 기록
+    pub fn record_multistream(&self, manifest: &MultiStreamManifest, source: &Path) {
+        self.insert(
+            manifest.job_id.clone(),
+            Prepared::Multistream {
+                manifest: manifest.clone(),
+                source: source.to_path_buf(),
+            },
+        );
+    }
+
+    /// Grid 작업 메타데이터 기록
+    #[cfg_attr(not(feature = "grid-experimental"), allow(dead_code))]
+    pub fn record_grid(&self, job_id: &str, metadata: &FileMetadata) {
+        self.insert(
+            job_id.to_string(),
+            Prepared::Ready(Box::new(JobManifest {
+                version: JOB_MANIFEST_VERSION,
+                job_id: job_id.to_string(),
+                created_at: chrono::Utc::now().timestamp(),
+                dataset: DatasetManifest::Grid {
+                    metadata: metadata.clone(),
+                },
+            })),
+        );
+    }
+
+    /// 가져오기 등으로 이미 완성된 매니페스트 (멀티스트림은 아직 해시 전이면 None)
+    pub fn get(&self, job_id: &str) -> Option<JobManifest> {
+        match self.jobs.lock().get(job_id) {
+            Some((_, Prepared::Ready(manifest))) => Some((**manifest).clone()),
+            _ => None,
+        }
+    }
+
+    /// 작업의 매니페스트를 파일로 내보내기
+    pub async fn export(&self, job_id: &str, path: &Path) -> Result<JobManifest> {
+        let prepared = self
+            .jobs
+            .lock()
+            .get(job_id)
+            .map(|(_, prepared)| prepared.clone())
+            .ok_or_else(|| anyhow!("매니페스트가 없는 작업입니다: {}", job_id))?;
+
+        let manifest = match prepared {
+            Prepared::Ready(manifest) => *manifest,
+            Prepared::Multistream { manifest, source } => {
+                let block_size = manifest.block_size as usize;
+                let (block_checksums, sha256) =
+                    tokio::task::spawn_blocking(move || hash_blocks(&source, block_size)).await??;
+                let manifest = JobManifest {
+                    version: JOB_MANIFEST_VERSION,
+                    job_id: job_id.to_string(),
+                    created_at: chrono::Utc::now().timestamp(),
+                    dataset: DatasetManifest::Multistream {
+                        manifest,
+                        block_checksums,
+                        sha256,
+                    },
+                };
+                manifest.dataset.validate()?;
+                // 다시 내보낼 때는 해시를 재계산하지 않음
+                self.insert(
+                    job_id.to_string(),
+                    Prepared::Ready(Box::new(manifest.clone())),
+                );
+                manifest
+            }
+        };
+
+        if let Some(dir) = path.parent() {
+            tokio::fs::create_dir_all(dir).await?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        tokio::fs::write(&tmp, serde_json::to_vec_pretty(&manifest)?).await?;
+        tokio::fs::rename(&tmp, path).await?;
+        info!("📋 매니페스트 내보내기: {} -> {}", job_id, path.display());
+        Ok(manifest)
+    }
+
+    /// 매니페스트 파일 가져오기 (검사 후 job_id로 보관)
+    pub async fn import(&self, path: &Path) -> Result<JobManifest> {
+        let data = tokio::fs::read(path).await?;
+        let manifest: JobManifest =
+            serde_json::from_slice(&data).map_err(|e| anyhow!("매니페스트 형식 오류: {}", e))?;
+        if manifest.version > JOB_MANIFEST_VERSION {
+            bail!("지원하지 않는 매니페스트 버전: {}", manifest.version);
+        }
+        manifest.dataset.validate()?;
+
+        self.insert(
+            manifest.job_id.clone(),
+            Prepared::Ready(Box::new(manifest.clone())),
+        );
+        info!(
+            "📋 매니페스트 가져오기: {} ({}, {} bytes)",
+            manifest.job_id,
+            manifest.dataset.file_name(),
+            manifest.dataset.file_size()
+        );
+        Ok(manifest)
+    }
+
+    fn insert(&self, job_id: String, prepared: Prepared) {
+        let mut jobs = self.jobs.lock();
+        jobs.insert(job_id, (Instant::now(), prepared));
+        while jobs.len() > MAX_PREPARED {
+            let oldest = jobs
+                .iter()
+                .min_by_key(|(_, (at, _))| *at)
+                .map(|(id, _)| id.clone());
+            match oldest {
+                Some(id) => jobs.remove(&id),
+                None => break,
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn multistream_manifest(job_id: &str, file_size: u64, block_size: u32) -> MultiStreamManifest {
+        MultiStreamManifest {
+            job_id: job_id.to_string(),
+            file_name: "data.bin".to_string(),
+            file_size,
+            block_size,
+            total_blocks: file_size.div_ceil(block_size as u64) as u32,
+            checksum: None,
+            attributes: None,
+            holes: Vec::new(),
+            resumable: true,
+            encrypted: false,
+            flow_control: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_export_import_verify() {
+        let dir = std::env::temp_dir().join(format!("pswp-manifest-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("data.bin");
+        let data: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&source, &data).unwrap();
+
+        let store = JobManifestStore::new();
+        store.record_multistream(&multistream_manifest("job-1", 10_000, 4096), &source);
+        let exported_path = dir.join("job-1.json");
+        let exported = store.export("job-1", &exported_path).await.unwrap();
+        let DatasetManifest::Multistream {
+            ref block_checksums,
+            ..
+        } = exported.dataset
+        else {
+            panic!("멀티스트림 매니페스트여야 합니다");
+        };
+        assert_eq!(block_checksums.len(), 3);
+
+        // 다른 기기에서 가져와 검증
+        let other = JobManifestStore::new();
+        let imported = other.import(&exported_path).await.unwrap();
+        assert_eq!(imported.job_id, "job-1");
+        assert!(other.get("job-1").is_some());
+        let report = verify_file(&imported.dataset, &source).unwrap();
+        assert!(report.matches);
+
+        let mut changed = data.clone();
+        changed[5000] ^= 0xff;
+        let copy = dir.join("copy.bin");
+        std::fs::write(&copy, &changed).unwrap();
+        let report = verify_file(&imported.dataset, &copy).unwrap();
+        assert!(!report.matches && report.size_matches);
+        assert_eq!(report.mismatched_blocks, vec![1]);
+
+        // Grid 메타데이터도 같은 방식
+        let metadata = FileMetadata::from_file(&source, 4096).await.unwrap();
+        store.record_grid("grid-1", &metadata);
+        let grid_path = dir.join("grid-1.json");
+        store.export("grid-1", &grid_path).await.unwrap();
+        let imported = other.import(&grid_path).await.unwrap();
+        assert!(verify_file(&imported.dataset, &source).unwrap().matches);
+        assert_eq!(
+            verify_file(&imported.dataset, &copy)
+                .unwrap()
+                .mismatched_blocks,
+            vec![1]
+        );
+
+        // 손상된 매니페스트 거부
+        let mut value: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(&exported_path).unwrap()).unwrap();
+        value["blockChecksums"].as_array_mut().unwrap().pop();
+        std::fs::write(&exported_path, serde_json::to_vec(&value).unwrap()).unwrap();
+        assert!(other.import(&exported_path).await.is_err());
+        assert!(store.export("unknown", &exported_path).await.is_err());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
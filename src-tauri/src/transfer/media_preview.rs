@@ -0,0 +1,556 @@
+//! 수신 중인 미디어 미리보기
+//!
+//! 앞에서부터 차례로 쓰는 수신(단일 스트림 `FileTransferEngine`)은 받은 만큼이 파일 앞부분과 같으므로,
+//! 그 `.pswp-part` 파일을 `http://127.0.0.1:<port>/m/<토큰>/<파일명>`으로 내보내 프론트엔드의
+//! `<video>`/`<audio>`가 전송이 끝나기 전에 재생을 시작할 수 있게 합니다.
+//!
+//! Range 요청을 지원하며, 아직 받지 않은 위치를 요청하면 데이터가 도착할 때까지 기다렸다가 보냅니다
+//! (완료되면 최종 파일을, 실패하면 연결을 끊음). 서버는 loopback에만 열리고 링크마다 토큰이 있습니다.
+
+use super::http_share::{generate_token, read_request_head};
+use crate::util::constant_time_eq;
+use anyhow::{anyhow, bail, Result};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::SeekFrom;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Notify;
+use tracing::{info, warn};
+
+/// 요청 헤더를 기다리는 최대 시간
+const HEADER_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 데이터가 더 오지 않으면 응답을 끊는 시간 (일시정지 등)
+const STALL_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// 읽어서 보내는 단위
+const CHUNK_SIZE: usize = 256 * 1024;
+
+const STATE_PENDING: u8 = 0;
+const STATE_RECEIVING: u8 = 1;
+const STATE_COMPLETED: u8 = 2;
+const STATE_FAILED: u8 = 3;
+
+/// 수신 엔진이 갱신하는 미리보기 대상 (파일 앞에서부터 읽을 수 있는 바이트 수)
+#[derive(Default)]
+pub struct PreviewSource {
+    path: Mutex<Option<PathBuf>>,
+    available: AtomicU64,
+    total: AtomicU64,
+    state: AtomicU8,
+    changed: Notify,
+}
+
+impl PreviewSource {
+    /// 수신 시작 (`path`는 쓰고 있는 임시 파일)
+    pub fn start(&self, path: &Path, total: u64) {
+        *self.path.lock() = Some(path.to_path_buf());
+        self.total.store(total, Ordering::SeqCst);
+        self.available.store(0, Ordering::SeqCst);
+        self.state.store(STATE_RECEIVING, Ordering::SeqCst);
+        self.changed.notify_waiters();
+    }
+
+    /// 디스크에 기록된 앞부분 바이트 수 갱신
+    pub fn advance(&self, available: u64) {
+        self.available.store(available, Ordering::SeqCst);
+        self.changed.notify_waiters();
+    }
+
+    /// 수신 완료 (최종 파일 경로)
+    pub fn complete(&self, path: &Path) {
+        *self.path.lock() = Some(path.to_path_buf());
+        self.available
+            .store(self.total.load(Ordering::SeqCst), Ordering::SeqCst);
+        self.state.store(STATE_COMPLETED, Ordering::SeqCst);
+        self.changed.notify_waiters();
+    }
+
+    pub fn fail(&self) {
+        self.state.store(STATE_FAILED, Ordering::SeqCst);
+        self.changed.notify_waiters();
+    }
+
+    fn is_finished(&self) -> bool {
+        self.state.load(Ordering::SeqCst) >= STATE_COMPLETED
+    }
+
+    /// `offset` 이후 데이터가 생길 때까지 대기 (읽을 수 있는 끝 위치 반환)
+    async fn wait_for(&self, offset: u64) -> Result<u64> {
+        loop {
+            let notified = self.changed.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+
+            match self.state.load(Ordering::SeqCst) {
+                STATE_FAILED => bail!("수신 실패"),
+                STATE_PENDING => {}
+                _ => {
+                    let available = self.available.load(Ordering::SeqCst);
+                    if available > offset || self.is_finished() {
+                        return Ok(available);
+                    }
+                }
+            }
+            tokio::time::timeout(STALL_TIMEOUT, notified)
+                .await
+                .map_err(|_| anyhow!("데이터 대기 시간 초과"))?;
+        }
+    }
+}
+
+/// 미리보기 링크
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreviewLink {
+    pub job_id: String,
+    pub url: String,
+    pub available_bytes: u64,
+    pub total_bytes: u64,
+}
+
+struct Preview {
+    source: Arc<PreviewSource>,
+    /// 링크를 연 경우의 토큰
+    token: Option<String>,
+}
+
+struct RunningServer {
+    local_addr: SocketAddr,
+    task: tokio::task::JoinHandle<()>,
+}
+
+/// loopback 미리보기 서버 (첫 링크를 열 때 시작)
+#[derive(Default)]
+pub struct MediaPreviewServer {
+    previews: Arc<Mutex<HashMap<String, Preview>>>,
+    server: tokio::sync::Mutex<Option<RunningServer>>,
+}
+
+impl MediaPreviewServer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 작업의 미리보기 대상 (수신 엔진에 연결, 없으면 생성)
+    pub fn source(&self, job_id: &str) -> Arc<PreviewSource> {
+        self.previews
+            .lock()
+            .entry(job_id.to_string())
+            .or_insert_with(|| Preview {
+                source: Arc::new(PreviewSource::default()),
+                token: None,
+            })
+            .source
+            .clone()
+    }
+
+    /// 수신이 끝난 작업 정리 (링크를 열지 않았으면 제거, 열었으면 최종 파일로 계속 재생)
+    pub fn finish(&self, job_id: &str) {
+        let mut previews = self.previews.lock();
+        if let Some(preview) = previews.get(job_id) {
+            if !preview.source.is_finished() {
+                preview.source.fail();
+            }
+            if preview.token.is_none() {
+                previews.remove(job_id);
+            }
+        }
+    }
+
+    /// 미리보기 링크 열기 (수신 시작 전에 열어도 됨)
+    pub async fn open(&self, job_id: &str, file_name: Option<&str>) -> Result<PreviewLink> {
+        let port = self.ensure_started().await?.port();
+        let source = self.source(job_id);
+        let token = {
+            let mut previews = self.previews.lock();
+            let preview = previews
+                .get_mut(job_id)
+                .ok_or_else(|| anyhow!("미리보기가 없습니다: {}", job_id))?;
+            preview.token.get_or_insert_with(generate_token).clone()
+        };
+
+        // 파일명은 브라우저의 형식 추정용 (서버는 토큰만 봄)
+        let name = file_name
+            .map(str::to_string)
+            .or_else(|| {
+                source
+                    .path
+                    .lock()
+                    .as_ref()
+                    .map(|path| final_name(path).to_string())
+            })
+            .unwrap_or_else(|| "media".to_string());
+        let link = PreviewLink {
+            job_id: job_id.to_string(),
+            url: format!(
+                "http://127.0.0.1:{}/m/{}/{}",
+                port,
+                token,
+                url_encode(&name)
+            ),
+            available_bytes: source.available.load(Ordering::SeqCst),
+            total_bytes: source.total.load(Ordering::SeqCst),
+        };
+        info!("🎬 미리보기 링크: {} ({})", job_id, name);
+        Ok(link)
+    }
+
+    /// 링크 닫기
+    pub fn close(&self, job_id: &str) -> bool {
+        let mut previews = self.previews.lock();
+        let Some(preview) = previews.get_mut(job_id) else {
+            return false;
+        };
+        let opened = preview.token.take().is_some();
+        if preview.source.is_finished() {
+            previews.remove(job_id);
+        }
+        opened
+    }
+
+    /// 서버 중지 및 모든 링크 닫기
+    pub async fn stop(&self) {
+        {
+            let mut previews = self.previews.lock();
+            previews
+                .values_mut()
+                .for_each(|preview| preview.token = None);
+            previews.retain(|_, preview| !preview.source.is_finished());
+        }
+        if let Some(server) = self.server.lock().await.take() {
+            server.task.abort();
+            info!("🎬 미리보기 서버 중지: {}", server.local_addr);
+        }
+    }
+
+    async fn ensure_started(&self) -> Result<SocketAddr> {
+        let mut server = self.server.lock().await;
+        if let Some(running) = server.as_ref() {
+            return Ok(running.local_addr);
+        }
+
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let local_addr = listener.local_addr()?;
+        let previews = self.previews.clone();
+        let task = tokio::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((socket, _)) => {
+                        let previews = previews.clone();
+                        tokio::spawn(async move {
+                            if let Err(e) = handle_connection(socket, &previews).await {
+                                warn!("미리보기 응답 중단: {}", e);
+                            }
+                        });
+                    }
+                    Err(e) => {
+                        warn!("미리보기 연결 수락 실패: {}", e);
+                        tokio::time::sleep(Duration::from_millis(100)).await;
+                    }
+                }
+            }
+        });
+
+        info!("🎬 미리보기 서버 시작: {}", local_addr);
+        *server = Some(RunningServer { local_addr, task });
+        Ok(local_addr)
+    }
+}
+
+async fn handle_connection(
+    mut socket: TcpStream,
+    previews: &Mutex<HashMap<String, Preview>>,
+) -> Result<()> {
+    let request = match tokio::time::timeout(HEADER_TIMEOUT, read_request_head(&mut socket)).await {
+        Ok(Ok((request, _))) => request,
+        _ => return Ok(()),
+    };
+
+    let mut request_line = request
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .split_whitespace();
+    let method = request_line.next().unwrap_or_default();
+    let token = request_line
+        .next()
+        .and_then(|path| path.strip_prefix("/m/"))
+        .and_then(|rest| rest.split(['/', '?']).next())
+        .unwrap_or_default();
+    if method != "GET" && method != "HEAD" {
+        return write_status(&mut socket, "405 Method Not Allowed", "").await;
+    }
+
+    let source = previews
+        .lock()
+        .values()
+        .find(|preview| {
+            preview
+                .token
+                .as_ref()
+                .is_some_and(|t| constant_time_eq(t.as_bytes(), token.as_bytes()))
+        })
+        .map(|preview| preview.source.clone());
+    let Some(source) = source else {
+        return write_status(&mut socket, "404 Not Found", "").await;
+    };
+
+    // 크기는 수신이 시작되어야 알 수 있음
+    source.wait_for(0).await.ok();
+    match source.state.load(Ordering::SeqCst) {
+        STATE_PENDING => {
+            return write_status(&mut socket, "503 Service Unavailable", "Retry-After: 1\r\n").await
+        }
+        STATE_FAILED => return write_status(&mut socket, "410 Gone", "").await,
+        _ => {}
+    }
+    let total = source.total.load(Ordering::SeqCst);
+    let Some(path) = source.path.lock().clone() else {
+        return write_status(&mut socket, "404 Not Found", "").await;
+    };
+
+    let range = header(&request, "range");
+    let (status, start, end) = match range {
+        Some(value) => match parse_range(value, total) {
+            Some((start, end)) => ("206 Partial Content", start, end),
+            None => {
+                let extra = format!("Content-Range: bytes */{}\r\n", total);
+                return write_status(&mut socket, "416 Range Not Satisfiable", &extra).await;
+            }
+        },
+        None => ("200 OK", 0, total.saturating_sub(1)),
+    };
+    let len = if total == 0 { 0 } else { end - start + 1 };
+
+    let mut head = format!(
+        "HTTP/1.1 {}\r\n\
+        Content-Type: {}\r\n\
+        Content-Length: {}\r\n\
+        Accept-Ranges: bytes\r\n\
+        Cache-Control: no-store\r\n\
+        Connection: close\r\n",
+        status,
+        content_type(final_name(&path)),
+        len
+    );
+    if range.is_some() {
+        head.push_str(&format!(
+            "Content-Range: bytes {}-{}/{}\r\n",
+            start, end, total
+        ));
+    }
+    head.push_str("\r\n");
+    socket.write_all(head.as_bytes()).await?;
+    if method == "HEAD" || len == 0 {
+        return Ok(());
+    }
+
+    let mut file = tokio::fs::File::open(&path).await?;
+    file.seek(SeekFrom::Start(start)).await?;
+    let mut buf = vec![0u8; CHUNK_SIZE];
+    let mut position = start;
+    let stop = end + 1;
+    while position < stop {
+        // 받은 만큼만 읽고, 아직 안 온 부분은 도착할 때까지 대기
+        let available = source.wait_for(position).await?.min(stop);
+        if available <= position {
+            bail!("파일이 예상보다 짧습니다 ({} / {})", position, total);
+        }
+        let want = (available - position).min(CHUNK_SIZE as u64) as usize;
+        let n = file.read(&mut buf[..want]).await?;
+        if n == 0 {
+            bail!("파일 읽기 중 끝에 도달 ({})", position);
+        }
+        socket.write_all(&buf[..n]).await?;
+        position += n as u64;
+    }
+    socket.shutdown().await?;
+    Ok(())
+}
+
+/// 헤더 값 (이름은 대소문자 무시)
+fn header<'a>(request: &'a str, name: &str) -> Option<&'a str> {
+    request.lines().skip(1).find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        key.trim()
+            .eq_ignore_ascii_case(name)
+            .then_some(value.trim())
+    })
+}
+
+/// `bytes=a-b`, `bytes=a-`, `bytes=-n` 파싱 (포함 범위, 여러 구간은 첫 구간만)
+fn parse_range(value: &str, total: u64) -> Option<(u64, u64)> {
+    let spec = value.strip_prefix("bytes=")?.split(',').next()?.trim();
+    let (start, end) = spec.split_once('-')?;
+    if total == 0 {
+        return None;
+    }
+    let (start, end) = match (start.trim(), end.trim()) {
+        ("", suffix) => {
+            let suffix: u64 = suffix.parse().ok()?;
+            if suffix == 0 {
+                return None;
+            }
+            (total.saturating_sub(suffix), total - 1)
+        }
+        (start, "") => (start.parse().ok()?, total - 1),
+        (start, end) => (start.parse().ok()?, end.parse::<u64>().ok()?.min(total - 1)),
+    };
+    (start <= end && start < total).then_some((start, end))
+}
+
+/// 임시 파일이면 `.pswp-part`를 뗀 최종 파일명
+fn final_name(path: &Path) -> &str {
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+    name.strip_suffix(".pswp-part").unwrap_or(name)
+}
+
+fn content_type(file_name: &str) -> &'static str {
+    let extension = file_name
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "mp4" | "m4v" => "video/mp4",
+        "webm" => "video/webm",
+        "mkv" => "video/x-matroska",
+        "mov" => "video/quicktime",
+        "ogv" => "video/ogg",
+        "mp3" => "audio/mpeg",
+        "m4a" | "aac" => "audio/mp4",
+        "wav" => "audio/wav",
+        "ogg" | "oga" | "opus" => "audio/ogg",
+        "flac" => "audio/flac",
+        _ => "application/octet-stream",
+    }
+}
+
+fn url_encode(value: &str) -> String {
+    let mut encoded = String::new();
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+async fn write_status(socket: &mut TcpStream, status: &str, extra_headers: &str) -> Result<()> {
+    let response = format!(
+        "HTTP/1.1 {}\r\n\
+        Content-Type: text/plain; charset=utf-8\r\n\
+        Content-Length: {}\r\n\
+        Connection: close\r\n\
+        {}\r\n\
+        {}",
+        status,
+        status.len(),
+        extra_headers,
+        status
+    );
+    socket.write_all(response.as_bytes()).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("bytes=0-99", 1000), Some((0, 99)));
+        assert_eq!(parse_range("bytes=500-", 1000), Some((500, 999)));
+        assert_eq!(parse_range("bytes=-100", 1000), Some((900, 999)));
+        assert_eq!(parse_range("bytes=900-5000", 1000), Some((900, 999)));
+        assert_eq!(parse_range("bytes=0-0,5-9", 1000), Some((0, 0)));
+        assert_eq!(parse_range("bytes=1000-", 1000), None);
+        assert_eq!(parse_range("bytes=10-5", 1000), None);
+        assert_eq!(parse_range("bytes=-0", 1000), None);
+        assert_eq!(parse_range("items=0-1", 1000), None);
+        assert_eq!(
+            final_name(Path::new("/tmp/movie.mp4.pswp-part")),
+            "movie.mp4"
+        );
+        assert_eq!(content_type("Movie.MP4"), "video/mp4");
+    }
+
+    #[tokio::test]
+    async fn test_serves_range_as_data_arrives() {
+        let dir = std::env::temp_dir().join(format!("pswp-preview-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let part = dir.join("clip.mp4.pswp-part");
+        let data: Vec<u8> = (0..4000u32).map(|i| (i % 256) as u8).collect();
+        std::fs::write(&part, &data[..1000]).unwrap();
+
+        let server = MediaPreviewServer::new();
+        let source = server.source("job");
+        source.start(&part, data.len() as u64);
+        source.advance(1000);
+        let link = server.open("job", None).await.unwrap();
+        assert!(link.url.ends_with("/clip.mp4"));
+
+        // 아직 받지 않은 범위 요청: 데이터가 도착하면 응답
+        let url = link.url.trim_start_matches("http://").to_string();
+        let (addr, path) = url.split_once('/').unwrap();
+        let request = format!(
+            "GET /{} HTTP/1.1\r\nHost: {}\r\nRange: bytes=500-2499\r\n\r\n",
+            path, addr
+        );
+        let mut socket = TcpStream::connect(addr).await.unwrap();
+        socket.write_all(request.as_bytes()).await.unwrap();
+        let reader = tokio::spawn(async move {
+            let mut response = Vec::new();
+            socket.read_to_end(&mut response).await.unwrap();
+            response
+        });
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        {
+            use std::io::Write;
+            let mut file = std::fs::OpenOptions::new()
+                .append(true)
+                .open(&part)
+                .unwrap();
+            file.write_all(&data[1000..]).unwrap();
+        }
+        let done = dir.join("clip.mp4");
+        std::fs::rename(&part, &done).unwrap();
+        source.complete(&done);
+
+        let response = reader.await.unwrap();
+        let split = response.windows(4).position(|w| w == b"\r\n\r\n").unwrap();
+        let head = String::from_utf8_lossy(&response[..split]);
+        assert!(head.starts_with("HTTP/1.1 206"));
+        assert!(head.contains("Content-Range: bytes 500-2499/4000"));
+        assert!(head.contains("Content-Type: video/mp4"));
+        assert_eq!(&response[split + 4..], &data[500..2500]);
+
+        // 잘못된 토큰
+        let mut socket = TcpStream::connect(addr).await.unwrap();
+        socket
+            .write_all(b"GET /m/wrong/clip.mp4 HTTP/1.1\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = Vec::new();
+        socket.read_to_end(&mut response).await.unwrap();
+        assert!(response.starts_with(b"HTTP/1.1 404"));
+
+        server.finish("job");
+        assert!(server.close("job"));
+        server.stop().await;
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
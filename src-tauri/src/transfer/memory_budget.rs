@@ -0,0 +1,126 @@
+//! 앱 전체 전송 버퍼 메모리 예산
+//!
+//! 멀티스트림 작업 3개 × 스트림 32개 × 8MB 블록처럼 엔진마다 따로 버퍼를 잡으면
+//! 수 GB를 쉽게 넘기므로, 모든 엔진이 버퍼를 새로 할당하기 전에 공용 세마포어에서
+//! 크기만큼 예약합니다. 예산이 모자라면 다른 작업이 버퍼를 돌려줄 때까지 기다립니다.
+//! 예약은 버퍼(또는 [`BudgetPermit`])가 해제될 때 자동으로 반환됩니다.
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore, TryAcquireError};
+use tracing::debug;
+
+/// 기본 예산 (512MB)
+pub const DEFAULT_BUFFER_BUDGET: usize = 512 * 1024 * 1024;
+
+/// 예약 단위 (페이지 크기, 세마포어 허가 하나)
+const UNIT: usize = 4096;
+
+/// 예약한 버퍼 메모리 (drop 시 예산으로 반환)
+#[derive(Debug)]
+pub struct BudgetPermit {
+    _permit: OwnedSemaphorePermit,
+}
+
+/// `get_memory_budget` 응답
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryBudgetStatus {
+    pub total_bytes: u64,
+    pub used_bytes: u64,
+    /// 예산이 모자라 할당을 기다린 횟수
+    pub waits: u64,
+}
+
+/// 전송 버퍼 예산 (AppState 공유)
+pub struct MemoryBudget {
+    semaphore: Arc<Semaphore>,
+    total_units: usize,
+    waits: AtomicU64,
+}
+
+impl MemoryBudget {
+    pub fn new(total_bytes: usize) -> Arc<Self> {
+        let total_units = total_bytes.div_ceil(UNIT).max(1);
+        Arc::new(Self {
+            semaphore: Arc::new(Semaphore::new(total_units)),
+            total_units,
+            waits: AtomicU64::new(0),
+        })
+    }
+
+    /// `bytes`만큼 예약 (예산이 빌 때까지 대기)
+    ///
+    /// 예산 전체보다 큰 요청은 예산 전체를 예약하므로 다른 버퍼가 모두 반환된 뒤 진행됩니다.
+    pub async fn reserve(&self, bytes: usize) -> BudgetPermit {
+        let units = bytes.div_ceil(UNIT).clamp(1, self.total_units) as u32;
+        let permit = match self.semaphore.clone().try_acquire_many_owned(units) {
+            Ok(permit) => permit,
+            Err(TryAcquireError::NoPermits) => {
+                self.waits.fetch_add(1, Ordering::Relaxed);
+                debug!(
+                    "⏳ 버퍼 예산 대기: {} KB 요청, {} KB 남음",
+                    bytes / 1024,
+                    self.available_bytes() / 1024
+                );
+                self.semaphore
+                    .clone()
+                    .acquire_many_owned(units)
+                    .await
+                    .expect("버퍼 예산 세마포어는 닫히지 않음")
+            }
+            Err(TryAcquireError::Closed) => unreachable!("버퍼 예산 세마포어는 닫히지 않음"),
+        };
+        BudgetPermit { _permit: permit }
+    }
+
+    pub fn total_bytes(&self) -> usize {
+        self.total_units * UNIT
+    }
+
+    pub fn available_bytes(&self) -> usize {
+        self.semaphore.available_permits() * UNIT
+    }
+
+    pub fn status(&self) -> MemoryBudgetStatus {
+        MemoryBudgetStatus {
+            total_bytes: self.total_bytes() as u64,
+            used_bytes: (self.total_bytes() - self.available_bytes()) as u64,
+            waits: self.waits.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_reserve_waits_until_released() {
+        let budget = MemoryBudget::new(64 * 1024);
+        let first = budget.reserve(48 * 1024).await;
+        assert_eq!(budget.status().used_bytes, 48 * 1024);
+
+        // 남은 16KB로는 부족하므로 첫 예약이 반환될 때까지 대기
+        let waiting = tokio::spawn({
+            let budget = budget.clone();
+            async move { budget.reserve(32 * 1024).await }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!waiting.is_finished());
+
+        drop(first);
+        let second = waiting.await.unwrap();
+        assert_eq!(budget.status().used_bytes, 32 * 1024);
+        assert_eq!(budget.status().waits, 1);
+        drop(second);
+
+        // 예산보다 큰 요청은 예산 전체를 예약
+        let whole = budget.reserve(1024 * 1024).await;
+        assert_eq!(budget.available_bytes(), 0);
+        drop(whole);
+        assert_eq!(budget.status().used_bytes, 0);
+    }
+}
@@ -1,13 +1,50 @@
+pub mod audit;
+pub mod auto_accept;
+pub mod benchmark;
+pub mod block_pool;
+pub mod broadcast;
+pub mod control_stream;
+pub mod dir_stream;
+pub mod disk_space;
+pub mod estimate;
+pub mod file_attrs;
 pub mod file_transfer;
+pub mod http_share;
+pub mod job_manifest;
+pub mod media_preview;
+pub mod memory_budget;
+pub mod multi_source;
 pub mod multistream;
+pub mod pacer;
+pub mod pairing;
+pub mod part_file;
+pub mod path_filter;
+pub mod payload_crypto;
+pub mod quota;
+pub mod range_request;
+pub mod registry;
+pub mod reliable_udp;
+#[cfg(feature = "storage-s3")]
+pub mod s3;
+pub mod sparse;
+pub mod storage;
+pub mod stream_tuner;
+pub mod text_share;
+pub mod tus;
 pub mod udp_core;
+pub mod web_protocol;
+pub mod webrtc_channel;
 pub mod zero_copy_io;
 pub mod zip_stream;
 
 pub use file_transfer::{
-    FileStreamManager, FileTransferEngine, TransferManifest, TransferProgress, TransferState,
+    FileStreamManager, FileTransferEngine, FolderSendOptions, OverwritePolicy, SymlinkPolicy,
+    TransferManifest, TransferProgress, TransferState,
 };
 pub use multistream::{MultiStreamProgress, MultiStreamReceiver, MultiStreamSender};
+pub use registry::{JobControl, TransferKind, TransferRegistry};
+pub use reliable_udp::{ReliableUdpReceiver, ReliableUdpSender, TransferTransport};
+pub use text_share::ReceivedText;
 pub use udp_core::{TransferStats, UdpTransferCore};
 pub use zero_copy_io::{IoMethod, ZeroCopyEngine};
 
@@ -0,0 +1,578 @@
+//! 다중 소스 다운로드
+//!
+//! 같은 파일(Info Hash)을 가진 여러 피어에게서 직접 QUIC 연결로 서로 다른 조각을 동시에 받습니다.
+//! 조각은 Grid 메타데이터(`FileMetadata`)의 해시로 검증한 뒤 멀티스트림 수신과 같은
+//! `MappedFileReceiver`로 `.pswp-part` 파일에 쓰며, 남은 조각을 공유 대기열에서 꺼내므로
+//! 빠른 피어가 더 많은 조각을 가져갑니다.
+//! 요청/응답은 제어 스트림(`control_stream`)의 `PieceRequest`/`PieceResponse` 명령입니다.
+
+use super::control_stream::{self, IncomingStream};
+use super::file_transfer::safe_destination;
+use super::multistream::MultiStreamProgress;
+use super::part_file;
+use super::registry::JobControl;
+use super::zero_copy_io::MappedFileReceiver;
+use crate::grid::piece_manager::{FileMetadata, PieceManager};
+use crate::protocol::Command;
+use anyhow::{anyhow, Result};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinSet;
+use tracing::{info, warn};
+
+/// 조각 하나를 기다리는 최대 시간
+pub const PIECE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 피어당 동시에 요청하는 조각 수
+pub const REQUESTS_PER_PEER: usize = 4;
+
+/// 이 횟수만큼 실패한 피어는 다운로드에서 제외
+pub const MAX_PEER_FAILURES: u32 = 3;
+
+/// 메타데이터 응답 최대 크기 (조각 해시 목록 포함 JSON)
+const MAX_METADATA_BYTES: usize = 64 * 1024 * 1024;
+
+/// 응답을 받지 못한 요청을 중단할 때의 QUIC 에러 코드
+const STOP_UNKNOWN: u32 = 1;
+
+/// 다른 피어가 조각을 요청할 수 있도록 공유한 파일
+struct SharedFile {
+    metadata: FileMetadata,
+    path: PathBuf,
+}
+
+/// 응답을 기다리는 요청
+struct PendingRequest {
+    max_len: usize,
+    tx: oneshot::Sender<Option<Vec<u8>>>,
+}
+
+/// 피어별 기여도
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceStats {
+    pub peer_id: String,
+    pub pieces: u32,
+    pub bytes: u64,
+    pub failures: u32,
+}
+
+/// 공유 파일 목록과 진행 중인 조각 요청 관리 (연결 전체에서 하나)
+#[derive(Default)]
+pub struct MultiSourceManager {
+    shared: Mutex<HashMap<[u8; 32], Arc<SharedFile>>>,
+    pending: Mutex<HashMap<String, PendingRequest>>,
+}
+
+impl MultiSourceManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 파일을 조각 요청에 응답할 수 있도록 공유
+    pub fn share(&self, metadata: FileMetadata, path: PathBuf) {
+        info!(
+            "📤 다중 소스 공유: {} ({})",
+            metadata.file_name,
+            metadata.info_hash_hex()
+        );
+        self.shared
+            .lock()
+            .insert(metadata.info_hash, Arc::new(SharedFile { metadata, path }));
+    }
+
+    /// 공유 해제
+    pub fn unshare(&self, info_hash: &[u8; 32]) -> bool {
+        self.shared.lock().remove(info_hash).is_some()
+    }
+
+    /// 공유 중인 파일의 메타데이터
+    pub fn shared_metadata(&self, info_hash: &[u8; 32]) -> Option<FileMetadata> {
+        self.shared
+            .lock()
+            .get(info_hash)
+            .map(|file| file.metadata.clone())
+    }
+
+    /// 제어 스트림의 `PieceRequest`/`PieceResponse` 처리
+    pub async fn handle_incoming(&self, conn: quinn::Connection, incoming: IncomingStream) {
+        let IncomingStream {
+            peer_id,
+            command,
+            mut recv,
+        } = incoming;
+
+        match command {
+            Command::PieceRequest {
+                request_id,
+                info_hash,
+                piece,
+            } => {
+                let _ = control_stream::finish_header_only(&mut recv).await;
+                let payload = self.serve(&info_hash, piece).await;
+                if let Err(e) = respond(&conn, request_id, payload).await {
+                    warn!("조각 응답 전송 실패 ({}): {}", peer_id, e);
+                }
+            }
+            Command::PieceResponse {
+                request_id, found, ..
+            } => {
+                let Some(pending) = self.pending.lock().remove(&request_id) else {
+                    let _ = recv.stop(STOP_UNKNOWN.into());
+                    return;
+                };
+                let data = if found {
+                    match recv.read_to_end(pending.max_len).await {
+                        Ok(data) => Some(data),
+                        Err(e) => {
+                            warn!("조각 응답 읽기 실패 ({}): {}", peer_id, e);
+                            None
+                        }
+                    }
+                } else {
+                    let _ = control_stream::finish_header_only(&mut recv).await;
+                    None
+                };
+                let _ = pending.tx.send(data);
+            }
+            _ => {}
+        }
+    }
+
+    /// 요청받은 조각(또는 `piece`가 없으면 메타데이터 JSON) 읽기
+    async fn serve(&self, info_hash: &str, piece: Option<u32>) -> Option<Vec<u8>> {
+        let hash: [u8; 32] = hex::decode(info_hash).ok()?.try_into().ok()?;
+        let file = self.shared.lock().get(&hash).cloned()?;
+
+        match piece {
+            None => serde_json::to_vec(&file.metadata).ok(),
+            Some(index) => {
+                let result = tokio::task::spawn_blocking(move || {
+                    read_piece(&file.path, &file.metadata, index as usize)
+                })
+                .await;
+                match result {
+                    Ok(Ok(data)) => Some(data),
+                    Ok(Err(e)) => {
+                        warn!("공유 조각 읽기 실패 ({} #{}): {}", info_hash, index, e);
+                        None
+                    }
+                    Err(_) => None,
+                }
+            }
+        }
+    }
+
+    /// 피어에게 요청을 보내고 응답 페이로드 대기 (피어에게 없으면 None)
+    async fn request(
+        &self,
+        conn: &quinn::Connection,
+        info_hash: &[u8; 32],
+        piece: Option<u32>,
+        max_len: usize,
+    ) -> Result<Option<Vec<u8>>> {
+        let request_id = uuid::Uuid::new_v4().to_string();
+        let (tx, rx) = oneshot::channel();
+        self.pending
+            .lock()
+            .insert(request_id.clone(), PendingRequest { max_len, tx });
+
+        let result = async {
+            let command = Command::PieceRequest {
+                request_id: request_id.clone(),
+                info_hash: hex::encode(info_hash),
+                piece,
+            };
+            let mut send = control_stream::open(conn, &command).await?;
+            send.finish()?;
+            tokio::time::timeout(PIECE_TIMEOUT, rx)
+                .await
+                .map_err(|_| anyhow!("응답 시간 초과"))?
+                .map_err(|_| anyhow!("응답 대기 중단"))
+        }
+        .await;
+
+        self.pending.lock().remove(&request_id);
+        result
+    }
+
+    /// 피어에게서 파일 메타데이터 받기
+    pub async fn fetch_metadata(
+        &self,
+        conn: &quinn::Connection,
+        info_hash: &[u8; 32],
+    ) -> Result<Option<FileMetadata>> {
+        let Some(data) = self
+            .request(conn, info_hash, None, MAX_METADATA_BYTES)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let metadata: FileMetadata = serde_json::from_slice(&data)?;
+        if metadata.info_hash != *info_hash
+            || !metadata.is_complete()
+            || FileMetadata::compute_info_hash(&metadata.piece_hashes) != *info_hash
+        {
+            return Err(anyhow!("Info Hash와 맞지 않는 메타데이터"));
+        }
+        Ok(Some(metadata))
+    }
+}
+
+/// 요청 응답 전송 (헤더 뒤에 페이로드)
+async fn respond(
+    conn: &quinn::Connection,
+    request_id: String,
+    payload: Option<Vec<u8>>,
+) -> Result<()> {
+    let command = Command::PieceResponse {
+        request_id,
+        found: payload.is_some(),
+        size: payload.as_ref().map_or(0, |data| data.len() as u64),
+    };
+    let mut send = control_stream::open(conn, &command).await?;
+    if let Some(data) = payload {
+        send.write_all(&data).await?;
+    }
+    send.finish()?;
+    Ok(())
+}
+
+/// 조각의 파일 내 위치 (오프셋, 길이)
+fn piece_range(metadata: &FileMetadata, index: usize) -> Option<(u64, usize)> {
+    if index >= metadata.total_pieces {
+        return None;
+    }
+    let offset = index as u64 * metadata.piece_size as u64;
+    let len = (metadata.file_size - offset).min(metadata.piece_size as u64);
+    Some((offset, len as usize))
+}
+
+/// 원본 파일에서 조각 읽기
+fn read_piece(path: &Path, metadata: &FileMetadata, index: usize) -> std::io::Result<Vec<u8>> {
+    let (offset, len) = piece_range(metadata, index)
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "잘못된 조각 번호"))?;
+    let mut file = std::fs::File::open(path)?;
+    file.seek(SeekFrom::Start(offset))?;
+    let mut data = vec![0u8; len];
+    file.read_exact(&mut data)?;
+    Ok(data)
+}
+
+/// 여러 피어에게서 한 파일을 나눠 받는 다운로드
+pub struct MultiSourceDownload {
+    manager: Arc<MultiSourceManager>,
+    metadata: FileMetadata,
+    sources: Vec<(String, quinn::Connection)>,
+    requests_per_peer: usize,
+    job_control: Option<JobControl>,
+    progress_tx: Option<mpsc::Sender<MultiStreamProgress>>,
+}
+
+/// 조각 작업자들이 공유하는 상태
+struct DownloadState {
+    pieces: Arc<PieceManager>,
+    writer: MappedFileReceiver,
+    queue: Mutex<VecDeque<usize>>,
+    completed: AtomicUsize,
+    bytes: AtomicU64,
+    active_peers: AtomicU32,
+    started_at: Instant,
+}
+
+impl MultiSourceDownload {
+    pub fn new(
+        manager: Arc<MultiSourceManager>,
+        metadata: FileMetadata,
+        sources: Vec<(String, quinn::Connection)>,
+    ) -> Self {
+        Self {
+            manager,
+            metadata,
+            sources,
+            requests_per_peer: REQUESTS_PER_PEER,
+            job_control: None,
+            progress_tx: None,
+        }
+    }
+
+    pub fn with_requests_per_peer(mut self, count: usize) -> Self {
+        self.requests_per_peer = count.max(1);
+        self
+    }
+
+    pub fn with_job_control(mut self, control: JobControl) -> Self {
+        self.job_control = Some(control);
+        self
+    }
+
+    pub fn with_progress_channel(mut self, tx: mpsc::Sender<MultiStreamProgress>) -> Self {
+        self.progress_tx = Some(tx);
+        self
+    }
+
+    /// `save_dir`에 받아 저장 (저장 경로와 피어별 기여도 반환)
+    pub async fn download(
+        self,
+        save_dir: &Path,
+        job_id: &str,
+    ) -> Result<(PathBuf, Vec<SourceStats>)> {
+        if !self.metadata.is_complete() {
+            return Err(anyhow!("조각 해시가 없는 메타데이터"));
+        }
+        if self.sources.is_empty() {
+            return Err(anyhow!("다운로드할 피어가 없습니다"));
+        }
+
+        let save_path = safe_destination(save_dir, &self.metadata.file_name)?;
+        if let Some(parent) = save_path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let part_path = part_file::part_path(&save_path);
+        let file_size = self.metadata.file_size;
+        let total_pieces = self.metadata.total_pieces;
+
+        info!(
+            "📥 다중 소스 다운로드 시작: {} ({} bytes, {} 조각, 피어 {}개)",
+            self.metadata.file_name,
+            file_size,
+            total_pieces,
+            self.sources.len()
+        );
+
+        let writer = {
+            let part_path = part_path.clone();
+            tokio::task::spawn_blocking(move || {
+                MappedFileReceiver::create(part_path, file_size, false)
+            })
+            .await??
+        };
+        let state = Arc::new(DownloadState {
+            pieces: Arc::new(PieceManager::new(self.metadata.clone())),
+            writer,
+            queue: Mutex::new((0..total_pieces).collect()),
+            completed: AtomicUsize::new(0),
+            bytes: AtomicU64::new(0),
+            active_peers: AtomicU32::new(self.sources.len() as u32),
+            started_at: Instant::now(),
+        });
+
+        let mut peers = JoinSet::new();
+        for (peer_id, conn) in self.sources.iter().cloned() {
+            let worker = Arc::new(PeerWorker {
+                manager: self.manager.clone(),
+                state: state.clone(),
+                peer_id,
+                conn,
+                job_control: self.job_control.clone(),
+                progress_tx: self.progress_tx.clone(),
+                job_id: job_id.to_string(),
+                pieces: AtomicU32::new(0),
+                bytes: AtomicU64::new(0),
+                failures: AtomicU32::new(0),
+            });
+            peers.spawn(worker.run(self.requests_per_peer));
+        }
+
+        let mut stats = Vec::new();
+        while let Some(result) = peers.join_next().await {
+            if let Ok(peer_stats) = result {
+                info!(
+                    "📊 피어 기여도 {}: {} 조각, {} bytes, 실패 {}",
+                    peer_stats.peer_id, peer_stats.pieces, peer_stats.bytes, peer_stats.failures
+                );
+                stats.push(peer_stats);
+            }
+        }
+
+        let cancelled = self.job_control.as_ref().is_some_and(|c| c.is_cancelled());
+        let completed = state.completed.load(Ordering::Relaxed);
+        if cancelled || completed < total_pieces {
+            drop(state);
+            part_file::discard(&part_path);
+            if cancelled {
+                return Err(anyhow!("사용자에 의해 취소됨"));
+            }
+            return Err(anyhow!(
+                "남은 조각을 받을 수 있는 피어가 없습니다 ({}/{})",
+                completed,
+                total_pieces
+            ));
+        }
+
+        let elapsed = state.started_at.elapsed();
+        let state = Arc::try_unwrap(state).map_err(|_| anyhow!("수신 상태가 아직 사용 중"))?;
+        {
+            let save_path = save_path.clone();
+            tokio::task::spawn_blocking(move || -> Result<()> {
+                state.writer.sync()?;
+                drop(state);
+                part_file::commit(&part_path, &save_path)?;
+                Ok(())
+            })
+            .await??;
+        }
+
+        info!(
+            "✅ 다중 소스 다운로드 완료: {:?} ({:.1}s)",
+            save_path,
+            elapsed.as_secs_f64()
+        );
+        Ok((save_path, stats))
+    }
+}
+
+/// 피어 하나의 조각 작업자
+struct PeerWorker {
+    manager: Arc<MultiSourceManager>,
+    state: Arc<DownloadState>,
+    peer_id: String,
+    conn: quinn::Connection,
+    job_control: Option<JobControl>,
+    progress_tx: Option<mpsc::Sender<MultiStreamProgress>>,
+    job_id: String,
+    pieces: AtomicU32,
+    bytes: AtomicU64,
+    failures: AtomicU32,
+}
+
+impl PeerWorker {
+    /// 동시 요청 `lanes`개로 대기열이 빌 때까지 조각을 받음
+    async fn run(self: Arc<Self>, lanes: usize) -> SourceStats {
+        let mut set = JoinSet::new();
+        for _ in 0..lanes {
+            set.spawn(self.clone().run_lane());
+        }
+        while set.join_next().await.is_some() {}
+        self.state.active_peers.fetch_sub(1, Ordering::Relaxed);
+
+        SourceStats {
+            peer_id: self.peer_id.clone(),
+            pieces: self.pieces.load(Ordering::Relaxed),
+            bytes: self.bytes.load(Ordering::Relaxed),
+            failures: self.failures.load(Ordering::Relaxed),
+        }
+    }
+
+    async fn run_lane(self: Arc<Self>) {
+        let total_pieces = self.state.pieces.total_pieces();
+        loop {
+            if let Some(control) = &self.job_control {
+                if control.checkpoint().await.is_err() {
+                    break;
+                }
+            }
+            if self.failures.load(Ordering::Relaxed) >= MAX_PEER_FAILURES {
+                warn!("⚠️ 실패가 많은 피어 제외: {}", self.peer_id);
+                break;
+            }
+
+            let next = self.state.queue.lock().pop_front();
+            let Some(index) = next else {
+                // 다른 피어가 받는 중인 조각이 실패해 대기열로 돌아올 수 있음
+                if self.state.completed.load(Ordering::Relaxed) >= total_pieces {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                continue;
+            };
+
+            match self.fetch_piece(index).await {
+                Ok(len) => {
+                    self.pieces.fetch_add(1, Ordering::Relaxed);
+                    self.bytes.fetch_add(len, Ordering::Relaxed);
+                    self.state.completed.fetch_add(1, Ordering::Relaxed);
+                    self.state.bytes.fetch_add(len, Ordering::Relaxed);
+                    self.report_progress();
+                }
+                Err(e) => {
+                    warn!("조각 #{} 수신 실패 ({}): {}", index, self.peer_id, e);
+                    self.failures.fetch_add(1, Ordering::Relaxed);
+                    self.state.queue.lock().push_back(index);
+                }
+            }
+        }
+    }
+
+    /// 조각 요청, 해시 검증 후 임시 파일에 기록 (받은 바이트 수)
+    async fn fetch_piece(&self, index: usize) -> Result<u64> {
+        let metadata = self.state.pieces.get_metadata();
+        let (offset, len) =
+            piece_range(metadata, index).ok_or_else(|| anyhow!("잘못된 조각 번호"))?;
+        let data = self
+            .manager
+            .request(&self.conn, &metadata.info_hash, Some(index as u32), len)
+            .await?
+            .ok_or_else(|| anyhow!("피어에게 없는 조각"))?;
+        if !self.state.pieces.verify_piece(index, &data) {
+            return Err(anyhow!("조각 해시 불일치"));
+        }
+
+        let state = self.state.clone();
+        tokio::task::spawn_blocking(move || state.writer.write_block_at(offset, &data)).await??;
+        Ok(len as u64)
+    }
+
+    fn report_progress(&self) {
+        let Some(tx) = &self.progress_tx else {
+            return;
+        };
+        let state = &self.state;
+        let bytes = state.bytes.load(Ordering::Relaxed);
+        let elapsed = state.started_at.elapsed().as_secs_f64();
+        let _ = tx.try_send(MultiStreamProgress {
+            job_id: self.job_id.clone(),
+            blocks_completed: state.completed.load(Ordering::Relaxed) as u32,
+            total_blocks: state.pieces.total_pieces() as u32,
+            bytes_transferred: bytes,
+            acknowledged_bytes: bytes,
+            total_bytes: state.pieces.get_metadata().file_size,
+            active_streams: state.active_peers.load(Ordering::Relaxed),
+            speed_bps: if elapsed > 0.0 {
+                (bytes as f64 / elapsed) as u64
+            } else {
+                0
+            },
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sha2::Digest;
+
+    #[test]
+    fn test_read_piece_matches_metadata() {
+        let path =
+            std::env::temp_dir().join(format!("ponswarp-multi-source-{}", std::process::id()));
+        let data: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&path, &data).unwrap();
+
+        let hashes: Vec<[u8; 32]> = data
+            .chunks(4096)
+            .map(|chunk| sha2::Sha256::digest(chunk).into())
+            .collect();
+        let metadata =
+            FileMetadata::from_piece_hashes("data.bin".to_string(), 10_000, 4096, hashes);
+        let pieces = PieceManager::new(metadata.clone());
+
+        // 마지막 조각은 남은 길이만큼
+        assert_eq!(piece_range(&metadata, 2), Some((8192, 1808)));
+        assert_eq!(piece_range(&metadata, 3), None);
+        for index in 0..3 {
+            let piece = read_piece(&path, &metadata, index).unwrap();
+            assert!(pieces.verify_piece(index, &piece));
+        }
+        assert!(!pieces.verify_piece(0, &data[1..4097]));
+
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -11,15 +11,29 @@
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::{HashMap, VecDeque};
-use std::path::PathBuf;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::io::AsyncWriteExt;
 use tokio::sync::{mpsc, RwLock, Semaphore};
 use tracing::{debug, info, warn};
 
-use super::zero_copy_io::{BlockInfo, HighPerformanceFileSender};
+use super::block_pool::{BlockBufferPool, PooledBuffer};
+use super::file_attrs::FileAttributes;
+use super::file_transfer::safe_destination;
+use super::job_manifest::JobManifestStore;
+use super::memory_budget::MemoryBudget;
+use super::part_file;
+use super::payload_crypto::{self, PayloadCipher};
+use super::registry::JobControl;
+use super::sparse::{self, HoleRange};
+use super::stream_tuner::{FlowWindow, StreamTuner};
+use super::zero_copy_io::{BlockInfo, HighPerformanceFileSender, MappedFileReceiver};
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+use super::zero_copy_io::{IoMethod, ZeroCopyEngine};
 
 /// 동시 스트림 수 (QUIC max_concurrent_bidi_streams와 연동)
 pub const MAX_CONCURRENT_STREAMS: usize = 32;
@@ -27,6 +41,41 @@ pub const MAX_CONCURRENT_STREAMS: usize = 32;
 /// 기본 블록 크기
 pub const DEFAULT_BLOCK_SIZE: usize = 8 * 1024 * 1024;
 
+/// 블록 크기 하한/상한 (자동 결정과 사용자 지정 모두 이 범위)
+pub const MIN_BLOCK_SIZE: usize = 256 * 1024;
+pub const MAX_BLOCK_SIZE: usize = 16 * 1024 * 1024;
+
+/// 연결당 QUIC 양방향 스트림 한도 (client/server transport 설정과 동일)
+pub const QUIC_STREAM_LIMIT: usize = 128;
+
+/// 완료 후 감사에서 누락/손상 블록 재전송을 반복하는 최대 횟수
+const MAX_REPAIR_ROUNDS: u32 = 3;
+
+/// DONE 이후 수신 측 감사 응답 대기 시간
+const AUDIT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 동시 스트림 수 재조정 주기
+const TUNE_INTERVAL: Duration = Duration::from_millis(500);
+
+/// 쓰기 태스크 대기열 길이 (블록 단위, 가득 차면 수신이 ACK를 늦춰 송신 측을 늦춤)
+const WRITE_QUEUE_BLOCKS: usize = 8;
+
+/// 쓰기 대기열 여유 슬롯이 이 이하이면 ACK 대신 `BSLW`로 송신 측에 감속 요청
+const FLOW_CONTROL_SLACK: usize = WRITE_QUEUE_BLOCKS / 4;
+
+/// 한 번에 합쳐 쓰는 최대 블록 수 (pwritev iovec 수)
+const MAX_COALESCED_BLOCKS: usize = 16;
+
+/// 쓰기 태스크의 주기적 플러시 간격
+const WRITE_FLUSH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 수신 측 블록 버퍼 풀 크기 (대기열 + 수신 중인 블록)
+const RECEIVE_POOL_BUFFERS: usize = WRITE_QUEUE_BLOCKS + 2;
+
+/// 비동기 블록 리더(io_uring 링 / Overlapped 핸들) 최대 개수
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+const IO_READERS: usize = 4;
+
 /// 멀티스트림 전송 매니페스트
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MultiStreamManifest {
@@ -36,6 +85,21 @@ pub struct MultiStreamManifest {
     pub block_size: u32,
     pub total_blocks: u32,
     pub checksum: Option<String>,
+    /// 수정/생성 시각, 권한 등 (수신 측이 적용)
+    #[serde(default)]
+    pub attributes: Option<FileAttributes>,
+    /// 희소 파일의 구멍 구간 (구멍에 완전히 들어가는 블록은 전송하지 않음)
+    #[serde(default)]
+    pub holes: Vec<HoleRange>,
+    /// 송신 측이 `.pswp-part` 이어받기를 지원하는지 (수신 측이 기존 블록 SHA-256을 보내도 되는지)
+    #[serde(default)]
+    pub resumable: bool,
+    /// 블록 데이터가 페어링 키로 암호화되어 있는지 (블록 뒤에 nonce + 태그)
+    #[serde(default)]
+    pub encrypted: bool,
+    /// 송신 측이 수신 측 흐름 제어(`BSLW` ACK)를 이해하는지
+    #[serde(default)]
+    pub flow_control: bool,
 }
 
 /// 블록 헤더 (각 스트림의 첫 부분에 전송)
@@ -62,7 +126,7 @@ impl BlockHeader {
 ///
 /// 순간 속도 스파이크를 필터링하고 부드러운 UI 업데이트를 제공합니다.
 /// 2초 윈도우 기반 이동 평균 알고리즘을 사용합니다.
-struct SpeedCalculator {
+pub(super) struct SpeedCalculator {
     /// (시간, ACK된 바이트 수) 쌍의 윈도우
     window: VecDeque<(Instant, u64)>,
     /// 윈도우 유지 시간 (기본 2초)
@@ -70,7 +134,7 @@ struct SpeedCalculator {
 }
 
 impl SpeedCalculator {
-    fn new(window_duration_secs: u64) -> Self {
+    pub(super) fn new(window_duration_secs: u64) -> Self {
         Self {
             window: VecDeque::with_capacity(100),
             window_duration: Duration::from_secs(window_duration_secs),
@@ -78,7 +142,7 @@ impl SpeedCalculator {
     }
 
     /// 새로운 ACK 데이터를 추가합니다
-    fn update(&mut self, acked_bytes: u64) {
+    pub(super) fn update(&mut self, acked_bytes: u64) {
         let now = Instant::now();
         self.window.push_back((now, acked_bytes));
 
@@ -94,7 +158,7 @@ impl SpeedCalculator {
 
     /// 현재 속도를 계산합니다 (bytes/sec)
     /// 데이터가 충분하지 않으면 0을 반환합니다
-    fn get_speed(&self) -> u64 {
+    pub(super) fn get_speed(&self) -> u64 {
         if self.window.len() < 2 {
             return 0;
         }
@@ -111,7 +175,7 @@ impl SpeedCalculator {
     }
 
     /// 윈도우를 초기화합니다
-    fn reset(&mut self) {
+    pub(super) fn reset(&mut self) {
         self.window.clear();
     }
 }
@@ -141,9 +205,20 @@ pub struct MultiStreamProgress {
 /// 멀티스트림 파일 전송기 (Sender)
 pub struct MultiStreamSender {
     conn: quinn::Connection,
-    block_size: usize,
+    /// 고정 블록 크기 (None이면 파일 크기에 맞춰 자동 결정)
+    block_size: Option<usize>,
     max_concurrent: usize,
+    /// 동시 스트림 수 자동 조절 (max_concurrent는 상한)
+    auto_tune: bool,
+    /// 레지스트리 작업 제어 (취소/일시정지)
+    job_control: Option<JobControl>,
     progress_tx: Option<mpsc::Sender<MultiStreamProgress>>,
+    /// 페이로드 암호화 (페어링된 피어)
+    cipher: Option<Arc<PayloadCipher>>,
+    /// 보낸 매니페스트 기록 (내보내기용)
+    manifest_store: Option<Arc<JobManifestStore>>,
+    /// 블록 버퍼를 예약할 앱 전체 메모리 예산
+    memory_budget: Option<Arc<MemoryBudget>>,
     /// Sliding Window 속도 계산기 (Patch 2)
     speed_calculator: Arc<RwLock<SpeedCalculator>>,
 }
@@ -152,42 +227,107 @@ impl MultiStreamSender {
     pub fn new(conn: quinn::Connection) -> Self {
         Self {
             conn,
-            block_size: DEFAULT_BLOCK_SIZE,
+            block_size: None,
             max_concurrent: MAX_CONCURRENT_STREAMS,
+            auto_tune: true,
+            job_control: None,
             progress_tx: None,
+            cipher: None,
+            manifest_store: None,
+            memory_budget: None,
             // 2초 윈도우 기반 속도 계산기 초기화
             speed_calculator: Arc::new(RwLock::new(SpeedCalculator::new(2))),
         }
     }
 
-    /// 블록 크기 설정 (수동)
+    /// 블록 크기 고정 (설정하지 않으면 파일 크기에 맞춰 자동 결정)
     pub fn with_block_size(mut self, size: usize) -> Self {
-        self.block_size = size;
+        self.block_size = Some(size);
         self
     }
 
-    /// 동시 스트림 수 설정
+    /// 동시 스트림 수 설정 (자동 조절 시 상한)
     pub fn with_max_concurrent(mut self, count: usize) -> Self {
         self.max_concurrent = count;
         self
     }
 
+    /// 동시 스트림 수 자동 조절 여부 (false면 항상 max_concurrent개)
+    pub fn with_auto_tune(mut self, enabled: bool) -> Self {
+        self.auto_tune = enabled;
+        self
+    }
+
+    /// 작업 제어 핸들 설정 (블록마다 취소/일시정지 확인)
+    pub fn with_job_control(mut self, control: JobControl) -> Self {
+        self.job_control = Some(control);
+        self
+    }
+
     /// 진행률 채널 설정
     pub fn with_progress_channel(mut self, tx: mpsc::Sender<MultiStreamProgress>) -> Self {
         self.progress_tx = Some(tx);
         self
     }
 
+    /// 블록 데이터를 페어링 키로 암호화해 전송
+    pub fn with_cipher(mut self, cipher: Option<Arc<PayloadCipher>>) -> Self {
+        self.cipher = cipher;
+        self
+    }
+
+    /// 보낸 매니페스트를 기록해 나중에 내보낼 수 있게 함
+    pub fn with_manifest_store(mut self, store: Arc<JobManifestStore>) -> Self {
+        self.manifest_store = Some(store);
+        self
+    }
+
+    /// 블록 버퍼를 앱 전체 메모리 예산 안에서 할당
+    pub fn with_memory_budget(mut self, budget: Arc<MemoryBudget>) -> Self {
+        self.memory_budget = Some(budget);
+        self
+    }
+
     /// 파일 전송 (멀티스트림 + Zero-Copy + Adaptive Block)
     pub async fn send_file(&self, file_path: PathBuf, job_id: &str) -> Result<u64> {
+        let file_sender = self.open_reader(&file_path)?;
+        self.send_file_with_reader(file_path, file_sender, job_id)
+            .await
+    }
+
+    /// 전송용 파일 리더 열기
+    ///
+    /// 여러 피어에게 같은 파일을 보낼 때는 리더 하나(mmap/링)를 `send_file_with_reader`로 공유합니다.
+    pub fn open_reader(&self, file_path: &Path) -> Result<Arc<HighPerformanceFileSender>> {
         // Zero-Copy Sender 초기화
         // 여기서 임시 block_size로 열고, 파일 크기 확인 후 재조정은 불가능하므로(open시 mmap하진 않음)
         // 먼저 파일 크기를 확인하는 것이 좋지만, HighPerformanceFileSender가 크기를 줌.
         // open 자체는 비용이 낮으므로 일단 open.
-        let file_sender = Arc::new(HighPerformanceFileSender::open(
-            &file_path,
-            self.block_size,
-        )?);
+        let mut file_sender = HighPerformanceFileSender::open(
+            file_path,
+            self.block_size.unwrap_or(DEFAULT_BLOCK_SIZE),
+        )?;
+
+        // io_uring 사용 가능 시 동시 스트림 수만큼(최대 IO_READERS) 링을 만들어 읽기
+        #[cfg(target_os = "linux")]
+        if ZeroCopyEngine::new().io_method() == IoMethod::IoUring {
+            file_sender.enable_io_uring(self.max_concurrent.min(IO_READERS));
+        }
+        // Windows: Overlapped + 비버퍼 읽기 (실패 시 Buffered)
+        #[cfg(target_os = "windows")]
+        if ZeroCopyEngine::new().io_method() == IoMethod::OverlappedIo {
+            file_sender.enable_overlapped_io(self.max_concurrent.min(IO_READERS));
+        }
+        Ok(Arc::new(file_sender))
+    }
+
+    /// 이미 연 리더로 파일 전송
+    pub async fn send_file_with_reader(
+        &self,
+        file_path: PathBuf,
+        file_sender: Arc<HighPerformanceFileSender>,
+        job_id: &str,
+    ) -> Result<u64> {
         let file_size = file_sender.file_size();
 
         // --- Patch 3: Adaptive Block Size ---
@@ -199,6 +339,24 @@ impl MultiStreamSender {
         let blocks = file_sender.get_blocks(optimal_block_size);
         let total_blocks = blocks.len() as u32;
 
+        // 희소 파일: 구멍 블록은 보내지 않고 완료된 것으로 집계
+        let holes = sparse::detect_holes(&file_path, file_size).unwrap_or_else(|e| {
+            warn!("구멍 구간 조회 실패 (일반 파일로 전송): {}", e);
+            Vec::new()
+        });
+        let hole_blocks = sparse::hole_blocks(&holes, file_size, optimal_block_size);
+        let hole_bytes: u64 = hole_blocks
+            .iter()
+            .map(|&index| blocks[index as usize].size as u64)
+            .sum();
+        if !hole_blocks.is_empty() {
+            info!(
+                "🕳️ 희소 파일: 구멍 블록 {}개 ({} bytes) 전송 생략",
+                hole_blocks.len(),
+                hole_bytes
+            );
+        }
+
         let file_name = file_path
             .file_name()
             .map(|n| n.to_string_lossy().to_string())
@@ -221,42 +379,96 @@ impl MultiStreamSender {
             block_size: optimal_block_size as u32,
             total_blocks,
             checksum: None,
+            attributes: FileAttributes::read(&file_path).ok(),
+            holes,
+            resumable: true,
+            encrypted: self.cipher.is_some(),
+            flow_control: true,
         };
+        if let Some(store) = &self.manifest_store {
+            store.record_multistream(&manifest, &file_path);
+        }
+
+        let resumed_blocks = self.send_manifest(&manifest, &file_sender, &blocks).await?;
 
-        self.send_manifest(&manifest).await?;
+        // 구멍 블록과 수신 측이 이미 가진 블록은 보내지 않고 완료된 것으로 집계
+        let mut skipped_blocks = hole_blocks;
+        skipped_blocks.extend(resumed_blocks);
+        skipped_blocks.sort_unstable();
+        skipped_blocks.dedup();
+        let skipped_bytes: u64 = skipped_blocks
+            .iter()
+            .map(|&index| blocks[index as usize].size as u64)
+            .sum();
 
         // 동시성 제어를 위한 세마포어
-        let semaphore = Arc::new(Semaphore::new(self.max_concurrent));
+        let mut tuner = StreamTuner::new(self.max_concurrent);
+        let stream_limit = Arc::new(AtomicUsize::new(if self.auto_tune {
+            tuner.current()
+        } else {
+            self.max_concurrent
+        }));
+        let semaphore = Arc::new(Semaphore::new(stream_limit.load(Ordering::Relaxed)));
+        // 수신 측이 디스크가 밀린다고 알린 횟수 (조절 주기마다 0으로 되돌림)
+        let congestion = Arc::new(AtomicUsize::new(0));
+        // 블록 버퍼 풀 (동시 스트림 수만큼 재사용)
+        let buffer_pool = BlockBufferPool::new(
+            self.max_concurrent,
+            optimal_block_size,
+            self.memory_budget.clone(),
+        );
 
         // 진행률 추적
-        let completed_blocks = Arc::new(RwLock::new(0u32));
-        let bytes_transferred = Arc::new(RwLock::new(0u64));
+        let completed_blocks = Arc::new(RwLock::new(skipped_blocks.len() as u32));
+        let bytes_transferred = Arc::new(RwLock::new(skipped_bytes));
         // --- Patch 2: Acknowledged Bytes ---
-        let bytes_acknowledged = Arc::new(RwLock::new(0u64));
+        let bytes_acknowledged = Arc::new(RwLock::new(skipped_bytes));
 
         let start_time = std::time::Instant::now();
         // 블록 전송 태스크들
         let mut handles = Vec::with_capacity(blocks.len());
 
-        for block in blocks {
+        for block in blocks.iter().cloned() {
+            if skipped_blocks.binary_search(&block.index).is_ok() {
+                continue;
+            }
             let speed_calc = self.speed_calculator.clone();
             let conn = self.conn.clone();
             let sem = semaphore.clone();
+            let limit = stream_limit.clone();
+            let congestion = congestion.clone();
+            let control = self.job_control.clone();
             let sender = file_sender.clone(); // Arc 공유
+            let pool = buffer_pool.clone();
             let job_id = job_id.to_string();
             let completed = completed_blocks.clone();
             let transferred = bytes_transferred.clone();
             let acknowledged = bytes_acknowledged.clone();
             let progress_tx = self.progress_tx.clone();
+            let cipher = self.cipher.clone();
             let total_bytes = file_size;
 
             let handle = tauri::async_runtime::spawn(async move {
                 // 세마포어 획득 (동시 스트림 수 제한)
                 let _permit = sem.acquire().await.unwrap();
 
+                // 일시정지 중이면 대기, 취소되었으면 남은 블록은 보내지 않음
+                if let Some(control) = &control {
+                    control.checkpoint().await?;
+                }
+
                 // Zero-Copy send_block 호출 (이 함수는 ACK를 기다림)
                 // ACK가 오면 Ok(size) 반환
-                let result = Self::send_block_zerocopy(&conn, &sender, &block, &job_id).await;
+                let result = Self::send_block_zerocopy(
+                    &conn,
+                    &sender,
+                    &pool,
+                    &block,
+                    &job_id,
+                    &cipher,
+                    &congestion,
+                )
+                .await;
 
                 if let Ok(sent_size) = result {
                     // 성공했다는 것은 ACK를 받았다는 것
@@ -300,7 +512,10 @@ impl MultiStreamSender {
                                 bytes_transferred: bytes_done,
                                 acknowledged_bytes: bytes_acked_val, // Patch 2 added
                                 total_bytes,
-                                active_streams: sem.available_permits() as u32, // 남은 permit이 아니라 사용중인 건 (max - available)여야 하는데 로직 수정 필요. 일단 그대로 둠.
+                                active_streams: limit
+                                    .load(Ordering::Relaxed)
+                                    .saturating_sub(sem.available_permits())
+                                    as u32,
                                 speed_bps: speed,
                             })
                             .await;
@@ -313,6 +528,70 @@ impl MultiStreamSender {
             handles.push(handle);
         }
 
+        // 동시 스트림 수 조절: 자동 조절 결과(또는 고정값)를 수신 측 흐름 제어 창으로 제한
+        // (블록 전송이 끝나면 중단)
+        let tuner_task = {
+            let conn = self.conn.clone();
+            let semaphore = semaphore.clone();
+            let limit = stream_limit.clone();
+            let congestion = congestion.clone();
+            let acknowledged = bytes_acknowledged.clone();
+            let (auto_tune, max_concurrent) = (self.auto_tune, self.max_concurrent);
+            let mut flow = FlowWindow::new(max_concurrent);
+            tauri::async_runtime::spawn(async move {
+                let mut last_acked = skipped_bytes;
+                let mut last_path = conn.stats().path;
+                let mut last_at = Instant::now();
+                loop {
+                    tokio::time::sleep(TUNE_INTERVAL).await;
+
+                    let now = Instant::now();
+                    let acked = *acknowledged.read().await;
+                    let path = conn.stats().path;
+                    let goodput =
+                        (acked - last_acked) as f64 / now.duration_since(last_at).as_secs_f64();
+                    let sent = path.sent_packets.saturating_sub(last_path.sent_packets);
+                    let lost = path.lost_packets.saturating_sub(last_path.lost_packets);
+                    let loss_rate = if sent == 0 {
+                        0.0
+                    } else {
+                        lost as f64 / sent as f64
+                    };
+                    (last_acked, last_path, last_at) = (acked, path, now);
+
+                    let target = if auto_tune {
+                        tuner.update(goodput, loss_rate)
+                    } else {
+                        max_concurrent
+                    };
+                    let slowdowns = congestion.swap(0, Ordering::Relaxed);
+                    let next = target.min(flow.update(slowdowns > 0));
+
+                    let current = limit.load(Ordering::Relaxed);
+                    if next == current {
+                        continue;
+                    }
+                    debug!(
+                        "🎛️ 동시 스트림 {} → {} (goodput {:.1} MB/s, 손실 {:.2}%, 수신 측 감속 요청 {}회)",
+                        current,
+                        next,
+                        goodput / 1_000_000.0,
+                        loss_rate * 100.0,
+                        slowdowns
+                    );
+                    limit.store(next, Ordering::Relaxed);
+                    if next > current {
+                        semaphore.add_permits(next - current);
+                    } else if let Ok(permits) =
+                        semaphore.acquire_many((current - next) as u32).await
+                    {
+                        // 진행 중인 스트림이 끝나는 대로 permit을 회수
+                        permits.forget();
+                    }
+                }
+            })
+        };
+
         // 모든 블록 전송 완료 대기
         let mut total_sent = 0u64;
         for handle in handles {
@@ -323,10 +602,65 @@ impl MultiStreamSender {
             }
         }
 
-        // 완료 신호 전송
-        self.send_completion_signal(job_id).await?;
+        tuner_task.abort();
+
+        if let Some(control) = &self.job_control {
+            control.checkpoint().await?;
+        }
+
+        // 완료 신호 전송 → 수신 측 감사에서 누락/손상 블록이 나오면 복구 스트림으로 재전송
+        let mut repair_round = 0;
+        while let Some(missing) = self.send_completion_signal(job_id, total_blocks).await? {
+            repair_round += 1;
+            if repair_round > MAX_REPAIR_ROUNDS {
+                return Err(anyhow::anyhow!(
+                    "블록 복구 실패: {}개 블록이 {}회 재전송 후에도 누락",
+                    missing.len(),
+                    MAX_REPAIR_ROUNDS
+                ));
+            }
+            warn!(
+                "🔧 블록 {}개 재전송 요청 수신 ({}차)",
+                missing.len(),
+                repair_round
+            );
+
+            let mut repairs = Vec::with_capacity(missing.len());
+            for index in missing {
+                let block = blocks[index as usize].clone();
+                let conn = self.conn.clone();
+                let sem = semaphore.clone();
+                let sender = file_sender.clone();
+                let pool = buffer_pool.clone();
+                let job_id = job_id.to_string();
+                let cipher = self.cipher.clone();
+                let congestion = congestion.clone();
+                repairs.push(tauri::async_runtime::spawn(async move {
+                    let _permit = sem.acquire().await.unwrap();
+                    Self::send_block_zerocopy(
+                        &conn,
+                        &sender,
+                        &pool,
+                        &block,
+                        &job_id,
+                        &cipher,
+                        &congestion,
+                    )
+                    .await
+                }));
+            }
+            for handle in repairs {
+                match handle.await {
+                    Ok(Ok(bytes)) => total_sent += bytes,
+                    Ok(Err(e)) => warn!("복구 블록 전송 실패: {}", e),
+                    Err(e) => warn!("태스크 실패: {}", e),
+                }
+            }
+        }
 
         info!("✅ 멀티스트림 전송 완료: {} bytes", total_sent);
+        let (reused, allocated) = buffer_pool.stats();
+        debug!("🔧 블록 버퍼 재사용 {}회 / 할당 {}회", reused, allocated);
 
         // 속도 계산기 리셋
         {
@@ -337,22 +671,31 @@ impl MultiStreamSender {
         Ok(total_sent)
     }
 
-    /// 파일 크기 기반 최적 블록 크기 계산 (Patch 3)
+    /// 파일 크기 기반 최적 블록 크기 계산 (Patch 3), 고정 크기가 있으면 그대로 사용
     fn calculate_optimal_block_size(&self, file_size: u64) -> usize {
-        const MIN_BLOCK: u64 = 256 * 1024; // 256KB
-        const MAX_BLOCK: u64 = 16 * 1024 * 1024; // 16MB
         const TARGET_PARTS: u64 = 100; // 적절한 분할 수
 
+        if let Some(size) = self.block_size {
+            return size;
+        }
         if file_size == 0 {
-            return MIN_BLOCK as usize;
+            return MIN_BLOCK_SIZE;
         }
 
         let ideal_size = file_size / TARGET_PARTS;
-        ideal_size.clamp(MIN_BLOCK, MAX_BLOCK) as usize
+        ideal_size.clamp(MIN_BLOCK_SIZE as u64, MAX_BLOCK_SIZE as u64) as usize
     }
 
     /// 매니페스트 전송 (제어 스트림)
-    async fn send_manifest(&self, manifest: &MultiStreamManifest) -> Result<()> {
+    ///
+    /// 수신 측에 이전 `.pswp-part`가 있으면 블록별 SHA-256을 보내오므로(`MRSM`),
+    /// 원본과 대조해 일치하는 블록만 확정해 돌려주고 그 블록 번호를 반환합니다.
+    async fn send_manifest(
+        &self,
+        manifest: &MultiStreamManifest,
+        file_sender: &Arc<HighPerformanceFileSender>,
+        blocks: &[BlockInfo],
+    ) -> Result<Vec<u32>> {
         let (mut send, mut recv) = self.conn.open_bi().await?;
 
         // 매니페스트 타입 마커
@@ -362,35 +705,74 @@ impl MultiStreamSender {
         let len = manifest_json.len() as u32;
         send.write_all(&len.to_le_bytes()).await?;
         send.write_all(&manifest_json).await?;
-        send.finish()?;
 
         // ACK 대기
         let mut ack = [0u8; 4];
         recv.read_exact(&mut ack).await?;
-        if &ack != b"MACK" {
-            return Err(anyhow::anyhow!("Manifest ACK failed"));
-        }
+        let resumed = match &ack {
+            b"MACK" => Vec::new(),
+            b"MRSM" => {
+                let candidates = read_block_checksums(&mut recv, manifest.total_blocks).await?;
+                let resumed = verify_existing_blocks(file_sender, blocks, candidates).await?;
+                let mut reply = Vec::with_capacity(4 + resumed.len() * 4);
+                reply.extend_from_slice(&(resumed.len() as u32).to_le_bytes());
+                for index in &resumed {
+                    reply.extend_from_slice(&index.to_le_bytes());
+                }
+                send.write_all(&reply).await?;
+                info!(
+                    "♻️ 이어받기: 수신 측 블록 {}/{}개 확인",
+                    resumed.len(),
+                    manifest.total_blocks
+                );
+                resumed
+            }
+            _ => return Err(anyhow::anyhow!("Manifest ACK failed")),
+        };
+        send.finish()?;
 
         debug!("📋 매니페스트 전송 완료");
-        Ok(())
+        Ok(resumed)
     }
 
     /// 최적화된 블록 전송 (스레드 차단 방지 적용)
+    ///
+    /// 수신 측이 `BSLW`(저장 완료, 디스크가 밀림)로 응답하면 `congestion`을 올립니다.
     async fn send_block_zerocopy(
         conn: &quinn::Connection,
         sender: &Arc<HighPerformanceFileSender>,
+        pool: &Arc<BlockBufferPool>,
         block: &BlockInfo,
         job_id: &str,
+        cipher: &Option<Arc<PayloadCipher>>,
+        congestion: &AtomicUsize,
     ) -> Result<u64> {
+        // 1. 데이터 읽기 + CRC32 (Blocking IO Isolation, 풀 버퍼 재사용)
+        let sender_clone = sender.clone();
+        let block_clone = block.clone();
+        let mut buffer = pool.acquire(block.size as usize).await;
+        let cipher = cipher.clone();
+        let aad = payload_crypto::aad(job_id, block.index as u64);
+
+        let (data, checksum, trailer) = tokio::task::spawn_blocking(move || -> Result<_> {
+            sender_clone.read_block_into(&block_clone, &mut buffer)?;
+            // 암호화하면 AEAD 태그가 무결성을 보장하므로 평문 CRC는 보내지 않음 (0 = 검사 안 함)
+            let (checksum, trailer) = match &cipher {
+                Some(cipher) => (0, Some(cipher.seal_in_place(&aad, &mut buffer)?)),
+                None => (crc32fast::hash(&buffer), None),
+            };
+            Ok((buffer, checksum, trailer))
+        })
+        .await??;
+
+        // 2. 헤더 전송
         let (mut send, mut recv) = conn.open_bi().await?;
-
-        // 1. 헤더 전송
         let header = BlockHeader {
             job_id: job_id.to_string(),
             block_index: block.index,
             offset: block.offset,
             size: block.size,
-            checksum: 0,
+            checksum,
         };
         send.write_all(b"BLCK").await?;
         let header_json = header.to_bytes();
@@ -398,15 +780,11 @@ impl MultiStreamSender {
         send.write_all(&header_len.to_le_bytes()).await?;
         send.write_all(&header_json).await?;
 
-        // 2. 데이터 읽기 (Blocking IO Isolation)
-        let sender_clone = sender.clone();
-        let block_clone = block.clone();
-
-        let data = tokio::task::spawn_blocking(move || sender_clone.read_block_owned(&block_clone))
-            .await??;
-
-        // 3. 데이터 전송
+        // 3. 데이터 전송 (암호화된 경우 nonce + 태그가 뒤따름)
         send.write_all(&data).await?;
+        if let Some(trailer) = trailer {
+            send.write_all(&trailer).await?;
+        }
         send.finish()?;
 
         // 4. ACK 대기 (Patch 2: Sync Point)
@@ -420,6 +798,9 @@ impl MultiStreamSender {
             Ok(Ok(_)) if &ack == b"BACK" => {
                 // debug!("✅ 블록 {} ACK 수신", block.index);
             }
+            Ok(Ok(_)) if &ack == b"BSLW" => {
+                congestion.fetch_add(1, Ordering::Relaxed);
+            }
             _ => {
                 warn!("⚠️ 블록 {} ACK 타임아웃", block.index);
                 // 여기서 에러를 내면 전체 재전송 로직이 필요하나,
@@ -432,26 +813,193 @@ impl MultiStreamSender {
         Ok(block.size as u64)
     }
 
-    /// 완료 신호 전송
-    async fn send_completion_signal(&self, job_id: &str) -> Result<()> {
-        let (mut send, _) = self.conn.open_bi().await?;
+    /// 완료 신호 전송 후 수신 측 감사 결과 대기
+    ///
+    /// 반환값: 재전송할 블록 인덱스 (None = 모두 수신, 또는 응답하지 않는 이전 버전 수신자)
+    async fn send_completion_signal(
+        &self,
+        job_id: &str,
+        total_blocks: u32,
+    ) -> Result<Option<Vec<u32>>> {
+        let (mut send, mut recv) = self.conn.open_bi().await?;
 
         send.write_all(b"DONE").await?;
         send.write_all(job_id.as_bytes()).await?;
         send.finish()?;
 
         debug!("🏁 완료 신호 전송");
-        Ok(())
+
+        let mut marker = [0u8; 4];
+        match tokio::time::timeout(AUDIT_TIMEOUT, recv.read_exact(&mut marker)).await {
+            Ok(Ok(())) => {}
+            _ => {
+                debug!("감사 응답 없음 (이전 버전 수신자로 간주)");
+                return Ok(None);
+            }
+        }
+
+        match &marker {
+            b"FINE" => Ok(None),
+            b"RPRQ" => {
+                let mut count_buf = [0u8; 4];
+                recv.read_exact(&mut count_buf).await?;
+                let count = u32::from_le_bytes(count_buf);
+                if count == 0 || count > total_blocks {
+                    return Err(anyhow::anyhow!("잘못된 재전송 요청: {}개", count));
+                }
+
+                let mut indices_buf = vec![0u8; count as usize * 4];
+                recv.read_exact(&mut indices_buf).await?;
+                let indices = indices_buf
+                    .chunks_exact(4)
+                    .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                    .collect::<Vec<_>>();
+                if let Some(bad) = indices.iter().find(|index| **index >= total_blocks) {
+                    return Err(anyhow::anyhow!("잘못된 블록 인덱스 재전송 요청: {}", bad));
+                }
+                Ok(Some(indices))
+            }
+            _ => Err(anyhow::anyhow!("알 수 없는 감사 응답: {:?}", marker)),
+        }
     }
 }
 
-use tokio::io::AsyncSeekExt;
+/// 쓰기 대기 블록
+struct PendingWrite {
+    offset: u64,
+    data: PooledBuffer,
+}
+
+/// 수신 블록 단일 쓰기 태스크
+///
+/// 대기열에 쌓인 블록을 모아 오프셋 순으로 정렬하고, 인접한 블록은 한 번에 씁니다.
+/// 주기적으로 플러시하고, 대기열이 닫히면 남은 블록을 쓴 뒤 한 번 fsync 합니다.
+fn spawn_block_writer(
+    writer: MappedFileReceiver,
+) -> (
+    mpsc::Sender<PendingWrite>,
+    tokio::task::JoinHandle<Result<()>>,
+) {
+    let (tx, mut rx) = mpsc::channel::<PendingWrite>(WRITE_QUEUE_BLOCKS);
+
+    let task = tokio::task::spawn_blocking(move || {
+        let mut last_flush = Instant::now();
+
+        while let Some(first) = rx.blocking_recv() {
+            let mut batch = vec![first];
+            while batch.len() < MAX_COALESCED_BLOCKS {
+                match rx.try_recv() {
+                    Ok(pending) => batch.push(pending),
+                    Err(_) => break,
+                }
+            }
+            batch.sort_by_key(|pending| pending.offset);
+
+            let extents: Vec<(u64, usize)> = batch
+                .iter()
+                .map(|pending| (pending.offset, pending.data.len()))
+                .collect();
+            for run in coalesce_runs(&extents) {
+                let parts: Vec<&[u8]> = batch[run.clone()]
+                    .iter()
+                    .map(|pending| &*pending.data)
+                    .collect();
+                writer.write_blocks_at(batch[run.start].offset, &parts)?;
+            }
+            // batch drop → 버퍼 풀로 반환
+
+            if last_flush.elapsed() >= WRITE_FLUSH_INTERVAL {
+                writer.flush_async()?;
+                last_flush = Instant::now();
+            }
+        }
+
+        writer.sync()
+    });
+
+    (tx, task)
+}
+
+/// 수신 측이 보낸 기존 블록 SHA-256 목록 (`MRSM` 뒤)
+async fn read_block_checksums(
+    recv: &mut quinn::RecvStream,
+    total_blocks: u32,
+) -> Result<Vec<(u32, [u8; 32])>> {
+    let mut count_buf = [0u8; 4];
+    recv.read_exact(&mut count_buf).await?;
+    let count = u32::from_le_bytes(count_buf);
+    if count > total_blocks {
+        return Err(anyhow::anyhow!("잘못된 이어받기 요청: {}개", count));
+    }
+
+    let mut entries = vec![0u8; count as usize * 36];
+    recv.read_exact(&mut entries).await?;
+    Ok(entries
+        .chunks_exact(36)
+        .map(|c| {
+            let mut hash = [0u8; 32];
+            hash.copy_from_slice(&c[4..]);
+            (u32::from_le_bytes([c[0], c[1], c[2], c[3]]), hash)
+        })
+        .collect())
+}
+
+/// 원본 블록을 읽어 SHA-256이 일치하는 블록 번호만 반환 (수신 측이 이미 가진 블록)
+async fn verify_existing_blocks(
+    sender: &Arc<HighPerformanceFileSender>,
+    blocks: &[BlockInfo],
+    candidates: Vec<(u32, [u8; 32])>,
+) -> Result<Vec<u32>> {
+    let sender = sender.clone();
+    let blocks = blocks.to_vec();
+    tokio::task::spawn_blocking(move || -> Result<Vec<u32>> {
+        let mut buffer = Vec::new();
+        let mut verified = Vec::new();
+        for (index, hash) in candidates {
+            let Some(block) = blocks.get(index as usize) else {
+                continue;
+            };
+            buffer.resize(block.size as usize, 0);
+            sender.read_block_into(block, &mut buffer)?;
+            if <[u8; 32]>::from(Sha256::digest(&buffer)) == hash {
+                verified.push(index);
+            }
+        }
+        verified.sort_unstable();
+        verified.dedup();
+        Ok(verified)
+    })
+    .await?
+}
+
+/// 오프셋 순으로 정렬된 (offset, len) 목록에서 서로 맞닿은 구간의 인덱스 범위
+fn coalesce_runs(extents: &[(u64, usize)]) -> Vec<Range<usize>> {
+    let mut runs = Vec::new();
+    let mut start = 0;
+    for i in 1..=extents.len() {
+        let adjacent = i < extents.len() && {
+            let (prev_offset, prev_len) = extents[i - 1];
+            prev_offset + prev_len as u64 == extents[i].0
+        };
+        if !adjacent {
+            runs.push(start..i);
+            start = i;
+        }
+    }
+    runs
+}
 
 /// 멀티스트림 파일 수신기 (Receiver)
 pub struct MultiStreamReceiver {
     conn: quinn::Connection,
     save_dir: PathBuf,
     progress_tx: Option<mpsc::Sender<MultiStreamProgress>>,
+    /// 레지스트리 작업 제어 (취소/일시정지)
+    job_control: Option<JobControl>,
+    /// 암호화된 전송을 풀 페어링 키
+    cipher: Option<Arc<PayloadCipher>>,
+    /// 블록 버퍼를 예약할 앱 전체 메모리 예산
+    memory_budget: Option<Arc<MemoryBudget>>,
     /// Sliding Window 속도 계산기 (Patch 2)
     speed_calculator: Arc<RwLock<SpeedCalculator>>,
 }
@@ -462,6 +1010,9 @@ impl MultiStreamReceiver {
             conn,
             save_dir,
             progress_tx: None,
+            job_control: None,
+            cipher: None,
+            memory_budget: None,
             // 2초 윈도우 기반 속도 계산기 초기화
             speed_calculator: Arc::new(RwLock::new(SpeedCalculator::new(2))),
         }
@@ -473,18 +1024,62 @@ impl MultiStreamReceiver {
         self
     }
 
+    /// 블록 버퍼를 앱 전체 메모리 예산 안에서 할당
+    pub fn with_memory_budget(mut self, budget: Arc<MemoryBudget>) -> Self {
+        self.memory_budget = Some(budget);
+        self
+    }
+
+    /// 작업 제어 핸들 설정 (일시정지 중엔 스트림 수락을 멈추고, 취소되면 수신 중단)
+    pub fn with_job_control(mut self, control: JobControl) -> Self {
+        self.job_control = Some(control);
+        self
+    }
+
+    /// 페어링 키 설정 (송신 측이 암호화해 보낸 경우에만 사용)
+    pub fn with_cipher(mut self, cipher: Option<Arc<PayloadCipher>>) -> Self {
+        self.cipher = cipher;
+        self
+    }
+
+    /// 다음 스트림 수락 (일시정지면 대기, 취소되면 에러)
+    async fn accept_stream(
+        &self,
+    ) -> Result<std::result::Result<(quinn::SendStream, quinn::RecvStream), quinn::ConnectionError>>
+    {
+        let Some(control) = &self.job_control else {
+            return Ok(self.conn.accept_bi().await);
+        };
+
+        control.checkpoint().await?;
+        tokio::select! {
+            accepted = self.conn.accept_bi() => Ok(accepted),
+            _ = control.cancelled() => Err(anyhow::anyhow!("사용자에 의해 취소됨")),
+        }
+    }
+
     /// 파일 수신 (멀티스트림)
     pub async fn receive_file(&self, job_id: &str) -> Result<PathBuf> {
         info!("📥 멀티스트림 수신 대기 중...");
 
-        // 매니페스트 수신
-        let manifest = self.receive_manifest().await?;
+        // 매니페스트 수신 (ACK는 이어받을 블록을 확인한 뒤 보냄)
+        let (manifest, manifest_send, manifest_recv) = self.receive_manifest().await?;
 
         if manifest.job_id != job_id {
             return Err(anyhow::anyhow!("Job ID mismatch"));
         }
+        let cipher = match (manifest.encrypted, &self.cipher) {
+            (true, None) => {
+                return Err(anyhow::anyhow!(
+                    "암호화된 전송이지만 이 피어와 페어링되어 있지 않습니다"
+                ))
+            }
+            (true, Some(cipher)) => Some(cipher.clone()),
+            (false, _) => None,
+        };
 
-        let save_path = self.save_dir.join(&manifest.file_name);
+        // 경로 조작 방지: 저장 폴더 밖으로 나가는 이름은 거부
+        let save_path = safe_destination(&self.save_dir, &manifest.file_name)?;
 
         // 저장 디렉토리 생성
         if let Some(parent) = save_path.parent() {
@@ -496,28 +1091,72 @@ impl MultiStreamReceiver {
             manifest.file_name, manifest.file_size, manifest.total_blocks
         );
 
-        // 파일 생성 및 크기 예약
-        let file = tokio::fs::OpenOptions::new()
-            .write(true)
-            .create(true)
-            .truncate(true)
-            .open(&save_path)
-            .await?;
-        file.set_len(manifest.file_size).await?;
-        drop(file);
-
-        // 블록 수신 상태 추적
-        let received_blocks = Arc::new(RwLock::new(HashMap::<u32, bool>::new()));
-        let bytes_received = Arc::new(RwLock::new(0u64));
+        // 이전 수신의 임시 파일이 남아 있으면 송신 측과 대조해 유효한 블록은 다시 받지 않음
+        let part_path = part_file::part_path(&save_path);
+        let hole_blocks = sparse::hole_blocks(
+            &manifest.holes,
+            manifest.file_size,
+            manifest.block_size as usize,
+        );
+        let resumed_blocks = Self::acknowledge_manifest(
+            &manifest,
+            &part_path,
+            &hole_blocks,
+            manifest_send,
+            manifest_recv,
+        )
+        .await?;
+
+        // 임시 파일 생성(이어받기면 기존 내용 유지), 크기 예약 및 매핑
+        // → 단일 쓰기 태스크가 파일을 열어둔 채 블록을 씀 (모든 블록을 받은 뒤에만 최종 이름으로 변경)
+        let (write_tx, write_task) = {
+            let save_path = part_path.clone();
+            let file_size = manifest.file_size;
+            let sparse = !manifest.holes.is_empty();
+            let resume = !resumed_blocks.is_empty();
+            let writer = tokio::task::spawn_blocking(move || {
+                if resume {
+                    MappedFileReceiver::open_existing(save_path, file_size)
+                } else {
+                    MappedFileReceiver::create(save_path, file_size, sparse)
+                }
+            })
+            .await??;
+            spawn_block_writer(writer)
+        };
+
+        // 블록 수신 상태 추적 (구멍 블록과 이어받은 블록은 받은 것으로 처리)
+        let received_blocks: HashMap<u32, bool> = hole_blocks
+            .iter()
+            .chain(&resumed_blocks)
+            .map(|&index| (index, true))
+            .collect();
+        let skipped_bytes: u64 = received_blocks
+            .keys()
+            .map(|&index| {
+                let offset = index as u64 * manifest.block_size as u64;
+                (manifest.file_size - offset).min(manifest.block_size as u64)
+            })
+            .sum();
+        let received_blocks = Arc::new(RwLock::new(received_blocks));
+        let bytes_received = Arc::new(RwLock::new(skipped_bytes));
         // Receiver는 수신 즉시가 Acked이므로 별도 필드 불필요 (bytes_received == bytes_acked)
 
+        // 수신 블록 버퍼 풀 (쓰기 대기 중인 블록 수만큼)
+        let buffer_pool = BlockBufferPool::new(
+            RECEIVE_POOL_BUFFERS,
+            manifest.block_size as usize,
+            self.memory_budget.clone(),
+        );
+
         let start_time = std::time::Instant::now();
         let speed_calc = self.speed_calculator.clone();
 
         // 블록 수신 루프
         let mut completed = false;
+        let mut repair_round = 0;
         while !completed {
-            match self.conn.accept_bi().await {
+            match self.accept_stream().await? {
                 Ok((mut send, mut recv)) => {
                     // 스트림 타입 확인
                     let mut marker = [0u8; 4];
@@ -528,13 +1167,30 @@ impl MultiStreamReceiver {
                     match &marker {
                         b"BLCK" => {
                             // 블록 수신
-                            let result =
-                                Self::receive_block(&mut send, &mut recv, &save_path).await;
+                            let result = Self::receive_block(
+                                &mut send,
+                                &mut recv,
+                                &manifest,
+                                &write_tx,
+                                &buffer_pool,
+                                cipher.as_deref(),
+                            )
+                            .await;
+
+                            if let Err(e) = &result {
+                                warn!("블록 수신 실패 (완료 후 재요청): {}", e);
+                            }
 
                             if let Ok((block_index, block_size)) = result {
-                                // 상태 업데이트
-                                received_blocks.write().await.insert(block_index, true);
-                                *bytes_received.write().await += block_size as u64;
+                                // 상태 업데이트 (복구로 다시 받은 블록은 한 번만 집계)
+                                let is_new = received_blocks
+                                    .write()
+                                    .await
+                                    .insert(block_index, true)
+                                    .is_none();
+                                if is_new {
+                                    *bytes_received.write().await += block_size as u64;
+                                }
 
                                 // Sliding Window 속도 계산기 업데이트
                                 {
@@ -570,7 +1226,34 @@ impl MultiStreamReceiver {
                         }
                         b"DONE" => {
                             info!("🏁 완료 신호 수신");
-                            completed = true;
+
+                            // 매니페스트 기준 감사: 누락/손상 블록은 복구 스트림으로 재요청
+                            let missing = {
+                                let received = received_blocks.read().await;
+                                (0..manifest.total_blocks)
+                                    .filter(|index| !received.contains_key(index))
+                                    .collect::<Vec<u32>>()
+                            };
+
+                            if missing.is_empty() {
+                                let _ = send.write_all(b"FINE").await;
+                                let _ = send.finish();
+                                completed = true;
+                            } else if repair_round >= MAX_REPAIR_ROUNDS {
+                                warn!("⚠️ 블록 복구 한도 초과: {}개 누락", missing.len());
+                                completed = true;
+                            } else {
+                                repair_round += 1;
+                                warn!(
+                                    "🔧 누락/손상 블록 {}개 재요청 ({}차)",
+                                    missing.len(),
+                                    repair_round
+                                );
+                                if let Err(e) = Self::request_repair(&mut send, &missing).await {
+                                    warn!("재전송 요청 실패: {}", e);
+                                    completed = true;
+                                }
+                            }
                         }
                         _ => {
                             warn!("알 수 없는 스트림 타입: {:?}", marker);
@@ -588,7 +1271,11 @@ impl MultiStreamReceiver {
             }
         }
 
-        // 모든 블록 수신 확인
+        // 쓰기 대기열을 닫고 남은 블록 기록 + 디스크 동기화 (수신 전체에서 한 번)
+        drop(write_tx);
+        write_task.await??;
+
+        // 모든 블록 수신 확인 (복구 후에도 누락이면 완료로 보고하지 않음)
         let received = received_blocks.read().await;
         if received.len() as u32 != manifest.total_blocks {
             warn!(
@@ -596,8 +1283,22 @@ impl MultiStreamReceiver {
                 received.len(),
                 manifest.total_blocks
             );
+            // 임시 파일은 다음 시도에서 이어받을 수 있도록 남겨 둠
+            self.speed_calculator.write().await.reset();
+            return Err(anyhow::anyhow!(
+                "일부 블록 누락: {}/{}",
+                received.len(),
+                manifest.total_blocks
+            ));
         }
 
+        if let Err(e) = sparse::punch_holes(&part_path, &manifest.holes) {
+            warn!("구멍 복원 실패 (0으로 채워진 채 유지): {}", e);
+        }
+        part_file::commit(&part_path, &save_path)?;
+        if let Some(attributes) = &manifest.attributes {
+            attributes.apply_or_warn(&save_path);
+        }
         info!("✅ 멀티스트림 수신 완료: {:?}", save_path);
 
         // 속도 계산기 리셋
@@ -610,9 +1311,11 @@ impl MultiStreamReceiver {
     }
 
     /// 매니페스트 수신
-    async fn receive_manifest(&self) -> Result<MultiStreamManifest> {
+    async fn receive_manifest(
+        &self,
+    ) -> Result<(MultiStreamManifest, quinn::SendStream, quinn::RecvStream)> {
         loop {
-            let (mut send, mut recv) = self.conn.accept_bi().await?;
+            let (send, mut recv) = self.conn.accept_bi().await?;
 
             // 스트림 타입 확인
             let mut marker = [0u8; 4];
@@ -630,21 +1333,99 @@ impl MultiStreamReceiver {
 
                 let manifest: MultiStreamManifest = serde_json::from_slice(&manifest_buf)?;
 
-                // ACK 전송
-                send.write_all(b"MACK").await?;
-                send.finish()?;
-
                 debug!("📋 매니페스트 수신: {:?}", manifest);
-                return Ok(manifest);
+                return Ok((manifest, send, recv));
             }
         }
     }
 
+    /// 매니페스트 ACK 전송
+    ///
+    /// 이어받을 임시 파일이 없으면 `MACK`, 있으면 `MRSM` + 블록별 SHA-256을 보내고
+    /// 송신 측이 원본과 일치한다고 확정한 블록 번호를 받아 반환합니다.
+    async fn acknowledge_manifest(
+        manifest: &MultiStreamManifest,
+        part_path: &Path,
+        hole_blocks: &[u32],
+        mut send: quinn::SendStream,
+        mut recv: quinn::RecvStream,
+    ) -> Result<Vec<u32>> {
+        let candidates = if manifest.resumable && part_path.exists() {
+            let part = part_path.to_path_buf();
+            let (file_size, block_size) = (manifest.file_size, manifest.block_size as usize);
+            let skip = hole_blocks.to_vec();
+            tokio::task::spawn_blocking(move || {
+                part_file::block_checksums(&part, file_size, block_size, &skip)
+            })
+            .await?
+            .unwrap_or_else(|e| {
+                warn!("임시 파일 확인 실패 (처음부터 수신): {}", e);
+                Vec::new()
+            })
+        } else {
+            Vec::new()
+        };
+
+        if candidates.is_empty() {
+            send.write_all(b"MACK").await?;
+            send.finish()?;
+            return Ok(Vec::new());
+        }
+
+        let mut request = Vec::with_capacity(8 + candidates.len() * 36);
+        request.extend_from_slice(b"MRSM");
+        request.extend_from_slice(&(candidates.len() as u32).to_le_bytes());
+        for (index, hash) in &candidates {
+            request.extend_from_slice(&index.to_le_bytes());
+            request.extend_from_slice(hash);
+        }
+        send.write_all(&request).await?;
+        send.finish()?;
+
+        let mut count_buf = [0u8; 4];
+        recv.read_exact(&mut count_buf).await?;
+        let count = u32::from_le_bytes(count_buf) as usize;
+        if count > candidates.len() {
+            return Err(anyhow::anyhow!("잘못된 이어받기 응답: {}개", count));
+        }
+        let mut indices = vec![0u8; count * 4];
+        recv.read_exact(&mut indices).await?;
+        let resumed: Vec<u32> = indices
+            .chunks_exact(4)
+            .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .filter(|index| *index < manifest.total_blocks)
+            .collect();
+
+        info!(
+            "♻️ 이어받기: {}/{} 블록 재사용 ({:?})",
+            resumed.len(),
+            manifest.total_blocks,
+            part_path
+        );
+        Ok(resumed)
+    }
+
+    /// 누락/손상 블록 재전송 요청 (DONE 스트림의 응답으로 전송)
+    async fn request_repair(send: &mut quinn::SendStream, missing: &[u32]) -> Result<()> {
+        let mut request = Vec::with_capacity(8 + missing.len() * 4);
+        request.extend_from_slice(b"RPRQ");
+        request.extend_from_slice(&(missing.len() as u32).to_le_bytes());
+        for index in missing {
+            request.extend_from_slice(&index.to_le_bytes());
+        }
+        send.write_all(&request).await?;
+        send.finish()?;
+        Ok(())
+    }
+
     /// 단일 블록 수신
     async fn receive_block(
         send: &mut quinn::SendStream,
         recv: &mut quinn::RecvStream,
-        save_path: &PathBuf,
+        manifest: &MultiStreamManifest,
+        write_tx: &mpsc::Sender<PendingWrite>,
+        pool: &Arc<BlockBufferPool>,
+        cipher: Option<&PayloadCipher>,
     ) -> Result<(u32, u32)> {
         // 헤더 길이
         let mut len_buf = [0u8; 4];
@@ -656,27 +1437,64 @@ impl MultiStreamReceiver {
         recv.read_exact(&mut header_buf).await?;
         let header = BlockHeader::from_bytes(&header_buf)?;
 
+        if header.block_index >= manifest.total_blocks
+            || header.offset != header.block_index as u64 * manifest.block_size as u64
+        {
+            return Err(anyhow::anyhow!(
+                "잘못된 블록 헤더: index {}, offset {}",
+                header.block_index,
+                header.offset
+            ));
+        }
+
         // debug!("📦 블록 {} 수신 중 (offset: {}, size: {})", header.block_index, header.offset, header.size);
 
         // 블록 데이터 수신
-        let mut buffer = vec![0u8; header.size as usize];
+        let mut buffer = pool.acquire(header.size as usize).await;
         recv.read_exact(&mut buffer).await?;
+        if let Some(cipher) = cipher {
+            let mut trailer = [0u8; payload_crypto::TRAILER_LEN];
+            recv.read_exact(&mut trailer).await?;
+            let aad = payload_crypto::aad(&manifest.job_id, header.block_index as u64);
+            cipher.open_in_place(&aad, &mut buffer, &trailer)?;
+        }
+
+        // 무결성 검사 (checksum 0 = 검사하지 않는 이전 버전 송신자)
+        if header.checksum != 0 && crc32fast::hash(&buffer) != header.checksum {
+            return Err(anyhow::anyhow!("블록 {} CRC 불일치", header.block_index));
+        }
 
-        // 파일에 쓰기 (특정 오프셋) - Blocking IO Isolation 필요할 수 있으나
-        // Receiver는 병렬성이 낮아도 되므로 일단 Async File IO 사용
-        let mut file = tokio::fs::OpenOptions::new()
-            .write(true)
-            .open(save_path)
-            .await?;
-        file.seek(tokio::io::SeekFrom::Start(header.offset)).await?;
-        file.write_all(&buffer).await?;
-        // file.sync_data().await?; // 너무 잦은 sync는 성능 저하, OS 캐시 믿음
-
-        // ACK 전송
-        send.write_all(b"BACK").await?;
+        // 쓰기 태스크로 넘김 (대기열이 가득 차면 여기서 대기 → ACK 지연으로 역압)
+        write_tx
+            .send(PendingWrite {
+                offset: header.offset,
+                data: buffer,
+            })
+            .await
+            .map_err(|_| anyhow::anyhow!("쓰기 태스크 종료됨"))?;
+
+        // ACK 전송 (쓰기 대기열이 거의 찼으면 송신 측에 감속 요청)
+        let ack = if manifest.flow_control && write_tx.capacity() <= FLOW_CONTROL_SLACK {
+            b"BSLW"
+        } else {
+            b"BACK"
+        };
+        send.write_all(ack).await?;
         let _ = send.finish();
 
         // debug!("✅ 블록 {} 저장 완료", header.block_index);
         Ok((header.block_index, header.size))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coalesce_runs_merges_adjacent_blocks() {
+        let extents = [(0, 10), (10, 10), (30, 5), (35, 5), (40, 1), (100, 4)];
+        assert_eq!(coalesce_runs(&extents), vec![0..2, 2..5, 5..6]);
+        assert!(coalesce_runs(&[]).is_empty());
+    }
+}
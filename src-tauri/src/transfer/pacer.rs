@@ -0,0 +1,212 @@
+//! UDP 전송 페이싱
+//!
+//! 블록 단위로 데이터그램을 한꺼번에 쏟아내면 스위치 버퍼가 넘쳐 손실이 폭증합니다.
+//! `Pacer`는 목표 속도에 맞춰 데이터그램 사이 간격을 벌리고(버스트는 수 ms 분량),
+//! `RateController`는 재전송 비율을 보고 목표 속도를 조절합니다 (사용자 상한 이내).
+
+use parking_lot::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// 한 번에 몰아 보낼 수 있는 분량 (시간 기준)
+const PACER_BURST: Duration = Duration::from_millis(2);
+
+/// 저속에서도 보장하는 최소 버스트 (bytes)
+const MIN_BURST_BYTES: f64 = 64.0 * 1024.0;
+
+/// 시작 속도 (1 Gbit/s)
+pub const INITIAL_RATE_BPS: u64 = 125_000_000;
+
+/// 하한 속도 (1 MB/s)
+const MIN_RATE_BPS: u64 = 1_000_000;
+
+/// 사용자 상한이 없을 때의 상한 (40 Gbit/s)
+const MAX_RATE_BPS: u64 = 5_000_000_000;
+
+/// 목표 속도 재계산 주기
+const ADJUST_INTERVAL: Duration = Duration::from_millis(50);
+
+/// 이 비율을 넘는 재전송이 있으면 감속
+const LOSS_THRESHOLD: f64 = 0.01;
+
+/// 토큰 버킷 페이서 (rate 0 = 페이싱 없음)
+pub struct Pacer {
+    rate_bps: AtomicU64,
+    bucket: Mutex<Bucket>,
+}
+
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl Pacer {
+    pub fn new(rate_bps: u64) -> Self {
+        Self {
+            rate_bps: AtomicU64::new(rate_bps),
+            bucket: Mutex::new(Bucket {
+                tokens: Self::burst(rate_bps),
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    pub fn unlimited() -> Self {
+        Self::new(0)
+    }
+
+    /// 현재 목표 속도 (bytes/sec, 0 = 페이싱 없음)
+    pub fn rate(&self) -> u64 {
+        self.rate_bps.load(Ordering::Relaxed)
+    }
+
+    /// 목표 속도 변경 (누적된 빚은 유지)
+    pub fn set_rate(&self, rate_bps: u64) {
+        self.rate_bps.store(rate_bps, Ordering::Relaxed);
+        let mut bucket = self.bucket.lock();
+        bucket.tokens = bucket.tokens.min(Self::burst(rate_bps));
+    }
+
+    fn burst(rate: u64) -> f64 {
+        (rate as f64 * PACER_BURST.as_secs_f64()).max(MIN_BURST_BYTES)
+    }
+
+    /// 빚(음수 토큰)을 갚는 데 필요한 대기 시간. 0이면 바로 전송 가능
+    fn reserve(&self, bytes: usize) -> Duration {
+        let rate = self.rate();
+        if rate == 0 {
+            return Duration::ZERO;
+        }
+
+        let mut bucket = self.bucket.lock();
+        let now = Instant::now();
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.last_refill = now;
+        bucket.tokens = (bucket.tokens + elapsed * rate as f64).min(Self::burst(rate));
+        bucket.tokens -= bytes as f64;
+
+        if bucket.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / rate as f64)
+        }
+    }
+
+    /// 데이터그램 전송 전 호출: 목표 속도를 넘으면 그만큼 대기
+    pub async fn pace(&self, bytes: usize) {
+        let wait = self.reserve(bytes);
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+impl Default for Pacer {
+    fn default() -> Self {
+        Self::unlimited()
+    }
+}
+
+/// 손실 기반 목표 속도 조절기
+///
+/// 주기마다 재전송 비율이 `LOSS_THRESHOLD`를 넘으면 1/8 감속, 아니면 1/8 가속합니다.
+pub struct RateController {
+    rate_bps: u64,
+    max_rate_bps: u64,
+    sent: u64,
+    lost: u64,
+    last_adjust: Instant,
+}
+
+impl RateController {
+    /// `max_rate_bps`: 사용자 상한 (None = 링크 속도까지)
+    pub fn new(max_rate_bps: Option<u64>) -> Self {
+        let max_rate_bps = max_rate_bps
+            .filter(|rate| *rate > 0)
+            .unwrap_or(MAX_RATE_BPS)
+            .max(MIN_RATE_BPS);
+        Self {
+            rate_bps: INITIAL_RATE_BPS.min(max_rate_bps),
+            max_rate_bps,
+            sent: 0,
+            lost: 0,
+            last_adjust: Instant::now(),
+        }
+    }
+
+    pub fn rate(&self) -> u64 {
+        self.rate_bps
+    }
+
+    pub fn on_sent(&mut self, packets: u64) {
+        self.sent += packets;
+    }
+
+    pub fn on_lost(&mut self, packets: u64) {
+        self.lost += packets;
+    }
+
+    /// 주기가 지났으면 목표 속도를 재계산해 반환
+    pub fn update(&mut self, now: Instant) -> Option<u64> {
+        if now.duration_since(self.last_adjust) < ADJUST_INTERVAL || self.sent == 0 {
+            return None;
+        }
+
+        let loss = self.lost as f64 / self.sent as f64;
+        self.rate_bps = if loss > LOSS_THRESHOLD {
+            self.rate_bps - self.rate_bps / 8
+        } else {
+            self.rate_bps + self.rate_bps / 8
+        }
+        .clamp(MIN_RATE_BPS, self.max_rate_bps);
+
+        self.sent = 0;
+        self.lost = 0;
+        self.last_adjust = now;
+        Some(self.rate_bps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pacer_spreads_sends() {
+        let pacer = Pacer::new(1_000_000);
+
+        // 버스트(최소 64KB)까지는 대기 없음
+        assert!(pacer.reserve(64 * 1024).is_zero());
+
+        // 이후 10KB는 약 10ms 대기
+        let wait = pacer.reserve(10_000);
+        assert!(wait >= Duration::from_millis(9) && wait <= Duration::from_millis(11));
+
+        assert!(Pacer::unlimited().reserve(usize::MAX).is_zero());
+    }
+
+    #[test]
+    fn test_rate_controller_backs_off_on_loss() {
+        let mut controller = RateController::new(Some(200_000_000));
+        let start = Instant::now();
+        assert_eq!(controller.rate(), INITIAL_RATE_BPS);
+
+        controller.on_sent(1000);
+        assert_eq!(controller.update(start), None); // 주기 전
+
+        let rate = controller.update(start + ADJUST_INTERVAL).unwrap();
+        assert!(rate > INITIAL_RATE_BPS);
+
+        controller.on_sent(1000);
+        controller.on_lost(50);
+        let backed_off = controller.update(start + ADJUST_INTERVAL * 2).unwrap();
+        assert!(backed_off < rate);
+
+        // 사용자 상한은 넘지 않음
+        for i in 3..100 {
+            controller.on_sent(1000);
+            controller.update(start + ADJUST_INTERVAL * i);
+        }
+        assert_eq!(controller.rate(), 200_000_000);
+    }
+}
@@ -0,0 +1,477 @@
+//! 기기 페어링 (X25519 키 교환)
+//!
+//! 제어 스트림으로 임시 공개 키를 주고받아 두 기기만 아는 페이로드 암호화 키(`payload_crypto`)를 만듭니다.
+//! 양쪽 화면에 같은 6자리 확인 코드가 보이는지 비교하면 중간에 끼어든 기기(릴레이 포함)가 없음을 확인할 수 있습니다.
+//!
+//! 요청 측은 먼저 공개 키의 해시만 보내고(`PairRequest`), 응답 측 키를 받은 뒤에 공개 키를 밝힙니다(`PairReveal`).
+//! 중간자는 한쪽 키를 보기 전에 자기 키를 정해야 하므로 6자리 코드가 맞는 키를 골라낼 수 없습니다.
+//! 키 유도에는 TLS 연결 비밀(`export_keying_material`)도 섞어, 연결을 둘로 나눈 중간자는 양쪽 코드가 달라집니다.
+//!
+//! 새 키는 사용자가 확인 코드를 비교해 `confirm`해야 쓰이며, 각 기기가 따로 확인합니다.
+//! 이미 페어링된 기기의 키는 덮어쓰지 않으므로 다시 페어링하려면 먼저 해제해야 합니다.
+//! 키는 메모리에만 두며, 앱을 다시 시작하면 다시 페어링합니다.
+//!
+//! QR 코드처럼 공개 키를 미리 전달할 수 있으면 `create_offer`로 만든 일회용 키를 상대가
+//! `pair_with_offer`로 사용합니다. 이때는 응답 키를 미리 받은 키와 비교합니다.
+
+use super::control_stream::{self, IncomingStream};
+use super::payload_crypto::{PayloadCipher, KEY_LEN};
+use crate::protocol::Command;
+use anyhow::{anyhow, Result};
+use hkdf::Hkdf;
+use parking_lot::Mutex;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::oneshot;
+use tracing::{info, warn};
+use x25519_dalek::{EphemeralSecret, PublicKey};
+
+/// 상대 응답을 기다리는 최대 시간
+pub const PAIRING_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 미리 전달한 일회용 페어링 키의 유효 시간
+pub const OFFER_TTL: Duration = Duration::from_secs(10 * 60);
+
+const KEY_INFO: &[u8] = b"ponswarp payload key v2";
+const CODE_INFO: &[u8] = b"ponswarp verification code v2";
+const COMMITMENT_DOMAIN: &[u8] = b"ponswarp pairing commitment v1";
+const EXPORTER_LABEL: &[u8] = b"EXPORTER-ponswarp-pairing-v1";
+
+/// 페어링된 피어 정보 (프론트엔드 표시용)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PairedPeer {
+    pub peer_id: String,
+    /// 양쪽 기기에서 같아야 하는 6자리 확인 코드
+    pub verification_code: String,
+    /// 페어링 시각 (Unix ms)
+    pub paired_at: u64,
+}
+
+/// 요청 측의 공개 키 공개를 기다리는 응답 측 상태
+struct Responding {
+    peer_id: String,
+    commitment: [u8; 32],
+    secret: EphemeralSecret,
+    created: Instant,
+}
+
+/// 피어별 페어링 키 관리
+#[derive(Default)]
+pub struct PairingManager {
+    peers: Mutex<HashMap<String, (PairedPeer, Arc<PayloadCipher>)>>,
+    /// 사용자 확인을 기다리는 키 (`confirm`해야 `peers`로 옮겨 사용)
+    unconfirmed: Mutex<HashMap<String, (PairedPeer, [u8; KEY_LEN])>>,
+    pending: Mutex<HashMap<String, oneshot::Sender<[u8; 32]>>>,
+    /// 응답을 보내고 공개 키 공개를 기다리는 요청 (페어링 ID → 상태)
+    responding: Mutex<HashMap<String, Responding>>,
+    /// 미리 전달한 일회용 키 (페어링 ID → 비밀 키, 생성 시각)
+    offers: Mutex<HashMap<String, (EphemeralSecret, Instant)>>,
+}
+
+impl PairingManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 피어와 페어링 시작 (상대 응답까지 대기, 반환된 확인 코드를 사용자가 비교한 뒤 `confirm`)
+    pub async fn pair(&self, conn: &quinn::Connection, peer_id: &str) -> Result<PairedPeer> {
+        self.request(conn, peer_id, uuid::Uuid::new_v4().to_string(), None)
+            .await
+    }
+
+    /// 연결 밖(QR 코드 등)으로 전달할 일회용 페어링 키 생성 (페어링 ID, 공개 키)
+    pub fn create_offer(&self) -> (String, [u8; 32]) {
+        let pairing_id = uuid::Uuid::new_v4().simple().to_string();
+        let secret = EphemeralSecret::random_from_rng(rand::rngs::OsRng);
+        let public = PublicKey::from(&secret);
+
+        let mut offers = self.offers.lock();
+        offers.retain(|_, (_, created)| created.elapsed() < OFFER_TTL);
+        offers.insert(pairing_id.clone(), (secret, Instant::now()));
+        (pairing_id, public.to_bytes())
+    }
+
+    /// 상대가 미리 전달한 일회용 키로 페어링 (응답 키가 다르면 실패)
+    pub async fn pair_with_offer(
+        &self,
+        conn: &quinn::Connection,
+        peer_id: &str,
+        pairing_id: &str,
+        offer_key: [u8; 32],
+    ) -> Result<PairedPeer> {
+        self.request(conn, peer_id, pairing_id.to_string(), Some(offer_key))
+            .await
+    }
+
+    async fn request(
+        &self,
+        conn: &quinn::Connection,
+        peer_id: &str,
+        pairing_id: String,
+        expected_key: Option<[u8; 32]>,
+    ) -> Result<PairedPeer> {
+        if self.is_paired(peer_id) {
+            return Err(anyhow!(
+                "이미 페어링된 기기입니다 (다시 페어링하려면 먼저 해제)"
+            ));
+        }
+        let binding = connection_binding(conn)?;
+        let secret = EphemeralSecret::random_from_rng(rand::rngs::OsRng);
+        let public = PublicKey::from(&secret);
+
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().insert(pairing_id.clone(), tx);
+
+        let result = async {
+            let command = Command::PairRequest {
+                pairing_id: pairing_id.clone(),
+                commitment: hex::encode(commitment(&public)),
+            };
+            let mut send = control_stream::open(conn, &command).await?;
+            send.finish()?;
+            tokio::time::timeout(PAIRING_TIMEOUT, rx)
+                .await
+                .map_err(|_| anyhow!("페어링 응답 시간 초과"))?
+                .map_err(|_| anyhow!("페어링 응답 대기 중단"))
+        }
+        .await;
+        self.pending.lock().remove(&pairing_id);
+
+        let peer_key = result?;
+        if expected_key.is_some_and(|expected| expected != peer_key) {
+            return Err(anyhow!("미리 받은 페어링 키와 응답 키가 다릅니다"));
+        }
+        let peer_public = PublicKey::from(peer_key);
+        let (key, code) = derive_keys(secret, &public, &peer_public, &binding)?;
+
+        // 상대 키를 받았으니 약속한 공개 키를 밝힘
+        let command = Command::PairReveal {
+            pairing_id,
+            public_key: hex::encode(public.as_bytes()),
+        };
+        let mut send = control_stream::open(conn, &command).await?;
+        send.finish()?;
+        Ok(self.hold(peer_id, key, code))
+    }
+
+    /// 제어 스트림의 `PairRequest`/`PairResponse`/`PairReveal` 처리
+    ///
+    /// 상대가 요청한 페어링의 키가 만들어지면 그 정보를 반환합니다 (확인 코드 표시 후 `confirm`).
+    pub async fn handle_incoming(
+        &self,
+        conn: &quinn::Connection,
+        incoming: IncomingStream,
+    ) -> Option<PairedPeer> {
+        let IncomingStream {
+            peer_id,
+            command,
+            mut recv,
+        } = incoming;
+        let _ = control_stream::finish_header_only(&mut recv).await;
+
+        match command {
+            Command::PairRequest {
+                pairing_id,
+                commitment,
+            } => {
+                if self.is_paired(&peer_id) {
+                    warn!("이미 페어링된 기기의 페어링 요청 무시 ({})", peer_id);
+                    return None;
+                }
+                let result = async {
+                    let commitment = parse_public_key(&commitment)?;
+                    let secret = self
+                        .take_offer(&pairing_id)
+                        .unwrap_or_else(|| EphemeralSecret::random_from_rng(rand::rngs::OsRng));
+                    let public = PublicKey::from(&secret);
+
+                    {
+                        let mut responding = self.responding.lock();
+                        responding.retain(|_, r| r.created.elapsed() < PAIRING_TIMEOUT);
+                        responding.insert(
+                            pairing_id.clone(),
+                            Responding {
+                                peer_id: peer_id.clone(),
+                                commitment,
+                                secret,
+                                created: Instant::now(),
+                            },
+                        );
+                    }
+
+                    let command = Command::PairResponse {
+                        pairing_id,
+                        public_key: hex::encode(public.as_bytes()),
+                    };
+                    let mut send = control_stream::open(conn, &command).await?;
+                    send.finish()?;
+                    anyhow::Ok(())
+                }
+                .await;
+
+                if let Err(e) = result {
+                    warn!("페어링 요청 처리 실패 ({}): {}", peer_id, e);
+                }
+                None
+            }
+            Command::PairResponse {
+                pairing_id,
+                public_key,
+            } => {
+                let Some(tx) = self.pending.lock().remove(&pairing_id) else {
+                    warn!("대기 중이 아닌 페어링 응답 ({})", peer_id);
+                    return None;
+                };
+                match parse_public_key(&public_key) {
+                    Ok(bytes) => {
+                        let _ = tx.send(bytes);
+                    }
+                    Err(e) => warn!("페어링 응답 처리 실패 ({}): {}", peer_id, e),
+                }
+                None
+            }
+            Command::PairReveal {
+                pairing_id,
+                public_key,
+            } => {
+                let result = async {
+                    let responding = self
+                        .responding
+                        .lock()
+                        .remove(&pairing_id)
+                        .filter(|r| r.peer_id == peer_id && r.created.elapsed() < PAIRING_TIMEOUT)
+                        .ok_or_else(|| anyhow!("대기 중이 아닌 페어링"))?;
+                    let peer_public = PublicKey::from(parse_public_key(&public_key)?);
+                    if commitment(&peer_public) != responding.commitment {
+                        return Err(anyhow!("공개 키가 먼저 보낸 해시와 다릅니다"));
+                    }
+                    let binding = connection_binding(conn)?;
+                    let public = PublicKey::from(&responding.secret);
+                    derive_keys(responding.secret, &public, &peer_public, &binding)
+                }
+                .await;
+
+                match result {
+                    Ok((key, code)) => Some(self.hold(&peer_id, key, code)),
+                    Err(e) => {
+                        warn!("페어링 키 공개 처리 실패 ({}): {}", peer_id, e);
+                        None
+                    }
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// 피어의 페이로드 암호기 (페어링하지 않았으면 None)
+    pub fn cipher(&self, peer_id: &str) -> Option<Arc<PayloadCipher>> {
+        self.peers
+            .lock()
+            .get(peer_id)
+            .map(|(_, cipher)| cipher.clone())
+    }
+
+    /// 확인 코드가 같음을 사용자가 확인 → 키 사용 시작
+    ///
+    /// 이미 페어링된 기기의 키는 바꾸지 않으므로, 다시 페어링하려면 먼저 `unpair`해야 합니다.
+    pub fn confirm(&self, peer_id: &str) -> Result<PairedPeer> {
+        let mut peers = self.peers.lock();
+        if peers.contains_key(peer_id) {
+            return Err(anyhow!(
+                "이미 페어링된 기기입니다 (다시 페어링하려면 먼저 해제)"
+            ));
+        }
+        let (peer, key) = self
+            .unconfirmed
+            .lock()
+            .remove(peer_id)
+            .ok_or_else(|| anyhow!("확인을 기다리는 페어링이 없습니다: {}", peer_id))?;
+        info!(
+            "🔐 페어링 확인: {} (확인 코드 {})",
+            peer_id, peer.verification_code
+        );
+        peers.insert(
+            peer_id.to_string(),
+            (peer.clone(), Arc::new(PayloadCipher::new(&key))),
+        );
+        Ok(peer)
+    }
+
+    /// 확인 코드가 다름 → 새 키 폐기 (중간자가 있을 수 있음)
+    pub fn reject(&self, peer_id: &str) -> bool {
+        let rejected = self.unconfirmed.lock().remove(peer_id).is_some();
+        if rejected {
+            warn!("🔐 확인 코드 불일치로 페어링 취소: {}", peer_id);
+        }
+        rejected
+    }
+
+    /// 페어링 해제 (확인을 기다리는 키도 폐기)
+    pub fn unpair(&self, peer_id: &str) -> bool {
+        let pending = self.unconfirmed.lock().remove(peer_id).is_some();
+        self.peers.lock().remove(peer_id).is_some() || pending
+    }
+
+    /// 페어링된 피어 목록
+    pub fn list(&self) -> Vec<PairedPeer> {
+        self.peers
+            .lock()
+            .values()
+            .map(|(peer, _)| peer.clone())
+            .collect()
+    }
+
+    fn is_paired(&self, peer_id: &str) -> bool {
+        self.peers.lock().contains_key(peer_id)
+    }
+
+    fn take_offer(&self, pairing_id: &str) -> Option<EphemeralSecret> {
+        self.offers
+            .lock()
+            .remove(pairing_id)
+            .filter(|(_, created)| created.elapsed() < OFFER_TTL)
+            .map(|(secret, _)| secret)
+    }
+
+    /// 새 키를 사용자 확인 대기로 보관 (확인 전에는 `cipher`가 돌려주지 않음)
+    fn hold(&self, peer_id: &str, key: [u8; KEY_LEN], code: String) -> PairedPeer {
+        let peer = PairedPeer {
+            peer_id: peer_id.to_string(),
+            verification_code: code,
+            paired_at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64,
+        };
+        info!(
+            "🔐 페어링 키 교환 완료, 확인 대기: {} (확인 코드 {})",
+            peer_id, peer.verification_code
+        );
+        self.unconfirmed
+            .lock()
+            .insert(peer_id.to_string(), (peer.clone(), key));
+        peer
+    }
+}
+
+/// 공개 키 약속값 (요청 측이 응답 키를 받기 전에 보냄)
+fn commitment(public: &PublicKey) -> [u8; 32] {
+    Sha256::new()
+        .chain_update(COMMITMENT_DOMAIN)
+        .chain_update(public.as_bytes())
+        .finalize()
+        .into()
+}
+
+/// 두 기기가 공유하는 TLS 연결 비밀 (연결이 둘로 나뉘면 양쪽 값이 다름)
+fn connection_binding(conn: &quinn::Connection) -> Result<[u8; 32]> {
+    let mut binding = [0u8; 32];
+    conn.export_keying_material(&mut binding, EXPORTER_LABEL, b"")
+        .map_err(|_| anyhow!("연결 비밀을 만들 수 없습니다"))?;
+    Ok(binding)
+}
+
+fn parse_public_key(hex_key: &str) -> Result<[u8; 32]> {
+    hex::decode(hex_key)?
+        .try_into()
+        .map_err(|_| anyhow!("잘못된 공개 키 길이"))
+}
+
+/// 공유 비밀과 연결 비밀에서 페이로드 키와 확인 코드 유도
+///
+/// 두 공개 키를 정렬해 연결 비밀과 함께 salt로 쓰므로 양쪽이 같은 값을 얻습니다.
+fn derive_keys(
+    secret: EphemeralSecret,
+    public: &PublicKey,
+    peer_public: &PublicKey,
+    binding: &[u8; 32],
+) -> Result<([u8; KEY_LEN], String)> {
+    let shared = secret.diffie_hellman(peer_public);
+    if !shared.was_contributory() {
+        return Err(anyhow!("잘못된 공개 키 (저차 점)"));
+    }
+
+    let (first, second) = if public.as_bytes() <= peer_public.as_bytes() {
+        (public, peer_public)
+    } else {
+        (peer_public, public)
+    };
+    let mut salt = [0u8; 96];
+    salt[..32].copy_from_slice(first.as_bytes());
+    salt[32..64].copy_from_slice(second.as_bytes());
+    salt[64..].copy_from_slice(binding);
+
+    let hkdf = Hkdf::<Sha256>::new(Some(&salt), shared.as_bytes());
+    let mut key = [0u8; KEY_LEN];
+    let mut code = [0u8; 4];
+    hkdf.expand(KEY_INFO, &mut key)
+        .and_then(|_| hkdf.expand(CODE_INFO, &mut code))
+        .map_err(|_| anyhow!("키 유도 실패"))?;
+    Ok((key, format!("{:06}", u32::from_be_bytes(code) % 1_000_000)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_both_sides_derive_same_key() {
+        let a = EphemeralSecret::random_from_rng(rand::rngs::OsRng);
+        let a_public = PublicKey::from(&a);
+        let b = EphemeralSecret::random_from_rng(rand::rngs::OsRng);
+        let b_public = PublicKey::from(&b);
+
+        let binding = [7u8; 32];
+        let (a_key, a_code) = derive_keys(a, &a_public, &b_public, &binding).unwrap();
+        let (b_key, b_code) = derive_keys(b, &b_public, &a_public, &binding).unwrap();
+        assert_eq!(a_key, b_key);
+        assert_eq!(a_code, b_code);
+        assert_eq!(a_code.len(), 6);
+
+        // 연결 비밀이 다르면(연결이 둘로 나뉨) 다른 키
+        let c = EphemeralSecret::random_from_rng(rand::rngs::OsRng);
+        let c_public = PublicKey::from(&c);
+        let (c_key, _) = derive_keys(c, &c_public, &a_public, &[8u8; 32]).unwrap();
+        assert_ne!(c_key, a_key);
+
+        // 저차 점(모두 0)은 거부
+        let d = EphemeralSecret::random_from_rng(rand::rngs::OsRng);
+        let d_public = PublicKey::from(&d);
+        assert!(derive_keys(d, &d_public, &PublicKey::from([0u8; 32]), &binding).is_err());
+    }
+
+    #[test]
+    fn test_key_used_only_after_confirm() {
+        let pairing = PairingManager::new();
+        let peer = pairing.hold("peer", [1u8; KEY_LEN], "123456".to_string());
+        assert_eq!(peer.verification_code, "123456");
+        assert!(pairing.cipher("peer").is_none());
+        assert!(pairing.list().is_empty());
+
+        assert!(pairing.confirm("peer").is_ok());
+        assert!(pairing.cipher("peer").is_some());
+        assert!(pairing.confirm("peer").is_err());
+
+        // 이미 페어링된 기기의 키는 새 키로 바뀌지 않음
+        pairing.hold("peer", [2u8; KEY_LEN], "654321".to_string());
+        assert!(pairing.confirm("peer").is_err());
+        assert_eq!(pairing.list()[0].verification_code, "123456");
+
+        assert!(pairing.reject("peer"));
+        assert!(!pairing.reject("peer"));
+        assert!(pairing.unpair("peer"));
+        assert!(pairing.cipher("peer").is_none());
+    }
+
+    #[test]
+    fn test_commitment_binds_public_key() {
+        let a = PublicKey::from(&EphemeralSecret::random_from_rng(rand::rngs::OsRng));
+        let b = PublicKey::from(&EphemeralSecret::random_from_rng(rand::rngs::OsRng));
+        assert_eq!(commitment(&a), commitment(&a));
+        assert_ne!(commitment(&a), commitment(&b));
+    }
+}
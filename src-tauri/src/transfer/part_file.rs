@@ -0,0 +1,138 @@
+//! 수신 중 임시 파일 (`<이름>.pswp-part`)
+//!
+//! 받은 데이터는 임시 파일에 쓰고, 디스크 동기화와 검증이 끝난 뒤에만 최종 이름으로 바꿉니다.
+//! 전송이 중간에 끊겨도 완성된 것처럼 보이는 반쪽 파일이 남지 않고, 덮어쓸 기존 파일도 그때까지 유지됩니다.
+
+use super::zero_copy_io::split_file_into_blocks;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// 임시 파일 확장자
+pub const PART_EXTENSION: &str = "pswp-part";
+
+/// 최종 경로에 대응하는 임시 파일 경로
+pub fn part_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(PART_EXTENSION);
+    path.with_file_name(name)
+}
+
+/// 수신 중인 임시 파일인지
+pub fn is_part_file(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == PART_EXTENSION)
+}
+
+/// 임시 파일을 디스크에 동기화한 뒤 최종 이름으로 교체
+///
+/// 같은 폴더 안의 rename이므로 최종 경로에는 이전 파일 또는 완성된 파일만 보입니다.
+pub fn commit(part: &Path, path: &Path) -> io::Result<()> {
+    fs::OpenOptions::new().write(true).open(part)?.sync_all()?;
+    fs::rename(part, path)?;
+
+    // 이름 변경 자체도 디스크에 남도록 폴더 동기화 (Unix)
+    #[cfg(unix)]
+    if let Some(parent) = path.parent() {
+        if let Ok(dir) = fs::File::open(parent) {
+            let _ = dir.sync_all();
+        }
+    }
+    Ok(())
+}
+
+/// 임시 파일에 이미 있는 블록들의 SHA-256 (이어받기 후보, 블록 번호 순)
+///
+/// CRC32는 우연한 충돌을 걸러낼 만큼 강하지 않아, 이어받기로 재사용할 블록은 SHA-256으로 대조합니다.
+///
+/// 모두 0인 블록은 아직 쓰지 않은 구간일 가능성이 높아 후보에서 빼고, `skip` 블록(희소 파일의 구멍 등)도 제외합니다.
+/// 후보가 실제로 유효한지는 송신 측이 원본과 대조해 확정합니다.
+pub fn block_checksums(
+    part: &Path,
+    file_size: u64,
+    block_size: usize,
+    skip: &[u32],
+) -> io::Result<Vec<(u32, [u8; 32])>> {
+    let mut file = fs::File::open(part)?;
+    let available = file.metadata()?.len().min(file_size);
+    let mut buffer = vec![0u8; block_size];
+    let mut checksums = Vec::new();
+
+    for block in split_file_into_blocks(file_size, block_size) {
+        if block.offset + block.size as u64 > available {
+            break;
+        }
+        if skip.binary_search(&block.index).is_ok() {
+            continue;
+        }
+        let data = &mut buffer[..block.size as usize];
+        file.seek(SeekFrom::Start(block.offset))?;
+        file.read_exact(data)?;
+        if data.iter().all(|&b| b == 0) {
+            continue;
+        }
+        checksums.push((block.index, Sha256::digest(data).into()));
+    }
+    Ok(checksums)
+}
+
+/// 실패/취소된 수신의 임시 파일 삭제
+pub fn discard(part: &Path) {
+    if let Err(e) = fs::remove_file(part) {
+        if e.kind() != io::ErrorKind::NotFound {
+            warn!("임시 파일 삭제 실패 ({:?}): {}", part, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commit_replaces_atomically() {
+        let dir = std::env::temp_dir().join(format!("ponswarp-part-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("video.mp4");
+        let part = part_path(&path);
+        assert_eq!(part, dir.join("video.mp4.pswp-part"));
+        assert!(is_part_file(&part));
+
+        // 완료 전까지 기존 파일 유지
+        fs::write(&path, b"old").unwrap();
+        fs::write(&part, b"new").unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"old");
+
+        commit(&part, &path).unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"new");
+        assert!(!part.exists());
+
+        discard(&part);
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_block_checksums() {
+        let path = std::env::temp_dir().join(format!("ponswarp-part-crc-{}", std::process::id()));
+        let mut data = vec![0u8; 10];
+        data[..4].copy_from_slice(b"abcd");
+        data[8..].copy_from_slice(b"ef");
+        fs::write(&path, &data).unwrap();
+
+        // 블록 1(0으로만 채워짐)과 건너뛸 블록은 후보에서 제외
+        let checksums = block_checksums(&path, 10, 4, &[]).unwrap();
+        assert_eq!(
+            checksums,
+            vec![
+                (0, Sha256::digest(b"abcd").into()),
+                (2, Sha256::digest(b"ef").into())
+            ]
+        );
+        assert_eq!(block_checksums(&path, 10, 4, &[0]).unwrap().len(), 1);
+
+        let _ = fs::remove_file(path);
+    }
+}
@@ -0,0 +1,371 @@
+//! 폴더 전송 포함/제외 글롭 필터
+//!
+//! 폴더를 걷는 동안 적용하므로 `node_modules`, `.git`처럼 제외한 폴더는 안으로 들어가지도 않습니다.
+//!
+//! - `/`가 없는 패턴(`*.tmp`, `node_modules`)은 경로의 각 이름과 비교
+//! - `/`가 있는 패턴(`src/**/*.rs`)은 루트 기준 상대 경로 전체와 비교
+//! - `*`, `?`는 `/`를 넘지 않고 `**`는 여러 폴더에 걸침, `[abc]`/`[a-z]`/`[!x]` 문자 집합 지원
+//! - 제외는 폴더와 파일 모두에, 포함은 파일에만 적용 (포함 패턴이 없으면 모든 파일)
+//! - 보내는 폴더 루트의 `.pswpignore`(gitignore 문법)도 함께 적용
+
+use anyhow::{anyhow, Result};
+use std::fs;
+use std::path::Path;
+use tracing::{info, warn};
+
+/// 보내는 폴더 루트에 두는 무시 파일 이름
+pub const IGNORE_FILE_NAME: &str = ".pswpignore";
+
+/// 글롭 패턴 구성 요소
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Literal(char),
+    /// `?`
+    Any,
+    /// `*` (`/` 제외)
+    Star,
+    /// `**` (`/` 포함)
+    DoubleStar,
+    /// `[...]` (범위 목록, 부정 여부)
+    Class(Vec<(char, char)>, bool),
+}
+
+/// 컴파일된 글롭 패턴
+#[derive(Debug, Clone)]
+pub struct Glob {
+    tokens: Vec<Token>,
+    /// `/`로 시작하거나 중간에 `/`가 있으면 상대 경로 전체, 없으면 이름 하나와 비교
+    anchored: bool,
+}
+
+impl Glob {
+    pub fn new(pattern: &str) -> Result<Self> {
+        let trimmed = pattern
+            .trim()
+            .trim_start_matches("./")
+            .trim_end_matches('/');
+        let rooted = trimmed.starts_with('/');
+        let trimmed = trimmed.trim_start_matches('/');
+        if trimmed.is_empty() {
+            return Err(anyhow!("빈 필터 패턴"));
+        }
+
+        let mut tokens = Vec::new();
+        let mut chars = trimmed.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '*' if chars.peek() == Some(&'*') => {
+                    chars.next();
+                    // `**/`는 "0개 이상의 폴더"이므로 뒤따르는 `/`까지 포함
+                    if chars.peek() == Some(&'/') {
+                        chars.next();
+                    }
+                    tokens.push(Token::DoubleStar);
+                }
+                '*' => tokens.push(Token::Star),
+                '?' => tokens.push(Token::Any),
+                '[' => {
+                    let negated = matches!(chars.peek(), Some('!') | Some('^'));
+                    if negated {
+                        chars.next();
+                    }
+                    let mut ranges = Vec::new();
+                    let mut closed = false;
+                    while let Some(c) = chars.next() {
+                        if c == ']' && !ranges.is_empty() {
+                            closed = true;
+                            break;
+                        }
+                        let mut lookahead = chars.clone();
+                        match (lookahead.next(), lookahead.next()) {
+                            (Some('-'), Some(end)) if end != ']' => {
+                                chars.next();
+                                chars.next();
+                                ranges.push((c, end));
+                            }
+                            _ => ranges.push((c, c)),
+                        }
+                    }
+                    if !closed {
+                        return Err(anyhow!("닫히지 않은 [ : {}", pattern));
+                    }
+                    tokens.push(Token::Class(ranges, negated));
+                }
+                '\\' => tokens.push(Token::Literal(chars.next().unwrap_or('\\'))),
+                c => tokens.push(Token::Literal(c)),
+            }
+        }
+
+        Ok(Self {
+            anchored: rooted || trimmed.contains('/'),
+            tokens,
+        })
+    }
+
+    /// 상대 경로(`/` 구분)가 패턴과 맞는지
+    pub fn matches(&self, relative_path: &str) -> bool {
+        let path: Vec<char> = relative_path.chars().collect();
+        if self.anchored {
+            return match_tokens(&self.tokens, &path);
+        }
+        path.split(|&c| c == '/')
+            .any(|name| match_tokens(&self.tokens, name))
+    }
+
+    /// 폴더 경로가 패턴과 맞는지 (`build/**`처럼 폴더 안 전체를 가리키는 패턴도 폴더와 맞음)
+    pub fn matches_dir(&self, relative_path: &str) -> bool {
+        self.matches(relative_path)
+            || (self.anchored && self.matches(&format!("{}/", relative_path)))
+    }
+}
+
+fn match_tokens(tokens: &[Token], text: &[char]) -> bool {
+    let Some((token, rest)) = tokens.split_first() else {
+        return text.is_empty();
+    };
+    match token {
+        Token::Star => (0..=text.len())
+            .take_while(|&i| i == 0 || text[i - 1] != '/')
+            .any(|i| match_tokens(rest, &text[i..])),
+        Token::DoubleStar => (0..=text.len()).any(|i| match_tokens(rest, &text[i..])),
+        _ => {
+            let Some((&c, text_rest)) = text.split_first() else {
+                return false;
+            };
+            let matched = match token {
+                Token::Literal(literal) => c == *literal,
+                Token::Any => c != '/',
+                Token::Class(ranges, negated) => {
+                    c != '/' && ranges.iter().any(|&(lo, hi)| lo <= c && c <= hi) != *negated
+                }
+                Token::Star | Token::DoubleStar => unreachable!(),
+            };
+            matched && match_tokens(rest, text_rest)
+        }
+    }
+}
+
+/// `.pswpignore` 규칙 한 줄
+#[derive(Debug, Clone)]
+struct IgnoreRule {
+    glob: Glob,
+    /// `!`로 시작 (앞선 규칙이 무시한 항목을 다시 포함)
+    negated: bool,
+    /// `/`로 끝남 (폴더에만 적용)
+    dir_only: bool,
+}
+
+/// gitignore 문법의 무시 규칙 (마지막으로 맞은 규칙이 결정)
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreRules {
+    rules: Vec<IgnoreRule>,
+}
+
+impl IgnoreRules {
+    /// 무시 파일 내용 파싱 (빈 줄과 `#` 주석은 건너뛰고, 잘못된 패턴은 경고 후 무시)
+    pub fn parse(content: &str) -> Self {
+        let mut rules = Vec::new();
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (negated, pattern) = match line.strip_prefix('!') {
+                Some(rest) => (true, rest),
+                None => (false, line),
+            };
+            match Glob::new(pattern) {
+                Ok(glob) => rules.push(IgnoreRule {
+                    glob,
+                    negated,
+                    dir_only: pattern.ends_with('/'),
+                }),
+                Err(e) => warn!("{} 패턴 무시 ({}): {}", IGNORE_FILE_NAME, line, e),
+            }
+        }
+        Self { rules }
+    }
+
+    /// 폴더 루트의 `.pswpignore` 읽기 (없으면 None)
+    pub fn load(root: &Path) -> Option<Self> {
+        let path = root.join(IGNORE_FILE_NAME);
+        match fs::read_to_string(&path) {
+            Ok(content) => {
+                let rules = Self::parse(&content);
+                info!("📄 {:?} 규칙 {}개 적용", path, rules.rules.len());
+                Some(rules)
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => {
+                warn!("{:?} 읽기 실패: {}", path, e);
+                None
+            }
+        }
+    }
+
+    pub fn is_ignored(&self, relative_path: &str, is_dir: bool) -> bool {
+        let mut ignored = false;
+        for rule in &self.rules {
+            if rule.dir_only && !is_dir {
+                continue;
+            }
+            let matched = if is_dir {
+                rule.glob.matches_dir(relative_path)
+            } else {
+                rule.glob.matches(relative_path)
+            };
+            if matched {
+                ignored = !rule.negated;
+            }
+        }
+        ignored
+    }
+}
+
+/// 폴더 순회에 적용하는 포함/제외 필터
+#[derive(Debug, Clone, Default)]
+pub struct PathFilter {
+    include: Vec<Glob>,
+    exclude: Vec<Glob>,
+    /// 보내는 폴더의 `.pswpignore` 규칙
+    ignore: Option<IgnoreRules>,
+}
+
+impl PathFilter {
+    /// 포함/제외 패턴 컴파일 (빈 패턴은 무시, 잘못된 패턴은 에러)
+    pub fn new(include: &[String], exclude: &[String]) -> Result<Self> {
+        let compile = |patterns: &[String]| {
+            patterns
+                .iter()
+                .filter(|p| !p.trim().is_empty())
+                .map(|p| Glob::new(p))
+                .collect::<Result<Vec<_>>>()
+        };
+        Ok(Self {
+            include: compile(include)?,
+            exclude: compile(exclude)?,
+            ignore: None,
+        })
+    }
+
+    /// 보내는 폴더 루트의 `.pswpignore`를 함께 적용
+    pub fn with_ignore_file(mut self, root: &Path) -> Self {
+        self.ignore = IgnoreRules::load(root);
+        self
+    }
+
+    /// 포함/제외 패턴과 무시 규칙이 하나도 없는지
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty() && self.ignore.is_none()
+    }
+
+    /// 폴더 안으로 들어갈지 (제외 패턴과 무시 규칙만 적용)
+    pub fn allows_dir(&self, relative_path: &str) -> bool {
+        if self
+            .ignore
+            .as_ref()
+            .is_some_and(|ignore| ignore.is_ignored(relative_path, true))
+        {
+            return false;
+        }
+        !self
+            .exclude
+            .iter()
+            .any(|glob| glob.matches_dir(relative_path))
+    }
+
+    /// 파일을 전송할지
+    pub fn allows_file(&self, relative_path: &str) -> bool {
+        if self
+            .ignore
+            .as_ref()
+            .is_some_and(|ignore| ignore.is_ignored(relative_path, false))
+        {
+            return false;
+        }
+        if self.exclude.iter().any(|glob| glob.matches(relative_path)) {
+            return false;
+        }
+        self.include.is_empty() || self.include.iter().any(|glob| glob.matches(relative_path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(include: &[&str], exclude: &[&str]) -> PathFilter {
+        let strings =
+            |patterns: &[&str]| patterns.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        PathFilter::new(&strings(include), &strings(exclude)).unwrap()
+    }
+
+    #[test]
+    fn test_glob_matching() {
+        let glob = Glob::new("*.tmp").unwrap();
+        assert!(glob.matches("a.tmp"));
+        assert!(glob.matches("dir/sub/b.tmp"));
+        assert!(!glob.matches("a.tmp.txt"));
+
+        let glob = Glob::new("src/**/*.rs").unwrap();
+        assert!(glob.matches("src/lib.rs"));
+        assert!(glob.matches("src/a/b/mod.rs"));
+        assert!(!glob.matches("other/src/lib.rs"));
+
+        let glob = Glob::new("data/*.csv").unwrap();
+        assert!(glob.matches("data/x.csv"));
+        assert!(!glob.matches("data/sub/x.csv"));
+
+        let glob = Glob::new("log[0-9]?.txt").unwrap();
+        assert!(glob.matches("log1a.txt"));
+        assert!(!glob.matches("logxa.txt"));
+        assert!(Glob::new("[!a]*").unwrap().matches("b.txt"));
+
+        assert!(Glob::new("[abc").is_err());
+        assert!(Glob::new("  ").is_err());
+    }
+
+    #[test]
+    fn test_path_filter_include_exclude() {
+        let f = filter(&[], &["node_modules", ".git", "*.tmp"]);
+        assert!(!f.allows_dir("web/node_modules"));
+        assert!(f.allows_dir("web/src"));
+        assert!(!f.allows_file("a/b.tmp"));
+        assert!(f.allows_file("a/b.txt"));
+
+        // 포함 패턴은 파일에만 적용되어 하위 폴더 순회는 막지 않음
+        let f = filter(&["*.jpg", "docs/**"], &["docs/private/**"]);
+        assert!(f.allows_dir("photos/2024"));
+        assert!(!f.allows_dir("docs/private"));
+        assert!(f.allows_file("photos/2024/a.jpg"));
+        assert!(f.allows_file("docs/guide.md"));
+        assert!(!f.allows_file("photos/2024/a.png"));
+        assert!(!f.allows_file("docs/private/key.md"));
+
+        assert!(filter(&[], &[]).is_empty());
+    }
+
+    #[test]
+    fn test_ignore_rules_gitignore_syntax() {
+        let rules = IgnoreRules::parse(
+            "# 빌드 산출물\n\
+             /build\n\
+             target/\n\
+             *.log\n\
+             !keep.log\n\
+             secrets/**\n\
+             [bad\n",
+        );
+        // `/build`는 루트에만 적용
+        assert!(rules.is_ignored("build", true));
+        assert!(!rules.is_ignored("src/build", true));
+        // `target/`은 어디에 있든 폴더에만 적용
+        assert!(rules.is_ignored("crates/a/target", true));
+        assert!(!rules.is_ignored("target", false));
+        // 마지막으로 맞은 규칙이 결정
+        assert!(rules.is_ignored("logs/app.log", false));
+        assert!(!rules.is_ignored("logs/keep.log", false));
+        assert!(rules.is_ignored("secrets", true));
+        assert!(!rules.is_ignored("src/main.rs", false));
+    }
+}
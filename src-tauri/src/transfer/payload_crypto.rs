@@ -0,0 +1,171 @@
+//! 애플리케이션 계층 페이로드 암호화 (XChaCha20-Poly1305)
+//!
+//! QUIC TLS와 별개로, 페어링(`pairing`)에서 만든 키로 전송 데이터를 한 번 더 봉인합니다.
+//! TURN 릴레이나 이후 추가될 TLS 없는 전송 경로를 지나도 내용은 두 기기만 읽을 수 있습니다.
+//! 봉인 단위(블록/프레임)마다 임의 nonce를 쓰고, 작업 ID와 순번을 AAD로 묶어
+//! 다른 위치의 봉인 데이터로 바꿔치기하는 것을 막습니다.
+
+use anyhow::{anyhow, Result};
+use chacha20poly1305::aead::{AeadInPlace, KeyInit};
+use chacha20poly1305::{Tag, XChaCha20Poly1305, XNonce};
+use rand::RngCore;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+/// 키 길이
+pub const KEY_LEN: usize = 32;
+/// XChaCha20 nonce 길이 (임의 생성해도 충돌 걱정이 없는 크기)
+pub const NONCE_LEN: usize = 24;
+/// Poly1305 태그 길이
+pub const TAG_LEN: usize = 16;
+/// 봉인 데이터 뒤에 붙는 nonce + 태그
+pub const TRAILER_LEN: usize = NONCE_LEN + TAG_LEN;
+
+/// 프레임 최대 평문 크기 (잘못된 길이로 메모리를 잡지 않도록)
+pub const MAX_FRAME_LEN: usize = 16 * 1024 * 1024;
+
+/// 페어링된 피어와 공유하는 페이로드 암호기
+#[derive(Clone)]
+pub struct PayloadCipher {
+    aead: XChaCha20Poly1305,
+}
+
+impl PayloadCipher {
+    pub fn new(key: &[u8; KEY_LEN]) -> Self {
+        Self {
+            aead: XChaCha20Poly1305::new(key.into()),
+        }
+    }
+
+    /// 제자리 암호화 후 nonce + 태그 반환 (`data`는 같은 길이의 암호문이 됨)
+    pub fn seal_in_place(&self, aad: &[u8], data: &mut [u8]) -> Result<[u8; TRAILER_LEN]> {
+        let mut trailer = [0u8; TRAILER_LEN];
+        rand::thread_rng().fill_bytes(&mut trailer[..NONCE_LEN]);
+        let tag = self
+            .aead
+            .encrypt_in_place_detached(XNonce::from_slice(&trailer[..NONCE_LEN]), aad, data)
+            .map_err(|_| anyhow!("페이로드 암호화 실패"))?;
+        trailer[NONCE_LEN..].copy_from_slice(&tag);
+        Ok(trailer)
+    }
+
+    /// 제자리 복호화 (AAD나 내용이 바뀌었으면 에러)
+    pub fn open_in_place(
+        &self,
+        aad: &[u8],
+        data: &mut [u8],
+        trailer: &[u8; TRAILER_LEN],
+    ) -> Result<()> {
+        self.aead
+            .decrypt_in_place_detached(
+                XNonce::from_slice(&trailer[..NONCE_LEN]),
+                aad,
+                data,
+                Tag::from_slice(&trailer[NONCE_LEN..]),
+            )
+            .map_err(|_| anyhow!("페이로드 복호화 실패 (키 불일치 또는 변조)"))
+    }
+}
+
+/// 봉인 단위의 AAD (작업 ID + 순번)
+pub fn aad(context: &str, counter: u64) -> Vec<u8> {
+    let mut aad = Vec::with_capacity(context.len() + 8);
+    aad.extend_from_slice(context.as_bytes());
+    aad.extend_from_slice(&counter.to_le_bytes());
+    aad
+}
+
+/// 봉인 프레임 쓰기: `[평문 길이 u32 LE][암호문][nonce][태그]`
+///
+/// `data`는 제자리에서 암호화되므로 호출 후 내용이 바뀝니다.
+pub async fn write_frame<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    cipher: &PayloadCipher,
+    aad: &[u8],
+    data: &mut [u8],
+) -> Result<()> {
+    let trailer = cipher.seal_in_place(aad, data)?;
+    writer.write_all(&(data.len() as u32).to_le_bytes()).await?;
+    writer.write_all(data).await?;
+    writer.write_all(&trailer).await?;
+    Ok(())
+}
+
+/// 봉인 프레임 읽기 (평문 길이 반환, 프레임 경계에서 스트림이 끝났으면 None)
+pub async fn read_frame<R: AsyncRead + Unpin>(
+    reader: &mut R,
+    cipher: &PayloadCipher,
+    aad: &[u8],
+    buffer: &mut Vec<u8>,
+) -> Result<Option<usize>> {
+    let mut len_buf = [0u8; 4];
+    let mut filled = 0;
+    while filled < len_buf.len() {
+        let n = reader.read(&mut len_buf[filled..]).await?;
+        if n == 0 {
+            if filled == 0 {
+                return Ok(None);
+            }
+            return Err(anyhow!("프레임 길이를 읽는 중 스트림 종료"));
+        }
+        filled += n;
+    }
+
+    let len = u32::from_le_bytes(len_buf) as usize;
+    if len > MAX_FRAME_LEN {
+        return Err(anyhow!("프레임이 너무 큽니다: {} bytes", len));
+    }
+    buffer.resize(len, 0);
+    reader.read_exact(buffer).await?;
+    let mut trailer = [0u8; TRAILER_LEN];
+    reader.read_exact(&mut trailer).await?;
+    cipher.open_in_place(aad, buffer, &trailer)?;
+    Ok(Some(len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_frame_round_trip() {
+        let cipher = PayloadCipher::new(&[7u8; KEY_LEN]);
+        let (mut writer, mut reader) = tokio::io::duplex(1024);
+
+        let mut data = b"hello ponswarp".to_vec();
+        write_frame(&mut writer, &cipher, &aad("job-1", 0), &mut data)
+            .await
+            .unwrap();
+        assert_ne!(data, b"hello ponswarp");
+        drop(writer);
+
+        let mut buffer = Vec::new();
+        let len = read_frame(&mut reader, &cipher, &aad("job-1", 0), &mut buffer)
+            .await
+            .unwrap();
+        assert_eq!(len, Some(14));
+        assert_eq!(buffer, b"hello ponswarp");
+        assert_eq!(
+            read_frame(&mut reader, &cipher, &aad("job-1", 1), &mut buffer)
+                .await
+                .unwrap(),
+            None
+        );
+
+        // 다른 순번(AAD)이나 다른 키로는 열리지 않음
+        let mut block = b"block".to_vec();
+        let trailer = cipher.seal_in_place(&aad("job-1", 3), &mut block).unwrap();
+        let mut copy = block.clone();
+        assert!(cipher
+            .open_in_place(&aad("job-1", 4), &mut copy, &trailer)
+            .is_err());
+        let other = PayloadCipher::new(&[8u8; KEY_LEN]);
+        let mut copy = block.clone();
+        assert!(other
+            .open_in_place(&aad("job-1", 3), &mut copy, &trailer)
+            .is_err());
+        cipher
+            .open_in_place(&aad("job-1", 3), &mut block, &trailer)
+            .unwrap();
+        assert_eq!(block, b"block");
+    }
+}
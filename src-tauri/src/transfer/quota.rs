@@ -0,0 +1,346 @@
+//! 피어별/세션별 수신 용량 한도
+//!
+//! 여러 사람이 함께 쓰는 수신 기기가 한 사람의 전송으로 가득 차지 않도록
+//! 피어(인증서 지문)별 하루 수신량과 연결(세션)별 수신량에 상한을 둡니다.
+//! 요청이 알린 크기로 미리 확인해 넘는 요청은 이유와 함께 거절하고, 사용량은 실제로 받은
+//! 바이트로 집계해 수신 중에 한도를 넘으면 중단합니다 (`QuotaMeter`).
+//! 인증서가 없는 피어는 피어 ID를 바꿔 한도를 피할 수 없도록 모두 한 버킷(`anonymous`)으로 셉니다.
+//! 한도와 오늘 사용량은 앱 데이터 디렉토리에 저장되어 재시작 후에도 유지됩니다.
+
+use crate::quic::identity::normalize_fingerprint;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{info, warn};
+
+/// 한도/사용량 파일명
+pub const RECEIVE_QUOTA_FILE: &str = "receive_quota.json";
+
+/// 인증서 지문이 없는 피어가 함께 쓰는 사용량 키
+pub const ANONYMOUS_PEER: &str = "anonymous";
+
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// 수신 한도 (None = 제한 없음)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuotaLimits {
+    /// 피어(지문)별 하루(UTC) 최대 수신 바이트
+    #[serde(default)]
+    pub per_peer_daily_bytes: Option<u64>,
+    /// 연결 한 번(세션)에서 받을 수 있는 최대 바이트
+    #[serde(default)]
+    pub per_session_bytes: Option<u64>,
+}
+
+/// 피어의 오늘 수신량
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerQuotaUsage {
+    /// 인증서 지문 (지문이 없는 피어는 모두 `anonymous`)
+    pub peer: String,
+    /// UNIX epoch 기준 일 수 (UTC)
+    pub day: u64,
+    pub bytes: u64,
+}
+
+/// 현재 한도와 오늘 사용량
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReceiveQuotaStatus {
+    pub limits: QuotaLimits,
+    pub usage: Vec<PeerQuotaUsage>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct QuotaFile {
+    #[serde(default)]
+    limits: QuotaLimits,
+    #[serde(default)]
+    usage: Vec<PeerQuotaUsage>,
+}
+
+#[derive(Default)]
+struct QuotaState {
+    limits: QuotaLimits,
+    /// 피어 → (일, 바이트)
+    daily: HashMap<String, (u64, u64)>,
+    /// 연결 ID → 바이트
+    sessions: HashMap<usize, u64>,
+}
+
+/// 수신 한도 관리자 (모든 전송 요청 공유)
+pub struct ReceiveQuota {
+    path: Option<PathBuf>,
+    state: Mutex<QuotaState>,
+}
+
+impl ReceiveQuota {
+    /// 저장하지 않는 메모리 전용 (한도 없음)
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            state: Mutex::new(QuotaState::default()),
+        }
+    }
+
+    /// 파일에서 로드 (없거나 손상되었으면 한도 없음)
+    pub fn load(path: PathBuf) -> Self {
+        let file = match std::fs::read(&path) {
+            Ok(data) => serde_json::from_slice::<QuotaFile>(&data).unwrap_or_else(|e| {
+                warn!("수신 한도 파싱 실패, 초기화: {}", e);
+                QuotaFile::default()
+            }),
+            Err(_) => QuotaFile::default(),
+        };
+
+        if file.limits != QuotaLimits::default() {
+            info!("📦 수신 한도 로드: {:?}", file.limits);
+        }
+
+        let today = today();
+        let daily = file
+            .usage
+            .into_iter()
+            .filter(|usage| usage.day == today)
+            .map(|usage| (usage.peer, (usage.day, usage.bytes)))
+            .collect();
+
+        Self {
+            path: Some(path),
+            state: Mutex::new(QuotaState {
+                limits: file.limits,
+                daily,
+                sessions: HashMap::new(),
+            }),
+        }
+    }
+
+    /// 한도와 오늘 사용량
+    pub fn status(&self) -> ReceiveQuotaStatus {
+        let state = self.state.lock();
+        ReceiveQuotaStatus {
+            limits: state.limits.clone(),
+            usage: usage_on(&state, today()),
+        }
+    }
+
+    /// 한도 변경 후 저장
+    pub fn set_limits(&self, limits: QuotaLimits) -> QuotaLimits {
+        self.state.lock().limits = limits.clone();
+        info!("📦 수신 한도 변경: {:?}", limits);
+        self.persist();
+        limits
+    }
+
+    /// 요청이 알린 크기가 남은 한도 안인지 확인 (넘으면 거절 사유 반환, 사용량은 바꾸지 않음)
+    ///
+    /// `fingerprint`: 연결의 인증서 지문, `session`: 요청이 온 연결 ID
+    pub fn check(
+        &self,
+        fingerprint: Option<&str>,
+        session: usize,
+        bytes: u64,
+    ) -> Result<(), String> {
+        self.add_on(&peer_key(fingerprint), session, bytes, today(), false)
+    }
+
+    /// 실제로 받은 바이트를 집계할 미터 (미터가 사라질 때 사용량 저장)
+    pub fn meter(self: &Arc<Self>, fingerprint: Option<&str>, session: usize) -> QuotaMeter {
+        QuotaMeter {
+            quota: self.clone(),
+            peer: peer_key(fingerprint),
+            session,
+        }
+    }
+
+    /// 연결이 끊긴 세션의 사용량 정리
+    pub fn end_session(&self, session: usize) {
+        self.state.lock().sessions.remove(&session);
+    }
+
+    /// 한도 안이면 `record`일 때 사용량에 더함
+    fn add_on(
+        &self,
+        key: &str,
+        session: usize,
+        bytes: u64,
+        day: u64,
+        record: bool,
+    ) -> Result<(), String> {
+        let mut state = self.state.lock();
+
+        let daily_used = match state.daily.get(key) {
+            Some(&(used_day, used)) if used_day == day => used,
+            _ => 0,
+        };
+        let session_used = state.sessions.get(&session).copied().unwrap_or(0);
+
+        if let Some(limit) = state.limits.per_peer_daily_bytes {
+            if daily_used.saturating_add(bytes) > limit {
+                return Err(format!(
+                    "수신 측 피어별 하루 한도 초과: 오늘 {} / {} bytes 사용, 요청 {} bytes",
+                    daily_used, limit, bytes
+                ));
+            }
+        }
+        if let Some(limit) = state.limits.per_session_bytes {
+            if session_used.saturating_add(bytes) > limit {
+                return Err(format!(
+                    "수신 측 세션 한도 초과: 이번 연결에서 {} / {} bytes 사용, 요청 {} bytes",
+                    session_used, limit, bytes
+                ));
+            }
+        }
+
+        if record {
+            state
+                .daily
+                .insert(key.to_string(), (day, daily_used + bytes));
+            state.sessions.insert(session, session_used + bytes);
+        }
+        Ok(())
+    }
+
+    fn persist(&self) {
+        let Some(ref path) = self.path else {
+            return;
+        };
+        let file = {
+            let state = self.state.lock();
+            QuotaFile {
+                limits: state.limits.clone(),
+                usage: usage_on(&state, today()),
+            }
+        };
+
+        let result = (|| -> anyhow::Result<()> {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            let tmp = path.with_extension("json.tmp");
+            std::fs::write(&tmp, serde_json::to_vec_pretty(&file)?)?;
+            std::fs::rename(&tmp, path)?;
+            Ok(())
+        })();
+
+        if let Err(e) = result {
+            warn!("수신 한도 저장 실패: {}", e);
+        }
+    }
+}
+
+/// 수신 중인 전송 하나의 사용량 집계
+pub struct QuotaMeter {
+    quota: Arc<ReceiveQuota>,
+    peer: String,
+    session: usize,
+}
+
+impl QuotaMeter {
+    /// 받은 `bytes`를 사용량에 더함 (한도를 넘으면 더하지 않고 사유 반환)
+    pub fn charge(&self, bytes: u64) -> Result<(), String> {
+        self.quota
+            .add_on(&self.peer, self.session, bytes, today(), true)
+    }
+}
+
+impl Drop for QuotaMeter {
+    fn drop(&mut self) {
+        self.quota.persist();
+    }
+}
+
+/// 지문이면 정규화, 없거나 지문이 아니면 공용 키
+fn peer_key(fingerprint: Option<&str>) -> String {
+    match fingerprint.map(normalize_fingerprint) {
+        Some(fingerprint) if fingerprint.len() == 64 => fingerprint,
+        _ => ANONYMOUS_PEER.to_string(),
+    }
+}
+
+fn usage_on(state: &QuotaState, day: u64) -> Vec<PeerQuotaUsage> {
+    let mut usage: Vec<PeerQuotaUsage> = state
+        .daily
+        .iter()
+        .filter(|(_, &(used_day, bytes))| used_day == day && bytes > 0)
+        .map(|(peer, &(day, bytes))| PeerQuotaUsage {
+            peer: peer.clone(),
+            day,
+            bytes,
+        })
+        .collect();
+    usage.sort_by_key(|u| std::cmp::Reverse(u.bytes));
+    usage
+}
+
+fn today() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+        / SECS_PER_DAY
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_meter_enforces_daily_and_session_limits() {
+        let quota = Arc::new(ReceiveQuota::in_memory());
+        let peer = "ab".repeat(32);
+
+        // 한도가 없으면 모두 허용
+        assert!(quota.meter(Some(&peer), 1).charge(u64::MAX / 2).is_ok());
+
+        let quota = Arc::new(ReceiveQuota::in_memory());
+        quota.set_limits(QuotaLimits {
+            per_peer_daily_bytes: Some(100),
+            per_session_bytes: Some(60),
+        });
+
+        // 요청 크기 확인은 사용량을 바꾸지 않음
+        assert!(quota.check(Some(&peer), 1, 60).is_ok());
+        assert!(quota.check(Some(&peer), 1, 61).is_err());
+        assert!(quota.check(Some(&peer), 1, 60).is_ok());
+
+        // 실제로 받은 바이트로 집계하고 한도를 넘는 청크는 거부
+        let meter = quota.meter(Some(&peer), 1);
+        assert!(meter.charge(50).is_ok());
+        let err = meter.charge(20).unwrap_err();
+        assert!(err.contains("세션"));
+        assert!(quota.check(Some(&peer), 1, 20).is_err());
+        // 새 연결은 세션 한도가 새로 시작되지만 하루 한도는 이어짐 (지문 대소문자 무관)
+        assert!(quota
+            .meter(Some(&peer.to_uppercase()), 2)
+            .charge(50)
+            .is_ok());
+        let err = quota.meter(Some(&peer), 3).charge(1).unwrap_err();
+        assert!(err.contains("하루"));
+        // 다른 피어는 별도로 집계
+        assert!(quota.meter(Some(&"cd".repeat(32)), 3).charge(60).is_ok());
+        assert_eq!(quota.status().usage[0].bytes, 100);
+    }
+
+    #[test]
+    fn test_peers_without_certificate_share_one_bucket() {
+        let quota = Arc::new(ReceiveQuota::in_memory());
+        quota.set_limits(QuotaLimits {
+            per_peer_daily_bytes: Some(100),
+            per_session_bytes: None,
+        });
+
+        // 인증서 없는 피어는 연결(피어 ID)을 바꿔도 같은 하루 한도를 씀
+        assert!(quota.meter(None, 1).charge(60).is_ok());
+        assert!(quota.check(None, 2, 60).is_err());
+        assert!(quota
+            .meter(Some("not-a-fingerprint"), 3)
+            .charge(60)
+            .is_err());
+        assert_eq!(quota.status().usage[0].peer, ANONYMOUS_PEER);
+    }
+}
@@ -0,0 +1,654 @@
+//! 부분 파일 요청
+//!
+//! 송신 측이 파일이나 폴더를 제공(offer)해 두면, 수신 측이 목록을 받아 필요한 파일/하위 폴더만
+//! 고르거나 파일의 특정 바이트 범위만 요청해 받습니다. 요청/응답은 제어 스트림(`control_stream`)의
+//! `RangeRequest`/`RangeResponse` 명령이며, 응답 헤더 뒤에 목록 JSON 또는 범위 데이터가 옵니다.
+//!
+//! 송신 측은 제공할 때 만든 목록에 있는 파일만 읽으므로, 요청 경로로 제공 폴더 밖을 읽을 수 없습니다.
+//! 범위만 받은 파일은 요청한 바이트만 담은 파일로 저장합니다.
+
+use super::control_stream::{self, IncomingStream};
+use super::file_transfer::{resolve_destination, safe_destination, OverwritePolicy, ReceiveAction};
+use super::part_file;
+use super::registry::{TransferKind, TransferRegistry};
+use crate::error::AppError;
+use crate::protocol::Command;
+use anyhow::{anyhow, bail, Result};
+use parking_lot::Mutex;
+use quinn::RecvStream;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::SeekFrom;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use tokio::sync::oneshot;
+use tracing::{info, warn};
+
+/// 응답 헤더를 기다리는 최대 시간
+pub const RESPONSE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 목록 응답 최대 크기
+const MAX_CATALOG_BYTES: usize = 64 * 1024 * 1024;
+
+/// 범위 데이터를 읽고 쓰는 단위
+const CHUNK_SIZE: usize = 1024 * 1024;
+
+/// 응답을 받지 못한 요청을 중단할 때의 QUIC 에러 코드
+const STOP_UNKNOWN: u32 = 1;
+
+/// 제공 목록의 파일
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct OfferedFile {
+    /// 제공 루트 기준 상대 경로 (`/` 구분)
+    pub path: String,
+    pub size: u64,
+}
+
+/// 제공 목록
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OfferCatalog {
+    pub offer_id: String,
+    /// 제공한 파일/폴더 이름
+    pub name: String,
+    pub files: Vec<OfferedFile>,
+    pub total_size: u64,
+}
+
+/// 바이트 범위 (`end`는 포함하지 않으며, 없으면 파일 끝까지)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ByteRange {
+    pub start: u64,
+    #[serde(default)]
+    pub end: Option<u64>,
+}
+
+impl ByteRange {
+    /// 파일 크기에 맞춘 (오프셋, 길이)
+    fn clamp(&self, size: u64) -> (u64, u64) {
+        let start = self.start.min(size);
+        let end = self.end.unwrap_or(size).clamp(start, size);
+        (start, end - start)
+    }
+}
+
+/// 수신 측 선택 (파일 경로 또는 하위 폴더 경로, 범위는 파일에만)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileSelection {
+    pub path: String,
+    #[serde(default)]
+    pub range: Option<ByteRange>,
+}
+
+/// 선택한 파일 하나의 수신 결과
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RangeDownloadResult {
+    pub path: String,
+    pub destination: String,
+    pub bytes: u64,
+    pub range: Option<ByteRange>,
+    pub action: &'static str,
+}
+
+/// 제공 중인 파일/폴더
+struct Offer {
+    root: PathBuf,
+    /// 요청할 수 있는 피어 (없으면 연결된 모든 피어)
+    peer_id: Option<String>,
+    catalog: OfferCatalog,
+}
+
+/// 응답 헤더를 기다리는 요청 (받은 크기와 데이터 스트림 전달)
+type PendingResponse = oneshot::Sender<Option<(u64, RecvStream)>>;
+
+/// 제공 목록과 진행 중인 범위 요청 관리 (연결 전체에서 하나)
+#[derive(Default)]
+pub struct RangeRequestManager {
+    offers: Mutex<HashMap<String, Arc<Offer>>>,
+    pending: Mutex<HashMap<String, PendingResponse>>,
+}
+
+impl RangeRequestManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 파일이나 폴더를 부분 요청용으로 제공 (숨김 파일과 심볼릭 링크 제외)
+    pub async fn offer(&self, root: PathBuf, peer_id: Option<String>) -> Result<OfferCatalog> {
+        let scan_root = root.clone();
+        let files = tokio::task::spawn_blocking(move || scan_offer(&scan_root)).await??;
+        let name = root
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "offer".to_string());
+        let catalog = OfferCatalog {
+            offer_id: uuid::Uuid::new_v4().to_string(),
+            name,
+            total_size: files.iter().map(|file| file.size).sum(),
+            files,
+        };
+
+        info!(
+            "📂 부분 요청 제공: {} ({}개 파일, {} bytes)",
+            root.display(),
+            catalog.files.len(),
+            catalog.total_size
+        );
+        self.offers.lock().insert(
+            catalog.offer_id.clone(),
+            Arc::new(Offer {
+                root,
+                peer_id,
+                catalog: catalog.clone(),
+            }),
+        );
+        Ok(catalog)
+    }
+
+    /// 제공 중단
+    pub fn withdraw(&self, offer_id: &str) -> bool {
+        self.offers.lock().remove(offer_id).is_some()
+    }
+
+    /// 제공 중인 목록
+    pub fn list(&self) -> Vec<OfferCatalog> {
+        self.offers
+            .lock()
+            .values()
+            .map(|offer| offer.catalog.clone())
+            .collect()
+    }
+
+    /// 제어 스트림의 `RangeRequest`/`RangeResponse` 처리
+    pub async fn handle_incoming(&self, conn: quinn::Connection, incoming: IncomingStream) {
+        let IncomingStream {
+            peer_id,
+            command,
+            mut recv,
+        } = incoming;
+
+        match command {
+            Command::RangeRequest {
+                request_id,
+                offer_id,
+                path,
+                start,
+                end,
+            } => {
+                let _ = control_stream::finish_header_only(&mut recv).await;
+                let offer = self
+                    .offers
+                    .lock()
+                    .get(&offer_id)
+                    .filter(|offer| offer.peer_id.as_ref().map_or(true, |id| *id == peer_id))
+                    .cloned();
+                let range = ByteRange { start, end };
+                if let Err(e) = serve(&conn, request_id, offer, path, range).await {
+                    warn!("범위 응답 전송 실패 ({}): {}", peer_id, e);
+                }
+            }
+            Command::RangeResponse {
+                request_id,
+                found,
+                size,
+            } => {
+                let Some(tx) = self.pending.lock().remove(&request_id) else {
+                    let _ = recv.stop(STOP_UNKNOWN.into());
+                    return;
+                };
+                if found {
+                    let _ = tx.send(Some((size, recv)));
+                } else {
+                    let _ = control_stream::finish_header_only(&mut recv).await;
+                    let _ = tx.send(None);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// 요청을 보내고 응답 헤더 대기 (없는 항목이면 None)
+    async fn request(
+        &self,
+        conn: &quinn::Connection,
+        offer_id: &str,
+        path: Option<String>,
+        range: Option<ByteRange>,
+    ) -> Result<Option<(u64, RecvStream)>> {
+        let request_id = uuid::Uuid::new_v4().to_string();
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().insert(request_id.clone(), tx);
+
+        let result = async {
+            let command = Command::RangeRequest {
+                request_id: request_id.clone(),
+                offer_id: offer_id.to_string(),
+                path,
+                start: range.map_or(0, |range| range.start),
+                end: range.and_then(|range| range.end),
+            };
+            let mut send = control_stream::open(conn, &command).await?;
+            send.finish()?;
+            tokio::time::timeout(RESPONSE_TIMEOUT, rx)
+                .await
+                .map_err(|_| anyhow!("응답 시간 초과"))?
+                .map_err(|_| anyhow!("응답 대기 중단"))
+        }
+        .await;
+
+        self.pending.lock().remove(&request_id);
+        result
+    }
+
+    /// 피어의 제공 목록 받기
+    pub async fn fetch_catalog(
+        &self,
+        conn: &quinn::Connection,
+        offer_id: &str,
+    ) -> Result<OfferCatalog> {
+        let (size, mut recv) = self
+            .request(conn, offer_id, None, None)
+            .await?
+            .ok_or_else(|| anyhow!("제공 목록을 찾을 수 없습니다: {}", offer_id))?;
+        if size as usize > MAX_CATALOG_BYTES {
+            bail!("제공 목록이 너무 큽니다: {} bytes", size);
+        }
+        let data = recv.read_to_end(MAX_CATALOG_BYTES).await?;
+        Ok(serde_json::from_slice(&data)?)
+    }
+
+    /// 선택한 파일/폴더/범위를 `save_dir`에 받기 (레지스트리 작업 하나로 진행률/일시정지/취소 반영)
+    #[allow(clippy::too_many_arguments)]
+    pub async fn download(
+        &self,
+        conn: &quinn::Connection,
+        peer_id: &str,
+        offer_id: &str,
+        selection: &[FileSelection],
+        save_dir: &Path,
+        policy: OverwritePolicy,
+        registry: &TransferRegistry,
+        job_id: &str,
+    ) -> Result<Vec<RangeDownloadResult>, AppError> {
+        let catalog = self
+            .fetch_catalog(conn, offer_id)
+            .await
+            .map_err(|e| AppError::Network(format!("제공 목록 요청 실패: {}", e)))?;
+        let wanted = expand_selection(&catalog, selection)
+            .map_err(|e| AppError::InvalidInput(e.to_string()))?;
+        let total: u64 = wanted
+            .iter()
+            .map(|(file, range)| range.map_or(file.size, |r| r.clamp(file.size).1))
+            .sum();
+
+        let control = registry.register(job_id, peer_id, TransferKind::File)?;
+        registry.update_progress(job_id, 0, total, 0);
+        let started = Instant::now();
+        let mut received = 0u64;
+
+        let result = async {
+            let mut results = Vec::with_capacity(wanted.len());
+            for (file, range) in wanted {
+                let destination = safe_destination(save_dir, &file.path)?;
+                let (destination, action) = resolve_destination(&destination, policy)?;
+                if action == ReceiveAction::Skipped {
+                    info!("⏭️ 이미 있는 파일 건너뜀: {}", destination.display());
+                    received += range.map_or(file.size, |r| r.clamp(file.size).1);
+                    results.push(RangeDownloadResult {
+                        path: file.path,
+                        destination: destination.to_string_lossy().to_string(),
+                        bytes: 0,
+                        range,
+                        action: action.as_str(),
+                    });
+                    continue;
+                }
+
+                let (size, mut recv) = self
+                    .request(conn, offer_id, Some(file.path.clone()), range)
+                    .await?
+                    .ok_or_else(|| anyhow!("피어에게 없는 파일입니다: {}", file.path))?;
+
+                let part = part_file::part_path(&destination);
+                if let Some(parent) = part.parent() {
+                    tokio::fs::create_dir_all(parent).await?;
+                }
+                let mut out = tokio::fs::File::create(&part).await?;
+                let copied = async {
+                    let mut buf = vec![0u8; CHUNK_SIZE];
+                    let mut remaining = size;
+                    while remaining > 0 {
+                        control.checkpoint().await?;
+                        let want = remaining.min(CHUNK_SIZE as u64) as usize;
+                        let n = recv
+                            .read(&mut buf[..want])
+                            .await?
+                            .ok_or_else(|| anyhow!("범위 데이터가 중간에 끊겼습니다"))?;
+                        out.write_all(&buf[..n]).await?;
+                        remaining -= n as u64;
+                        received += n as u64;
+
+                        let elapsed = started.elapsed().as_secs_f64().max(f64::EPSILON);
+                        registry.update_progress(
+                            job_id,
+                            received,
+                            total,
+                            (received as f64 / elapsed) as u64,
+                        );
+                    }
+                    out.flush().await?;
+                    anyhow::Ok(())
+                }
+                .await;
+                drop(out);
+                if let Err(e) = copied {
+                    let _ = recv.stop(STOP_UNKNOWN.into());
+                    part_file::discard(&part);
+                    return Err(e);
+                }
+                part_file::commit(&part, &destination)?;
+
+                results.push(RangeDownloadResult {
+                    path: file.path,
+                    destination: destination.to_string_lossy().to_string(),
+                    bytes: size,
+                    range,
+                    action: action.as_str(),
+                });
+            }
+            anyhow::Ok(results)
+        }
+        .await
+        .map_err(|e| match e.downcast::<AppError>() {
+            Ok(e) => e,
+            Err(e) => AppError::Network(format!("부분 요청 수신 실패: {}", e)),
+        });
+
+        registry.finish(job_id, result.as_ref().map(|_| ()).map_err(|e| e.clone()));
+        if let Ok(results) = &result {
+            info!(
+                "✅ 부분 요청 수신 완료: {}개 파일, {} bytes ({})",
+                results.len(),
+                received,
+                catalog.name
+            );
+        }
+        result
+    }
+}
+
+/// 요청 응답 전송 (`path`가 없으면 목록 JSON, 있으면 범위 데이터)
+async fn serve(
+    conn: &quinn::Connection,
+    request_id: String,
+    offer: Option<Arc<Offer>>,
+    path: Option<String>,
+    range: ByteRange,
+) -> Result<()> {
+    let not_found = Command::RangeResponse {
+        request_id: request_id.clone(),
+        found: false,
+        size: 0,
+    };
+    let Some(offer) = offer else {
+        control_stream::open(conn, &not_found).await?.finish()?;
+        return Ok(());
+    };
+
+    let Some(path) = path else {
+        let data = serde_json::to_vec(&offer.catalog)?;
+        let command = Command::RangeResponse {
+            request_id,
+            found: true,
+            size: data.len() as u64,
+        };
+        let mut send = control_stream::open(conn, &command).await?;
+        send.write_all(&data).await?;
+        send.finish()?;
+        return Ok(());
+    };
+
+    // 목록에 있는 경로만 (요청 경로로 제공 폴더 밖을 읽지 못하도록)
+    let opened = async {
+        if !offer.catalog.files.iter().any(|file| file.path == path) {
+            bail!("제공 목록에 없는 경로: {}", path);
+        }
+        let file_path = if offer.root.is_file() {
+            offer.root.clone()
+        } else {
+            offer.root.join(&path)
+        };
+        let mut file = tokio::fs::File::open(&file_path).await?;
+        let size = file.metadata().await?.len();
+        let (offset, len) = range.clamp(size);
+        file.seek(SeekFrom::Start(offset)).await?;
+        Ok((file, len))
+    }
+    .await;
+    let (file, len) = match opened {
+        Ok(opened) => opened,
+        Err(e) => {
+            warn!("범위 요청 거부: {}", e);
+            control_stream::open(conn, &not_found).await?.finish()?;
+            return Ok(());
+        }
+    };
+
+    let command = Command::RangeResponse {
+        request_id,
+        found: true,
+        size: len,
+    };
+    let mut send = control_stream::open(conn, &command).await?;
+    let mut reader = file.take(len);
+    let mut buf = vec![0u8; CHUNK_SIZE];
+    loop {
+        let n = reader.read(&mut buf).await?;
+        if n == 0 {
+            break;
+        }
+        send.write_all(&buf[..n]).await?;
+    }
+    send.finish()?;
+    Ok(())
+}
+
+/// 제공할 파일 목록 만들기 (파일 하나면 그 파일만)
+fn scan_offer(root: &Path) -> Result<Vec<OfferedFile>> {
+    fn walk(dir: &Path, base: &Path, files: &mut Vec<OfferedFile>) -> std::io::Result<()> {
+        for entry in std::fs::read_dir(dir)? {
+            let entry = entry?;
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            let file_type = entry.file_type()?;
+            let path = entry.path();
+            if file_type.is_dir() {
+                walk(&path, base, files)?;
+            } else if file_type.is_file() {
+                let relative = path
+                    .strip_prefix(base)
+                    .map(|p| p.to_string_lossy().replace('\\', "/"))
+                    .unwrap_or_default();
+                files.push(OfferedFile {
+                    path: relative,
+                    size: entry.metadata()?.len(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    let metadata = std::fs::metadata(root)?;
+    let mut files = Vec::new();
+    if metadata.is_file() {
+        let name = root
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .ok_or_else(|| anyhow!("잘못된 파일 경로: {}", root.display()))?;
+        files.push(OfferedFile {
+            path: name,
+            size: metadata.len(),
+        });
+    } else {
+        walk(root, root, &mut files)?;
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+    }
+    Ok(files)
+}
+
+/// 선택을 받을 파일 목록으로 펼침 (폴더 경로는 그 아래 모든 파일, 중복 제거)
+fn expand_selection(
+    catalog: &OfferCatalog,
+    selection: &[FileSelection],
+) -> Result<Vec<(OfferedFile, Option<ByteRange>)>> {
+    if selection.is_empty() {
+        bail!("선택한 파일이 없습니다");
+    }
+
+    let mut wanted: Vec<(OfferedFile, Option<ByteRange>)> = Vec::new();
+    for item in selection {
+        let path = item.path.trim_matches('/');
+        let matched: Vec<&OfferedFile> = match catalog.files.iter().find(|file| file.path == path) {
+            Some(file) => vec![file],
+            None => {
+                if item.range.is_some() {
+                    bail!("범위는 파일에만 지정할 수 있습니다: {}", item.path);
+                }
+                let prefix = format!("{}/", path);
+                catalog
+                    .files
+                    .iter()
+                    .filter(|file| path.is_empty() || file.path.starts_with(&prefix))
+                    .collect()
+            }
+        };
+        if matched.is_empty() {
+            bail!("제공 목록에 없는 경로: {}", item.path);
+        }
+        for file in matched {
+            if !wanted
+                .iter()
+                .any(|(existing, _)| existing.path == file.path)
+            {
+                wanted.push((file.clone(), item.range));
+            }
+        }
+    }
+    Ok(wanted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn catalog() -> OfferCatalog {
+        let files = vec![
+            OfferedFile {
+                path: "docs/a.txt".into(),
+                size: 10,
+            },
+            OfferedFile {
+                path: "docs/sub/b.txt".into(),
+                size: 20,
+            },
+            OfferedFile {
+                path: "docsx/c.txt".into(),
+                size: 30,
+            },
+            OfferedFile {
+                path: "video.mp4".into(),
+                size: 1000,
+            },
+        ];
+        OfferCatalog {
+            offer_id: "offer".into(),
+            name: "project".into(),
+            total_size: files.iter().map(|file| file.size).sum(),
+            files,
+        }
+    }
+
+    fn select(path: &str, range: Option<ByteRange>) -> FileSelection {
+        FileSelection {
+            path: path.into(),
+            range,
+        }
+    }
+
+    #[test]
+    fn test_expand_selection_and_ranges() {
+        let catalog = catalog();
+
+        // 폴더 선택은 접두사가 같은 다른 폴더(docsx)를 포함하지 않음
+        let wanted = expand_selection(&catalog, &[select("docs/", None)]).unwrap();
+        let paths: Vec<&str> = wanted.iter().map(|(file, _)| file.path.as_str()).collect();
+        assert_eq!(paths, vec!["docs/a.txt", "docs/sub/b.txt"]);
+
+        // 파일과 범위, 중복 선택은 한 번만
+        let range = ByteRange {
+            start: 100,
+            end: Some(200),
+        };
+        let wanted = expand_selection(
+            &catalog,
+            &[select("video.mp4", Some(range)), select("video.mp4", None)],
+        )
+        .unwrap();
+        assert_eq!(wanted.len(), 1);
+        assert_eq!(wanted[0].1, Some(range));
+        assert_eq!(range.clamp(1000), (100, 100));
+        assert_eq!(range.clamp(150), (100, 50));
+        assert_eq!(range.clamp(50), (50, 0));
+        let open_ended = ByteRange {
+            start: 900,
+            end: None,
+        };
+        assert_eq!(open_ended.clamp(1000), (900, 100));
+
+        // 빈 경로는 전체, 목록 밖 경로와 폴더 범위는 거부
+        assert_eq!(
+            expand_selection(&catalog, &[select("", None)])
+                .unwrap()
+                .len(),
+            4
+        );
+        assert!(expand_selection(&catalog, &[select("../etc/passwd", None)]).is_err());
+        assert!(expand_selection(&catalog, &[select("docs", Some(range))]).is_err());
+        assert!(expand_selection(&catalog, &[]).is_err());
+    }
+
+    #[test]
+    fn test_scan_offer() {
+        let dir = std::env::temp_dir().join(format!("pswp-offer-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("sub")).unwrap();
+        std::fs::write(dir.join("a.bin"), [1u8; 5]).unwrap();
+        std::fs::write(dir.join("sub/b.bin"), [2u8; 7]).unwrap();
+        std::fs::write(dir.join(".hidden"), [3u8; 1]).unwrap();
+
+        let files = scan_offer(&dir).unwrap();
+        assert_eq!(
+            files,
+            vec![
+                OfferedFile {
+                    path: "a.bin".into(),
+                    size: 5
+                },
+                OfferedFile {
+                    path: "sub/b.bin".into(),
+                    size: 7
+                },
+            ]
+        );
+        let single = scan_offer(&dir.join("sub/b.bin")).unwrap();
+        assert_eq!(single[0].path, "b.bin");
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
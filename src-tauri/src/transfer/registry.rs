@@ -0,0 +1,653 @@
+//! 전송 작업 레지스트리
+//!
+//! job_id 기준으로 실행 중/완료된 전송의 진행률, 상태, 제어 핸들(취소/일시정지)을 보관합니다.
+//! 명령마다 엔진을 새로 만들더라도 상태 조회와 취소/일시정지는 이 레지스트리를 통해 이루어집니다.
+//!
+//! 엔진 종류(File/Multistream/Zip/Grid)와 관계없이 상태가 바뀌면 여기서 같은 스키마
+//! ([`TransferJobSnapshot`])로 수명주기 이벤트를 내보냅니다.
+
+use crate::error::AppError;
+use crate::metrics::{MetricsCollector, Subsystem};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::{mpsc, Notify};
+
+/// 완료/실패한 작업을 보관하는 최대 개수 (오래된 것부터 정리)
+const MAX_FINISHED_JOBS: usize = 100;
+
+/// 작업별 속도 기록 보관 기간 (초당 1개, 최근 10분)
+const SPEED_HISTORY_SECS: u64 = 600;
+
+/// 수명주기 이벤트 이름
+pub const EVENT_STARTED: &str = "transfer-started";
+pub const EVENT_PROGRESS: &str = "transfer-progress";
+pub const EVENT_PAUSED: &str = "transfer-paused";
+pub const EVENT_RESUMED: &str = "transfer-resumed";
+pub const EVENT_COMPLETED: &str = "transfer-completed";
+/// 실패와 취소 모두 (상태 필드로 구분)
+pub const EVENT_FAILED: &str = "transfer-failed";
+
+/// (이벤트 이름, 작업 스냅샷)
+pub type TransferEvent = (&'static str, TransferJobSnapshot);
+
+/// 전송 엔진 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransferKind {
+    File,
+    Multistream,
+    Zip,
+    Grid,
+    MultiSource,
+    Directory,
+}
+
+/// 작업 상태
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    Running,
+    Paused,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl JobStatus {
+    pub fn is_finished(self) -> bool {
+        matches!(
+            self,
+            JobStatus::Completed | JobStatus::Failed | JobStatus::Cancelled
+        )
+    }
+}
+
+/// 작업 제어 핸들 (엔진이 블록/청크 경계에서 확인)
+#[derive(Clone, Default)]
+pub struct JobControl {
+    cancelled: Arc<AtomicBool>,
+    paused: Arc<AtomicBool>,
+    changed: Arc<Notify>,
+}
+
+impl JobControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    /// 취소 플래그 (AtomicBool 기반 엔진용)
+    pub fn cancel_flag(&self) -> Arc<AtomicBool> {
+        self.cancelled.clone()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        self.changed.notify_waiters();
+    }
+
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::SeqCst);
+        self.changed.notify_waiters();
+    }
+
+    /// 일시정지 중이면 재개되거나 취소될 때까지 대기
+    pub async fn wait_if_paused(&self) {
+        loop {
+            let notified = self.changed.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+
+            if !self.is_paused() || self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    /// 취소될 때까지 대기 (select!에서 블로킹 대기와 함께 사용)
+    pub async fn cancelled(&self) {
+        loop {
+            let notified = self.changed.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    /// 일시정지면 대기한 뒤, 취소되었으면 에러 반환
+    pub async fn checkpoint(&self) -> anyhow::Result<()> {
+        self.wait_if_paused().await;
+        if self.is_cancelled() {
+            return Err(anyhow::anyhow!("사용자에 의해 취소됨"));
+        }
+        Ok(())
+    }
+}
+
+struct TransferJob {
+    peer_id: String,
+    kind: TransferKind,
+    status: JobStatus,
+    bytes_transferred: u64,
+    total_bytes: u64,
+    speed_bps: u64,
+    error: Option<AppError>,
+    action: Option<&'static str>,
+    started_at: Instant,
+    finished_at: Option<Instant>,
+    control: JobControl,
+    speed_history: VecDeque<SpeedSample>,
+    /// 마지막 기록 시점 (시작 후 초, 누적 바이트)
+    sampled_at: (u64, u64),
+}
+
+impl TransferJob {
+    /// 초가 바뀌었으면 지난 기록 이후의 처리량을 초당 샘플로 기록
+    ///
+    /// 진행률 보고가 없던 초(정체, 일시정지)는 그 구간의 평균 처리량으로 채웁니다.
+    fn record_speed_sample(&mut self) {
+        let now = self.started_at.elapsed().as_secs();
+        let (last_secs, last_bytes) = self.sampled_at;
+        if now <= last_secs {
+            return;
+        }
+
+        let rate = self.bytes_transferred.saturating_sub(last_bytes) / (now - last_secs);
+        let first = (last_secs + 1).max((now + 1).saturating_sub(SPEED_HISTORY_SECS));
+        for second in first..=now {
+            if self.speed_history.len() as u64 >= SPEED_HISTORY_SECS {
+                self.speed_history.pop_front();
+            }
+            self.speed_history.push_back(SpeedSample {
+                elapsed_secs: second,
+                bytes_per_sec: rate,
+            });
+        }
+        self.sampled_at = (now, self.bytes_transferred);
+    }
+}
+
+/// 초당 처리량 샘플 (속도 그래프용)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpeedSample {
+    /// 작업 시작 후 경과 초
+    pub elapsed_secs: u64,
+    pub bytes_per_sec: u64,
+}
+
+/// 작업 상태 스냅샷 (프론트엔드 조회용)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferJobSnapshot {
+    pub job_id: String,
+    pub peer_id: String,
+    pub kind: TransferKind,
+    pub status: JobStatus,
+    /// 진행률 (0~100)
+    pub progress: f64,
+    pub bytes_transferred: u64,
+    pub total_bytes: u64,
+    pub speed_bps: u64,
+    pub error: Option<AppError>,
+    /// 수신 시 기존 파일 처리 결과 (created/overwritten/skipped/renamed)
+    pub action: Option<&'static str>,
+    pub elapsed_secs: f64,
+}
+
+/// 진행 중인 작업 합계 (트레이 표시용)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferSummary {
+    pub running: usize,
+    pub paused: usize,
+    pub bytes_transferred: u64,
+    pub total_bytes: u64,
+    pub speed_bps: u64,
+}
+
+impl TransferSummary {
+    /// 전체 진행률 (0~100, 크기를 모르면 None)
+    pub fn progress(&self) -> Option<f64> {
+        (self.total_bytes > 0)
+            .then(|| self.bytes_transferred as f64 / self.total_bytes as f64 * 100.0)
+    }
+}
+
+/// job_id → 작업
+#[derive(Default)]
+pub struct TransferRegistry {
+    jobs: Mutex<HashMap<String, TransferJob>>,
+    events: Option<mpsc::UnboundedSender<TransferEvent>>,
+    /// 앱 전체 처리량 지표 (진행률 증가분을 작업 종류별 서브시스템에 기록)
+    metrics: Option<Arc<MetricsCollector>>,
+}
+
+impl TransferRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 수명주기 이벤트 채널 설정 (프론트엔드로 전달)
+    pub fn with_event_channel(mut self, tx: mpsc::UnboundedSender<TransferEvent>) -> Self {
+        self.events = Some(tx);
+        self
+    }
+
+    /// 앱 전체 처리량 지표 수집기 설정
+    pub fn with_metrics(mut self, metrics: Arc<MetricsCollector>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// 새 작업 등록 (같은 job_id가 진행 중이면 에러)
+    pub fn register(
+        &self,
+        job_id: &str,
+        peer_id: &str,
+        kind: TransferKind,
+    ) -> Result<JobControl, AppError> {
+        let mut jobs = self.jobs.lock();
+        if let Some(existing) = jobs.get(job_id) {
+            if !existing.status.is_finished() {
+                return Err(AppError::InvalidInput(format!(
+                    "이미 진행 중인 작업입니다: {}",
+                    job_id
+                )));
+            }
+        }
+
+        let control = JobControl::new();
+        let job = TransferJob {
+            peer_id: peer_id.to_string(),
+            kind,
+            status: JobStatus::Running,
+            bytes_transferred: 0,
+            total_bytes: 0,
+            speed_bps: 0,
+            error: None,
+            action: None,
+            started_at: Instant::now(),
+            finished_at: None,
+            control: control.clone(),
+            speed_history: VecDeque::new(),
+            sampled_at: (0, 0),
+        };
+        self.emit(EVENT_STARTED, job_id, &job);
+        if let Some(metrics) = &self.metrics {
+            metrics.counter(Subsystem::of_kind(kind)).started();
+        }
+        jobs.insert(job_id.to_string(), job);
+        Ok(control)
+    }
+
+    /// 진행률 갱신
+    pub fn update_progress(&self, job_id: &str, bytes: u64, total: u64, speed_bps: u64) {
+        if let Some(job) = self.jobs.lock().get_mut(job_id) {
+            if job.status.is_finished() {
+                return;
+            }
+            if let Some(metrics) = &self.metrics {
+                metrics.record(
+                    Subsystem::of_kind(job.kind),
+                    bytes.saturating_sub(job.bytes_transferred),
+                );
+            }
+            job.bytes_transferred = bytes;
+            job.total_bytes = total;
+            job.speed_bps = speed_bps;
+            job.record_speed_sample();
+            self.emit(EVENT_PROGRESS, job_id, job);
+        }
+    }
+
+    /// 작업 종료 기록 (취소 요청 후 실패한 작업은 Cancelled로 기록)
+    pub fn finish(&self, job_id: &str, result: Result<(), AppError>) {
+        let mut jobs = self.jobs.lock();
+        if let Some(job) = jobs.get_mut(job_id) {
+            if job.status.is_finished() {
+                return;
+            }
+            job.status = match &result {
+                Ok(()) => JobStatus::Completed,
+                Err(_) if job.control.is_cancelled() => JobStatus::Cancelled,
+                Err(_) => JobStatus::Failed,
+            };
+            if job.status == JobStatus::Completed && job.total_bytes > 0 {
+                job.bytes_transferred = job.total_bytes;
+            }
+            job.error = result.err();
+            job.speed_bps = 0;
+            job.finished_at = Some(Instant::now());
+            if let Some(metrics) = &self.metrics {
+                metrics.counter(Subsystem::of_kind(job.kind)).finished();
+            }
+
+            let event = if job.status == JobStatus::Completed {
+                EVENT_COMPLETED
+            } else {
+                EVENT_FAILED
+            };
+            self.emit(event, job_id, job);
+        }
+        Self::prune_finished(&mut jobs);
+    }
+
+    /// 작업에 적용된 처리 기록 (finish 전에 호출하면 완료 이벤트에 포함)
+    pub fn set_action(&self, job_id: &str, action: &'static str) {
+        if let Some(job) = self.jobs.lock().get_mut(job_id) {
+            job.action = Some(action);
+        }
+    }
+
+    pub fn cancel(&self, job_id: &str) -> bool {
+        self.with_running(job_id, |job| job.control.cancel())
+    }
+
+    pub fn pause(&self, job_id: &str) -> bool {
+        let changed = self.with_running(job_id, |job| {
+            job.control.set_paused(true);
+            job.status = JobStatus::Paused;
+            job.speed_bps = 0;
+        });
+        if changed {
+            self.emit_current(EVENT_PAUSED, job_id);
+        }
+        changed
+    }
+
+    pub fn resume(&self, job_id: &str) -> bool {
+        let changed = self.with_running(job_id, |job| {
+            job.control.set_paused(false);
+            job.status = JobStatus::Running;
+        });
+        if changed {
+            self.emit_current(EVENT_RESUMED, job_id);
+        }
+        changed
+    }
+
+    pub fn snapshot(&self, job_id: &str) -> Option<TransferJobSnapshot> {
+        self.jobs
+            .lock()
+            .get(job_id)
+            .map(|job| Self::to_snapshot(job_id, job))
+    }
+
+    /// 작업의 초당 처리량 기록 (오래된 순, 최근 10분)
+    pub fn speed_history(&self, job_id: &str) -> Option<Vec<SpeedSample>> {
+        self.jobs
+            .lock()
+            .get(job_id)
+            .map(|job| job.speed_history.iter().copied().collect())
+    }
+
+    /// 전체 작업 (시작 순)
+    pub fn list(&self) -> Vec<TransferJobSnapshot> {
+        let jobs = self.jobs.lock();
+        let mut list: Vec<_> = jobs.iter().collect();
+        list.sort_by_key(|(_, job)| job.started_at);
+        list.into_iter()
+            .map(|(job_id, job)| Self::to_snapshot(job_id, job))
+            .collect()
+    }
+
+    /// 실행 중인 모든 작업 일시정지 (바뀐 작업 수)
+    pub fn pause_all(&self) -> usize {
+        self.ids_with_status(JobStatus::Running)
+            .iter()
+            .filter(|job_id| self.pause(job_id))
+            .count()
+    }
+
+    /// 일시정지된 모든 작업 재개 (바뀐 작업 수)
+    pub fn resume_all(&self) -> usize {
+        self.ids_with_status(JobStatus::Paused)
+            .iter()
+            .filter(|job_id| self.resume(job_id))
+            .count()
+    }
+
+    /// 진행 중(일시정지 포함)인 작업 합계
+    pub fn summary(&self) -> TransferSummary {
+        let jobs = self.jobs.lock();
+        let mut summary = TransferSummary::default();
+        for job in jobs.values().filter(|job| !job.status.is_finished()) {
+            match job.status {
+                JobStatus::Paused => summary.paused += 1,
+                _ => summary.running += 1,
+            }
+            summary.bytes_transferred += job.bytes_transferred;
+            summary.total_bytes += job.total_bytes;
+            summary.speed_bps += job.speed_bps;
+        }
+        summary
+    }
+
+    fn ids_with_status(&self, status: JobStatus) -> Vec<String> {
+        self.jobs
+            .lock()
+            .iter()
+            .filter(|(_, job)| job.status == status)
+            .map(|(job_id, _)| job_id.clone())
+            .collect()
+    }
+
+    /// 진행 중(일시정지 포함)인 작업이 있는지
+    pub fn has_active(&self) -> bool {
+        self.jobs
+            .lock()
+            .values()
+            .any(|job| !job.status.is_finished())
+    }
+
+    fn with_running(&self, job_id: &str, f: impl FnOnce(&mut TransferJob)) -> bool {
+        match self.jobs.lock().get_mut(job_id) {
+            Some(job) if !job.status.is_finished() => {
+                f(job);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn emit(&self, event: &'static str, job_id: &str, job: &TransferJob) {
+        if let Some(tx) = &self.events {
+            let _ = tx.send((event, Self::to_snapshot(job_id, job)));
+        }
+    }
+
+    fn emit_current(&self, event: &'static str, job_id: &str) {
+        if let Some(job) = self.jobs.lock().get(job_id) {
+            self.emit(event, job_id, job);
+        }
+    }
+
+    fn to_snapshot(job_id: &str, job: &TransferJob) -> TransferJobSnapshot {
+        let end = job.finished_at.unwrap_or_else(Instant::now);
+        let progress = if job.total_bytes > 0 {
+            (job.bytes_transferred as f64 / job.total_bytes as f64 * 100.0).min(100.0)
+        } else {
+            0.0
+        };
+        TransferJobSnapshot {
+            job_id: job_id.to_string(),
+            peer_id: job.peer_id.clone(),
+            kind: job.kind,
+            status: job.status,
+            progress,
+            bytes_transferred: job.bytes_transferred,
+            total_bytes: job.total_bytes,
+            speed_bps: job.speed_bps,
+            error: job.error.clone(),
+            action: job.action,
+            elapsed_secs: end.duration_since(job.started_at).as_secs_f64(),
+        }
+    }
+
+    fn prune_finished(jobs: &mut HashMap<String, TransferJob>) {
+        let mut finished: Vec<(String, Instant)> = jobs
+            .iter()
+            .filter_map(|(id, job)| job.finished_at.map(|at| (id.clone(), at)))
+            .collect();
+        if finished.len() <= MAX_FINISHED_JOBS {
+            return;
+        }
+        finished.sort_by_key(|(_, at)| *at);
+        let excess = finished.len() - MAX_FINISHED_JOBS;
+        for (id, _) in finished.into_iter().take(excess) {
+            jobs.remove(&id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_registry_lifecycle() {
+        let registry = TransferRegistry::new();
+        let control = registry
+            .register("job-1", "peer-a", TransferKind::Multistream)
+            .unwrap();
+        assert!(registry
+            .register("job-1", "peer-a", TransferKind::File)
+            .is_err());
+
+        registry.update_progress("job-1", 50, 100, 10);
+        assert!(registry.pause("job-1"));
+        assert_eq!(
+            registry.snapshot("job-1").unwrap().status,
+            JobStatus::Paused
+        );
+
+        // 일시정지 중인 엔진은 재개될 때까지 대기
+        let waiter = {
+            let control = control.clone();
+            tokio::spawn(async move { control.checkpoint().await })
+        };
+        tokio::task::yield_now().await;
+        assert!(!waiter.is_finished());
+        assert!(registry.resume("job-1"));
+        assert!(waiter.await.unwrap().is_ok());
+
+        assert!(registry.cancel("job-1"));
+        assert!(control.checkpoint().await.is_err());
+        registry.finish("job-1", Err(AppError::Cancelled("cancelled".into())));
+
+        let snapshot = registry.snapshot("job-1").unwrap();
+        assert_eq!(snapshot.status, JobStatus::Cancelled);
+        assert_eq!(snapshot.bytes_transferred, 50);
+        assert!(!registry.has_active());
+        assert!(!registry.cancel("job-1"));
+    }
+
+    #[test]
+    fn test_pause_all_and_summary() {
+        let registry = TransferRegistry::new();
+        for job_id in ["job-1", "job-2", "job-3"] {
+            registry
+                .register(job_id, "peer-a", TransferKind::File)
+                .unwrap();
+            registry.update_progress(job_id, 25, 100, 10);
+        }
+        registry.finish("job-3", Ok(()));
+
+        assert_eq!(registry.pause_all(), 2);
+        assert_eq!(registry.pause_all(), 0);
+        let summary = registry.summary();
+        assert_eq!((summary.running, summary.paused), (0, 2));
+        assert_eq!(summary.progress(), Some(25.0));
+        assert_eq!(summary.speed_bps, 0);
+
+        assert_eq!(registry.resume_all(), 2);
+        assert_eq!(registry.summary().running, 2);
+    }
+
+    #[test]
+    fn test_lifecycle_events() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let registry = TransferRegistry::new().with_event_channel(tx);
+
+        registry
+            .register("job-1", "peer-a", TransferKind::Zip)
+            .unwrap();
+        registry.update_progress("job-1", 25, 100, 10);
+        registry.pause("job-1");
+        registry.resume("job-1");
+        registry.finish("job-1", Ok(()));
+        // 종료 후 늦게 도착한 진행률/종료는 무시
+        registry.update_progress("job-1", 30, 100, 10);
+        registry.finish("job-1", Err(AppError::Network("late".into())));
+
+        let mut events = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            events.push(event);
+        }
+        let names: Vec<_> = events.iter().map(|(name, _)| *name).collect();
+        assert_eq!(
+            names,
+            vec![
+                EVENT_STARTED,
+                EVENT_PROGRESS,
+                EVENT_PAUSED,
+                EVENT_RESUMED,
+                EVENT_COMPLETED
+            ]
+        );
+        assert_eq!(events[1].1.progress, 25.0);
+        assert_eq!(events[2].1.status, JobStatus::Paused);
+
+        let (_, completed) = events.last().unwrap();
+        assert_eq!(completed.kind, TransferKind::Zip);
+        assert_eq!(completed.status, JobStatus::Completed);
+        assert_eq!(completed.progress, 100.0);
+        assert!(completed.error.is_none());
+    }
+
+    #[test]
+    fn test_speed_history() {
+        let registry = TransferRegistry::new();
+        registry
+            .register("job-1", "peer-a", TransferKind::File)
+            .unwrap();
+        assert_eq!(registry.speed_history("job-1"), Some(Vec::new()));
+
+        // 3초 동안 보고가 없었으면 그 구간 평균으로 초마다 채움
+        {
+            let mut jobs = registry.jobs.lock();
+            let job = jobs.get_mut("job-1").unwrap();
+            job.started_at -= std::time::Duration::from_secs(3);
+        }
+        registry.update_progress("job-1", 3000, 10_000, 0);
+        // 같은 초 안의 보고는 샘플을 늘리지 않음
+        registry.update_progress("job-1", 3500, 10_000, 0);
+
+        let history = registry.speed_history("job-1").unwrap();
+        assert_eq!(
+            history
+                .iter()
+                .map(|s| (s.elapsed_secs, s.bytes_per_sec))
+                .collect::<Vec<_>>(),
+            vec![(1, 1000), (2, 1000), (3, 1000)]
+        );
+        assert!(registry.speed_history("missing").is_none());
+    }
+}
@@ -0,0 +1,920 @@
+//! 신뢰성 UDP 전송 모드 (QUIC 대체)
+//!
+//! 통제된 10/40GbE LAN에서 QUIC의 암호화/혼잡 제어 오버헤드 없이
+//! `UdpTransferCore`의 다중 소켓으로 파일을 전송합니다.
+//!
+//! 전략:
+//! - 제어 메시지(매니페스트, 포트 교환, 완료 통지)는 기존 QUIC 연결의 스트림 하나로 교환
+//! - 데이터는 순번(`ChunkHeader::chunk_index`)과 CRC32가 붙은 raw UDP 데이터그램으로 전송
+//! - 수신측은 누적 ACK + 누락 순번(NACK) 목록을 주기적으로 송신측에 보냄
+//! - 송신측은 NACK 순번을 즉시, ACK 없는 순번은 타임아웃 후 재전송
+//! - 송신 윈도우(미확인 최저 순번 기준)로 전송 중인 청크 범위 제한
+//! - 재전송 비율로 목표 속도를 조절하고 `UdpTransferCore` 페이서로 전송 간격 유지
+
+use anyhow::Result;
+use bytes::Bytes;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+
+use super::file_transfer::{resolve_destination, safe_destination, OverwritePolicy, ReceiveAction};
+use super::multistream::{MultiStreamProgress, SpeedCalculator};
+use super::pacer::RateController;
+use super::part_file;
+use super::udp_core::{ChunkHeader, UdpTransferCore, MAX_CHUNK_DATA};
+use super::zero_copy_io::{BlockInfo, HighPerformanceFileReceiver, HighPerformanceFileSender};
+
+/// 데이터그램당 기본 청크 크기 (점보 프레임 9000 MTU에 맞춤)
+pub const DEFAULT_CHUNK_SIZE: usize = 8192;
+
+/// 데이터그램당 최소 청크 크기
+pub const MIN_CHUNK_SIZE: usize = 512;
+
+/// 작업당 기본 UDP 소켓 수
+pub const DEFAULT_SOCKET_COUNT: usize = 4;
+
+/// 미확인 최저 순번부터 보낼 수 있는 청크 범위
+const SEND_WINDOW: u32 = 4096;
+
+/// 한 번에 읽어 보내는 새 청크 수
+const SEND_BATCH: usize = 256;
+
+/// 수신측 ACK 전송 주기
+const ACK_INTERVAL: Duration = Duration::from_millis(5);
+
+/// ACK 하나에 담는 최대 NACK 수
+const MAX_NACKS: usize = 256;
+
+/// 같은 청크를 NACK으로 다시 보내기 전 최소 간격 (중복 재전송 방지)
+const RETRANSMIT_MIN_INTERVAL: Duration = Duration::from_millis(20);
+
+/// ACK를 받지 못한 청크의 재전송 타임아웃
+const RETRANSMIT_TIMEOUT: Duration = Duration::from_millis(250);
+
+/// 상대가 이 시간 동안 응답(ACK/데이터)이 없으면 실패 처리
+const PEER_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 진행률 이벤트 최소 간격
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 디스크 쓰기 대기열 길이 (청크 수)
+const WRITE_QUEUE: usize = 8192;
+
+/// 제어 메시지 최대 크기
+const MAX_CONTROL_MESSAGE: usize = 64 * 1024;
+
+/// ACK 패킷 고정 헤더: job_id(4) + cumulative(4) + highest(4) + nack_count(2)
+const ACK_HEADER_SIZE: usize = 14;
+
+const MANIFEST_MARKER: &[u8; 4] = b"UDPM";
+const DONE_MARKER: &[u8; 4] = b"UDPD";
+
+/// 작업별 전송 방식
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransferTransport {
+    /// QUIC 멀티스트림 (기본)
+    #[default]
+    Quic,
+    /// 신뢰성 UDP (통제된 LAN 전용, 암호화 없음)
+    Udp,
+}
+
+/// UDP 전송 매니페스트 (제어 스트림)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct UdpManifest {
+    job_id: String,
+    file_name: String,
+    file_size: u64,
+    chunk_size: u32,
+    total_chunks: u32,
+    /// ACK를 받을 송신측 UDP 포트
+    ack_port: u16,
+}
+
+/// 매니페스트 응답: 데이터를 받을 수신측 UDP 포트들
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct UdpManifestAck {
+    data_ports: Vec<u16>,
+}
+
+/// 수신측 → 송신측 ACK/NACK
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct AckPacket {
+    job_id: u32,
+    /// 이 순번 미만은 모두 수신
+    cumulative: u32,
+    /// 수신한 최대 순번 + 1
+    highest: u32,
+    /// `[cumulative, highest)` 구간의 누락 순번 (최대 `MAX_NACKS`개)
+    nacks: Vec<u32>,
+}
+
+impl AckPacket {
+    fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(ACK_HEADER_SIZE + self.nacks.len() * 4);
+        buf.extend_from_slice(&self.job_id.to_le_bytes());
+        buf.extend_from_slice(&self.cumulative.to_le_bytes());
+        buf.extend_from_slice(&self.highest.to_le_bytes());
+        buf.extend_from_slice(&(self.nacks.len() as u16).to_le_bytes());
+        for seq in &self.nacks {
+            buf.extend_from_slice(&seq.to_le_bytes());
+        }
+        buf
+    }
+
+    fn decode(buf: &[u8]) -> Option<Self> {
+        if buf.len() < ACK_HEADER_SIZE {
+            return None;
+        }
+
+        let read_u32 =
+            |at: usize| u32::from_le_bytes([buf[at], buf[at + 1], buf[at + 2], buf[at + 3]]);
+        let nack_count = u16::from_le_bytes([buf[12], buf[13]]) as usize;
+        if nack_count > MAX_NACKS || buf.len() != ACK_HEADER_SIZE + nack_count * 4 {
+            return None;
+        }
+
+        Some(Self {
+            job_id: read_u32(0),
+            cumulative: read_u32(4),
+            highest: read_u32(8),
+            nacks: (0..nack_count)
+                .map(|i| read_u32(ACK_HEADER_SIZE + i * 4))
+                .collect(),
+        })
+    }
+}
+
+/// 송신측 윈도우: 미확인 청크와 마지막 전송 시각 추적
+struct SendWindow {
+    total: u32,
+    /// 이 순번 미만은 모두 확인됨
+    base: u32,
+    /// 처음 보낼 다음 순번
+    next: u32,
+    in_flight: HashMap<u32, Instant>,
+}
+
+impl SendWindow {
+    fn new(total: u32) -> Self {
+        Self {
+            total,
+            base: 0,
+            next: 0,
+            in_flight: HashMap::new(),
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.base >= self.total
+    }
+
+    fn acked_chunks(&self) -> u32 {
+        self.next - self.in_flight.len() as u32
+    }
+
+    /// 윈도우 안에서 새로 보낼 순번
+    fn next_batch(&mut self, max: usize, now: Instant) -> Vec<u32> {
+        let limit = self.total.min(self.base.saturating_add(SEND_WINDOW));
+        let end = limit.min(self.next.saturating_add(max as u32));
+        let batch: Vec<u32> = (self.next..end).collect();
+        for seq in &batch {
+            self.in_flight.insert(*seq, now);
+        }
+        self.next = self.next.max(end);
+        batch
+    }
+
+    /// ACK 반영 후 즉시 재전송할 NACK 순번 반환
+    fn on_ack(&mut self, ack: &AckPacket, now: Instant) -> Vec<u32> {
+        let cumulative = ack.cumulative.min(self.next);
+        if cumulative > self.base {
+            self.base = cumulative;
+        }
+
+        // NACK 목록이 잘렸으면 마지막 NACK 이후는 수신 여부를 알 수 없음
+        let covered = if ack.nacks.len() >= MAX_NACKS {
+            ack.nacks.last().copied().unwrap_or(cumulative)
+        } else {
+            ack.highest.min(self.next)
+        };
+        let nacks: HashSet<u32> = ack.nacks.iter().copied().collect();
+        let base = self.base;
+        self.in_flight
+            .retain(|seq, _| *seq >= base && (*seq >= covered || nacks.contains(seq)));
+
+        let mut resend = Vec::new();
+        for seq in &ack.nacks {
+            if let Some(sent_at) = self.in_flight.get_mut(seq) {
+                if now.duration_since(*sent_at) >= RETRANSMIT_MIN_INTERVAL {
+                    *sent_at = now;
+                    resend.push(*seq);
+                }
+            }
+        }
+        resend
+    }
+
+    /// ACK 없이 타임아웃된 순번 (재전송 시각으로 갱신)
+    fn timed_out(&mut self, now: Instant) -> Vec<u32> {
+        let mut resend = Vec::new();
+        for (seq, sent_at) in self.in_flight.iter_mut() {
+            if now.duration_since(*sent_at) >= RETRANSMIT_TIMEOUT {
+                *sent_at = now;
+                resend.push(*seq);
+            }
+        }
+        resend.sort_unstable();
+        resend
+    }
+}
+
+/// 수신측 윈도우: 청크 수신 비트맵과 누적 ACK 지점
+struct ReceiveWindow {
+    bits: Vec<u64>,
+    total: u32,
+    cumulative: u32,
+    highest: u32,
+    received: u32,
+}
+
+impl ReceiveWindow {
+    fn new(total: u32) -> Self {
+        Self {
+            bits: vec![0; (total as usize).div_ceil(64)],
+            total,
+            cumulative: 0,
+            highest: 0,
+            received: 0,
+        }
+    }
+
+    fn has(&self, seq: u32) -> bool {
+        self.bits[seq as usize / 64] & (1 << (seq % 64)) != 0
+    }
+
+    /// 새 청크면 true (중복/범위 밖이면 false)
+    fn insert(&mut self, seq: u32) -> bool {
+        if seq >= self.total || self.has(seq) {
+            return false;
+        }
+
+        self.bits[seq as usize / 64] |= 1 << (seq % 64);
+        self.received += 1;
+        self.highest = self.highest.max(seq + 1);
+        while self.cumulative < self.total && self.has(self.cumulative) {
+            self.cumulative += 1;
+        }
+        true
+    }
+
+    fn is_complete(&self) -> bool {
+        self.cumulative >= self.total
+    }
+
+    fn ack(&self, job_id: u32) -> AckPacket {
+        AckPacket {
+            job_id,
+            cumulative: self.cumulative,
+            highest: self.highest,
+            nacks: (self.cumulative..self.highest)
+                .filter(|seq| !self.has(*seq))
+                .take(MAX_NACKS)
+                .collect(),
+        }
+    }
+}
+
+/// 문자열 작업 ID → 데이터그램 헤더용 32비트 ID
+fn wire_job_id(job_id: &str) -> u32 {
+    crc32fast::hash(job_id.as_bytes())
+}
+
+fn chunk_count(file_size: u64, chunk_size: usize) -> Result<u32> {
+    let count = file_size.div_ceil(chunk_size as u64);
+    u32::try_from(count).map_err(|_| anyhow::anyhow!("청크 수 초과: {} 청크", count))
+}
+
+/// UDP 코어 소켓은 IPv4(0.0.0.0)에 바인딩되므로 IPv4 피어만 지원
+fn peer_ipv4(conn: &quinn::Connection) -> Result<IpAddr> {
+    match conn.remote_address().ip().to_canonical() {
+        ip @ IpAddr::V4(_) => Ok(ip),
+        ip => Err(anyhow::anyhow!("UDP 전송 모드는 IPv4 피어만 지원: {}", ip)),
+    }
+}
+
+async fn write_json<T: Serialize>(send: &mut quinn::SendStream, value: &T) -> Result<()> {
+    let json = serde_json::to_vec(value)?;
+    send.write_all(&(json.len() as u32).to_le_bytes()).await?;
+    send.write_all(&json).await?;
+    Ok(())
+}
+
+async fn read_json<T: DeserializeOwned>(recv: &mut quinn::RecvStream) -> Result<T> {
+    let mut len_buf = [0u8; 4];
+    recv.read_exact(&mut len_buf).await?;
+    let len = u32::from_le_bytes(len_buf) as usize;
+    if len > MAX_CONTROL_MESSAGE {
+        return Err(anyhow::anyhow!("제어 메시지가 너무 큼: {} bytes", len));
+    }
+
+    let mut buf = vec![0u8; len];
+    recv.read_exact(&mut buf).await?;
+    Ok(serde_json::from_slice(&buf)?)
+}
+
+/// 순번 목록의 청크 데이터 읽기 (블로킹 I/O 격리)
+async fn read_chunks(
+    file: Arc<HighPerformanceFileSender>,
+    seqs: Vec<u32>,
+    chunk_size: usize,
+) -> Result<Vec<(u32, Vec<u8>)>> {
+    tokio::task::spawn_blocking(move || {
+        let file_size = file.file_size();
+        seqs.into_iter()
+            .map(|seq| {
+                let offset = seq as u64 * chunk_size as u64;
+                let block = BlockInfo {
+                    index: seq,
+                    offset,
+                    size: (chunk_size as u64).min(file_size - offset) as u32,
+                    total_blocks: 0,
+                };
+                Ok((seq, file.read_block_owned(&block)?))
+            })
+            .collect()
+    })
+    .await?
+}
+
+/// 신뢰성 UDP 파일 전송기 (Sender)
+pub struct ReliableUdpSender {
+    conn: quinn::Connection,
+    socket_count: usize,
+    chunk_size: usize,
+    /// 사용자 지정 최대 송신 속도 (bytes/sec)
+    max_rate_bps: Option<u64>,
+    progress_tx: Option<mpsc::Sender<MultiStreamProgress>>,
+}
+
+impl ReliableUdpSender {
+    pub fn new(conn: quinn::Connection) -> Self {
+        Self {
+            conn,
+            socket_count: DEFAULT_SOCKET_COUNT,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            max_rate_bps: None,
+            progress_tx: None,
+        }
+    }
+
+    /// 송신 소켓 수 설정
+    pub fn with_socket_count(mut self, count: usize) -> Self {
+        self.socket_count = count;
+        self
+    }
+
+    /// 데이터그램당 청크 크기 설정 (경로 MTU에 맞출 것)
+    pub fn with_chunk_size(mut self, size: usize) -> Self {
+        self.chunk_size = size.clamp(MIN_CHUNK_SIZE, MAX_CHUNK_DATA);
+        self
+    }
+
+    /// 최대 송신 속도 설정 (bytes/sec, None/0 = 손실 기반 자동 조절만)
+    pub fn with_max_rate(mut self, rate_bps: Option<u64>) -> Self {
+        self.max_rate_bps = rate_bps;
+        self
+    }
+
+    /// 진행률 채널 설정
+    pub fn with_progress_channel(mut self, tx: mpsc::Sender<MultiStreamProgress>) -> Self {
+        self.progress_tx = Some(tx);
+        self
+    }
+
+    /// 파일 전송 (완료 시 수신측이 디스크에 기록을 마친 뒤 반환)
+    pub async fn send_file(&self, file_path: PathBuf, job_id: &str) -> Result<u64> {
+        let peer_ip = peer_ipv4(&self.conn)?;
+        let file = Arc::new(HighPerformanceFileSender::open(
+            &file_path,
+            self.chunk_size,
+        )?);
+        let file_size = file.file_size();
+        let total_chunks = chunk_count(file_size, self.chunk_size)?;
+
+        let file_name = file_path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "unknown".to_string());
+
+        let core = UdpTransferCore::new(self.socket_count).await?;
+        let ack_port = core
+            .get_local_addrs()
+            .await
+            .first()
+            .map(|addr| addr.port())
+            .ok_or_else(|| anyhow::anyhow!("UDP 소켓 주소 없음"))?;
+
+        // 1. 제어 스트림으로 매니페스트 전송, 수신측 데이터 포트 수신
+        let (mut control_send, mut control_recv) = self.conn.open_bi().await?;
+        control_send.write_all(MANIFEST_MARKER).await?;
+        write_json(
+            &mut control_send,
+            &UdpManifest {
+                job_id: job_id.to_string(),
+                file_name: file_name.clone(),
+                file_size,
+                chunk_size: self.chunk_size as u32,
+                total_chunks,
+                ack_port,
+            },
+        )
+        .await?;
+        control_send.finish()?;
+
+        let manifest_ack: UdpManifestAck =
+            tokio::time::timeout(PEER_TIMEOUT, read_json(&mut control_recv)).await??;
+
+        let targets: Vec<SocketAddr> = manifest_ack
+            .data_ports
+            .iter()
+            .map(|port| SocketAddr::new(peer_ip, *port))
+            .collect();
+        if targets.is_empty() {
+            return Err(anyhow::anyhow!("수신측 UDP 포트 없음"));
+        }
+
+        info!(
+            "📤 UDP 전송 시작: {} ({} bytes, {} 청크 × {} bytes) -> {:?}",
+            file_name, file_size, total_chunks, self.chunk_size, targets
+        );
+
+        // 수신측이 모든 데이터를 기록하면 제어 스트림으로 완료 통지
+        let done = tauri::async_runtime::spawn(async move {
+            let mut marker = [0u8; 4];
+            control_recv.read_exact(&mut marker).await?;
+            if &marker != DONE_MARKER {
+                return Err(anyhow::anyhow!("잘못된 완료 신호: {:?}", marker));
+            }
+            Ok(())
+        });
+
+        // 2. 데이터 전송 + ACK/NACK 처리 루프
+        let wire_id = wire_job_id(job_id);
+        let mut acks = core.start_raw_receiver(0);
+        let workers = core.start_send_workers();
+        let mut window = SendWindow::new(total_chunks);
+        let mut rate = RateController::new(self.max_rate_bps);
+        core.set_pacing_rate(rate.rate());
+        let mut speed = SpeedCalculator::new(2);
+        let mut bytes_sent = 0u64;
+        let mut last_ack = Instant::now();
+        let mut last_progress = Instant::now();
+
+        while !window.is_complete() && !done.inner().is_finished() {
+            let now = Instant::now();
+
+            let mut resend = Vec::new();
+            loop {
+                match acks.try_recv() {
+                    Ok((packet, _)) => {
+                        if let Some(ack) =
+                            AckPacket::decode(&packet).filter(|ack| ack.job_id == wire_id)
+                        {
+                            last_ack = now;
+                            resend.extend(window.on_ack(&ack, now));
+                        }
+                    }
+                    Err(mpsc::error::TryRecvError::Empty) => break,
+                    Err(mpsc::error::TryRecvError::Disconnected) => {
+                        return Err(anyhow::anyhow!("ACK 수신 중단"));
+                    }
+                }
+            }
+            resend.extend(window.timed_out(now));
+            if !resend.is_empty() {
+                debug!("🔁 UDP 재전송: {} 청크", resend.len());
+                core.record_lost(resend.len() as u64);
+                rate.on_lost(resend.len() as u64);
+            }
+            if let Some(rate_bps) = rate.update(now) {
+                core.set_pacing_rate(rate_bps);
+            }
+
+            let mut seqs = resend;
+            seqs.extend(window.next_batch(SEND_BATCH, now));
+
+            if seqs.is_empty() {
+                // 윈도우가 가득 참: ACK 대기
+                if now.duration_since(last_ack) > PEER_TIMEOUT {
+                    return Err(anyhow::anyhow!("수신측 ACK 없음 ({:?})", PEER_TIMEOUT));
+                }
+                tokio::time::sleep(Duration::from_millis(1)).await;
+                continue;
+            }
+
+            for (seq, data) in read_chunks(file.clone(), seqs, self.chunk_size).await? {
+                let header = ChunkHeader {
+                    job_id: wire_id,
+                    file_index: 0,
+                    chunk_index: seq,
+                    offset: seq as u64 * self.chunk_size as u64,
+                    data_len: data.len() as u16,
+                    checksum: crc32fast::hash(&data),
+                };
+                let target = targets[seq as usize % targets.len()];
+                // 소켓별 워커가 병렬 전송, 실패한 청크는 in-flight로 남아 타임아웃 후 재전송됨
+                workers
+                    .send(seq as usize, target, header.to_packet(&data))
+                    .await?;
+                bytes_sent += data.len() as u64;
+                rate.on_sent(1);
+            }
+
+            if last_progress.elapsed() >= PROGRESS_INTERVAL {
+                last_progress = Instant::now();
+                let acked_bytes =
+                    (window.acked_chunks() as u64 * self.chunk_size as u64).min(file_size);
+                speed.update(acked_bytes);
+                self.report_progress(
+                    job_id,
+                    &window,
+                    bytes_sent,
+                    acked_bytes,
+                    file_size,
+                    speed.get_speed(),
+                )
+                .await;
+            }
+        }
+
+        // 3. 수신측 디스크 기록 완료 대기
+        tokio::time::timeout(PEER_TIMEOUT, done)
+            .await
+            .map_err(|_| anyhow::anyhow!("수신측 완료 신호 타임아웃"))??
+            .map_err(|e| anyhow::anyhow!("완료 신호 수신 실패: {}", e))?;
+
+        self.report_progress(
+            job_id,
+            &window,
+            bytes_sent,
+            file_size,
+            file_size,
+            speed.get_speed(),
+        )
+        .await;
+
+        let stats = core.get_stats().await;
+        info!(
+            "✅ UDP 전송 완료: {} bytes (전송 {} bytes, 재전송 {} 패킷, 최종 페이싱 {} B/s)",
+            file_size,
+            bytes_sent,
+            stats.packets_lost,
+            core.pacing_rate()
+        );
+
+        Ok(file_size)
+    }
+
+    async fn report_progress(
+        &self,
+        job_id: &str,
+        window: &SendWindow,
+        bytes_sent: u64,
+        acked_bytes: u64,
+        total_bytes: u64,
+        speed_bps: u64,
+    ) {
+        if let Some(tx) = &self.progress_tx {
+            let _ = tx
+                .send(MultiStreamProgress {
+                    job_id: job_id.to_string(),
+                    blocks_completed: window.acked_chunks(),
+                    total_blocks: window.total,
+                    bytes_transferred: bytes_sent,
+                    acknowledged_bytes: acked_bytes,
+                    total_bytes,
+                    active_streams: self.socket_count as u32,
+                    speed_bps,
+                })
+                .await;
+        }
+    }
+}
+
+/// 신뢰성 UDP 파일 수신기 (Receiver)
+pub struct ReliableUdpReceiver {
+    conn: quinn::Connection,
+    save_dir: PathBuf,
+    socket_count: usize,
+    overwrite_policy: OverwritePolicy,
+    progress_tx: Option<mpsc::Sender<MultiStreamProgress>>,
+}
+
+impl ReliableUdpReceiver {
+    pub fn new(conn: quinn::Connection, save_dir: PathBuf) -> Self {
+        Self {
+            conn,
+            save_dir,
+            socket_count: DEFAULT_SOCKET_COUNT,
+            overwrite_policy: OverwritePolicy::default(),
+            progress_tx: None,
+        }
+    }
+
+    /// 수신 소켓(샤드) 수 설정
+    pub fn with_socket_count(mut self, count: usize) -> Self {
+        self.socket_count = count;
+        self
+    }
+
+    /// 🆕 같은 이름의 파일이 있을 때의 처리 (`Skip`이면 수신하지 않고 에러)
+    pub fn with_overwrite_policy(mut self, policy: OverwritePolicy) -> Self {
+        self.overwrite_policy = policy;
+        self
+    }
+
+    /// 진행률 채널 설정
+    pub fn with_progress_channel(mut self, tx: mpsc::Sender<MultiStreamProgress>) -> Self {
+        self.progress_tx = Some(tx);
+        self
+    }
+
+    /// 파일 수신 (UDP)
+    pub async fn receive_file(&self, job_id: &str) -> Result<PathBuf> {
+        info!("📥 UDP 수신 대기 중...");
+        let peer_ip = peer_ipv4(&self.conn)?;
+
+        // 1. 매니페스트 수신
+        let (mut control_send, mut control_recv) = loop {
+            let (send, mut recv) = self.conn.accept_bi().await?;
+            let mut marker = [0u8; 4];
+            recv.read_exact(&mut marker).await?;
+            if &marker == MANIFEST_MARKER {
+                break (send, recv);
+            }
+            warn!("알 수 없는 스트림 타입: {:?}", marker);
+        };
+        let manifest: UdpManifest = read_json(&mut control_recv).await?;
+
+        if manifest.job_id != job_id {
+            return Err(anyhow::anyhow!("Job ID mismatch"));
+        }
+        let chunk_size = manifest.chunk_size as usize;
+        if chunk_size == 0
+            || chunk_size > MAX_CHUNK_DATA
+            || chunk_count(manifest.file_size, chunk_size)? != manifest.total_chunks
+        {
+            return Err(anyhow::anyhow!("잘못된 UDP 매니페스트: {:?}", manifest));
+        }
+
+        // 경로 조작 방지 + 기존 파일 처리 정책
+        let destination = safe_destination(&self.save_dir, &manifest.file_name)?;
+        let (save_path, action) = resolve_destination(&destination, self.overwrite_policy)?;
+        if action == ReceiveAction::Skipped {
+            return Err(anyhow::anyhow!(
+                "같은 이름의 파일이 이미 있습니다: {}",
+                save_path.display()
+            ));
+        }
+        if let Some(parent) = save_path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        // 모든 청크를 받은 뒤에만 최종 이름으로 변경
+        let part_path = part_file::part_path(&save_path);
+        let mut writer = HighPerformanceFileReceiver::create(&part_path, manifest.file_size)?;
+
+        // 2. 데이터 소켓 준비 후 포트 통지
+        // 한 포트에 SO_REUSEPORT 샤드: 커널이 송신 소켓별로 수신 워커에 분배
+        let core = UdpTransferCore::new_sharded(0, Some(self.socket_count)).await?;
+        let mut packets = core.start_receivers();
+        let mut data_ports: Vec<u16> = core
+            .get_local_addrs()
+            .await
+            .iter()
+            .map(|addr| addr.port())
+            .collect();
+        data_ports.dedup();
+        write_json(&mut control_send, &UdpManifestAck { data_ports }).await?;
+
+        let ack_target = SocketAddr::new(peer_ip, manifest.ack_port);
+        let wire_id = wire_job_id(job_id);
+
+        info!(
+            "📥 UDP 수신 시작: {} ({} bytes, {} 청크)",
+            manifest.file_name, manifest.file_size, manifest.total_chunks
+        );
+
+        // 디스크 쓰기는 블로킹 스레드에서 순서대로 처리
+        let (write_tx, mut write_rx) = mpsc::channel::<(u64, Bytes)>(WRITE_QUEUE);
+        let writer_task = tokio::task::spawn_blocking(move || -> Result<()> {
+            while let Some((offset, data)) = write_rx.blocking_recv() {
+                writer.write_block_at(offset, &data)?;
+            }
+            writer.sync()
+        });
+
+        // 3. 수신 + 주기적 ACK 루프
+        let mut window = ReceiveWindow::new(manifest.total_chunks);
+        let mut ack_timer = tokio::time::interval(ACK_INTERVAL);
+        let mut speed = SpeedCalculator::new(2);
+        let mut bytes_received = 0u64;
+        let mut last_packet = Instant::now();
+        let mut last_progress = Instant::now();
+        let mut ack_pending = false;
+
+        while !window.is_complete() {
+            tokio::select! {
+                packet = packets.recv() => {
+                    let Some((header, data, _)) = packet else {
+                        return Err(anyhow::anyhow!("UDP 수신 중단"));
+                    };
+                    if header.job_id != wire_id {
+                        continue;
+                    }
+                    last_packet = Instant::now();
+                    ack_pending = true;
+
+                    let expected_offset = header.chunk_index as u64 * chunk_size as u64;
+                    let expected_len = (chunk_size as u64)
+                        .min(manifest.file_size.saturating_sub(expected_offset))
+                        as usize;
+                    if header.chunk_index >= manifest.total_chunks
+                        || header.offset != expected_offset
+                        || data.len() != expected_len
+                        || header.data_len as usize != expected_len
+                        || crc32fast::hash(&data) != header.checksum
+                    {
+                        // 손상된 청크는 버리고 NACK으로 재전송 요청
+                        core.record_lost(1);
+                        continue;
+                    }
+
+                    if window.insert(header.chunk_index) {
+                        bytes_received += data.len() as u64;
+                        write_tx
+                            .send((header.offset, data))
+                            .await
+                            .map_err(|_| anyhow::anyhow!("파일 쓰기 중단"))?;
+                    }
+
+                    if last_progress.elapsed() >= PROGRESS_INTERVAL {
+                        last_progress = Instant::now();
+                        speed.update(bytes_received);
+                        self.report_progress(job_id, &window, bytes_received, manifest.file_size, speed.get_speed())
+                            .await;
+                    }
+                }
+                _ = ack_timer.tick() => {
+                    if ack_pending {
+                        ack_pending = false;
+                        let ack = window.ack(wire_id).encode();
+                        if let Err(e) = core.send_raw(0, &ack, ack_target).await {
+                            debug!("ACK 전송 실패: {}", e);
+                        }
+                    }
+                    if last_packet.elapsed() > PEER_TIMEOUT {
+                        return Err(anyhow::anyhow!("송신측 데이터 없음 ({:?})", PEER_TIMEOUT));
+                    }
+                }
+            }
+        }
+
+        // 4. 디스크 기록 완료 후 완료 통지 (송신측은 마지막 ACK 유실과 무관하게 종료)
+        drop(write_tx);
+        writer_task.await??;
+        part_file::commit(&part_path, &save_path)?;
+
+        let ack = window.ack(wire_id).encode();
+        let _ = core.send_raw(0, &ack, ack_target).await;
+        control_send.write_all(DONE_MARKER).await?;
+        control_send.finish()?;
+
+        self.report_progress(
+            job_id,
+            &window,
+            bytes_received,
+            manifest.file_size,
+            speed.get_speed(),
+        )
+        .await;
+
+        let stats = core.get_stats().await;
+        info!(
+            "✅ UDP 수신 완료: {:?} ({} 패킷, 손상 {} 패킷)",
+            save_path, stats.packets_received, stats.packets_lost
+        );
+
+        Ok(save_path)
+    }
+
+    async fn report_progress(
+        &self,
+        job_id: &str,
+        window: &ReceiveWindow,
+        bytes_received: u64,
+        total_bytes: u64,
+        speed_bps: u64,
+    ) {
+        if let Some(tx) = &self.progress_tx {
+            let _ = tx
+                .send(MultiStreamProgress {
+                    job_id: job_id.to_string(),
+                    blocks_completed: window.received,
+                    total_blocks: window.total,
+                    bytes_transferred: bytes_received,
+                    acknowledged_bytes: bytes_received, // Receiver는 항상 일치
+                    total_bytes,
+                    active_streams: 0,
+                    speed_bps,
+                })
+                .await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ack_packet_roundtrip() {
+        let ack = AckPacket {
+            job_id: wire_job_id("job-1"),
+            cumulative: 10,
+            highest: 20,
+            nacks: vec![11, 15, 19],
+        };
+
+        let encoded = ack.encode();
+        assert_eq!(encoded.len(), ACK_HEADER_SIZE + 3 * 4);
+        assert_eq!(AckPacket::decode(&encoded), Some(ack));
+
+        // 잘린 패킷은 거부
+        assert_eq!(AckPacket::decode(&encoded[..encoded.len() - 1]), None);
+    }
+
+    #[test]
+    fn test_receive_window_tracks_gaps() {
+        let mut window = ReceiveWindow::new(100);
+
+        assert!(window.insert(0));
+        assert!(window.insert(1));
+        assert!(window.insert(4));
+        assert!(!window.insert(4)); // 중복
+        assert!(!window.insert(100)); // 범위 밖
+
+        let ack = window.ack(7);
+        assert_eq!(ack.cumulative, 2);
+        assert_eq!(ack.highest, 5);
+        assert_eq!(ack.nacks, vec![2, 3]);
+
+        for seq in 2..100 {
+            window.insert(seq);
+        }
+        assert!(window.is_complete());
+        assert_eq!(window.received, 100);
+    }
+
+    #[test]
+    fn test_send_window_selective_ack_and_retransmit() {
+        let start = Instant::now();
+        let mut window = SendWindow::new(10);
+
+        assert_eq!(window.next_batch(8, start), (0..8).collect::<Vec<_>>());
+
+        // 0..5 수신, 2는 누락 → 2만 재전송 대상
+        let later = start + RETRANSMIT_MIN_INTERVAL;
+        let ack = AckPacket {
+            job_id: 0,
+            cumulative: 2,
+            highest: 5,
+            nacks: vec![2],
+        };
+        assert_eq!(window.on_ack(&ack, later), vec![2]);
+        assert_eq!(window.base, 2);
+        assert_eq!(window.acked_chunks(), 4);
+
+        // 5..8은 ACK 없이 타임아웃되면 재전송
+        let timeout = start + RETRANSMIT_TIMEOUT;
+        assert_eq!(window.timed_out(timeout), vec![5, 6, 7]);
+
+        assert_eq!(window.next_batch(8, timeout), vec![8, 9]);
+        let ack = AckPacket {
+            job_id: 0,
+            cumulative: 10,
+            highest: 10,
+            nacks: vec![],
+        };
+        window.on_ack(&ack, timeout);
+        assert!(window.is_complete());
+    }
+}
@@ -0,0 +1,302 @@
+//! 희소(sparse) 파일 처리
+//!
+//! VM 디스크 이미지처럼 대부분이 구멍(hole)인 파일은 송신 측에서 구멍 구간을 찾아
+//! 매니페스트로 알리고, 구멍에 완전히 들어가는 블록은 보내지 않습니다.
+//! 수신 측은 파일을 희소 파일로 만들고 구멍을 다시 뚫어 원본과 같은 디스크 사용량을 유지합니다.
+
+use super::zero_copy_io::split_file_into_blocks;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+/// 구멍이 너무 잘게 쪼개진 파일은 매니페스트가 커지므로 일반 파일로 취급
+pub const MAX_HOLES: usize = 16_384;
+
+/// 파일 안의 구멍 구간 (읽으면 0)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HoleRange {
+    pub offset: u64,
+    pub len: u64,
+}
+
+impl HoleRange {
+    pub fn end(&self) -> u64 {
+        self.offset + self.len
+    }
+}
+
+/// 파일의 구멍 구간 (지원하지 않는 파일 시스템이거나 구멍이 없으면 빈 목록)
+pub fn detect_holes(path: &Path, file_size: u64) -> io::Result<Vec<HoleRange>> {
+    if file_size == 0 {
+        return Ok(Vec::new());
+    }
+    let file = File::open(path)?;
+    let holes = query_holes(&file, file_size)?;
+    if holes.len() > MAX_HOLES {
+        return Ok(Vec::new());
+    }
+    Ok(holes)
+}
+
+/// `offset..offset+size` 구간이 구멍 안에 완전히 들어가는지
+pub fn is_hole(holes: &[HoleRange], offset: u64, size: u64) -> bool {
+    holes
+        .iter()
+        .any(|hole| hole.offset <= offset && offset + size <= hole.end())
+}
+
+/// 구멍에 완전히 들어가 전송하지 않는 블록 번호
+pub fn hole_blocks(holes: &[HoleRange], file_size: u64, block_size: usize) -> Vec<u32> {
+    if holes.is_empty() {
+        return Vec::new();
+    }
+    split_file_into_blocks(file_size, block_size)
+        .into_iter()
+        .filter(|block| is_hole(holes, block.offset, block.size as u64))
+        .map(|block| block.index)
+        .collect()
+}
+
+/// 쓰기 전에 파일을 희소 파일로 표시 (Windows NTFS, Unix는 기본 동작)
+pub fn mark_sparse(file: &File) -> io::Result<()> {
+    #[cfg(target_os = "windows")]
+    {
+        use windows_sys::Win32::System::Ioctl::FSCTL_SET_SPARSE;
+        windows::device_io_control(file, FSCTL_SET_SPARSE, &[], &mut [])?;
+    }
+    #[cfg(not(target_os = "windows"))]
+    let _ = file;
+    Ok(())
+}
+
+/// 받은 파일에 구멍 다시 뚫기 (지원하지 않는 플랫폼에서는 0으로 채워진 채 유지)
+pub fn punch_holes(path: &Path, holes: &[HoleRange]) -> io::Result<()> {
+    if holes.is_empty() {
+        return Ok(());
+    }
+    let file = std::fs::OpenOptions::new().write(true).open(path)?;
+    for hole in holes {
+        punch_hole(&file, hole)?;
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn punch_hole(file: &File, hole: &HoleRange) -> io::Result<()> {
+    use std::os::unix::io::AsRawFd;
+    // SAFETY: 열린 파일 디스크립터에 대한 fallocate 호출
+    let ret = unsafe {
+        libc::fallocate(
+            file.as_raw_fd(),
+            libc::FALLOC_FL_PUNCH_HOLE | libc::FALLOC_FL_KEEP_SIZE,
+            hole.offset as libc::off_t,
+            hole.len as libc::off_t,
+        )
+    };
+    if ret != 0 {
+        let err = io::Error::last_os_error();
+        // 구멍을 지원하지 않는 파일 시스템: 0으로 채워진 파일도 내용은 같음
+        if err.raw_os_error() == Some(libc::EOPNOTSUPP) {
+            return Ok(());
+        }
+        return Err(err);
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+fn punch_hole(file: &File, hole: &HoleRange) -> io::Result<()> {
+    use windows_sys::Win32::System::Ioctl::{FILE_ZERO_DATA_INFORMATION, FSCTL_SET_ZERO_DATA};
+    let info = FILE_ZERO_DATA_INFORMATION {
+        FileOffset: hole.offset as i64,
+        BeyondFinalZero: hole.end() as i64,
+    };
+    windows::device_io_control(file, FSCTL_SET_ZERO_DATA, windows::as_bytes(&info), &mut [])?;
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+fn punch_hole(_file: &File, _hole: &HoleRange) -> io::Result<()> {
+    // macOS(APFS)는 쓰지 않은 구간이 자동으로 구멍으로 남음
+    Ok(())
+}
+
+#[cfg(unix)]
+fn query_holes(file: &File, file_size: u64) -> io::Result<Vec<HoleRange>> {
+    use std::os::unix::io::AsRawFd;
+    let fd = file.as_raw_fd();
+    let size = file_size as libc::off_t;
+    let mut holes = Vec::new();
+    let mut offset: libc::off_t = 0;
+
+    while offset < size {
+        // SAFETY: 열린 파일 디스크립터에 대한 lseek 호출
+        let hole = unsafe { libc::lseek(fd, offset, libc::SEEK_HOLE) };
+        if hole < 0 {
+            let err = io::Error::last_os_error();
+            // SEEK_HOLE 미지원: 구멍 없는 파일로 취급
+            if err.raw_os_error() == Some(libc::EINVAL) {
+                return Ok(Vec::new());
+            }
+            return Err(err);
+        }
+        if hole >= size {
+            break;
+        }
+
+        // SAFETY: 위와 같음
+        let data = unsafe { libc::lseek(fd, hole, libc::SEEK_DATA) };
+        let end = if data < 0 {
+            let err = io::Error::last_os_error();
+            // 파일 끝까지 구멍
+            if err.raw_os_error() != Some(libc::ENXIO) {
+                return Err(err);
+            }
+            size
+        } else {
+            data.min(size)
+        };
+
+        holes.push(HoleRange {
+            offset: hole as u64,
+            len: (end - hole) as u64,
+        });
+        if holes.len() > MAX_HOLES {
+            break;
+        }
+        offset = end;
+    }
+    Ok(holes)
+}
+
+#[cfg(target_os = "windows")]
+fn query_holes(file: &File, file_size: u64) -> io::Result<Vec<HoleRange>> {
+    use windows_sys::Win32::System::Ioctl::{
+        FILE_ALLOCATED_RANGE_BUFFER, FSCTL_QUERY_ALLOCATED_RANGES,
+    };
+
+    // 할당된 구간 사이의 빈 곳이 구멍
+    let mut holes = Vec::new();
+    let mut cursor = 0u64;
+    let mut ranges = vec![
+        FILE_ALLOCATED_RANGE_BUFFER {
+            FileOffset: 0,
+            Length: 0,
+        };
+        256
+    ];
+    loop {
+        let query = FILE_ALLOCATED_RANGE_BUFFER {
+            FileOffset: cursor as i64,
+            Length: (file_size - cursor) as i64,
+        };
+        let returned = windows::device_io_control(
+            file,
+            FSCTL_QUERY_ALLOCATED_RANGES,
+            windows::as_bytes(&query),
+            windows::as_bytes_mut(&mut ranges),
+        )?;
+
+        let count = returned as usize / std::mem::size_of::<FILE_ALLOCATED_RANGE_BUFFER>();
+        for range in &ranges[..count] {
+            let start = range.FileOffset as u64;
+            if start > cursor {
+                holes.push(HoleRange {
+                    offset: cursor,
+                    len: start - cursor,
+                });
+            }
+            cursor = start + range.Length as u64;
+        }
+        // 버퍼가 가득 찼으면(ERROR_MORE_DATA) 이어서 조회
+        if count < ranges.len() || holes.len() > MAX_HOLES {
+            break;
+        }
+    }
+    if cursor < file_size && holes.len() <= MAX_HOLES {
+        holes.push(HoleRange {
+            offset: cursor,
+            len: file_size - cursor,
+        });
+    }
+    Ok(holes)
+}
+
+#[cfg(target_os = "windows")]
+mod windows {
+    use std::fs::File;
+    use std::io;
+    use std::os::windows::io::AsRawHandle;
+    use windows_sys::Win32::Foundation::{GetLastError, ERROR_MORE_DATA, HANDLE};
+    use windows_sys::Win32::System::IO::DeviceIoControl;
+
+    /// DeviceIoControl 동기 호출 (출력 버퍼에 채워진 바이트 수)
+    ///
+    /// ERROR_MORE_DATA는 버퍼가 가득 찬 것이므로 성공으로 취급합니다.
+    pub fn device_io_control(
+        file: &File,
+        code: u32,
+        input: &[u8],
+        output: &mut [u8],
+    ) -> io::Result<u32> {
+        let mut returned = 0u32;
+        // SAFETY: 버퍼 포인터와 길이는 슬라이스에서 얻음, 동기 핸들이므로 OVERLAPPED 없음
+        let ok = unsafe {
+            DeviceIoControl(
+                file.as_raw_handle() as HANDLE,
+                code,
+                input.as_ptr().cast(),
+                input.len() as u32,
+                output.as_mut_ptr().cast(),
+                output.len() as u32,
+                &mut returned,
+                std::ptr::null_mut(),
+            )
+        };
+        if ok == 0 && unsafe { GetLastError() } != ERROR_MORE_DATA {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(returned)
+    }
+
+    pub fn as_bytes<T>(value: &T) -> &[u8] {
+        // SAFETY: POD 구조체를 바이트로 보기만 함
+        unsafe { std::slice::from_raw_parts((value as *const T).cast(), std::mem::size_of::<T>()) }
+    }
+
+    pub fn as_bytes_mut<T>(values: &mut [T]) -> &mut [u8] {
+        // SAFETY: POD 구조체 배열을 출력 버퍼로 사용
+        unsafe {
+            std::slice::from_raw_parts_mut(
+                values.as_mut_ptr().cast(),
+                std::mem::size_of_val(values),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hole_blocks() {
+        let holes = [
+            HoleRange {
+                offset: 0,
+                len: 4096,
+            },
+            HoleRange {
+                offset: 8192,
+                len: 6000,
+            },
+        ];
+        assert!(is_hole(&holes, 0, 4096));
+        assert!(!is_hole(&holes, 2048, 4096));
+
+        // 블록 2개(0, 2)만 구멍에 완전히 들어가고, 마지막 블록은 구멍 밖에 걸침
+        assert_eq!(hole_blocks(&holes, 16384, 4096), vec![0, 2]);
+        assert!(hole_blocks(&[], 16384, 4096).is_empty());
+    }
+}
@@ -0,0 +1,272 @@
+//! 전송 저장 대상
+//!
+//! 받은 파일이나 보낼 파일을 로컬 폴더 대신 오브젝트 스토리지(S3 호환 버킷)에 둘 수 있도록
+//! 저장 대상을 추상화합니다. [`StorageTarget::create`]로 연 [`ObjectWriter`]에 순서대로 쓰고
+//! `finish`/`abort`로 끝내며, [`store_file`]은 이를 레지스트리 작업으로 감싸 다른 전송과 같은
+//! 진행률/일시정지/취소 동작을 제공합니다.
+//!
+//! 피어에서 받은 파일은 수신 엔진이 경로에 직접 쓰므로, 버킷으로 보낼 때는 임시 폴더로 받은 뒤
+//! 업로드하고 임시 파일을 지웁니다 (`lib.rs`의 `receive_file_to_storage`).
+
+use super::file_transfer::{resolve_destination, safe_destination, OverwritePolicy, ReceiveAction};
+use super::part_file;
+use super::registry::{TransferKind, TransferRegistry};
+#[cfg(feature = "storage-s3")]
+use super::s3::{MultipartUpload, S3Client, S3Config};
+use crate::error::AppError;
+use anyhow::{bail, Result};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tracing::info;
+
+/// 오브젝트 스토리지 파트 크기 (S3 최소 5 MiB)
+pub const PART_SIZE: usize = 8 * 1024 * 1024;
+
+/// 저장 대상
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum StorageTarget {
+    /// 로컬 폴더 (일반 수신과 같은 `.pswp-part` 임시 파일과 덮어쓰기 정책)
+    #[serde(rename_all = "camelCase")]
+    Local {
+        dir: PathBuf,
+        #[serde(default)]
+        overwrite_policy: OverwritePolicy,
+    },
+    /// S3 호환 버킷 (AWS S3, MinIO 등)
+    #[cfg(feature = "storage-s3")]
+    S3(S3Config),
+}
+
+impl StorageTarget {
+    /// 로그/작업 표시용 이름
+    pub fn label(&self) -> String {
+        match self {
+            StorageTarget::Local { dir, .. } => dir.display().to_string(),
+            #[cfg(feature = "storage-s3")]
+            StorageTarget::S3(config) => format!("s3://{}", config.bucket),
+        }
+    }
+
+    /// `name` (상대 경로 허용)으로 쓸 객체 열기
+    pub async fn create(&self, name: &str) -> Result<ObjectWriter> {
+        match self {
+            StorageTarget::Local {
+                dir,
+                overwrite_policy,
+            } => {
+                let destination = safe_destination(dir, name)?;
+                let (destination, action) = resolve_destination(&destination, *overwrite_policy)?;
+                if action == ReceiveAction::Skipped {
+                    bail!(
+                        "같은 이름의 파일이 이미 있습니다: {}",
+                        destination.display()
+                    );
+                }
+                let part = part_file::part_path(&destination);
+                if let Some(parent) = part.parent() {
+                    tokio::fs::create_dir_all(parent).await?;
+                }
+                let file = tokio::fs::File::create(&part).await?;
+                Ok(ObjectWriter::Local {
+                    file,
+                    part,
+                    destination,
+                })
+            }
+            #[cfg(feature = "storage-s3")]
+            StorageTarget::S3(config) => {
+                // 키도 로컬과 같은 경로 검사 (`..` 등으로 prefix를 벗어나지 않도록)
+                let key = safe_destination(Path::new(""), name)?
+                    .to_string_lossy()
+                    .replace('\\', "/");
+                let client = S3Client::new(config.clone())?;
+                let upload = client.create_multipart(&client.object_key(&key)).await?;
+                Ok(ObjectWriter::S3 {
+                    upload,
+                    buffer: Vec::with_capacity(PART_SIZE),
+                })
+            }
+        }
+    }
+}
+
+/// 저장 대상에 순서대로 쓰는 객체
+pub enum ObjectWriter {
+    Local {
+        file: tokio::fs::File,
+        part: PathBuf,
+        destination: PathBuf,
+    },
+    #[cfg(feature = "storage-s3")]
+    S3 {
+        upload: MultipartUpload,
+        /// 다음 파트로 보낼 데이터
+        buffer: Vec<u8>,
+    },
+}
+
+impl ObjectWriter {
+    pub async fn write(&mut self, data: &[u8]) -> Result<()> {
+        match self {
+            ObjectWriter::Local { file, .. } => file.write_all(data).await?,
+            #[cfg(feature = "storage-s3")]
+            ObjectWriter::S3 { upload, buffer } => {
+                buffer.extend_from_slice(data);
+                while buffer.len() >= PART_SIZE {
+                    let rest = buffer.split_off(PART_SIZE);
+                    let part = std::mem::replace(buffer, rest);
+                    upload.upload_part(part).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 쓰기 완료 (저장 위치 반환: 로컬 경로 또는 `s3://<bucket>/<key>`)
+    pub async fn finish(self) -> Result<String> {
+        match self {
+            ObjectWriter::Local {
+                mut file,
+                part,
+                destination,
+            } => {
+                file.flush().await?;
+                drop(file);
+                part_file::commit(&part, &destination)?;
+                Ok(destination.to_string_lossy().to_string())
+            }
+            #[cfg(feature = "storage-s3")]
+            ObjectWriter::S3 { mut upload, buffer } => {
+                // 빈 파일도 파트 하나는 있어야 완료할 수 있음
+                if !buffer.is_empty() || upload.part_count() == 0 {
+                    if let Err(e) = upload.upload_part(buffer).await {
+                        upload.abort().await;
+                        return Err(e);
+                    }
+                }
+                upload.complete().await
+            }
+        }
+    }
+
+    /// 쓰기 중단 (임시 파일/올린 파트 삭제)
+    pub async fn abort(self) {
+        match self {
+            ObjectWriter::Local { file, part, .. } => {
+                drop(file);
+                part_file::discard(&part);
+            }
+            #[cfg(feature = "storage-s3")]
+            ObjectWriter::S3 { upload, .. } => upload.abort().await,
+        }
+    }
+}
+
+/// 로컬 파일을 저장 대상에 저장 (레지스트리 작업으로 진행률/일시정지/취소 반영)
+pub async fn store_file(
+    target: &StorageTarget,
+    path: &Path,
+    name: &str,
+    registry: &TransferRegistry,
+    job_id: &str,
+) -> Result<String, AppError> {
+    let control = registry.register(job_id, &target.label(), TransferKind::File)?;
+    let result = async {
+        let mut file = tokio::fs::File::open(path).await?;
+        let total = file.metadata().await?.len();
+        registry.update_progress(job_id, 0, total, 0);
+
+        let mut writer = target.create(name).await?;
+        let copied = async {
+            let mut buf = vec![0u8; PART_SIZE];
+            let mut written = 0u64;
+            let started = Instant::now();
+            loop {
+                control.checkpoint().await?;
+                let n = file.read(&mut buf).await?;
+                if n == 0 {
+                    break;
+                }
+                writer.write(&buf[..n]).await?;
+                written += n as u64;
+
+                let elapsed = started.elapsed().as_secs_f64().max(f64::EPSILON);
+                registry.update_progress(job_id, written, total, (written as f64 / elapsed) as u64);
+            }
+            anyhow::Ok(())
+        }
+        .await;
+
+        match copied {
+            Ok(()) => writer.finish().await,
+            Err(e) => {
+                writer.abort().await;
+                Err(e)
+            }
+        }
+    }
+    .await
+    .map_err(|e| AppError::Io(format!("{} 저장 실패: {}", target.label(), e)));
+
+    registry.finish(job_id, result.as_ref().map(|_| ()).map_err(|e| e.clone()));
+    if let Ok(location) = &result {
+        info!("🗄️ 저장 완료: {} -> {}", path.display(), location);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_store_file_to_local_target() {
+        let dir = std::env::temp_dir().join(format!("pswp-storage-{}", uuid::Uuid::new_v4()));
+        let source = dir.join("source.bin");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(&source, vec![7u8; 1000]).unwrap();
+
+        let target: StorageTarget = serde_json::from_str(&format!(
+            r#"{{"type":"local","dir":{:?},"overwritePolicy":"fail"}}"#,
+            dir.join("out")
+        ))
+        .unwrap();
+        let registry = TransferRegistry::new();
+        let location = store_file(&target, &source, "nested/copy.bin", &registry, "job")
+            .await
+            .unwrap();
+        assert_eq!(PathBuf::from(&location), dir.join("out/nested/copy.bin"));
+        assert_eq!(std::fs::read(&location).unwrap(), vec![7u8; 1000]);
+        let snapshot = registry.snapshot("job").unwrap();
+        assert_eq!(snapshot.bytes_transferred, 1000);
+
+        // Fail 정책: 같은 이름이 있으면 실패하고 임시 파일을 남기지 않음
+        assert!(
+            store_file(&target, &source, "nested/copy.bin", &registry, "job2")
+                .await
+                .is_err()
+        );
+        assert!(!part_file::part_path(&dir.join("out/nested/copy.bin")).exists());
+        // 경로 밖으로 나가는 이름 거부
+        assert!(
+            store_file(&target, &source, "../escape.bin", &registry, "job3")
+                .await
+                .is_err()
+        );
+
+        #[cfg(feature = "storage-s3")]
+        {
+            let s3: StorageTarget = serde_json::from_str(
+                r#"{"type":"s3","endpoint":"http://localhost:9000","bucket":"b",
+                    "accessKeyId":"k","secretAccessKey":"s"}"#,
+            )
+            .unwrap();
+            assert_eq!(s3.label(), "s3://b");
+        }
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
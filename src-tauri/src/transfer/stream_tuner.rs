@@ -0,0 +1,185 @@
+//! 멀티스트림 동시 스트림 수 자동 조절
+//!
+//! 항상 32개 스트림을 여는 대신 적은 수로 시작해 주기마다 관측한 goodput(ACK된 바이트)과
+//! QUIC 손실률을 보고 늘리거나 줄입니다.
+//! - 손실이 임계값을 넘으면 1/4 감소 (저사양 수신 측 보호)
+//! - 늘린 뒤 goodput이 오르면 계속 증가 (처음엔 2배씩, 이후 1/8씩)
+//! - 늘렸는데 효과가 없으면 직전 값으로 되돌리고, 한동안 유지한 뒤 다시 탐색
+//!
+//! 수신 측 디스크가 따라오지 못하면(`FlowWindow`) 위 결과와 별개로 상한을 낮춥니다.
+
+/// 시작 스트림 수
+pub const INITIAL_STREAMS: usize = 4;
+
+/// 하한 스트림 수
+pub const MIN_STREAMS: usize = 2;
+
+/// 이 비율을 넘는 패킷 손실이 있으면 감소
+const LOSS_THRESHOLD: f64 = 0.02;
+
+/// 이만큼(비율) 이상 goodput이 올라야 증가 효과가 있었다고 판단
+const GAIN_THRESHOLD: f64 = 0.05;
+
+/// 유지 상태에서 다시 증가를 시도하기까지의 주기 수
+const PROBE_ROUNDS: u32 = 10;
+
+pub struct StreamTuner {
+    current: usize,
+    max: usize,
+    /// 처음 손실/정체를 만나기 전까지 2배씩 증가
+    slow_start: bool,
+    last_goodput: Option<f64>,
+    /// 직전 주기에 바꾼 스트림 수 (양수 = 증가)
+    last_change: isize,
+    stable_rounds: u32,
+}
+
+impl StreamTuner {
+    /// `max`: 사용자/QUIC 한도에 따른 상한
+    pub fn new(max: usize) -> Self {
+        let max = max.max(MIN_STREAMS);
+        Self {
+            current: INITIAL_STREAMS.clamp(MIN_STREAMS, max),
+            max,
+            slow_start: true,
+            last_goodput: None,
+            last_change: 0,
+            stable_rounds: 0,
+        }
+    }
+
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    /// 한 주기의 관측값으로 다음 스트림 수 결정
+    ///
+    /// `goodput_bps`: 주기 동안 ACK된 바이트/초, `loss_rate`: 주기 동안 손실 패킷 비율
+    pub fn update(&mut self, goodput_bps: f64, loss_rate: f64) -> usize {
+        let next = if loss_rate > LOSS_THRESHOLD {
+            self.slow_start = false;
+            self.current - self.current / 4
+        } else {
+            match self.last_goodput {
+                None => self.grow(),
+                Some(prev) if goodput_bps > prev * (1.0 + GAIN_THRESHOLD) => self.grow(),
+                Some(_) if self.last_change > 0 => {
+                    // 늘렸는데 효과 없음 → 되돌리고 유지
+                    self.slow_start = false;
+                    self.current - self.last_change as usize
+                }
+                Some(_) => {
+                    self.stable_rounds += 1;
+                    if self.stable_rounds >= PROBE_ROUNDS {
+                        self.grow()
+                    } else {
+                        self.current
+                    }
+                }
+            }
+        }
+        .clamp(MIN_STREAMS, self.max);
+
+        if next != self.current {
+            self.stable_rounds = 0;
+        }
+        self.last_change = next as isize - self.current as isize;
+        self.last_goodput = Some(goodput_bps);
+        self.current = next;
+        next
+    }
+
+    fn grow(&self) -> usize {
+        if self.slow_start {
+            self.current * 2
+        } else {
+            self.current + (self.current / 8).max(1)
+        }
+    }
+}
+
+/// 수신 측 흐름 제어 창
+///
+/// 수신 측이 쓰기 대기열이 밀린다고 알리면 스트림 상한을 1/4 줄이고,
+/// 알림이 없는 주기마다 하나씩 되돌립니다. 느린 HDD/NAS로 보낼 때 QUIC 버퍼가
+/// 부풀었다 비기를 반복하지 않고 디스크 속도에 맞춰 수렴하도록 합니다.
+pub struct FlowWindow {
+    cap: usize,
+    max: usize,
+}
+
+impl FlowWindow {
+    pub fn new(max: usize) -> Self {
+        let max = max.max(MIN_STREAMS);
+        Self { cap: max, max }
+    }
+
+    pub fn cap(&self) -> usize {
+        self.cap
+    }
+
+    /// 한 주기 동안 수신 측 혼잡 알림이 있었는지로 다음 상한 결정
+    pub fn update(&mut self, congested: bool) -> usize {
+        self.cap = if congested {
+            (self.cap - (self.cap / 4).max(1)).max(MIN_STREAMS)
+        } else {
+            (self.cap + 1).min(self.max)
+        };
+        self.cap
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tuner_ramps_up_then_settles_and_backs_off() {
+        let mut tuner = StreamTuner::new(64);
+        assert_eq!(tuner.current(), INITIAL_STREAMS);
+
+        // goodput이 스트림 수에 비례해 오르다가 16개에서 포화되는 링크
+        let link = |streams: usize| (streams.min(16) * 100) as f64;
+
+        for _ in 0..3 {
+            let current = tuner.current();
+            tuner.update(link(current), 0.0);
+        }
+        assert_eq!(tuner.current(), 32);
+
+        // 32개는 16개보다 나을 게 없으므로 되돌림
+        tuner.update(link(32), 0.0);
+        assert_eq!(tuner.current(), 16);
+
+        // 유지
+        tuner.update(link(16), 0.0);
+        assert_eq!(tuner.current(), 16);
+
+        // 손실이 나면 감소
+        tuner.update(link(16), 0.1);
+        assert_eq!(tuner.current(), 12);
+
+        // 상한/하한 유지
+        let mut small = StreamTuner::new(1);
+        assert_eq!(small.update(1.0, 0.0), MIN_STREAMS);
+    }
+
+    #[test]
+    fn test_flow_window_backs_off_and_recovers() {
+        let mut window = FlowWindow::new(16);
+        assert_eq!(window.cap(), 16);
+
+        assert_eq!(window.update(true), 12);
+        assert_eq!(window.update(true), 9);
+        for _ in 0..10 {
+            window.update(true);
+        }
+        assert_eq!(window.cap(), MIN_STREAMS);
+
+        assert_eq!(window.update(false), MIN_STREAMS + 1);
+        for _ in 0..100 {
+            window.update(false);
+        }
+        assert_eq!(window.cap(), 16);
+    }
+}
@@ -0,0 +1,100 @@
+//! 텍스트/클립보드 공유
+//!
+//! URL, 코드 조각, 클립보드 내용처럼 파일을 만들 필요 없는 짧은 텍스트를 피어에게 보냅니다.
+//! 텍스트는 제어 스트림([`super::control_stream`]) 헤더에 `Command::TextMessage`로 실어 보냅니다.
+
+use super::control_stream;
+use crate::protocol::Command;
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 한 메시지의 최대 크기 (UTF-8 바이트)
+pub const MAX_TEXT_BYTES: usize = 1024 * 1024;
+
+/// 수신한 텍스트 (프론트엔드 전송용)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReceivedText {
+    pub peer_id: String,
+    pub message_id: String,
+    pub content: String,
+    pub timestamp: u64,
+}
+
+impl ReceivedText {
+    /// 제어 스트림 명령에서 변환 (텍스트 메시지가 아니면 None)
+    pub fn from_command(peer_id: &str, command: Command) -> Option<Self> {
+        match command {
+            Command::TextMessage {
+                message_id,
+                content,
+                timestamp,
+            } => Some(Self {
+                peer_id: peer_id.to_string(),
+                message_id,
+                content,
+                timestamp,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// 텍스트 전송 후 피어가 끝까지 읽을 때까지 대기, 메시지 ID 반환
+pub async fn send_text(conn: &quinn::Connection, content: String) -> Result<String> {
+    if content.is_empty() {
+        return Err(anyhow!("빈 텍스트는 보낼 수 없습니다"));
+    }
+    if content.len() > MAX_TEXT_BYTES {
+        return Err(anyhow!(
+            "텍스트가 너무 깁니다: {} bytes (최대 {} bytes)",
+            content.len(),
+            MAX_TEXT_BYTES
+        ));
+    }
+
+    let message_id = uuid::Uuid::new_v4().to_string();
+    let command = Command::TextMessage {
+        message_id: message_id.clone(),
+        content,
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+    };
+
+    let mut send = control_stream::open(conn, &command).await?;
+    send.finish()?;
+    // 피어가 스트림을 끝까지 읽어야 완료 (중간에 중단하면 에러)
+    if let Some(code) = send.stopped().await? {
+        return Err(anyhow!("피어가 텍스트 수신을 거부했습니다 (code {})", code));
+    }
+
+    Ok(message_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_message_roundtrip() {
+        let command = Command::TextMessage {
+            message_id: "msg-1".to_string(),
+            content: "https://example.com/한글 \"quoted\"\n".to_string(),
+            timestamp: 42,
+        };
+        let bytes = command.to_bytes().unwrap();
+        assert!(bytes.len() <= control_stream::MAX_HEADER_BYTES);
+
+        let text = ReceivedText::from_command("peer-a", Command::from_bytes(&bytes).unwrap())
+            .expect("텍스트 메시지");
+        assert_eq!(text.peer_id, "peer-a");
+        assert_eq!(text.message_id, "msg-1");
+        assert_eq!(text.content, "https://example.com/한글 \"quoted\"\n");
+        assert_eq!(text.timestamp, 42);
+
+        assert!(ReceivedText::from_command("peer-a", Command::Ping).is_none());
+    }
+}
@@ -0,0 +1,991 @@
+//! 이어 올리기 HTTP 업로드 엔드포인트 (tus 1.0.0)
+//!
+//! QUIC을 쓸 수 없는 환경(UDP 차단, 앱 설치 불가)의 송신자를 위해 수신 측이 tus 호환 엔드포인트를
+//! 엽니다. `http://<LAN IP>:<port>/tus/<토큰>/`을 tus 클라이언트(tus-js-client, tusd 도구 등)의
+//! 엔드포인트로 쓰면 되고, 연결이 끊기면 `HEAD`로 받은 오프셋부터 이어 올립니다.
+//!
+//! 지원 확장: `creation`, `termination`, `checksum` (`Upload-Checksum: sha1|sha256 <base64>`).
+//! 네이티브 수신과 같이 저장 폴더의 `.pswp-part` 임시 파일에 쓰고, 기존 파일 처리 정책과 경로 검사를
+//! 적용하며, 레지스트리에 작업으로 등록해 같은 진행률/완료 이벤트를 보냅니다. 업로드 메타데이터에
+//! `checksum`(파일 전체 SHA-256 hex)이 있으면 완료 시 검증합니다.
+//! 업로드 상태는 메모리에만 있어 앱을 다시 시작하면 처음부터 올려야 합니다.
+
+use super::file_transfer::{resolve_destination, safe_destination, OverwritePolicy, ReceiveAction};
+use super::http_share::{generate_token, read_request_head};
+use super::part_file;
+use super::registry::{JobControl, TransferKind, TransferRegistry};
+use crate::dht::flood::SourceLimiter;
+use crate::error::AppError;
+use crate::util::constant_time_eq;
+use anyhow::{anyhow, Result};
+use base64::Engine;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::net::{IpAddr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tracing::{info, warn};
+
+const TUS_VERSION: &str = "1.0.0";
+
+/// 요청 하나의 비용 (`SourceLimiter` 기준 IP별 초당 20건)
+const REQUEST_COST: f64 = 1.0;
+
+/// 잘못된 토큰 요청에 추가로 매기는 비용
+const INVALID_TOKEN_COST: f64 = 16.0;
+
+/// 요청 헤더를 기다리는 시간
+const HEADER_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 본문 읽기가 이 시간 동안 멈추면 중단 (받은 만큼은 유지)
+const BODY_IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 이 시간 동안 이어 올리지 않은 업로드는 폐기
+const UPLOAD_IDLE_EXPIRY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// 진행률 보고 간격
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+
+/// 파일 쓰기 단위
+const CHUNK_SIZE: usize = 256 * 1024;
+
+/// 브라우저 tus 클라이언트가 읽어야 하는 응답 헤더 (CORS)
+const EXPOSE_HEADERS: &str = "Location, Upload-Offset, Upload-Length, Tus-Resumable, \
+    Tus-Version, Tus-Extension, Tus-Max-Size, Tus-Checksum-Algorithm";
+
+/// 브라우저 tus 클라이언트가 보내는 요청 헤더 (CORS)
+const ALLOW_HEADERS: &str = "Tus-Resumable, Upload-Length, Upload-Metadata, Upload-Offset, \
+    Upload-Checksum, Content-Type, X-HTTP-Method-Override";
+
+/// tus 체크섬 확장의 불일치 상태 코드
+const STATUS_CHECKSUM_MISMATCH: &str = "460 Checksum Mismatch";
+
+/// 엔드포인트 설정
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TusConfig {
+    pub save_dir: PathBuf,
+    #[serde(default)]
+    pub overwrite_policy: OverwritePolicy,
+    /// 업로드 하나의 최대 크기 (`Tus-Max-Size`)
+    #[serde(default)]
+    pub max_size: Option<u64>,
+    /// 수신 포트 (0 = 임의 포트)
+    #[serde(default)]
+    pub port: u16,
+}
+
+/// 실행 중인 엔드포인트 정보
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TusEndpoint {
+    /// tus 클라이언트에 넣을 엔드포인트 (토큰 포함)
+    pub url: String,
+    pub save_dir: String,
+    pub max_size: Option<u64>,
+}
+
+/// 진행 중인(또는 완료된) 업로드
+struct Upload {
+    job_id: String,
+    length: u64,
+    offset: u64,
+    part: PathBuf,
+    destination: PathBuf,
+    action: ReceiveAction,
+    /// 메타데이터로 받은 파일 전체 SHA-256 (hex)
+    expected_sha256: Option<String>,
+    hasher: Sha256,
+    control: JobControl,
+    /// PATCH 처리 중 (같은 업로드에 동시 PATCH 거부)
+    busy: bool,
+    completed: bool,
+    started: Instant,
+    last_activity: Instant,
+}
+
+struct Session {
+    token: String,
+    config: TusConfig,
+    uploads: Mutex<HashMap<String, Upload>>,
+    /// 끝나지 않은 업로드가 저장할 경로 (같은 이름의 동시 업로드가 임시 파일을 덮어쓰지 않도록)
+    destinations: Mutex<HashSet<PathBuf>>,
+    registry: Arc<TransferRegistry>,
+    limiter: SourceLimiter,
+}
+
+impl Session {
+    /// 다른 업로드가 받는 중이 아니면 저장 경로 예약
+    fn reserve_destination(&self, destination: &Path) -> bool {
+        self.destinations.lock().insert(destination.to_path_buf())
+    }
+
+    fn release_destination(&self, destination: &Path) {
+        self.destinations.lock().remove(destination);
+    }
+}
+
+struct RunningServer {
+    endpoint: TusEndpoint,
+    session: Arc<Session>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+/// tus 업로드 서버
+pub struct TusServer {
+    registry: Arc<TransferRegistry>,
+    server: tokio::sync::Mutex<Option<RunningServer>>,
+}
+
+impl TusServer {
+    pub fn new(registry: Arc<TransferRegistry>) -> Self {
+        Self {
+            registry,
+            server: tokio::sync::Mutex::new(None),
+        }
+    }
+
+    /// 엔드포인트 시작 (이미 실행 중이면 중지 후 새 토큰으로 다시 시작)
+    pub async fn start(&self, config: TusConfig, host_ip: IpAddr) -> Result<TusEndpoint> {
+        self.stop().await;
+        tokio::fs::create_dir_all(&config.save_dir).await?;
+
+        let listener = TcpListener::bind(("0.0.0.0", config.port)).await?;
+        let port = listener.local_addr()?.port();
+        let token = generate_token();
+        let endpoint = TusEndpoint {
+            url: format!("http://{}/tus/{}/", SocketAddr::new(host_ip, port), token),
+            save_dir: config.save_dir.to_string_lossy().to_string(),
+            max_size: config.max_size,
+        };
+        let session = Arc::new(Session {
+            token,
+            config,
+            uploads: Mutex::new(HashMap::new()),
+            destinations: Mutex::new(HashSet::new()),
+            registry: self.registry.clone(),
+            limiter: SourceLimiter::new(),
+        });
+
+        let task_session = session.clone();
+        let task = tokio::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((socket, remote)) => {
+                        let session = task_session.clone();
+                        tokio::spawn(async move {
+                            handle_connection(socket, remote, &session).await;
+                        });
+                    }
+                    Err(e) => {
+                        warn!("tus 연결 수락 실패: {}", e);
+                        tokio::time::sleep(Duration::from_millis(100)).await;
+                    }
+                }
+            }
+        });
+
+        info!(
+            "📤 tus 업로드 엔드포인트 시작: 포트 {} -> {}",
+            port, endpoint.save_dir
+        );
+        *self.server.lock().await = Some(RunningServer {
+            endpoint: endpoint.clone(),
+            session,
+            task,
+        });
+        Ok(endpoint)
+    }
+
+    /// 실행 중인 엔드포인트
+    pub async fn endpoint(&self) -> Option<TusEndpoint> {
+        self.server
+            .lock()
+            .await
+            .as_ref()
+            .map(|s| s.endpoint.clone())
+    }
+
+    /// 엔드포인트 중지 (끝나지 않은 업로드는 실패 처리하고 임시 파일 삭제)
+    pub async fn stop(&self) {
+        let Some(server) = self.server.lock().await.take() else {
+            return;
+        };
+        server.task.abort();
+        let uploads: Vec<Upload> = server
+            .session
+            .uploads
+            .lock()
+            .drain()
+            .map(|(_, u)| u)
+            .collect();
+        for upload in uploads {
+            abandon(&server.session, upload, "업로드 엔드포인트 중지됨");
+        }
+        info!("📤 tus 업로드 엔드포인트 중지");
+    }
+}
+
+/// 끝나지 않은 업로드 폐기
+fn abandon(session: &Session, upload: Upload, reason: &str) {
+    if upload.completed {
+        return;
+    }
+    upload.control.cancel();
+    part_file::discard(&upload.part);
+    session.release_destination(&upload.destination);
+    session
+        .registry
+        .finish(&upload.job_id, Err(AppError::Cancelled(reason.to_string())));
+}
+
+/// 응답 (상태, 추가 헤더, 본문)
+struct Response {
+    status: &'static str,
+    headers: Vec<(&'static str, String)>,
+    body: String,
+}
+
+impl Response {
+    fn new(status: &'static str) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: String::new(),
+        }
+    }
+
+    fn error(status: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            body: message.into(),
+            ..Self::new(status)
+        }
+    }
+
+    fn header(mut self, name: &'static str, value: impl ToString) -> Self {
+        self.headers.push((name, value.to_string()));
+        self
+    }
+
+    fn encode(&self) -> String {
+        let mut out = format!(
+            "HTTP/1.1 {}\r\n\
+            Tus-Resumable: {}\r\n\
+            Access-Control-Allow-Origin: *\r\n\
+            Access-Control-Expose-Headers: {}\r\n\
+            Content-Length: {}\r\n\
+            Connection: close\r\n",
+            self.status,
+            TUS_VERSION,
+            EXPOSE_HEADERS,
+            self.body.len()
+        );
+        for (name, value) in &self.headers {
+            out.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        out.push_str("\r\n");
+        out.push_str(&self.body);
+        out
+    }
+}
+
+/// 요청 줄과 헤더
+struct Request {
+    method: String,
+    path: String,
+    headers: Vec<(String, String)>,
+}
+
+impl Request {
+    fn parse(head: &str) -> Option<Self> {
+        let mut lines = head.lines();
+        let mut parts = lines.next()?.split_whitespace();
+        let mut method = parts.next()?.to_string();
+        let path = parts.next()?.split('?').next()?.to_string();
+        let headers: Vec<(String, String)> = lines
+            .take_while(|line| !line.is_empty())
+            .filter_map(|line| line.split_once(':'))
+            .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+            .collect();
+
+        // PATCH를 막는 환경을 위한 tus 규약
+        if let Some((_, overridden)) = headers.iter().find(|(n, _)| n == "x-http-method-override") {
+            method = overridden.to_ascii_uppercase();
+        }
+        Some(Self {
+            method,
+            path,
+            headers,
+        })
+    }
+
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn header_u64(&self, name: &str) -> Option<u64> {
+        self.header(name)?.parse().ok()
+    }
+}
+
+async fn handle_connection(mut socket: TcpStream, remote: SocketAddr, session: &Session) {
+    let (head, body_start) =
+        match tokio::time::timeout(HEADER_TIMEOUT, read_request_head(&mut socket)).await {
+            Ok(Ok(read)) => read,
+            _ => return,
+        };
+
+    let response = match Request::parse(&head) {
+        Some(request) => handle_request(&request, &mut socket, body_start, remote, session).await,
+        None => Response::error("400 Bad Request", "잘못된 요청"),
+    };
+    let _ = socket.write_all(response.encode().as_bytes()).await;
+    let _ = socket.shutdown().await;
+}
+
+async fn handle_request(
+    request: &Request,
+    socket: &mut TcpStream,
+    body_start: Vec<u8>,
+    remote: SocketAddr,
+    session: &Session,
+) -> Response {
+    if !session.limiter.allow(remote.ip(), REQUEST_COST) {
+        return Response::error("429 Too Many Requests", "요청이 너무 많습니다")
+            .header("Retry-After", 1);
+    }
+
+    // /tus/<토큰>/ 또는 /tus/<토큰>/<업로드 ID>
+    let Some(rest) = request.path.strip_prefix("/tus/") else {
+        return Response::error("404 Not Found", "Not Found");
+    };
+    let (token, upload_id) = rest.split_once('/').unwrap_or((rest, ""));
+    if !constant_time_eq(token.as_bytes(), session.token.as_bytes()) {
+        let _ = session.limiter.allow(remote.ip(), INVALID_TOKEN_COST);
+        return Response::error("404 Not Found", "Not Found");
+    }
+
+    if request.method == "OPTIONS" {
+        let mut response = Response::new("204 No Content")
+            .header("Tus-Version", TUS_VERSION)
+            .header("Tus-Extension", "creation,termination,checksum")
+            .header("Tus-Checksum-Algorithm", "sha1,sha256")
+            .header(
+                "Access-Control-Allow-Methods",
+                "POST, HEAD, PATCH, DELETE, OPTIONS",
+            )
+            .header("Access-Control-Allow-Headers", ALLOW_HEADERS);
+        if let Some(max) = session.config.max_size {
+            response = response.header("Tus-Max-Size", max);
+        }
+        return response;
+    }
+    if request.header("tus-resumable") != Some(TUS_VERSION) {
+        return Response::error("412 Precondition Failed", "지원하지 않는 tus 버전")
+            .header("Tus-Version", TUS_VERSION);
+    }
+
+    prune_idle(session, Instant::now());
+    match (request.method.as_str(), upload_id.trim_end_matches('/')) {
+        ("POST", "") => create(request, remote, session).await,
+        ("HEAD", id) if !id.is_empty() => head(session, id),
+        ("PATCH", id) if !id.is_empty() => patch(request, socket, body_start, session, id).await,
+        ("DELETE", id) if !id.is_empty() => terminate(session, id),
+        _ => Response::error("405 Method Not Allowed", "지원하지 않는 요청"),
+    }
+}
+
+/// 오래 멈춘 업로드 폐기
+fn prune_idle(session: &Session, now: Instant) {
+    let expired: Vec<Upload> = {
+        let mut uploads = session.uploads.lock();
+        let ids: Vec<String> = uploads
+            .iter()
+            .filter(|(_, u)| {
+                !u.busy && now.saturating_duration_since(u.last_activity) >= UPLOAD_IDLE_EXPIRY
+            })
+            .map(|(id, _)| id.clone())
+            .collect();
+        ids.iter().filter_map(|id| uploads.remove(id)).collect()
+    };
+    for upload in expired {
+        warn!("📤 tus 업로드 만료: {}", upload.job_id);
+        abandon(session, upload, "업로드가 오래 멈춰 만료됨");
+    }
+}
+
+/// 업로드 생성 (`creation` 확장)
+async fn create(request: &Request, remote: SocketAddr, session: &Session) -> Response {
+    let Some(length) = request.header_u64("upload-length") else {
+        return Response::error("400 Bad Request", "Upload-Length가 필요합니다");
+    };
+    if session.config.max_size.is_some_and(|max| length > max) {
+        return Response::error("413 Request Entity Too Large", "최대 업로드 크기 초과");
+    }
+    let metadata = parse_metadata(request.header("upload-metadata").unwrap_or(""));
+    let Some(file_name) = metadata
+        .get("filename")
+        .or_else(|| metadata.get("name"))
+        .filter(|name| !name.is_empty())
+    else {
+        return Response::error("400 Bad Request", "Upload-Metadata에 filename이 필요합니다");
+    };
+
+    let destination = match safe_destination(&session.config.save_dir, file_name) {
+        Ok(path) => path,
+        Err(e) => return Response::error("400 Bad Request", e.to_string()),
+    };
+    let (destination, action) =
+        match resolve_destination(&destination, session.config.overwrite_policy) {
+            Ok((_, ReceiveAction::Skipped)) => {
+                return Response::error("409 Conflict", "같은 이름의 파일이 이미 있습니다")
+            }
+            Ok(resolved) => resolved,
+            Err(e) => return Response::error("409 Conflict", e.to_string()),
+        };
+    if !session.reserve_destination(&destination) {
+        return Response::error("409 Conflict", "같은 이름의 파일을 이미 받고 있습니다");
+    }
+
+    let part = part_file::part_path(&destination);
+    let created = async {
+        if let Some(parent) = part.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        // 다른 수신이 쓰고 있는 임시 파일은 비우지 않음
+        tokio::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&part)
+            .await?;
+        anyhow::Ok(())
+    }
+    .await;
+    if let Err(e) = created {
+        session.release_destination(&destination);
+        let status = match e.downcast_ref::<std::io::Error>() {
+            Some(io) if io.kind() == std::io::ErrorKind::AlreadyExists => "409 Conflict",
+            _ => "500 Internal Server Error",
+        };
+        return Response::error(status, format!("임시 파일 생성 실패: {}", e));
+    }
+
+    let upload_id = uuid::Uuid::new_v4().simple().to_string();
+    let job_id = format!("tus-{}", upload_id);
+    let control =
+        match session
+            .registry
+            .register(&job_id, &remote.ip().to_string(), TransferKind::File)
+        {
+            Ok(control) => control,
+            Err(e) => {
+                part_file::discard(&part);
+                session.release_destination(&destination);
+                return Response::error("500 Internal Server Error", e.to_string());
+            }
+        };
+    session.registry.update_progress(&job_id, 0, length, 0);
+
+    info!(
+        "📤 tus 업로드 생성: {} ({} bytes) <- {}",
+        file_name, length, remote
+    );
+    let now = Instant::now();
+    session.uploads.lock().insert(
+        upload_id.clone(),
+        Upload {
+            job_id,
+            length,
+            offset: 0,
+            part,
+            destination,
+            action,
+            expected_sha256: metadata.get("checksum").map(|c| c.to_ascii_lowercase()),
+            hasher: Sha256::new(),
+            control,
+            busy: false,
+            completed: false,
+            started: now,
+            last_activity: now,
+        },
+    );
+
+    // 빈 파일은 PATCH 없이 바로 완료
+    if length == 0 {
+        if let Err(response) = complete(session, &upload_id) {
+            return response;
+        }
+    }
+
+    Response::new("201 Created")
+        .header("Location", format!("/tus/{}/{}", session.token, upload_id))
+        .header("Upload-Offset", 0)
+}
+
+/// 현재 오프셋 조회
+fn head(session: &Session, upload_id: &str) -> Response {
+    match session.uploads.lock().get(upload_id) {
+        Some(upload) => Response::new("200 OK")
+            .header("Upload-Offset", upload.offset)
+            .header("Upload-Length", upload.length)
+            .header("Cache-Control", "no-store"),
+        None => Response::error("404 Not Found", "업로드를 찾을 수 없습니다"),
+    }
+}
+
+/// 업로드 취소 (`termination` 확장)
+fn terminate(session: &Session, upload_id: &str) -> Response {
+    let upload = {
+        let mut uploads = session.uploads.lock();
+        match uploads.get(upload_id) {
+            Some(upload) if upload.busy => {
+                return Response::error("409 Conflict", "업로드 처리 중입니다")
+            }
+            Some(_) => uploads.remove(upload_id),
+            None => None,
+        }
+    };
+    match upload {
+        Some(upload) => {
+            info!("📤 tus 업로드 취소: {}", upload.job_id);
+            abandon(session, upload, "송신자가 업로드를 취소함");
+            Response::new("204 No Content")
+        }
+        None => Response::error("404 Not Found", "업로드를 찾을 수 없습니다"),
+    }
+}
+
+/// PATCH 한 번의 체크섬 (`Upload-Checksum: <알고리즘> <base64>`)
+enum ChunkChecksum {
+    Sha1(Sha1, Vec<u8>),
+    Sha256(Sha256, Vec<u8>),
+}
+
+impl ChunkChecksum {
+    fn parse(header: &str) -> Option<Self> {
+        let (algorithm, encoded) = header.split_once(' ')?;
+        let expected = base64::engine::general_purpose::STANDARD
+            .decode(encoded.trim())
+            .ok()?;
+        match algorithm.to_ascii_lowercase().as_str() {
+            "sha1" => Some(Self::Sha1(Sha1::new(), expected)),
+            "sha256" => Some(Self::Sha256(Sha256::new(), expected)),
+            _ => None,
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Self::Sha1(hasher, _) => hasher.update(data),
+            Self::Sha256(hasher, _) => hasher.update(data),
+        }
+    }
+
+    fn matches(self) -> bool {
+        match self {
+            Self::Sha1(hasher, expected) => hasher.finalize().as_slice() == expected,
+            Self::Sha256(hasher, expected) => hasher.finalize().as_slice() == expected,
+        }
+    }
+}
+
+/// 데이터 이어 쓰기
+async fn patch(
+    request: &Request,
+    socket: &mut TcpStream,
+    body_start: Vec<u8>,
+    session: &Session,
+    upload_id: &str,
+) -> Response {
+    if request.header("content-type") != Some("application/offset+octet-stream") {
+        return Response::error(
+            "415 Unsupported Media Type",
+            "application/offset+octet-stream이어야 합니다",
+        );
+    }
+    let (Some(offset), Some(content_length)) = (
+        request.header_u64("upload-offset"),
+        request.header_u64("content-length"),
+    ) else {
+        return Response::error(
+            "400 Bad Request",
+            "Upload-Offset과 Content-Length가 필요합니다",
+        );
+    };
+    let mut checksum = match request.header("upload-checksum") {
+        Some(header) => match ChunkChecksum::parse(header) {
+            Some(checksum) => Some(checksum),
+            None => return Response::error("400 Bad Request", "지원하지 않는 Upload-Checksum"),
+        },
+        None => None,
+    };
+
+    // 처리 중 표시 후 잠금 밖에서 파일 쓰기
+    let (part, length, mut hasher, control, job_id, started) = {
+        let mut uploads = session.uploads.lock();
+        let Some(upload) = uploads.get_mut(upload_id) else {
+            return Response::error("404 Not Found", "업로드를 찾을 수 없습니다");
+        };
+        if upload.busy {
+            return Response::error("409 Conflict", "업로드 처리 중입니다");
+        }
+        if upload.completed || offset != upload.offset {
+            return Response::error("409 Conflict", "오프셋이 맞지 않습니다")
+                .header("Upload-Offset", upload.offset);
+        }
+        if offset + content_length > upload.length {
+            return Response::error("400 Bad Request", "Upload-Length를 넘는 데이터");
+        }
+        upload.busy = true;
+        (
+            upload.part.clone(),
+            upload.length,
+            upload.hasher.clone(),
+            upload.control.clone(),
+            upload.job_id.clone(),
+            upload.started,
+        )
+    };
+
+    let progress = |bytes: u64| {
+        let elapsed = started.elapsed().as_secs_f64().max(f64::EPSILON);
+        session
+            .registry
+            .update_progress(&job_id, bytes, length, (bytes as f64 / elapsed) as u64);
+    };
+    let written = write_body(
+        socket,
+        body_start,
+        &part,
+        offset,
+        content_length,
+        &control,
+        &mut hasher,
+        checksum.as_mut(),
+        progress,
+    )
+    .await;
+
+    let (written, error) = match written {
+        Ok(written) => (written, None),
+        Err((written, e)) => (written, Some(e)),
+    };
+    let verified = checksum.map(ChunkChecksum::matches);
+    // 체크섬이 있는 PATCH는 전부 받고 검증된 경우에만 반영
+    let keep = match verified {
+        Some(ok) => ok && error.is_none() && written == content_length,
+        None => true,
+    };
+
+    let new_offset = {
+        let mut uploads = session.uploads.lock();
+        let Some(upload) = uploads.get_mut(upload_id) else {
+            return Response::error("404 Not Found", "업로드를 찾을 수 없습니다");
+        };
+        upload.busy = false;
+        upload.last_activity = Instant::now();
+        if keep {
+            upload.offset = offset + written;
+            upload.hasher = hasher;
+        }
+        upload.offset
+    };
+    if !keep {
+        // 반영하지 않은 데이터는 잘라냄
+        if let Ok(file) = tokio::fs::OpenOptions::new().write(true).open(&part).await {
+            let _ = file.set_len(offset).await;
+        }
+    }
+    session
+        .registry
+        .update_progress(&job_id, new_offset, length, 0);
+
+    if control.is_cancelled() {
+        if let Some(upload) = session.uploads.lock().remove(upload_id) {
+            abandon(session, upload, "사용자에 의해 취소됨");
+        }
+        return Response::error("410 Gone", "수신 측에서 업로드를 취소했습니다");
+    }
+    if verified == Some(false) {
+        return Response::error(STATUS_CHECKSUM_MISMATCH, "청크 체크섬 불일치")
+            .header("Upload-Offset", new_offset);
+    }
+    if let Some(e) = error {
+        warn!("📤 tus PATCH 중단 ({}): {}", job_id, e);
+        return Response::error("500 Internal Server Error", e.to_string())
+            .header("Upload-Offset", new_offset);
+    }
+
+    if new_offset == length {
+        if let Err(response) = complete(session, upload_id) {
+            return response;
+        }
+    }
+    Response::new("204 No Content").header("Upload-Offset", new_offset)
+}
+
+/// 본문을 임시 파일의 `offset` 위치에 기록, 쓴 바이트 수 (실패해도 쓴 만큼 함께 반환)
+#[allow(clippy::too_many_arguments)]
+async fn write_body(
+    socket: &mut TcpStream,
+    body_start: Vec<u8>,
+    part: &std::path::Path,
+    offset: u64,
+    content_length: u64,
+    control: &JobControl,
+    hasher: &mut Sha256,
+    mut checksum: Option<&mut ChunkChecksum>,
+    progress: impl Fn(u64),
+) -> std::result::Result<u64, (u64, anyhow::Error)> {
+    let mut file = match tokio::fs::OpenOptions::new().write(true).open(part).await {
+        Ok(file) => file,
+        Err(e) => return Err((0, e.into())),
+    };
+    if let Err(e) = file.seek(std::io::SeekFrom::Start(offset)).await {
+        return Err((0, e.into()));
+    }
+
+    let mut written = 0u64;
+    let mut pending = body_start;
+    let mut buf = vec![0u8; CHUNK_SIZE];
+    let mut last_progress = Instant::now();
+    while written < content_length {
+        if let Err(e) = control.checkpoint().await {
+            return Err((written, e));
+        }
+
+        let data: &[u8] = if !pending.is_empty() {
+            &pending
+        } else {
+            let want = (content_length - written).min(CHUNK_SIZE as u64) as usize;
+            match tokio::time::timeout(BODY_IDLE_TIMEOUT, socket.read(&mut buf[..want])).await {
+                Ok(Ok(0)) => return Err((written, anyhow!("본문을 받는 중 연결 종료"))),
+                Ok(Ok(n)) => &buf[..n],
+                Ok(Err(e)) => return Err((written, e.into())),
+                Err(_) => return Err((written, anyhow!("본문 수신 시간 초과"))),
+            }
+        };
+        let data = &data[..data.len().min((content_length - written) as usize)];
+
+        if let Err(e) = file.write_all(data).await {
+            return Err((written, e.into()));
+        }
+        hasher.update(data);
+        if let Some(checksum) = checksum.as_deref_mut() {
+            checksum.update(data);
+        }
+        written += data.len() as u64;
+        pending.clear();
+
+        if last_progress.elapsed() >= PROGRESS_INTERVAL {
+            last_progress = Instant::now();
+            progress(offset + written);
+        }
+    }
+
+    if let Err(e) = file.flush().await {
+        return Err((written, e.into()));
+    }
+    Ok(written)
+}
+
+/// 모두 받은 업로드 검증 후 최종 이름으로 저장
+fn complete(session: &Session, upload_id: &str) -> std::result::Result<(), Response> {
+    let (job_id, part, destination, action, expected, hasher) = {
+        let mut uploads = session.uploads.lock();
+        let Some(upload) = uploads.get_mut(upload_id) else {
+            return Ok(());
+        };
+        upload.completed = true;
+        (
+            upload.job_id.clone(),
+            upload.part.clone(),
+            upload.destination.clone(),
+            upload.action,
+            upload.expected_sha256.clone(),
+            upload.hasher.clone(),
+        )
+    };
+
+    let checksum = hex::encode(hasher.finalize());
+    let result = match expected {
+        Some(expected) if expected != checksum => Err(anyhow!(
+            "SHA-256 불일치 (예상 {}, 실제 {})",
+            expected,
+            checksum
+        )),
+        _ => part_file::commit(&part, &destination).map_err(Into::into),
+    };
+    session.release_destination(&destination);
+
+    match result {
+        Ok(()) => {
+            info!(
+                "✅ tus 업로드 완료: {:?} (SHA-256 {})",
+                destination, checksum
+            );
+            session.registry.set_action(&job_id, action.as_str());
+            session.registry.finish(&job_id, Ok(()));
+            Ok(())
+        }
+        Err(e) => {
+            warn!("📤 tus 업로드 검증 실패 ({}): {}", job_id, e);
+            part_file::discard(&part);
+            session.uploads.lock().remove(upload_id);
+            session
+                .registry
+                .finish(&job_id, Err(AppError::Io(e.to_string())));
+            Err(Response::error(STATUS_CHECKSUM_MISMATCH, e.to_string()))
+        }
+    }
+}
+
+/// `Upload-Metadata` (`키 base64값` 쉼표 구분) 해석
+fn parse_metadata(header: &str) -> HashMap<String, String> {
+    header
+        .split(',')
+        .filter_map(|pair| {
+            let mut parts = pair.split_whitespace();
+            let key = parts.next()?.to_string();
+            let value = match parts.next() {
+                Some(encoded) => {
+                    let bytes = base64::engine::general_purpose::STANDARD
+                        .decode(encoded)
+                        .ok()?;
+                    String::from_utf8(bytes).ok()?
+                }
+                None => String::new(),
+            };
+            Some((key, value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metadata_and_request_parsing() {
+        // filename "보고서.pdf", checksum "ab12", 값 없는 키
+        let metadata =
+            parse_metadata("filename 67O06rOg7IScLnBkZg==,checksum YWIxMg==, is_confidential");
+        assert_eq!(
+            metadata.get("filename").map(String::as_str),
+            Some("보고서.pdf")
+        );
+        assert_eq!(metadata.get("checksum").map(String::as_str), Some("ab12"));
+        assert_eq!(
+            metadata.get("is_confidential").map(String::as_str),
+            Some("")
+        );
+
+        let request = Request::parse(
+            "POST /tus/abc/123?x=1 HTTP/1.1\r\nTus-Resumable: 1.0.0\r\n\
+            X-HTTP-Method-Override: patch\r\nUpload-Offset: 42\r\n\r\n",
+        )
+        .unwrap();
+        assert_eq!(request.method, "PATCH");
+        assert_eq!(request.path, "/tus/abc/123");
+        assert_eq!(request.header("tus-resumable"), Some(TUS_VERSION));
+        assert_eq!(request.header_u64("upload-offset"), Some(42));
+
+        // "hello"의 SHA-1
+        let mut checksum = ChunkChecksum::parse("sha1 qvTGHdzF6KLavt4PO0gs2a6pQ00=").unwrap();
+        checksum.update(b"hel");
+        checksum.update(b"lo");
+        assert!(checksum.matches());
+        let mut checksum = ChunkChecksum::parse("sha1 qvTGHdzF6KLavt4PO0gs2a6pQ00=").unwrap();
+        checksum.update(b"world");
+        assert!(!checksum.matches());
+        assert!(ChunkChecksum::parse("md5 qvTGHdzF6KLavt4PO0gs2a6pQ00=").is_none());
+
+        let response = Response::new("204 No Content")
+            .header("Upload-Offset", 7)
+            .encode();
+        assert!(response.starts_with("HTTP/1.1 204 No Content\r\nTus-Resumable: 1.0.0\r\n"));
+        assert!(response.contains("Upload-Offset: 7\r\n"));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_same_name_uploads() {
+        let dir = std::env::temp_dir().join(format!("ponswarp-tus-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let session = Session {
+            token: "t".into(),
+            config: TusConfig {
+                save_dir: dir.clone(),
+                overwrite_policy: OverwritePolicy::Rename,
+                max_size: None,
+                port: 0,
+            },
+            uploads: Mutex::new(HashMap::new()),
+            destinations: Mutex::new(HashSet::new()),
+            registry: Arc::new(TransferRegistry::new()),
+            limiter: SourceLimiter::new(),
+        };
+        let remote: SocketAddr = "127.0.0.1:50000".parse().unwrap();
+        // filename "a.txt"
+        let request = Request::parse(
+            "POST /tus/t/ HTTP/1.1\r\nUpload-Length: 5\r\nUpload-Metadata: filename YS50eHQ=\r\n\r\n",
+        )
+        .unwrap();
+
+        // 같은 이름의 동시 업로드는 하나만 만들어짐
+        let (a, b) = tokio::join!(
+            create(&request, remote, &session),
+            create(&request, remote, &session)
+        );
+        let (first, second) = if a.status == "201 Created" {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        assert_eq!(first.status, "201 Created");
+        assert_eq!(second.status, "409 Conflict");
+
+        // 받는 중인 업로드의 임시 파일은 그대로 유지
+        let part = part_file::part_path(&dir.join("a.txt"));
+        std::fs::write(&part, b"hel").unwrap();
+        assert_eq!(
+            create(&request, remote, &session).await.status,
+            "409 Conflict"
+        );
+        assert_eq!(std::fs::read(&part).unwrap(), b"hel");
+
+        // 취소하면 임시 파일과 예약이 정리됨
+        let location = &first
+            .headers
+            .iter()
+            .find(|(name, _)| *name == "Location")
+            .unwrap()
+            .1;
+        let upload_id = location.rsplit('/').next().unwrap();
+        assert_eq!(terminate(&session, upload_id).status, "204 No Content");
+        assert!(!part.exists());
+
+        // 다른 수신이 쓰고 있는 임시 파일도 비우지 않음
+        std::fs::write(&part, b"other").unwrap();
+        assert_eq!(
+            create(&request, remote, &session).await.status,
+            "409 Conflict"
+        );
+        assert_eq!(std::fs::read(&part).unwrap(), b"other");
+        std::fs::remove_file(&part).unwrap();
+        assert_eq!(
+            create(&request, remote, &session).await.status,
+            "201 Created"
+        );
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
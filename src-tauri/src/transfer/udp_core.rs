@@ -1,17 +1,34 @@
 use anyhow::Result;
 use bytes::{Bytes, BytesMut};
-use std::net::SocketAddr;
+use parking_lot::Mutex;
+use serde::Serialize;
+use socket2::{Domain, Protocol, Socket, Type};
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tokio::net::UdpSocket;
-use tokio::sync::{mpsc, RwLock, Semaphore};
+use tokio::sync::{mpsc, Semaphore};
 use tracing::{debug, info, warn};
 
+use super::pacer::Pacer;
+use crate::metrics::SubsystemCounter;
+
 const UDP_PAYLOAD_SIZE: usize = 65507;
 const CHUNK_HEADER_SIZE: usize = 24;
-const MAX_CHUNK_DATA: usize = UDP_PAYLOAD_SIZE - CHUNK_HEADER_SIZE;
+/// 데이터그램 하나에 담을 수 있는 최대 청크 데이터
+pub const MAX_CHUNK_DATA: usize = UDP_PAYLOAD_SIZE - CHUNK_HEADER_SIZE;
 const DEFAULT_SOCKET_COUNT: usize = 8;
 
+/// 소켓 송수신 버퍼 크기
+const SOCKET_BUFFER_SIZE: usize = 16 * 1024 * 1024;
+
+/// 송신 워커 대기열 길이 (패킷 수)
+const SEND_QUEUE: usize = 1024;
+
+/// 🆕 소켓별 현재 속도를 다시 계산하는 최소 간격 (더 자주 조회하면 직전 값 반환)
+const RATE_SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
 #[derive(Debug, Clone)]
 pub struct TransferStats {
     pub bytes_sent: u64,
@@ -72,6 +89,14 @@ impl ChunkHeader {
         buf
     }
 
+    /// 헤더 + 데이터를 데이터그램 하나로 조립
+    pub fn to_packet(&self, data: &[u8]) -> Bytes {
+        let mut packet = BytesMut::with_capacity(CHUNK_HEADER_SIZE + data.len());
+        packet.extend_from_slice(&self.encode());
+        packet.extend_from_slice(data);
+        packet.freeze()
+    }
+
     pub fn decode(buf: &[u8]) -> Option<Self> {
         if buf.len() < CHUNK_HEADER_SIZE {
             return None;
@@ -90,65 +115,226 @@ impl ChunkHeader {
     }
 }
 
+/// 🆕 현재 속도 계산용 직전 표본
+#[derive(Debug, Clone, Copy)]
+struct RateSample {
+    at: Instant,
+    bytes_sent: u64,
+    bytes_received: u64,
+    send_rate_bps: u64,
+    receive_rate_bps: u64,
+}
+
+/// 소켓(샤드)별 통계 - 워커가 락 없이 갱신하고 조회 시 합산
+#[derive(Debug, Default)]
+pub struct SocketStats {
+    bytes_sent: AtomicU64,
+    bytes_received: AtomicU64,
+    packets_sent: AtomicU64,
+    packets_received: AtomicU64,
+    /// 🆕 전송 실패한 패킷
+    send_errors: AtomicU64,
+    /// 🆕 받았지만 버린 데이터그램 (너무 짧거나 헤더 손상)
+    packets_dropped: AtomicU64,
+    /// 🆕 직전 조회 시점 (현재 속도 계산용)
+    last_sample: Mutex<Option<RateSample>>,
+    /// 앱 전체 처리량 지표 (송수신 합산)
+    metrics: Option<Arc<SubsystemCounter>>,
+}
+
+impl SocketStats {
+    fn record_sent(&self, bytes: usize) {
+        self.bytes_sent.fetch_add(bytes as u64, Ordering::Relaxed);
+        self.packets_sent.fetch_add(1, Ordering::Relaxed);
+        if let Some(metrics) = &self.metrics {
+            metrics.add_bytes(bytes as u64);
+        }
+    }
+
+    fn record_received(&self, bytes: usize) {
+        self.bytes_received
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+        self.packets_received.fetch_add(1, Ordering::Relaxed);
+        if let Some(metrics) = &self.metrics {
+            metrics.add_bytes(bytes as u64);
+        }
+    }
+
+    fn record_send_error(&self) {
+        self.send_errors.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn record_dropped(&self) {
+        self.packets_dropped.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> SocketStatsSnapshot {
+        self.snapshot_at(Instant::now())
+    }
+
+    /// 현재 속도는 직전 조회 이후 바이트 증가량으로 계산
+    fn snapshot_at(&self, now: Instant) -> SocketStatsSnapshot {
+        let bytes_sent = self.bytes_sent.load(Ordering::Relaxed);
+        let bytes_received = self.bytes_received.load(Ordering::Relaxed);
+
+        let mut last = self.last_sample.lock();
+        let sample = match *last {
+            Some(prev) if now.duration_since(prev.at) < RATE_SAMPLE_INTERVAL => prev,
+            Some(prev) => {
+                let elapsed = now.duration_since(prev.at).as_secs_f64();
+                let rate =
+                    |now: u64, before: u64| (now.saturating_sub(before) as f64 / elapsed) as u64;
+                RateSample {
+                    at: now,
+                    bytes_sent,
+                    bytes_received,
+                    send_rate_bps: rate(bytes_sent, prev.bytes_sent),
+                    receive_rate_bps: rate(bytes_received, prev.bytes_received),
+                }
+            }
+            None => RateSample {
+                at: now,
+                bytes_sent,
+                bytes_received,
+                send_rate_bps: 0,
+                receive_rate_bps: 0,
+            },
+        };
+        *last = Some(sample);
+
+        SocketStatsSnapshot {
+            bytes_sent,
+            bytes_received,
+            packets_sent: self.packets_sent.load(Ordering::Relaxed),
+            packets_received: self.packets_received.load(Ordering::Relaxed),
+            send_errors: self.send_errors.load(Ordering::Relaxed),
+            packets_dropped: self.packets_dropped.load(Ordering::Relaxed),
+            send_rate_bps: sample.send_rate_bps,
+            receive_rate_bps: sample.receive_rate_bps,
+        }
+    }
+}
+
+/// 프론트엔드 전송용 소켓별 통계
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SocketStatsSnapshot {
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    pub packets_sent: u64,
+    pub packets_received: u64,
+    /// 🆕 전송 실패한 패킷
+    pub send_errors: u64,
+    /// 🆕 받았지만 버린 데이터그램
+    pub packets_dropped: u64,
+    /// 🆕 직전 조회 이후 송신 속도 (bytes/sec)
+    pub send_rate_bps: u64,
+    /// 🆕 직전 조회 이후 수신 속도 (bytes/sec)
+    pub receive_rate_bps: u64,
+}
+
+/// UDP 소켓 생성 (송수신 버퍼 확대, `reuse_port`면 같은 포트에 여러 소켓 바인딩 허용)
+fn bind_socket(port: u16, reuse_port: bool) -> Result<UdpSocket> {
+    let socket = Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::UDP))?;
+
+    // 버퍼 확대 실패는 치명적이지 않음 (OS 상한에 걸릴 수 있음)
+    if let Err(e) = socket.set_send_buffer_size(SOCKET_BUFFER_SIZE) {
+        debug!("SO_SNDBUF 설정 실패: {}", e);
+    }
+    if let Err(e) = socket.set_recv_buffer_size(SOCKET_BUFFER_SIZE) {
+        debug!("SO_RCVBUF 설정 실패: {}", e);
+    }
+
+    #[cfg(unix)]
+    if reuse_port {
+        socket.set_reuse_port(true)?;
+    }
+    #[cfg(not(unix))]
+    let _ = reuse_port;
+
+    socket.set_nonblocking(true)?;
+    socket.bind(&SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), port).into())?;
+    Ok(UdpSocket::from_std(socket.into())?)
+}
+
 pub struct UdpTransferCore {
     sockets: Vec<Arc<UdpSocket>>,
-    stats: Arc<RwLock<TransferStats>>,
+    /// 소켓과 같은 순서의 소켓별 통계
+    socket_stats: Vec<Arc<SocketStats>>,
+    packets_lost: AtomicU64,
+    start_time: Instant,
     send_semaphore: Arc<Semaphore>,
+    /// 모든 소켓 합산 송신 속도 페이서 (기본: 페이싱 없음)
+    pacer: Arc<Pacer>,
 }
 
 impl UdpTransferCore {
+    /// 소켓마다 다른 임시 포트에 바인딩
     pub async fn new(socket_count: usize) -> Result<Self> {
-        let count = socket_count.max(1).min(64);
+        let count = socket_count.clamp(1, 64);
         let mut sockets = Vec::with_capacity(count);
 
         for i in 0..count {
-            let socket = UdpSocket::bind("0.0.0.0:0").await?;
-
-            #[cfg(target_os = "linux")]
-            {
-                use std::os::unix::io::AsRawFd;
-                let fd = socket.as_raw_fd();
-                unsafe {
-                    let optval: libc::c_int = 1;
-                    libc::setsockopt(
-                        fd,
-                        libc::SOL_SOCKET,
-                        libc::SO_REUSEPORT,
-                        &optval as *const _ as *const libc::c_void,
-                        std::mem::size_of::<libc::c_int>() as libc::socklen_t,
-                    );
-
-                    let sndbuf: libc::c_int = 16 * 1024 * 1024;
-                    libc::setsockopt(
-                        fd,
-                        libc::SOL_SOCKET,
-                        libc::SO_SNDBUF,
-                        &sndbuf as *const _ as *const libc::c_void,
-                        std::mem::size_of::<libc::c_int>() as libc::socklen_t,
-                    );
-
-                    let rcvbuf: libc::c_int = 16 * 1024 * 1024;
-                    libc::setsockopt(
-                        fd,
-                        libc::SOL_SOCKET,
-                        libc::SO_RCVBUF,
-                        &rcvbuf as *const _ as *const libc::c_void,
-                        std::mem::size_of::<libc::c_int>() as libc::socklen_t,
-                    );
-                }
-            }
-
+            let socket = bind_socket(0, false)?;
             debug!("UDP 소켓 {} 생성: {}", i, socket.local_addr()?);
-            sockets.push(Arc::new(socket));
+            sockets.push(socket);
         }
 
         info!("🚀 UDP 전송 코어 초기화: {} 소켓", count);
+        Ok(Self::with_sockets(sockets))
+    }
+
+    /// 한 포트에 SO_REUSEPORT로 여러 소켓을 바인딩 (코어당 하나, 10Gbps 이상용)
+    ///
+    /// 커널이 송신측 주소/포트 해시로 수신 패킷을 소켓에 분배하므로 소켓별 워커가
+    /// 서로 다른 코어에서 병렬로 처리합니다. `port`가 0이면 첫 소켓이 받은 포트를 공유합니다.
+    /// SO_REUSEPORT가 없는 플랫폼에서는 소켓 하나로 동작합니다.
+    pub async fn new_sharded(port: u16, shards: Option<usize>) -> Result<Self> {
+        let count = if cfg!(unix) {
+            shards.unwrap_or_else(num_cpus::get).clamp(1, 64)
+        } else {
+            1
+        };
+
+        let first = bind_socket(port, true)?;
+        let port = first.local_addr()?.port();
+        let mut sockets = Vec::with_capacity(count);
+        sockets.push(first);
+        for _ in 1..count {
+            sockets.push(bind_socket(port, true)?);
+        }
 
-        Ok(Self {
-            sockets,
-            stats: Arc::new(RwLock::new(TransferStats::new())),
+        info!(
+            "🚀 UDP 전송 코어 초기화: 포트 {}에 {} 샤드 (SO_REUSEPORT)",
+            port, count
+        );
+        Ok(Self::with_sockets(sockets))
+    }
+
+    fn with_sockets(sockets: Vec<UdpSocket>) -> Self {
+        Self {
+            socket_stats: sockets.iter().map(|_| Arc::default()).collect(),
+            sockets: sockets.into_iter().map(Arc::new).collect(),
+            packets_lost: AtomicU64::new(0),
+            start_time: Instant::now(),
             send_semaphore: Arc::new(Semaphore::new(1000)),
-        })
+            pacer: Arc::new(Pacer::unlimited()),
+        }
+    }
+
+    /// 앱 전체 처리량 지표 카운터 설정 (송수신 시작 전에 호출)
+    pub fn with_metrics(mut self, counter: Arc<SubsystemCounter>) -> Self {
+        self.socket_stats = self
+            .sockets
+            .iter()
+            .map(|_| {
+                Arc::new(SocketStats {
+                    metrics: Some(counter.clone()),
+                    ..Default::default()
+                })
+            })
+            .collect();
+        self
     }
 
     pub async fn send_chunk(
@@ -165,11 +351,12 @@ impl UdpTransferCore {
         packet.extend_from_slice(data);
 
         let _permit = self.send_semaphore.acquire().await?;
-        socket.send_to(&packet, target).await?;
-
-        let mut stats = self.stats.write().await;
-        stats.bytes_sent += packet.len() as u64;
-        stats.packets_sent += 1;
+        self.pacer.pace(packet.len()).await;
+        if let Err(e) = socket.send_to(&packet, target).await {
+            self.socket_stats[socket_idx].record_send_error();
+            return Err(e.into());
+        }
+        self.socket_stats[socket_idx].record_sent(packet.len());
 
         Ok(())
     }
@@ -201,9 +388,11 @@ impl UdpTransferCore {
         let mut handles = Vec::with_capacity(total_chunks);
 
         for (header, chunk_data) in chunks {
-            let socket = self.sockets[header.chunk_index as usize % self.sockets.len()].clone();
-            let stats = self.stats.clone();
+            let socket_idx = header.chunk_index as usize % self.sockets.len();
+            let socket = self.sockets[socket_idx].clone();
+            let stats = self.socket_stats[socket_idx].clone();
             let semaphore = self.send_semaphore.clone();
+            let pacer = self.pacer.clone();
 
             let handle = tauri::async_runtime::spawn(async move {
                 let mut packet = BytesMut::with_capacity(CHUNK_HEADER_SIZE + chunk_data.len());
@@ -211,14 +400,14 @@ impl UdpTransferCore {
                 packet.extend_from_slice(&chunk_data);
 
                 let _permit = semaphore.acquire().await.unwrap();
+                pacer.pace(packet.len()).await;
                 if let Err(e) = socket.send_to(&packet, target).await {
                     warn!("청크 전송 실패: {}", e);
+                    stats.record_send_error();
                     return 0u64;
                 }
 
-                let mut s = stats.write().await;
-                s.bytes_sent += packet.len() as u64;
-                s.packets_sent += 1;
+                stats.record_sent(packet.len());
 
                 packet.len() as u64
             });
@@ -238,50 +427,176 @@ impl UdpTransferCore {
         Ok(total_sent)
     }
 
+    /// 소켓별 송신 워커 시작 (소켓마다 별도 태스크가 대기열을 비움)
+    ///
+    /// 패킷은 페이서를 거쳐 전송되며, 반환된 `SendWorkers`를 모두 drop하면 워커가 종료됩니다.
+    pub fn start_send_workers(&self) -> SendWorkers {
+        let queues = self
+            .sockets
+            .iter()
+            .zip(&self.socket_stats)
+            .map(|(socket, stats)| {
+                let (tx, mut rx) = mpsc::channel::<(SocketAddr, Bytes)>(SEND_QUEUE);
+                let socket = socket.clone();
+                let stats = stats.clone();
+                let pacer = self.pacer.clone();
+
+                tauri::async_runtime::spawn(async move {
+                    while let Some((target, packet)) = rx.recv().await {
+                        pacer.pace(packet.len()).await;
+                        match socket.send_to(&packet, target).await {
+                            Ok(_) => stats.record_sent(packet.len()),
+                            Err(e) => {
+                                debug!("UDP 전송 실패: {}", e);
+                                stats.record_send_error();
+                            }
+                        }
+                    }
+                });
+
+                tx
+            })
+            .collect();
+
+        SendWorkers { queues }
+    }
+
     pub fn start_receiver(
         &self,
         socket_idx: usize,
     ) -> mpsc::Receiver<(ChunkHeader, Bytes, SocketAddr)> {
         let (tx, rx) = mpsc::channel(10000);
+        let idx = socket_idx % self.sockets.len();
+        Self::spawn_chunk_receiver(
+            self.sockets[idx].clone(),
+            self.socket_stats[idx].clone(),
+            tx,
+        );
+        rx
+    }
+
+    /// 모든 소켓의 청크를 하나의 채널로 수신 (소켓마다 수신 워커 하나)
+    pub fn start_receivers(&self) -> mpsc::Receiver<(ChunkHeader, Bytes, SocketAddr)> {
+        let (tx, rx) = mpsc::channel(10000);
+        for (socket, stats) in self.sockets.iter().zip(&self.socket_stats) {
+            Self::spawn_chunk_receiver(socket.clone(), stats.clone(), tx.clone());
+        }
+        rx
+    }
+
+    /// 청크 헤더 없이 원본 데이터그램 수신 (ACK 등 제어 패킷용)
+    pub fn start_raw_receiver(&self, socket_idx: usize) -> mpsc::Receiver<(Bytes, SocketAddr)> {
+        let (tx, rx) = mpsc::channel(1024);
         let socket = self.sockets[socket_idx % self.sockets.len()].clone();
-        let stats = self.stats.clone();
 
         tauri::async_runtime::spawn(async move {
             let mut buf = vec![0u8; UDP_PAYLOAD_SIZE];
 
             loop {
-                match socket.recv_from(&mut buf).await {
-                    Ok((len, addr)) => {
-                        if len < CHUNK_HEADER_SIZE {
-                            continue;
+                tokio::select! {
+                    _ = tx.closed() => break,
+                    result = socket.recv_from(&mut buf) => match result {
+                        Ok((len, addr)) => {
+                            if tx.send((Bytes::copy_from_slice(&buf[..len]), addr)).await.is_err() {
+                                break;
+                            }
+                        }
+                        Err(e) => {
+                            warn!("UDP 수신 오류: {}", e);
                         }
+                    },
+                }
+            }
+        });
 
-                        if let Some(header) = ChunkHeader::decode(&buf[..CHUNK_HEADER_SIZE]) {
-                            let data = Bytes::copy_from_slice(&buf[CHUNK_HEADER_SIZE..len]);
+        rx
+    }
 
-                            let mut s = stats.write().await;
-                            s.bytes_received += len as u64;
-                            s.packets_received += 1;
+    fn spawn_chunk_receiver(
+        socket: Arc<UdpSocket>,
+        stats: Arc<SocketStats>,
+        tx: mpsc::Sender<(ChunkHeader, Bytes, SocketAddr)>,
+    ) {
+        tauri::async_runtime::spawn(async move {
+            let mut buf = vec![0u8; UDP_PAYLOAD_SIZE];
 
-                            if tx.send((header, data, addr)).await.is_err() {
-                                break;
-                            }
+            loop {
+                // 수신측이 채널을 닫으면 소켓을 놓아줌
+                let (len, addr) = tokio::select! {
+                    _ = tx.closed() => break,
+                    result = socket.recv_from(&mut buf) => match result {
+                        Ok(received) => received,
+                        Err(e) => {
+                            warn!("UDP 수신 오류: {}", e);
+                            continue;
                         }
+                    },
+                };
+
+                if len < CHUNK_HEADER_SIZE {
+                    stats.record_dropped();
+                    continue;
+                }
+
+                if let Some(header) = ChunkHeader::decode(&buf[..CHUNK_HEADER_SIZE]) {
+                    let data = Bytes::copy_from_slice(&buf[CHUNK_HEADER_SIZE..len]);
+                    stats.record_received(len);
+
+                    if tx.send((header, data, addr)).await.is_err() {
+                        break;
                     }
-                    Err(e) => {
-                        warn!("UDP 수신 오류: {}", e);
-                    }
+                } else {
+                    stats.record_dropped();
                 }
             }
         });
+    }
 
-        rx
+    /// 청크 헤더 없이 원본 데이터그램 전송 (ACK 등 제어 패킷용)
+    pub async fn send_raw(
+        &self,
+        socket_idx: usize,
+        packet: &[u8],
+        target: SocketAddr,
+    ) -> Result<()> {
+        let socket = &self.sockets[socket_idx % self.sockets.len()];
+        socket.send_to(packet, target).await?;
+        Ok(())
+    }
+
+    /// 재전송한(유실로 판단된) 패킷 수 기록
+    pub fn record_lost(&self, packets: u64) {
+        self.packets_lost.fetch_add(packets, Ordering::Relaxed);
+    }
+
+    /// 송신 목표 속도 설정 (bytes/sec, 0 = 페이싱 없음)
+    pub fn set_pacing_rate(&self, rate_bps: u64) {
+        self.pacer.set_rate(rate_bps);
+    }
+
+    /// 현재 송신 목표 속도 (bytes/sec, 0 = 페이싱 없음)
+    pub fn pacing_rate(&self) -> u64 {
+        self.pacer.rate()
     }
 
+    /// 모든 소켓 통계 합산
     pub async fn get_stats(&self) -> TransferStats {
-        let mut stats = self.stats.write().await;
+        let mut stats = TransferStats::new();
+        stats.start_time = self.start_time;
+        stats.packets_lost = self.packets_lost.load(Ordering::Relaxed);
+        for socket_stats in self.socket_stats() {
+            stats.bytes_sent += socket_stats.bytes_sent;
+            stats.bytes_received += socket_stats.bytes_received;
+            stats.packets_sent += socket_stats.packets_sent;
+            stats.packets_received += socket_stats.packets_received;
+        }
         stats.calculate_bandwidth();
-        stats.clone()
+        stats
+    }
+
+    /// 소켓(샤드)별 통계
+    pub fn socket_stats(&self) -> Vec<SocketStatsSnapshot> {
+        self.socket_stats.iter().map(|s| s.snapshot()).collect()
     }
 
     pub fn socket_count(&self) -> usize {
@@ -298,3 +613,65 @@ impl UdpTransferCore {
         addrs
     }
 }
+
+/// 소켓별 송신 워커 대기열
+#[derive(Clone)]
+pub struct SendWorkers {
+    queues: Vec<mpsc::Sender<(SocketAddr, Bytes)>>,
+}
+
+impl SendWorkers {
+    /// `shard % 소켓 수` 번째 워커에 패킷 전달 (대기열이 차면 대기)
+    pub async fn send(&self, shard: usize, target: SocketAddr, packet: Bytes) -> Result<()> {
+        self.queues[shard % self.queues.len()]
+            .send((target, packet))
+            .await
+            .map_err(|_| anyhow::anyhow!("UDP 송신 워커 종료"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_sharded_sockets_share_port() {
+        let core = UdpTransferCore::new_sharded(0, Some(4)).await.unwrap();
+        let addrs = core.get_local_addrs().await;
+
+        let expected = if cfg!(unix) { 4 } else { 1 };
+        assert_eq!(addrs.len(), expected);
+        assert!(addrs.iter().all(|addr| addr.port() == addrs[0].port()));
+        assert_eq!(core.socket_stats().len(), expected);
+    }
+
+    #[test]
+    fn test_socket_rate_and_drops() {
+        let stats = SocketStats::default();
+        let start = Instant::now();
+        assert_eq!(stats.snapshot_at(start).send_rate_bps, 0);
+
+        for _ in 0..4 {
+            stats.record_sent(1000);
+        }
+        stats.record_received(500);
+        stats.record_send_error();
+        stats.record_dropped();
+
+        // 간격이 짧으면 직전 속도 유지
+        let early = stats.snapshot_at(start + Duration::from_millis(100));
+        assert_eq!(early.send_rate_bps, 0);
+
+        let snapshot = stats.snapshot_at(start + Duration::from_secs(2));
+        assert_eq!(snapshot.packets_sent, 4);
+        assert_eq!(snapshot.send_rate_bps, 2000);
+        assert_eq!(snapshot.receive_rate_bps, 250);
+        assert_eq!(snapshot.send_errors, 1);
+        assert_eq!(snapshot.packets_dropped, 1);
+
+        // 트래픽이 없으면 속도 0
+        let idle = stats.snapshot_at(start + Duration::from_secs(4));
+        assert_eq!(idle.send_rate_bps, 0);
+        assert_eq!(idle.bytes_sent, 4000);
+    }
+}
@@ -0,0 +1,334 @@
+//! 웹 버전 PonsWarp 데이터 채널 프로토콜
+//!
+//! 브라우저의 `SwarmManager`/`webRTCService`가 쓰는 메시지 형식을 그대로 옮긴 것입니다.
+//! 제어 메시지는 `{"type": "MANIFEST", ...}` 같은 JSON 텍스트이고, 데이터는 22바이트 헤더
+//! (파일 인덱스 u16, 청크 인덱스 u32, 오프셋 u64, 길이 u32, CRC32 u32, 모두 리틀 엔디언) 뒤에
+//! 청크가 붙은 바이너리 패킷입니다. 파일 인덱스가 `0xffff`인 헤더만 있는 패킷이 전송 끝(EOS)입니다.
+//!
+//! 흐름: 송신 `MANIFEST` → 수신 `TRANSFER_READY` → 송신 `TRANSFER_STARTED` + 청크 + EOS →
+//! 수신 `DOWNLOAD_COMPLETE`. 브라우저 수신자는 여러 파일을 하나의 zip 스트림으로 받으므로 오프셋은
+//! 항상 출력 파일 하나 기준입니다.
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+/// 패킷 헤더 크기
+pub const HEADER_SIZE: usize = 22;
+
+/// 송신 청크 크기 (브라우저 한계 128KB 이내)
+pub const CHUNK_SIZE: usize = 64 * 1024;
+
+/// 전송 끝(EOS) 표시 파일 인덱스
+pub const EOS_FILE_INDEX: u16 = 0xffff;
+
+/// 청크 인덱스 계산 기준 (브라우저 `CHUNK_SIZE_MAX`)
+const CHUNK_INDEX_UNIT: u64 = 128 * 1024;
+
+/// 브라우저 `TransferManifest`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebManifest {
+    pub transfer_id: String,
+    pub total_size: u64,
+    pub total_files: u32,
+    pub root_name: String,
+    pub files: Vec<WebFileNode>,
+    #[serde(default)]
+    pub is_folder: bool,
+    #[serde(default)]
+    pub is_size_estimated: bool,
+    #[serde(default)]
+    pub is_zip_stream: bool,
+}
+
+/// 브라우저 `FileNode`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebFileNode {
+    pub id: u32,
+    pub name: String,
+    pub path: String,
+    pub size: u64,
+    #[serde(rename = "type", default)]
+    pub mime_type: String,
+    #[serde(default)]
+    pub last_modified: u64,
+}
+
+impl WebManifest {
+    /// 파일 하나짜리 매니페스트
+    pub fn single(transfer_id: &str, name: &str, size: u64, last_modified: u64) -> Self {
+        Self {
+            transfer_id: transfer_id.to_string(),
+            total_size: size,
+            total_files: 1,
+            root_name: name.to_string(),
+            files: vec![WebFileNode {
+                id: 0,
+                name: name.to_string(),
+                path: name.to_string(),
+                size,
+                mime_type: "application/octet-stream".to_string(),
+                last_modified,
+            }],
+            is_folder: false,
+            is_size_estimated: false,
+            is_zip_stream: false,
+        }
+    }
+
+    /// 수신 측 출력 파일명 (브라우저 `DirectFileWriter`와 같은 규칙)
+    ///
+    /// 파일 하나면 원본 이름, 여러 개면 `<rootName>.zip`.
+    pub fn output_name(&self) -> String {
+        match self.files.as_slice() {
+            [file] if self.total_files <= 1 => file
+                .path
+                .rsplit(['/', '\\'])
+                .next()
+                .unwrap_or(&file.name)
+                .to_string(),
+            _ if self.root_name.is_empty() => "download.zip".to_string(),
+            _ => format!("{}.zip", self.root_name),
+        }
+    }
+}
+
+/// JSON 제어 메시지
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ControlMessage {
+    Manifest {
+        manifest: WebManifest,
+    },
+    TransferReady,
+    TransferStarted,
+    DownloadComplete,
+    KeepAlive,
+    /// 대기열/제어 등 데스크톱이 쓰지 않는 메시지
+    #[serde(other)]
+    Unknown,
+}
+
+impl ControlMessage {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+/// 바이너리 메시지가 JSON 제어 메시지인지 (브라우저도 첫 바이트 `{`로 구분)
+pub fn is_control(data: &[u8]) -> bool {
+    data.first() == Some(&b'{')
+}
+
+/// 수신한 데이터 패킷
+#[derive(Debug, PartialEq, Eq)]
+pub enum Packet<'a> {
+    Chunk {
+        file_index: u16,
+        offset: u64,
+        data: &'a [u8],
+    },
+    Eos,
+}
+
+/// 청크를 패킷으로 인코딩
+pub fn encode_packet(file_index: u16, offset: u64, data: &[u8]) -> Vec<u8> {
+    let mut packet = Vec::with_capacity(HEADER_SIZE + data.len());
+    packet.extend_from_slice(&file_index.to_le_bytes());
+    packet.extend_from_slice(&((offset / CHUNK_INDEX_UNIT) as u32).to_le_bytes());
+    packet.extend_from_slice(&offset.to_le_bytes());
+    packet.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    packet.extend_from_slice(&crc32fast::hash(data).to_le_bytes());
+    packet.extend_from_slice(data);
+    packet
+}
+
+/// 전송 끝 패킷
+pub fn eos_packet() -> Vec<u8> {
+    let mut packet = vec![0u8; HEADER_SIZE];
+    packet[..2].copy_from_slice(&EOS_FILE_INDEX.to_le_bytes());
+    packet
+}
+
+/// 패킷 디코딩 (길이/CRC32 검증)
+pub fn decode_packet(packet: &[u8]) -> Result<Packet<'_>> {
+    if packet.len() < HEADER_SIZE {
+        bail!("패킷이 너무 짧습니다: {} bytes", packet.len());
+    }
+    let file_index = u16::from_le_bytes([packet[0], packet[1]]);
+    if file_index == EOS_FILE_INDEX {
+        return Ok(Packet::Eos);
+    }
+
+    let offset = u64::from_le_bytes(packet[6..14].try_into()?);
+    let len = u32::from_le_bytes(packet[14..18].try_into()?) as usize;
+    let checksum = u32::from_le_bytes(packet[18..22].try_into()?);
+    let data = &packet[HEADER_SIZE..];
+    if data.len() != len {
+        bail!("패킷 길이 불일치: 헤더 {} / 실제 {}", len, data.len());
+    }
+    if crc32fast::hash(data) != checksum {
+        bail!("청크 체크섬 불일치 (offset {})", offset);
+    }
+
+    Ok(Packet::Chunk {
+        file_index,
+        offset,
+        data,
+    })
+}
+
+/// 시그널링 메시지 (simple-peer `signal` 데이터와 같은 형태)
+///
+/// 프론트엔드가 기존 시그널링 서버로 그대로 중계합니다.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum WebRtcSignal {
+    Offer { sdp: String },
+    Answer { sdp: String },
+    Candidate { candidate: IceCandidateSignal },
+}
+
+/// 브라우저 `RTCIceCandidateInit`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IceCandidateSignal {
+    pub candidate: String,
+    #[serde(default)]
+    pub sdp_mid: Option<String>,
+    #[serde(rename = "sdpMLineIndex", default)]
+    pub sdp_mline_index: Option<u16>,
+    #[serde(default)]
+    pub username_fragment: Option<String>,
+}
+
+/// ICE 서버 (브라우저 `RTCIceServer`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct IceServer {
+    pub urls: Vec<String>,
+    pub username: String,
+    pub credential: String,
+}
+
+/// TURN 서버 (`host:port`)와 자격 증명
+pub struct TurnServer<'a> {
+    pub server_url: &'a str,
+    pub tls: bool,
+    pub username: String,
+    pub credential: String,
+}
+
+/// STUN 서버 목록과 TURN 설정으로 ICE 서버 구성
+pub fn ice_servers(stun_servers: &[String], turn: Option<TurnServer<'_>>) -> Vec<IceServer> {
+    let mut servers = Vec::new();
+    if !stun_servers.is_empty() {
+        servers.push(IceServer {
+            urls: stun_servers
+                .iter()
+                .map(|server| format!("stun:{}", server))
+                .collect(),
+            username: String::new(),
+            credential: String::new(),
+        });
+    }
+    if let Some(turn) = turn {
+        let scheme = if turn.tls { "turns" } else { "turn" };
+        servers.push(IceServer {
+            urls: vec![format!("{}:{}", scheme, turn.server_url)],
+            username: turn.username,
+            credential: turn.credential,
+        });
+    }
+    servers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_packet_roundtrip() {
+        let packet = encode_packet(0, 256 * 1024, b"hello");
+        assert_eq!(packet.len(), HEADER_SIZE + 5);
+        // 청크 인덱스는 브라우저와 같이 128KB 단위
+        assert_eq!(&packet[2..6], &2u32.to_le_bytes());
+        assert_eq!(
+            decode_packet(&packet).unwrap(),
+            Packet::Chunk {
+                file_index: 0,
+                offset: 256 * 1024,
+                data: b"hello"
+            }
+        );
+
+        let mut corrupt = packet.clone();
+        corrupt[HEADER_SIZE] ^= 0xff;
+        assert!(decode_packet(&corrupt).is_err());
+        assert!(decode_packet(&packet[..HEADER_SIZE + 2]).is_err());
+
+        assert_eq!(decode_packet(&eos_packet()).unwrap(), Packet::Eos);
+        assert!(!is_control(&packet));
+    }
+
+    #[test]
+    fn test_control_messages_match_browser() {
+        let manifest = WebManifest::single("job", "a.bin", 10, 1_700_000_000_000);
+        let json = ControlMessage::Manifest {
+            manifest: manifest.clone(),
+        }
+        .to_json();
+        assert!(is_control(json.as_bytes()));
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["type"], "MANIFEST");
+        assert_eq!(value["manifest"]["totalSize"], 10);
+        assert_eq!(
+            value["manifest"]["files"][0]["type"],
+            "application/octet-stream"
+        );
+
+        assert_eq!(
+            ControlMessage::TransferReady.to_json(),
+            r#"{"type":"TRANSFER_READY"}"#
+        );
+        let queued: ControlMessage =
+            serde_json::from_str(r#"{"type":"QUEUED","position":2}"#).unwrap();
+        assert_eq!(queued, ControlMessage::Unknown);
+
+        // 브라우저 매니페스트 (여러 파일 → zip 하나)
+        let browser: WebManifest = serde_json::from_str(
+            r#"{"transferId":"t","totalSize":30,"totalFiles":2,"rootName":"photos",
+                "files":[{"id":0,"name":"a.jpg","path":"photos/a.jpg","size":10,"type":"image/jpeg","lastModified":0},
+                         {"id":1,"name":"b.jpg","path":"photos/b.jpg","size":20,"type":"image/jpeg","lastModified":0}],
+                "isFolder":true,"isZipStream":true}"#,
+        )
+        .unwrap();
+        assert_eq!(browser.output_name(), "photos.zip");
+        assert_eq!(manifest.output_name(), "a.bin");
+    }
+
+    #[test]
+    fn test_signals_and_ice_servers() {
+        let signal: WebRtcSignal = serde_json::from_str(
+            r#"{"type":"candidate","candidate":{"candidate":"candidate:1 1 udp 1 10.0.0.2 5000 typ host","sdpMid":"0","sdpMLineIndex":0}}"#,
+        )
+        .unwrap();
+        let WebRtcSignal::Candidate { candidate } = signal else {
+            panic!("candidate 시그널이어야 합니다");
+        };
+        assert_eq!(candidate.sdp_mline_index, Some(0));
+
+        let servers = ice_servers(
+            &["stun.l.google.com:19302".to_string()],
+            Some(TurnServer {
+                server_url: "turn.example.com:5349",
+                tls: true,
+                username: "user".to_string(),
+                credential: "pass".to_string(),
+            }),
+        );
+        assert_eq!(servers[0].urls, vec!["stun:stun.l.google.com:19302"]);
+        assert_eq!(servers[1].urls, vec!["turns:turn.example.com:5349"]);
+        assert!(ice_servers(&[], None).is_empty());
+    }
+}
@@ -0,0 +1,679 @@
+//! 웹 버전과의 WebRTC 데이터 채널 전송
+//!
+//! 상대가 브라우저(웹 버전 PonsWarp)라 QUIC을 쓸 수 없을 때 webrtc-rs 데이터 채널로 파일을
+//! 주고받습니다. 메시지 형식은 [`super::web_protocol`]을 따르고, 시그널링(offer/answer/candidate)은
+//! `webrtc-signal` 이벤트로 프론트엔드에 넘겨 기존 시그널링 서버로 중계합니다.
+//! ICE 서버는 네트워크 진단과 같은 STUN 서버와 설정된 TURN 서버를 씁니다.
+//!
+//! 세션 ID는 레지스트리 작업 ID(`webrtc-<uuid>`)와 같아서 진행률/완료 이벤트, 일시정지/취소가
+//! 다른 전송과 똑같이 동작합니다. 수신은 한 번에 출력 파일 하나(여러 파일이면 브라우저가 만든 zip)를
+//! 저장 폴더의 `.pswp-part` 임시 파일에 쓴 뒤 완료 시 이름을 바꿉니다.
+
+use super::file_transfer::{resolve_destination, safe_destination, OverwritePolicy, ReceiveAction};
+use super::part_file;
+use super::registry::{JobControl, TransferKind, TransferRegistry};
+use super::web_protocol::{
+    decode_packet, encode_packet, eos_packet, is_control, ControlMessage, IceCandidateSignal,
+    IceServer, Packet, WebManifest, WebRtcSignal, CHUNK_SIZE,
+};
+use crate::error::AppError;
+use anyhow::{anyhow, bail, Result};
+use bytes::Bytes;
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::SeekFrom;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, UNIX_EPOCH};
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use tokio::sync::{mpsc, Notify};
+use tracing::{info, warn};
+use webrtc::api::APIBuilder;
+use webrtc::data_channel::data_channel_init::RTCDataChannelInit;
+use webrtc::data_channel::data_channel_message::DataChannelMessage;
+use webrtc::data_channel::RTCDataChannel;
+use webrtc::ice_transport::ice_candidate::{RTCIceCandidate, RTCIceCandidateInit};
+use webrtc::ice_transport::ice_server::RTCIceServer;
+use webrtc::peer_connection::configuration::RTCConfiguration;
+use webrtc::peer_connection::peer_connection_state::RTCPeerConnectionState;
+use webrtc::peer_connection::sdp::session_description::RTCSessionDescription;
+use webrtc::peer_connection::RTCPeerConnection;
+
+/// 브라우저와 같은 데이터 채널 이름
+const CHANNEL_LABEL: &str = "data";
+
+/// 연결(데이터 채널 열림) 대기 시간
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// 송신 버퍼 상한/재개 기준 (브라우저 `HIGH_WATER_MARK`/`LOW_WATER_MARK`)
+const HIGH_WATER_MARK: usize = 12 * 1024 * 1024;
+const LOW_WATER_MARK: usize = 4 * 1024 * 1024;
+
+/// 송신 버퍼가 줄지 않을 때 연결 끊김으로 보는 시간
+const STALL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// EOS 후 `DOWNLOAD_COMPLETE` 대기 시간
+const COMPLETE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// 프론트엔드로 보낼 시그널링 메시지
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebRtcSignalEvent {
+    pub session_id: String,
+    pub signal: WebRtcSignal,
+}
+
+/// 데이터 채널/연결 이벤트
+enum ChannelEvent {
+    Open(Arc<RTCDataChannel>),
+    Message(Bytes),
+    Closed,
+}
+
+struct Session {
+    peer_connection: Arc<RTCPeerConnection>,
+    control: JobControl,
+    /// 원격 SDP 적용 여부 (그 전에 온 candidate는 보관)
+    remote_described: AtomicBool,
+    pending_candidates: Mutex<Vec<IceCandidateSignal>>,
+}
+
+type Sessions = Arc<Mutex<HashMap<String, Arc<Session>>>>;
+
+/// WebRTC 데이터 채널 세션 관리
+pub struct WebRtcInterop {
+    registry: Arc<TransferRegistry>,
+    sessions: Sessions,
+    signals: Option<mpsc::UnboundedSender<WebRtcSignalEvent>>,
+}
+
+impl WebRtcInterop {
+    pub fn new(registry: Arc<TransferRegistry>) -> Self {
+        Self {
+            registry,
+            sessions: Arc::new(Mutex::new(HashMap::new())),
+            signals: None,
+        }
+    }
+
+    /// 시그널링 메시지 채널 연결
+    pub fn with_signal_channel(mut self, tx: mpsc::UnboundedSender<WebRtcSignalEvent>) -> Self {
+        self.signals = Some(tx);
+        self
+    }
+
+    /// 브라우저 피어에게 파일 전송 (offer를 만들어 시그널로 보냄)
+    pub async fn send_file(
+        &self,
+        peer_id: &str,
+        path: PathBuf,
+        ice_servers: Vec<IceServer>,
+    ) -> Result<String> {
+        let metadata = tokio::fs::metadata(&path).await?;
+        if !metadata.is_file() {
+            bail!("파일이 아닙니다: {}", path.display());
+        }
+
+        let session_id = format!("webrtc-{}", uuid::Uuid::new_v4().simple());
+        let control = self
+            .registry
+            .register(&session_id, peer_id, TransferKind::File)?;
+        self.registry
+            .update_progress(&session_id, 0, metadata.len(), 0);
+
+        let (events_tx, events_rx) = mpsc::unbounded_channel();
+        let opened = async {
+            let peer_connection = self
+                .new_peer_connection(&session_id, ice_servers, events_tx.clone())
+                .await?;
+            let channel = peer_connection
+                .create_data_channel(
+                    CHANNEL_LABEL,
+                    Some(RTCDataChannelInit {
+                        ordered: Some(true),
+                        ..Default::default()
+                    }),
+                )
+                .await?;
+            attach_channel(&channel, events_tx);
+
+            let offer = peer_connection.create_offer(None).await?;
+            peer_connection.set_local_description(offer.clone()).await?;
+            anyhow::Ok((peer_connection, offer.sdp))
+        }
+        .await;
+        let (peer_connection, sdp) = match opened {
+            Ok(opened) => opened,
+            Err(e) => {
+                self.registry.finish(
+                    &session_id,
+                    Err(AppError::Network(format!("WebRTC 연결 준비 실패: {}", e))),
+                );
+                return Err(e);
+            }
+        };
+
+        self.insert_session(&session_id, peer_connection, control.clone());
+        self.emit_signal(&session_id, WebRtcSignal::Offer { sdp });
+
+        let registry = self.registry.clone();
+        let sessions = self.sessions.clone();
+        let job_id = session_id.clone();
+        tokio::spawn(async move {
+            info!("🌐 WebRTC 송신 대기: {} ({})", path.display(), job_id);
+            let result = send_over_channel(events_rx, &registry, &job_id, &control, &path).await;
+            if result.is_ok() {
+                info!("🌐 WebRTC 송신 완료: {}", path.display());
+            }
+            close_session(&sessions, &job_id).await;
+            registry.finish(
+                &job_id,
+                result.map_err(|e| AppError::Network(format!("WebRTC 전송 실패: {}", e))),
+            );
+        });
+
+        Ok(session_id)
+    }
+
+    /// 브라우저 피어로부터 수신 대기 (상대의 offer를 [`Self::signal`]로 전달받음)
+    pub async fn receive(
+        &self,
+        peer_id: &str,
+        save_dir: PathBuf,
+        overwrite_policy: OverwritePolicy,
+        ice_servers: Vec<IceServer>,
+    ) -> Result<String> {
+        let session_id = format!("webrtc-{}", uuid::Uuid::new_v4().simple());
+        let control = self
+            .registry
+            .register(&session_id, peer_id, TransferKind::File)?;
+
+        let (events_tx, events_rx) = mpsc::unbounded_channel();
+        let peer_connection = match self
+            .new_peer_connection(&session_id, ice_servers, events_tx.clone())
+            .await
+        {
+            Ok(peer_connection) => peer_connection,
+            Err(e) => {
+                self.registry.finish(
+                    &session_id,
+                    Err(AppError::Network(format!("WebRTC 연결 준비 실패: {}", e))),
+                );
+                return Err(e);
+            }
+        };
+        peer_connection.on_data_channel(Box::new(move |channel: Arc<RTCDataChannel>| {
+            attach_channel(&channel, events_tx.clone());
+            Box::pin(async {})
+        }));
+        self.insert_session(&session_id, peer_connection, control.clone());
+
+        let registry = self.registry.clone();
+        let sessions = self.sessions.clone();
+        let job_id = session_id.clone();
+        tokio::spawn(async move {
+            let result = receive_over_channel(
+                events_rx,
+                &registry,
+                &job_id,
+                &control,
+                &save_dir,
+                overwrite_policy,
+            )
+            .await;
+            close_session(&sessions, &job_id).await;
+            match result {
+                Ok((path, action)) => {
+                    info!("🌐 WebRTC 수신 완료: {}", path.display());
+                    registry.set_action(&job_id, action.as_str());
+                    registry.finish(&job_id, Ok(()));
+                }
+                Err(e) => registry.finish(
+                    &job_id,
+                    Err(AppError::Network(format!("WebRTC 수신 실패: {}", e))),
+                ),
+            }
+        });
+
+        Ok(session_id)
+    }
+
+    /// 시그널링 서버로 받은 상대 메시지 적용 (offer를 받으면 answer를 시그널로 보냄)
+    pub async fn signal(&self, session_id: &str, signal: WebRtcSignal) -> Result<()> {
+        let session = self
+            .sessions
+            .lock()
+            .get(session_id)
+            .cloned()
+            .ok_or_else(|| anyhow!("WebRTC 세션을 찾을 수 없습니다: {}", session_id))?;
+        let peer_connection = &session.peer_connection;
+
+        match signal {
+            WebRtcSignal::Offer { sdp } => {
+                peer_connection
+                    .set_remote_description(RTCSessionDescription::offer(sdp)?)
+                    .await?;
+                flush_candidates(&session).await;
+                let answer = peer_connection.create_answer(None).await?;
+                peer_connection
+                    .set_local_description(answer.clone())
+                    .await?;
+                self.emit_signal(session_id, WebRtcSignal::Answer { sdp: answer.sdp });
+            }
+            WebRtcSignal::Answer { sdp } => {
+                peer_connection
+                    .set_remote_description(RTCSessionDescription::answer(sdp)?)
+                    .await?;
+                flush_candidates(&session).await;
+            }
+            WebRtcSignal::Candidate { candidate } => {
+                {
+                    let mut pending = session.pending_candidates.lock();
+                    if !session.remote_described.load(Ordering::SeqCst) {
+                        pending.push(candidate);
+                        return Ok(());
+                    }
+                }
+                peer_connection
+                    .add_ice_candidate(to_candidate_init(candidate))
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 세션 종료 (진행 중이면 취소로 기록)
+    pub fn close(&self, session_id: &str) -> bool {
+        match self.sessions.lock().get(session_id) {
+            Some(session) => {
+                session.control.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 모든 세션 종료
+    pub fn close_all(&self) {
+        for session in self.sessions.lock().values() {
+            session.control.cancel();
+        }
+    }
+
+    async fn new_peer_connection(
+        &self,
+        session_id: &str,
+        ice_servers: Vec<IceServer>,
+        events: mpsc::UnboundedSender<ChannelEvent>,
+    ) -> Result<Arc<RTCPeerConnection>> {
+        let config = RTCConfiguration {
+            ice_servers: ice_servers
+                .into_iter()
+                .map(|server| RTCIceServer {
+                    urls: server.urls,
+                    username: server.username,
+                    credential: server.credential,
+                })
+                .collect(),
+            ..Default::default()
+        };
+        let peer_connection = Arc::new(
+            APIBuilder::new()
+                .build()
+                .new_peer_connection(config)
+                .await?,
+        );
+
+        let signals = self.signals.clone();
+        let id = session_id.to_string();
+        peer_connection.on_ice_candidate(Box::new(move |candidate: Option<RTCIceCandidate>| {
+            if let (Some(candidate), Some(tx)) = (candidate, &signals) {
+                match candidate.to_json() {
+                    Ok(init) => {
+                        let _ = tx.send(WebRtcSignalEvent {
+                            session_id: id.clone(),
+                            signal: WebRtcSignal::Candidate {
+                                candidate: IceCandidateSignal {
+                                    candidate: init.candidate,
+                                    sdp_mid: init.sdp_mid,
+                                    sdp_mline_index: init.sdp_mline_index,
+                                    username_fragment: init.username_fragment,
+                                },
+                            },
+                        });
+                    }
+                    Err(e) => warn!("ICE 후보 직렬화 실패: {}", e),
+                }
+            }
+            Box::pin(async {})
+        }));
+
+        let id = session_id.to_string();
+        peer_connection.on_peer_connection_state_change(Box::new(
+            move |state: RTCPeerConnectionState| {
+                info!("🌐 WebRTC 연결 상태 ({}): {}", id, state);
+                if matches!(
+                    state,
+                    RTCPeerConnectionState::Failed | RTCPeerConnectionState::Closed
+                ) {
+                    let _ = events.send(ChannelEvent::Closed);
+                }
+                Box::pin(async {})
+            },
+        ));
+
+        Ok(peer_connection)
+    }
+
+    fn insert_session(
+        &self,
+        session_id: &str,
+        peer_connection: Arc<RTCPeerConnection>,
+        control: JobControl,
+    ) {
+        self.sessions.lock().insert(
+            session_id.to_string(),
+            Arc::new(Session {
+                peer_connection,
+                control,
+                remote_described: AtomicBool::new(false),
+                pending_candidates: Mutex::new(Vec::new()),
+            }),
+        );
+    }
+
+    fn emit_signal(&self, session_id: &str, signal: WebRtcSignal) {
+        if let Some(ref tx) = self.signals {
+            let _ = tx.send(WebRtcSignalEvent {
+                session_id: session_id.to_string(),
+                signal,
+            });
+        }
+    }
+}
+
+/// 데이터 채널 이벤트를 세션 이벤트 큐로 연결
+fn attach_channel(channel: &Arc<RTCDataChannel>, events: mpsc::UnboundedSender<ChannelEvent>) {
+    let weak = Arc::downgrade(channel);
+    let tx = events.clone();
+    channel.on_open(Box::new(move || {
+        if let Some(channel) = weak.upgrade() {
+            let _ = tx.send(ChannelEvent::Open(channel));
+        }
+        Box::pin(async {})
+    }));
+
+    let tx = events.clone();
+    channel.on_message(Box::new(move |message: DataChannelMessage| {
+        let _ = tx.send(ChannelEvent::Message(message.data));
+        Box::pin(async {})
+    }));
+
+    channel.on_close(Box::new(move || {
+        let _ = events.send(ChannelEvent::Closed);
+        Box::pin(async {})
+    }));
+}
+
+/// 원격 SDP 적용 후 보관해 둔 candidate 추가
+async fn flush_candidates(session: &Session) {
+    let pending: Vec<IceCandidateSignal> = {
+        let mut pending = session.pending_candidates.lock();
+        session.remote_described.store(true, Ordering::SeqCst);
+        pending.drain(..).collect()
+    };
+    for candidate in pending {
+        if let Err(e) = session
+            .peer_connection
+            .add_ice_candidate(to_candidate_init(candidate))
+            .await
+        {
+            warn!("ICE 후보 추가 실패: {}", e);
+        }
+    }
+}
+
+fn to_candidate_init(candidate: IceCandidateSignal) -> RTCIceCandidateInit {
+    RTCIceCandidateInit {
+        candidate: candidate.candidate,
+        sdp_mid: candidate.sdp_mid,
+        sdp_mline_index: candidate.sdp_mline_index,
+        username_fragment: candidate.username_fragment,
+    }
+}
+
+async fn close_session(sessions: &Sessions, session_id: &str) {
+    let session = sessions.lock().remove(session_id);
+    if let Some(session) = session {
+        if let Err(e) = session.peer_connection.close().await {
+            warn!("WebRTC 연결 종료 실패: {}", e);
+        }
+    }
+}
+
+/// 다음 이벤트 (취소되면 에러)
+async fn next_event(
+    events: &mut mpsc::UnboundedReceiver<ChannelEvent>,
+    control: &JobControl,
+) -> Result<ChannelEvent> {
+    tokio::select! {
+        event = events.recv() => event.ok_or_else(|| anyhow!("데이터 채널이 닫혔습니다")),
+        _ = control.cancelled() => Err(anyhow!("전송이 취소되었습니다")),
+    }
+}
+
+/// 데이터 채널이 열릴 때까지 대기
+async fn wait_open(
+    events: &mut mpsc::UnboundedReceiver<ChannelEvent>,
+    control: &JobControl,
+) -> Result<Arc<RTCDataChannel>> {
+    let opened = async {
+        loop {
+            match next_event(events, control).await? {
+                ChannelEvent::Open(channel) => return Ok(channel),
+                ChannelEvent::Closed => bail!("연결이 끊겼습니다"),
+                ChannelEvent::Message(_) => {}
+            }
+        }
+    };
+    tokio::time::timeout(CONNECT_TIMEOUT, opened)
+        .await
+        .map_err(|_| anyhow!("WebRTC 연결 시간 초과"))?
+}
+
+/// 특정 제어 메시지가 올 때까지 대기
+async fn wait_for(
+    events: &mut mpsc::UnboundedReceiver<ChannelEvent>,
+    control: &JobControl,
+    expected: ControlMessage,
+) -> Result<()> {
+    loop {
+        match next_event(events, control).await? {
+            ChannelEvent::Message(data)
+                if is_control(&data)
+                    && serde_json::from_slice::<ControlMessage>(&data)
+                        .is_ok_and(|message| message == expected) =>
+            {
+                return Ok(());
+            }
+            ChannelEvent::Closed => bail!("연결이 끊겼습니다"),
+            _ => {}
+        }
+    }
+}
+
+async fn send_over_channel(
+    mut events: mpsc::UnboundedReceiver<ChannelEvent>,
+    registry: &TransferRegistry,
+    job_id: &str,
+    control: &JobControl,
+    path: &Path,
+) -> Result<()> {
+    let channel = wait_open(&mut events, control).await?;
+
+    let metadata = tokio::fs::metadata(path).await?;
+    let total = metadata.len();
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "download".to_string());
+    let last_modified = metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+    let manifest = WebManifest::single(job_id, &name, total, last_modified);
+    channel
+        .send_text(ControlMessage::Manifest { manifest }.to_json())
+        .await?;
+
+    // 수신자가 저장 위치를 고를 때까지 대기
+    wait_for(&mut events, control, ControlMessage::TransferReady).await?;
+    channel
+        .send_text(ControlMessage::TransferStarted.to_json())
+        .await?;
+
+    let drained = Arc::new(Notify::new());
+    channel
+        .set_buffered_amount_low_threshold(LOW_WATER_MARK)
+        .await;
+    let notify = drained.clone();
+    channel
+        .on_buffered_amount_low(Box::new(move || {
+            notify.notify_one();
+            Box::pin(async {})
+        }))
+        .await;
+
+    let mut file = tokio::fs::File::open(path).await?;
+    let mut buf = vec![0u8; CHUNK_SIZE];
+    let mut offset = 0u64;
+    let started = Instant::now();
+    loop {
+        control.checkpoint().await?;
+        let n = file.read(&mut buf).await?;
+        if n == 0 {
+            break;
+        }
+
+        while channel.buffered_amount().await > HIGH_WATER_MARK {
+            tokio::select! {
+                result = tokio::time::timeout(STALL_TIMEOUT, drained.notified()) => {
+                    result.map_err(|_| anyhow!("송신 버퍼가 비워지지 않습니다 (연결 끊김)"))?;
+                }
+                _ = control.cancelled() => bail!("전송이 취소되었습니다"),
+            }
+        }
+        channel
+            .send(&Bytes::from(encode_packet(0, offset, &buf[..n])))
+            .await?;
+        offset += n as u64;
+
+        let elapsed = started.elapsed().as_secs_f64().max(f64::EPSILON);
+        registry.update_progress(job_id, offset, total, (offset as f64 / elapsed) as u64);
+    }
+
+    channel.send(&Bytes::from(eos_packet())).await?;
+    tokio::time::timeout(
+        COMPLETE_TIMEOUT,
+        wait_for(&mut events, control, ControlMessage::DownloadComplete),
+    )
+    .await
+    .map_err(|_| anyhow!("수신 완료 확인 시간 초과"))?
+}
+
+async fn receive_over_channel(
+    mut events: mpsc::UnboundedReceiver<ChannelEvent>,
+    registry: &TransferRegistry,
+    job_id: &str,
+    control: &JobControl,
+    save_dir: &Path,
+    overwrite_policy: OverwritePolicy,
+) -> Result<(PathBuf, ReceiveAction)> {
+    let channel = wait_open(&mut events, control).await?;
+
+    let manifest = loop {
+        match next_event(&mut events, control).await? {
+            ChannelEvent::Message(data) if is_control(&data) => {
+                if let Ok(ControlMessage::Manifest { manifest }) = serde_json::from_slice(&data) {
+                    break manifest;
+                }
+            }
+            ChannelEvent::Closed => bail!("매니페스트를 받기 전에 연결이 끊겼습니다"),
+            _ => {}
+        }
+    };
+
+    let destination = safe_destination(save_dir, &manifest.output_name())?;
+    let (destination, action) = resolve_destination(&destination, overwrite_policy)?;
+    if action == ReceiveAction::Skipped {
+        bail!(
+            "같은 이름의 파일이 이미 있습니다: {}",
+            destination.display()
+        );
+    }
+    info!(
+        "🌐 WebRTC 수신 시작: {} ({} bytes)",
+        destination.display(),
+        manifest.total_size
+    );
+
+    let part = part_file::part_path(&destination);
+    if let Some(parent) = part.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let mut file = tokio::fs::File::create(&part).await?;
+
+    let received = async {
+        channel
+            .send_text(ControlMessage::TransferReady.to_json())
+            .await?;
+
+        let started = Instant::now();
+        let mut received = 0u64;
+        loop {
+            let data = match next_event(&mut events, control).await? {
+                ChannelEvent::Message(data) if !is_control(&data) => data,
+                ChannelEvent::Closed => bail!("전송이 끝나기 전에 연결이 끊겼습니다"),
+                _ => continue,
+            };
+            match decode_packet(&data)? {
+                Packet::Chunk { offset, data, .. } => {
+                    control.checkpoint().await?;
+                    file.seek(SeekFrom::Start(offset)).await?;
+                    file.write_all(data).await?;
+                    received += data.len() as u64;
+
+                    let elapsed = started.elapsed().as_secs_f64().max(f64::EPSILON);
+                    registry.update_progress(
+                        job_id,
+                        received,
+                        manifest.total_size.max(received),
+                        (received as f64 / elapsed) as u64,
+                    );
+                }
+                Packet::Eos => break,
+            }
+        }
+        file.flush().await?;
+        drop(file);
+        part_file::commit(&part, &destination)?;
+
+        if let Err(e) = channel
+            .send_text(ControlMessage::DownloadComplete.to_json())
+            .await
+        {
+            warn!("DOWNLOAD_COMPLETE 전송 실패: {}", e);
+        }
+        anyhow::Ok(())
+    }
+    .await;
+
+    if received.is_err() {
+        part_file::discard(&part);
+    }
+    received.map(|_| (destination, action))
+}
@@ -7,9 +7,11 @@
 
 use std::fs::File;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
 use std::sync::Arc;
 
 use anyhow::Result;
+use parking_lot::Mutex;
 use tracing::{info, warn};
 
 /// Zero-Copy I/O 엔진
@@ -85,6 +87,30 @@ impl ZeroCopyEngine {
     }
 }
 
+// 가장 최근에 연 송신 파일이 실제로 쓰는 읽기 경로 (감지 결과와 다를 수 있음)
+const READ_PATH_NONE: u8 = 0;
+const READ_PATH_BUFFERED: u8 = 1;
+const READ_PATH_MMAP: u8 = 2;
+const READ_PATH_IO_URING: u8 = 3;
+const READ_PATH_OVERLAPPED: u8 = 4;
+
+static ACTIVE_READ_PATH: AtomicU8 = AtomicU8::new(READ_PATH_NONE);
+
+fn set_active_read_path(path: u8) {
+    ACTIVE_READ_PATH.store(path, Ordering::Relaxed);
+}
+
+/// 실제 블록 읽기에 쓰인 방식 (아직 전송이 없으면 None)
+pub fn active_read_path() -> Option<&'static str> {
+    match ACTIVE_READ_PATH.load(Ordering::Relaxed) {
+        READ_PATH_BUFFERED => Some("buffered"),
+        READ_PATH_MMAP => Some("mmap"),
+        READ_PATH_IO_URING => Some("io_uring"),
+        READ_PATH_OVERLAPPED => Some("overlapped_io"),
+        _ => None,
+    }
+}
+
 /// 블록 정보 (멀티스트림 전송용)
 #[derive(Debug, Clone)]
 pub struct BlockInfo {
@@ -122,16 +148,443 @@ pub fn split_file_into_blocks(file_size: u64, block_size: usize) -> Vec<BlockInf
 // Linux io_uring 지원 (고성능 비동기 I/O)
 // ============================================================================
 
-#[allow(dead_code)]
 #[cfg(target_os = "linux")]
 pub mod linux_io {
-    // Content removed as it was unused and contained unused imports
+    //! io_uring 블록 리더
+    //!
+    //! 블록을 세그먼트로 나눠 등록 버퍼(`ReadFixed`)로 읽습니다.
+    //! 세그먼트 SQE는 큐 깊이만큼 모아 한 번의 `submit_and_wait`로 제출합니다.
+
+    use super::BlockInfo;
+    use io_uring::{opcode, types, IoUring};
+    use parking_lot::Mutex;
+    use std::collections::VecDeque;
+    use std::fs::File;
+    use std::io;
+    use std::os::unix::io::AsRawFd;
+    use std::path::Path;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// 등록 버퍼 하나의 크기 (SQE 하나가 읽는 최대 단위)
+    pub const SEGMENT_SIZE: usize = 1024 * 1024;
+
+    /// 링 하나에 한 번에 올리는 SQE 수 (= 등록 버퍼 수)
+    pub const QUEUE_DEPTH: usize = 8;
+
+    /// 등록 버퍼를 가진 io_uring 리더 (한 번에 한 블록)
+    pub struct UringBlockReader {
+        // 필드 선언 순서대로 drop되므로 링(버퍼 등록 해제)이 버퍼보다 먼저 정리됨
+        ring: IoUring,
+        buffers: Vec<Vec<u8>>,
+        file: File,
+    }
+
+    impl UringBlockReader {
+        pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+            let file = File::open(path)?;
+            let ring = IoUring::new(QUEUE_DEPTH as u32)?;
+
+            let mut buffers: Vec<Vec<u8>> =
+                (0..QUEUE_DEPTH).map(|_| vec![0u8; SEGMENT_SIZE]).collect();
+            let iovecs: Vec<libc::iovec> = buffers
+                .iter_mut()
+                .map(|buf| libc::iovec {
+                    iov_base: buf.as_mut_ptr() as *mut libc::c_void,
+                    iov_len: buf.len(),
+                })
+                .collect();
+
+            // SAFETY: 버퍼는 리더가 소유하고 링보다 늦게 drop되므로 등록 기간 동안 유효함
+            unsafe { ring.submitter().register_buffers(&iovecs)? };
+
+            Ok(Self {
+                ring,
+                buffers,
+                file,
+            })
+        }
+
+        /// 블록 전체를 `out`에 읽기 (짧은 읽기는 나머지를 다시 제출)
+        pub fn read_block_into(&mut self, block: &BlockInfo, out: &mut [u8]) -> io::Result<()> {
+            let size = block.size as usize;
+            debug_assert_eq!(out.len(), size);
+            let fd = types::Fd(self.file.as_raw_fd());
+
+            // (블록 내 오프셋, 길이) 대기열
+            let mut pending: VecDeque<(usize, usize)> = (0..size)
+                .step_by(SEGMENT_SIZE)
+                .map(|start| (start, SEGMENT_SIZE.min(size - start)))
+                .collect();
+            let mut in_flight = [(0usize, 0usize); QUEUE_DEPTH];
+
+            while !pending.is_empty() {
+                // 1. 버퍼 슬롯마다 SQE 하나씩 채워 일괄 제출
+                let mut submitted = 0;
+                while submitted < QUEUE_DEPTH {
+                    let Some((start, len)) = pending.pop_front() else {
+                        break;
+                    };
+                    let slot = submitted;
+                    let sqe = opcode::ReadFixed::new(
+                        fd,
+                        self.buffers[slot].as_mut_ptr(),
+                        len as u32,
+                        slot as u16,
+                    )
+                    .offset(block.offset + start as u64)
+                    .build()
+                    .user_data(slot as u64);
+
+                    // SAFETY: 버퍼는 완료를 모두 수거할 때까지 다른 곳에서 쓰이지 않음
+                    unsafe {
+                        self.ring.submission().push(&sqe).map_err(|_| {
+                            io::Error::new(io::ErrorKind::Other, "io_uring 제출 큐 가득 참")
+                        })?;
+                    }
+                    in_flight[slot] = (start, len);
+                    submitted += 1;
+                }
+
+                self.ring.submit_and_wait(submitted)?;
+
+                // 2. 완료 수거 (에러가 있어도 제출한 건 전부 수거한 뒤 반환)
+                let mut error = None;
+                for cqe in self.ring.completion() {
+                    let slot = cqe.user_data() as usize;
+                    let (start, len) = in_flight[slot];
+                    let result = cqe.result();
+
+                    if result < 0 {
+                        error.get_or_insert(io::Error::from_raw_os_error(-result));
+                        continue;
+                    }
+                    let n = result as usize;
+                    if n == 0 {
+                        error.get_or_insert(io::Error::new(
+                            io::ErrorKind::UnexpectedEof,
+                            "파일이 블록 범위보다 짧음",
+                        ));
+                        continue;
+                    }
+
+                    out[start..start + n].copy_from_slice(&self.buffers[slot][..n]);
+                    if n < len {
+                        pending.push_back((start + n, len - n));
+                    }
+                }
+
+                if let Some(e) = error {
+                    return Err(e);
+                }
+            }
+
+            Ok(())
+        }
+    }
+
+    /// 동시 블록 읽기용 리더 풀 (리더마다 링과 등록 버퍼를 따로 가짐)
+    pub struct UringReaderPool {
+        readers: Vec<Mutex<UringBlockReader>>,
+        next: AtomicUsize,
+    }
+
+    impl UringReaderPool {
+        pub fn open<P: AsRef<Path>>(path: P, count: usize) -> io::Result<Self> {
+            let readers = (0..count.max(1))
+                .map(|_| UringBlockReader::open(path.as_ref()).map(Mutex::new))
+                .collect::<io::Result<Vec<_>>>()?;
+            Ok(Self {
+                readers,
+                next: AtomicUsize::new(0),
+            })
+        }
+
+        pub fn len(&self) -> usize {
+            self.readers.len()
+        }
+
+        /// 쉬고 있는 리더로 읽기 (모두 바쁘면 순서대로 대기)
+        pub fn read_block_into(&self, block: &BlockInfo, out: &mut [u8]) -> io::Result<()> {
+            let count = self.readers.len();
+            let start = self.next.fetch_add(1, Ordering::Relaxed);
+
+            for i in 0..count {
+                if let Some(mut reader) = self.readers[(start + i) % count].try_lock() {
+                    return reader.read_block_into(block, out);
+                }
+            }
+            self.readers[start % count]
+                .lock()
+                .read_block_into(block, out)
+        }
+    }
 }
 
-#[allow(dead_code)]
 #[cfg(target_os = "windows")]
 pub mod windows_io {
-    // Content removed as it was unused
+    //! Windows Overlapped I/O 블록 리더
+    //!
+    //! `FILE_FLAG_NO_BUFFERING | FILE_FLAG_OVERLAPPED`로 연 파일에서 섹터 정렬 버퍼로 세그먼트를 동시에 읽습니다.
+    //! 버퍼는 `SetFileIoOverlappedRange`로 고정을 시도합니다 (SeLockMemoryPrivilege가 없으면 실패해도 그대로 동작).
+
+    use super::BlockInfo;
+    use parking_lot::Mutex;
+    use std::alloc::{alloc_zeroed, dealloc, handle_alloc_error, Layout};
+    use std::fs::{File, OpenOptions};
+    use std::io;
+    use std::os::windows::fs::OpenOptionsExt;
+    use std::os::windows::io::AsRawHandle;
+    use std::path::Path;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tracing::debug;
+    use windows_sys::Win32::Foundation::{
+        CloseHandle, GetLastError, ERROR_HANDLE_EOF, ERROR_IO_PENDING, HANDLE,
+    };
+    use windows_sys::Win32::Storage::FileSystem::{
+        ReadFile, SetFileIoOverlappedRange, FILE_FLAG_NO_BUFFERING, FILE_FLAG_OVERLAPPED,
+        FILE_FLAG_SEQUENTIAL_SCAN,
+    };
+    use windows_sys::Win32::System::Threading::CreateEventW;
+    use windows_sys::Win32::System::IO::{GetOverlappedResult, OVERLAPPED};
+
+    /// 비버퍼 I/O 정렬 단위 (512B/4KB 섹터 모두 만족)
+    const SECTOR_ALIGN: u64 = 4096;
+
+    /// 요청 하나가 읽는 최대 단위 (정렬 단위의 배수)
+    pub const SEGMENT_SIZE: usize = 1024 * 1024;
+
+    /// 동시에 걸어두는 요청 수 (= 버퍼 슬롯 수)
+    pub const QUEUE_DEPTH: usize = 8;
+
+    /// 섹터 정렬 버퍼
+    struct AlignedBuffer {
+        ptr: *mut u8,
+        layout: Layout,
+    }
+
+    // 버퍼는 리더가 단독 소유하며 리더는 Mutex 뒤에서만 사용됨
+    unsafe impl Send for AlignedBuffer {}
+
+    impl AlignedBuffer {
+        fn new(len: usize) -> Self {
+            let layout =
+                Layout::from_size_align(len, SECTOR_ALIGN as usize).expect("정렬 버퍼 레이아웃");
+            let ptr = unsafe { alloc_zeroed(layout) };
+            if ptr.is_null() {
+                handle_alloc_error(layout);
+            }
+            Self { ptr, layout }
+        }
+
+        fn slot_ptr(&self, slot: usize) -> *mut u8 {
+            unsafe { self.ptr.add(slot * SEGMENT_SIZE) }
+        }
+
+        fn slot(&self, slot: usize, len: usize) -> &[u8] {
+            unsafe { std::slice::from_raw_parts(self.slot_ptr(slot), len) }
+        }
+    }
+
+    impl Drop for AlignedBuffer {
+        fn drop(&mut self) {
+            unsafe { dealloc(self.ptr, self.layout) };
+        }
+    }
+
+    /// Overlapped 블록 리더 (한 번에 한 블록)
+    pub struct OverlappedBlockReader {
+        file: File,
+        buffer: AlignedBuffer,
+        events: Vec<HANDLE>,
+    }
+
+    unsafe impl Send for OverlappedBlockReader {}
+
+    impl OverlappedBlockReader {
+        pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+            let file = OpenOptions::new()
+                .read(true)
+                .custom_flags(
+                    FILE_FLAG_OVERLAPPED | FILE_FLAG_NO_BUFFERING | FILE_FLAG_SEQUENTIAL_SCAN,
+                )
+                .open(path)?;
+
+            let mut reader = Self {
+                file,
+                buffer: AlignedBuffer::new(SEGMENT_SIZE * QUEUE_DEPTH),
+                events: Vec::with_capacity(QUEUE_DEPTH),
+            };
+
+            for _ in 0..QUEUE_DEPTH {
+                // 수동 리셋 이벤트 (ReadFile이 요청 시작 시 리셋함)
+                let event = unsafe { CreateEventW(std::ptr::null(), 1, 0, std::ptr::null()) };
+                if event == 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                reader.events.push(event);
+            }
+
+            let pinned = unsafe {
+                SetFileIoOverlappedRange(
+                    reader.handle(),
+                    reader.buffer.ptr.cast(),
+                    reader.buffer.layout.size() as u32,
+                )
+            };
+            if pinned == 0 {
+                debug!(
+                    "SetFileIoOverlappedRange 미적용 (권한 없음): {}",
+                    io::Error::last_os_error()
+                );
+            }
+
+            Ok(reader)
+        }
+
+        fn handle(&self) -> HANDLE {
+            self.file.as_raw_handle() as HANDLE
+        }
+
+        /// 블록 전체를 `out`에 읽기 (정렬된 범위를 읽고 요청 구간만 복사)
+        pub fn read_block_into(&mut self, block: &BlockInfo, out: &mut [u8]) -> io::Result<()> {
+            let end = block.offset + block.size as u64;
+            let aligned_start = block.offset & !(SECTOR_ALIGN - 1);
+            let aligned_end = (end + SECTOR_ALIGN - 1) & !(SECTOR_ALIGN - 1);
+
+            let segments: Vec<(u64, usize)> = (aligned_start..aligned_end)
+                .step_by(SEGMENT_SIZE)
+                .map(|start| (start, SEGMENT_SIZE.min((aligned_end - start) as usize)))
+                .collect();
+
+            debug_assert_eq!(out.len(), block.size as usize);
+
+            for wave in segments.chunks(QUEUE_DEPTH) {
+                let mut overlapped: [OVERLAPPED; QUEUE_DEPTH] = unsafe { std::mem::zeroed() };
+                let mut issued = [false; QUEUE_DEPTH];
+                let mut error = None;
+
+                // 1. 세그먼트 요청을 한꺼번에 걸어둠
+                for (slot, &(start, len)) in wave.iter().enumerate() {
+                    let ov = &mut overlapped[slot];
+                    ov.Anonymous.Anonymous.Offset = start as u32;
+                    ov.Anonymous.Anonymous.OffsetHigh = (start >> 32) as u32;
+                    ov.hEvent = self.events[slot];
+
+                    let ok = unsafe {
+                        ReadFile(
+                            self.handle(),
+                            self.buffer.slot_ptr(slot).cast(),
+                            len as u32,
+                            std::ptr::null_mut(),
+                            ov,
+                        )
+                    };
+                    if ok != 0 {
+                        issued[slot] = true;
+                        continue;
+                    }
+                    match unsafe { GetLastError() } {
+                        ERROR_IO_PENDING => issued[slot] = true,
+                        // EOF 이후 세그먼트: 0바이트로 처리
+                        ERROR_HANDLE_EOF => {}
+                        code => {
+                            error = Some(io::Error::from_raw_os_error(code as i32));
+                            break;
+                        }
+                    }
+                }
+
+                // 2. 걸어둔 요청은 에러가 있어도 전부 완료를 기다린 뒤 복사
+                for (slot, &(start, _)) in wave.iter().enumerate() {
+                    let mut read = 0u32;
+                    if issued[slot] {
+                        let ok = unsafe {
+                            GetOverlappedResult(self.handle(), &overlapped[slot], &mut read, 1)
+                        };
+                        if ok == 0 {
+                            let code = unsafe { GetLastError() };
+                            if code != ERROR_HANDLE_EOF {
+                                error.get_or_insert(io::Error::from_raw_os_error(code as i32));
+                                continue;
+                            }
+                            read = 0;
+                        }
+                    }
+
+                    // 이 세그먼트에서 요청 구간에 해당하는 부분만 복사
+                    let want_start = start.max(block.offset);
+                    let want_end = (start + SEGMENT_SIZE as u64).min(end);
+                    if want_start >= want_end {
+                        continue;
+                    }
+                    if start + (read as u64) < want_end {
+                        error.get_or_insert(io::Error::new(
+                            io::ErrorKind::UnexpectedEof,
+                            "파일이 블록 범위보다 짧음",
+                        ));
+                        continue;
+                    }
+
+                    let src_from = (want_start - start) as usize;
+                    let src_to = (want_end - start) as usize;
+                    let dst_from = (want_start - block.offset) as usize;
+                    out[dst_from..dst_from + (src_to - src_from)]
+                        .copy_from_slice(&self.buffer.slot(slot, src_to)[src_from..]);
+                }
+
+                if let Some(e) = error {
+                    return Err(e);
+                }
+            }
+
+            Ok(())
+        }
+    }
+
+    impl Drop for OverlappedBlockReader {
+        fn drop(&mut self) {
+            for event in &self.events {
+                unsafe { CloseHandle(*event) };
+            }
+        }
+    }
+
+    /// 동시 블록 읽기용 리더 풀 (리더마다 핸들과 버퍼를 따로 가짐)
+    pub struct OverlappedReaderPool {
+        readers: Vec<Mutex<OverlappedBlockReader>>,
+        next: AtomicUsize,
+    }
+
+    impl OverlappedReaderPool {
+        pub fn open<P: AsRef<Path>>(path: P, count: usize) -> io::Result<Self> {
+            let readers = (0..count.max(1))
+                .map(|_| OverlappedBlockReader::open(path.as_ref()).map(Mutex::new))
+                .collect::<io::Result<Vec<_>>>()?;
+            Ok(Self {
+                readers,
+                next: AtomicUsize::new(0),
+            })
+        }
+
+        pub fn len(&self) -> usize {
+            self.readers.len()
+        }
+
+        /// 쉬고 있는 리더로 읽기 (모두 바쁘면 순서대로 대기)
+        pub fn read_block_into(&self, block: &BlockInfo, out: &mut [u8]) -> io::Result<()> {
+            let count = self.readers.len();
+            let start = self.next.fetch_add(1, Ordering::Relaxed);
+
+            for i in 0..count {
+                if let Some(mut reader) = self.readers[(start + i) % count].try_lock() {
+                    return reader.read_block_into(block, out);
+                }
+            }
+            self.readers[start % count]
+                .lock()
+                .read_block_into(block, out)
+        }
+    }
 }
 
 // ============================================================================
@@ -148,6 +601,12 @@ pub struct HighPerformanceFileSender {
     file_size: u64,
     #[cfg(unix)]
     mmap: Option<Arc<memmap2::Mmap>>,
+    /// io_uring 리더 (활성화된 경우 mmap보다 우선)
+    #[cfg(target_os = "linux")]
+    uring: Option<linux_io::UringReaderPool>,
+    /// Overlapped 리더 (활성화된 경우 Buffered보다 우선)
+    #[cfg(target_os = "windows")]
+    overlapped: Option<windows_io::OverlappedReaderPool>,
 }
 
 impl HighPerformanceFileSender {
@@ -176,6 +635,15 @@ impl HighPerformanceFileSender {
             }
         };
 
+        #[cfg(unix)]
+        set_active_read_path(if mmap.is_some() {
+            READ_PATH_MMAP
+        } else {
+            READ_PATH_BUFFERED
+        });
+        #[cfg(not(unix))]
+        set_active_read_path(READ_PATH_BUFFERED);
+
         info!("📂 파일 열기 완료 (Zero-Copy 준비): {} bytes", file_size);
 
         Ok(Self {
@@ -183,9 +651,51 @@ impl HighPerformanceFileSender {
             file_size,
             #[cfg(unix)]
             mmap,
+            #[cfg(target_os = "linux")]
+            uring: None,
+            #[cfg(target_os = "windows")]
+            overlapped: None,
         })
     }
 
+    /// io_uring 블록 리더 활성화 (`readers`개의 링을 만들어 동시 읽기)
+    ///
+    /// 링 생성이나 버퍼 등록이 실패하면(seccomp, memlock 제한 등) false를 반환하고 mmap 경로를 유지합니다.
+    #[cfg(target_os = "linux")]
+    pub fn enable_io_uring(&mut self, readers: usize) -> bool {
+        match linux_io::UringReaderPool::open(&self.file_path, readers) {
+            Ok(pool) => {
+                info!("⚡ io_uring 블록 리더 활성화: 링 {}개", pool.len());
+                self.uring = Some(pool);
+                set_active_read_path(READ_PATH_IO_URING);
+                true
+            }
+            Err(e) => {
+                warn!("io_uring 초기화 실패 (mmap 사용): {}", e);
+                false
+            }
+        }
+    }
+
+    /// Overlapped(비버퍼) 블록 리더 활성화 (`readers`개의 핸들로 동시 읽기)
+    ///
+    /// 실패하면 false를 반환하고 Buffered 경로를 유지합니다.
+    #[cfg(target_os = "windows")]
+    pub fn enable_overlapped_io(&mut self, readers: usize) -> bool {
+        match windows_io::OverlappedReaderPool::open(&self.file_path, readers) {
+            Ok(pool) => {
+                info!("⚡ Overlapped I/O 블록 리더 활성화: 핸들 {}개", pool.len());
+                self.overlapped = Some(pool);
+                set_active_read_path(READ_PATH_OVERLAPPED);
+                true
+            }
+            Err(e) => {
+                warn!("Overlapped I/O 초기화 실패 (Buffered I/O 사용): {}", e);
+                false
+            }
+        }
+    }
+
     /// 파일 크기
     pub fn file_size(&self) -> u64 {
         self.file_size
@@ -252,6 +762,13 @@ impl HighPerformanceFileSender {
     ///
     /// 반환값: Vec<u8> (소유권 있는 데이터)
     pub fn read_block_owned(&self, block: &BlockInfo) -> Result<Vec<u8>> {
+        if self.has_async_reader() {
+            let mut data = vec![0u8; block.size as usize];
+            if self.read_block_async_reader(block, &mut data) {
+                return Ok(data);
+            }
+        }
+
         #[cfg(unix)]
         if let Some(mmap) = &self.mmap {
             let start = block.offset as usize;
@@ -268,6 +785,74 @@ impl HighPerformanceFileSender {
         // Fallback: Mmap 실패 시 기존 Buffered I/O 사용
         self.read_block_buffered(block)
     }
+
+    /// 호출자 버퍼에 블록 읽기 (버퍼 풀 재사용용, `out.len()`은 블록 크기와 같아야 함)
+    pub fn read_block_into(&self, block: &BlockInfo, out: &mut [u8]) -> Result<()> {
+        if out.len() != block.size as usize {
+            return Err(anyhow::anyhow!(
+                "버퍼 크기 불일치: {} != {}",
+                out.len(),
+                block.size
+            ));
+        }
+
+        if self.has_async_reader() && self.read_block_async_reader(block, out) {
+            return Ok(());
+        }
+
+        #[cfg(unix)]
+        if let Some(slice) = self.read_block_mmap(block) {
+            out.copy_from_slice(slice);
+            return Ok(());
+        }
+
+        use std::io::{Read, Seek, SeekFrom};
+        let mut file = File::open(&self.file_path)?;
+        file.seek(SeekFrom::Start(block.offset))?;
+        file.read_exact(out)?;
+        Ok(())
+    }
+
+    /// io_uring / Overlapped 리더 활성화 여부
+    fn has_async_reader(&self) -> bool {
+        #[cfg(target_os = "linux")]
+        {
+            self.uring.is_some()
+        }
+        #[cfg(target_os = "windows")]
+        {
+            self.overlapped.is_some()
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+        {
+            false
+        }
+    }
+
+    /// io_uring / Overlapped 리더로 읽기 (실패하면 false, 호출자가 기본 경로로 재시도)
+    #[cfg_attr(
+        not(any(target_os = "linux", target_os = "windows")),
+        allow(unused_variables)
+    )]
+    fn read_block_async_reader(&self, block: &BlockInfo, out: &mut [u8]) -> bool {
+        #[cfg(target_os = "linux")]
+        if let Some(uring) = &self.uring {
+            match uring.read_block_into(block, out) {
+                Ok(()) => return true,
+                Err(e) => warn!("io_uring 읽기 실패 (mmap 사용): {}", e),
+            }
+        }
+
+        #[cfg(target_os = "windows")]
+        if let Some(overlapped) = &self.overlapped {
+            match overlapped.read_block_into(block, out) {
+                Ok(()) => return true,
+                Err(e) => warn!("Overlapped 읽기 실패 (Buffered I/O 사용): {}", e),
+            }
+        }
+
+        false
+    }
 }
 
 /// 고성능 파일 수신기
@@ -333,6 +918,212 @@ impl HighPerformanceFileReceiver {
     }
 }
 
+/// 사전 할당 + 메모리 매핑 수신기
+///
+/// 파일을 한 번만 열어 전체 크기를 예약하고, 블록을 오프셋 위치에 바로 씁니다.
+/// 매핑이 불가능하면(빈 파일, 주소 공간 부족 등) 공유 핸들에 위치 지정 쓰기(pwrite)로 대체합니다.
+/// 블록마다 열기/seek/sync를 하지 않고, 디스크 동기화는 `sync`에서 한 번만 수행합니다.
+pub struct MappedFileReceiver {
+    file: File,
+    file_size: u64,
+    backend: WriteBackend,
+    bytes_written: AtomicU64,
+}
+
+enum WriteBackend {
+    Mmap(Mutex<memmap2::MmapMut>),
+    Pwrite,
+}
+
+impl MappedFileReceiver {
+    /// 파일 생성, 크기 예약 및 쓰기 가능 매핑
+    ///
+    /// `sparse`면 크기를 잡기 전에 희소 파일로 표시해 쓰지 않은 구간이 디스크를 차지하지 않게 합니다.
+    pub fn create<P: AsRef<Path>>(path: P, expected_size: u64, sparse: bool) -> Result<Self> {
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path.as_ref())?;
+        if sparse {
+            if let Err(e) = super::sparse::mark_sparse(&file) {
+                warn!("희소 파일 표시 실패: {}", e);
+            }
+        }
+        file.set_len(expected_size)?;
+        Self::map(file, expected_size, "생성")
+    }
+
+    /// 이전에 받던 파일을 내용 그대로 열기 (이어받기)
+    pub fn open_existing<P: AsRef<Path>>(path: P, expected_size: u64) -> Result<Self> {
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(path.as_ref())?;
+        file.set_len(expected_size)?;
+        Self::map(file, expected_size, "이어쓰기")
+    }
+
+    fn map(file: File, expected_size: u64, label: &str) -> Result<Self> {
+        let backend = if expected_size == 0 {
+            WriteBackend::Pwrite
+        } else {
+            match unsafe { memmap2::MmapMut::map_mut(&file) } {
+                Ok(mmap) => WriteBackend::Mmap(Mutex::new(mmap)),
+                Err(e) => {
+                    warn!("쓰기 mmap 실패 (pwrite 사용): {}", e);
+                    WriteBackend::Pwrite
+                }
+            }
+        };
+
+        info!(
+            "📂 수신 파일 {}: {} bytes 예약 ({})",
+            label,
+            expected_size,
+            match backend {
+                WriteBackend::Mmap(_) => "mmap",
+                WriteBackend::Pwrite => "pwrite",
+            }
+        );
+
+        Ok(Self {
+            file,
+            file_size: expected_size,
+            backend,
+            bytes_written: AtomicU64::new(0),
+        })
+    }
+
+    /// 특정 오프셋에 블록 쓰기 (블로킹 호출이므로 spawn_blocking에서 사용)
+    pub fn write_block_at(&self, offset: u64, data: &[u8]) -> Result<()> {
+        self.write_blocks_at(offset, &[data])
+    }
+
+    /// `offset`부터 이어지는 인접 블록들을 한 번에 쓰기 (pwrite 경로는 pwritev 한 번)
+    pub fn write_blocks_at(&self, offset: u64, parts: &[&[u8]]) -> Result<()> {
+        let len: u64 = parts.iter().map(|part| part.len() as u64).sum();
+        let end = offset
+            .checked_add(len)
+            .filter(|end| *end <= self.file_size)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "블록 범위 초과: offset {} + {} > {}",
+                    offset,
+                    len,
+                    self.file_size
+                )
+            })?;
+
+        match &self.backend {
+            WriteBackend::Mmap(mmap) => {
+                let mut mmap = mmap.lock();
+                let mut start = offset as usize;
+                for part in parts {
+                    mmap[start..start + part.len()].copy_from_slice(part);
+                    start += part.len();
+                }
+                debug_assert_eq!(start as u64, end);
+            }
+            WriteBackend::Pwrite => self.write_vectored_at(offset, parts)?,
+        }
+
+        self.bytes_written.fetch_add(len, Ordering::Relaxed);
+        Ok(())
+    }
+
+    #[cfg(target_os = "linux")]
+    fn write_vectored_at(&self, offset: u64, parts: &[&[u8]]) -> std::io::Result<()> {
+        use std::os::unix::io::AsRawFd;
+
+        let iovecs: Vec<libc::iovec> = parts
+            .iter()
+            .map(|part| libc::iovec {
+                iov_base: part.as_ptr() as *mut libc::c_void,
+                iov_len: part.len(),
+            })
+            .collect();
+        let written = unsafe {
+            libc::pwritev(
+                self.file.as_raw_fd(),
+                iovecs.as_ptr(),
+                iovecs.len() as libc::c_int,
+                offset as libc::off_t,
+            )
+        };
+        if written < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+
+        // 부분 쓰기: 남은 부분은 조각별로 마저 씀
+        let mut skip = written as usize;
+        let mut part_offset = offset;
+        for part in parts {
+            if skip < part.len() {
+                self.write_all_at(part_offset + skip as u64, &part[skip..])?;
+            }
+            skip = skip.saturating_sub(part.len());
+            part_offset += part.len() as u64;
+        }
+        Ok(())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn write_vectored_at(&self, mut offset: u64, parts: &[&[u8]]) -> std::io::Result<()> {
+        for part in parts {
+            self.write_all_at(offset, part)?;
+            offset += part.len() as u64;
+        }
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    fn write_all_at(&self, offset: u64, data: &[u8]) -> std::io::Result<()> {
+        use std::os::unix::fs::FileExt;
+        self.file.write_all_at(data, offset)
+    }
+
+    #[cfg(windows)]
+    fn write_all_at(&self, mut offset: u64, mut data: &[u8]) -> std::io::Result<()> {
+        use std::os::windows::fs::FileExt;
+        while !data.is_empty() {
+            let n = self.file.seek_write(data, offset)?;
+            if n == 0 {
+                return Err(std::io::ErrorKind::WriteZero.into());
+            }
+            data = &data[n..];
+            offset += n as u64;
+        }
+        Ok(())
+    }
+
+    /// 주기적 플러시: 더티 페이지가 한꺼번에 쌓이지 않도록 디스크 기록을 시작
+    ///
+    /// mmap은 비동기 플러시(MS_ASYNC), pwrite는 데이터만 동기화합니다.
+    pub fn flush_async(&self) -> Result<()> {
+        match &self.backend {
+            WriteBackend::Mmap(mmap) => mmap.lock().flush_async()?,
+            WriteBackend::Pwrite => self.file.sync_data()?,
+        }
+        Ok(())
+    }
+
+    /// 매핑 플러시 + fsync (수신 완료 시 한 번)
+    pub fn sync(&self) -> Result<()> {
+        if let WriteBackend::Mmap(mmap) = &self.backend {
+            mmap.lock().flush()?;
+        }
+        self.file.sync_all()?;
+        Ok(())
+    }
+
+    /// 수신된 바이트 수
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes_written.load(Ordering::Relaxed)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -353,4 +1144,50 @@ mod tests {
         // 시스템에 따라 다른 I/O 방식이 선택됨
         println!("Detected I/O method: {:?}", engine.io_method());
     }
+
+    #[test]
+    fn test_mapped_receiver_writes_out_of_order() {
+        let path = std::env::temp_dir().join(format!("ponswarp-recv-{}", rand::random::<u64>()));
+        let data: Vec<u8> = (0..10_000u32).map(|i| (i % 253) as u8).collect();
+
+        let receiver = MappedFileReceiver::create(&path, data.len() as u64, false).unwrap();
+        for block in split_file_into_blocks(data.len() as u64, 4096).iter().rev() {
+            let start = block.offset as usize;
+            receiver
+                .write_block_at(block.offset, &data[start..start + block.size as usize])
+                .unwrap();
+        }
+        assert!(receiver.write_block_at(9_999, &[0, 0]).is_err());
+        assert_eq!(receiver.bytes_written(), data.len() as u64);
+        receiver.sync().unwrap();
+        drop(receiver);
+
+        assert_eq!(std::fs::read(&path).unwrap(), data);
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_io_uring_reader_matches_file() {
+        let path = std::env::temp_dir().join(format!("ponswarp-uring-{}", rand::random::<u64>()));
+        // 세그먼트 경계를 넘는 크기
+        let data: Vec<u8> = (0..linux_io::SEGMENT_SIZE * 3 + 123)
+            .map(|i| (i % 251) as u8)
+            .collect();
+        std::fs::write(&path, &data).unwrap();
+
+        let mut sender = HighPerformanceFileSender::open(&path, 0).unwrap();
+        if !sender.enable_io_uring(2) {
+            // 샌드박스 등 io_uring을 쓸 수 없는 환경
+            std::fs::remove_file(&path).ok();
+            return;
+        }
+
+        for block in sender.get_blocks(linux_io::SEGMENT_SIZE * 2 + 7) {
+            let start = block.offset as usize;
+            let read = sender.read_block_owned(&block).unwrap();
+            assert_eq!(read, &data[start..start + block.size as usize]);
+        }
+        std::fs::remove_file(&path).ok();
+    }
 }
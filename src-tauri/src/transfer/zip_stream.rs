@@ -9,7 +9,7 @@ use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Instant, SystemTime};
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
@@ -19,6 +19,11 @@ use uuid::Uuid;
 use zip::write::FileOptions;
 use zip::{CompressionMethod, ZipWriter};
 
+use super::disk_space::{self, RESPONSE_NO_SPACE};
+use super::file_attrs::FileAttributes;
+use super::file_transfer::safe_destination;
+use super::part_file;
+use super::payload_crypto::{self, PayloadCipher};
 use super::TransferProgress;
 use super::TransferState;
 
@@ -52,6 +57,9 @@ pub struct FileEntry {
     pub relative_path: String,
     /// 파일 크기
     pub size: u64,
+    /// 심볼릭 링크로 다시 만들 항목의 대상 경로 (`SymlinkPolicy::Recreate`)
+    #[serde(default)]
+    pub symlink_target: Option<String>,
 }
 
 /// Zip 스트리밍 전송기 (Sender)
@@ -60,6 +68,8 @@ pub struct ZipStreamSender {
     progress_tx: Option<mpsc::Sender<TransferProgress>>,
     /// 취소 플래그 (Graceful Cancellation)
     is_cancelled: Option<Arc<AtomicBool>>,
+    /// 페이로드 암호화 (페어링 키)
+    cipher: Option<Arc<PayloadCipher>>,
 }
 
 impl ZipStreamSender {
@@ -68,6 +78,7 @@ impl ZipStreamSender {
             config,
             progress_tx: None,
             is_cancelled: None,
+            cipher: None,
         }
     }
 
@@ -82,6 +93,12 @@ impl ZipStreamSender {
         self
     }
 
+    /// 페어링 키로 Zip 데이터 암호화 ("ZIPE" 마커로 전송)
+    pub fn with_cipher(mut self, cipher: Option<Arc<PayloadCipher>>) -> Self {
+        self.cipher = cipher;
+        self
+    }
+
     /// QUIC 연결을 통해 Zip 스트림 전송 (True Streaming Architecture)
     pub async fn send_zip_stream(
         &self,
@@ -114,8 +131,13 @@ impl ZipStreamSender {
         // QUIC 양방향 스트림 열기
         let (mut send, mut recv) = conn.open_bi().await?;
 
-        // 헤더 전송: "ZIPS" + job_id 길이 + job_id + 파일 수 + 총 크기
-        send.write_all(b"ZIPS").await?;
+        // 헤더 전송: "ZIPS"(암호화 시 "ZIPE") + job_id 길이 + job_id + 파일 수 + 총 크기
+        let marker = if self.cipher.is_some() {
+            b"ZIPE"
+        } else {
+            b"ZIPS"
+        };
+        send.write_all(marker).await?;
         let job_id_bytes = job_id.as_bytes();
         send.write_all(&(job_id_bytes.len() as u32).to_le_bytes())
             .await?;
@@ -126,6 +148,10 @@ impl ZipStreamSender {
         // Receiver의 READY 응답 대기
         let mut ready_buf = [0u8; 5];
         recv.read_exact(&mut ready_buf).await?;
+        if &ready_buf == RESPONSE_NO_SPACE {
+            let _ = send.finish();
+            return Err(disk_space::read_no_space(&mut recv).await?.into());
+        }
         if &ready_buf != b"READY" {
             return Err(anyhow::anyhow!("Receiver not ready for zip stream"));
         }
@@ -177,7 +203,30 @@ impl ZipStreamSender {
                     file_entry.relative_path
                 );
 
-                zip_writer.start_file(&file_entry.relative_path, options)?;
+                // 심볼릭 링크는 대상 경로만 기록
+                if let Some(target) = &file_entry.symlink_target {
+                    zip_writer.add_symlink(
+                        file_entry.relative_path.as_str(),
+                        target.as_str(),
+                        options,
+                    )?;
+                    continue;
+                }
+
+                // 파일별 권한/수정 시각 보존 (실행 권한 포함)
+                let mut file_options = options;
+                if let Ok(attributes) =
+                    FileAttributes::read(Path::new(&file_entry.absolute_path))
+                {
+                    if let Some(mode) = attributes.unix_mode {
+                        file_options = file_options.unix_permissions(mode);
+                    }
+                    if let Some(time) = attributes.modified_ms.and_then(zip_datetime) {
+                        file_options = file_options.last_modified_time(time);
+                    }
+                }
+
+                zip_writer.start_file(&file_entry.relative_path, file_options)?;
                 let mut input = File::open(&file_entry.absolute_path)?;
 
                 let mut buffer = vec![0u8; 128 * 1024];
@@ -280,6 +329,7 @@ impl ZipStreamSender {
             let mut zip_file = tokio::fs::File::open(&tmp_zip_path_for_cleanup).await?;
             let mut buffer = vec![0u8; self.config.chunk_size];
             let mut total_sent: u64 = 0;
+            let mut frames: u64 = 0;
             let start_time = Instant::now();
             let mut last_progress = Instant::now();
 
@@ -289,7 +339,15 @@ impl ZipStreamSender {
                 if n == 0 {
                     break;
                 }
-                send.write_all(&buffer[..n]).await?;
+                match &self.cipher {
+                    Some(cipher) => {
+                        let aad = payload_crypto::aad(job_id, frames);
+                        frames += 1;
+                        payload_crypto::write_frame(&mut send, cipher, &aad, &mut buffer[..n])
+                            .await?;
+                    }
+                    None => send.write_all(&buffer[..n]).await?,
+                }
                 total_sent += n as u64;
 
                 if last_progress.elapsed().as_millis() >= self.config.progress_interval_ms as u128 {
@@ -394,6 +452,8 @@ pub struct ZipStreamReceiver {
     progress_tx: Option<mpsc::Sender<TransferProgress>>,
     /// 취소 플래그 (Graceful Cancellation)
     is_cancelled: Option<Arc<AtomicBool>>,
+    /// 페이로드 암호화 (페어링 키)
+    cipher: Option<Arc<PayloadCipher>>,
 }
 
 impl ZipStreamReceiver {
@@ -402,6 +462,7 @@ impl ZipStreamReceiver {
             config,
             progress_tx: None,
             is_cancelled: None,
+            cipher: None,
         }
     }
 
@@ -416,6 +477,12 @@ impl ZipStreamReceiver {
         self
     }
 
+    /// 암호화된 Zip 스트림("ZIPE")을 풀 페어링 키
+    pub fn with_cipher(mut self, cipher: Option<Arc<PayloadCipher>>) -> Self {
+        self.cipher = cipher;
+        self
+    }
+
     /// QUIC 스트림에서 Zip 데이터를 수신하여 파일로 저장
     pub async fn receive_zip_stream(
         &self,
@@ -445,9 +512,13 @@ impl ZipStreamReceiver {
         // 헤더 수신 (기존 프로토콜 유지)
         let mut marker = [0u8; 4];
         recv.read_exact(&mut marker).await?;
-        if &marker != b"ZIPS" {
-            return Err(anyhow::anyhow!("Invalid zip stream marker"));
-        }
+        let cipher = match &marker {
+            b"ZIPS" => None,
+            b"ZIPE" => Some(self.cipher.clone().ok_or_else(|| {
+                anyhow::anyhow!("암호화된 전송이지만 이 피어와 페어링되어 있지 않습니다")
+            })?),
+            _ => return Err(anyhow::anyhow!("Invalid zip stream marker")),
+        };
 
         // Job ID
         let mut job_id_len_buf = [0u8; 4];
@@ -472,6 +543,14 @@ impl ZipStreamReceiver {
             received_job_id, file_count, total_size
         );
 
+        // 저장 공간 확인 (원본 총 크기 + 여유분, 압축 해제 공간은 별도)
+        if let Err(error) = disk_space::check_space(&save_path, total_size) {
+            warn!("🚫 Zip 수신 거부: {}", error);
+            let _ = disk_space::write_no_space(&mut send, &error).await;
+            let _ = send.finish();
+            return Err(error.into());
+        }
+
         // READY 응답 전송
         send.write_all(b"READY").await?;
 
@@ -501,9 +580,11 @@ impl ZipStreamReceiver {
             tokio::fs::create_dir_all(parent).await?;
         }
 
-        // 파일에 직접 쓰기
-        let mut file = tokio::fs::File::create(&final_save_path).await?;
+        // 임시 파일에 쓰고 다 받은 뒤 최종 이름으로 변경
+        let part_path = part_file::part_path(&final_save_path);
+        let mut file = tokio::fs::File::create(&part_path).await?;
         let mut bytes_received: u64 = 0;
+        let mut frames: u64 = 0;
         let start_time = Instant::now();
         let mut last_progress = Instant::now();
 
@@ -525,9 +606,19 @@ impl ZipStreamReceiver {
             };
 
             // quinn의 read는 Result<Option<usize>>를 반환함 (Some(n)=데이터, None=EOF)
-            let chunk_len = match recv.read(&mut buffer[..max_read]).await? {
-                Some(len) => len,
-                None => 0,
+            let chunk_len = match &cipher {
+                Some(cipher) => {
+                    // 프레임 단위로 봉인되어 있으므로 잔여 크기와 무관하게 한 프레임씩 읽음
+                    let aad = payload_crypto::aad(&received_job_id, frames);
+                    frames += 1;
+                    payload_crypto::read_frame(&mut recv, cipher, &aad, &mut buffer)
+                        .await?
+                        .unwrap_or(0)
+                }
+                None => recv
+                    .read(&mut buffer[..max_read])
+                    .await?
+                    .unwrap_or_default(),
             };
 
             // EOF 체크
@@ -556,6 +647,14 @@ impl ZipStreamReceiver {
         tokio::io::AsyncWriteExt::flush(&mut file).await?;
         drop(file);
 
+        // 취소된 경우 부분 파일 삭제
+        if is_cancelled.load(Ordering::SeqCst) {
+            warn!("🗑️ 취소된 전송, 부분 파일 삭제: {:?}", part_path);
+            part_file::discard(&part_path);
+            return Err(anyhow::anyhow!("Receive cancelled, partial file removed"));
+        }
+        part_file::commit(&part_path, &final_save_path)?;
+
         // 완료 응답 전송
         send.write_all(b"DONE").await?;
         let _ = send.finish();
@@ -574,13 +673,6 @@ impl ZipStreamReceiver {
             final_save_path, bytes_received
         );
 
-        // 취소된 경우 부분 파일 삭제
-        if is_cancelled.load(Ordering::SeqCst) {
-            warn!("🗑️ 취소된 전송, 부분 파일 삭제: {:?}", final_save_path);
-            let _ = tokio::fs::remove_file(&final_save_path).await;
-            return Err(anyhow::anyhow!("Receive cancelled, partial file removed"));
-        }
-
         Ok(final_save_path)
     }
 
@@ -612,6 +704,80 @@ impl ZipStreamReceiver {
     }
 }
 
+/// Unix ms → Zip 항목 시각 (로컬 시간, 1980년 이전은 표현 불가)
+fn zip_datetime(ms: u64) -> Option<zip::DateTime> {
+    use chrono::{Datelike, Timelike};
+
+    let time = chrono::DateTime::from_timestamp_millis(ms as i64)?.with_timezone(&chrono::Local);
+    zip::DateTime::from_date_and_time(
+        time.year() as u16,
+        time.month() as u8,
+        time.day() as u8,
+        time.hour() as u8,
+        time.minute() as u8,
+        time.second() as u8,
+    )
+    .ok()
+}
+
+/// Zip 항목 시각 → SystemTime
+fn system_time(time: zip::DateTime) -> Option<SystemTime> {
+    let local = chrono::NaiveDate::from_ymd_opt(
+        time.year() as i32,
+        time.month() as u32,
+        time.day() as u32,
+    )?
+    .and_hms_opt(
+        time.hour() as u32,
+        time.minute() as u32,
+        time.second() as u32,
+    )?
+    .and_local_timezone(chrono::Local)
+    .earliest()?;
+    Some(local.into())
+}
+
+/// Unix 파일 종류 비트 (Zip 외부 속성에 담긴 mode)
+const S_IFMT: u32 = 0o170000;
+const S_IFLNK: u32 = 0o120000;
+
+/// 링크 대상이 압축 해제 폴더 안에 머무는지 (절대 경로나 폴더 밖으로 나가는 `..`은 거부)
+///
+/// 폴더 밖을 가리키는 링크를 만들면 뒤따르는 항목이 링크를 통해 폴더 밖에 쓰일 수 있습니다.
+pub(super) fn is_contained_link(link_path: &Path, target: &str) -> bool {
+    use std::path::Component;
+
+    let mut depth = link_path
+        .parent()
+        .map(|parent| parent.components().count())
+        .unwrap_or(0);
+    for component in Path::new(target).components() {
+        match component {
+            Component::Normal(_) => depth += 1,
+            Component::CurDir => {}
+            Component::ParentDir if depth > 0 => depth -= 1,
+            _ => return false,
+        }
+    }
+    true
+}
+
+#[cfg(unix)]
+pub(super) fn create_symlink(target: &str, link: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(windows)]
+pub(super) fn create_symlink(target: &str, link: &Path) -> std::io::Result<()> {
+    // 대상 종류에 따라 파일/폴더 링크 (개발자 모드나 관리자 권한 필요)
+    let resolved = link.parent().unwrap_or(link).join(target);
+    if resolved.is_dir() {
+        std::os::windows::fs::symlink_dir(target, link)
+    } else {
+        std::os::windows::fs::symlink_file(target, link)
+    }
+}
+
 /// Zip 파일 압축 해제 유틸리티
 pub fn extract_zip_to_directory(zip_path: &Path, output_dir: &Path) -> Result<Vec<PathBuf>> {
     use std::fs;
@@ -620,12 +786,38 @@ pub fn extract_zip_to_directory(zip_path: &Path, output_dir: &Path) -> Result<Ve
     let mut archive = zip::ZipArchive::new(file)?;
     let mut extracted_files = Vec::new();
 
+    // 하나라도 폴더 밖을 가리키는 항목이 있으면(zip-slip) 아무것도 풀지 않음
+    for name in archive.file_names() {
+        safe_destination(output_dir, name)?;
+    }
+
     for i in 0..archive.len() {
         let mut file = archive.by_index(i)?;
-        let outpath = match file.enclosed_name() {
-            Some(path) => output_dir.join(path),
-            None => continue,
-        };
+        let outpath = safe_destination(output_dir, file.name())?;
+
+        if file
+            .unix_mode()
+            .is_some_and(|mode| mode & S_IFMT == S_IFLNK)
+        {
+            let mut target = String::new();
+            file.read_to_string(&mut target)?;
+            let link_path = outpath.strip_prefix(output_dir).unwrap_or(&outpath);
+            if !is_contained_link(link_path, &target) {
+                warn!(
+                    "폴더 밖을 가리키는 심볼릭 링크 거부: {:?} -> {}",
+                    outpath, target
+                );
+                continue;
+            }
+            if let Some(parent) = outpath.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            if let Err(e) = create_symlink(&target, &outpath) {
+                warn!("심볼릭 링크 생성 실패 ({:?}): {}", outpath, e);
+            }
+            // 링크에 권한을 적용하면 대상 파일이 바뀌므로 건너뜀
+            continue;
+        }
 
         if file.is_dir() {
             fs::create_dir_all(&outpath)?;
@@ -635,8 +827,17 @@ pub fn extract_zip_to_directory(zip_path: &Path, output_dir: &Path) -> Result<Ve
                     fs::create_dir_all(parent)?;
                 }
             }
-            let mut outfile = File::create(&outpath)?;
+            let part_path = part_file::part_path(&outpath);
+            let mut outfile = File::create(&part_path)?;
             std::io::copy(&mut file, &mut outfile)?;
+            if let Some(modified) = system_time(file.last_modified()) {
+                if let Err(e) = outfile.set_modified(modified) {
+                    warn!("수정 시각 적용 실패 ({:?}): {}", outpath, e);
+                }
+            }
+            drop(outfile);
+            // 받은 Zip은 이미 디스크에 있으므로 항목마다 동기화하지 않고 이름만 변경
+            fs::rename(&part_path, &outpath)?;
             extracted_files.push(outpath.clone());
         }
 
@@ -653,3 +854,19 @@ pub fn extract_zip_to_directory(zip_path: &Path, output_dir: &Path) -> Result<Ve
     info!("📂 Zip 압축 해제 완료: {} 파일", extracted_files.len());
     Ok(extracted_files)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contained_link() {
+        assert!(is_contained_link(Path::new("app/lib/current"), "v2/lib.so"));
+        assert!(is_contained_link(
+            Path::new("app/lib/current"),
+            "../bin/run"
+        ));
+        assert!(!is_contained_link(Path::new("app/current"), "../../etc"));
+        assert!(!is_contained_link(Path::new("current"), "/etc/passwd"));
+    }
+}
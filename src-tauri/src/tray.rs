@@ -0,0 +1,202 @@
+//! 시스템 트레이
+//!
+//! 긴 전송 중에는 메인 창을 닫아 두어도 되도록, 트레이 아이콘에 전체 진행 상황을 보여 주고
+//! 모두 일시정지/재개, 내 연결 코드 복사 같은 자주 쓰는 동작을 제공합니다.
+//! 진행 중인 전송이 있을 때 창을 닫으면 종료하지 않고 트레이로 숨깁니다 (`lib.rs`의 창 이벤트).
+
+use crate::AppState;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use tauri::menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent};
+use tauri::{AppHandle, Emitter, Manager, Wry};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tracing::{info, warn};
+
+/// 상태 표시 갱신 간격
+const STATUS_INTERVAL: Duration = Duration::from_secs(1);
+
+const MAIN_WINDOW: &str = "main";
+
+const MENU_PAUSE_ALL: &str = "tray-pause-all";
+const MENU_RESUME_ALL: &str = "tray-resume-all";
+const MENU_COPY_CODE: &str = "tray-copy-connection-code";
+const MENU_SHOW: &str = "tray-show";
+const MENU_QUIT: &str = "tray-quit";
+
+/// 트레이 아이콘 생성 및 상태 갱신 시작
+pub fn init(app: &AppHandle) -> tauri::Result<()> {
+    let status = MenuItem::with_id(
+        app,
+        "tray-status",
+        "진행 중인 전송 없음",
+        false,
+        None::<&str>,
+    )?;
+    let menu = Menu::with_items(
+        app,
+        &[
+            &status,
+            &PredefinedMenuItem::separator(app)?,
+            &MenuItem::with_id(app, MENU_PAUSE_ALL, "모두 일시정지", true, None::<&str>)?,
+            &MenuItem::with_id(app, MENU_RESUME_ALL, "모두 재개", true, None::<&str>)?,
+            &MenuItem::with_id(app, MENU_COPY_CODE, "내 연결 코드 복사", true, None::<&str>)?,
+            &PredefinedMenuItem::separator(app)?,
+            &MenuItem::with_id(app, MENU_SHOW, "PonsWarp 열기", true, None::<&str>)?,
+            &MenuItem::with_id(app, MENU_QUIT, "종료", true, None::<&str>)?,
+        ],
+    )?;
+
+    let mut builder = TrayIconBuilder::with_id("main")
+        .tooltip("PonsWarp")
+        .menu(&menu)
+        .show_menu_on_left_click(false)
+        .on_menu_event(handle_menu_event)
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                show_main_window(tray.app_handle());
+            }
+        });
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    let tray = builder.build(app)?;
+
+    spawn_status_updater(app.clone(), tray, status);
+    Ok(())
+}
+
+fn handle_menu_event(app: &AppHandle, event: MenuEvent) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+
+    match event.id.as_ref() {
+        MENU_PAUSE_ALL => {
+            let count = state.transfer_registry.pause_all();
+            info!("⏸️ 트레이: 전송 {}개 일시정지", count);
+        }
+        MENU_RESUME_ALL => {
+            let count = state.transfer_registry.resume_all();
+            info!("▶️ 트레이: 전송 {}개 재개", count);
+        }
+        MENU_COPY_CODE => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                let state = app.state::<AppState>();
+                match crate::connection_qr_payload(&state).await {
+                    Ok(code) => match app.clipboard().write_text(code) {
+                        Ok(()) => {
+                            info!("📋 트레이: 연결 코드 복사");
+                            let _ = app.emit("tray-connection-code-copied", ());
+                        }
+                        Err(e) => warn!("클립보드 쓰기 실패: {}", e),
+                    },
+                    Err(e) => {
+                        warn!("연결 코드 생성 실패: {}", e);
+                        let _ = app.emit("tray-action-failed", &e);
+                    }
+                }
+            });
+        }
+        MENU_SHOW => show_main_window(app),
+        MENU_QUIT => {
+            state.is_closing.store(true, Ordering::SeqCst);
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                crate::shutdown_services(&app.state::<AppState>()).await;
+                app.exit(0);
+            });
+        }
+        _ => {}
+    }
+}
+
+/// 숨긴 메인 창을 다시 표시
+pub fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window(MAIN_WINDOW) {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+}
+
+/// 전송 합계를 메뉴 첫 줄과 툴팁에 주기적으로 반영
+fn spawn_status_updater(app: AppHandle, tray: TrayIcon, status: MenuItem<Wry>) {
+    tauri::async_runtime::spawn(async move {
+        let mut ticker = tokio::time::interval(STATUS_INTERVAL);
+        let mut last_text = String::new();
+        loop {
+            ticker.tick().await;
+            let Some(state) = app.try_state::<AppState>() else {
+                continue;
+            };
+            if state.is_closing.load(Ordering::SeqCst) {
+                break;
+            }
+
+            let text = status_text(&state.transfer_registry.summary());
+            if text == last_text {
+                continue;
+            }
+            let _ = status.set_text(&text);
+            let _ = tray.set_tooltip(Some(format!("PonsWarp - {}", text)));
+            last_text = text;
+        }
+    });
+}
+
+fn status_text(summary: &crate::transfer::registry::TransferSummary) -> String {
+    if summary.running == 0 && summary.paused == 0 {
+        return "진행 중인 전송 없음".to_string();
+    }
+
+    let mut text = format!("전송 {}개", summary.running + summary.paused);
+    if let Some(progress) = summary.progress() {
+        text.push_str(&format!(" · {:.0}%", progress));
+    }
+    if summary.running > 0 {
+        text.push_str(&format!(
+            " · {:.1} MB/s",
+            summary.speed_bps as f64 / 1_000_000.0
+        ));
+    } else {
+        text.push_str(" · 일시정지됨");
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transfer::registry::TransferSummary;
+
+    #[test]
+    fn test_status_text() {
+        assert_eq!(
+            status_text(&TransferSummary::default()),
+            "진행 중인 전송 없음"
+        );
+
+        let summary = TransferSummary {
+            running: 2,
+            paused: 0,
+            bytes_transferred: 50,
+            total_bytes: 200,
+            speed_bps: 12_500_000,
+        };
+        assert_eq!(status_text(&summary), "전송 2개 · 25% · 12.5 MB/s");
+
+        let summary = TransferSummary {
+            running: 0,
+            paused: 1,
+            ..summary
+        };
+        assert_eq!(status_text(&summary), "전송 1개 · 25% · 일시정지됨");
+    }
+}
@@ -1,7 +1,17 @@
 use crate::turn::config::{TurnAuthMethod, TurnConfig};
+use crate::turn::credentials::generate_turn_credentials;
+use crate::turn::stun::{self, StunMessage};
 use std::net::SocketAddr;
 use std::sync::{Arc, Mutex};
 
+const ALLOCATE_REQUEST: u16 = 0x0003;
+const REFRESH_REQUEST: u16 = 0x0004;
+const ATTR_LIFETIME: u16 = 0x000D;
+const ATTR_XOR_RELAYED_ADDRESS: u16 = 0x0016;
+const ATTR_REQUESTED_TRANSPORT: u16 = 0x0019;
+/// REQUESTED-TRANSPORT 값 (프로토콜 번호 17 = UDP)
+const TRANSPORT_UDP: [u8; 4] = [17, 0, 0, 0];
+
 #[derive(Debug, Clone)]
 pub struct TurnConnectionInfo {
     pub server_addr: SocketAddr,
@@ -82,6 +92,105 @@ impl TurnClient {
         Ok(())
     }
 
+    /// 설정된 인증 방식에 맞는 (username, password)
+    pub fn credentials(&self) -> Result<(String, String), String> {
+        match self.config.auth_method {
+            TurnAuthMethod::LongTerm => {
+                let creds = generate_turn_credentials(&self.config, "ponswarp")?;
+                Ok((creds.username, creds.password))
+            }
+            TurnAuthMethod::ShortTerm => match (&self.config.username, &self.config.password) {
+                (Some(username), Some(password)) => Ok((username.clone(), password.clone())),
+                _ => Err("TURN 사용자 이름/비밀번호가 없습니다".to_string()),
+            },
+        }
+    }
+
+    /// 실제 Allocate 요청으로 릴레이 주소를 받아 본 뒤 바로 해제 (네트워크 진단용)
+    ///
+    /// 연결 상태(`is_connected`)는 바꾸지 않습니다.
+    pub async fn test_allocation(&self) -> Result<SocketAddr, String> {
+        let bind = if self.server_addr.is_ipv4() {
+            "0.0.0.0:0"
+        } else {
+            "[::]:0"
+        };
+        let socket = tokio::net::UdpSocket::bind(bind)
+            .await
+            .map_err(|e| format!("UDP 소켓 생성 실패: {}", e))?;
+
+        // 1. 인증 없이 요청해 서버의 REALM/NONCE를 받음 (401)
+        let request = StunMessage::new(ALLOCATE_REQUEST)
+            .with_attribute(ATTR_REQUESTED_TRANSPORT, TRANSPORT_UDP);
+        let challenge =
+            stun::transact(&socket, self.server_addr, &request, &request.encode()).await?;
+        if challenge.error_code() != Some(401) {
+            return Err(format!(
+                "TURN 서버가 인증을 요구하지 않거나 거부함 (코드 {:?})",
+                challenge.error_code()
+            ));
+        }
+        let realm = challenge
+            .attribute(stun::ATTR_REALM)
+            .ok_or_else(|| "TURN 응답에 REALM이 없습니다".to_string())?
+            .to_vec();
+        let nonce = challenge
+            .attribute(stun::ATTR_NONCE)
+            .ok_or_else(|| "TURN 응답에 NONCE가 없습니다".to_string())?
+            .to_vec();
+
+        // 2. 장기 자격 증명 키 = MD5(username:realm:password)
+        let (username, password) = self.credentials()?;
+        let key = {
+            use md5::{Digest, Md5};
+            let mut hasher = Md5::new();
+            hasher.update(username.as_bytes());
+            hasher.update(b":");
+            hasher.update(&realm);
+            hasher.update(b":");
+            hasher.update(password.as_bytes());
+            hasher.finalize()
+        };
+        let authenticated = |message: StunMessage| {
+            message
+                .with_attribute(stun::ATTR_USERNAME, username.as_bytes())
+                .with_attribute(stun::ATTR_REALM, realm.clone())
+                .with_attribute(stun::ATTR_NONCE, nonce.clone())
+        };
+
+        let request = authenticated(
+            StunMessage::new(ALLOCATE_REQUEST)
+                .with_attribute(ATTR_REQUESTED_TRANSPORT, TRANSPORT_UDP),
+        );
+        let response = stun::transact(
+            &socket,
+            self.server_addr,
+            &request,
+            &request.encode_with_integrity(&key),
+        )
+        .await?;
+        if !response.is_success() {
+            return Err(format!("TURN 할당 실패 (코드 {:?})", response.error_code()));
+        }
+        let relay_addr = response
+            .xor_address(ATTR_XOR_RELAYED_ADDRESS)
+            .ok_or_else(|| "TURN 응답에 릴레이 주소가 없습니다".to_string())?;
+
+        // 3. LIFETIME 0으로 즉시 해제 (실패해도 서버에서 만료됨)
+        let refresh = authenticated(
+            StunMessage::new(REFRESH_REQUEST).with_attribute(ATTR_LIFETIME, [0u8; 4]),
+        );
+        let _ = stun::transact(
+            &socket,
+            self.server_addr,
+            &refresh,
+            &refresh.encode_with_integrity(&key),
+        )
+        .await;
+
+        Ok(relay_addr)
+    }
+
     pub fn connection_info(&self) -> TurnConnectionInfo {
         TurnConnectionInfo {
             server_addr: self.server_addr,
@@ -1,4 +1,29 @@
-use std::net::{Ipv4Addr, SocketAddr};
+use rand::RngCore;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
+use tokio::net::UdpSocket;
+
+/// STUN 매직 쿠키 (RFC 5389)
+const MAGIC_COOKIE: u32 = 0x2112_A442;
+const HEADER_LEN: usize = 20;
+
+pub(crate) const BINDING_REQUEST: u16 = 0x0001;
+const BINDING_SUCCESS: u16 = 0x0101;
+
+const ATTR_MAPPED_ADDRESS: u16 = 0x0001;
+pub(crate) const ATTR_USERNAME: u16 = 0x0006;
+const ATTR_MESSAGE_INTEGRITY: u16 = 0x0008;
+const ATTR_ERROR_CODE: u16 = 0x0009;
+pub(crate) const ATTR_REALM: u16 = 0x0014;
+pub(crate) const ATTR_NONCE: u16 = 0x0015;
+pub(crate) const ATTR_XOR_MAPPED_ADDRESS: u16 = 0x0020;
+
+/// 재전송 간격 (UDP 손실 대비, 총 3회 전송)
+const RETRANSMIT_INTERVAL: Duration = Duration::from_millis(500);
+const MAX_TRANSMITS: usize = 3;
+
+/// 🆕 NAT 매핑 유지용 Binding 전송 간격 (대부분의 NAT UDP 타임아웃 30초보다 짧게)
+pub const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(15);
 
 #[derive(Debug, Clone)]
 pub struct StunClient {
@@ -22,6 +47,225 @@ pub enum NatType {
     Unknown,
 }
 
+impl NatType {
+    /// 두 STUN 서버에서 본 매핑 주소로 NAT 유형 추정
+    ///
+    /// CHANGE-REQUEST를 지원하는 공개 서버가 드물어 Full/Restricted Cone은 구분하지 않고,
+    /// 매핑이 목적지와 무관하면 보수적으로 `RestrictedCone`으로 봅니다.
+    pub fn classify(
+        local_ip: Option<IpAddr>,
+        first: SocketAddr,
+        second: Option<SocketAddr>,
+    ) -> Self {
+        if local_ip == Some(first.ip()) {
+            return NatType::Open;
+        }
+        match second {
+            Some(second) if second == first => NatType::RestrictedCone,
+            Some(_) => NatType::Symmetric,
+            None => NatType::Unknown,
+        }
+    }
+
+    /// 홀펀칭으로 직접 연결이 가능한 유형인지
+    pub fn allows_hole_punching(&self) -> bool {
+        matches!(
+            self,
+            NatType::Open | NatType::FullCone | NatType::RestrictedCone
+        )
+    }
+}
+
+/// STUN/TURN 메시지 (속성은 원본 바이트 그대로 보관)
+#[derive(Debug, Clone)]
+pub(crate) struct StunMessage {
+    pub msg_type: u16,
+    pub transaction_id: [u8; 12],
+    pub attributes: Vec<(u16, Vec<u8>)>,
+}
+
+impl StunMessage {
+    pub fn new(msg_type: u16) -> Self {
+        let mut transaction_id = [0u8; 12];
+        rand::thread_rng().fill_bytes(&mut transaction_id);
+        Self {
+            msg_type,
+            transaction_id,
+            attributes: Vec::new(),
+        }
+    }
+
+    pub fn with_attribute(mut self, attr_type: u16, value: impl Into<Vec<u8>>) -> Self {
+        self.attributes.push((attr_type, value.into()));
+        self
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut body = Vec::new();
+        for (attr_type, value) in &self.attributes {
+            body.extend_from_slice(&attr_type.to_be_bytes());
+            body.extend_from_slice(&(value.len() as u16).to_be_bytes());
+            body.extend_from_slice(value);
+            // 속성은 4바이트 경계로 패딩
+            body.resize(body.len() + (4 - value.len() % 4) % 4, 0);
+        }
+
+        let mut buf = Vec::with_capacity(HEADER_LEN + body.len());
+        buf.extend_from_slice(&self.msg_type.to_be_bytes());
+        buf.extend_from_slice(&(body.len() as u16).to_be_bytes());
+        buf.extend_from_slice(&MAGIC_COOKIE.to_be_bytes());
+        buf.extend_from_slice(&self.transaction_id);
+        buf.extend_from_slice(&body);
+        buf
+    }
+
+    /// MESSAGE-INTEGRITY(HMAC-SHA1)를 붙여 인코딩 (TURN 장기 자격 증명)
+    pub fn encode_with_integrity(&self, key: &[u8]) -> Vec<u8> {
+        use hmac::{Hmac, Mac};
+
+        let mut buf = self.encode();
+        // 길이 필드는 MESSAGE-INTEGRITY 속성(24바이트)까지 포함해야 함
+        let length = (buf.len() - HEADER_LEN + 24) as u16;
+        buf[2..4].copy_from_slice(&length.to_be_bytes());
+
+        let mut mac = Hmac::<sha1::Sha1>::new_from_slice(key).expect("HMAC은 모든 키 길이 허용");
+        mac.update(&buf);
+        buf.extend_from_slice(&ATTR_MESSAGE_INTEGRITY.to_be_bytes());
+        buf.extend_from_slice(&20u16.to_be_bytes());
+        buf.extend_from_slice(&mac.finalize().into_bytes());
+        buf
+    }
+
+    pub fn decode(buf: &[u8]) -> Result<Self, String> {
+        if buf.len() < HEADER_LEN {
+            return Err("STUN 메시지가 너무 짧습니다".to_string());
+        }
+        let msg_type = u16::from_be_bytes([buf[0], buf[1]]);
+        let length = u16::from_be_bytes([buf[2], buf[3]]) as usize;
+        if u32::from_be_bytes([buf[4], buf[5], buf[6], buf[7]]) != MAGIC_COOKIE {
+            return Err("STUN 매직 쿠키 불일치".to_string());
+        }
+        if buf.len() < HEADER_LEN + length {
+            return Err("STUN 메시지 길이 불일치".to_string());
+        }
+
+        let mut transaction_id = [0u8; 12];
+        transaction_id.copy_from_slice(&buf[8..HEADER_LEN]);
+
+        let mut attributes = Vec::new();
+        let mut offset = HEADER_LEN;
+        let end = HEADER_LEN + length;
+        while offset + 4 <= end {
+            let attr_type = u16::from_be_bytes([buf[offset], buf[offset + 1]]);
+            let attr_len = u16::from_be_bytes([buf[offset + 2], buf[offset + 3]]) as usize;
+            let value_start = offset + 4;
+            if value_start + attr_len > end {
+                return Err("STUN 속성 길이 불일치".to_string());
+            }
+            attributes.push((attr_type, buf[value_start..value_start + attr_len].to_vec()));
+            offset = value_start + attr_len + (4 - attr_len % 4) % 4;
+        }
+
+        Ok(Self {
+            msg_type,
+            transaction_id,
+            attributes,
+        })
+    }
+
+    pub fn attribute(&self, attr_type: u16) -> Option<&[u8]> {
+        self.attributes
+            .iter()
+            .find(|(t, _)| *t == attr_type)
+            .map(|(_, v)| v.as_slice())
+    }
+
+    /// 성공 응답 여부 (클래스 비트 0b10)
+    pub fn is_success(&self) -> bool {
+        self.msg_type & 0x0110 == 0x0100
+    }
+
+    /// ERROR-CODE 속성 (예: 401)
+    pub fn error_code(&self) -> Option<u16> {
+        let value = self.attribute(ATTR_ERROR_CODE)?;
+        if value.len() < 4 {
+            return None;
+        }
+        Some((value[2] & 0x07) as u16 * 100 + value[3] as u16)
+    }
+
+    /// XOR-*-ADDRESS 속성 디코딩
+    pub fn xor_address(&self, attr_type: u16) -> Option<SocketAddr> {
+        let value = self.attribute(attr_type)?;
+        if value.len() < 8 {
+            return None;
+        }
+        let cookie = MAGIC_COOKIE.to_be_bytes();
+        let port = u16::from_be_bytes([value[2], value[3]]) ^ (MAGIC_COOKIE >> 16) as u16;
+        match value[1] {
+            0x01 => {
+                let ip: [u8; 4] = std::array::from_fn(|i| value[4 + i] ^ cookie[i]);
+                Some(SocketAddr::new(Ipv4Addr::from(ip).into(), port))
+            }
+            0x02 if value.len() >= 20 => {
+                let ip: [u8; 16] = std::array::from_fn(|i| {
+                    let mask = if i < 4 {
+                        cookie[i]
+                    } else {
+                        self.transaction_id[i - 4]
+                    };
+                    value[4 + i] ^ mask
+                });
+                Some(SocketAddr::new(Ipv6Addr::from(ip).into(), port))
+            }
+            _ => None,
+        }
+    }
+
+    /// MAPPED-ADDRESS 속성 디코딩 (XOR 미지원 구형 서버용)
+    fn mapped_address(&self) -> Option<SocketAddr> {
+        let value = self.attribute(ATTR_MAPPED_ADDRESS)?;
+        if value.len() < 8 || value[1] != 0x01 {
+            return None;
+        }
+        let port = u16::from_be_bytes([value[2], value[3]]);
+        let ip = Ipv4Addr::new(value[4], value[5], value[6], value[7]);
+        Some(SocketAddr::new(ip.into(), port))
+    }
+}
+
+/// 요청을 보내고 같은 트랜잭션의 응답을 기다림 (재전송 포함)
+pub(crate) async fn transact(
+    socket: &UdpSocket,
+    server: SocketAddr,
+    request: &StunMessage,
+    encoded: &[u8],
+) -> Result<StunMessage, String> {
+    let mut buf = [0u8; 1500];
+    for _ in 0..MAX_TRANSMITS {
+        socket
+            .send_to(encoded, server)
+            .await
+            .map_err(|e| format!("STUN 요청 전송 실패: {}", e))?;
+
+        let deadline = tokio::time::Instant::now() + RETRANSMIT_INTERVAL;
+        while let Ok(received) = tokio::time::timeout_at(deadline, socket.recv_from(&mut buf)).await
+        {
+            let (len, from) = received.map_err(|e| format!("STUN 응답 수신 실패: {}", e))?;
+            if from != server {
+                continue;
+            }
+            match StunMessage::decode(&buf[..len]) {
+                Ok(response) if response.transaction_id == request.transaction_id => {
+                    return Ok(response)
+                }
+                _ => continue,
+            }
+        }
+    }
+    Err(format!("STUN 서버 응답 없음: {}", server))
+}
+
 impl StunClient {
     pub fn new(server_addr: SocketAddr) -> Self {
         Self { server_addr }
@@ -31,15 +275,201 @@ impl StunClient {
         self.server_addr
     }
 
+    /// Binding 요청으로 이 소켓의 공인 매핑 주소 조회
+    pub async fn binding(&self, socket: &UdpSocket) -> Result<SocketAddr, String> {
+        let request = StunMessage::new(BINDING_REQUEST);
+        let response = transact(socket, self.server_addr, &request, &request.encode()).await?;
+        if response.msg_type != BINDING_SUCCESS {
+            return Err(format!(
+                "STUN Binding 실패 (코드 {:?})",
+                response.error_code()
+            ));
+        }
+        response
+            .xor_address(ATTR_XOR_MAPPED_ADDRESS)
+            .or_else(|| response.mapped_address())
+            .ok_or_else(|| "STUN 응답에 매핑 주소가 없습니다".to_string())
+    }
+
     pub async fn discover_public_ip(
         &self,
-        _turn_socket: Option<std::sync::Arc<tokio::net::UdpSocket>>,
+        turn_socket: Option<std::sync::Arc<tokio::net::UdpSocket>>,
     ) -> Result<StunDiscoveryResult, String> {
+        let socket = match turn_socket {
+            Some(socket) => socket,
+            None => std::sync::Arc::new(
+                UdpSocket::bind("0.0.0.0:0")
+                    .await
+                    .map_err(|e| format!("UDP 소켓 생성 실패: {}", e))?,
+            ),
+        };
+        let mapped = self.binding(&socket).await?;
+        let public_addr = match mapped.ip() {
+            IpAddr::V4(ip) => ip,
+            IpAddr::V6(_) => return Err("IPv6 매핑 주소는 지원하지 않습니다".to_string()),
+        };
+        let local_addr = socket
+            .local_addr()
+            .map_err(|e| format!("로컬 주소 조회 실패: {}", e))?;
+
         Ok(StunDiscoveryResult {
-            public_addr: Ipv4Addr::new(127, 0, 0, 1),
-            public_port: self.server_addr.port(),
-            nat_type: NatType::Unknown,
-            local_addr: SocketAddr::from(([127, 0, 0, 1], 0)),
+            public_addr,
+            public_port: mapped.port(),
+            nat_type: NatType::classify(local_ip_towards(self.server_addr), mapped, None),
+            local_addr,
         })
     }
 }
+
+/// 🆕 소켓의 NAT 매핑이 만료되지 않도록 주기적으로 Binding 요청 전송
+///
+/// 다른 주체(QUIC 엔드포인트 등)가 수신하는 소켓의 복제본을 받아 보내기만 하며,
+/// 응답은 그쪽에서 알 수 없는 패킷으로 버려집니다. 태스크를 abort하면 중단됩니다.
+pub fn spawn_keepalive(
+    socket: std::net::UdpSocket,
+    server: SocketAddr,
+) -> tauri::async_runtime::JoinHandle<()> {
+    tauri::async_runtime::spawn(keepalive_loop(socket, server, KEEPALIVE_INTERVAL))
+}
+
+async fn keepalive_loop(socket: std::net::UdpSocket, server: SocketAddr, interval: Duration) {
+    let mut ticker = tokio::time::interval(interval);
+    // 첫 tick은 즉시 완료되며, 직전에 Binding을 마쳤으므로 건너뜀
+    ticker.tick().await;
+    loop {
+        ticker.tick().await;
+        let request = StunMessage::new(BINDING_REQUEST).encode();
+        match socket.send_to(&request, server) {
+            Ok(_) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {}
+            Err(e) => log::debug!("STUN keepalive 전송 실패 ({}): {}", server, e),
+        }
+    }
+}
+
+/// 여러 STUN 서버로 같은 소켓의 매핑을 비교해 NAT 유형 감지
+///
+/// 응답한 서버가 하나뿐이면 매핑 비교가 불가능해 `Unknown`(공인 IP면 `Open`)이 됩니다.
+pub async fn detect_nat_type(servers: &[SocketAddr]) -> Result<StunDiscoveryResult, String> {
+    let socket = UdpSocket::bind("0.0.0.0:0")
+        .await
+        .map_err(|e| format!("UDP 소켓 생성 실패: {}", e))?;
+
+    let mut mappings = Vec::new();
+    let mut last_error = None;
+    for &server in servers {
+        match StunClient::new(server).binding(&socket).await {
+            Ok(mapped) => mappings.push((server, mapped)),
+            Err(e) => last_error = Some(e),
+        }
+        if mappings.len() == 2 {
+            break;
+        }
+    }
+
+    let (first_server, first) = *mappings
+        .first()
+        .ok_or_else(|| last_error.unwrap_or_else(|| "STUN 서버가 없습니다".to_string()))?;
+    let public_addr = match first.ip() {
+        IpAddr::V4(ip) => ip,
+        IpAddr::V6(_) => return Err("IPv6 매핑 주소는 지원하지 않습니다".to_string()),
+    };
+
+    Ok(StunDiscoveryResult {
+        public_addr,
+        public_port: first.port(),
+        nat_type: NatType::classify(
+            local_ip_towards(first_server),
+            first,
+            mappings.get(1).map(|(_, mapped)| *mapped),
+        ),
+        local_addr: socket
+            .local_addr()
+            .map_err(|e| format!("로컬 주소 조회 실패: {}", e))?,
+    })
+}
+
+/// 해당 서버로 나갈 때 사용하는 로컬 IP (라우팅 테이블 기준)
+fn local_ip_towards(server: SocketAddr) -> Option<IpAddr> {
+    let bind: SocketAddr = if server.is_ipv4() {
+        "0.0.0.0:0".parse().ok()?
+    } else {
+        "[::]:0".parse().ok()?
+    };
+    let socket = std::net::UdpSocket::bind(bind).ok()?;
+    socket.connect(server).ok()?;
+    socket.local_addr().ok().map(|addr| addr.ip())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_roundtrip_and_nat_classify() {
+        // RFC 5769 예제의 XOR-MAPPED-ADDRESS (192.0.2.1:32853)
+        let message = StunMessage::new(BINDING_SUCCESS).with_attribute(
+            ATTR_XOR_MAPPED_ADDRESS,
+            vec![0x00, 0x01, 0xa1, 0x47, 0xe1, 0x12, 0xa6, 0x43],
+        );
+        let decoded = StunMessage::decode(&message.encode()).unwrap();
+        assert!(decoded.is_success());
+        assert_eq!(decoded.transaction_id, message.transaction_id);
+        assert_eq!(
+            decoded.xor_address(ATTR_XOR_MAPPED_ADDRESS),
+            Some("192.0.2.1:32853".parse().unwrap())
+        );
+
+        // MESSAGE-INTEGRITY는 마지막 속성이며 길이 필드에 포함됨
+        let request =
+            StunMessage::new(BINDING_REQUEST).with_attribute(ATTR_USERNAME, b"user".to_vec());
+        let signed = request.encode_with_integrity(b"key");
+        assert_eq!(signed.len(), request.encode().len() + 24);
+        let decoded = StunMessage::decode(&signed).unwrap();
+        assert!(!decoded.is_success());
+        assert_eq!(decoded.attributes.len(), 2);
+
+        let public: SocketAddr = "203.0.113.5:4000".parse().unwrap();
+        let other: SocketAddr = "203.0.113.5:4001".parse().unwrap();
+        assert_eq!(
+            NatType::classify(None, public, Some(public)),
+            NatType::RestrictedCone
+        );
+        assert_eq!(
+            NatType::classify(None, public, Some(other)),
+            NatType::Symmetric
+        );
+        assert_eq!(
+            NatType::classify(Some(public.ip()), public, None),
+            NatType::Open
+        );
+    }
+
+    #[tokio::test]
+    async fn test_keepalive_sends_binding_requests() {
+        let server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let client = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+        client.set_nonblocking(true).unwrap();
+        let client_addr = client.local_addr().unwrap();
+        let task = tokio::spawn(keepalive_loop(
+            client,
+            server.local_addr().unwrap(),
+            Duration::from_millis(20),
+        ));
+
+        let mut buf = [0u8; 1500];
+        for _ in 0..2 {
+            let (len, from) =
+                tokio::time::timeout(Duration::from_secs(2), server.recv_from(&mut buf))
+                    .await
+                    .unwrap()
+                    .unwrap();
+            assert_eq!(from, client_addr);
+            assert_eq!(
+                StunMessage::decode(&buf[..len]).unwrap().msg_type,
+                BINDING_REQUEST
+            );
+        }
+        task.abort();
+    }
+}
@@ -0,0 +1,16 @@
+//! 여러 모듈이 함께 쓰는 작은 도우미
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 현재 유닉스 시각 (초)
+pub fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// 길이가 같으면 내용과 무관하게 같은 시간이 걸리는 비교
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
@@ -0,0 +1,277 @@
+import React, { useState, useEffect, useMemo } from 'react';
+import { motion, AnimatePresence } from 'framer-motion';
+import { Folder, FileText, X, Download } from 'lucide-react';
+import { formatBytes } from '../../utils/fileUtils';
+import { logInfo, logError } from '../../utils/logger';
+import { getErrorMessage } from '../../utils/tauri';
+import {
+  nativeTransferService,
+  FileSelection,
+  OfferCatalog,
+  RangeDownloadResult,
+} from '../../services/native-transfer';
+
+interface OfferSelectionModalProps {
+  isOpen: boolean;
+  peerId: string;
+  offerId: string;
+  saveDir: string;
+  onComplete: (results: RangeDownloadResult[]) => void;
+  onClose: () => void;
+}
+
+// 파일 경로의 모든 상위 폴더 ("a/b/c.txt" -> ["a", "a/b"])
+const parentFolders = (path: string): string[] => {
+  const parts = path.split('/').slice(0, -1);
+  return parts.map((_, i) => parts.slice(0, i + 1).join('/'));
+};
+
+/**
+ * 🆕 피어가 제공한 파일 중 받을 파일/폴더(또는 한 파일의 바이트 범위)를 고르는 화면
+ */
+export const OfferSelectionModal: React.FC<OfferSelectionModalProps> = ({
+  isOpen,
+  peerId,
+  offerId,
+  saveDir,
+  onComplete,
+  onClose,
+}) => {
+  const [catalog, setCatalog] = useState<OfferCatalog | null>(null);
+  const [selected, setSelected] = useState<Set<string>>(new Set());
+  const [rangeStart, setRangeStart] = useState('');
+  const [rangeEnd, setRangeEnd] = useState('');
+  const [error, setError] = useState<string | null>(null);
+  const [isRequesting, setIsRequesting] = useState(false);
+
+  useEffect(() => {
+    if (!isOpen) return;
+    setCatalog(null);
+    setSelected(new Set());
+    setRangeStart('');
+    setRangeEnd('');
+    setError(null);
+    nativeTransferService
+      .fetchOfferCatalog(peerId, offerId)
+      .then(setCatalog)
+      .catch(e => setError(getErrorMessage(e)));
+  }, [isOpen, peerId, offerId]);
+
+  const folders = useMemo(() => {
+    const all = new Set<string>();
+    catalog?.files.forEach(file =>
+      parentFolders(file.path).forEach(folder => all.add(folder))
+    );
+    return [...all].sort();
+  }, [catalog]);
+
+  if (!isOpen) return null;
+
+  const files = catalog?.files ?? [];
+  const filesUnder = (folder: string) =>
+    files.filter(file => file.path.startsWith(`${folder}/`));
+  const isFolderSelected = (folder: string) =>
+    filesUnder(folder).every(file => selected.has(file.path));
+
+  const toggle = (paths: string[], on: boolean) => {
+    const next = new Set(selected);
+    paths.forEach(path => (on ? next.add(path) : next.delete(path)));
+    setSelected(next);
+  };
+
+  const selectedFiles = files.filter(file => selected.has(file.path));
+  const singleFile = selectedFiles.length === 1 ? selectedFiles[0] : null;
+  const selectedSize = selectedFiles.reduce((sum, file) => sum + file.size, 0);
+
+  // 통째로 고른 최상위 폴더는 폴더 경로로, 나머지는 파일 경로로 요청
+  const buildSelection = (): FileSelection[] => {
+    if (singleFile && (rangeStart || rangeEnd)) {
+      return [
+        {
+          path: singleFile.path,
+          range: {
+            start: Number(rangeStart || 0),
+            end: rangeEnd ? Number(rangeEnd) : undefined,
+          },
+        },
+      ];
+    }
+    const whole = folders.filter(
+      folder =>
+        isFolderSelected(folder) &&
+        !parentFolders(folder).some(parent => isFolderSelected(parent))
+    );
+    const covered = (path: string) =>
+      whole.some(folder => path.startsWith(`${folder}/`));
+    return [
+      ...whole.map(path => ({ path })),
+      ...selectedFiles
+        .filter(file => !covered(file.path))
+        .map(file => ({ path: file.path })),
+    ];
+  };
+
+  const handleRequest = async () => {
+    if (isRequesting || selectedFiles.length === 0) return;
+    try {
+      setIsRequesting(true);
+      const jobId = `offer-${crypto.randomUUID()}`;
+      const results = await nativeTransferService.requestOfferFiles(
+        peerId,
+        offerId,
+        buildSelection(),
+        saveDir,
+        jobId
+      );
+      logInfo(
+        '[OfferSelectionModal]',
+        `Received ${results.length} file(s) from offer ${offerId}`
+      );
+      onComplete(results);
+      onClose();
+    } catch (e) {
+      logError(
+        '[OfferSelectionModal]',
+        `Failed to request files: ${getErrorMessage(e)}`
+      );
+      setError(getErrorMessage(e));
+    } finally {
+      setIsRequesting(false);
+    }
+  };
+
+  const rows = [
+    ...folders.map(path => ({ path, folder: true, size: 0 })),
+    ...files.map(file => ({ path: file.path, folder: false, size: file.size })),
+  ].sort((a, b) => a.path.localeCompare(b.path));
+
+  return (
+    <AnimatePresence>
+      {isOpen && (
+        <motion.div
+          initial={{ opacity: 0 }}
+          animate={{ opacity: 1 }}
+          exit={{ opacity: 0 }}
+          className="fixed inset-0 z-50 flex items-center justify-center bg-black/50 backdrop-blur-sm p-4"
+        >
+          <motion.div
+            initial={{ scale: 0.9, opacity: 0 }}
+            animate={{ scale: 1, opacity: 1 }}
+            exit={{ scale: 0.9, opacity: 0 }}
+            className="bg-white dark:bg-gray-800 rounded-2xl shadow-2xl max-w-2xl w-full overflow-hidden"
+          >
+            <div className="p-6">
+              <div className="flex items-start justify-between mb-4">
+                <div>
+                  <h2 className="text-xl font-bold text-gray-900 dark:text-gray-100">
+                    {catalog ? catalog.name : 'Loading offer...'}
+                  </h2>
+                  {catalog && (
+                    <p className="text-sm text-gray-500 dark:text-gray-400">
+                      {catalog.files.length} files,{' '}
+                      {formatBytes(catalog.totalSize)}
+                    </p>
+                  )}
+                </div>
+                <button
+                  onClick={onClose}
+                  className="p-2 hover:bg-gray-100 dark:hover:bg-gray-700 rounded-lg transition-colors"
+                  disabled={isRequesting}
+                >
+                  <X className="w-5 h-5 text-gray-500 dark:text-gray-400" />
+                </button>
+              </div>
+
+              <div className="max-h-80 overflow-y-auto border border-gray-200 dark:border-gray-700 rounded-lg divide-y divide-gray-100 dark:divide-gray-700">
+                {rows.map(row => {
+                  const depth = row.path.split('/').length - 1;
+                  const checked = row.folder
+                    ? isFolderSelected(row.path)
+                    : selected.has(row.path);
+                  const paths = row.folder
+                    ? filesUnder(row.path).map(file => file.path)
+                    : [row.path];
+                  return (
+                    <label
+                      key={`${row.folder ? 'd' : 'f'}:${row.path}`}
+                      className="flex items-center space-x-2 px-3 py-2 text-sm cursor-pointer hover:bg-gray-50 dark:hover:bg-gray-900"
+                      style={{ paddingLeft: `${0.75 + depth * 1.25}rem` }}
+                    >
+                      <input
+                        type="checkbox"
+                        checked={checked}
+                        onChange={e => toggle(paths, e.target.checked)}
+                        disabled={isRequesting}
+                      />
+                      {row.folder ? (
+                        <Folder className="w-4 h-4 text-yellow-500" />
+                      ) : (
+                        <FileText className="w-4 h-4 text-blue-500" />
+                      )}
+                      <span className="flex-1 text-gray-900 dark:text-gray-100 truncate">
+                        {row.path.split('/').pop()}
+                      </span>
+                      {!row.folder && (
+                        <span className="text-gray-500 dark:text-gray-400">
+                          {formatBytes(row.size)}
+                        </span>
+                      )}
+                    </label>
+                  );
+                })}
+              </div>
+
+              {singleFile && (
+                <div className="mt-4 grid grid-cols-2 gap-3 text-sm">
+                  <label className="text-gray-700 dark:text-gray-300">
+                    Range start (bytes)
+                    <input
+                      type="number"
+                      min={0}
+                      value={rangeStart}
+                      onChange={e => setRangeStart(e.target.value)}
+                      placeholder="0"
+                      className="mt-1 w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700"
+                    />
+                  </label>
+                  <label className="text-gray-700 dark:text-gray-300">
+                    Range end (exclusive)
+                    <input
+                      type="number"
+                      min={0}
+                      value={rangeEnd}
+                      onChange={e => setRangeEnd(e.target.value)}
+                      placeholder={String(singleFile.size)}
+                      className="mt-1 w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700"
+                    />
+                  </label>
+                </div>
+              )}
+
+              {error && (
+                <p className="mt-4 text-sm text-red-600 dark:text-red-400">
+                  {error}
+                </p>
+              )}
+
+              <motion.button
+                onClick={handleRequest}
+                className="mt-6 w-full flex items-center justify-center space-x-2 px-6 py-3 bg-blue-600 hover:bg-blue-700 disabled:opacity-50 text-white font-medium rounded-lg transition-colors"
+                whileHover={{ scale: 1.02 }}
+                whileTap={{ scale: 0.98 }}
+                disabled={isRequesting || selectedFiles.length === 0}
+              >
+                <Download className="w-4 h-4" />
+                <span>
+                  {isRequesting
+                    ? 'Requesting...'
+                    : `Receive ${selectedFiles.length} file(s) (${formatBytes(selectedSize)})`}
+                </span>
+              </motion.button>
+            </div>
+          </motion.div>
+        </motion.div>
+      )}
+    </AnimatePresence>
+  );
+};
@@ -11,6 +11,7 @@ import {
 import { invoke } from '@tauri-apps/api/core';
 import { formatBytes } from '../../utils/fileUtils';
 import { logInfo, logError } from '../../utils/logger';
+import { getErrorMessage } from '../../utils/tauri';
 
 interface TransferApprovalModalProps {
   isOpen: boolean;
@@ -67,10 +68,10 @@ export const TransferApprovalModal: React.FC<TransferApprovalModalProps> = ({
     } catch (error) {
       logError(
         '[TransferApprovalModal]',
-        `Failed to approve transfer: ${error}`
+        `Failed to approve transfer: ${getErrorMessage(error)}`
       );
       setIsApproving(false);
-      alert(`Failed to approve transfer: ${error}`);
+      alert(`Failed to approve transfer: ${getErrorMessage(error)}`);
     }
   };
 
@@ -95,10 +96,10 @@ export const TransferApprovalModal: React.FC<TransferApprovalModalProps> = ({
     } catch (error) {
       logError(
         '[TransferApprovalModal]',
-        `Failed to reject transfer: ${error}`
+        `Failed to reject transfer: ${getErrorMessage(error)}`
       );
       setIsApproving(false);
-      alert(`Failed to reject transfer: ${error}`);
+      alert(`Failed to reject transfer: ${getErrorMessage(error)}`);
     }
   };
 
@@ -18,6 +18,7 @@ import {
   type BootstrapPeerDiscoveredEvent,
 } from '../services/embeddedBootstrap';
 import { logInfo, logError } from '../utils/logger';
+import { getErrorMessage } from '../utils/tauri';
 
 export interface UseEmbeddedBootstrapReturn {
   // 상태
@@ -59,7 +60,7 @@ export function useEmbeddedBootstrap(): UseEmbeddedBootstrapReturn {
       setError(null);
     } catch (err) {
       logError('[useEmbeddedBootstrap]', '상태 조회 실패:', err);
-      setError(err instanceof Error ? err.message : String(err));
+      setError(getErrorMessage(err));
     }
   }, []);
 
@@ -79,7 +80,7 @@ export function useEmbeddedBootstrap(): UseEmbeddedBootstrapReturn {
         await refreshStatus();
         logInfo('[useEmbeddedBootstrap]', '부트스트랩 시작 완료');
       } catch (err) {
-        const errorMsg = err instanceof Error ? err.message : String(err);
+        const errorMsg = getErrorMessage(err);
         setError(errorMsg);
         logError('[useEmbeddedBootstrap]', '부트스트랩 시작 실패:', err);
         throw err;
@@ -105,7 +106,7 @@ export function useEmbeddedBootstrap(): UseEmbeddedBootstrapReturn {
       await refreshStatus();
       logInfo('[useEmbeddedBootstrap]', '부트스트랩 중지 완료');
     } catch (err) {
-      const errorMsg = err instanceof Error ? err.message : String(err);
+      const errorMsg = getErrorMessage(err);
       setError(errorMsg);
       logError('[useEmbeddedBootstrap]', '부트스트랩 중지 실패:', err);
       throw err;
@@ -130,7 +131,7 @@ export function useEmbeddedBootstrap(): UseEmbeddedBootstrapReturn {
         await refreshStatus();
         logInfo('[useEmbeddedBootstrap]', '설정 업데이트 완료');
       } catch (err) {
-        const errorMsg = err instanceof Error ? err.message : String(err);
+        const errorMsg = getErrorMessage(err);
         setError(errorMsg);
         logError('[useEmbeddedBootstrap]', '설정 업데이트 실패:', err);
         throw err;
@@ -19,7 +19,7 @@ import {
   autoConnectBootstrap,
   BootstrapNodeInfo,
 } from '../services/gridService';
-import { isNative } from '../utils/tauri';
+import { isNative, getErrorMessage } from '../utils/tauri';
 
 interface UseGridSwarmOptions {
   onComplete?: (jobId: string) => void;
@@ -83,7 +83,7 @@ export function useGridSwarm(
       setBootstrapConnected(result.connected > 0);
       options.onBootstrapConnected?.(result.nodes);
     } catch (e) {
-      setError(`부트스트랩 발견 실패: ${e}`);
+      setError(`부트스트랩 발견 실패: ${getErrorMessage(e)}`);
     }
   }, [options]);
 
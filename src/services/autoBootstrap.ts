@@ -80,21 +80,14 @@ export async function checkBootstrapNodeStatus(): Promise<BootstrapNodeStatus> {
         const controller = new AbortController();
         const timeoutId = setTimeout(() => controller.abort(), 2000);
 
-        const response = await fetch(`http://localhost:${port}/stats`, {
+        // /stats는 토큰이 필요하므로 헬스 체크로 확인
+        const response = await fetch(`http://localhost:${port}/health`, {
           signal: controller.signal,
-          headers: {
-            Accept: 'application/json',
-          },
         });
 
         clearTimeout(timeoutId);
         if (response.ok) {
-          const stats = await response.json();
-          logInfo(
-            '[AutoBootstrap]',
-            `✅ 부트스트랩 노드 발견: 포트 ${port}`,
-            stats
-          );
+          logInfo('[AutoBootstrap]', `✅ 부트스트랩 노드 발견: 포트 ${port}`);
           return {
             isRunning: true,
             port: port,
@@ -159,7 +152,7 @@ export function getBootstrapNodeInstructions(): string {
    ./target/debug/ponswarp-bootstrap
 
 4. 실행 확인:
-   curl http://localhost:6883/stats
+   curl http://localhost:6883/health
 
 팁:
 - 여러 PC에서 실행할수록 네트워크 안정성이 향상됩니다
@@ -17,6 +17,17 @@ export interface BootstrapConfig {
   enable_mdns_discovery: boolean;
   enable_relay: boolean;
   max_relay_sessions: number;
+  dht?: DhtTuning;
+  stats_api_token?: string | null;
+  stats_localhost_only?: boolean;
+}
+
+export interface DhtTuning {
+  k_bucket_size: number;
+  replication_factor: number;
+  lookup_alpha: number;
+  bucket_refresh_secs: number;
+  provider_ttl_secs: number;
 }
 
 export interface BoundPorts {
@@ -30,12 +41,16 @@ export interface DhtStats {
   providers_stored: number;
   messages_received: number;
   messages_sent: number;
+  messages_dropped_flood: number;
+  messages_dropped_malformed: number;
 }
 
 export interface RelayStats {
   active_sessions: number;
   total_connections: number;
   bytes_relayed: number;
+  sessions_evicted_idle: number;
+  sessions_evicted_lifetime: number;
 }
 
 export interface BootstrapStatus {
@@ -126,6 +141,18 @@ export async function getEmbeddedBootstrapStatus(): Promise<BootstrapStatus> {
   }
 }
 
+/**
+ * Stats API 토큰 조회 (`X-Api-Token` 헤더로 전송)
+ */
+export async function getBootstrapStatsToken(): Promise<string | null> {
+  try {
+    return await invoke<string | null>('get_bootstrap_stats_token');
+  } catch (error) {
+    logError('[EmbeddedBootstrap]', 'Stats 토큰 조회 실패:', error);
+    throw error;
+  }
+}
+
 /**
  * 부트스트랩 설정 업데이트
  */
@@ -160,6 +187,191 @@ export async function updateBootstrapConfig(
   }
 }
 
+// 🆕 오프라인 우편함에 맡기는 시그널 (Rust Command와 같은 형식)
+export type OfflineSignalCommand =
+  | { type: 'Offer'; room_id: string; sdp: string; target: string | null }
+  | { type: 'Answer'; room_id: string; sdp: string; target: string | null };
+
+// 🆕 발신자가 서명한 오프라인 시그널
+export interface SignedSignal {
+  from: string; // 발신자 노드 ID
+  to: string;
+  command: OfflineSignalCommand;
+  createdAt: number; // Unix 초
+  ttlSecs: number;
+  signature: string;
+}
+
+/**
+ * 🆕 이 기기의 시그널링 노드 ID (오프라인 우편함 주소)
+ */
+export async function getSignalingNodeId(): Promise<string> {
+  return invoke<string>('get_signaling_node_id');
+}
+
+/**
+ * 🆕 오프라인 피어 앞으로 시그널링 메시지(Offer/Answer/Bye 등)를 부트스트랩 우편함에 맡기기
+ * @param bootstrapAddr 부트스트랩 QUIC 릴레이 주소 (ip:port)
+ * @param ttlSecs 보관 시간 (기본 1시간, 최대 24시간)
+ */
+export async function queueOfflineSignal(
+  bootstrapAddr: string,
+  toNodeId: string,
+  command: OfflineSignalCommand,
+  ttlSecs?: number
+): Promise<void> {
+  await invoke('queue_offline_signal', {
+    bootstrapAddr,
+    toNodeId,
+    command,
+    ttlSecs,
+  });
+}
+
+/**
+ * 🆕 다시 접속했을 때 우편함에 쌓인 시그널 가져오기 (가져간 시그널은 삭제됨)
+ */
+export async function pollOfflineSignals(
+  bootstrapAddr: string
+): Promise<SignedSignal[]> {
+  return invoke<SignedSignal[]>('poll_offline_signals', { bootstrapAddr });
+}
+
+// 🆕 접속 중인 연락처 (접속 명부 조회 결과)
+export interface PresenceEntry {
+  nodeId: string;
+  candidates: string[]; // "ip:port" (부트스트랩이 본 공인 주소 포함)
+  lastSeenSecs: number;
+}
+
+/**
+ * 🆕 부트스트랩 접속 명부에 이 기기 등록 시작 (주기적으로 갱신, QUIC 서버가 실행 중이어야 함)
+ * @param bootstrapAddr 부트스트랩 QUIC 릴레이 주소 (ip:port)
+ */
+export async function startPresence(bootstrapAddr: string): Promise<void> {
+  await invoke('start_presence', { bootstrapAddr });
+}
+
+/**
+ * 🆕 접속 명부 등록 중단 (부트스트랩에 오프라인 알림)
+ */
+export async function stopPresence(): Promise<void> {
+  await invoke('stop_presence');
+}
+
+/**
+ * 🆕 알고 있는 연락처(노드 ID) 중 지금 접속 중인 피어 조회
+ * @param bootstrapAddr 생략하면 등록 중인 부트스트랩에 조회
+ */
+export async function getRoster(
+  nodeIds: string[],
+  bootstrapAddr?: string
+): Promise<PresenceEntry[]> {
+  return invoke<PresenceEntry[]>('get_roster', { nodeIds, bootstrapAddr });
+}
+
+// 🆕 방 코드 생성 결과
+export interface RoomCode {
+  code: string;
+  expiresInSecs: number;
+}
+
+// 🆕 방에 수신자가 참여함 (수신자 후보 주소 포함)
+export interface RoomPeerJoinedEvent {
+  code: string;
+  candidates: string[];
+}
+
+/**
+ * 🆕 후보 주소들에 동시에 연결을 시도해 먼저 성공한 주소 사용
+ * @returns 연결된 주소
+ */
+export async function connectToPeerRace(
+  peerId: string,
+  candidates: string[]
+): Promise<string> {
+  return invoke<string>('connect_to_peer_race', { peerId, candidates });
+}
+
+// 🆕 ICE 재시작 결과
+export interface IceRestartResult {
+  /** migrated: 기존 연결 유지, reconnected: 새 후보로 재연결, pending: 상대 후보 필요 */
+  mode: 'migrated' | 'reconnected' | 'pending';
+  /** 상대에게 시그널링으로 다시 보낼 이 기기의 후보 주소 */
+  candidates: string[];
+}
+
+// 🆕 ICE 재시작으로 경로가 복구됨
+export interface IceRestartedEvent {
+  peerId: string;
+  mode: 'migrated' | 'reconnected';
+  remoteAddr: string;
+}
+
+/**
+ * 🆕 네트워크 변경 후 ICE 재시작 (진행 중인 전송은 유지)
+ * @param remoteCandidates 시그널링으로 다시 받은 상대 후보 (없으면 기존 연결 마이그레이션만 시도)
+ */
+export async function restartIce(
+  peerId: string,
+  remoteCandidates?: string[]
+): Promise<IceRestartResult> {
+  return invoke<IceRestartResult>('restart_ice', { peerId, remoteCandidates });
+}
+
+/**
+ * 🆕 ICE 재시작 완료 이벤트 구독
+ */
+export async function onIceRestarted(
+  callback: (event: IceRestartedEvent) => void
+): Promise<UnlistenFn> {
+  return await listen<IceRestartedEvent>('ice-restarted', event => {
+    logInfo('[EmbeddedBootstrap]', 'ICE 재시작:', event.payload);
+    callback(event.payload);
+  });
+}
+
+/**
+ * 🆕 송신자: 부트스트랩에 짧은 방 코드 등록 (10분 후 만료)
+ */
+export async function createRoomCode(bootstrapAddr: string): Promise<RoomCode> {
+  return invoke<RoomCode>('create_room_code', { bootstrapAddr });
+}
+
+/**
+ * 🆕 수신자: 방 코드로 송신자에 연결
+ * @returns 연결된 송신자의 피어 ID
+ */
+export async function joinRoomCode(
+  bootstrapAddr: string,
+  code: string
+): Promise<string> {
+  return invoke<string>('join_room_code', { bootstrapAddr, code });
+}
+
+/**
+ * 🆕 방 코드에 수신자가 참여한 이벤트 구독
+ */
+export async function onRoomPeerJoined(
+  callback: (event: RoomPeerJoinedEvent) => void
+): Promise<UnlistenFn> {
+  return await listen<RoomPeerJoinedEvent>('room-peer-joined', event => {
+    logInfo('[EmbeddedBootstrap]', '방 참여:', event.payload);
+    callback(event.payload);
+  });
+}
+
+/**
+ * 🆕 방 코드가 아무도 참여하지 않은 채 만료된 이벤트 구독
+ */
+export async function onRoomCodeExpired(
+  callback: (code: string) => void
+): Promise<UnlistenFn> {
+  return await listen<{ code: string }>('room-code-expired', event => {
+    callback(event.payload.code);
+  });
+}
+
 /**
  * 부트스트랩 상태 변경 이벤트 구독
  */
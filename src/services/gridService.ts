@@ -13,6 +13,10 @@ import {
   GridStateUpdateEvent,
   GridSwarmState,
   GridPeerDiscoveredEvent,
+  GridHashingProgressEvent,
+  GridHashAlgorithm,
+  GridPonsFile,
+  GridPonsFileOptions,
   convertGridState,
   GRID_EVENTS,
 } from '../types/grid';
@@ -40,10 +44,16 @@ export async function getGridInfo(): Promise<GridInfo> {
 
 /**
  * 파일 메타데이터 생성 (Grid 전송 준비)
+ *
+ * jobId를 주면 해시 진행률이 grid-hashing-progress 이벤트로 오고
+ * cancelGridHashing(jobId)로 취소할 수 있습니다.
+ * hashAlgorithm 기본값은 sha256 (blake3가 더 빠르지만 새 버전 피어만 검증 가능)
  */
 export async function createGridMetadata(
   filePath: string,
-  pieceSize?: number
+  pieceSize?: number,
+  jobId?: string,
+  hashAlgorithm?: GridHashAlgorithm
 ): Promise<{
   infoHash: string;
   fileName: string;
@@ -51,13 +61,55 @@ export async function createGridMetadata(
   pieceSize: number;
   totalPieces: number;
   merkleRoot?: string;
+  hashAlgorithm: GridHashAlgorithm;
 }> {
   return invoke('create_grid_metadata', {
     filePath,
     pieceSize,
+    jobId,
+    hashAlgorithm,
   });
 }
 
+/**
+ * 메타데이터를 .pons 파일로 저장 (메일/공유 드라이브로 전달)
+ */
+export async function saveGridMetadata(
+  filePath: string,
+  outputPath: string,
+  options: GridPonsFileOptions = {}
+): Promise<{ path: string; infoHash: string }> {
+  return invoke('save_grid_metadata', { filePath, outputPath, ...options });
+}
+
+/**
+ * .pons 파일 읽기 (다운로드는 경로를 start_grid_download의 source로 전달)
+ */
+export async function loadGridMetadata(path: string): Promise<GridPonsFile> {
+  return invoke<GridPonsFile>('load_grid_metadata', { path });
+}
+
+/**
+ * 진행 중인 메타데이터 해시 계산 취소
+ */
+export async function cancelGridHashing(jobId: string): Promise<void> {
+  return invoke('cancel_grid_hashing', { jobId });
+}
+
+/**
+ * 메타데이터 해시 진행률 리스너 등록
+ */
+export function onGridHashingProgress(
+  callback: (event: GridHashingProgressEvent) => void
+): Promise<UnlistenFn> {
+  return listen<GridHashingProgressEvent>(
+    GRID_EVENTS.HASHING_PROGRESS,
+    event => {
+      callback(event.payload);
+    }
+  );
+}
+
 /**
  * Grid 상태 업데이트 리스너 등록
  */
@@ -15,6 +15,9 @@ import { isNative, getDiscoveredPeers, DiscoveredPeer } from '../utils/tauri';
 
 type MessageHandler = (data: unknown) => void;
 
+// 🆕 Rust protocol::SIGNALING_VERSION과 맞춤 (v2: Renegotiate/Bye)
+const SIGNALING_VERSION = 2;
+
 class NativeSignalingService {
   private handlers: Map<string, MessageHandler[]> = new Map();
   private nodeId: string | null = null;
@@ -86,6 +89,27 @@ class NativeSignalingService {
         candidate: { candidate: payload.candidate },
       });
     });
+
+    // 🆕 재협상 (후보 추가, 전송 방식 변경)
+    listen('signaling-renegotiate', event => {
+      console.log('[NativeSignaling] 📨 Renegotiate received:', event.payload);
+      const payload = event.payload as any;
+      this.emit('renegotiate', {
+        from: payload.from,
+        candidates: payload.candidates ?? [],
+        transport: payload.transport ?? null,
+      });
+    });
+
+    // 🆕 상대가 세션을 종료함
+    listen('signaling-bye', event => {
+      console.log('[NativeSignaling] 📨 Bye received:', event.payload);
+      const payload = event.payload as any;
+      this.emit('bye', {
+        from: payload.from,
+        reason: payload.reason ?? null,
+      });
+    });
   }
 
   private startPeerPolling() {
@@ -155,6 +179,7 @@ class NativeSignalingService {
       room_id: roomId,
       sdp: offer.sdp,
       target,
+      version: SIGNALING_VERSION,
     };
 
     invoke('send_signaling_message', {
@@ -187,6 +212,7 @@ class NativeSignalingService {
       room_id: roomId,
       sdp: answer.sdp,
       target,
+      version: SIGNALING_VERSION,
     };
 
     invoke('send_signaling_message', {
@@ -237,6 +263,54 @@ class NativeSignalingService {
       });
   }
 
+  /**
+   * 🆕 세션을 유지한 채 후보 추가/전송 방식 변경을 알림
+   */
+  sendRenegotiate(
+    roomId: string,
+    target: string,
+    candidates: string[],
+    transport?: string
+  ): void {
+    const message = {
+      type: 'Renegotiate',
+      room_id: roomId,
+      target,
+      version: SIGNALING_VERSION,
+      candidates,
+      transport: transport ?? null,
+    };
+
+    invoke('send_signaling_message', { peerId: target, message })
+      .then(() => {
+        console.log(`[NativeSignaling] ✅ Renegotiate sent to ${target}`);
+      })
+      .catch(error => {
+        console.error('[NativeSignaling] ❌ Failed to send renegotiate:', error);
+        this.emit('error', { message: 'Failed to send renegotiate' });
+      });
+  }
+
+  /**
+   * 🆕 세션 종료를 명시적으로 알림 (연결 끊김을 기다리지 않음)
+   */
+  sendBye(roomId: string, target: string, reason?: string): void {
+    const message = {
+      type: 'Bye',
+      room_id: roomId,
+      target,
+      reason: reason ?? null,
+    };
+
+    invoke('send_signaling_message', { peerId: target, message })
+      .then(() => {
+        console.log(`[NativeSignaling] ✅ Bye sent to ${target}`);
+      })
+      .catch(error => {
+        console.error('[NativeSignaling] ❌ Failed to send bye:', error);
+      });
+  }
+
   async requestTurnConfig(_roomId: string): Promise<unknown> {
     console.log('[NativeSignaling] Native 모드에서는 TURN 불필요 (직접 연결)');
     return {
@@ -22,7 +22,14 @@
 
 import { invoke } from '@tauri-apps/api/core';
 import { listen, UnlistenFn } from '@tauri-apps/api/event';
-import { isNative, getDiscoveredPeers, DiscoveredPeer } from '../utils/tauri';
+import {
+  isNative,
+  getDiscoveredPeers,
+  DiscoveredPeer,
+  AppError,
+  getErrorMessage,
+  isAppError,
+} from '../utils/tauri';
 import { logInfo, logError, logWarn, logDebug } from '../utils/logger';
 import { rustSignalingAdapter } from './signaling-adapter';
 import { initWasmCore, Zip64Stream } from './wasmCore';
@@ -50,6 +57,460 @@ export interface TransferProgress {
   state: string;
 }
 
+// 🆕 Rust 전송 레지스트리 수명주기 이벤트 (모든 엔진 공통)
+export interface TransferLifecycleEvent {
+  jobId: string;
+  peerId: string;
+  kind:
+    | 'file'
+    | 'multistream'
+    | 'zip'
+    | 'grid'
+    | 'multisource'
+    | 'directory';
+  status: 'running' | 'paused' | 'completed' | 'failed' | 'cancelled';
+  progress: number; // 0~100
+  bytesTransferred: number;
+  totalBytes: number;
+  speedBps: number;
+  error: AppError | null;
+  // 🆕 수신 시 기존 파일 처리 결과
+  action: 'created' | 'overwritten' | 'skipped' | 'renamed' | null;
+  elapsedSecs: number;
+}
+
+// 🆕 전송 작업의 초당 처리량 샘플 (속도 그래프용)
+export interface SpeedSample {
+  elapsedSecs: number; // 작업 시작 후 경과 초
+  bytesPerSec: number;
+}
+
+// 🆕 폴더 전송 포함/제외 글롭 패턴 (예: exclude: ['node_modules', '.git', '*.tmp'])
+export interface FolderFilter {
+  include?: string[]; // 파일에만 적용, 생략하면 모든 파일
+  exclude?: string[]; // 제외된 폴더는 스캔하지 않음
+}
+
+// 🆕 전송 전 크기 미리 계산 결과 (dry-run)
+export interface TransferEstimate {
+  fileCount: number;
+  dirCount: number;
+  totalBytes: number;
+  largestFiles: { size: number; path: string }[]; // 큰 순서
+}
+
+// 🆕 스트리밍 매니페스트 폴더 전송 결과
+export interface DirStreamSummary {
+  root: string; // 송신: 보낸 폴더, 수신: 저장한 폴더
+  files: number;
+  bytes: number;
+}
+
+// 🆕 작업별 전송 파라미터 (생략하면 기본값/자동 조절, 범위를 벗어나면 INVALID_INPUT)
+export interface TransferTuning {
+  blockSize?: number; // 멀티스트림 블록 크기 (256KB~16MB)
+  maxConcurrentStreams?: number; // 멀티스트림 동시 스트림 상한 (2~128)
+  chunkSize?: number; // 직접 전송 청크 크기 (64KB~16MB)
+}
+
+// 🆕 1:N 브로드캐스트 전송의 대상별 결과
+export interface BroadcastDestinationResult {
+  peerId: string;
+  jobId: string; // 대상별 작업 ID (`<jobId>@<peerId>`)
+  bytesSent: number;
+  error: AppError | null;
+}
+
+// 🆕 다중 소스 다운로드 공유 정보
+export interface MultiSourceShare {
+  infoHash: string;
+  fileName: string;
+  fileSize: number;
+  pieceSize: number;
+  totalPieces: number;
+}
+
+// 🆕 다중 소스 다운로드 결과 (피어별 기여도 포함)
+export interface MultiSourceResult {
+  path: string;
+  sources: {
+    peerId: string;
+    pieces: number;
+    bytes: number;
+    failures: number;
+  }[];
+}
+
+// 🆕 부분 요청 제공 목록 (송신 측이 제공한 파일/폴더)
+export interface OfferedFile {
+  path: string; // 제공 루트 기준 상대 경로 ('/' 구분)
+  size: number;
+}
+
+export interface OfferCatalog {
+  offerId: string;
+  name: string;
+  files: OfferedFile[];
+  totalSize: number;
+}
+
+// 🆕 바이트 범위 (end는 포함하지 않으며, 없으면 파일 끝까지)
+export interface ByteRange {
+  start: number;
+  end?: number;
+}
+
+// 🆕 수신 측 선택 (파일 또는 하위 폴더 경로, 범위는 파일에만)
+export interface FileSelection {
+  path: string;
+  range?: ByteRange;
+}
+
+export interface RangeDownloadResult {
+  path: string;
+  destination: string;
+  bytes: number;
+  range: ByteRange | null;
+  action: 'created' | 'overwritten' | 'renamed' | 'skipped';
+}
+
+// 🆕 페어링된 피어 (양쪽의 확인 코드가 같아야 안전)
+export interface PairedPeer {
+  peerId: string;
+  verificationCode: string;
+  pairedAt: number; // Unix ms
+}
+
+// 🆕 대역폭 측정 결과
+export interface BenchmarkRttStats {
+  minMs: number;
+  avgMs: number;
+  maxMs: number;
+  samples: number;
+}
+
+export interface BenchmarkDirectionResult {
+  bytes: number;
+  durationMs: number;
+  throughputMbps: number;
+  /** 부하 중 RTT */
+  rtt: BenchmarkRttStats;
+}
+
+export interface BenchmarkReport {
+  peerId: string;
+  idleRttMs: number;
+  upload: BenchmarkDirectionResult;
+  download: BenchmarkDirectionResult;
+}
+
+// 🆕 QR 코드로 연결한 결과
+export interface QrConnectionResult {
+  peerId: string;
+  nodeId: string;
+  fingerprint: string;
+  pairing: PairedPeer;
+}
+
+// 🆕 수신 경로에 같은 이름의 파일이 있을 때의 처리
+export type OverwritePolicy = 'overwrite' | 'skip' | 'rename' | 'fail';
+
+// 🆕 전송 중 피어 연결 품질 (3초마다)
+export type ConnectionPathType = 'loopback' | 'lan' | 'wan' | 'relay';
+export type ConnectionQualityIssue = 'HIGH_LOSS' | 'RTT_SPIKE';
+
+export interface ConnectionQuality {
+  peerId: string;
+  remoteAddr: string;
+  pathType: ConnectionPathType;
+  rttMs: number;
+  minRttMs: number;
+  /** 직전 이벤트 이후 손실률 (0 ~ 1) */
+  lossRate: number;
+  /** 혼잡 윈도우 (바이트) */
+  cwnd: number;
+  congestionEvents: number;
+  mtu: number;
+  issues: ConnectionQualityIssue[];
+}
+
+// 🆕 피어에게서 받은 텍스트
+export interface ReceivedText {
+  peerId: string;
+  messageId: string;
+  content: string;
+  timestamp: number; // Unix 초
+}
+
+// 🆕 신뢰하는 피어 자동 수락 규칙
+export interface AutoAcceptRule {
+  id: string; // 비어 있으면 저장 시 생성
+  fingerprint: string; // 보낸 기기의 인증서 지문 (SHA-256 hex, 연락처를 지정하면 비워 둠)
+  contactId?: string | null; // 🆕 지문 대신 연락처 지정
+  label?: string | null;
+  saveDir: string;
+  maxBytes?: number | null; // 없으면 제한 없음
+  enabled: boolean;
+}
+
+// 🆕 연락처 (노드 ID/인증서 지문 중 하나 이상 필요)
+export interface Contact {
+  id: string; // 비어 있으면 저장 시 생성
+  nickname: string;
+  nodeId?: string | null; // 시그널링 노드 ID (Ed25519 공개 키 hex)
+  fingerprint?: string | null; // QUIC 인증서 지문 (SHA-256 hex)
+  addresses: string[]; // 마지막으로 확인된 주소 (최근 순)
+  lastSeen?: number | null; // Unix 초
+  addedAt: number; // Unix 초
+  trusted: boolean; // 🆕 짧은 인증 문자열로 확인한 연락처
+  verifiedAt?: number | null; // Unix 초
+}
+
+// 🆕 새 연락처 확인용 짧은 인증 문자열 (양쪽 화면의 단어가 같아야 함)
+export interface SasVerification {
+  peerId: string;
+  words: string[];
+  nodeId?: string | null;
+  fingerprint?: string | null;
+}
+
+export interface SasVerificationRequiredEvent {
+  verification: SasVerification;
+  contact?: Contact | null; // 키가 같은 (아직 확인하지 않은) 연락처
+}
+
+export interface AutoAcceptLogEntry {
+  jobId: string;
+  fileName: string;
+  fileSize: number;
+  fingerprint: string;
+  ruleId: string;
+  accepted: boolean;
+  reason: string;
+  timestamp: number; // Unix 초
+}
+
+// 🆕 피어별 하루/세션별 수신 한도 (없으면 제한 없음)
+export interface QuotaLimits {
+  perPeerDailyBytes?: number | null;
+  perSessionBytes?: number | null;
+}
+
+export interface PeerQuotaUsage {
+  peer: string; // 인증서 지문 (없는 피어는 모두 'anonymous')
+  day: number; // UNIX epoch 기준 일 수 (UTC)
+  bytes: number;
+}
+
+export interface ReceiveQuotaStatus {
+  limits: QuotaLimits;
+  usage: PeerQuotaUsage[];
+}
+
+// 🆕 앱 전체 처리량 (모든 서브시스템 합계와 내역)
+export interface SubsystemMetrics {
+  subsystem: 'direct' | 'multistream' | 'grid' | 'relay' | 'udp';
+  bytes: number; // 앱 시작 후 처리한 바이트
+  bytesPerSec: number;
+  active: number; // 진행 중인 작업/세션 수
+}
+
+export interface AppMetrics {
+  uptimeSecs: number;
+  totalBytes: number;
+  totalBytesPerSec: number;
+  active: number;
+  subsystems: SubsystemMetrics[];
+}
+
+// 🆕 전송 버퍼 메모리 예산 (모든 엔진 공용)
+export interface MemoryBudgetStatus {
+  totalBytes: number;
+  usedBytes: number;
+  waits: number; // 예산이 모자라 버퍼 할당을 기다린 횟수
+}
+
+// 🆕 노드 키로 서명한 전송 감사 로그 항목
+export interface AuditEntry {
+  seq: number;
+  timestamp: number; // Unix 초
+  nodeId: string; // 서명한 노드 ID (Ed25519 공개 키 hex)
+  jobId: string;
+  direction: 'send' | 'receive' | null;
+  kind: TransferLifecycleEvent['kind'];
+  peerId: string;
+  peerFingerprint: string | null;
+  fileName: string | null;
+  bytes: number;
+  sha256: string | null;
+  status: TransferLifecycleEvent['status'];
+  error: string | null;
+  prevHash: string;
+  signature: string;
+}
+
+export interface AuditVerification {
+  valid: boolean;
+  entries: number; // 검증을 통과한 항목 수
+  nodeIds: string[];
+  firstInvalidLine: number | null;
+  error: string | null;
+}
+
+// 🆕 데스크톱 알림 종류별 사용 여부
+export interface NotificationSettings {
+  incomingOffer: boolean;
+  transferComplete: boolean;
+  transferFailed: boolean; // 사용자가 취소한 전송은 제외
+}
+
+// 🆕 브라우저 수신용 HTTP 공유 링크
+export interface HttpShareOptions {
+  expiresInSecs?: number; // 기본 1시간, 최대 24시간
+  maxDownloads?: number; // 기본 1 (일회용)
+  rateLimitBps?: number; // 다운로드 속도 상한
+}
+
+export interface HttpShareLink {
+  id: string;
+  url: string;
+  fileName: string;
+  fileSize: number;
+  expiresAt: number; // Unix 초
+  maxDownloads: number;
+  downloads: number; // 시작된 다운로드 수
+  rateLimitBps: number | null;
+}
+
+export interface HttpShareEvent {
+  shareId: string;
+  remoteAddr: string;
+  kind: 'started' | 'completed' | 'failed';
+  bytes: number;
+  error: string | null;
+}
+
+// 🆕 수신 중인 미디어 미리보기 링크 (loopback, <video>/<audio> src로 사용)
+export interface MediaPreviewLink {
+  jobId: string;
+  url: string;
+  availableBytes: number;
+  totalBytes: number; // 수신 시작 전이면 0
+}
+
+// 🆕 QUIC을 쓸 수 없는 송신자를 위한 tus 업로드 엔드포인트
+export interface TusConfig {
+  saveDir: string;
+  overwritePolicy?: OverwritePolicy;
+  maxSize?: number | null; // 업로드 하나의 최대 크기
+  port?: number; // 0 = 임의 포트
+}
+
+export interface TusEndpoint {
+  url: string; // tus 클라이언트에 넣을 엔드포인트 (토큰 포함)
+  saveDir: string;
+  maxSize: number | null;
+}
+
+// 🆕 전송 저장 대상 (로컬 폴더 또는 S3 호환 버킷)
+export interface S3Config {
+  endpoint: string; // 예: "http://minio.local:9000"
+  region?: string; // 기본 us-east-1
+  bucket: string;
+  accessKeyId: string;
+  secretAccessKey: string;
+  prefix?: string; // 오브젝트 키 앞 경로
+  pathStyle?: boolean; // 기본 true (MinIO)
+}
+
+export type StorageTarget =
+  | { type: 'local'; dir: string; overwritePolicy?: OverwritePolicy }
+  | ({ type: 's3' } & S3Config);
+
+// 🆕 내보낸 작업 매니페스트 (멀티스트림 블록 맵 또는 Grid 메타데이터)
+export type JobManifest = {
+  version: number;
+  jobId: string;
+  createdAt: number;
+} & (
+  | {
+      kind: 'multistream';
+      manifest: {
+        job_id: string;
+        file_name: string;
+        file_size: number;
+        block_size: number;
+        total_blocks: number;
+      };
+      blockChecksums: number[];
+      sha256: string;
+    }
+  | {
+      kind: 'grid';
+      metadata: {
+        info_hash: number[];
+        file_name: string;
+        file_size: number;
+        piece_size: number;
+        total_pieces: number;
+      };
+    }
+);
+
+export interface ManifestVerification {
+  matches: boolean;
+  sizeMatches: boolean;
+  mismatchedBlocks: number[];
+  totalBlocks: number;
+}
+
+// 🆕 웹 버전(브라우저) 피어와의 WebRTC 데이터 채널 시그널링 (simple-peer signal 형식)
+export type WebRtcSignal =
+  | { type: 'offer'; sdp: string }
+  | { type: 'answer'; sdp: string }
+  | { type: 'candidate'; candidate: RTCIceCandidateInit };
+
+export interface WebRtcSignalEvent {
+  sessionId: string; // 전송 작업 ID와 같음
+  signal: WebRtcSignal;
+}
+
+export interface TransferOfferResponse {
+  job_id: string;
+  approved: boolean;
+  reason: string | null;
+  timestamp: number;
+}
+
+// 🆕 폴더 동기화
+export type ConflictPolicy =
+  | 'newerWins'
+  | 'sourceWins'
+  | 'keepLocal'
+  | 'keepBoth';
+
+export interface FolderSyncEvent {
+  syncId: string;
+  path: string; // 동기화 폴더 기준 상대 경로
+  action:
+    | 'pushed'
+    | 'received'
+    | 'deleted'
+    | 'upToDate'
+    | 'conflict'
+    | 'failed';
+  error: string | null;
+}
+
+export interface FolderSyncInfo {
+  syncId: string;
+  direction: 'outgoing' | 'incoming';
+  root: string;
+  peerId: string | null;
+  policy: ConflictPolicy | null;
+  running: boolean;
+}
+
 export interface NativeTransferConfig {
   peerId: string;
   peerAddress: string;
@@ -61,22 +522,20 @@ export interface NativePeerInfo {
   roomId: string;
 }
 
-function normalizeRustTransferState(rawState: unknown): string {
-  if (typeof rawState === 'string') return rawState.toUpperCase();
-  if (rawState && typeof rawState === 'object') {
-    const keys = Object.keys(rawState as Record<string, unknown>);
-    if (keys.length > 0) return keys[0].toUpperCase();
-  }
-  return 'TRANSFERRING';
-}
-
-function mapTransferStateToUiStatus(state: string): string {
-  // SenderView/ReceiverView가 사용하는 status 값에 맞춰 최소 매핑만 수행
-  if (state === 'PREPARING') return 'PREPARING';
-  if (state === 'TRANSFERRING') return 'TRANSFERRING';
-  if (state === 'COMPLETED') return 'COMPLETED';
-  if (state === 'FAILED') return 'ERROR';
-  return state;
+function toTransferProgress(
+  event: TransferLifecycleEvent
+): Partial<TransferProgress> {
+  return {
+    jobId: event.jobId,
+    progressPercent: event.progress,
+    progress: event.progress,
+    speedBps: event.speedBps,
+    speed: event.speedBps,
+    bytesTransferred: event.bytesTransferred,
+    acknowledgedBytes: event.bytesTransferred,
+    totalBytes: event.totalBytes,
+    state: event.status.toUpperCase(),
+  };
 }
 
 /**
@@ -112,6 +571,9 @@ class NativeTransferService {
   // 🆕 호스트 모드 여부 (Sender = true, Receiver = false)
   private isHost = false;
 
+  // 🆕 수신 시 기존 파일 처리 정책
+  private overwritePolicy: OverwritePolicy = 'overwrite';
+
   /**
    * 이벤트 리스너 설정
    */
@@ -143,116 +605,78 @@ class NativeTransferService {
       logWarn('[NativeTransfer]', '런타임 정보 조회 실패:', e);
     }
 
-    // Rust 백엔드 이벤트 수신 (스로틀링 적용)
-    // 🚨 Rust는 snake_case 필드명 사용: progress_percent, bytes_transferred, speed_bps, total_bytes
-    const progressUnlisten = await listen<any>('transfer-progress', event => {
-      const now = Date.now();
-      const payload = event.payload;
-
-      // 🆕 snake_case -> camelCase 변환
-      const progressPercent =
-        payload?.progress_percent ?? payload?.progressPercent ?? 0;
-      const bytesTransferred =
-        payload?.bytes_transferred ?? payload?.bytesTransferred ?? 0;
-      const speedBps = payload?.speed_bps ?? payload?.speedBps ?? 0;
-      const totalBytes = payload?.total_bytes ?? payload?.totalBytes ?? 0;
-      const acknowledgedBytes =
-        payload?.acknowledged_bytes ?? payload?.acknowledgedBytes ?? 0;
-      const rawState = payload?.state;
-      const state = normalizeRustTransferState(rawState);
-
-      // 🆕 null 체크 - payload가 유효한지 확인
-      if (!payload || typeof progressPercent !== 'number') {
-        logWarn('[NativeTransfer]', '잘못된 진행률 데이터:', payload);
-        return;
-      }
-
-      // 🆕 스로틀링: 200ms마다 또는 100% 완료 시에만 emit
-      if (
-        now - this.lastProgressEmit >= this.PROGRESS_THROTTLE_MS ||
-        progressPercent >= 100
-      ) {
-        this.lastProgressEmit = now;
-
-        const progressData: Partial<TransferProgress> = {
-          jobId: payload?.job_id || payload?.jobId,
-          progressPercent: progressPercent,
-          progress: progressPercent,
-          speedBps: speedBps,
-          speed: speedBps,
-          bytesTransferred: bytesTransferred,
-          acknowledgedBytes: acknowledgedBytes,
-          totalBytes: totalBytes,
-          state,
-        };
-
-        this.emit('progress', progressData);
-        // 상태도 같이 전달 (Sender/Receiver UI가 단계 전환에 활용)
-        this.emit('status', mapTransferStateToUiStatus(state));
-      }
-    });
-    this.unlisteners.push(progressUnlisten);
-
-    // 🆕 Multistream Progress Listener
-    const multistreamProgressUnlisten = await listen<any>(
-      'multistream-progress',
+    // Rust 전송 레지스트리 이벤트 수신 (File/Multistream/Zip/Grid 공통 스키마)
+    const progressUnlisten = await listen<TransferLifecycleEvent>(
+      'transfer-progress',
       event => {
-        const now = Date.now();
         const payload = event.payload;
+        const now = Date.now();
 
-        const progressPercent =
-          payload?.progress_percent ?? payload?.progressPercent ?? 0;
-        const bytesTransferred =
-          payload?.bytes_transferred ?? payload?.bytesTransferred ?? 0;
-        const acknowledgedBytes =
-          payload?.acknowledged_bytes ?? payload?.acknowledgedBytes ?? 0;
-        const speedBps = payload?.speed_bps ?? payload?.speedBps ?? 0;
-        const totalBytes = payload?.total_bytes ?? payload?.totalBytes ?? 0;
-        const rawState = payload?.state;
-        const state = normalizeRustTransferState(rawState);
-
+        // 🆕 스로틀링: 200ms마다 또는 100% 완료 시에만 emit
         if (
           now - this.lastProgressEmit >= this.PROGRESS_THROTTLE_MS ||
-          progressPercent >= 100
+          payload.progress >= 100
         ) {
           this.lastProgressEmit = now;
-
-          const progressData: Partial<TransferProgress> = {
-            jobId: payload?.job_id || payload?.jobId,
-            progressPercent: progressPercent,
-            progress: progressPercent,
-            speedBps: speedBps,
-            speed: speedBps,
-            bytesTransferred: bytesTransferred,
-            acknowledgedBytes: acknowledgedBytes, // 🚀 Sync된 진행률
-            totalBytes: totalBytes,
-            state,
-          };
-
-          this.emit('progress', progressData);
-          this.emit('status', mapTransferStateToUiStatus(state));
+          this.emit('progress', toTransferProgress(payload));
+          this.emit('status', 'TRANSFERRING');
         }
       }
     );
-    this.unlisteners.push(multistreamProgressUnlisten);
+    this.unlisteners.push(progressUnlisten);
+
+    const startedUnlisten = await listen<TransferLifecycleEvent>(
+      'transfer-started',
+      event => {
+        logInfo('[NativeTransfer]', '전송 시작:', event.payload.jobId);
+        this.emit('status', 'TRANSFERRING');
+      }
+    );
+    this.unlisteners.push(startedUnlisten);
 
-    const completeUnlisten = await listen('transfer-complete', event => {
-      logInfo('[NativeTransfer]', '전송 완료:', event.payload);
-      this.emit('complete', event.payload);
-      this.emit('status', 'COMPLETED');
-    });
-    this.unlisteners.push(completeUnlisten);
+    const pausedUnlisten = await listen<TransferLifecycleEvent>(
+      'transfer-paused',
+      event => {
+        logInfo('[NativeTransfer]', '전송 일시정지:', event.payload.jobId);
+        this.emit('progress', toTransferProgress(event.payload));
+        this.emit('status', 'PAUSED');
+      }
+    );
+    this.unlisteners.push(pausedUnlisten);
+
+    const resumedUnlisten = await listen<TransferLifecycleEvent>(
+      'transfer-resumed',
+      event => {
+        logInfo('[NativeTransfer]', '전송 재개:', event.payload.jobId);
+        this.emit('status', 'TRANSFERRING');
+      }
+    );
+    this.unlisteners.push(resumedUnlisten);
 
-    // 🆕 Multistream Complete Listener
-    const multistreamCompleteUnlisten = await listen(
-      'multistream-complete',
+    const completeUnlisten = await listen<TransferLifecycleEvent>(
+      'transfer-completed',
       event => {
-        logInfo('[NativeTransfer]', '멀티스트림 전송 완료:', event.payload);
+        logInfo('[NativeTransfer]', '전송 완료:', event.payload);
+        this.emit('progress', toTransferProgress(event.payload));
         this.emit('complete', event.payload);
         this.emit('status', 'COMPLETED');
       }
     );
-    this.unlisteners.push(multistreamCompleteUnlisten);
+    this.unlisteners.push(completeUnlisten);
+
+    // 실패/취소 (에러 처리는 invoke 결과에서 수행하므로 여기선 기록만)
+    const failedUnlisten = await listen<TransferLifecycleEvent>(
+      'transfer-failed',
+      event => {
+        logWarn(
+          '[NativeTransfer]',
+          `전송 ${event.payload.status}:`,
+          event.payload.jobId,
+          event.payload.error?.message
+        );
+      }
+    );
+    this.unlisteners.push(failedUnlisten);
 
     // 피어 발견 이벤트
     const peerDiscoveredUnlisten = await listen<NativePeerInfo>(
@@ -264,6 +688,135 @@ class NativeTransferService {
     );
     this.unlisteners.push(peerDiscoveredUnlisten);
 
+    // 🆕 피어가 보낸 텍스트/클립보드 내용
+    const textReceivedUnlisten = await listen<ReceivedText>(
+      'text-received',
+      event => {
+        logInfo(
+          '[NativeTransfer]',
+          '💬 텍스트 수신:',
+          event.payload.peerId,
+          event.payload.messageId
+        );
+        this.emit('text-received', event.payload);
+      }
+    );
+    this.unlisteners.push(textReceivedUnlisten);
+
+    // 🆕 상대가 시작한 페어링의 키 교환 완료 (확인 코드 표시 후 confirmPairing/rejectPairing)
+    const pairingUnlisten = await listen<PairedPeer>(
+      'pairing-completed',
+      event => {
+        logInfo(
+          '[NativeTransfer]',
+          '🔐 페어링 완료:',
+          event.payload.peerId,
+          event.payload.verificationCode
+        );
+        this.emit('pairing-completed', event.payload);
+      }
+    );
+    this.unlisteners.push(pairingUnlisten);
+
+    // 🆕 신뢰하는 연락처가 아닌 상대와 연결됨 (인증 문자열 비교 필요)
+    const sasUnlisten = await listen<SasVerificationRequiredEvent>(
+      'sas-verification-required',
+      event => {
+        logInfo(
+          '[NativeTransfer]',
+          '🔐 연락처 확인 필요:',
+          event.payload.verification.peerId,
+          event.payload.verification.words.join(' ')
+        );
+        this.emit('sas-verification-required', event.payload);
+      }
+    );
+    this.unlisteners.push(sasUnlisten);
+
+    const contactVerifiedUnlisten = await listen('contact-verified', event => {
+      this.emit('contact-verified', event.payload);
+    });
+    this.unlisteners.push(contactVerifiedUnlisten);
+
+    // 🆕 전송 중 연결 품질 (손실/지연 급증 시 "유선 연결 권장" 경고용)
+    const qualityUnlisten = await listen<ConnectionQuality>(
+      'connection-quality',
+      event => {
+        if (event.payload.issues.length > 0) {
+          logWarn(
+            '[NativeTransfer]',
+            '연결 품질 저하:',
+            event.payload.peerId,
+            event.payload.issues
+          );
+        }
+        this.emit('connection-quality', event.payload);
+      }
+    );
+    this.unlisteners.push(qualityUnlisten);
+
+    // 🆕 폴더 동기화 이벤트 (푸시/수신/충돌 등 파일 단위)
+    const folderSyncUnlisten = await listen<FolderSyncEvent>(
+      'folder-sync-event',
+      event => {
+        if (event.payload.action === 'failed') {
+          logWarn(
+            '[NativeTransfer]',
+            '폴더 동기화 실패:',
+            event.payload.path,
+            event.payload.error
+          );
+        }
+        this.emit('folder-sync-event', event.payload);
+      }
+    );
+    this.unlisteners.push(folderSyncUnlisten);
+
+    // 🆕 피어의 전송 요청 (승인 필요 / 자동 수락 규칙으로 수락됨)
+    const transferRequestUnlisten = await listen('transfer-request', event => {
+      logInfo('[NativeTransfer]', '📨 전송 요청 수신:', event.payload);
+      this.emit('transfer-request', event.payload);
+    });
+    this.unlisteners.push(transferRequestUnlisten);
+
+    const autoAcceptedUnlisten = await listen(
+      'transfer-auto-accepted',
+      event => {
+        logInfo('[NativeTransfer]', '🤝 전송 자동 수락:', event.payload);
+        this.emit('transfer-auto-accepted', event.payload);
+      }
+    );
+    this.unlisteners.push(autoAcceptedUnlisten);
+
+    // 🆕 수신 한도 초과로 자동 거절된 전송 요청
+    const quotaExceededUnlisten = await listen(
+      'transfer-quota-exceeded',
+      event => {
+        logWarn('[NativeTransfer]', '📦 수신 한도 초과:', event.payload);
+        this.emit('transfer-quota-exceeded', event.payload);
+      }
+    );
+    this.unlisteners.push(quotaExceededUnlisten);
+
+    // 🆕 HTTP 공유 링크 다운로드 시작/완료/실패
+    const httpShareUnlisten = await listen<HttpShareEvent>(
+      'http-share-event',
+      event => {
+        logInfo('[NativeTransfer]', '🌐 HTTP 공유:', event.payload);
+        this.emit('http-share-event', event.payload);
+      }
+    );
+    this.unlisteners.push(httpShareUnlisten);
+
+    // 🆕 WebRTC 시그널 (시그널링 서버로 웹 버전 피어에게 중계)
+    const webrtcSignalUnlisten = await listen<WebRtcSignalEvent>(
+      'webrtc-signal',
+      event => {
+        this.emit('webrtc-signal', event.payload);
+      }
+    );
+    this.unlisteners.push(webrtcSignalUnlisten);
+
     // 🆕 QUIC 서버에서 피어 연결 수락 이벤트 (Sender용)
     const quicPeerConnectedUnlisten = await listen<{
       peerId: string;
@@ -613,7 +1166,10 @@ class NativeTransferService {
         this.emit('complete', { jobId: transferId });
       } catch (error) {
         this.isZipping = false;
-        this.emit('error', { message: `Zip 전송 실패: ${error}` });
+        this.emit('error', {
+          message: `Zip 전송 실패: ${getErrorMessage(error)}`,
+          code: isAppError(error) ? error.code : undefined,
+        });
         this.emit('status', 'ERROR');
       }
     } else {
@@ -685,8 +1241,7 @@ class NativeTransferService {
       logInfo('[NativeTransfer]', `✅ Zip 스트리밍 완료: ${bytesSent} bytes`);
       return bytesSent;
     } catch (error) {
-      const errorMessage =
-        error instanceof Error ? error.message : String(error);
+      const errorMessage = getErrorMessage(error);
       logError('[NativeTransfer]', '❌ Zip 스트리밍 실패:', errorMessage);
       throw error;
     }
@@ -717,58 +1272,155 @@ class NativeTransferService {
       logInfo('[NativeTransfer]', `✅ Zip 파일 저장 완료: ${savedPath}`);
       return savedPath;
     } catch (error) {
-      const errorMessage =
-        error instanceof Error ? error.message : String(error);
+      const errorMessage = getErrorMessage(error);
       logError('[NativeTransfer]', '❌ Zip 스트리밍 수신 실패:', errorMessage);
       throw error;
     }
   }
 
   /**
-   * 🆕 Zip 파일 압축 해제
+   * 🆕 스트리밍 매니페스트 폴더 전송 (Sender)
+   * 폴더를 스캔하는 동안 이미 찾은 파일부터 전송합니다.
    */
-  async extractZipFile(zipPath: string, outputDir: string): Promise<string[]> {
-    logInfo('[NativeTransfer]', `📂 Zip 압축 해제: ${zipPath} -> ${outputDir}`);
+  async sendDirectoryStream(
+    folderPath: string,
+    jobId: string,
+    symlinkPolicy?: 'skip' | 'follow' | 'recreate',
+    encrypt?: boolean,
+    filter?: FolderFilter
+  ): Promise<DirStreamSummary> {
+    if (!this.connected || !this.currentPeerId) {
+      throw new Error('피어에 연결되어 있지 않습니다.');
+    }
+
+    logInfo('[NativeTransfer]', `📂 스트리밍 폴더 전송 시작: ${folderPath}`);
 
     try {
-      const extractedFiles = await invoke<string[]>('extract_zip_file', {
-        zipPath,
-        outputDir,
-        removeZip: true,
+      const summary = await invoke<DirStreamSummary>('send_directory_stream', {
+        peerId: this.currentPeerId,
+        folderPath,
+        jobId,
+        options: {
+          symlinkPolicy,
+          include: filter?.include,
+          exclude: filter?.exclude,
+          encrypt,
+        },
       });
 
       logInfo(
         '[NativeTransfer]',
-        `✅ ${extractedFiles.length} 파일 압축 해제 완료`
+        `✅ 스트리밍 폴더 전송 완료: ${summary.files} 파일, ${summary.bytes} bytes`
       );
-      return extractedFiles;
+      return summary;
     } catch (error) {
-      const errorMessage =
-        error instanceof Error ? error.message : String(error);
-      logError('[NativeTransfer]', '❌ Zip 압축 해제 실패:', errorMessage);
+      const errorMessage = getErrorMessage(error);
+      logError('[NativeTransfer]', '❌ 스트리밍 폴더 전송 실패:', errorMessage);
       throw error;
     }
   }
 
   /**
-   * 피어 입장 핸들러 (Sender 측)
+   * 🆕 스트리밍 매니페스트 폴더 수신 (Receiver)
    */
-  private handlePeerJoined(data: unknown): void {
-    const payload = data as { socketId?: string; id?: string; peerId?: string };
-    const peerId = payload?.socketId || payload?.id || payload?.peerId;
-
-    if (!peerId) {
-      logWarn('[NativeTransfer]', '피어 입장 이벤트에 ID가 없음:', data);
-      return;
+  async receiveDirectoryStream(
+    saveDir: string,
+    jobId: string
+  ): Promise<DirStreamSummary> {
+    if (!this.connected || !this.currentPeerId) {
+      throw new Error('피어에 연결되어 있지 않습니다.');
     }
 
-    logInfo('[NativeTransfer]', `피어 입장: ${peerId}`);
-    this.emit('peer-joined', { peerId });
+    logInfo('[NativeTransfer]', `📥 스트리밍 폴더 수신 대기: ${saveDir}`);
 
-    // 대기 중인 Manifest가 있으면 전송
-    if (this.pendingManifest && this.currentRoomId) {
-      logInfo('[NativeTransfer]', `새 피어(${peerId})에게 Manifest 전송`);
-      rustSignalingAdapter.sendManifest(
+    try {
+      const summary = await invoke<DirStreamSummary>(
+        'receive_directory_stream',
+        {
+          peerId: this.currentPeerId,
+          saveDir,
+          jobId,
+        }
+      );
+
+      logInfo('[NativeTransfer]', `✅ 스트리밍 폴더 저장 완료: ${summary.root}`);
+      return summary;
+    } catch (error) {
+      const errorMessage = getErrorMessage(error);
+      logError('[NativeTransfer]', '❌ 스트리밍 폴더 수신 실패:', errorMessage);
+      throw error;
+    }
+  }
+
+  /**
+   * 🆕 저장 위치의 여유 공간 (바이트)
+   * 수신 측은 매니페스트 크기와 비교해 모자라면 INSUFFICIENT_SPACE로 거절합니다.
+   */
+  async getDiskSpace(path: string): Promise<number> {
+    return await invoke<number>('get_disk_space', { path });
+  }
+
+  /**
+   * 🆕 전송 전 크기 미리 계산 (전송은 시작하지 않음)
+   * 폴더는 필터와 .pswpignore를 적용해 걷습니다.
+   */
+  async estimateTransfer(
+    paths: string[],
+    filter?: FolderFilter,
+    largest?: number
+  ): Promise<TransferEstimate> {
+    return await invoke<TransferEstimate>('estimate_transfer', {
+      paths,
+      include: filter?.include,
+      exclude: filter?.exclude,
+      largest,
+    });
+  }
+
+  /**
+   * 🆕 Zip 파일 압축 해제
+   */
+  async extractZipFile(zipPath: string, outputDir: string): Promise<string[]> {
+    logInfo('[NativeTransfer]', `📂 Zip 압축 해제: ${zipPath} -> ${outputDir}`);
+
+    try {
+      const extractedFiles = await invoke<string[]>('extract_zip_file', {
+        zipPath,
+        outputDir,
+        removeZip: true,
+      });
+
+      logInfo(
+        '[NativeTransfer]',
+        `✅ ${extractedFiles.length} 파일 압축 해제 완료`
+      );
+      return extractedFiles;
+    } catch (error) {
+      const errorMessage = getErrorMessage(error);
+      logError('[NativeTransfer]', '❌ Zip 압축 해제 실패:', errorMessage);
+      throw error;
+    }
+  }
+
+  /**
+   * 피어 입장 핸들러 (Sender 측)
+   */
+  private handlePeerJoined(data: unknown): void {
+    const payload = data as { socketId?: string; id?: string; peerId?: string };
+    const peerId = payload?.socketId || payload?.id || payload?.peerId;
+
+    if (!peerId) {
+      logWarn('[NativeTransfer]', '피어 입장 이벤트에 ID가 없음:', data);
+      return;
+    }
+
+    logInfo('[NativeTransfer]', `피어 입장: ${peerId}`);
+    this.emit('peer-joined', { peerId });
+
+    // 대기 중인 Manifest가 있으면 전송
+    if (this.pendingManifest && this.currentRoomId) {
+      logInfo('[NativeTransfer]', `새 피어(${peerId})에게 Manifest 전송`);
+      rustSignalingAdapter.sendManifest(
         this.currentRoomId,
         this.pendingManifest,
         peerId
@@ -865,7 +1517,8 @@ class NativeTransferService {
     } catch (error) {
       logError('[NativeTransfer]', '❌ 피어 연결 실패:', error);
       this.emit('error', {
-        message: `연결 실패: ${error instanceof Error ? error.message : String(error)}`,
+        message: `연결 실패: ${getErrorMessage(error)}`,
+        code: isAppError(error) ? error.code : undefined,
       });
       return false;
     }
@@ -1055,8 +1708,7 @@ class NativeTransferService {
         rustSignalingAdapter.sendTransferComplete(this.currentRoomId);
       }
     } catch (error) {
-      const errorMessage =
-        error instanceof Error ? error.message : String(error);
+      const errorMessage = getErrorMessage(error);
       logError('[NativeTransfer]', '❌ Zip transfer failed:', {
         error,
         errorMessage,
@@ -1144,7 +1796,11 @@ class NativeTransferService {
   /**
    * 파일 전송 (Sender - 클라이언트로 연결한 경우)
    */
-  async sendFile(filePath: string, jobId: string): Promise<number> {
+  async sendFile(
+    filePath: string,
+    jobId: string,
+    tuning?: TransferTuning
+  ): Promise<number> {
     if (!this.connected || !this.currentPeerId) {
       throw new Error('피어에 연결되어 있지 않습니다.');
     }
@@ -1157,6 +1813,7 @@ class NativeTransferService {
         peerId: this.currentPeerId,
         filePath,
         jobId,
+        chunkSize: tuning?.chunkSize,
       });
 
       this.emit('status', 'COMPLETED');
@@ -1164,8 +1821,7 @@ class NativeTransferService {
       return bytesSent;
     } catch (error) {
       // 🚨 [수정] 더 상세한 오류 정보 로깅
-      const errorMessage =
-        error instanceof Error ? error.message : String(error);
+      const errorMessage = getErrorMessage(error);
       const errorType =
         error instanceof Error ? error.constructor.name : typeof error;
 
@@ -1296,7 +1952,8 @@ class NativeTransferService {
   async sendFileToAcceptedPeer(
     peerId: string,
     filePath: string,
-    jobId: string
+    jobId: string,
+    tuning?: TransferTuning
   ): Promise<number> {
     // 🚨 [수정] 전송 완료 상태 추적을 위한 플래그
     let isCompleted = false;
@@ -1314,6 +1971,7 @@ class NativeTransferService {
         peerId,
         filePath,
         jobId,
+        chunkSize: tuning?.chunkSize,
         // Rust API가 fileIndex를 지원한다면 추가할 수 있음
         // 현재는 순차적 호출만으로도 순서가 보장됨
       });
@@ -1324,8 +1982,7 @@ class NativeTransferService {
       logInfo('[NativeTransfer]', `전송 완료:`, { bytesSent, jobId, peerId });
       return bytesSent;
     } catch (error) {
-      const errorMessage =
-        error instanceof Error ? error.message : String(error);
+      const errorMessage = getErrorMessage(error);
 
       // 🆕 중복 오류 확인
       if (errorMessage === lastErrorMessage) {
@@ -1386,6 +2043,554 @@ class NativeTransferService {
     }
   }
 
+  /**
+   * 🆕 전송 작업의 초당 처리량 기록 (최근 10분, 오래된 순)
+   */
+  async getTransferSpeedHistory(jobId: string): Promise<SpeedSample[]> {
+    return invoke<SpeedSample[]>('get_transfer_speed_history', { jobId });
+  }
+
+  /**
+   * 🆕 여러 피어에게 같은 파일들을 동시에 전송 (파일 읽기는 모든 대상이 공유)
+   * 대상별 진행률은 전송 레지스트리 이벤트(jobId: `<jobId>@<peerId>`)로 전달됩니다.
+   */
+  async sendFilesToPeers(
+    peerIds: string[],
+    paths: string[],
+    jobId: string,
+    tuning?: TransferTuning
+  ): Promise<BroadcastDestinationResult[]> {
+    return invoke<BroadcastDestinationResult[]>('send_files_to_peers', {
+      peerIds,
+      paths,
+      jobId,
+      blockSize: tuning?.blockSize,
+      maxConcurrentStreams: tuning?.maxConcurrentStreams,
+    });
+  }
+
+  /**
+   * 🆕 파일을 다중 소스 다운로드용으로 공유 (연결된 피어가 Info Hash로 조각 요청)
+   */
+  async shareMultiSourceFile(
+    filePath: string,
+    pieceSize?: number
+  ): Promise<MultiSourceShare> {
+    return invoke<MultiSourceShare>('share_multi_source_file', {
+      filePath,
+      pieceSize,
+    });
+  }
+
+  /**
+   * 🆕 다중 소스 공유 해제
+   */
+  async stopMultiSourceShare(infoHash: string): Promise<boolean> {
+    return invoke<boolean>('stop_multi_source_share', { infoHash });
+  }
+
+  /**
+   * 🆕 같은 파일을 가진 여러 피어에게서 조각을 나눠 받기
+   * 진행률은 전송 레지스트리 이벤트(kind: 'multisource')로 전달됩니다.
+   */
+  async downloadMultiSource(
+    infoHash: string,
+    peerIds: string[],
+    saveDir: string,
+    jobId: string
+  ): Promise<MultiSourceResult> {
+    return invoke<MultiSourceResult>('download_multi_source', {
+      infoHash,
+      peerIds,
+      saveDir,
+      jobId,
+    });
+  }
+
+  /**
+   * 🆕 파일이나 폴더를 부분 요청용으로 제공
+   * 반환된 offerId를 상대에게 알려 주면 필요한 파일/범위만 요청할 수 있습니다.
+   * peerId를 주면 그 피어만 요청할 수 있습니다.
+   */
+  async offerFiles(path: string, peerId?: string): Promise<OfferCatalog> {
+    return invoke<OfferCatalog>('offer_files', { path, peerId });
+  }
+
+  /**
+   * 🆕 부분 요청 제공 중단
+   */
+  async withdrawOffer(offerId: string): Promise<boolean> {
+    return invoke<boolean>('withdraw_offer', { offerId });
+  }
+
+  /**
+   * 🆕 이 기기가 제공 중인 부분 요청 목록
+   */
+  async listOffers(): Promise<OfferCatalog[]> {
+    return invoke<OfferCatalog[]>('list_offers');
+  }
+
+  /**
+   * 🆕 피어가 제공한 파일 목록 받기 (선택 화면용)
+   */
+  async fetchOfferCatalog(
+    peerId: string,
+    offerId: string
+  ): Promise<OfferCatalog> {
+    return invoke<OfferCatalog>('fetch_offer_catalog', { peerId, offerId });
+  }
+
+  /**
+   * 🆕 피어가 제공한 파일 중 고른 파일/하위 폴더/바이트 범위만 받기
+   * 진행률은 전송 레지스트리 이벤트(jobId)로 전달됩니다.
+   */
+  async requestOfferFiles(
+    peerId: string,
+    offerId: string,
+    selection: FileSelection[],
+    saveDir: string,
+    jobId: string,
+    overwritePolicy?: OverwritePolicy
+  ): Promise<RangeDownloadResult[]> {
+    return invoke<RangeDownloadResult[]>('request_offer_files', {
+      peerId,
+      offerId,
+      selection,
+      saveDir,
+      jobId,
+      overwritePolicy,
+    });
+  }
+
+  /**
+   * 🆕 피어와 페어링 (키 교환)
+   * 반환된 확인 코드가 상대 화면의 코드와 같은지 사용자가 비교한 뒤 confirmPairing을 호출해야 키가 쓰입니다.
+   * 페어링 후 전송 명령에 encrypt: true를 주면 페이로드가 암호화됩니다.
+   */
+  async pairWithPeer(peerId: string): Promise<PairedPeer> {
+    return invoke<PairedPeer>('pair_with_peer', { peerId });
+  }
+
+  /**
+   * 🆕 확인 코드가 같음을 확인 → 페어링 키 사용 시작 (양쪽 기기에서 각각 호출)
+   */
+  async confirmPairing(peerId: string): Promise<PairedPeer> {
+    return invoke<PairedPeer>('confirm_pairing', { peerId });
+  }
+
+  /**
+   * 🆕 확인 코드가 다름 → 새 페어링 키 폐기
+   */
+  async rejectPairing(peerId: string): Promise<boolean> {
+    return invoke<boolean>('reject_pairing', { peerId });
+  }
+
+  /**
+   * 🆕 페어링 해제
+   */
+  async unpairPeer(peerId: string): Promise<boolean> {
+    return invoke<boolean>('unpair_peer', { peerId });
+  }
+
+  /**
+   * 🆕 페어링된 피어 목록
+   */
+  async listPairedPeers(): Promise<PairedPeer[]> {
+    return invoke<PairedPeer[]>('list_paired_peers');
+  }
+
+  /**
+   * 🆕 QR 코드로 보여줄 연결 정보 (10분 동안 한 번 사용 가능)
+   */
+  async getConnectionQrPayload(): Promise<string> {
+    return invoke<string>('get_connection_qr_payload');
+  }
+
+  /**
+   * 🆕 스캔한 QR 연결 정보로 연결 후 페어링
+   * 연결한 기기가 QR 인증서의 개인 키로 핸드셰이크에 서명했는지 확인한 뒤에만 성공합니다.
+   * 페어링 키는 confirmPairing 후에 사용됩니다.
+   */
+  async connectFromQrPayload(blob: string): Promise<QrConnectionResult> {
+    return invoke<QrConnectionResult>('connect_from_qr_payload', { blob });
+  }
+
+  /**
+   * 🆕 피어에게 텍스트/클립보드 내용 전송 (파일 생성 없이)
+   * @returns 메시지 ID
+   */
+  async sendText(peerId: string, content: string): Promise<string> {
+    try {
+      return await invoke<string>('send_text', { peerId, content });
+    } catch (error) {
+      logError('[NativeTransfer]', '텍스트 전송 실패:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * 🆕 피어와의 처리량/RTT 측정 (생성 데이터, 디스크 미사용)
+   * @param duration 방향별 측정 시간(초, 기본 5초, 최대 60초)
+   */
+  async benchmarkPeer(
+    peerId: string,
+    duration?: number
+  ): Promise<BenchmarkReport> {
+    try {
+      return await invoke<BenchmarkReport>('benchmark_peer', {
+        peerId,
+        duration,
+      });
+    } catch (error) {
+      logError('[NativeTransfer]', '대역폭 측정 실패:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * 🆕 피어에게 전송 요청을 보내고 수락/거절 응답 대기
+   */
+  async requestTransferApproval(
+    peerId: string,
+    jobId: string,
+    fileName: string,
+    fileSize: number,
+    senderName?: string
+  ): Promise<TransferOfferResponse> {
+    return await invoke<TransferOfferResponse>('request_transfer_approval', {
+      peerId,
+      jobId,
+      fileName,
+      fileSize,
+      senderName,
+    });
+  }
+
+  /**
+   * 🆕 이 기기의 인증서 지문 (상대 기기의 자동 수락 규칙에 등록)
+   */
+  async getDeviceFingerprint(): Promise<string | null> {
+    return await invoke<string | null>('get_device_fingerprint');
+  }
+
+  async listAutoAcceptRules(): Promise<AutoAcceptRule[]> {
+    return await invoke<AutoAcceptRule[]>('list_auto_accept_rules');
+  }
+
+  async saveAutoAcceptRule(rule: AutoAcceptRule): Promise<AutoAcceptRule> {
+    return await invoke<AutoAcceptRule>('save_auto_accept_rule', { rule });
+  }
+
+  async removeAutoAcceptRule(ruleId: string): Promise<void> {
+    await invoke('remove_auto_accept_rule', { ruleId });
+  }
+
+  async getAutoAcceptLog(): Promise<AutoAcceptLogEntry[]> {
+    return await invoke<AutoAcceptLogEntry[]>('get_auto_accept_log');
+  }
+
+  async listContacts(): Promise<Contact[]> {
+    return await invoke<Contact[]>('list_contacts');
+  }
+
+  async saveContact(contact: Contact): Promise<Contact> {
+    return await invoke<Contact>('save_contact', { contact });
+  }
+
+  async removeContact(contactId: string): Promise<void> {
+    await invoke('remove_contact', { contactId });
+  }
+
+  /**
+   * 🆕 연락처에 연결 (반환된 피어 ID로 전송, 인증서 지문이 다르면 실패)
+   * 지문이 없는 연락처는 상대를 확인하지 않으므로, confirmShortAuthString으로 확인해야 지문이 저장됩니다.
+   */
+  async connectContact(contactId: string): Promise<string> {
+    return await invoke<string>('connect_contact', { contactId });
+  }
+
+  /**
+   * 🆕 연결된 피어와 소리 내어 비교할 인증 단어
+   */
+  async getShortAuthString(peerId: string): Promise<SasVerification> {
+    return await invoke<SasVerification>('get_short_auth_string', { peerId });
+  }
+
+  /**
+   * 🆕 단어가 같음을 확인 → 신뢰하는 연락처로 표시 (새 상대는 별명 필요)
+   */
+  async confirmShortAuthString(
+    peerId: string,
+    nickname?: string
+  ): Promise<Contact> {
+    return await invoke<Contact>('confirm_short_auth_string', {
+      peerId,
+      nickname,
+    });
+  }
+
+  /**
+   * 🆕 단어가 다름 → 연결 종료
+   */
+  async rejectShortAuthString(peerId: string): Promise<void> {
+    await invoke('reject_short_auth_string', { peerId });
+  }
+
+  /**
+   * 🆕 수신 한도와 피어별 오늘 사용량
+   */
+  async getReceiveQuota(): Promise<ReceiveQuotaStatus> {
+    return await invoke<ReceiveQuotaStatus>('get_receive_quota');
+  }
+
+  /**
+   * 🆕 수신 한도 변경 (한도를 넘는 전송 요청은 이유와 함께 거절)
+   */
+  async setReceiveQuota(limits: QuotaLimits): Promise<QuotaLimits> {
+    return await invoke<QuotaLimits>('set_receive_quota', { limits });
+  }
+
+  /**
+   * 🆕 앱 전체 처리량 (대시보드용, 1초 이상 간격으로 조회하면 속도가 갱신됨)
+   */
+  async getAppMetrics(): Promise<AppMetrics> {
+    return await invoke<AppMetrics>('get_app_metrics');
+  }
+
+  /**
+   * 🆕 전송 버퍼 메모리 예산 사용량
+   */
+  async getMemoryBudget(): Promise<MemoryBudgetStatus> {
+    return await invoke<MemoryBudgetStatus>('get_memory_budget');
+  }
+
+  /**
+   * 🆕 전송 감사 로그 (기업 배포용, 기본 꺼짐)
+   */
+  async getAuditLogEnabled(): Promise<boolean> {
+    return await invoke<boolean>('get_audit_log_enabled');
+  }
+
+  async setAuditLogEnabled(enabled: boolean): Promise<void> {
+    await invoke('set_audit_log_enabled', { enabled });
+  }
+
+  async getAuditLog(limit?: number): Promise<AuditEntry[]> {
+    return await invoke<AuditEntry[]>('get_audit_log', { limit });
+  }
+
+  /**
+   * 🆕 감사 로그를 파일로 내보내고 서명/해시 사슬 검증 결과 반환
+   */
+  async exportAuditLog(path: string): Promise<AuditVerification> {
+    return await invoke<AuditVerification>('export_audit_log', { path });
+  }
+
+  async verifyAuditLog(path: string): Promise<AuditVerification> {
+    return await invoke<AuditVerification>('verify_audit_log', { path });
+  }
+
+  /**
+   * 🆕 알림 설정 (메인 창에 포커스가 없을 때만 OS 알림 표시)
+   */
+  async getNotificationSettings(): Promise<NotificationSettings> {
+    return await invoke<NotificationSettings>('get_notification_settings');
+  }
+
+  async setNotificationSettings(settings: NotificationSettings): Promise<void> {
+    await invoke('set_notification_settings', { settings });
+  }
+
+  /**
+   * 🆕 앱이 없는 LAN 사용자가 브라우저로 받을 수 있는 링크 생성
+   * 첫 링크를 만들 때 HTTP 서버가 시작됩니다.
+   */
+  async createHttpShare(
+    path: string,
+    options?: HttpShareOptions
+  ): Promise<HttpShareLink> {
+    return await invoke<HttpShareLink>('create_http_share', { path, options });
+  }
+
+  async listHttpShares(): Promise<HttpShareLink[]> {
+    return await invoke<HttpShareLink[]>('list_http_shares');
+  }
+
+  async revokeHttpShare(shareId: string): Promise<void> {
+    await invoke('revoke_http_share', { shareId });
+  }
+
+  async stopHttpShareServer(): Promise<void> {
+    await invoke('stop_http_share_server');
+  }
+
+  /**
+   * 🆕 수신 중인 미디어 미리보기 링크 열기
+   * 단일 스트림 수신(receiveFile) 작업에서 전송이 끝나기 전에 재생을 시작할 수 있습니다.
+   * 아직 받지 않은 위치를 탐색하면 데이터가 도착할 때까지 응답이 지연됩니다.
+   */
+  async openMediaPreview(
+    jobId: string,
+    fileName?: string
+  ): Promise<MediaPreviewLink> {
+    return await invoke<MediaPreviewLink>('open_media_preview', {
+      jobId,
+      fileName,
+    });
+  }
+
+  async closeMediaPreview(jobId: string): Promise<boolean> {
+    return await invoke<boolean>('close_media_preview', { jobId });
+  }
+
+  /**
+   * 🆕 tus 업로드 엔드포인트 시작 (진행률/완료는 transfer-* 이벤트로 전달)
+   */
+  async startTusServer(config: TusConfig): Promise<TusEndpoint> {
+    return await invoke<TusEndpoint>('start_tus_server', { config });
+  }
+
+  async getTusEndpoint(): Promise<TusEndpoint | null> {
+    return await invoke<TusEndpoint | null>('get_tus_endpoint');
+  }
+
+  async stopTusServer(): Promise<void> {
+    await invoke('stop_tus_server');
+  }
+
+  /**
+   * 🆕 피어에게서 파일을 받아 저장 대상에 보관
+   * 임시 폴더로 받은 뒤(jobId) 업로드하며(`${jobId}-store`) 저장 위치를 반환합니다.
+   */
+  async receiveFileToStorage(
+    peerId: string,
+    jobId: string,
+    target: StorageTarget
+  ): Promise<string> {
+    return await invoke<string>('receive_file_to_storage', {
+      peerId,
+      jobId,
+      target,
+    });
+  }
+
+  /**
+   * 🆕 로컬 파일을 저장 대상으로 전송 (진행률은 transfer-* 이벤트)
+   * @returns 저장 위치 (s3://bucket/key 또는 로컬 경로)
+   */
+  async sendFileToStorage(
+    filePath: string,
+    target: StorageTarget,
+    jobId: string,
+    objectName?: string
+  ): Promise<string> {
+    return await invoke<string>('send_file_to_storage', {
+      filePath,
+      target,
+      jobId,
+      objectName,
+    });
+  }
+
+  /**
+   * 🆕 멀티스트림/Grid 작업의 매니페스트(블록 맵, 해시)를 파일로 내보내기
+   */
+  async exportJobManifest(jobId: string, path: string): Promise<JobManifest> {
+    return await invoke<JobManifest>('export_job_manifest', { jobId, path });
+  }
+
+  /**
+   * 🆕 내보낸 매니페스트 가져오기 (이후 jobId로 검증/재시드)
+   */
+  async importJobManifest(path: string): Promise<JobManifest> {
+    return await invoke<JobManifest>('import_job_manifest', { path });
+  }
+
+  /**
+   * 🆕 로컬 파일이 매니페스트의 데이터셋과 같은지 블록 단위로 검증
+   */
+  async verifyJobManifest(
+    jobId: string,
+    filePath: string
+  ): Promise<ManifestVerification> {
+    return await invoke<ManifestVerification>('verify_job_manifest', {
+      jobId,
+      filePath,
+    });
+  }
+
+  /**
+   * 🆕 웹 버전 피어에게 WebRTC 데이터 채널로 파일 전송
+   * 'webrtc-signal' 이벤트의 offer/candidate를 시그널링 서버로 중계하고,
+   * 상대의 answer/candidate는 webrtcSignal로 전달합니다.
+   * @returns 세션 ID (전송 작업 ID)
+   */
+  async webrtcSendFile(peerId: string, filePath: string): Promise<string> {
+    return await invoke<string>('webrtc_send_file', { peerId, filePath });
+  }
+
+  /**
+   * 🆕 웹 버전 피어로부터 WebRTC 데이터 채널 수신 대기
+   * 상대의 offer를 받으면 webrtcSignal로 전달하세요.
+   */
+  async webrtcReceive(
+    peerId: string,
+    saveDir: string,
+    overwritePolicy?: OverwritePolicy
+  ): Promise<string> {
+    return await invoke<string>('webrtc_receive', {
+      peerId,
+      saveDir,
+      overwritePolicy,
+    });
+  }
+
+  async webrtcSignal(sessionId: string, signal: WebRtcSignal): Promise<void> {
+    await invoke('webrtc_signal', { sessionId, signal });
+  }
+
+  async webrtcClose(sessionId: string): Promise<void> {
+    await invoke('webrtc_close', { sessionId });
+  }
+
+  /**
+   * 🆕 로컬 폴더 감시 및 피어로 변경 푸시 시작
+   * 피어는 먼저 같은 syncId로 acceptFolderSync를 호출해야 합니다.
+   */
+  async startFolderSync(
+    peerId: string,
+    syncId: string,
+    localDir: string
+  ): Promise<void> {
+    await invoke('start_folder_sync', { peerId, syncId, localDir });
+  }
+
+  /**
+   * 🆕 피어가 보내는 변경을 받을 폴더 등록
+   */
+  async acceptFolderSync(
+    syncId: string,
+    localDir: string,
+    conflictPolicy?: ConflictPolicy
+  ): Promise<void> {
+    await invoke('accept_folder_sync', { syncId, localDir, conflictPolicy });
+  }
+
+  async stopFolderSync(syncId: string): Promise<void> {
+    await invoke('stop_folder_sync', { syncId });
+  }
+
+  async listFolderSyncs(): Promise<FolderSyncInfo[]> {
+    return await invoke<FolderSyncInfo[]>('list_folder_syncs');
+  }
+
+  /**
+   * 🆕 수신 경로에 같은 이름의 파일이 있을 때의 처리 설정
+   */
+  setOverwritePolicy(policy: OverwritePolicy): void {
+    this.overwritePolicy = policy;
+  }
+
   /**
    * 🆕 다중 파일 순차 수신 (Receiver)
    * Sender가 파일을 순차적으로 전송할 때, 각 파일을 순차적으로 수신합니다.
@@ -1448,6 +2653,7 @@ class NativeTransferService {
             peerId: this.currentPeerId,
             saveDir,
             jobId,
+            overwritePolicy: this.overwritePolicy,
           });
 
           lastSavedPath = savedPath;
@@ -1461,8 +2667,7 @@ class NativeTransferService {
           // 진행률 업데이트 (파일 수신 성공 마다)
           this.emit('status', 'RECEIVING');
         } catch (error) {
-          const errorMessage =
-            error instanceof Error ? error.message : String(error);
+          const errorMessage = getErrorMessage(error);
 
           // 🚨 [핵심 수정] 전송 완료 후 발생하는 정상적인 연결 종료 에러들
           const isNormalClose =
@@ -1514,12 +2719,12 @@ class NativeTransferService {
 
       return lastSavedPath;
     } catch (error) {
-      const errorMessage =
-        error instanceof Error ? error.message : String(error);
+      const errorMessage = getErrorMessage(error);
 
       logError('[NativeTransfer]', '배치 파일 수신 실패:', error);
       this.emit('error', {
         message: `수신 실패: ${errorMessage}`,
+        code: isAppError(error) ? error.code : undefined,
       });
       this.emit('status', 'ERROR');
       throw error;
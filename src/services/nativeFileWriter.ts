@@ -10,6 +10,7 @@
 import { invoke } from '@tauri-apps/api/core';
 import { logInfo, logError, logWarn, logDebug } from '../utils/logger';
 import { HEADER_SIZE } from '../utils/constants';
+import { getErrorMessage } from '../utils/tauri';
 
 // Flow Control Watermarks
 const WRITE_BUFFER_HIGH_MARK = 64 * 1024 * 1024;
@@ -86,7 +87,7 @@ export class NativeFileWriter {
       this.baseDir = selectedPath;
       logInfo('[NativeWriter]', `Base directory set: ${this.baseDir}`);
     } catch (error) {
-      logError('[NativeWriter]', `Init failed: ${error}`);
+      logError('[NativeWriter]', `Init failed: ${getErrorMessage(error)}`);
       throw error;
     }
   }
@@ -104,7 +105,7 @@ export class NativeFileWriter {
           await this.processChunkInternal(packet);
         } catch (error) {
           logError('[NativeWriter]', 'Write error:', error);
-          this.onErrorCallback?.(String(error));
+          this.onErrorCallback?.(getErrorMessage(error));
           throw error;
         }
       })
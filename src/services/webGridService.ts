@@ -25,12 +25,9 @@ export async function connectWebBootstrapNode(
 
     console.log(`[WebGrid] 부트스트랩 노드 연결 시도: ${address}`);
 
-    // 부트스트랩 노드의 통계 API를 통해 연결 상태 확인
-    const response = await fetch(`http://${host}:6883/stats`, {
+    // 부트스트랩 노드의 헬스 체크로 연결 상태 확인 (/stats는 토큰 필요)
+    const response = await fetch(`http://${host}:6883/health`, {
       method: 'GET',
-      headers: {
-        'Content-Type': 'application/json',
-      },
     });
 
     if (response.ok) {
@@ -52,6 +52,9 @@ export interface GridPeerDiscoveredEvent {
   source: 'mdns' | 'dht';
 }
 
+/** 조각 해시 알고리즘 */
+export type GridHashAlgorithm = 'sha256' | 'blake3';
+
 /** 파일 메타데이터 */
 export interface GridFileMetadata {
   infoHash: string;
@@ -60,6 +63,46 @@ export interface GridFileMetadata {
   pieceSize: number;
   totalPieces: number;
   pieceHashes: string[];
+  hashAlgorithm?: GridHashAlgorithm;
+}
+
+/** 메타데이터 해시 진행률 이벤트 (grid-hashing-progress) */
+export interface GridHashingProgressEvent {
+  jobId: string;
+  bytesHashed: number;
+  totalBytes: number;
+  piecesDone: number;
+  totalPieces: number;
+  speedBps: number;
+  etaSecs: number | null;
+}
+
+/** .pons 파일 저장 옵션 */
+export interface GridPonsFileOptions {
+  pieceSize?: number;
+  hashAlgorithm?: GridHashAlgorithm;
+  webSeeds?: string[];
+  /** 먼저 연결할 시드/트래커 주소 (host:port) */
+  trackers?: string[];
+  /** DHT 부트스트랩 노드 힌트 (host:port) */
+  bootstrapNodes?: string[];
+  comment?: string;
+}
+
+/** .pons 파일 내용 (load_grid_metadata 응답) */
+export interface GridPonsFile {
+  infoHash: string;
+  fileName: string;
+  fileSize: number;
+  pieceSize: number;
+  totalPieces: number;
+  hashAlgorithm: GridHashAlgorithm;
+  webSeeds: string[];
+  trackers: string[];
+  bootstrapNodes: string[];
+  createdAt: number;
+  comment: string | null;
+  shareLink: string;
 }
 
 /** 스케줄링 모드 */
@@ -105,6 +148,7 @@ export const GRID_EVENTS = {
   PIECE_COMPLETED: 'grid-piece-completed',
   TRANSFER_COMPLETE: 'grid-transfer-complete',
   ERROR: 'grid-error',
+  HASHING_PROGRESS: 'grid-hashing-progress',
 } as const;
 
 /** GridStateUpdateEvent를 GridSwarmState로 변환 */
@@ -21,6 +21,50 @@ export interface DiscoveredPeer {
   capabilities: PeerCapabilities;
 }
 
+// 🆕 Rust 커맨드/이벤트 공통 에러 (src-tauri/src/error.rs)
+export type AppErrorCode =
+  | 'INVALID_INPUT'
+  | 'NOT_FOUND'
+  | 'PEER_NOT_CONNECTED'
+  | 'NOT_RUNNING'
+  | 'NETWORK'
+  | 'TIMEOUT'
+  | 'CANCELLED'
+  | 'REJECTED'
+  | 'IO'
+  | 'INSUFFICIENT_SPACE'
+  | 'CRYPTO'
+  | 'UNSUPPORTED'
+  | 'INTERNAL';
+
+export interface AppError {
+  code: AppErrorCode;
+  message: string;
+  // PEER_NOT_CONNECTED: { peerId }, INSUFFICIENT_SPACE: { required, available }
+  details: Record<string, unknown> | null;
+}
+
+/**
+ * 🆕 invoke 실패 값이 Rust AppError인지 확인
+ */
+export function isAppError(error: unknown): error is AppError {
+  return (
+    typeof error === 'object' &&
+    error !== null &&
+    typeof (error as AppError).code === 'string' &&
+    typeof (error as AppError).message === 'string'
+  );
+}
+
+/**
+ * 🆕 invoke 실패 값에서 표시용 메시지 추출 (AppError/Error/문자열 모두 처리)
+ */
+export function getErrorMessage(error: unknown): string {
+  if (isAppError(error)) return error.message;
+  if (error instanceof Error) return error.message;
+  return String(error);
+}
+
 let isNativeEnv: boolean | null = null;
 
 export async function isNative(): Promise<boolean> {
@@ -62,13 +106,69 @@ export async function pingQuic(): Promise<string | null> {
   }
 }
 
+// 🆕 Rust 로그 수준
+export type LogLevel = 'off' | 'error' | 'warn' | 'info' | 'debug' | 'trace';
+
+/**
+ * 🆕 실행 중 로그 수준 변경 (재시작 없이 디버그 로그 켜기)
+ * @returns 변경된 로그 수준
+ */
+export async function setLogLevel(level: LogLevel): Promise<LogLevel> {
+  return invoke<LogLevel>('set_log_level', { level });
+}
+
+export async function getLogLevel(): Promise<LogLevel> {
+  return invoke<LogLevel>('get_log_level');
+}
+
+/**
+ * 🆕 로그 파일의 최근 n줄 (기본 200줄, 오래된 줄부터)
+ */
+export async function getRecentLogs(n?: number): Promise<string[]> {
+  return invoke<string[]>('get_recent_logs', { n });
+}
+
+// 🆕 네트워크 진단 결과
+export type DoctorCheckStatus = 'pass' | 'warn' | 'fail' | 'skipped';
+
+export interface DoctorCheck {
+  name: 'mdns' | 'stun' | 'turn' | 'dht' | 'udpPort' | 'relay';
+  status: DoctorCheckStatus;
+  message: string;
+  details: Record<string, unknown> | null;
+  durationMs: number;
+}
+
+export interface DoctorReport {
+  checks: DoctorCheck[];
+  passed: number;
+  failed: number;
+  durationMs: number;
+}
+
+/**
+ * 🆕 네트워크 진단 (mDNS, STUN/NAT, TURN, DHT, UDP 포트, 릴레이)
+ * @param udpPort 바인딩을 확인할 UDP 포트 (없으면 임시 포트)
+ * @param stunServers 사용할 STUN 서버 ("host:port", 없으면 기본 서버)
+ */
+export async function runNetworkDoctor(
+  udpPort?: number,
+  stunServers?: string[]
+): Promise<DoctorReport> {
+  return invoke<DoctorReport>('run_network_doctor', { udpPort, stunServers });
+}
+
 export async function startQuicServer(
-  port: number = 0
+  port: number = 0,
+  stunServer?: string
 ): Promise<string | null> {
   if (!(await isNative())) return null;
 
   try {
-    const addr = await invoke<string>('start_quic_server', { port });
+    const addr = await invoke<string>('start_quic_server', {
+      port,
+      stunServer,
+    });
     console.log('[Tauri] QUIC 서버 시작됨:', addr);
     return addr;
   } catch (error) {